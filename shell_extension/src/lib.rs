@@ -0,0 +1,121 @@
+//! Explorer thumbnail provider and preview handler for formats Windows does not preview on
+//! its own (animated WebP, PSD, and JPEG XL or AVIF through a viewer decoder plugin).
+//!
+//! The DLL does no decoding itself: it runs `rust-image-viewer.exe --thumbnail` from its own
+//! folder, so Explorer shows exactly what the viewer would, plugins and `plugin_filters`
+//! included. `regsvr32 rust_image_viewer_shell.dll` registers it for the current user and
+//! `regsvr32 /u` removes it again; see [`registration`].
+#![cfg(windows)]
+
+mod preview;
+mod registration;
+mod render;
+mod thumbnail;
+
+use std::ffi::c_void;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use windows::core::{implement, IUnknown, Interface, GUID, HRESULT};
+use windows::Win32::Foundation::{
+    BOOL, CLASS_E_CLASSNOTAVAILABLE, CLASS_E_NOAGGREGATION, E_FAIL, E_POINTER, HMODULE, MAX_PATH,
+    S_FALSE, S_OK, TRUE,
+};
+use windows::Win32::System::Com::{IClassFactory, IClassFactory_Impl};
+use windows::Win32::System::LibraryLoader::GetModuleFileNameW;
+use windows::Win32::System::SystemServices::DLL_PROCESS_ATTACH;
+
+pub const CLSID_THUMBNAIL_PROVIDER: GUID = GUID::from_u128(0x05a32dfe_0aaa_4327_8300_def56547dcd9);
+pub const CLSID_PREVIEW_HANDLER: GUID = GUID::from_u128(0x9037f32d_77b8_480c_b236_b0d4981f855b);
+
+/// Module handle of this DLL, set when it is loaded.
+static MODULE: AtomicUsize = AtomicUsize::new(0);
+
+fn module() -> HMODULE {
+    HMODULE(MODULE.load(Ordering::Relaxed) as *mut c_void)
+}
+
+/// Path of this DLL; the viewer is looked up next to it.
+fn module_path() -> Option<PathBuf> {
+    let mut buffer = vec![0u16; MAX_PATH as usize * 4];
+    let len = unsafe { GetModuleFileNameW(module(), &mut buffer) } as usize;
+    (len > 0 && len < buffer.len()).then(|| PathBuf::from(String::from_utf16_lossy(&buffer[..len])))
+}
+
+#[no_mangle]
+extern "system" fn DllMain(instance: HMODULE, reason: u32, _reserved: *mut c_void) -> BOOL {
+    if reason == DLL_PROCESS_ATTACH {
+        MODULE.store(instance.0 as usize, Ordering::Relaxed);
+    }
+    TRUE
+}
+
+#[implement(IClassFactory)]
+struct ClassFactory {
+    clsid: GUID,
+}
+
+impl IClassFactory_Impl for ClassFactory_Impl {
+    fn CreateInstance(
+        &self,
+        outer: Option<&IUnknown>,
+        riid: *const GUID,
+        object: *mut *mut c_void,
+    ) -> windows::core::Result<()> {
+        if object.is_null() {
+            return Err(E_POINTER.into());
+        }
+        unsafe { *object = std::ptr::null_mut() };
+        if outer.is_some() {
+            return Err(CLASS_E_NOAGGREGATION.into());
+        }
+        let unknown: IUnknown = if self.clsid == CLSID_THUMBNAIL_PROVIDER {
+            thumbnail::ThumbnailProvider::default().into()
+        } else {
+            preview::PreviewHandler::default().into()
+        };
+        unsafe { unknown.query(riid, object) }.ok()
+    }
+
+    fn LockServer(&self, _lock: BOOL) -> windows::core::Result<()> {
+        Ok(())
+    }
+}
+
+#[no_mangle]
+extern "system" fn DllGetClassObject(
+    clsid: *const GUID,
+    riid: *const GUID,
+    object: *mut *mut c_void,
+) -> HRESULT {
+    if clsid.is_null() || object.is_null() {
+        return E_POINTER;
+    }
+    unsafe { *object = std::ptr::null_mut() };
+    let clsid = unsafe { *clsid };
+    if clsid != CLSID_THUMBNAIL_PROVIDER && clsid != CLSID_PREVIEW_HANDLER {
+        return CLASS_E_CLASSNOTAVAILABLE;
+    }
+    let factory: IClassFactory = ClassFactory { clsid }.into();
+    unsafe { factory.query(riid, object) }
+}
+
+/// Explorer and the preview host keep the DLL loaded; unloading is never worth the risk of a
+/// window procedure or a factory outliving the code.
+#[no_mangle]
+extern "system" fn DllCanUnloadNow() -> HRESULT {
+    S_FALSE
+}
+
+#[no_mangle]
+extern "system" fn DllRegisterServer() -> HRESULT {
+    match module_path() {
+        Some(path) => registration::register(&path).map_or_else(|err| err.code(), |()| S_OK),
+        None => E_FAIL,
+    }
+}
+
+#[no_mangle]
+extern "system" fn DllUnregisterServer() -> HRESULT {
+    registration::unregister().map_or_else(|err| err.code(), |()| S_OK)
+}
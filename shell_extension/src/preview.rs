@@ -0,0 +1,265 @@
+//! `IPreviewHandler` for Explorer's preview pane: the image fitted into a child window of the
+//! pane, on the viewer's dark background. Animations show their first frame.
+
+use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
+use std::sync::Once;
+
+use windows::core::{implement, w, Result, PCWSTR};
+use windows::Win32::Foundation::{
+    COLORREF, E_FAIL, E_INVALIDARG, E_UNEXPECTED, HWND, LPARAM, LRESULT, RECT, S_FALSE, WPARAM,
+};
+use windows::Win32::Graphics::Gdi::{
+    BeginPaint, CreateSolidBrush, DeleteObject, EndPaint, FillRect, InvalidateRect,
+    SetStretchBltMode, StretchDIBits, DIB_RGB_COLORS, HALFTONE, PAINTSTRUCT, SRCCOPY,
+};
+use windows::Win32::UI::Shell::PropertiesSystem::{IInitializeWithFile, IInitializeWithFile_Impl};
+use windows::Win32::UI::Shell::{IPreviewHandler, IPreviewHandler_Impl};
+use windows::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DefWindowProcW, DestroyWindow, GetClientRect, GetWindowLongPtrW, LoadCursorW,
+    RegisterClassW, SetParent, SetWindowLongPtrW, SetWindowPos, GWLP_USERDATA, IDC_ARROW, MSG,
+    SWP_NOACTIVATE, SWP_NOZORDER, WINDOW_EX_STYLE, WM_ERASEBKGND, WM_NCDESTROY, WM_PAINT, WM_SIZE,
+    WNDCLASSW, WS_CHILD, WS_VISIBLE,
+};
+
+use crate::render::{self, Bitmap};
+use crate::thumbnail::{bitmap_info, path_from_wide};
+
+const WINDOW_CLASS: PCWSTR = w!("RustImageViewerPreview");
+/// The viewer's default background, `#1e1e1e`.
+const BACKGROUND: [u8; 3] = [0x1e, 0x1e, 0x1e];
+/// Previews are rendered at the pane size, within these bounds.
+const MIN_RENDER_SIZE: u32 = 256;
+const MAX_RENDER_SIZE: u32 = 2048;
+
+#[implement(IPreviewHandler, IInitializeWithFile)]
+#[derive(Default)]
+pub struct PreviewHandler {
+    path: RefCell<Option<PathBuf>>,
+    parent: Cell<Option<HWND>>,
+    rect: Cell<RECT>,
+    window: Cell<Option<HWND>>,
+}
+
+impl PreviewHandler {
+    fn place_window(&self) {
+        if let Some(window) = self.window.get() {
+            let rect = self.rect.get();
+            unsafe {
+                let _ = SetWindowPos(
+                    window,
+                    None,
+                    rect.left,
+                    rect.top,
+                    rect.right - rect.left,
+                    rect.bottom - rect.top,
+                    SWP_NOZORDER | SWP_NOACTIVATE,
+                );
+            }
+        }
+    }
+
+    fn destroy_window(&self) {
+        if let Some(window) = self.window.take() {
+            unsafe {
+                let _ = DestroyWindow(window);
+            }
+        }
+    }
+}
+
+impl IInitializeWithFile_Impl for PreviewHandler_Impl {
+    fn Initialize(&self, path: &PCWSTR, _mode: u32) -> Result<()> {
+        *self.path.borrow_mut() = Some(path_from_wide(path)?);
+        Ok(())
+    }
+}
+
+impl IPreviewHandler_Impl for PreviewHandler_Impl {
+    fn SetWindow(&self, parent: HWND, rect: *const RECT) -> Result<()> {
+        if rect.is_null() {
+            return Err(E_INVALIDARG.into());
+        }
+        self.parent.set(Some(parent));
+        self.rect.set(unsafe { *rect });
+        if let Some(window) = self.window.get() {
+            unsafe {
+                let _ = SetParent(window, parent);
+            }
+            self.place_window();
+        }
+        Ok(())
+    }
+
+    fn SetRect(&self, rect: *const RECT) -> Result<()> {
+        if rect.is_null() {
+            return Err(E_INVALIDARG.into());
+        }
+        self.rect.set(unsafe { *rect });
+        self.place_window();
+        Ok(())
+    }
+
+    fn DoPreview(&self) -> Result<()> {
+        let parent = self.parent.get().ok_or(E_UNEXPECTED)?;
+        let path = self.path.borrow().clone().ok_or(E_UNEXPECTED)?;
+        let rect = self.rect.get();
+        let side = (rect.right - rect.left).max(rect.bottom - rect.top).max(0) as u32;
+        let mut image =
+            render::render(&path, side.clamp(MIN_RENDER_SIZE, MAX_RENDER_SIZE)).ok_or(E_FAIL)?;
+        image.flatten(BACKGROUND);
+
+        self.destroy_window();
+        self.window.set(Some(create_window(parent, rect, image)?));
+        Ok(())
+    }
+
+    fn Unload(&self) -> Result<()> {
+        self.destroy_window();
+        self.path.borrow_mut().take();
+        Ok(())
+    }
+
+    fn SetFocus(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn QueryFocus(&self) -> Result<HWND> {
+        Err(E_FAIL.into())
+    }
+
+    fn TranslateAccelerator(&self, _message: *const MSG) -> Result<()> {
+        Err(S_FALSE.into())
+    }
+}
+
+impl Drop for PreviewHandler {
+    fn drop(&mut self) {
+        self.destroy_window();
+    }
+}
+
+fn register_class() {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| unsafe {
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(window_proc),
+            hInstance: crate::module().into(),
+            lpszClassName: WINDOW_CLASS,
+            hCursor: LoadCursorW(None, IDC_ARROW).unwrap_or_default(),
+            ..Default::default()
+        };
+        RegisterClassW(&class);
+    });
+}
+
+/// A child window of `parent` showing `image`; the window owns the pixels.
+fn create_window(parent: HWND, rect: RECT, image: Bitmap) -> Result<HWND> {
+    register_class();
+    let window = unsafe {
+        CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            WINDOW_CLASS,
+            PCWSTR::null(),
+            WS_CHILD | WS_VISIBLE,
+            rect.left,
+            rect.top,
+            rect.right - rect.left,
+            rect.bottom - rect.top,
+            parent,
+            None,
+            crate::module(),
+            None,
+        )?
+    };
+    let image = Box::into_raw(Box::new(image));
+    unsafe {
+        SetWindowLongPtrW(window, GWLP_USERDATA, image as isize);
+        let _ = InvalidateRect(window, None, true);
+    }
+    Ok(window)
+}
+
+/// `image` scaled to fit `width` x `height` and centered, never enlarged.
+fn fit(image: &Bitmap, width: i32, height: i32) -> RECT {
+    let scale = (width as f32 / image.width as f32)
+        .min(height as f32 / image.height as f32)
+        .min(1.0);
+    let fitted_width = (image.width as f32 * scale).round() as i32;
+    let fitted_height = (image.height as f32 * scale).round() as i32;
+    let left = (width - fitted_width) / 2;
+    let top = (height - fitted_height) / 2;
+    RECT {
+        left,
+        top,
+        right: left + fitted_width,
+        bottom: top + fitted_height,
+    }
+}
+
+fn paint(window: HWND, image: &Bitmap) {
+    unsafe {
+        let mut paint = PAINTSTRUCT::default();
+        let dc = BeginPaint(window, &mut paint);
+        let mut client = RECT::default();
+        let _ = GetClientRect(window, &mut client);
+        let [red, green, blue] = BACKGROUND;
+        let brush = CreateSolidBrush(COLORREF(
+            u32::from(red) | u32::from(green) << 8 | u32::from(blue) << 16,
+        ));
+        FillRect(dc, &client, brush);
+        let _ = DeleteObject(brush);
+
+        let target = fit(image, client.right, client.bottom);
+        SetStretchBltMode(dc, HALFTONE);
+        StretchDIBits(
+            dc,
+            target.left,
+            target.top,
+            target.right - target.left,
+            target.bottom - target.top,
+            0,
+            0,
+            image.width as i32,
+            image.height as i32,
+            Some(image.bgra.as_ptr().cast()),
+            &bitmap_info(image),
+            DIB_RGB_COLORS,
+            SRCCOPY,
+        );
+        let _ = EndPaint(window, &paint);
+    }
+}
+
+extern "system" fn window_proc(
+    window: HWND,
+    message: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    let image = unsafe { GetWindowLongPtrW(window, GWLP_USERDATA) } as *mut Bitmap;
+    match message {
+        WM_PAINT if !image.is_null() => {
+            // SAFETY: set by `create_window` and freed only on WM_NCDESTROY.
+            paint(window, unsafe { &*image });
+            LRESULT(0)
+        }
+        WM_ERASEBKGND => LRESULT(1),
+        WM_SIZE => {
+            unsafe {
+                let _ = InvalidateRect(window, None, false);
+            }
+            LRESULT(0)
+        }
+        WM_NCDESTROY => {
+            if !image.is_null() {
+                unsafe {
+                    SetWindowLongPtrW(window, GWLP_USERDATA, 0);
+                    drop(Box::from_raw(image));
+                }
+            }
+            unsafe { DefWindowProcW(window, message, wparam, lparam) }
+        }
+        _ => unsafe { DefWindowProcW(window, message, wparam, lparam) },
+    }
+}
@@ -0,0 +1,190 @@
+//! Per-user registration under `HKCU\Software\Classes`, written by `regsvr32` so no
+//! administrator rights are needed.
+//!
+//! For each extension in [`EXTENSIONS`] the thumbnail provider and preview handler are set as
+//! the `ShellEx` handlers of the extension itself, so they apply whichever app opens it.
+//! Unregistering only removes handler entries that still point at this DLL.
+
+use std::path::Path;
+
+use windows::core::{Result, GUID, HSTRING, PCWSTR};
+use windows::Win32::Foundation::{ERROR_FILE_NOT_FOUND, WIN32_ERROR};
+use windows::Win32::System::Registry::{
+    RegCloseKey, RegCreateKeyExW, RegDeleteKeyValueW, RegDeleteTreeW, RegGetValueW, RegSetValueExW,
+    HKEY, HKEY_CURRENT_USER, KEY_WRITE, REG_DWORD, REG_OPTION_NON_VOLATILE, REG_SZ, RRF_RT_REG_SZ,
+};
+use windows::Win32::UI::Shell::{SHChangeNotify, SHCNE_ASSOCCHANGED, SHCNF_IDLIST};
+
+use crate::{CLSID_PREVIEW_HANDLER, CLSID_THUMBNAIL_PROVIDER};
+
+/// Formats Explorer does not preview on its own. `jxl` and `avif` show up once a viewer
+/// decoder plugin handles them; until then Explorer keeps its icon.
+pub const EXTENSIONS: &[&str] = &["webp", "psd", "jxl", "avif"];
+
+const THUMBNAIL_HANDLER_KEY: &str = "{e357fccd-a995-4576-b01f-234630154e96}";
+const PREVIEW_HANDLER_KEY: &str = "{8895b1c6-b41f-4c1c-a562-0d564250836f}";
+/// The 64-bit preview host process, `prevhost.exe`.
+const PREVIEW_HOST_APPID: &str = "{6d2b5079-2f0b-48dd-ab7f-97cec514d30b}";
+const PREVIEW_HANDLERS_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\PreviewHandlers";
+const THUMBNAIL_NAME: &str = "Rust Image Viewer thumbnail provider";
+const PREVIEW_NAME: &str = "Rust Image Viewer preview handler";
+
+enum RegValue<'a> {
+    Str(&'a str),
+    Dword(u32),
+}
+
+fn clsid_string(clsid: GUID) -> String {
+    format!("{{{clsid:?}}}").to_ascii_lowercase()
+}
+
+fn check(error: WIN32_ERROR) -> Result<()> {
+    error.ok()
+}
+
+/// Creates `HKCU\<key>` and sets `name` (the default value when empty) to `value`.
+fn set_value(key: &str, name: &str, value: RegValue) -> Result<()> {
+    let mut handle = HKEY::default();
+    unsafe {
+        check(RegCreateKeyExW(
+            HKEY_CURRENT_USER,
+            &HSTRING::from(key),
+            0,
+            PCWSTR::null(),
+            REG_OPTION_NON_VOLATILE,
+            KEY_WRITE,
+            None,
+            &mut handle,
+            None,
+        ))?;
+    }
+    let value_name = HSTRING::from(name);
+    let name = if name.is_empty() {
+        PCWSTR::null()
+    } else {
+        PCWSTR(value_name.as_ptr())
+    };
+    let result = match value {
+        RegValue::Str(text) => {
+            let wide: Vec<u16> = text.encode_utf16().chain(Some(0)).collect();
+            let bytes: Vec<u8> = wide.iter().flat_map(|unit| unit.to_le_bytes()).collect();
+            unsafe { RegSetValueExW(handle, name, 0, REG_SZ, Some(&bytes)) }
+        }
+        RegValue::Dword(number) => unsafe {
+            RegSetValueExW(handle, name, 0, REG_DWORD, Some(&number.to_le_bytes()))
+        },
+    };
+    unsafe {
+        let _ = RegCloseKey(handle);
+    }
+    check(result)
+}
+
+/// Default value of `HKCU\<key>`, when it is a string.
+fn default_value(key: &str) -> Option<String> {
+    let mut buffer = [0u16; 128];
+    let mut len = std::mem::size_of_val(&buffer) as u32;
+    let result = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            &HSTRING::from(key),
+            PCWSTR::null(),
+            RRF_RT_REG_SZ,
+            None,
+            Some(buffer.as_mut_ptr().cast()),
+            Some(&mut len),
+        )
+    };
+    result.ok().ok()?;
+    let units = &buffer[..len as usize / 2];
+    Some(
+        String::from_utf16_lossy(units)
+            .trim_end_matches('\0')
+            .to_string(),
+    )
+}
+
+/// Deletes `HKCU\<key>` with its subkeys; a missing key is fine.
+fn delete_tree(key: &str) -> Result<()> {
+    match unsafe { RegDeleteTreeW(HKEY_CURRENT_USER, &HSTRING::from(key)) } {
+        ERROR_FILE_NOT_FOUND => Ok(()),
+        error => check(error),
+    }
+}
+
+fn register_server(clsid: GUID, name: &str, dll: &str) -> Result<String> {
+    let key = format!(r"Software\Classes\CLSID\{}", clsid_string(clsid));
+    set_value(&key, "", RegValue::Str(name))?;
+    set_value(&format!(r"{key}\InprocServer32"), "", RegValue::Str(dll))?;
+    set_value(
+        &format!(r"{key}\InprocServer32"),
+        "ThreadingModel",
+        RegValue::Str("Apartment"),
+    )?;
+    Ok(key)
+}
+
+pub fn register(dll: &Path) -> Result<()> {
+    let dll = dll.to_string_lossy();
+    let thumbnail = clsid_string(CLSID_THUMBNAIL_PROVIDER);
+    let preview = clsid_string(CLSID_PREVIEW_HANDLER);
+
+    let key = register_server(CLSID_THUMBNAIL_PROVIDER, THUMBNAIL_NAME, &dll)?;
+    // Lets Explorer pass the file path through IInitializeWithFile.
+    set_value(&key, "DisableProcessIsolation", RegValue::Dword(1))?;
+    let key = register_server(CLSID_PREVIEW_HANDLER, PREVIEW_NAME, &dll)?;
+    set_value(&key, "AppID", RegValue::Str(PREVIEW_HOST_APPID))?;
+    set_value(&key, "DisplayName", RegValue::Str(PREVIEW_NAME))?;
+    set_value(PREVIEW_HANDLERS_KEY, &preview, RegValue::Str(PREVIEW_NAME))?;
+
+    for extension in EXTENSIONS {
+        let shell_ex = format!(r"Software\Classes\.{extension}\ShellEx");
+        set_value(
+            &format!(r"{shell_ex}\{THUMBNAIL_HANDLER_KEY}"),
+            "",
+            RegValue::Str(&thumbnail),
+        )?;
+        set_value(
+            &format!(r"{shell_ex}\{PREVIEW_HANDLER_KEY}"),
+            "",
+            RegValue::Str(&preview),
+        )?;
+    }
+    notify_association_change();
+    Ok(())
+}
+
+pub fn unregister() -> Result<()> {
+    let thumbnail = clsid_string(CLSID_THUMBNAIL_PROVIDER);
+    let preview = clsid_string(CLSID_PREVIEW_HANDLER);
+    for extension in EXTENSIONS {
+        let shell_ex = format!(r"Software\Classes\.{extension}\ShellEx");
+        for (handler, clsid) in [
+            (THUMBNAIL_HANDLER_KEY, &thumbnail),
+            (PREVIEW_HANDLER_KEY, &preview),
+        ] {
+            let key = format!(r"{shell_ex}\{handler}");
+            if default_value(&key).is_some_and(|value| value.eq_ignore_ascii_case(clsid)) {
+                delete_tree(&key)?;
+            }
+        }
+    }
+    match unsafe {
+        RegDeleteKeyValueW(
+            HKEY_CURRENT_USER,
+            &HSTRING::from(PREVIEW_HANDLERS_KEY),
+            &HSTRING::from(preview.as_str()),
+        )
+    } {
+        ERROR_FILE_NOT_FOUND => {}
+        error => check(error)?,
+    }
+    delete_tree(&format!(r"Software\Classes\CLSID\{thumbnail}"))?;
+    delete_tree(&format!(r"Software\Classes\CLSID\{preview}"))?;
+    notify_association_change();
+    Ok(())
+}
+
+fn notify_association_change() {
+    unsafe { SHChangeNotify(SHCNE_ASSOCCHANGED, SHCNF_IDLIST, None, None) };
+}
@@ -0,0 +1,116 @@
+//! Rendering through the viewer: `rust-image-viewer.exe --thumbnail <file> <temp.png> --size N`
+//! next to this DLL, then reading the PNG back as BGRA for GDI.
+
+use std::fs::File;
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+const VIEWER_EXE: &str = "rust-image-viewer.exe";
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+/// Explorer gives up on slow thumbnails anyway; a stuck decode must not pile up processes.
+const RENDER_TIMEOUT: Duration = Duration::from_secs(15);
+
+static NEXT_TEMP: AtomicU64 = AtomicU64::new(0);
+
+/// Straight (non-premultiplied) BGRA8, top row first, as GDI DIBs take it.
+pub struct Bitmap {
+    pub width: u32,
+    pub height: u32,
+    pub bgra: Vec<u8>,
+}
+
+impl Bitmap {
+    /// Composites the pixels onto an opaque `background` (RGB), for windows that ignore alpha.
+    pub fn flatten(&mut self, background: [u8; 3]) {
+        for pixel in self.bgra.chunks_exact_mut(4) {
+            let alpha = u32::from(pixel[3]);
+            for (channel, back) in
+                pixel[..3]
+                    .iter_mut()
+                    .zip([background[2], background[1], background[0]])
+            {
+                *channel = ((u32::from(*channel) * alpha + u32::from(back) * (255 - alpha) + 127)
+                    / 255) as u8;
+            }
+            pixel[3] = u8::MAX;
+        }
+    }
+}
+
+fn viewer_exe() -> Option<PathBuf> {
+    let exe = crate::module_path()?.parent()?.join(VIEWER_EXE);
+    exe.is_file().then_some(exe)
+}
+
+/// `path` rendered with its longer side at most `size` pixels.
+pub fn render(path: &Path, size: u32) -> Option<Bitmap> {
+    let exe = viewer_exe()?;
+    let target = std::env::temp_dir().join(format!(
+        "riv-shell-{}-{}.png",
+        std::process::id(),
+        NEXT_TEMP.fetch_add(1, Ordering::Relaxed)
+    ));
+    let rendered = run_viewer(&exe, path, &target, size).then(|| read_png(&target));
+    let _ = std::fs::remove_file(&target);
+    rendered.flatten()
+}
+
+fn run_viewer(exe: &Path, path: &Path, target: &Path, size: u32) -> bool {
+    let Ok(mut child) = Command::new(exe)
+        .arg("--thumbnail")
+        .arg(path)
+        .arg(target)
+        .arg("--size")
+        .arg(size.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .creation_flags(CREATE_NO_WINDOW)
+        .spawn()
+    else {
+        return false;
+    };
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return status.success(),
+            Ok(None) if started.elapsed() < RENDER_TIMEOUT => {
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return false;
+            }
+        }
+    }
+}
+
+/// Reads the viewer's PNG (RGB8 or RGBA8) as BGRA8.
+fn read_png(path: &Path) -> Option<Bitmap> {
+    let mut decoder = png::Decoder::new(File::open(path).ok()?);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().ok()?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).ok()?;
+    let pixels = &buffer[..info.buffer_size()];
+    let bgra = match info.color_type {
+        png::ColorType::Rgba => pixels
+            .chunks_exact(4)
+            .flat_map(|pixel| [pixel[2], pixel[1], pixel[0], pixel[3]])
+            .collect(),
+        png::ColorType::Rgb => pixels
+            .chunks_exact(3)
+            .flat_map(|pixel| [pixel[2], pixel[1], pixel[0], u8::MAX])
+            .collect(),
+        _ => return None,
+    };
+    Some(Bitmap {
+        width: info.width,
+        height: info.height,
+        bgra,
+    })
+}
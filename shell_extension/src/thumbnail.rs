@@ -0,0 +1,95 @@
+//! `IThumbnailProvider` for Explorer's icon views.
+//!
+//! Explorer hands the file over through `IInitializeWithFile`, which it only does for
+//! handlers registered with `DisableProcessIsolation`; the viewer it starts does the
+//! decoding in its own process anyway.
+
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::path::PathBuf;
+
+use windows::core::{implement, Result, PCWSTR};
+use windows::Win32::Foundation::{E_FAIL, E_POINTER, E_UNEXPECTED};
+use windows::Win32::Graphics::Gdi::{
+    CreateDIBSection, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, HBITMAP,
+};
+use windows::Win32::UI::Shell::PropertiesSystem::{IInitializeWithFile, IInitializeWithFile_Impl};
+use windows::Win32::UI::Shell::{
+    IThumbnailProvider, IThumbnailProvider_Impl, WTSAT_ARGB, WTS_ALPHATYPE,
+};
+
+use crate::render::{self, Bitmap};
+
+#[implement(IThumbnailProvider, IInitializeWithFile)]
+#[derive(Default)]
+pub struct ThumbnailProvider {
+    path: RefCell<Option<PathBuf>>,
+}
+
+/// Wide, NUL-terminated `path` from a shell API.
+pub fn path_from_wide(path: &PCWSTR) -> Result<PathBuf> {
+    if path.is_null() {
+        return Err(E_POINTER.into());
+    }
+    let path = unsafe { path.to_string() }.map_err(|_| E_FAIL)?;
+    Ok(PathBuf::from(path))
+}
+
+impl IInitializeWithFile_Impl for ThumbnailProvider_Impl {
+    fn Initialize(&self, path: &PCWSTR, _mode: u32) -> Result<()> {
+        *self.path.borrow_mut() = Some(path_from_wide(path)?);
+        Ok(())
+    }
+}
+
+impl IThumbnailProvider_Impl for ThumbnailProvider_Impl {
+    fn GetThumbnail(
+        &self,
+        size: u32,
+        bitmap: *mut HBITMAP,
+        alpha: *mut WTS_ALPHATYPE,
+    ) -> Result<()> {
+        if bitmap.is_null() || alpha.is_null() {
+            return Err(E_POINTER.into());
+        }
+        let path = self.path.borrow().clone().ok_or(E_UNEXPECTED)?;
+        let rendered = render::render(&path, size.max(1)).ok_or(E_FAIL)?;
+        unsafe {
+            *bitmap = create_dib(&rendered)?;
+            *alpha = WTSAT_ARGB;
+        }
+        Ok(())
+    }
+}
+
+/// A top-down 32-bit DIB section holding `image`; the caller owns it.
+pub fn create_dib(image: &Bitmap) -> Result<HBITMAP> {
+    let info = bitmap_info(image);
+    let mut bits: *mut c_void = std::ptr::null_mut();
+    let dib = unsafe { CreateDIBSection(None, &info, DIB_RGB_COLORS, &mut bits, None, 0)? };
+    if bits.is_null() {
+        return Err(E_FAIL.into());
+    }
+    // SAFETY: the section holds `width * height` 32-bit pixels, the size of `bgra`.
+    unsafe {
+        std::ptr::copy_nonoverlapping(image.bgra.as_ptr(), bits.cast::<u8>(), image.bgra.len())
+    };
+    Ok(dib)
+}
+
+/// Header of a top-down 32-bit DIB the size of `image`.
+pub fn bitmap_info(image: &Bitmap) -> BITMAPINFO {
+    BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: image.width as i32,
+            // Negative height: rows run top to bottom, like the decoded pixels.
+            biHeight: -(image.height as i32),
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
@@ -0,0 +1,66 @@
+//! Keyboard and screen-reader support for the overlay controls.
+//!
+//! eframe's AccessKit integration exposes every egui widget to Windows UI Automation. The
+//! title bar and video bar paint many of their controls by hand, so those describe
+//! themselves with [`describe_button`] and [`describe_slider`] to be announced by name.
+//!
+//! Tab / Shift+Tab move keyboard focus through the controls and Enter / Space press the
+//! focused one. [`draw_focus_ring`] marks the focused control with a two-tone outline that
+//! stays visible on light and dark backgrounds alike.
+
+/// Inner color of the focus outline; a black edge around it keeps it visible on light UI.
+const FOCUS_RING_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 214, 0);
+
+/// Keys that move keyboard focus or press the focused control.
+const FOCUS_KEYS: [egui::Key; 8] = [
+    egui::Key::Tab,
+    egui::Key::Enter,
+    egui::Key::Space,
+    egui::Key::Escape,
+    egui::Key::ArrowLeft,
+    egui::Key::ArrowRight,
+    egui::Key::ArrowUp,
+    egui::Key::ArrowDown,
+];
+
+/// Names a hand-painted button for screen readers.
+pub fn describe_button(response: &egui::Response, label: &str) {
+    response.widget_info(|| {
+        egui::WidgetInfo::labeled(egui::WidgetType::Button, response.enabled(), label)
+    });
+}
+
+/// Names a hand-painted slider and reports its `value`.
+pub fn describe_slider(response: &egui::Response, label: &str, value: f64) {
+    response.widget_info(|| egui::WidgetInfo::slider(response.enabled(), value, label));
+}
+
+/// Whether a control other than a text field holds keyboard focus.
+pub fn control_has_focus(ctx: &egui::Context) -> bool {
+    ctx.memory(|memory| memory.focused().is_some()) && !ctx.wants_keyboard_input()
+}
+
+/// Whether a key that navigates between controls was pressed this frame.
+pub fn focus_key_pressed(ctx: &egui::Context) -> bool {
+    ctx.input(|input| FOCUS_KEYS.iter().any(|key| input.key_pressed(*key)))
+}
+
+/// Outlines the control with keyboard focus. Text fields are left alone: they show a cursor.
+pub fn draw_focus_ring(ctx: &egui::Context) {
+    if !control_has_focus(ctx) {
+        return;
+    }
+    let Some(response) = ctx
+        .memory(|memory| memory.focused())
+        .and_then(|id| ctx.read_response(id))
+    else {
+        return;
+    };
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Tooltip,
+        egui::Id::new("focus_ring"),
+    ));
+    let rect = response.rect.expand(2.0);
+    painter.rect_stroke(rect, 4.0, egui::Stroke::new(4.0, egui::Color32::BLACK));
+    painter.rect_stroke(rect, 4.0, egui::Stroke::new(2.0, FOCUS_RING_COLOR));
+}
@@ -0,0 +1,590 @@
+//! Animation conversion: the current animated GIF to animated WebP, or a time range of a
+//! video to an animated GIF or WebP.
+//!
+//! Frames are decoded one at a time in a background job and handed straight to the encoder,
+//! so long animations never sit in memory whole. Frames closer together than the chosen
+//! frame rate allows are dropped and their time goes to the frame shown before them. Video
+//! frames come from a GStreamer pipeline that resamples the clip to that frame rate, and
+//! are scaled down to [`MAX_VIDEO_SIDE`] so clips stay a sensible size.
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use gstreamer_video as gst_video;
+use image::imageops::FilterType;
+
+/// Long-side cap for frames taken from videos.
+pub const MAX_VIDEO_SIDE: u32 = 720;
+/// Longest video range that can be converted.
+pub const MAX_CLIP_LENGTH: Duration = Duration::from_secs(60);
+/// How long the worker waits for a video frame before checking for cancellation again.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// A video that has not prerolled by then is given up.
+const PREROLL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Output format of a conversion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationFormat {
+    WebP,
+    Gif,
+}
+
+impl AnimationFormat {
+    pub const ALL: [Self; 2] = [Self::WebP, Self::Gif];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::WebP => "Animated WebP",
+            Self::Gif => "GIF",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::WebP => "webp",
+            Self::Gif => "gif",
+        }
+    }
+}
+
+/// What a conversion reads from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnimationSource {
+    Gif(PathBuf),
+    Video {
+        path: PathBuf,
+        start: Duration,
+        end: Duration,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimationExportSettings {
+    pub format: AnimationFormat,
+    /// 1-100; lossy WebP quality, or how carefully GIF palettes are picked.
+    pub quality: u8,
+    /// Highest frame rate written.
+    pub fps: u32,
+}
+
+/// `<stem>.anim.<ext>` next to the source, never overwriting.
+pub fn export_path(source: &Path, format: AnimationFormat) -> PathBuf {
+    crate::image_loader::sibling_export_path(source, "anim", format.extension())
+}
+
+/// NeuQuant sampling step for GIF palettes: 1 (slowest, best) at quality 100 to 30 at 1.
+fn gif_quantizer_speed(quality: u8) -> i32 {
+    let quality = quality.clamp(1, 100) as i32;
+    30 - (quality - 1) * 29 / 99
+}
+
+/// Keeps frames at least one frame interval apart.
+struct FrameTimer {
+    interval_ms: u64,
+    next_ms: Option<u64>,
+}
+
+impl FrameTimer {
+    fn new(fps: u32) -> Self {
+        Self {
+            interval_ms: 1000 / u64::from(fps.clamp(1, 60)),
+            next_ms: None,
+        }
+    }
+
+    /// Whether the frame at `timestamp_ms` is written.
+    fn keep(&mut self, timestamp_ms: u64) -> bool {
+        if self.next_ms.is_some_and(|next| timestamp_ms < next) {
+            return false;
+        }
+        self.next_ms = Some(timestamp_ms + self.interval_ms);
+        true
+    }
+}
+
+enum AnimationWriter {
+    WebP {
+        encoder: Box<webp_animation::Encoder>,
+        target: PathBuf,
+    },
+    Gif {
+        encoder: gif::Encoder<BufWriter<File>>,
+        /// Frames are written once the next one arrives and their delay is known.
+        pending: Option<(Vec<u8>, u64)>,
+        speed: i32,
+        width: u16,
+        height: u16,
+    },
+}
+
+impl AnimationWriter {
+    fn new(
+        settings: &AnimationExportSettings,
+        width: u32,
+        height: u32,
+        target: &Path,
+    ) -> Result<Self, String> {
+        match settings.format {
+            AnimationFormat::WebP => {
+                use webp_animation::{EncoderOptions, EncodingConfig};
+
+                let options = EncoderOptions {
+                    encoding_config: Some(EncodingConfig::new_lossy(
+                        settings.quality.clamp(1, 100) as f32,
+                    )),
+                    ..Default::default()
+                };
+                let encoder = webp_animation::Encoder::new_with_options((width, height), options)
+                    .map_err(|err| err.to_string())?;
+                Ok(Self::WebP {
+                    encoder: Box::new(encoder),
+                    target: target.to_path_buf(),
+                })
+            }
+            AnimationFormat::Gif => {
+                let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height)) else {
+                    return Err(format!("{width}x{height} is too large for a GIF"));
+                };
+                let file = File::create(target)
+                    .map_err(|err| format!("Failed to create {}: {err}", target.display()))?;
+                let mut encoder = gif::Encoder::new(BufWriter::new(file), width, height, &[])
+                    .map_err(|err| err.to_string())?;
+                encoder
+                    .set_repeat(gif::Repeat::Infinite)
+                    .map_err(|err| err.to_string())?;
+                Ok(Self::Gif {
+                    encoder,
+                    pending: None,
+                    speed: gif_quantizer_speed(settings.quality),
+                    width,
+                    height,
+                })
+            }
+        }
+    }
+
+    /// Adds straight RGBA8 `pixels` shown from `timestamp_ms` on.
+    fn add(&mut self, pixels: Vec<u8>, timestamp_ms: u64) -> Result<(), String> {
+        match self {
+            Self::WebP { encoder, .. } => encoder
+                .add_frame(&pixels, timestamp_ms as i32)
+                .map_err(|err| err.to_string()),
+            Self::Gif { pending, .. } => {
+                let previous = pending.replace((pixels, timestamp_ms));
+                match previous {
+                    Some((pixels, shown_at)) => {
+                        self.write_gif_frame(pixels, timestamp_ms - shown_at)
+                    }
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+
+    fn write_gif_frame(&mut self, mut pixels: Vec<u8>, duration_ms: u64) -> Result<(), String> {
+        let Self::Gif {
+            encoder,
+            speed,
+            width,
+            height,
+            ..
+        } = self
+        else {
+            return Ok(());
+        };
+        let mut frame = gif::Frame::from_rgba_speed(*width, *height, &mut pixels, *speed);
+        // GIF delays are in hundredths of a second; most viewers treat less than 2 as 10.
+        frame.delay = (duration_ms.div_ceil(10)).clamp(2, u16::MAX as u64) as u16;
+        encoder.write_frame(&frame).map_err(|err| err.to_string())
+    }
+
+    /// Writes the file; the last frame is shown until `end_ms`.
+    fn finish(mut self, end_ms: u64) -> Result<(), String> {
+        if let Self::Gif { pending, .. } = &mut self {
+            if let Some((pixels, shown_at)) = pending.take() {
+                self.write_gif_frame(pixels, end_ms.saturating_sub(shown_at))?;
+            }
+        }
+        match self {
+            Self::WebP { encoder, target } => {
+                let data = (*encoder)
+                    .finalize(end_ms as i32)
+                    .map_err(|err| err.to_string())?;
+                std::fs::write(&target, &*data)
+                    .map_err(|err| format!("Failed to write {}: {err}", target.display()))
+            }
+            Self::Gif { encoder, .. } => {
+                let mut writer = encoder.into_inner().map_err(|err| err.to_string())?;
+                std::io::Write::flush(&mut writer).map_err(|err| err.to_string())
+            }
+        }
+    }
+}
+
+/// Converts `source` into `target`, calling `progress` with the fraction done. Stops with an
+/// error when `cancelled` is set; a partly written file is removed.
+pub fn export_animation(
+    source: &AnimationSource,
+    settings: &AnimationExportSettings,
+    target: &Path,
+    cancelled: &AtomicBool,
+    progress: impl Fn(f32),
+) -> Result<(), String> {
+    let result = match source {
+        AnimationSource::Gif(path) => export_gif(path, settings, target, cancelled, progress),
+        AnimationSource::Video { path, start, end } => {
+            export_video(path, *start, *end, settings, target, cancelled, progress)
+        }
+    };
+    if result.is_err() {
+        let _ = std::fs::remove_file(target);
+    }
+    result
+}
+
+fn open_gif(path: &Path) -> Result<gif::Decoder<std::io::BufReader<File>>, String> {
+    let file =
+        File::open(path).map_err(|err| format!("Failed to open {}: {err}", path.display()))?;
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::Indexed);
+    options
+        .read_info(std::io::BufReader::new(file))
+        .map_err(|err| format!("Failed to read GIF: {err}"))
+}
+
+fn export_gif(
+    path: &Path,
+    settings: &AnimationExportSettings,
+    target: &Path,
+    cancelled: &AtomicBool,
+    progress: impl Fn(f32),
+) -> Result<(), String> {
+    // A first pass counts the frames for the progress bar.
+    let mut counter = open_gif(path)?;
+    let mut total_frames = 0usize;
+    while counter
+        .next_frame_info()
+        .map_err(|err| format!("GIF frame error: {err}"))?
+        .is_some()
+    {
+        total_frames += 1;
+    }
+
+    let mut decoder = open_gif(path)?;
+    let (width, height) = (decoder.width() as u32, decoder.height() as u32);
+    let mut screen = gif_dispose::Screen::new_decoder(&decoder);
+    let mut writer = AnimationWriter::new(settings, width, height, target)?;
+    let mut timer = FrameTimer::new(settings.fps);
+    let mut timestamp_ms = 0u64;
+    let mut index = 0usize;
+    while let Some(frame) = decoder
+        .read_next_frame()
+        .map_err(|err| format!("GIF frame error: {err}"))?
+    {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err("Cancelled".to_string());
+        }
+        let delay_ms = match u64::from(frame.delay) * 10 {
+            0 => 100,
+            delay => delay,
+        };
+        screen
+            .blit_frame(frame)
+            .map_err(|err| format!("Failed to composite GIF frame: {err}"))?;
+        if timer.keep(timestamp_ms) {
+            let (pixels, _, _) = screen.pixels_rgba().to_contiguous_buf();
+            let pixels = pixels
+                .iter()
+                .flat_map(|px| [px.r, px.g, px.b, px.a])
+                .collect();
+            writer.add(pixels, timestamp_ms)?;
+        }
+        timestamp_ms += delay_ms;
+        index += 1;
+        progress(index as f32 / total_frames.max(1) as f32);
+    }
+    writer.finish(timestamp_ms)
+}
+
+fn export_video(
+    path: &Path,
+    start: Duration,
+    end: Duration,
+    settings: &AnimationExportSettings,
+    target: &Path,
+    cancelled: &AtomicBool,
+    progress: impl Fn(f32),
+) -> Result<(), String> {
+    static GST_INIT: OnceLock<Result<(), String>> = OnceLock::new();
+    GST_INIT
+        .get_or_init(|| gst::init().map_err(|err| err.to_string()))
+        .clone()?;
+    if end <= start {
+        return Err("The selected range is empty".to_string());
+    }
+
+    let uri = crate::remote::media_uri(path)?;
+    let description = format!(
+        "uridecodebin uri=\"{}\" ! videoconvert ! videorate ! \
+         video/x-raw,format=RGBA,framerate={}/1 ! \
+         appsink name=sink sync=false max-buffers=4",
+        uri.replace('"', "\\\""),
+        settings.fps.clamp(1, 60)
+    );
+    let pipeline = gst::parse::launch(&description)
+        .map_err(|err| err.to_string())?
+        .downcast::<gst::Pipeline>()
+        .map_err(|_| "Not a pipeline".to_string())?;
+    let result = pipeline
+        .by_name("sink")
+        .and_then(|sink| sink.dynamic_cast::<gst_app::AppSink>().ok())
+        .ok_or_else(|| "The conversion pipeline has no sink".to_string())
+        .and_then(|sink| {
+            pull_video_frames(
+                &pipeline, &sink, start, end, settings, target, cancelled, progress,
+            )
+        });
+    let _ = pipeline.set_state(gst::State::Null);
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+fn pull_video_frames(
+    pipeline: &gst::Pipeline,
+    sink: &gst_app::AppSink,
+    start: Duration,
+    end: Duration,
+    settings: &AnimationExportSettings,
+    target: &Path,
+    cancelled: &AtomicBool,
+    progress: impl Fn(f32),
+) -> Result<(), String> {
+    pipeline
+        .set_state(gst::State::Paused)
+        .map_err(|_| "The video could not be opened".to_string())?;
+    let (state, _, _) = pipeline.state(gst::ClockTime::from_nseconds(
+        PREROLL_TIMEOUT.as_nanos() as u64
+    ));
+    state.map_err(|_| "The video could not be opened".to_string())?;
+    pipeline
+        .seek(
+            1.0,
+            gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE,
+            gst::SeekType::Set,
+            gst::ClockTime::from_nseconds(start.as_nanos() as u64),
+            gst::SeekType::Set,
+            gst::ClockTime::from_nseconds(end.as_nanos() as u64),
+        )
+        .map_err(|err| format!("Seeking failed: {err}"))?;
+    pipeline
+        .set_state(gst::State::Playing)
+        .map_err(|_| "The video could not be played".to_string())?;
+
+    let bus = pipeline.bus().ok_or("The conversion pipeline has no bus")?;
+    let length_ms = (end - start).as_millis() as u64;
+    let mut timer = FrameTimer::new(settings.fps);
+    let mut writer: Option<AnimationWriter> = None;
+    let mut last_ms = 0u64;
+    loop {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err("Cancelled".to_string());
+        }
+        if let Some(message) = bus.pop_filtered(&[gst::MessageType::Error]) {
+            if let gst::MessageView::Error(err) = message.view() {
+                return Err(err.error().to_string());
+            }
+        }
+        let Some(sample) = sink.try_pull_sample(gst::ClockTime::from_nseconds(
+            POLL_INTERVAL.as_nanos() as u64,
+        )) else {
+            if sink.is_eos() {
+                break;
+            }
+            continue;
+        };
+        let Some(buffer) = sample.buffer() else {
+            continue;
+        };
+        let pts = buffer
+            .pts()
+            .map_or(Duration::ZERO, |pts| Duration::from_nanos(pts.nseconds()));
+        let timestamp_ms = pts.saturating_sub(start).as_millis() as u64;
+        if timestamp_ms >= length_ms || !timer.keep(timestamp_ms) {
+            continue;
+        }
+        let info = sample
+            .caps()
+            .and_then(|caps| gst_video::VideoInfo::from_caps(caps).ok())
+            .ok_or("The video frame has no size")?;
+        let map = buffer.map_readable().map_err(|err| err.to_string())?;
+        let (width, height) = (info.width(), info.height());
+        let stride = info.stride().first().copied().unwrap_or(0).max(0) as usize;
+        let row = width as usize * 4;
+        let mut pixels = Vec::with_capacity(row * height as usize);
+        for y in 0..height as usize {
+            let line = map
+                .get(y * stride..y * stride + row)
+                .ok_or("The video frame is shorter than its size")?;
+            pixels.extend_from_slice(line);
+        }
+        let (out_width, out_height, pixels) = fit_within(width, height, pixels, MAX_VIDEO_SIDE);
+        let writer = match writer.as_mut() {
+            Some(writer) => writer,
+            None => writer.insert(AnimationWriter::new(
+                settings, out_width, out_height, target,
+            )?),
+        };
+        writer.add(pixels, timestamp_ms)?;
+        last_ms = timestamp_ms;
+        progress(timestamp_ms as f32 / length_ms.max(1) as f32);
+    }
+    let writer = writer.ok_or("No frames in the selected range")?;
+    writer.finish(length_ms.max(last_ms + 1))
+}
+
+/// `pixels` scaled down to fit `max_side`, with the new size.
+fn fit_within(width: u32, height: u32, pixels: Vec<u8>, max_side: u32) -> (u32, u32, Vec<u8>) {
+    if width <= max_side && height <= max_side {
+        return (width, height, pixels);
+    }
+    let scale = max_side as f64 / width.max(height) as f64;
+    let out_width = ((width as f64 * scale).round() as u32).max(1);
+    let out_height = ((height as f64 * scale).round() as u32).max(1);
+    let Some(image) = image::RgbaImage::from_raw(width, height, pixels) else {
+        return (0, 0, Vec::new());
+    };
+    let resized = image::imageops::resize(&image, out_width, out_height, FilterType::Triangle);
+    (out_width, out_height, resized.into_raw())
+}
+
+/// State of the animation conversion dialog while it is open.
+pub struct AnimationExportDialog {
+    pub path: PathBuf,
+    /// Length of the video; `None` for an animated GIF.
+    pub video_duration: Option<Duration>,
+    /// Range of the video to convert, in seconds.
+    pub start_secs: f32,
+    pub end_secs: f32,
+    pub settings: AnimationExportSettings,
+    /// The last conversion started from this dialog.
+    pub job: Option<crate::jobs::JobId>,
+}
+
+impl AnimationExportDialog {
+    /// Converting the animated GIF at `path` to WebP.
+    pub fn for_gif(path: PathBuf, quality: u8, fps: u32) -> Self {
+        Self::new(path, None, 0.0, 0.0, AnimationFormat::WebP, quality, fps)
+    }
+
+    /// Converting a range of the video at `path`, starting at `position`.
+    pub fn for_video(
+        path: PathBuf,
+        duration: Duration,
+        position: Duration,
+        quality: u8,
+        fps: u32,
+    ) -> Self {
+        let length = duration.as_secs_f32();
+        let start = position.as_secs_f32().min(length);
+        let end = (start + 5.0).min(length);
+        let start = (end - 5.0).max(0.0).min(start);
+        Self::new(
+            path,
+            Some(duration),
+            start,
+            end,
+            AnimationFormat::Gif,
+            quality,
+            fps,
+        )
+    }
+
+    fn new(
+        path: PathBuf,
+        video_duration: Option<Duration>,
+        start_secs: f32,
+        end_secs: f32,
+        format: AnimationFormat,
+        quality: u8,
+        fps: u32,
+    ) -> Self {
+        Self {
+            path,
+            video_duration,
+            start_secs,
+            end_secs,
+            settings: AnimationExportSettings {
+                format,
+                quality,
+                fps,
+            },
+            job: None,
+        }
+    }
+
+    /// What the conversion reads, with the range limited to [`MAX_CLIP_LENGTH`].
+    pub fn source(&self) -> AnimationSource {
+        match self.video_duration {
+            None => AnimationSource::Gif(self.path.clone()),
+            Some(_) => {
+                let start = Duration::from_secs_f32(self.start_secs.max(0.0));
+                let end =
+                    Duration::from_secs_f32(self.end_secs.max(0.0)).min(start + MAX_CLIP_LENGTH);
+                AnimationSource::Video {
+                    path: self.path.clone(),
+                    start,
+                    end,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use super::{
+        gif_quantizer_speed, AnimationExportDialog, AnimationSource, FrameTimer, MAX_CLIP_LENGTH,
+    };
+
+    #[test]
+    fn frames_closer_than_the_frame_rate_are_dropped() {
+        let mut timer = FrameTimer::new(15);
+        let kept: Vec<u64> = (0..10)
+            .map(|frame| frame * 100 / 3)
+            .filter(|&timestamp| timer.keep(timestamp))
+            .collect();
+        assert_eq!(kept, vec![0, 66, 133, 200, 266]);
+        assert_eq!(gif_quantizer_speed(100), 1);
+        assert_eq!(gif_quantizer_speed(1), 30);
+    }
+
+    #[test]
+    fn video_ranges_start_at_the_position_and_are_capped() {
+        let dialog = AnimationExportDialog::for_video(
+            PathBuf::from("clip.mp4"),
+            Duration::from_secs(8),
+            Duration::from_secs(7),
+            80,
+            15,
+        );
+        assert_eq!((dialog.start_secs, dialog.end_secs), (3.0, 8.0));
+
+        let mut dialog = dialog;
+        dialog.video_duration = Some(Duration::from_secs(600));
+        dialog.end_secs = 500.0;
+        let AnimationSource::Video { start, end, .. } = dialog.source() else {
+            panic!("a video dialog reads a video");
+        };
+        assert_eq!(end - start, MAX_CLIP_LENGTH);
+    }
+}
@@ -0,0 +1,730 @@
+//! Annotation layer for the solo image view: freehand strokes, arrows and rectangles.
+//!
+//! Shapes are stored in image pixel coordinates of the displayed orientation, so they follow
+//! zoom, pan, flips and fine rotation. A layer can be flattened into a PNG next to the image
+//! or saved as a `<file>.annotations` sidecar that is loaded again the next time the image is
+//! annotated.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Palette offered by the annotation toolbar.
+pub const ANNOTATION_COLORS: [egui::Color32; 6] = [
+    egui::Color32::from_rgb(255, 59, 48),
+    egui::Color32::from_rgb(255, 204, 0),
+    egui::Color32::from_rgb(52, 199, 89),
+    egui::Color32::from_rgb(10, 132, 255),
+    egui::Color32::WHITE,
+    egui::Color32::BLACK,
+];
+pub const MIN_STROKE_WIDTH: f32 = 1.0;
+pub const MAX_STROKE_WIDTH: f32 = 48.0;
+/// Freehand points closer than this (in screen pixels) to the previous one are dropped.
+const FREEHAND_MIN_SPACING_PX: f32 = 1.5;
+const SIDECAR_EXTENSION: &str = "annotations";
+/// Pointer samples older than this (in seconds) no longer count towards stroke prediction.
+const PREDICTION_WINDOW_SECS: f64 = 0.05;
+/// Furthest a predicted stroke tip may run ahead of the pointer, in screen pixels.
+const MAX_PREDICTION_PX: f32 = 32.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationTool {
+    Freehand,
+    Arrow,
+    Rectangle,
+}
+
+impl AnnotationTool {
+    pub const ALL: [AnnotationTool; 3] = [
+        AnnotationTool::Freehand,
+        AnnotationTool::Arrow,
+        AnnotationTool::Rectangle,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AnnotationTool::Freehand => "✏ Pen",
+            AnnotationTool::Arrow => "➚ Arrow",
+            AnnotationTool::Rectangle => "▭ Rect",
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            AnnotationTool::Freehand => "freehand",
+            AnnotationTool::Arrow => "arrow",
+            AnnotationTool::Rectangle => "rectangle",
+        }
+    }
+
+    fn from_str(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "freehand" | "pen" => Some(AnnotationTool::Freehand),
+            "arrow" => Some(AnnotationTool::Arrow),
+            "rectangle" | "rect" => Some(AnnotationTool::Rectangle),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotationShape {
+    pub tool: AnnotationTool,
+    pub color: egui::Color32,
+    /// Stroke width in image pixels.
+    pub width: f32,
+    /// Freehand: every sampled point. Arrow: tail and tip. Rectangle: two opposite corners.
+    pub points: Vec<egui::Pos2>,
+}
+
+impl AnnotationShape {
+    /// Polylines that make up the shape, as `(points, closed)`.
+    fn polylines(&self) -> Vec<(Vec<egui::Pos2>, bool)> {
+        let (Some(&first), Some(&last)) = (self.points.first(), self.points.last()) else {
+            return Vec::new();
+        };
+        match self.tool {
+            AnnotationTool::Freehand => vec![(self.points.clone(), false)],
+            AnnotationTool::Arrow => {
+                let dir = last - first;
+                let length = dir.length();
+                if length < f32::EPSILON {
+                    return vec![(vec![first, last], false)];
+                }
+                let head_len = (self.width * 4.0).max(12.0).min(length * 0.5);
+                let back = -dir / length * head_len;
+                let (sin, cos) = 28f32.to_radians().sin_cos();
+                let rotate = |v: egui::Vec2, sin: f32| {
+                    egui::vec2(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+                };
+                vec![
+                    (vec![first, last], false),
+                    (
+                        vec![last + rotate(back, sin), last, last + rotate(back, -sin)],
+                        false,
+                    ),
+                ]
+            }
+            AnnotationTool::Rectangle => vec![(
+                vec![
+                    first,
+                    egui::pos2(last.x, first.y),
+                    last,
+                    egui::pos2(first.x, last.y),
+                ],
+                true,
+            )],
+        }
+    }
+
+    fn scaled(&self, scale: egui::Vec2) -> Self {
+        Self {
+            points: self
+                .points
+                .iter()
+                .map(|p| egui::pos2(p.x * scale.x, p.y * scale.y))
+                .collect(),
+            width: self.width * scale.x.max(scale.y),
+            ..self.clone()
+        }
+    }
+}
+
+/// Annotations of one image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotationLayer {
+    /// Image size the coordinates refer to.
+    pub image_size: egui::Vec2,
+    pub shapes: Vec<AnnotationShape>,
+    /// Changed since it was last saved as a sidecar.
+    pub dirty: bool,
+}
+
+impl AnnotationLayer {
+    pub fn new(image_size: egui::Vec2) -> Self {
+        Self {
+            image_size,
+            shapes: Vec::new(),
+            dirty: false,
+        }
+    }
+
+    /// Rescale coordinates after the image dimensions changed (e.g. a 90° rotation).
+    fn fit_to(&mut self, image_size: egui::Vec2) {
+        if self.image_size == image_size || self.image_size.min_elem() <= 0.0 {
+            self.image_size = image_size;
+            return;
+        }
+        let scale = image_size / self.image_size;
+        for shape in &mut self.shapes {
+            *shape = shape.scaled(scale);
+        }
+        self.image_size = image_size;
+    }
+
+    pub fn to_sidecar_string(&self) -> String {
+        let mut out = String::from("; rust-image-viewer annotations\n");
+        out.push_str(&format!(
+            "image_size = {}x{}\n",
+            self.image_size.x.round(),
+            self.image_size.y.round()
+        ));
+        for shape in &self.shapes {
+            let [r, g, b, a] = shape.color.to_srgba_unmultiplied();
+            let points: Vec<String> = shape
+                .points
+                .iter()
+                .map(|p| format!("{:.1},{:.1}", p.x, p.y))
+                .collect();
+            out.push_str(&format!(
+                "\n[shape]\ntool = {}\ncolor = #{r:02x}{g:02x}{b:02x}{a:02x}\nwidth = {}\npoints = {}\n",
+                shape.tool.as_str(),
+                shape.width,
+                points.join(" ")
+            ));
+        }
+        out
+    }
+
+    /// Parses a sidecar written by [`AnnotationLayer::to_sidecar_string`] and rescales it to
+    /// `image_size`.
+    pub fn parse_sidecar(text: &str, image_size: egui::Vec2) -> Result<Self, String> {
+        let mut layer = Self::new(image_size);
+        let mut current: Option<AnnotationShape> = None;
+
+        for (line_no, raw_line) in text.lines().enumerate() {
+            let line = raw_line.trim();
+            if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+                continue;
+            }
+            if line.eq_ignore_ascii_case("[shape]") {
+                layer
+                    .shapes
+                    .extend(current.take().filter(|s| !s.points.is_empty()));
+                current = Some(AnnotationShape {
+                    tool: AnnotationTool::Freehand,
+                    color: ANNOTATION_COLORS[0],
+                    width: 4.0,
+                    points: Vec::new(),
+                });
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(format!("line {}: expected `key = value`", line_no + 1));
+            };
+            let (key, value) = (key.trim(), value.trim());
+            let invalid = || format!("line {}: invalid {key} `{value}`", line_no + 1);
+
+            match (key, current.as_mut()) {
+                ("image_size", _) => {
+                    let (w, h) = value.split_once('x').ok_or_else(invalid)?;
+                    let w: f32 = w.trim().parse().map_err(|_| invalid())?;
+                    let h: f32 = h.trim().parse().map_err(|_| invalid())?;
+                    layer.image_size = egui::vec2(w, h);
+                }
+                ("tool", Some(shape)) => {
+                    shape.tool = AnnotationTool::from_str(value).ok_or_else(invalid)?
+                }
+                ("color", Some(shape)) => {
+                    shape.color = parse_hex_color(value).ok_or_else(invalid)?
+                }
+                ("width", Some(shape)) => {
+                    shape.width = value
+                        .parse::<f32>()
+                        .map_err(|_| invalid())?
+                        .clamp(MIN_STROKE_WIDTH, MAX_STROKE_WIDTH)
+                }
+                ("points", Some(shape)) => {
+                    for pair in value.split_whitespace() {
+                        let (x, y) = pair.split_once(',').ok_or_else(invalid)?;
+                        let x: f32 = x.parse().map_err(|_| invalid())?;
+                        let y: f32 = y.parse().map_err(|_| invalid())?;
+                        shape.points.push(egui::pos2(x, y));
+                    }
+                }
+                _ => {}
+            }
+        }
+        layer
+            .shapes
+            .extend(current.filter(|s| !s.points.is_empty()));
+        layer.fit_to(image_size);
+        Ok(layer)
+    }
+
+    /// Draws every shape into a straight-alpha RGBA8 buffer of `width` x `height` pixels.
+    pub fn flatten_into(&self, pixels: &mut [u8], width: u32, height: u32) {
+        if width == 0 || height == 0 || self.image_size.min_elem() <= 0.0 {
+            return;
+        }
+        let scale = egui::vec2(width as f32, height as f32) / self.image_size;
+        for shape in &self.shapes {
+            rasterize_shape(&shape.scaled(scale), pixels, width, height);
+        }
+    }
+}
+
+fn parse_hex_color(value: &str) -> Option<egui::Color32> {
+    let hex = value.trim().trim_start_matches('#');
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    match hex.len() {
+        6 => Some(egui::Color32::from_rgb(
+            channel(0)?,
+            channel(2)?,
+            channel(4)?,
+        )),
+        8 => Some(egui::Color32::from_rgba_unmultiplied(
+            channel(0)?,
+            channel(2)?,
+            channel(4)?,
+            channel(6)?,
+        )),
+        _ => None,
+    }
+}
+
+fn distance_to_segment(p: egui::Pos2, a: egui::Pos2, b: egui::Pos2) -> f32 {
+    let ab = b - a;
+    let len_sq = ab.length_sq();
+    let t = if len_sq <= f32::EPSILON {
+        0.0
+    } else {
+        ((p - a).dot(ab) / len_sq).clamp(0.0, 1.0)
+    };
+    (p - (a + ab * t)).length()
+}
+
+/// Antialiased stroke rasterizer: coverage is accumulated per shape (max over its segments) so
+/// overlapping segments don't darken joints, then blended once.
+fn rasterize_shape(shape: &AnnotationShape, pixels: &mut [u8], width: u32, height: u32) {
+    let half = shape.width.max(MIN_STROKE_WIDTH) * 0.5;
+    let mut segments = Vec::new();
+    for (points, closed) in shape.polylines() {
+        if points.len() == 1 {
+            segments.push((points[0], points[0]));
+        }
+        segments.extend(points.windows(2).map(|w| (w[0], w[1])));
+        if closed && points.len() > 2 {
+            segments.push((points[points.len() - 1], points[0]));
+        }
+    }
+    let Some(bounds) = segments
+        .iter()
+        .map(|&(a, b)| egui::Rect::from_two_pos(a, b))
+        .reduce(|acc, rect| acc.union(rect))
+    else {
+        return;
+    };
+
+    let min_x = (bounds.min.x - half - 1.0).floor().max(0.0) as u32;
+    let min_y = (bounds.min.y - half - 1.0).floor().max(0.0) as u32;
+    let max_x = ((bounds.max.x + half + 1.0).ceil().max(0.0) as u32).min(width);
+    let max_y = ((bounds.max.y + half + 1.0).ceil().max(0.0) as u32).min(height);
+    if min_x >= max_x || min_y >= max_y {
+        return;
+    }
+
+    let [sr, sg, sb, sa] = shape.color.to_srgba_unmultiplied();
+    let src_alpha = sa as f32 / 255.0;
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            let p = egui::pos2(x as f32 + 0.5, y as f32 + 0.5);
+            let coverage = segments
+                .iter()
+                .map(|&(a, b)| (half + 0.5 - distance_to_segment(p, a, b)).clamp(0.0, 1.0))
+                .fold(0.0f32, f32::max);
+            if coverage <= 0.0 {
+                continue;
+            }
+
+            let alpha = src_alpha * coverage;
+            let idx = ((y * width + x) * 4) as usize;
+            let Some(dst) = pixels.get_mut(idx..idx + 4) else {
+                return;
+            };
+            let dst_alpha = dst[3] as f32 / 255.0;
+            let out_alpha = alpha + dst_alpha * (1.0 - alpha);
+            if out_alpha <= 0.0 {
+                continue;
+            }
+            for (channel, src) in dst.iter_mut().take(3).zip([sr, sg, sb]) {
+                let blended =
+                    (src as f32 * alpha + *channel as f32 * dst_alpha * (1.0 - alpha)) / out_alpha;
+                *channel = blended.round().clamp(0.0, 255.0) as u8;
+            }
+            dst[3] = (out_alpha * 255.0).round() as u8;
+        }
+    }
+}
+
+/// Maps image pixel coordinates to the screen for the current solo view.
+#[derive(Debug, Clone, Copy)]
+pub struct AnnotationView {
+    pub center: egui::Pos2,
+    pub zoom: f32,
+    pub image_size: egui::Vec2,
+    pub angle_radians: f32,
+    pub flip_horizontal: bool,
+    pub flip_vertical: bool,
+}
+
+impl AnnotationView {
+    pub fn to_screen(self, p: egui::Pos2) -> egui::Pos2 {
+        let mut local = (p.to_vec2() - self.image_size * 0.5) * self.zoom;
+        if self.flip_horizontal {
+            local.x = -local.x;
+        }
+        if self.flip_vertical {
+            local.y = -local.y;
+        }
+        let (sin, cos) = self.angle_radians.sin_cos();
+        self.center + egui::vec2(local.x * cos - local.y * sin, local.x * sin + local.y * cos)
+    }
+
+    /// Inverse of [`AnnotationView::to_screen`], clamped to the image bounds.
+    pub fn to_image(self, screen: egui::Pos2) -> egui::Pos2 {
+        let p = self.to_image_unclamped(screen);
+        egui::pos2(
+            p.x.clamp(0.0, self.image_size.x),
+            p.y.clamp(0.0, self.image_size.y),
+        )
+    }
+
+    /// Inverse of [`AnnotationView::to_screen`]; may fall outside the image.
+    pub fn to_image_unclamped(self, screen: egui::Pos2) -> egui::Pos2 {
+        let d = screen - self.center;
+        let (sin, cos) = (-self.angle_radians).sin_cos();
+        let mut local = egui::vec2(d.x * cos - d.y * sin, d.x * sin + d.y * cos);
+        if self.flip_horizontal {
+            local.x = -local.x;
+        }
+        if self.flip_vertical {
+            local.y = -local.y;
+        }
+        (local / self.zoom.max(0.0001) + self.image_size * 0.5).to_pos2()
+    }
+}
+
+pub fn paint_shape(painter: &egui::Painter, view: &AnnotationView, shape: &AnnotationShape) {
+    let stroke = egui::Stroke::new((shape.width * view.zoom).max(1.0), shape.color);
+    for (points, closed) in shape.polylines() {
+        let points: Vec<egui::Pos2> = points.iter().map(|p| view.to_screen(*p)).collect();
+        if points.len() == 1 {
+            painter.circle_filled(points[0], stroke.width * 0.5, shape.color);
+        } else if closed {
+            painter.add(egui::Shape::closed_line(points, stroke));
+        } else {
+            painter.add(egui::Shape::line(points, stroke));
+        }
+    }
+}
+
+/// Where the pointer will be `lead` seconds after the newest `(time, position)` sample, from
+/// its velocity over the sample window. `None` while there is no movement to go on.
+pub fn predict_pointer(samples: &[(f64, egui::Pos2)], lead: f64) -> Option<egui::Pos2> {
+    let &(newest_time, newest) = samples.last()?;
+    let &(oldest_time, oldest) = samples
+        .iter()
+        .find(|(time, _)| newest_time - time <= PREDICTION_WINDOW_SECS)?;
+    let elapsed = newest_time - oldest_time;
+    if elapsed <= f64::EPSILON {
+        return None;
+    }
+    let offset = (newest - oldest) * (lead.max(0.0) / elapsed) as f32;
+    let length = offset.length();
+    if length > MAX_PREDICTION_PX {
+        Some(newest + offset * (MAX_PREDICTION_PX / length))
+    } else {
+        Some(newest + offset)
+    }
+}
+
+/// `photo.jpg` -> `photo.jpg.annotations`.
+pub fn sidecar_path(image_path: &Path) -> PathBuf {
+    let mut name = image_path.as_os_str().to_owned();
+    name.push(".");
+    name.push(SIDECAR_EXTENSION);
+    PathBuf::from(name)
+}
+
+/// First free `photo.annotated.png`, `photo.annotated-2.png`, ... next to the image.
+pub fn export_path(image_path: &Path) -> PathBuf {
+    crate::image_loader::sibling_export_path(image_path, "annotated", "png")
+}
+
+/// Annotation mode state: the active tool plus one layer per image annotated this session.
+pub struct AnnotationEditor {
+    pub active: bool,
+    pub tool: AnnotationTool,
+    pub color: egui::Color32,
+    /// Stroke width in screen pixels at the time of drawing.
+    pub width: f32,
+    layers: HashMap<PathBuf, AnnotationLayer>,
+    drawing: Option<AnnotationShape>,
+    /// Recent `(time, screen position)` pointer samples of the stroke being drawn.
+    pointer_samples: Vec<(f64, egui::Pos2)>,
+    /// Last save/export result shown in the toolbar.
+    pub status: Option<String>,
+}
+
+impl Default for AnnotationEditor {
+    fn default() -> Self {
+        Self {
+            active: false,
+            tool: AnnotationTool::Freehand,
+            color: ANNOTATION_COLORS[0],
+            width: 4.0,
+            layers: HashMap::new(),
+            drawing: None,
+            pointer_samples: Vec::new(),
+            status: None,
+        }
+    }
+}
+
+impl AnnotationEditor {
+    /// Layer for `path`, loading its sidecar on first use.
+    pub fn layer_mut(&mut self, path: &Path, image_size: egui::Vec2) -> &mut AnnotationLayer {
+        let layer = self.layers.entry(path.to_path_buf()).or_insert_with(|| {
+            std::fs::read_to_string(sidecar_path(path))
+                .ok()
+                .and_then(|text| AnnotationLayer::parse_sidecar(&text, image_size).ok())
+                .unwrap_or_else(|| AnnotationLayer::new(image_size))
+        });
+        layer.fit_to(image_size);
+        layer
+    }
+
+    pub fn layer(&self, path: &Path) -> Option<&AnnotationLayer> {
+        self.layers.get(path)
+    }
+
+    pub fn in_progress(&self) -> Option<&AnnotationShape> {
+        self.drawing.as_ref()
+    }
+
+    pub fn is_drawing(&self) -> bool {
+        self.drawing.is_some()
+    }
+
+    pub fn begin_shape(&mut self, view: &AnnotationView, screen_pos: egui::Pos2) {
+        let p = view.to_image(screen_pos);
+        self.drawing = Some(AnnotationShape {
+            tool: self.tool,
+            color: self.color,
+            width: self.width / view.zoom.max(0.0001),
+            points: vec![p, p],
+        });
+        self.pointer_samples.clear();
+    }
+
+    /// Records where the pointer was at `time` (seconds), for [`Self::predicted_shape`].
+    pub fn record_pointer_sample(&mut self, time: f64, screen_pos: egui::Pos2) {
+        if self.drawing.is_none() {
+            return;
+        }
+        self.pointer_samples
+            .retain(|(sample_time, _)| time - sample_time <= PREDICTION_WINDOW_SECS);
+        if self
+            .pointer_samples
+            .last()
+            .map_or(true, |(last_time, _)| *last_time < time)
+        {
+            self.pointer_samples.push((time, screen_pos));
+        }
+    }
+
+    /// The shape being drawn, extended to where the pointer is expected to be `lead` seconds
+    /// from now. Only for display: it hides a frame of latency and is never stored.
+    pub fn predicted_shape(&self, view: &AnnotationView, lead: f64) -> Option<AnnotationShape> {
+        let shape = self.drawing.as_ref()?;
+        let tip = view.to_image(predict_pointer(&self.pointer_samples, lead)?);
+        let mut predicted = shape.clone();
+        match predicted.tool {
+            AnnotationTool::Freehand => predicted.points.push(tip),
+            AnnotationTool::Arrow | AnnotationTool::Rectangle => {
+                if let Some(end) = predicted.points.last_mut() {
+                    *end = tip;
+                }
+            }
+        }
+        Some(predicted)
+    }
+
+    pub fn extend_shape(&mut self, view: &AnnotationView, screen_pos: egui::Pos2) {
+        let Some(shape) = self.drawing.as_mut() else {
+            return;
+        };
+        let p = view.to_image(screen_pos);
+        match shape.tool {
+            AnnotationTool::Freehand => {
+                let last = shape.points.last().copied().unwrap_or(p);
+                if (p - last).length() * view.zoom >= FREEHAND_MIN_SPACING_PX {
+                    shape.points.push(p);
+                }
+            }
+            AnnotationTool::Arrow | AnnotationTool::Rectangle => {
+                if let Some(end) = shape.points.last_mut() {
+                    *end = p;
+                }
+            }
+        }
+    }
+
+    pub fn finish_shape(&mut self, path: &Path, image_size: egui::Vec2) {
+        self.pointer_samples.clear();
+        let Some(mut shape) = self.drawing.take() else {
+            return;
+        };
+        if shape.tool == AnnotationTool::Freehand && shape.points.len() > 2 {
+            // Drop the duplicated start point used while the stroke was a single dot.
+            shape.points.remove(0);
+        }
+        let layer = self.layer_mut(path, image_size);
+        layer.shapes.push(shape);
+        layer.dirty = true;
+    }
+
+    pub fn cancel_shape(&mut self) {
+        self.drawing = None;
+        self.pointer_samples.clear();
+    }
+
+    pub fn undo(&mut self, path: &Path) {
+        if let Some(layer) = self.layers.get_mut(path) {
+            if layer.shapes.pop().is_some() {
+                layer.dirty = true;
+            }
+        }
+    }
+
+    pub fn clear(&mut self, path: &Path) {
+        if let Some(layer) = self.layers.get_mut(path) {
+            if !layer.shapes.is_empty() {
+                layer.shapes.clear();
+                layer.dirty = true;
+            }
+        }
+    }
+
+    /// Images whose shapes changed since their sidecar was last saved.
+    pub fn unsaved_count(&self) -> usize {
+        self.layers.values().filter(|layer| layer.dirty).count()
+    }
+
+    /// Moves the layer of a renamed image to its new path.
+    pub fn rename_path(&mut self, from: &Path, to: &Path) {
+        if let Some(layer) = self.layers.remove(from) {
+            self.layers.insert(to.to_path_buf(), layer);
+        }
+    }
+
+    /// Writes the sidecar, or removes it once every shape has been cleared.
+    pub fn save_sidecar(&mut self, path: &Path) -> Result<PathBuf, String> {
+        let layer = self
+            .layers
+            .get_mut(path)
+            .ok_or_else(|| "Nothing to save".to_string())?;
+        let sidecar = sidecar_path(path);
+        if layer.shapes.is_empty() {
+            if sidecar.exists() {
+                std::fs::remove_file(&sidecar)
+                    .map_err(|err| format!("Failed to remove {}: {err}", sidecar.display()))?;
+            }
+        } else {
+            std::fs::write(&sidecar, layer.to_sidecar_string())
+                .map_err(|err| format!("Failed to write {}: {err}", sidecar.display()))?;
+        }
+        layer.dirty = false;
+        Ok(sidecar)
+    }
+}
+
+/// Flattens `layer` onto a copy of the frame and writes it as PNG, onto `background` when
+/// given (see [`crate::png_export::write_png`]). Runs on a worker thread.
+pub fn export_flattened_png(
+    layer: &AnnotationLayer,
+    mut pixels: Vec<u8>,
+    width: u32,
+    height: u32,
+    background: Option<[u8; 3]>,
+    target: &Path,
+) -> Result<(), String> {
+    layer.flatten_into(&mut pixels, width, height);
+    crate::png_export::write_png(pixels, width, height, background, target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_layer() -> AnnotationLayer {
+        let mut layer = AnnotationLayer::new(egui::vec2(100.0, 50.0));
+        layer.shapes.push(AnnotationShape {
+            tool: AnnotationTool::Arrow,
+            color: egui::Color32::from_rgb(255, 0, 0),
+            width: 3.0,
+            points: vec![egui::pos2(10.0, 10.0), egui::pos2(80.0, 40.0)],
+        });
+        layer.shapes.push(AnnotationShape {
+            tool: AnnotationTool::Rectangle,
+            color: egui::Color32::from_rgb(0, 0, 255),
+            width: 2.0,
+            points: vec![egui::pos2(5.0, 5.0), egui::pos2(50.0, 25.0)],
+        });
+        layer
+    }
+
+    #[test]
+    fn sidecar_round_trips_and_rescales() {
+        let layer = sample_layer();
+        let text = layer.to_sidecar_string();
+        assert_eq!(
+            AnnotationLayer::parse_sidecar(&text, layer.image_size).unwrap(),
+            layer
+        );
+
+        let doubled = AnnotationLayer::parse_sidecar(&text, egui::vec2(200.0, 100.0)).unwrap();
+        assert_eq!(doubled.shapes[0].points[1], egui::pos2(160.0, 80.0));
+        assert_eq!(doubled.shapes[0].width, 6.0);
+    }
+
+    #[test]
+    fn flatten_paints_along_strokes_only() {
+        let mut layer = AnnotationLayer::new(egui::vec2(20.0, 20.0));
+        layer.shapes.push(AnnotationShape {
+            tool: AnnotationTool::Freehand,
+            color: egui::Color32::from_rgb(255, 0, 0),
+            width: 2.0,
+            points: vec![egui::pos2(0.0, 10.0), egui::pos2(20.0, 10.0)],
+        });
+        let mut pixels = vec![255u8; 20 * 20 * 4];
+        layer.flatten_into(&mut pixels, 20, 20);
+
+        let at = |x: usize, y: usize| &pixels[(y * 20 + x) * 4..(y * 20 + x) * 4 + 4];
+        assert_eq!(at(10, 10), &[255, 0, 0, 255]);
+        assert_eq!(at(10, 2), &[255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn prediction_extrapolates_recent_velocity_within_a_cap() {
+        let samples = [
+            (0.0, egui::pos2(0.0, 0.0)),
+            (0.010, egui::pos2(10.0, 0.0)),
+            (0.020, egui::pos2(20.0, 5.0)),
+        ];
+        let tip = predict_pointer(&samples, 0.010).unwrap();
+        assert!((tip - egui::pos2(30.0, 7.5)).length() < 1e-3);
+
+        let far = predict_pointer(&samples, 1.0).unwrap();
+        assert!(((far - egui::pos2(20.0, 5.0)).length() - MAX_PREDICTION_PX).abs() < 1e-3);
+
+        // Samples outside the window and a pointer at rest predict nothing.
+        assert!(predict_pointer(
+            &[(0.0, egui::Pos2::ZERO), (1.0, egui::pos2(9.0, 9.0))],
+            0.01
+        )
+        .is_none());
+        assert!(predict_pointer(&samples[..1], 0.01).is_none());
+    }
+}
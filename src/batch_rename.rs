@@ -0,0 +1,385 @@
+//! Batch rename: one name template applied to every file in the folder list.
+//!
+//! Templates combine literal text with `{n}` (a zero-padded counter), `{name}` (the original
+//! file stem), and `{date}` / `{time}` (when the photo was taken, from EXIF, falling back to
+//! the file's modification time). The extension is always kept. Dates are read on a worker
+//! because that touches every file; the preview waits for them.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::exif::{jpeg_exif_block, Tiff};
+use crate::jobs::JobManager;
+
+/// Bytes read from the start of each file when looking for EXIF. JPEG keeps EXIF in the
+/// first APP1 segment, which is at most 64 KiB; TIFF-based raws keep IFD0 near the start.
+const EXIF_PROBE_BYTES: u64 = 256 * 1024;
+
+const TAG_DATE_TIME: u16 = 0x0132;
+const TAG_EXIF_IFD: u16 = 0x8769;
+const TAG_DATE_TIME_ORIGINAL: u16 = 0x9003;
+
+/// Capture time, in the camera's local time (EXIF stores no zone).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TakenAt {
+    pub year: u32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+impl TakenAt {
+    /// Parses EXIF's `YYYY:MM:DD HH:MM:SS`.
+    fn parse_exif(value: &[u8]) -> Option<Self> {
+        let text = std::str::from_utf8(value)
+            .ok()?
+            .trim_end_matches('\0')
+            .trim();
+        let (date, time) = text.split_once(' ')?;
+        let mut date = date.split(':').map(|part| part.parse::<u32>().ok());
+        let mut time = time.split(':').map(|part| part.parse::<u32>().ok());
+        let taken = Self {
+            year: date.next()??,
+            month: date.next()??,
+            day: date.next()??,
+            hour: time.next()??,
+            minute: time.next()??,
+            second: time.next()??,
+        };
+        // Cameras without a set clock write all zeros.
+        (taken.year > 0 && (1..=12).contains(&taken.month) && (1..=31).contains(&taken.day))
+            .then_some(taken)
+    }
+
+    fn from_unix_seconds(secs: u64) -> Self {
+        let (year, month, day) = crate::usage_stats::civil_date((secs / 86_400) as u32);
+        let secs_of_day = secs % 86_400;
+        Self {
+            year: year.max(0) as u32,
+            month,
+            day,
+            hour: (secs_of_day / 3_600) as u32,
+            minute: (secs_of_day / 60 % 60) as u32,
+            second: (secs_of_day % 60) as u32,
+        }
+    }
+
+    pub fn date(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+
+    pub fn time(&self) -> String {
+        format!("{:02}{:02}{:02}", self.hour, self.minute, self.second)
+    }
+}
+
+/// Capture time from a TIFF-structured EXIF block: `DateTimeOriginal`, else `DateTime`.
+fn taken_at_from_tiff(data: &[u8]) -> Option<TakenAt> {
+    let tiff = Tiff::parse(data)?;
+    let ifd0 = tiff.ifd0()?;
+    let original = tiff
+        .find_tag(ifd0, TAG_EXIF_IFD)
+        .and_then(|(offset, _)| tiff.u32_at(offset))
+        .and_then(|exif_ifd| tiff.ascii_tag(exif_ifd as usize, TAG_DATE_TIME_ORIGINAL))
+        .and_then(TakenAt::parse_exif);
+    original.or_else(|| {
+        tiff.ascii_tag(ifd0, TAG_DATE_TIME)
+            .and_then(TakenAt::parse_exif)
+    })
+}
+
+/// Capture time from the start of a JPEG or TIFF file.
+pub fn exif_taken_at(bytes: &[u8]) -> Option<TakenAt> {
+    if bytes.starts_with(&[0xFF, 0xD8]) {
+        return taken_at_from_tiff(jpeg_exif_block(bytes)?);
+    }
+    taken_at_from_tiff(bytes)
+}
+
+/// EXIF capture time of `path`, without the modification time fallback.
+pub fn exif_taken_at_path(path: &Path) -> Option<TakenAt> {
+    let mut head = Vec::new();
+    let file = std::fs::File::open(path).ok()?;
+    let _ = file.take(EXIF_PROBE_BYTES).read_to_end(&mut head);
+    exif_taken_at(&head)
+}
+
+/// EXIF capture time of `path`, or its modification time (UTC) when it has none.
+pub fn taken_at(path: &Path) -> Option<TakenAt> {
+    exif_taken_at_path(path).or_else(|| {
+        let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+        let secs = modified.duration_since(UNIX_EPOCH).ok()?.as_secs();
+        Some(TakenAt::from_unix_seconds(secs))
+    })
+}
+
+pub fn template_uses_dates(template: &str) -> bool {
+    template.contains("{date}") || template.contains("{time}")
+}
+
+/// New file name for `path` as the `index`-th file (0-based) under `template`.
+pub fn render_name(
+    template: &str,
+    path: &Path,
+    index: usize,
+    start: u32,
+    padding: usize,
+    taken: Option<&TakenAt>,
+) -> String {
+    let counter = format!("{:0padding$}", start as usize + index);
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let (date, time) = taken.map_or((String::new(), String::new()), |taken| {
+        (taken.date(), taken.time())
+    });
+    let base = template
+        .replace("{n}", &counter)
+        .replace("{name}", &stem)
+        .replace("{date}", &date)
+        .replace("{time}", &time);
+    match path.extension() {
+        Some(extension) => format!("{}.{}", base.trim(), extension.to_string_lossy()),
+        None => base.trim().to_string(),
+    }
+}
+
+/// `(original, renamed)` for every path whose name changes. Fails on invalid names and on
+/// two files mapping to the same name; collisions with files outside the batch are left to
+/// the rename step, which checks the disk.
+pub fn plan(
+    paths: &[PathBuf],
+    template: &str,
+    start: u32,
+    padding: usize,
+    dates: Option<&[Option<TakenAt>]>,
+) -> Result<Vec<(PathBuf, PathBuf)>, String> {
+    let mut seen = std::collections::HashSet::with_capacity(paths.len());
+    let mut renames = Vec::new();
+    for (index, path) in paths.iter().enumerate() {
+        let taken = dates
+            .and_then(|dates| dates.get(index))
+            .and_then(Option::as_ref);
+        let name = render_name(template, path, index, start, padding, taken);
+        let stem_empty = Path::new(&name)
+            .file_stem()
+            .map_or(true, |stem| stem.is_empty());
+        if stem_empty || name.starts_with('.') || name.contains(['/', '\\']) {
+            return Err(format!("\"{name}\" is not a valid file name"));
+        }
+        let target = path.with_file_name(&name);
+        if !seen.insert(target.clone()) {
+            return Err(format!(
+                "Several files would be named \"{name}\"; add {{n}} to the template"
+            ));
+        }
+        if target != *path {
+            renames.push((path.clone(), target));
+        }
+    }
+    Ok(renames)
+}
+
+/// Moves the rating and annotation sidecars along with renamed images. Best effort: a
+/// sidecar whose new name is already taken stays where it is.
+pub fn move_companion_files(renames: &[(PathBuf, PathBuf)]) {
+    transfer_companion_files(renames, false);
+}
+
+/// Copies the rating and annotation sidecars along with copied images, best effort like
+/// [`move_companion_files`].
+pub fn copy_companion_files(copies: &[(PathBuf, PathBuf)]) {
+    transfer_companion_files(copies, true);
+}
+
+fn transfer_companion_files(pairs: &[(PathBuf, PathBuf)], copy: bool) {
+    let companions: [fn(&Path) -> PathBuf; 2] = [
+        crate::ratings::sidecar_path,
+        crate::annotations::sidecar_path,
+    ];
+    for (original, target) in pairs {
+        for companion in companions {
+            let (from, to) = (companion(original), companion(target));
+            if from.exists() && !to.exists() {
+                let result = if copy {
+                    std::fs::copy(&from, &to).map(|_| ())
+                } else {
+                    std::fs::rename(&from, &to)
+                };
+                if let Err(err) = result {
+                    tracing::warn!("Failed to transfer {}: {err}", from.display());
+                }
+            }
+        }
+    }
+}
+
+/// Batch rename dialog state while it is open.
+pub struct BatchRenameDialog {
+    pub paths: Vec<PathBuf>,
+    pub template: String,
+    pub start: u32,
+    pub padding: usize,
+    /// Capture times in `paths` order, once the worker has read them.
+    pub dates: Option<Vec<Option<TakenAt>>>,
+    pub dates_rx: Option<crossbeam_channel::Receiver<Vec<Option<TakenAt>>>>,
+    pub status: Option<String>,
+}
+
+impl BatchRenameDialog {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        Self {
+            paths,
+            template: "{date}_{n}".to_string(),
+            start: 1,
+            padding: 3,
+            dates: None,
+            dates_rx: None,
+            status: None,
+        }
+    }
+
+    /// Starts a job reading capture times if the template needs them and they are not
+    /// loaded. A cancelled job leaves every file without a date.
+    pub fn ensure_dates(&mut self, jobs: &mut JobManager) {
+        if !template_uses_dates(&self.template) || self.dates.is_some() || self.dates_rx.is_some() {
+            return;
+        }
+        let paths = self.paths.clone();
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        let title = format!("Read capture dates of {} files", paths.len());
+        jobs.spawn(title, move |context| {
+            let mut dates = Vec::with_capacity(paths.len());
+            for (index, path) in paths.iter().enumerate() {
+                if context.is_cancelled() {
+                    return Err("Cancelled".to_string());
+                }
+                dates.push(taken_at(path));
+                context.set_progress((index + 1) as f32 / paths.len() as f32);
+            }
+            let found = dates.iter().flatten().count();
+            let _ = tx.send(dates);
+            Ok(format!("Found {found} of {}", paths.len()))
+        });
+        self.dates_rx = Some(rx);
+    }
+
+    /// Picks up the capture times. Returns `true` while they are still being read.
+    pub fn poll(&mut self) -> bool {
+        let Some(rx) = self.dates_rx.as_ref() else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(dates) => {
+                self.dates = Some(dates);
+                self.dates_rx = None;
+                false
+            }
+            Err(crossbeam_channel::TryRecvError::Empty) => true,
+            Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                self.dates = Some(vec![None; self.paths.len()]);
+                self.dates_rx = None;
+                false
+            }
+        }
+    }
+
+    /// The rename plan for the current settings; `Ok(None)` while dates are loading.
+    pub fn plan(&self) -> Result<Option<Vec<(PathBuf, PathBuf)>>, String> {
+        if template_uses_dates(&self.template) && self.dates.is_none() {
+            return Ok(None);
+        }
+        plan(
+            &self.paths,
+            &self.template,
+            self.start,
+            self.padding,
+            self.dates.as_deref(),
+        )
+        .map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{exif_taken_at, plan, render_name, TakenAt};
+    use std::path::{Path, PathBuf};
+
+    fn exif_jpeg(date: &[u8; 20]) -> Vec<u8> {
+        // Little-endian TIFF: IFD0 with one entry pointing at an Exif IFD holding
+        // DateTimeOriginal.
+        let mut tiff = Vec::new();
+        tiff.extend_from_slice(b"II\x2a\x00");
+        tiff.extend_from_slice(&8u32.to_le_bytes());
+        tiff.extend_from_slice(&1u16.to_le_bytes());
+        tiff.extend_from_slice(&0x8769u16.to_le_bytes());
+        tiff.extend_from_slice(&4u16.to_le_bytes());
+        tiff.extend_from_slice(&1u32.to_le_bytes());
+        tiff.extend_from_slice(&26u32.to_le_bytes());
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+        tiff.extend_from_slice(&1u16.to_le_bytes());
+        tiff.extend_from_slice(&0x9003u16.to_le_bytes());
+        tiff.extend_from_slice(&2u16.to_le_bytes());
+        tiff.extend_from_slice(&20u32.to_le_bytes());
+        tiff.extend_from_slice(&44u32.to_le_bytes());
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+        tiff.extend_from_slice(date);
+
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        jpeg.extend_from_slice(&((tiff.len() + 8) as u16).to_be_bytes());
+        jpeg.extend_from_slice(b"Exif\0\0");
+        jpeg.extend_from_slice(&tiff);
+        jpeg.extend_from_slice(&[0xFF, 0xDA, 0x00, 0x02]);
+        jpeg
+    }
+
+    #[test]
+    fn reads_date_time_original_from_jpeg_exif() {
+        let taken = exif_taken_at(&exif_jpeg(b"2023:07:14 18:05:09\0")).unwrap();
+        assert_eq!(taken.date(), "2023-07-14");
+        assert_eq!(taken.time(), "180509");
+        assert!(exif_taken_at(&exif_jpeg(b"0000:00:00 00:00:00\0")).is_none());
+        assert!(exif_taken_at(b"not an image").is_none());
+    }
+
+    #[test]
+    fn plan_renders_templates_and_rejects_duplicates() {
+        let taken = TakenAt {
+            year: 2024,
+            month: 2,
+            day: 29,
+            hour: 7,
+            minute: 0,
+            second: 1,
+        };
+        assert_eq!(
+            render_name(
+                "trip_{date}_{n}",
+                Path::new("/p/IMG_1.JPG"),
+                8,
+                1,
+                3,
+                Some(&taken)
+            ),
+            "trip_2024-02-29_009.JPG"
+        );
+
+        let paths: Vec<PathBuf> = ["/p/a.png", "/p/b.png", "/p/c.jpg"]
+            .map(PathBuf::from)
+            .into();
+        let renames = plan(&paths, "{n}_{name}", 1, 2, None).unwrap();
+        assert_eq!(renames[0], (paths[0].clone(), PathBuf::from("/p/01_a.png")));
+        assert_eq!(renames.len(), 3);
+
+        // Already-matching names are skipped rather than renamed onto themselves.
+        let renames = plan(&paths, "{name}", 1, 0, None).unwrap();
+        assert!(renames.is_empty());
+
+        assert!(plan(&paths, "holiday", 1, 0, None).is_err());
+        assert!(plan(&paths, "", 1, 0, None).is_err());
+    }
+}
@@ -0,0 +1,176 @@
+//! Command palette: every action in one searchable list, run from the keyboard.
+//!
+//! Actions that are rarely used do not need a key of their own; typing a few letters of
+//! the name finds them. Each row shows the bound shortcuts so the palette also teaches them.
+
+use crate::config::Action;
+
+/// One palette row.
+pub struct PaletteEntry {
+    pub action: Action,
+    pub title: String,
+    pub detail: String,
+    /// Bound shortcuts as shown in the help, or empty when unbound.
+    pub shortcut: String,
+}
+
+/// Actions driven by pointer drags or held keys, which do nothing when run once.
+pub fn is_runnable(action: Action) -> bool {
+    !matches!(
+        action,
+        Action::CommandPalette
+            | Action::Pan
+            | Action::SelectArea
+            | Action::FreehandAutoscroll
+            | Action::Magnifier
+            | Action::ShowOriginal
+            | Action::FreeRotate
+            | Action::Minimize
+            | Action::Close
+            | Action::MangaPan
+            | Action::MangaGotoFile
+            | Action::MangaFreehandAutoscroll
+            | Action::MangaScrollUp
+            | Action::MangaScrollDown
+            | Action::MasonryPan
+            | Action::MasonryGotoFile
+            | Action::MasonryFreehandAutoscroll
+            | Action::MasonryScrollUp
+            | Action::MasonryScrollDown
+    )
+}
+
+/// Scores `text` against `query` when every query character appears in it in order,
+/// ignoring case and spaces in the query. Runs of adjacent characters and matches at
+/// word starts score higher, so "fw" ranks "Fit Width" above names that merely contain both.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous_match: Option<usize> = None;
+    for wanted in query.chars().filter(|ch| !ch.is_whitespace()) {
+        let wanted = wanted.to_lowercase().next().unwrap_or(wanted);
+        let found = (position..text.len())
+            .find(|&index| text[index].to_lowercase().next() == Some(wanted))?;
+        let word_start = found == 0
+            || !text[found - 1].is_alphanumeric()
+            || (text[found].is_uppercase() && text[found - 1].is_lowercase());
+        score += 1;
+        if word_start {
+            score += 8;
+        }
+        if previous_match.is_some_and(|previous| previous + 1 == found) {
+            score += 5;
+        }
+        score -= (found - position).min(10) as i32;
+        previous_match = Some(found);
+        position = found + 1;
+    }
+    Some(score)
+}
+
+/// The open palette: its rows, the search text and the highlighted match.
+pub struct CommandPalette {
+    pub entries: Vec<PaletteEntry>,
+    pub query: String,
+    /// Index into [`CommandPalette::matches`].
+    pub selected: usize,
+    /// Set when the selection moved by keyboard, so the list scrolls to it.
+    pub scroll_to_selected: bool,
+}
+
+impl CommandPalette {
+    pub fn new(entries: Vec<PaletteEntry>) -> Self {
+        Self {
+            entries,
+            query: String::new(),
+            selected: 0,
+            scroll_to_selected: false,
+        }
+    }
+
+    /// Indices of the entries matching the query, best first. The title counts fully; the
+    /// config.ini name and the description count for less, so typing either still works.
+    pub fn matches(&self) -> Vec<usize> {
+        let query = self.query.trim();
+        if query.is_empty() {
+            return (0..self.entries.len()).collect();
+        }
+        let mut scored: Vec<(usize, i32)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                let score = [
+                    fuzzy_score(query, &entry.title),
+                    fuzzy_score(query, entry.action.as_str()).map(|score| score - 2),
+                    fuzzy_score(query, &entry.detail).map(|score| score - 20),
+                ]
+                .into_iter()
+                .flatten()
+                .max()?;
+                Some((index, score))
+            })
+            .collect();
+        scored.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(index, _)| index).collect()
+    }
+
+    /// Moves the highlight by `step` rows within `count` matches, wrapping at the ends.
+    pub fn move_selection(&mut self, step: isize, count: usize) {
+        if count == 0 {
+            self.selected = 0;
+            return;
+        }
+        let selected = self.selected.min(count - 1) as isize;
+        self.selected = (selected + step).rem_euclid(count as isize) as usize;
+        self.scroll_to_selected = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fuzzy_score, CommandPalette, PaletteEntry};
+    use crate::config::Action;
+
+    fn entry(action: Action, title: &str) -> PaletteEntry {
+        PaletteEntry {
+            action,
+            title: title.to_string(),
+            detail: String::new(),
+            shortcut: String::new(),
+        }
+    }
+
+    #[test]
+    fn fuzzy_score_prefers_word_starts_and_runs() {
+        assert_eq!(fuzzy_score("xyz", "Fit Width"), None);
+        assert_eq!(fuzzy_score("wf", "Fit Width"), None);
+        assert!(fuzzy_score("FW", "fit width").is_some());
+        let fit_width = fuzzy_score("fw", "Fit Width").unwrap();
+        let flip = fuzzy_score("fw", "Flip vertically with the keyboard").unwrap();
+        assert!(fit_width > flip);
+        let run = fuzzy_score("zoom", "Zoom in").unwrap();
+        let scattered = fuzzy_score("zoom", "Zero out of memory").unwrap();
+        assert!(run > scattered);
+    }
+
+    #[test]
+    fn matches_rank_titles_and_wrap_the_selection() {
+        let mut palette = CommandPalette::new(vec![
+            entry(Action::FlipHorizontally, "Flip horizontally"),
+            entry(Action::FitWidth, "Fit width"),
+            entry(Action::ExportPdf, "Export PDF"),
+        ]);
+        assert_eq!(palette.matches(), [0, 1, 2]);
+        palette.query = "fit w".to_string();
+        assert_eq!(palette.matches(), [1]);
+        palette.query = "export_pdf".to_string();
+        assert_eq!(palette.matches(), [2]);
+
+        palette.move_selection(-1, 3);
+        assert_eq!(palette.selected, 2);
+        palette.move_selection(1, 3);
+        assert_eq!(palette.selected, 0);
+    }
+}
@@ -0,0 +1,255 @@
+//! Compare mode: two images side by side, as an A/B wipe, or as a difference highlight.
+//!
+//! Both images are decoded on a worker thread. Image B is resized to image A's dimensions so
+//! the wipe and the difference line up pixel for pixel; the side-by-side layout shows both at
+//! their own aspect ratio. Zoom and pan are shared by both panes.
+
+use std::path::{Path, PathBuf};
+
+use image::imageops::FilterType;
+
+use crate::image_loader::LoadedImage;
+use crate::image_resize::resize_rgba;
+
+/// Channel differences at or below this are treated as compression noise.
+pub const DIFFERENCE_THRESHOLD: u8 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareLayout {
+    SideBySide,
+    Wipe,
+    Difference,
+}
+
+impl CompareLayout {
+    pub const ALL: [CompareLayout; 3] = [
+        CompareLayout::SideBySide,
+        CompareLayout::Wipe,
+        CompareLayout::Difference,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CompareLayout::SideBySide => "Side by side",
+            CompareLayout::Wipe => "Wipe",
+            CompareLayout::Difference => "Difference",
+        }
+    }
+}
+
+/// Straight RGBA8 pixels with their dimensions.
+pub struct CompareImage {
+    pub pixels: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Decoded pair plus the difference image, as produced by [`load_pair`].
+pub struct ComparePair {
+    pub a: CompareImage,
+    pub b: CompareImage,
+    pub difference: CompareImage,
+    /// Share of pixels whose difference exceeds [`DIFFERENCE_THRESHOLD`].
+    pub differing_fraction: f32,
+}
+
+/// Highlights changed pixels in red over a dimmed grayscale copy of `a`. Both buffers must
+/// have the same length. Returns the highlight and the share of differing pixels.
+pub fn difference_highlight(a: &[u8], b: &[u8]) -> (Vec<u8>, f32) {
+    let mut out = Vec::with_capacity(a.len());
+    let mut differing = 0usize;
+    let mut total = 0usize;
+    for (pa, pb) in a.chunks_exact(4).zip(b.chunks_exact(4)) {
+        total += 1;
+        let delta = (0..4).map(|i| pa[i].abs_diff(pb[i])).max().unwrap_or(0);
+        let luma = (0.2126 * pa[0] as f32 + 0.7152 * pa[1] as f32 + 0.0722 * pa[2] as f32) * 0.3;
+        if delta <= DIFFERENCE_THRESHOLD {
+            let gray = luma.round() as u8;
+            out.extend_from_slice(&[gray, gray, gray, 255]);
+            continue;
+        }
+        differing += 1;
+        // Small differences still need to stand out, so amplify them before blending.
+        let strength = (delta as f32 * 4.0 / 255.0).clamp(0.35, 1.0);
+        let mix = |base: f32, highlight: f32| (base + (highlight - base) * strength).round() as u8;
+        out.extend_from_slice(&[mix(luma, 255.0), mix(luma, 40.0), mix(luma, 40.0), 255]);
+    }
+    let fraction = if total == 0 {
+        0.0
+    } else {
+        differing as f32 / total as f32
+    };
+    (out, fraction)
+}
+
+fn decode(path: &Path, max_texture_side: u32, filter: FilterType) -> Result<CompareImage, String> {
+    let image =
+        LoadedImage::load_with_max_texture_side(path, Some(max_texture_side), filter, filter)?;
+    let frame = image.current_frame_data();
+    Ok(CompareImage {
+        pixels: frame.pixels.clone(),
+        width: frame.width,
+        height: frame.height,
+    })
+}
+
+/// Decodes both images (first frame of animations) and builds the difference image.
+pub fn load_pair(
+    a: &Path,
+    b: &Path,
+    max_texture_side: u32,
+    filter: FilterType,
+) -> Result<ComparePair, String> {
+    let a = decode(a, max_texture_side, filter)?;
+    let mut b = decode(b, max_texture_side, filter)?;
+    if (b.width, b.height) != (a.width, a.height) {
+        b.pixels = resize_rgba(b.width, b.height, &b.pixels, a.width, a.height, filter)?;
+        b.width = a.width;
+        b.height = a.height;
+    }
+    let (pixels, differing_fraction) = difference_highlight(&a.pixels, &b.pixels);
+    let difference = CompareImage {
+        pixels,
+        width: a.width,
+        height: a.height,
+    };
+    Ok(ComparePair {
+        a,
+        b,
+        difference,
+        differing_fraction,
+    })
+}
+
+#[derive(Clone)]
+pub struct CompareTextures {
+    pub a: egui::TextureHandle,
+    pub b: egui::TextureHandle,
+    pub difference: egui::TextureHandle,
+    pub differing_fraction: f32,
+}
+
+pub enum CompareState {
+    Loading(crossbeam_channel::Receiver<Result<ComparePair, String>>),
+    Ready(CompareTextures),
+    Failed(String),
+}
+
+/// Compare mode state while it is open.
+pub struct CompareView {
+    pub a: PathBuf,
+    pub b: PathBuf,
+    pub layout: CompareLayout,
+    /// Wipe divider position as a fraction of the image width.
+    pub wipe: f32,
+    /// Zoom on top of fit-to-pane, shared by both panes.
+    pub zoom: f32,
+    /// Pan from the pane center in screen points, shared by both panes.
+    pub offset: egui::Vec2,
+    pub state: CompareState,
+}
+
+impl CompareView {
+    pub fn new(a: PathBuf, b: PathBuf, state: CompareState) -> Self {
+        Self {
+            a,
+            b,
+            layout: CompareLayout::SideBySide,
+            wipe: 0.5,
+            zoom: 1.0,
+            offset: egui::Vec2::ZERO,
+            state,
+        }
+    }
+
+    /// Uploads the decoded pair once the worker is done. Returns `true` while still loading.
+    pub fn poll(&mut self, ctx: &egui::Context) -> bool {
+        let CompareState::Loading(rx) = &self.state else {
+            return false;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(crossbeam_channel::TryRecvError::Empty) => return true,
+            Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                Err("Failed to load the images".to_string())
+            }
+        };
+        self.state = match result {
+            Ok(pair) => {
+                let upload = |name: &str, image: &CompareImage| {
+                    ctx.load_texture(
+                        name,
+                        egui::ColorImage::from_rgba_unmultiplied(
+                            [image.width as usize, image.height as usize],
+                            &image.pixels,
+                        ),
+                        egui::TextureOptions::LINEAR,
+                    )
+                };
+                CompareState::Ready(CompareTextures {
+                    a: upload("compare_a", &pair.a),
+                    b: upload("compare_b", &pair.b),
+                    difference: upload("compare_difference", &pair.difference),
+                    differing_fraction: pair.differing_fraction,
+                })
+            }
+            Err(err) => CompareState::Failed(err),
+        };
+        false
+    }
+
+    /// Screen rect of an image of `size` fitted into `pane`, after the shared zoom and pan.
+    pub fn image_rect(&self, pane: egui::Rect, size: egui::Vec2) -> egui::Rect {
+        let fit = (pane.width() / size.x).min(pane.height() / size.y);
+        egui::Rect::from_center_size(pane.center() + self.offset, size * fit * self.zoom)
+    }
+
+    /// Zooms by `factor` keeping the point under `pointer` in `pane` fixed.
+    pub fn zoom_at(&mut self, pane: egui::Rect, pointer: egui::Pos2, factor: f32) {
+        let new_zoom = (self.zoom * factor).clamp(0.1, 64.0);
+        let factor = new_zoom / self.zoom;
+        let image_center = pane.center() + self.offset;
+        self.offset = pointer + (image_center - pointer) * factor - pane.center();
+        self.zoom = new_zoom;
+    }
+
+    pub fn reset_view(&mut self) {
+        self.zoom = 1.0;
+        self.offset = egui::Vec2::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{difference_highlight, CompareState, CompareView};
+
+    #[test]
+    fn difference_ignores_noise_and_counts_changed_pixels() {
+        let a = [100, 100, 100, 255, 50, 60, 70, 255];
+        let b = [102, 99, 100, 255, 200, 60, 70, 255];
+        let (out, fraction) = difference_highlight(&a, &b);
+        assert_eq!(fraction, 0.5);
+        // Unchanged pixel: dimmed gray.
+        assert_eq!(out[0], out[1]);
+        assert_eq!(out[1], out[2]);
+        // Changed pixel: red dominates.
+        assert!(out[4] > 200 && out[5] < 80 && out[6] < 80);
+    }
+
+    #[test]
+    fn zoom_at_keeps_the_pointer_fixed() {
+        let (_tx, rx) = crossbeam_channel::bounded(1);
+        let mut view = CompareView::new("a.png".into(), "b.png".into(), CompareState::Loading(rx));
+        let pane = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(200.0, 100.0));
+        let size = egui::vec2(100.0, 100.0);
+        let pointer = egui::pos2(120.0, 30.0);
+        let before = view.image_rect(pane, size);
+        let uv = (pointer - before.min) / before.size();
+
+        view.zoom_at(pane, pointer, 2.0);
+        let after = view.image_rect(pane, size);
+        let moved = after.min + uv * after.size();
+        assert!((moved - pointer).length() < 1e-3);
+        assert_eq!(view.zoom, 2.0);
+    }
+}
@@ -6,13 +6,24 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
-use crate::app_dirs;
+use crate::i18n::Language;
+use crate::monitors::SavedPlacement;
+use crate::mouse_gestures::MouseGesture;
+use crate::remote_control;
+use crate::storage;
+use crate::transition::{TransitionEasing, TransitionEffect};
 use crate::video_player::cuda_acceleration_available;
+use crate::window_snap::{self, SizePreset};
 
 const DEFAULT_CONFIG_TEMPLATE: &str = include_str!("../assets/config.ini");
 const CONFIG_FILE_NAME: &str = "config.ini";
 const LEGACY_CONFIG_FILE_NAME: &str = "rust-image-viewer-config.ini";
 const LEGACY_SETTINGS_FILE_NAME: &str = "setting.ini";
+/// Length of the Recent Files list in the tray menu.
+pub const MAX_RECENT_FILES: usize = 10;
+/// Length of the recent LUT list in the adjustments panel.
+pub const MAX_RECENT_LUTS: usize = 8;
+const DEFAULT_WINDOW_SIZE_PRESETS: &str = "1280x720, 100%, 50%";
 
 fn default_config_ini() -> &'static str {
     DEFAULT_CONFIG_TEMPLATE
@@ -143,6 +154,34 @@ pub enum InputBinding {
     KeyWithCtrl(egui::Key),
     KeyWithShift(egui::Key),
     KeyWithAlt(egui::Key),
+    KeyWithCtrlShift(egui::Key),
+}
+
+/// Media a `[Shortcuts.Image]`, `[Shortcuts.Video]` or `[Shortcuts.Manga]` section applies
+/// to. Manga covers the long strip and masonry views.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BindingContext {
+    Image,
+    Video,
+    Manga,
+}
+
+impl BindingContext {
+    pub const ALL: [BindingContext; 3] = [Self::Image, Self::Video, Self::Manga];
+
+    pub fn section_name(self) -> &'static str {
+        match self {
+            Self::Image => "Shortcuts.Image",
+            Self::Video => "Shortcuts.Video",
+            Self::Manga => "Shortcuts.Manga",
+        }
+    }
+
+    fn from_section(section: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|context| section.eq_ignore_ascii_case(context.section_name()))
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -182,12 +221,69 @@ pub enum Action {
     RotateCounterClockwise,
     PreciseRotationClockwise,
     PreciseRotationCounterClockwise,
+    FreeRotate,
     FlipVertically,
     FlipHorizontally,
+    SaveOrientedCopy,
+    ToggleAnnotations,
+    ToggleAdjustments,
+    ToggleCheckerboard,
+    ToggleThirds,
+    ToggleGrid,
+    ToggleCrosshair,
+    Straighten,
+    Perspective,
+    ToggleEyedropper,
+    Magnifier,
+    ShowOriginal,
+    StereoExport,
+    ScanCleanup,
+    ExportPdf,
+    ConvertAnimation,
+    BatchRename,
+    MonitorWall,
+    CompareImages,
+    CopyViewToClipboard,
+    CycleFitMode,
+    FitWhole,
+    FitWidth,
+    FitHeight,
+    FitFill,
+    FitActualSize,
+    CycleZoomPresets,
+    Zoom25,
+    Zoom50,
+    Zoom100,
+    Zoom200,
+    Zoom400,
+    PanLeft,
+    PanRight,
+    PanUp,
+    PanDown,
+    ClearRating,
+    Rate1,
+    Rate2,
+    Rate3,
+    Rate4,
+    Rate5,
+    TogglePick,
+    ToggleReject,
+    CycleRatingFilter,
+    SortToFolder1,
+    SortToFolder2,
+    SortToFolder3,
+    SortToFolder4,
+    OpenInEditor,
+    NewWindow,
+    ToggleZoomSync,
+    UiScaleUp,
+    UiScaleDown,
+    ResetUiScale,
     ZoomIn,
     ZoomOut,
     ResetZoom,
     Exit,
+    ExitFullscreenOrQuit,
     Pan,
     SelectArea,
     FreehandAutoscroll,
@@ -195,6 +291,25 @@ pub enum Action {
     Close,
     VideoPlayPause,
     VideoMute,
+    VideoTrimIn,
+    VideoTrimOut,
+    VideoTrim,
+    OpenSettings,
+    CommandPalette,
+    FilterFolder,
+    MoveToNextMonitor,
+    SnapWindowLeft,
+    SnapWindowRight,
+    SnapWindowUp,
+    SnapWindowDown,
+    WindowSizePreset1,
+    WindowSizePreset2,
+    WindowSizePreset3,
+    ToggleAlwaysOnTop,
+    ToggleSlideshow,
+    TogglePlaylistPanel,
+    TogglePlaylistShuffle,
+    CyclePlaylistRepeat,
     // Manga reading mode
     MangaPan,
     MangaGotoFile,
@@ -209,6 +324,9 @@ pub enum Action {
     MangaScrollDown,
     MangaZoomIn,
     MangaZoomOut,
+    MangaTogglePageWidth,
+    MangaRotatePage,
+    MangaCyclePageFit,
     // Masonry mode
     MasonryPan,
     MasonryGotoFile,
@@ -226,6 +344,268 @@ pub enum Action {
 }
 
 impl Action {
+    /// Every action, in declaration order; the command palette lists these.
+    pub const ALL: [Action; 126] = [
+        Action::ToggleFullscreen,
+        Action::GotoFile,
+        Action::NextImage,
+        Action::PreviousImage,
+        Action::RotateClockwise,
+        Action::RotateCounterClockwise,
+        Action::PreciseRotationClockwise,
+        Action::PreciseRotationCounterClockwise,
+        Action::FreeRotate,
+        Action::FlipVertically,
+        Action::FlipHorizontally,
+        Action::SaveOrientedCopy,
+        Action::ToggleAnnotations,
+        Action::ToggleAdjustments,
+        Action::ToggleCheckerboard,
+        Action::ToggleThirds,
+        Action::ToggleGrid,
+        Action::ToggleCrosshair,
+        Action::Straighten,
+        Action::Perspective,
+        Action::ToggleEyedropper,
+        Action::Magnifier,
+        Action::ShowOriginal,
+        Action::StereoExport,
+        Action::ScanCleanup,
+        Action::ExportPdf,
+        Action::ConvertAnimation,
+        Action::BatchRename,
+        Action::MonitorWall,
+        Action::CompareImages,
+        Action::CopyViewToClipboard,
+        Action::CycleFitMode,
+        Action::FitWhole,
+        Action::FitWidth,
+        Action::FitHeight,
+        Action::FitFill,
+        Action::FitActualSize,
+        Action::CycleZoomPresets,
+        Action::Zoom25,
+        Action::Zoom50,
+        Action::Zoom100,
+        Action::Zoom200,
+        Action::Zoom400,
+        Action::PanLeft,
+        Action::PanRight,
+        Action::PanUp,
+        Action::PanDown,
+        Action::ClearRating,
+        Action::Rate1,
+        Action::Rate2,
+        Action::Rate3,
+        Action::Rate4,
+        Action::Rate5,
+        Action::TogglePick,
+        Action::ToggleReject,
+        Action::CycleRatingFilter,
+        Action::SortToFolder1,
+        Action::SortToFolder2,
+        Action::SortToFolder3,
+        Action::SortToFolder4,
+        Action::OpenInEditor,
+        Action::NewWindow,
+        Action::ToggleZoomSync,
+        Action::UiScaleUp,
+        Action::UiScaleDown,
+        Action::ResetUiScale,
+        Action::ZoomIn,
+        Action::ZoomOut,
+        Action::ResetZoom,
+        Action::Exit,
+        Action::ExitFullscreenOrQuit,
+        Action::Pan,
+        Action::SelectArea,
+        Action::FreehandAutoscroll,
+        Action::Minimize,
+        Action::Close,
+        Action::VideoPlayPause,
+        Action::VideoMute,
+        Action::VideoTrimIn,
+        Action::VideoTrimOut,
+        Action::VideoTrim,
+        Action::OpenSettings,
+        Action::CommandPalette,
+        Action::FilterFolder,
+        Action::MoveToNextMonitor,
+        Action::SnapWindowLeft,
+        Action::SnapWindowRight,
+        Action::SnapWindowUp,
+        Action::SnapWindowDown,
+        Action::WindowSizePreset1,
+        Action::WindowSizePreset2,
+        Action::WindowSizePreset3,
+        Action::ToggleAlwaysOnTop,
+        Action::ToggleSlideshow,
+        Action::TogglePlaylistPanel,
+        Action::TogglePlaylistShuffle,
+        Action::CyclePlaylistRepeat,
+        Action::MangaPan,
+        Action::MangaGotoFile,
+        Action::MangaFreehandAutoscroll,
+        Action::MangaPanUp,
+        Action::MangaPanDown,
+        Action::MangaNextImageFit,
+        Action::MangaPreviousImageFit,
+        Action::MangaNextImage,
+        Action::MangaPreviousImage,
+        Action::MangaScrollUp,
+        Action::MangaScrollDown,
+        Action::MangaZoomIn,
+        Action::MangaZoomOut,
+        Action::MangaTogglePageWidth,
+        Action::MangaRotatePage,
+        Action::MangaCyclePageFit,
+        Action::MasonryPan,
+        Action::MasonryGotoFile,
+        Action::MasonryFreehandAutoscroll,
+        Action::MasonryPanUp,
+        Action::MasonryPanDown,
+        Action::MasonryPanUp2,
+        Action::MasonryPanDown2,
+        Action::MasonryPanUp3,
+        Action::MasonryPanDown3,
+        Action::MasonryScrollUp,
+        Action::MasonryScrollDown,
+        Action::MasonryZoomIn,
+        Action::MasonryZoomOut,
+    ];
+
+    /// Canonical config.ini name, as accepted by [`Action::from_str`].
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Action::ToggleFullscreen => "toggle_fullscreen",
+            Action::GotoFile => "goto_file",
+            Action::NextImage => "next_image",
+            Action::PreviousImage => "previous_image",
+            Action::RotateClockwise => "rotate_clockwise",
+            Action::RotateCounterClockwise => "rotate_counterclockwise",
+            Action::PreciseRotationClockwise => "precise_rotation_clockwise",
+            Action::PreciseRotationCounterClockwise => "precise_rotation_counterclockwise",
+            Action::FreeRotate => "free_rotate",
+            Action::FlipVertically => "flip_vertically",
+            Action::FlipHorizontally => "flip_horizontally",
+            Action::SaveOrientedCopy => "save_oriented_copy",
+            Action::ToggleAnnotations => "toggle_annotations",
+            Action::ToggleAdjustments => "toggle_adjustments",
+            Action::ToggleCheckerboard => "toggle_checkerboard",
+            Action::ToggleThirds => "toggle_thirds",
+            Action::ToggleGrid => "toggle_grid",
+            Action::ToggleCrosshair => "toggle_crosshair",
+            Action::Straighten => "straighten",
+            Action::Perspective => "perspective",
+            Action::ToggleEyedropper => "toggle_eyedropper",
+            Action::Magnifier => "magnifier",
+            Action::ShowOriginal => "show_original",
+            Action::StereoExport => "stereo_export",
+            Action::ScanCleanup => "scan_cleanup",
+            Action::ExportPdf => "export_pdf",
+            Action::ConvertAnimation => "convert_animation",
+            Action::BatchRename => "batch_rename",
+            Action::MonitorWall => "monitor_wall",
+            Action::CompareImages => "compare_images",
+            Action::CopyViewToClipboard => "copy_view",
+            Action::CycleFitMode => "cycle_fit_mode",
+            Action::FitWhole => "fit_whole",
+            Action::FitWidth => "fit_width",
+            Action::FitHeight => "fit_height",
+            Action::FitFill => "fit_fill",
+            Action::FitActualSize => "fit_actual_size",
+            Action::CycleZoomPresets => "cycle_zoom_presets",
+            Action::Zoom25 => "zoom_25",
+            Action::Zoom50 => "zoom_50",
+            Action::Zoom100 => "zoom_100",
+            Action::Zoom200 => "zoom_200",
+            Action::Zoom400 => "zoom_400",
+            Action::PanLeft => "pan_left",
+            Action::PanRight => "pan_right",
+            Action::PanUp => "pan_up",
+            Action::PanDown => "pan_down",
+            Action::ClearRating => "clear_rating",
+            Action::Rate1 => "rate_1",
+            Action::Rate2 => "rate_2",
+            Action::Rate3 => "rate_3",
+            Action::Rate4 => "rate_4",
+            Action::Rate5 => "rate_5",
+            Action::TogglePick => "toggle_pick",
+            Action::ToggleReject => "toggle_reject",
+            Action::CycleRatingFilter => "cycle_rating_filter",
+            Action::SortToFolder1 => "sort_to_folder_1",
+            Action::SortToFolder2 => "sort_to_folder_2",
+            Action::SortToFolder3 => "sort_to_folder_3",
+            Action::SortToFolder4 => "sort_to_folder_4",
+            Action::OpenInEditor => "open_in_editor",
+            Action::NewWindow => "new_window",
+            Action::ToggleZoomSync => "toggle_zoom_sync",
+            Action::UiScaleUp => "ui_scale_up",
+            Action::UiScaleDown => "ui_scale_down",
+            Action::ResetUiScale => "reset_ui_scale",
+            Action::ZoomIn => "zoom_in",
+            Action::ZoomOut => "zoom_out",
+            Action::ResetZoom => "reset_zoom",
+            Action::Exit => "exit",
+            Action::ExitFullscreenOrQuit => "exit_fullscreen_or_quit",
+            Action::Pan => "pan",
+            Action::SelectArea => "select_area",
+            Action::FreehandAutoscroll => "freehand_autoscroll",
+            Action::Minimize => "minimize",
+            Action::Close => "close",
+            Action::VideoPlayPause => "video_play_pause",
+            Action::VideoMute => "video_mute",
+            Action::VideoTrimIn => "video_trim_in",
+            Action::VideoTrimOut => "video_trim_out",
+            Action::VideoTrim => "video_trim",
+            Action::OpenSettings => "open_settings",
+            Action::CommandPalette => "command_palette",
+            Action::FilterFolder => "filter_folder",
+            Action::MoveToNextMonitor => "move_to_next_monitor",
+            Action::SnapWindowLeft => "snap_window_left",
+            Action::SnapWindowRight => "snap_window_right",
+            Action::SnapWindowUp => "snap_window_up",
+            Action::SnapWindowDown => "snap_window_down",
+            Action::WindowSizePreset1 => "window_size_preset_1",
+            Action::WindowSizePreset2 => "window_size_preset_2",
+            Action::WindowSizePreset3 => "window_size_preset_3",
+            Action::ToggleAlwaysOnTop => "toggle_always_on_top",
+            Action::ToggleSlideshow => "toggle_slideshow",
+            Action::TogglePlaylistPanel => "toggle_playlist",
+            Action::TogglePlaylistShuffle => "toggle_shuffle",
+            Action::CyclePlaylistRepeat => "cycle_repeat",
+            Action::MangaPan => "manga_pan",
+            Action::MangaGotoFile => "manga_goto_file",
+            Action::MangaFreehandAutoscroll => "manga_freehand_autoscroll",
+            Action::MangaPanUp => "manga_pan_up",
+            Action::MangaPanDown => "manga_pan_down",
+            Action::MangaNextImageFit => "manga_next_image_fit",
+            Action::MangaPreviousImageFit => "manga_previous_image_fit",
+            Action::MangaNextImage => "manga_next_image",
+            Action::MangaPreviousImage => "manga_previous_image",
+            Action::MangaScrollUp => "manga_scroll_up",
+            Action::MangaScrollDown => "manga_scroll_down",
+            Action::MangaZoomIn => "manga_zoom_in",
+            Action::MangaZoomOut => "manga_zoom_out",
+            Action::MangaTogglePageWidth => "manga_toggle_page_width",
+            Action::MangaRotatePage => "manga_rotate_page",
+            Action::MangaCyclePageFit => "manga_cycle_page_fit",
+            Action::MasonryPan => "masonry_pan",
+            Action::MasonryGotoFile => "masonry_goto_file",
+            Action::MasonryFreehandAutoscroll => "masonry_freehand_autoscroll",
+            Action::MasonryPanUp => "masonry_pan_up",
+            Action::MasonryPanDown => "masonry_pan_down",
+            Action::MasonryPanUp2 => "masonry_pan_up_2",
+            Action::MasonryPanDown2 => "masonry_pan_down_2",
+            Action::MasonryPanUp3 => "masonry_pan_up_3",
+            Action::MasonryPanDown3 => "masonry_pan_down_3",
+            Action::MasonryScrollUp => "masonry_scroll_up",
+            Action::MasonryScrollDown => "masonry_scroll_down",
+            Action::MasonryZoomIn => "masonry_zoom_in",
+            Action::MasonryZoomOut => "masonry_zoom_out",
+        }
+    }
+
     pub fn from_str(s: &str) -> Option<Action> {
         match s.to_lowercase().as_str() {
             "toggle_fullscreen" | "fullscreen" => Some(Action::ToggleFullscreen),
@@ -240,12 +620,81 @@ impl Action {
             "precise_rotation_counterclockwise"
             | "precise_rotate_counterclockwise"
             | "precise_rotate_ccw" => Some(Action::PreciseRotationCounterClockwise),
+            "free_rotate" | "rotate_drag" | "drag_rotate" => Some(Action::FreeRotate),
             "flip_vertically" | "flip_vertical" => Some(Action::FlipVertically),
             "flip_horizontally" | "flip_horizontal" => Some(Action::FlipHorizontally),
+            "save_oriented_copy" | "save_oriented" | "export_oriented" => {
+                Some(Action::SaveOrientedCopy)
+            }
+            "toggle_annotations" | "annotate" | "annotation_mode" => {
+                Some(Action::ToggleAnnotations)
+            }
+            "toggle_adjustments" | "adjustments" | "image_adjustments" => {
+                Some(Action::ToggleAdjustments)
+            }
+            "toggle_checkerboard" | "transparency_grid" => Some(Action::ToggleCheckerboard),
+            "toggle_thirds" | "rule_of_thirds" | "thirds" => Some(Action::ToggleThirds),
+            "toggle_grid" | "grid" | "grid_overlay" => Some(Action::ToggleGrid),
+            "toggle_crosshair" | "crosshair" | "center_crosshair" => Some(Action::ToggleCrosshair),
+            "straighten" | "toggle_straighten" | "straighten_tool" => Some(Action::Straighten),
+            "perspective" | "perspective_correction" | "keystone" => Some(Action::Perspective),
+            "toggle_eyedropper" | "eyedropper" | "color_picker" => Some(Action::ToggleEyedropper),
+            "magnifier" | "loupe" | "hold_magnifier" => Some(Action::Magnifier),
+            "show_original" | "before_after" | "hold_original" => Some(Action::ShowOriginal),
+            "stereo_export" | "export_stereo" => Some(Action::StereoExport),
+            "scan_cleanup" | "clean_scan" | "document_cleanup" => Some(Action::ScanCleanup),
+            "export_pdf" | "pdf_export" | "batch_pdf" => Some(Action::ExportPdf),
+            "convert_animation" | "export_animation" | "gif_to_webp" => {
+                Some(Action::ConvertAnimation)
+            }
+            "batch_rename" | "rename_folder" | "bulk_rename" => Some(Action::BatchRename),
+            "monitor_wall" | "live_wall" | "watch_folder" => Some(Action::MonitorWall),
+            "cycle_fit_mode" | "fit_mode" | "next_fit_mode" => Some(Action::CycleFitMode),
+            "fit_whole" | "fit_to_screen" | "fit_image" => Some(Action::FitWhole),
+            "fit_width" => Some(Action::FitWidth),
+            "fit_height" => Some(Action::FitHeight),
+            "fit_fill" | "fill" | "fill_crop" => Some(Action::FitFill),
+            "fit_actual_size" | "actual_size" | "fit_100" => Some(Action::FitActualSize),
+            "cycle_zoom_presets" | "zoom_presets" | "cycle_zoom" => Some(Action::CycleZoomPresets),
+            "zoom_25" | "zoom_25_percent" => Some(Action::Zoom25),
+            "zoom_50" | "zoom_50_percent" => Some(Action::Zoom50),
+            "zoom_100" | "zoom_100_percent" => Some(Action::Zoom100),
+            "zoom_200" | "zoom_200_percent" => Some(Action::Zoom200),
+            "zoom_400" | "zoom_400_percent" => Some(Action::Zoom400),
+            "pan_left" | "keyboard_pan_left" => Some(Action::PanLeft),
+            "pan_right" | "keyboard_pan_right" => Some(Action::PanRight),
+            "pan_up" | "keyboard_pan_up" => Some(Action::PanUp),
+            "pan_down" | "keyboard_pan_down" => Some(Action::PanDown),
+            "clear_rating" | "rate_0" | "unrate" => Some(Action::ClearRating),
+            "rate_1" | "rating_1" => Some(Action::Rate1),
+            "rate_2" | "rating_2" => Some(Action::Rate2),
+            "rate_3" | "rating_3" => Some(Action::Rate3),
+            "rate_4" | "rating_4" => Some(Action::Rate4),
+            "rate_5" | "rating_5" => Some(Action::Rate5),
+            "toggle_pick" | "pick" | "flag_pick" => Some(Action::TogglePick),
+            "toggle_reject" | "reject" | "flag_reject" => Some(Action::ToggleReject),
+            "cycle_rating_filter" | "rating_filter" => Some(Action::CycleRatingFilter),
+            "sort_to_folder_1" | "sort_1" => Some(Action::SortToFolder1),
+            "sort_to_folder_2" | "sort_2" => Some(Action::SortToFolder2),
+            "sort_to_folder_3" | "sort_3" => Some(Action::SortToFolder3),
+            "sort_to_folder_4" | "sort_4" => Some(Action::SortToFolder4),
+            "open_in_editor" | "edit_externally" | "external_editor" => Some(Action::OpenInEditor),
+            "compare_images" | "compare" | "compare_mode" => Some(Action::CompareImages),
+            "copy_view" | "copy_view_to_clipboard" | "screenshot" | "copy_screenshot" => {
+                Some(Action::CopyViewToClipboard)
+            }
+            "new_window" | "open_in_new_window" => Some(Action::NewWindow),
+            "toggle_zoom_sync" | "zoom_sync" | "sync_zoom" => Some(Action::ToggleZoomSync),
+            "ui_scale_up" | "ui_zoom_in" => Some(Action::UiScaleUp),
+            "ui_scale_down" | "ui_zoom_out" => Some(Action::UiScaleDown),
+            "reset_ui_scale" | "ui_zoom_reset" => Some(Action::ResetUiScale),
             "zoom_in" => Some(Action::ZoomIn),
             "zoom_out" => Some(Action::ZoomOut),
             "reset_zoom" | "reset" => Some(Action::ResetZoom),
             "exit" | "quit" | "close_app" => Some(Action::Exit),
+            "exit_fullscreen_or_quit" | "leave_fullscreen_or_exit" => {
+                Some(Action::ExitFullscreenOrQuit)
+            }
             "pan" => Some(Action::Pan),
             "select_area" => Some(Action::SelectArea),
             "freehand_autoscroll" | "autoscroll" => Some(Action::FreehandAutoscroll),
@@ -253,6 +702,33 @@ impl Action {
             "close" => Some(Action::Close),
             "video_play_pause" | "play_pause" | "playpause" => Some(Action::VideoPlayPause),
             "video_mute" | "mute" | "toggle_mute" => Some(Action::VideoMute),
+            "video_trim_in" | "trim_in" | "mark_in" => Some(Action::VideoTrimIn),
+            "video_trim_out" | "trim_out" | "mark_out" => Some(Action::VideoTrimOut),
+            "video_trim" | "trim_video" | "export_clip" => Some(Action::VideoTrim),
+            "open_settings" | "settings" => Some(Action::OpenSettings),
+            "command_palette" | "action_palette" | "palette" => Some(Action::CommandPalette),
+            "filter_folder" | "quick_filter" | "filter" => Some(Action::FilterFolder),
+            "move_to_next_monitor" | "next_monitor" | "fullscreen_next_monitor" => {
+                Some(Action::MoveToNextMonitor)
+            }
+            "snap_window_left" | "snap_left" => Some(Action::SnapWindowLeft),
+            "snap_window_right" | "snap_right" => Some(Action::SnapWindowRight),
+            "snap_window_up" | "snap_up" => Some(Action::SnapWindowUp),
+            "snap_window_down" | "snap_down" => Some(Action::SnapWindowDown),
+            "window_size_preset_1" | "size_preset_1" => Some(Action::WindowSizePreset1),
+            "window_size_preset_2" | "size_preset_2" => Some(Action::WindowSizePreset2),
+            "window_size_preset_3" | "size_preset_3" => Some(Action::WindowSizePreset3),
+            "toggle_always_on_top" | "always_on_top" | "picture_in_picture" | "pin_window" => {
+                Some(Action::ToggleAlwaysOnTop)
+            }
+            "toggle_slideshow" | "slideshow" | "slide_show" => Some(Action::ToggleSlideshow),
+            "toggle_playlist" | "playlist" | "queue" | "toggle_queue" => {
+                Some(Action::TogglePlaylistPanel)
+            }
+            "toggle_shuffle" | "shuffle" | "playlist_shuffle" => {
+                Some(Action::TogglePlaylistShuffle)
+            }
+            "cycle_repeat" | "repeat" | "playlist_repeat" => Some(Action::CyclePlaylistRepeat),
             "manga_pan" => Some(Action::MangaPan),
             "manga_goto_file" | "manga_go_to_file" => Some(Action::MangaGotoFile),
             "manga_freehand_autoscroll" => Some(Action::MangaFreehandAutoscroll),
@@ -266,6 +742,9 @@ impl Action {
             "manga_scroll_down" => Some(Action::MangaScrollDown),
             "manga_zoom_in" | "manga_zoomin" => Some(Action::MangaZoomIn),
             "manga_zoom_out" | "manga_zoomout" => Some(Action::MangaZoomOut),
+            "manga_toggle_page_width" | "manga_page_width" => Some(Action::MangaTogglePageWidth),
+            "manga_rotate_page" | "manga_page_rotate" => Some(Action::MangaRotatePage),
+            "manga_cycle_page_fit" | "manga_page_fit" => Some(Action::MangaCyclePageFit),
             "masonry_pan" | "gallery_pan" => Some(Action::MasonryPan),
             "masonry_goto_file" | "masonry_go_to_file" | "gallery_goto_file"
             | "gallery_go_to_file" => Some(Action::MasonryGotoFile),
@@ -287,12 +766,31 @@ impl Action {
             _ => None,
         }
     }
+
+    /// Zoom factor of the `zoom_*` preset actions (1.0 = 100%).
+    pub fn preset_zoom(self) -> Option<f32> {
+        match self {
+            Action::Zoom25 => Some(0.25),
+            Action::Zoom50 => Some(0.5),
+            Action::Zoom100 => Some(1.0),
+            Action::Zoom200 => Some(2.0),
+            Action::Zoom400 => Some(4.0),
+            _ => None,
+        }
+    }
 }
 
 /// Parse an input binding from string
 pub fn parse_input_binding(s: &str) -> Option<InputBinding> {
     let s = s.trim().to_lowercase();
 
+    if let Some(key_str) = s
+        .strip_prefix("ctrl+shift+")
+        .or_else(|| s.strip_prefix("shift+ctrl+"))
+    {
+        return parse_key(key_str).map(InputBinding::KeyWithCtrlShift);
+    }
+
     // Check for modifiers with scroll wheel first (special case)
     if let Some(scroll_str) = s.strip_prefix("ctrl+") {
         match scroll_str {
@@ -337,6 +835,7 @@ fn is_strip_item_open_binding(binding: &InputBinding) -> bool {
             | InputBinding::KeyWithCtrl(_)
             | InputBinding::KeyWithShift(_)
             | InputBinding::KeyWithAlt(_)
+            | InputBinding::KeyWithCtrlShift(_)
     )
 }
 
@@ -366,6 +865,129 @@ impl VideoSeekPolicy {
     }
 }
 
+/// What a playlist does when a video ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaylistRepeat {
+    /// Move on, and stop after the last item.
+    Off,
+    /// Move on, and start over after the last item.
+    All,
+    /// Play the same item again.
+    One,
+}
+
+impl PlaylistRepeat {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "off" | "none" | "no" | "false" => Some(Self::Off),
+            "all" | "playlist" | "queue" | "true" => Some(Self::All),
+            "one" | "single" | "track" | "item" => Some(Self::One),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::All => "all",
+            Self::One => "one",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Self::Off => Self::All,
+            Self::All => Self::One,
+            Self::One => Self::Off,
+        }
+    }
+}
+
+/// When video frames are deinterlaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeinterlaceMode {
+    /// Only streams flagged as interlaced.
+    Auto,
+    /// Every stream, for interlaced material that is not flagged as such.
+    On,
+    Off,
+}
+
+impl DeinterlaceMode {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "auto" | "default" => Some(Self::Auto),
+            "on" | "true" | "yes" | "always" | "force" => Some(Self::On),
+            "off" | "false" | "no" | "never" | "disabled" => Some(Self::Off),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::On => "on",
+            Self::Off => "off",
+        }
+    }
+}
+
+/// How the trim tool writes the range between the in and out marks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrimMode {
+    /// Copy the streams as they are: fast and lossless, but cuts land on keyframes.
+    Copy,
+    /// Re-encode to H.264/AAC MP4, cutting exactly at the marks.
+    Mp4,
+}
+
+impl TrimMode {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "copy" | "lossless" | "stream_copy" | "remux" => Some(Self::Copy),
+            "mp4" | "reencode" | "re-encode" | "encode" | "h264" => Some(Self::Mp4),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Copy => "copy",
+            Self::Mp4 => "mp4",
+        }
+    }
+}
+
+/// Whether a video reopened later continues from where it was left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResumePolicy {
+    /// Continue without asking.
+    Always,
+    /// Start from the beginning and offer to jump to the saved position.
+    Ask,
+    /// Always start from the beginning and do not remember positions.
+    Never,
+}
+
+impl ResumePolicy {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "always" | "auto" | "true" | "yes" | "on" => Some(Self::Always),
+            "ask" | "prompt" | "offer" => Some(Self::Ask),
+            "never" | "off" | "false" | "no" => Some(Self::Never),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Always => "always",
+            Self::Ask => "ask",
+            Self::Never => "never",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MangaVirtualizationBackend {
     Auto,
@@ -392,6 +1014,442 @@ impl MangaVirtualizationBackend {
     }
 }
 
+/// Color scheme of the overlay UI (title bar, video bar and on-screen notices).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlayThemeMode {
+    Dark,
+    Light,
+    /// Follow the Windows app theme.
+    Auto,
+}
+
+impl OverlayThemeMode {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "dark" => Some(Self::Dark),
+            "light" => Some(Self::Light),
+            "auto" | "system" | "windows" => Some(Self::Auto),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dark => "dark",
+            Self::Light => "light",
+            Self::Auto => "auto",
+        }
+    }
+}
+
+/// Which point stays fixed when zooming with the wheel or a pinch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoomAnchor {
+    /// Keep the point under the cursor fixed.
+    Cursor,
+    /// Keep the center of the view fixed.
+    Center,
+    /// Cursor in fullscreen; in floating mode, center until zoomed past 100% or panned.
+    Smart,
+}
+
+impl ZoomAnchor {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "cursor" | "pointer" | "mouse" => Some(Self::Cursor),
+            "center" | "centre" | "centered" => Some(Self::Center),
+            "smart" | "auto" | "hybrid" | "default" => Some(Self::Smart),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cursor => "cursor",
+            Self::Center => "center",
+            Self::Smart => "smart",
+        }
+    }
+
+    /// Whether a zoom from `old_zoom` to `new_zoom` should keep the cursor point fixed
+    /// rather than the view center.
+    pub fn follows_cursor(
+        self,
+        fullscreen: bool,
+        old_zoom: f32,
+        new_zoom: f32,
+        panned: bool,
+    ) -> bool {
+        match self {
+            Self::Cursor => true,
+            Self::Center => false,
+            Self::Smart => fullscreen || old_zoom > 1.0 || new_zoom > 1.0 || panned,
+        }
+    }
+}
+
+/// One step of the `cycle_zoom_presets` sequence.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZoomPreset {
+    /// The window mode's fit mode layout.
+    Fit,
+    /// Zoom factor (1.0 = 100%).
+    Zoom(f32),
+}
+
+impl ZoomPreset {
+    pub fn from_str(s: &str) -> Option<Self> {
+        let s = s.trim().to_lowercase();
+        if matches!(s.as_str(), "fit" | "fit_mode") {
+            return Some(Self::Fit);
+        }
+        let percent = s.trim_end_matches('%').trim().parse::<f32>().ok()?;
+        (percent.is_finite() && percent > 0.0)
+            .then(|| Self::Zoom(percent.clamp(1.0, 10000.0) / 100.0))
+    }
+
+    pub fn to_config_string(self) -> String {
+        match self {
+            Self::Fit => "fit".to_string(),
+            Self::Zoom(zoom) => format!("{}", (zoom * 100.0).round()),
+        }
+    }
+
+    /// Comma-separated list; `None` when nothing in it is a valid preset.
+    pub fn parse_list(value: &str) -> Option<Vec<Self>> {
+        let presets: Vec<Self> = value.split(',').filter_map(Self::from_str).collect();
+        (!presets.is_empty()).then_some(presets)
+    }
+
+    /// The preset after the one matching `zoom`. Zoom levels that match no percentage
+    /// preset count as the fit step, so the first press from a fitted image moves on.
+    pub fn next_in(presets: &[Self], zoom: f32) -> Option<Self> {
+        let current = presets
+            .iter()
+            .position(|preset| matches!(preset, Self::Zoom(z) if (z - zoom).abs() < 0.005))
+            .or_else(|| presets.iter().position(|preset| *preset == Self::Fit));
+        match current {
+            Some(index) => presets.get((index + 1) % presets.len()).copied(),
+            None => presets.first().copied(),
+        }
+    }
+}
+
+/// Mouse button that clicks the window's click zones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClickZoneButton {
+    /// Click zones are off.
+    None,
+    Left,
+    Right,
+    Middle,
+}
+
+impl ClickZoneButton {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "none" | "off" | "disabled" => Some(Self::None),
+            "left" | "mouse_left" | "primary" => Some(Self::Left),
+            "right" | "mouse_right" | "secondary" => Some(Self::Right),
+            "middle" | "mouse_middle" => Some(Self::Middle),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Left => "left",
+            Self::Right => "right",
+            Self::Middle => "middle",
+        }
+    }
+}
+
+/// One vertical band of the window in the `click_zones` layout.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClickZone {
+    /// Width relative to the other zones.
+    pub weight: f32,
+    /// `None` keeps the button's usual click behavior inside the zone.
+    pub action: Option<Action>,
+}
+
+impl ClickZone {
+    /// `action:weight`, where the weight defaults to 1 and the action may be `none`.
+    pub fn from_str(s: &str) -> Option<Self> {
+        let (action, weight) = match s.split_once(':') {
+            Some((action, weight)) => (action, weight.trim().parse::<f32>().ok()?),
+            None => (s, 1.0),
+        };
+        if !weight.is_finite() || weight <= 0.0 {
+            return None;
+        }
+        let action = match action.trim().to_lowercase().as_str() {
+            "none" | "" => None,
+            name => Some(Action::from_str(name)?),
+        };
+        Some(Self {
+            weight: weight.min(1000.0),
+            action,
+        })
+    }
+
+    pub fn to_config_string(self) -> String {
+        format!(
+            "{}:{}",
+            self.action.map_or("none", Action::as_str),
+            format_with_optional_trailing_zero_f32(self.weight)
+        )
+    }
+
+    /// Comma-separated list, left to right; `None` when an entry is not a valid zone.
+    pub fn parse_list(value: &str) -> Option<Vec<Self>> {
+        let zones = value
+            .split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .map(Self::from_str)
+            .collect::<Option<Vec<Self>>>()?;
+        (!zones.is_empty()).then_some(zones)
+    }
+
+    /// Index of the zone under `fraction` (0.0 = left window edge, 1.0 = right edge).
+    pub fn index_at(zones: &[Self], fraction: f32) -> Option<usize> {
+        let total: f32 = zones.iter().map(|zone| zone.weight).sum();
+        if zones.is_empty() || total <= 0.0 {
+            return None;
+        }
+        let mut right_edge = 0.0;
+        for (index, zone) in zones.iter().enumerate() {
+            right_edge += zone.weight / total;
+            if fraction < right_edge {
+                return Some(index);
+            }
+        }
+        Some(zones.len() - 1)
+    }
+}
+
+/// How a solo image is sized when it is opened or its layout is reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FitMode {
+    /// Whole image visible.
+    Fit,
+    FitWidth,
+    FitHeight,
+    /// Fill the view, cropping the overflowing axis.
+    Fill,
+    /// 100%.
+    ActualSize,
+}
+
+impl FitMode {
+    pub const ALL: [FitMode; 5] = [
+        FitMode::Fit,
+        FitMode::FitWidth,
+        FitMode::FitHeight,
+        FitMode::Fill,
+        FitMode::ActualSize,
+    ];
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "fit" | "whole" | "contain" | "fit_whole" => Some(Self::Fit),
+            "width" | "fit_width" => Some(Self::FitWidth),
+            "height" | "fit_height" => Some(Self::FitHeight),
+            "fill" | "cover" | "crop" | "fill_crop" => Some(Self::Fill),
+            "actual_size" | "actual" | "original" | "100" | "100%" => Some(Self::ActualSize),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Fit => "fit",
+            Self::FitWidth => "fit_width",
+            Self::FitHeight => "fit_height",
+            Self::Fill => "fill",
+            Self::ActualSize => "actual_size",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Fit => "Fit",
+            Self::FitWidth => "Fit width",
+            Self::FitHeight => "Fit height",
+            Self::Fill => "Fill",
+            Self::ActualSize => "100%",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|mode| *mode == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Zoom that sizes `media` into `bounds` for this mode, before any clamping.
+    pub fn zoom_for(self, bounds: egui::Vec2, media: egui::Vec2) -> f32 {
+        let (fit_x, fit_y) = (bounds.x / media.x, bounds.y / media.y);
+        match self {
+            Self::Fit => fit_x.min(fit_y),
+            Self::FitWidth => fit_x,
+            Self::FitHeight => fit_y,
+            Self::Fill => fit_x.max(fit_y),
+            Self::ActualSize => 1.0,
+        }
+    }
+}
+
+/// Which items next/previous navigation visits, by rating and flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RatingFilter {
+    All,
+    /// One star or more.
+    Rated,
+    Picked,
+    Rejected,
+    /// Everything except rejects.
+    Unrejected,
+}
+
+impl RatingFilter {
+    pub const ALL: [RatingFilter; 5] = [
+        RatingFilter::All,
+        RatingFilter::Rated,
+        RatingFilter::Picked,
+        RatingFilter::Rejected,
+        RatingFilter::Unrejected,
+    ];
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "all" | "none" | "off" => Some(Self::All),
+            "rated" | "stars" => Some(Self::Rated),
+            "picked" | "pick" | "picks" => Some(Self::Picked),
+            "rejected" | "reject" | "rejects" => Some(Self::Rejected),
+            "unrejected" | "not_rejected" | "hide_rejected" => Some(Self::Unrejected),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Rated => "rated",
+            Self::Picked => "picked",
+            Self::Rejected => "rejected",
+            Self::Unrejected => "unrejected",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::All => "All",
+            Self::Rated => "Rated",
+            Self::Picked => "Picked",
+            Self::Rejected => "Rejected",
+            Self::Unrejected => "Not rejected",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|filter| *filter == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// What the sort_to_folder_* actions do with the current file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOperation {
+    Move,
+    Copy,
+}
+
+impl SortOperation {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "move" | "cut" => Some(Self::Move),
+            "copy" => Some(Self::Copy),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Move => "move",
+            Self::Copy => "copy",
+        }
+    }
+}
+
+/// Page size for PDF export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdfPageSize {
+    A4,
+    Letter,
+    /// Each page takes the size of its image (at 150 DPI) plus the margins.
+    Image,
+}
+
+impl PdfPageSize {
+    pub const ALL: [PdfPageSize; 3] = [PdfPageSize::A4, PdfPageSize::Letter, PdfPageSize::Image];
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "a4" => Some(Self::A4),
+            "letter" | "us_letter" | "usletter" => Some(Self::Letter),
+            "image" | "fit" | "auto" | "original" => Some(Self::Image),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::A4 => "a4",
+            Self::Letter => "letter",
+            Self::Image => "image",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::A4 => "A4",
+            Self::Letter => "Letter",
+            Self::Image => "Image size",
+        }
+    }
+}
+
+/// What PNG exports do with the transparent areas of an image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportAlpha {
+    /// Write the alpha channel as it is.
+    Keep,
+    /// Composite onto `export_background_rgb` and write an opaque image.
+    Flatten,
+}
+
+impl ExportAlpha {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "keep" | "alpha" | "transparent" | "true" => Some(Self::Keep),
+            "flatten" | "opaque" | "background" | "false" => Some(Self::Flatten),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Keep => "keep",
+            Self::Flatten => "flatten",
+        }
+    }
+}
+
 /// Parse a single key from string
 fn parse_key(s: &str) -> Option<egui::Key> {
     match s.to_lowercase().as_str() {
@@ -466,6 +1524,9 @@ fn parse_key(s: &str) -> Option<egui::Key> {
         // Punctuation
         "minus" | "-" => Some(egui::Key::Minus),
         "plus" | "=" | "equals" => Some(egui::Key::Equals),
+        "comma" => Some(egui::Key::Comma),
+        "slash" | "/" => Some(egui::Key::Slash),
+        "backslash" | "\\" => Some(egui::Key::Backslash),
         _ => None,
     }
 }
@@ -474,6 +1535,12 @@ fn parse_key(s: &str) -> Option<egui::Key> {
 pub struct Config {
     /// Map from action to configured bindings.
     pub action_bindings: HashMap<Action, Vec<InputBinding>>,
+    /// Per-media overrides from the `[Shortcuts.*]` sections; see [`Config::bindings_in`].
+    pub context_bindings: HashMap<BindingContext, HashMap<Action, Vec<InputBinding>>>,
+    /// Recognize hold-right-drag mouse gestures.
+    pub mouse_gestures_enabled: bool,
+    /// Map from mouse gesture to the action it runs. Unlisted gestures do nothing.
+    pub mouse_gestures: HashMap<MouseGesture, Action>,
     /// How long the controls bar stays visible (in seconds)
     pub controls_hide_delay: f32,
     /// How long bottom overlays stay visible (video controls + manga toggle + zoom HUD), in seconds
@@ -483,18 +1550,151 @@ pub struct Config {
     pub cursor_idle_hide_delay: f32,
     /// Maximum delay between clicks for double-click detection (in seconds)
     pub double_click_grace_period: f64,
+    /// Save power at the cost of smoothness: pace video and animation redraws at 60 FPS,
+    /// poll background loading less often and release paused videos sooner.
+    pub low_power_mode: bool,
     /// Show an FPS overlay in the top-right corner (debug)
     pub show_fps: bool,
     /// How often the FPS overlay values refresh (milliseconds).
     pub show_fps_update_interval_ms: u64,
+    /// Minimum log level (overridden by `--verbose` and the `RIV_LOG`/`RUST_LOG` env vars).
+    pub log_level: LogLevel,
+    /// Write logs to a rotating file in the `logs` folder of the data folder (see
+    /// [`storage::data_dir`]), or of the temp folder when that is not writable.
+    pub log_to_file: bool,
+    /// Size at which the active log file is rotated, in megabytes.
+    pub log_file_max_size_mb: u64,
+    /// Number of rotated log files kept besides the active one.
+    pub log_file_max_files: usize,
     /// Size of the resize border in pixels
     pub resize_border_size: f32,
+    /// Touchscreen: pinch with two fingers to zoom about the pinch center.
+    pub touch_pinch_zoom: bool,
+    /// Touchscreen: drag with two or more fingers to pan (fullscreen views).
+    pub touch_two_finger_pan: bool,
+    /// Touchscreen: one-finger horizontal swipe switches files when the media fits the screen.
+    pub touch_swipe_navigation: bool,
+    /// Touchscreen: double tap toggles fullscreen instead of the double-click fit/reset.
+    pub touch_double_tap_fullscreen: bool,
+    /// Auto-pan a zoomed-in image while the pointer rests near a window edge.
+    pub edge_pan: bool,
+    /// Distance from the window edge, in pixels, where edge panning starts.
+    pub edge_pan_margin: f32,
+    /// Edge pan speed in pixels per second with the pointer on the window edge.
+    pub edge_pan_speed: f32,
+    /// Button that clicks the `click_zones`.
+    pub click_zone_button: ClickZoneButton,
+    /// Vertical bands across the window, left to right, and the action a click in each runs.
+    pub click_zones: Vec<ClickZone>,
+    /// Always-on-top also shrinks the floating window into the bottom-right corner and
+    /// hides the title bar.
+    pub always_on_top_compact: bool,
+    /// Width of the compact always-on-top window, in percent of the monitor width.
+    pub always_on_top_compact_size: f32,
+    /// Minutes between wallpapers in `--wallpaper` mode.
+    pub wallpaper_interval_minutes: u32,
+    /// Seconds each image stays on screen while the slideshow runs.
+    pub slideshow_interval_seconds: f32,
+    /// Animation between images on next/previous.
+    pub transition_effect: TransitionEffect,
+    /// Length of `transition_effect`, in milliseconds.
+    pub transition_duration_ms: u32,
+    pub transition_easing: TransitionEasing,
+    /// What a playlist does when a video ends.
+    pub playlist_repeat: PlaylistRepeat,
+    /// Play playlists in a random order.
+    pub playlist_shuffle: bool,
+    /// Images of a playlist downloaded ahead of the current item when it lists URLs.
+    pub url_prefetch_count: usize,
+    /// Show a notification-area icon with Open File, Recent Files, Slideshow and Exit.
+    pub tray_icon: bool,
+    /// With the tray icon, minimizing hides the window to the tray instead of the taskbar.
+    pub minimize_to_tray: bool,
+    /// Accept commands on the local control socket; read once at startup.
+    pub remote_control: bool,
+    /// Control commands clients may run, by [`remote_control::COMMAND_NAMES`] name.
+    pub remote_control_allow: Vec<String>,
+    /// Ask before quitting with unsaved annotations or adjustments, or while a video plays.
+    pub confirm_exit: bool,
+    /// Briefly confirm actions such as zoom, rotate, mute, copy and delete on screen.
+    pub show_osd: bool,
+    /// UI language; `None` follows the system locale.
+    pub language: Option<Language>,
+    /// Size of the title bar, video bar, menus and their text on top of the Windows DPI
+    /// scale (0.5-3). `ui_scale_up` / `ui_scale_down` change it at runtime.
+    pub ui_scale: f32,
     /// Background color as RGB (0-255)
     pub background_rgb: [u8; 3],
+    /// Paint a checkerboard under images that have transparent pixels (solo view).
+    pub checkerboard_background: bool,
+    /// Edge length of one checkerboard square in screen pixels.
+    pub checkerboard_size: u32,
+    pub checkerboard_light_rgb: [u8; 3],
+    pub checkerboard_dark_rgb: [u8; 3],
+    /// Rule-of-thirds guides over the image (solo view).
+    pub overlay_thirds: bool,
+    /// Even grid over the image, `grid_divisions` columns and rows (solo view).
+    pub overlay_grid: bool,
+    pub grid_divisions: u32,
+    /// Crosshair through the image center (solo view).
+    pub overlay_crosshair: bool,
+    /// Outline every image pixel from 800% zoom.
+    pub pixel_grid: bool,
+    /// Color scheme of the title bar, video bar and on-screen notices.
+    pub overlay_theme: OverlayThemeMode,
+    /// Opacity of the overlay backgrounds, relative to the theme's own (0.2-1.0).
+    pub overlay_opacity: f32,
+    /// Corner radius of on-screen notices and other floating overlay panels, in pixels.
+    pub overlay_corner_radius: f32,
+    /// Overlay background color; `None` uses the theme's.
+    pub overlay_background_rgb: Option<[u8; 3]>,
+    /// Overlay text and icon color; `None` uses the theme's.
+    pub overlay_text_rgb: Option<[u8; 3]>,
+    /// Fill of the video seek bar.
+    pub overlay_accent_rgb: [u8; 3],
+    /// Fill of the seek bar of animated images.
+    pub overlay_animation_accent_rgb: [u8; 3],
+    /// Height of the title bar, in pixels.
+    pub title_bar_height: f32,
+    /// Height of the video / animation control bar, in pixels.
+    pub video_bar_height: f32,
+    /// Page size used by PDF export.
+    pub pdf_page_size: PdfPageSize,
+    /// Blank margin around each image in a PDF export, in millimeters.
+    pub pdf_margin_mm: f32,
+    /// Quality (1-100) of animation conversions: lossy WebP quality, or GIF palette care.
+    pub animation_export_quality: u8,
+    /// Highest frame rate written by animation conversions.
+    pub animation_export_fps: u32,
+    /// Whether PNG exports keep transparency or flatten it onto `export_background_rgb`.
+    pub export_alpha: ExportAlpha,
+    /// Color transparent areas are filled with under `export_alpha = flatten`.
+    pub export_background_rgb: [u8; 3],
+    /// Number of newest images tiled by the monitor wall.
+    pub monitor_wall_count: usize,
+    /// Draw annotation strokes ahead of the pointer, extrapolated from its recent velocity.
+    pub annotation_stroke_prediction: bool,
+    /// Items next/previous navigation visits, by rating and pick/reject flag.
+    pub rating_filter: RatingFilter,
+    /// Target folders of the sort_to_folder_1..4 actions; `None` when unset.
+    pub sort_folders: [Option<PathBuf>; 4],
+    /// Whether sorting moves or copies the current file.
+    pub sort_operation: SortOperation,
+    /// Program `open_in_editor` starts with the current file; `None` uses the file's default
+    /// app.
+    pub external_editor: Option<PathBuf>,
+    /// Plugin filters run on every still image, in order; read once at startup.
+    pub plugin_filters: Vec<String>,
     /// Border color for marked items as RGB (0-255)
     pub marked_file_border_rgb: [u8; 3],
     /// When entering fullscreen, reset image to center and fit-to-screen.
     pub fullscreen_reset_fit_on_enter: bool,
+    /// How images are sized in fullscreen when opened or reset.
+    pub fullscreen_fit_mode: FitMode,
+    /// How images are sized in floating mode; the window follows, capped at 100% and the monitor.
+    pub floating_fit_mode: FitMode,
+    /// Sizes for the `window_size_preset_*` actions, in order.
+    pub window_size_presets: Vec<SizePreset>,
     /// On Windows, use native maximize/restore-down animation for fullscreen transitions.
     pub fullscreen_native_window_transition: bool,
     /// When true, title-bar maximize actions use borderless fullscreen instead of a separate
@@ -523,6 +1723,19 @@ pub struct Config {
     pub precise_rotation_step_degrees: f32,
     /// Zoom step per scroll wheel notch (1.05 = 5% per step, 1.25 = 25% per step)
     pub zoom_step: f32,
+    /// Point kept fixed while zooming, in both floating and fullscreen mode.
+    pub zoom_anchor: ZoomAnchor,
+    /// Steps visited by `cycle_zoom_presets`, in order.
+    pub zoom_presets: Vec<ZoomPreset>,
+    /// Share of the image (0.0-1.0) that panning keeps on screen along each axis, capped at
+    /// the view size. 0 allows panning it out of view; 1 stops the edges at the window border.
+    pub pan_min_visible: f32,
+    /// Pixels moved by one press of a `pan_*` key.
+    pub keyboard_pan_step: f32,
+    /// Speed factor a held `pan_*` key ramps up to over its first second.
+    pub keyboard_pan_acceleration: f32,
+    /// Enlargement of the held `magnifier` loupe relative to the current zoom (2-8).
+    pub magnifier_zoom: f32,
 
     /// Maximum zoom level in percent (100 = 1.0x, 1000 = 10.0x)
     pub max_zoom_percent: f32,
@@ -550,6 +1763,19 @@ pub struct Config {
     pub manga_inertial_friction: f32,
     /// Manga mode: arrow-key scroll speed (pixels per key press)
     pub manga_arrow_scroll_speed: f32,
+    /// Long strip page-width zoom: narrowest page width in percent of the window width.
+    pub manga_page_width_min_percent: f32,
+    /// Long strip page-width zoom: widest page width in percent of the window width.
+    pub manga_page_width_max_percent: f32,
+    /// Long strip page-width zoom: percent added or removed per Ctrl+wheel step.
+    pub manga_page_width_step_percent: f32,
+    /// Manga mode: show a blurred micro-thumbnail instead of a flat placeholder while pages decode.
+    pub manga_blur_placeholders: bool,
+    /// Long strip (experimental): PageUp/PageDown stop at detected panel gutters inside tall pages.
+    pub manga_panel_snap: bool,
+    /// Long strip: decode pages taller than the GPU texture limit at full resolution and
+    /// draw them as stacked texture segments instead of downscaling them to fit.
+    pub manga_segment_tall_pages: bool,
     /// Masonry mode: number of items per row
     pub masonry_items_per_row: usize,
     /// Masonry mode: delay before hover autoplay resumes after interaction stops (milliseconds)
@@ -597,8 +1823,43 @@ pub struct Config {
     pub state_volume: f64,
     /// Persisted breadcrumb address bar visibility from last session
     pub state_show_breadcrumb_bar: bool,
+    /// Files opened from outside the viewer, most recent first (at most [`MAX_RECENT_FILES`]).
+    pub state_recent_files: Vec<PathBuf>,
+    /// `.cube` LUTs loaded in the adjustments panel, most recent first (at most
+    /// [`MAX_RECENT_LUTS`]).
+    pub state_recent_luts: Vec<PathBuf>,
+    /// Monitor and spot the floating window was last left on.
+    pub state_floating_placement: Option<SavedPlacement>,
     /// Whether videos loop by default
     pub video_loop: bool,
+    /// Move to the next file when a video ends and `video_loop` is off.
+    pub advance_on_video_end: bool,
+    /// Bring video audio to `loudness_target_lufs`, measured per file.
+    pub loudness_normalization: bool,
+    /// Target loudness for `loudness_normalization`, in LUFS.
+    pub loudness_target_lufs: f32,
+    /// Keep the next video in the folder paused at its first frame so switching to it is instant.
+    pub preload_next_video: bool,
+    /// Show each video frame on the display refresh closest to its presentation time
+    /// instead of as soon as it is decoded.
+    pub video_frame_pacing: bool,
+    /// With frame pacing, wake the UI only when the next frame is due instead of on every
+    /// display refresh while a video plays.
+    pub video_repaint_at_deadline: bool,
+    /// Show a thumbnail of the frame under the pointer while hovering the seek bar.
+    pub video_seek_thumbnails: bool,
+    /// Whether a reopened video continues from its remembered position.
+    pub video_resume: ResumePolicy,
+    /// When frames of interlaced video are deinterlaced.
+    pub video_deinterlace: DeinterlaceMode,
+    /// How trimmed video ranges are written.
+    pub video_trim_mode: TrimMode,
+    /// ffmpeg executable used for trimming; `None` runs `ffmpeg` from `PATH`.
+    pub ffmpeg_path: Option<PathBuf>,
+    /// Action-safe and title-safe frames over videos.
+    pub video_safe_areas: bool,
+    /// Target aspect ratio (width / height) whose letterbox mask covers videos.
+    pub video_aspect_mask: Option<f32>,
     /// Seek policy for scrub interactions: adaptive, accurate, or keyframe.
     pub video_seek_policy: VideoSeekPolicy,
     /// Prefer hardware decoders on Windows when available.
@@ -621,6 +1882,10 @@ pub struct Config {
     /// Single instance mode: when true, opening a file reuses the existing window
     /// instead of creating a new one
     pub single_instance: bool,
+    /// Save a crash report (minidump + recent log) and show a dialog when the app crashes.
+    pub crash_reports: bool,
+    /// Keep local usage statistics (files viewed per day, most-used actions). Never uploaded.
+    pub usage_stats: bool,
     /// Native window title path mode: auto, full path, or filename only.
     pub window_title_show_full_path: WindowTitlePathMode,
 
@@ -633,6 +1898,10 @@ pub struct Config {
     /// Enable CUDA acceleration path when runtime support is available.
     pub enable_cuda: bool,
 
+    /// Folder for caches and stores instead of the local app data folder.
+    pub cache_dir: Option<PathBuf>,
+    /// Folder for scratch files instead of the system temp folder.
+    pub temp_dir: Option<PathBuf>,
     /// Maximum size for metadata_cache.redb in MiB.
     /// This covers persistent metadata (dimensions, file type, animation).
     /// 0 disables the size limit.
@@ -640,6 +1909,9 @@ pub struct Config {
     /// Maximum RAM budget for per-folder masonry metadata preload snapshots in MiB.
     /// Default is 2048 (2 GiB).
     pub masonry_metadata_ram_cache_limit_mb: u64,
+    /// RAM budget in MiB for decoded single-view images, so going back to a recent image
+    /// skips decoding. 0 disables the cache.
+    pub decoded_image_cache_mb: u64,
 
     // ============ PERFORMANCE SETTINGS ============
     /// Filter for upscaling images (making them larger)
@@ -718,20 +1990,166 @@ impl WindowTitlePathMode {
     }
 }
 
+/// Minimum severity written to the console and the rotating log file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "off" | "none" | "false" | "0" => Some(Self::Off),
+            "error" | "errors" => Some(Self::Error),
+            "warn" | "warning" | "warnings" => Some(Self::Warn),
+            "info" => Some(Self::Info),
+            "debug" | "verbose" => Some(Self::Debug),
+            "trace" => Some(Self::Trace),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Debug => "debug",
+            Self::Trace => "trace",
+        }
+    }
+}
+
+/// One `key = value` line of the config template, as shown in the settings window.
+#[derive(Debug, Clone)]
+pub struct IniEntry {
+    pub key: String,
+    /// Current value in config.ini syntax.
+    pub value: String,
+    /// The `;` comment block above the key in the template.
+    pub comment: String,
+}
+
+/// A `[Section]` of the config template with its editable entries.
+#[derive(Debug, Clone)]
+pub struct IniSection {
+    pub name: String,
+    pub entries: Vec<IniEntry>,
+}
+
+/// A single edited value, applied on top of the current config by [`Config::with_ini_overrides`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IniOverride {
+    pub section: String,
+    pub key: String,
+    pub value: String,
+}
+
 impl Config {
     fn default_without_bindings() -> Self {
         Self {
             action_bindings: HashMap::new(),
+            context_bindings: HashMap::new(),
+            mouse_gestures_enabled: true,
+            mouse_gestures: HashMap::new(),
             controls_hide_delay: 0.5,
             bottom_overlay_hide_delay: 0.5,
             cursor_idle_hide_delay: 3.0,
             double_click_grace_period: 0.35,
+            low_power_mode: false,
             show_fps: false,
             show_fps_update_interval_ms: 500,
+            log_level: LogLevel::Warn,
+            log_to_file: true,
+            log_file_max_size_mb: 5,
+            log_file_max_files: 3,
             resize_border_size: 6.0,
+            touch_pinch_zoom: true,
+            touch_two_finger_pan: true,
+            touch_swipe_navigation: true,
+            touch_double_tap_fullscreen: true,
+            edge_pan: false,
+            edge_pan_margin: 40.0,
+            edge_pan_speed: 1200.0,
+            click_zone_button: ClickZoneButton::Right,
+            click_zones: vec![
+                ClickZone {
+                    weight: 1.0,
+                    action: Some(Action::PreviousImage),
+                },
+                ClickZone {
+                    weight: 7.0,
+                    action: None,
+                },
+                ClickZone {
+                    weight: 1.0,
+                    action: Some(Action::NextImage),
+                },
+            ],
+            always_on_top_compact: false,
+            always_on_top_compact_size: 25.0,
+            wallpaper_interval_minutes: 30,
+            slideshow_interval_seconds: 5.0,
+            transition_effect: TransitionEffect::Off,
+            transition_duration_ms: 250,
+            transition_easing: TransitionEasing::EaseOut,
+            playlist_repeat: PlaylistRepeat::Off,
+            playlist_shuffle: false,
+            url_prefetch_count: 3,
+            tray_icon: false,
+            minimize_to_tray: false,
+            remote_control: false,
+            remote_control_allow: remote_control::COMMAND_NAMES
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            confirm_exit: true,
+            show_osd: true,
+            language: None,
+            ui_scale: 1.0,
             background_rgb: [0, 0, 0],
+            checkerboard_background: false,
+            checkerboard_size: 8,
+            checkerboard_light_rgb: [153, 153, 153],
+            checkerboard_dark_rgb: [102, 102, 102],
+            overlay_thirds: false,
+            overlay_grid: false,
+            grid_divisions: 8,
+            overlay_crosshair: false,
+            pixel_grid: true,
+            overlay_theme: OverlayThemeMode::Dark,
+            overlay_opacity: 1.0,
+            overlay_corner_radius: 10.0,
+            overlay_background_rgb: None,
+            overlay_text_rgb: None,
+            overlay_accent_rgb: [66, 133, 244],
+            overlay_animation_accent_rgb: [76, 175, 80],
+            title_bar_height: 32.0,
+            video_bar_height: 56.0,
+            pdf_page_size: PdfPageSize::A4,
+            pdf_margin_mm: 10.0,
+            animation_export_quality: 80,
+            animation_export_fps: 15,
+            export_alpha: ExportAlpha::Keep,
+            export_background_rgb: [255, 255, 255],
+            monitor_wall_count: 12,
+            annotation_stroke_prediction: false,
+            rating_filter: RatingFilter::All,
+            sort_folders: [None, None, None, None],
+            sort_operation: SortOperation::Move,
+            external_editor: None,
+            plugin_filters: Vec::new(),
             marked_file_border_rgb: [94, 214, 255],
             fullscreen_reset_fit_on_enter: true,
+            fullscreen_fit_mode: FitMode::Fit,
+            floating_fit_mode: FitMode::Fit,
+            window_size_presets: window_snap::parse_size_presets(DEFAULT_WINDOW_SIZE_PRESETS),
             fullscreen_native_window_transition: true,
             maximize_to_borderless_fullscreen: true,
             confirm_delete_to_recycle_bin: true,
@@ -745,6 +2163,16 @@ impl Config {
             zoom_animation_speed: 20.0,
             precise_rotation_step_degrees: 2.0,
             zoom_step: 1.02,
+            zoom_anchor: ZoomAnchor::Smart,
+            pan_min_visible: 0.0,
+            keyboard_pan_step: 40.0,
+            keyboard_pan_acceleration: 4.0,
+            magnifier_zoom: 3.0,
+            zoom_presets: vec![
+                ZoomPreset::Fit,
+                ZoomPreset::Zoom(1.0),
+                ZoomPreset::Zoom(2.0),
+            ],
             max_zoom_percent: 1000.0,
             ctrl_scroll_up_pan_speed_px_per_step: 20.0,
             ctrl_scroll_down_pan_speed_px_per_step: 20.0,
@@ -757,6 +2185,12 @@ impl Config {
             manga_wheel_edge_spring_hz: 4.5,
             manga_inertial_friction: 0.33,
             manga_arrow_scroll_speed: 140.0,
+            manga_page_width_min_percent: 80.0,
+            manga_page_width_max_percent: 150.0,
+            manga_page_width_step_percent: 5.0,
+            manga_blur_placeholders: true,
+            manga_panel_snap: false,
+            manga_segment_tall_pages: true,
             masonry_items_per_row: 5,
             manga_hover_autoplay_resume_delay_ms: 220,
             manga_virtualization_backend: MangaVirtualizationBackend::RTree,
@@ -779,7 +2213,23 @@ impl Config {
             state_muted: true,
             state_volume: 0.0,
             state_show_breadcrumb_bar: true,
+            state_recent_files: Vec::new(),
+            state_recent_luts: Vec::new(),
+            state_floating_placement: None,
             video_loop: true,
+            advance_on_video_end: false,
+            loudness_normalization: false,
+            loudness_target_lufs: -18.0,
+            preload_next_video: true,
+            video_frame_pacing: true,
+            video_repaint_at_deadline: false,
+            video_seek_thumbnails: true,
+            video_resume: ResumePolicy::Ask,
+            video_deinterlace: DeinterlaceMode::Auto,
+            video_trim_mode: TrimMode::Copy,
+            ffmpeg_path: None,
+            video_safe_areas: false,
+            video_aspect_mask: None,
             video_seek_policy: VideoSeekPolicy::Adaptive,
             video_prefer_hardware_decode: true,
             video_disable_hardware_decode: false,
@@ -789,13 +2239,18 @@ impl Config {
             video_priority_play_pause_binding: Some(InputBinding::Key(egui::Key::Space)),
             startup_window_mode: StartupWindowMode::Floating,
             single_instance: true,
+            crash_reports: true,
+            usage_stats: true,
             window_title_show_full_path: WindowTitlePathMode::Auto,
             vsync: true,
             use_hardware_acceleration: true,
             enable_d3d12: true,
             enable_cuda: true,
+            cache_dir: None,
+            temp_dir: None,
             metadata_cache_max_size_mb: 1024,
             masonry_metadata_ram_cache_limit_mb: 2048,
+            decoded_image_cache_mb: 384,
             // Image quality defaults
             upscale_filter: ImageFilter::CatmullRom,
             downscale_filter: ImageFilter::Lanczos3,
@@ -830,7 +2285,54 @@ impl Config {
             Action::ToggleFullscreen,
         );
         self.add_binding(InputBinding::KeyWithCtrl(egui::Key::W), Action::Exit);
-        self.add_binding(InputBinding::Key(egui::Key::Escape), Action::Exit);
+        self.add_binding(
+            InputBinding::Key(egui::Key::Escape),
+            Action::ExitFullscreenOrQuit,
+        );
+        self.add_binding(
+            InputBinding::KeyWithCtrl(egui::Key::Comma),
+            Action::OpenSettings,
+        );
+        self.add_binding(
+            InputBinding::KeyWithCtrlShift(egui::Key::P),
+            Action::CommandPalette,
+        );
+        self.add_binding(InputBinding::Key(egui::Key::Slash), Action::FilterFolder);
+        self.add_binding(
+            InputBinding::KeyWithShift(egui::Key::M),
+            Action::MoveToNextMonitor,
+        );
+        self.add_binding(
+            InputBinding::KeyWithAlt(egui::Key::ArrowLeft),
+            Action::SnapWindowLeft,
+        );
+        self.add_binding(
+            InputBinding::KeyWithAlt(egui::Key::ArrowRight),
+            Action::SnapWindowRight,
+        );
+        self.add_binding(
+            InputBinding::KeyWithAlt(egui::Key::ArrowUp),
+            Action::SnapWindowUp,
+        );
+        self.add_binding(
+            InputBinding::KeyWithAlt(egui::Key::ArrowDown),
+            Action::SnapWindowDown,
+        );
+        self.add_binding(
+            InputBinding::KeyWithAlt(egui::Key::Num1),
+            Action::WindowSizePreset1,
+        );
+        self.add_binding(
+            InputBinding::KeyWithAlt(egui::Key::Num2),
+            Action::WindowSizePreset2,
+        );
+        self.add_binding(
+            InputBinding::KeyWithAlt(egui::Key::Num3),
+            Action::WindowSizePreset3,
+        );
+        self.add_binding(InputBinding::Key(egui::Key::T), Action::ToggleAlwaysOnTop);
+        self.add_binding(InputBinding::Key(egui::Key::F5), Action::ToggleSlideshow);
+        self.add_binding(InputBinding::Key(egui::Key::Q), Action::TogglePlaylistPanel);
 
         // Floating + fullscreen shortcuts
         self.add_binding(InputBinding::MouseLeft, Action::Pan);
@@ -869,6 +2371,8 @@ impl Config {
             InputBinding::KeyWithCtrl(egui::Key::ArrowDown),
             Action::PreciseRotationCounterClockwise,
         );
+        // Held in the solo view; `r` turns manga pages only in Long Strip mode.
+        self.add_binding(InputBinding::Key(egui::Key::R), Action::FreeRotate);
         self.add_binding(
             InputBinding::KeyWithCtrl(egui::Key::ArrowLeft),
             Action::FlipVertically,
@@ -877,6 +2381,86 @@ impl Config {
             InputBinding::KeyWithCtrl(egui::Key::ArrowRight),
             Action::FlipHorizontally,
         );
+        // Ctrl+arrows already rotate and flip, and most letters open tools.
+        self.add_binding(
+            InputBinding::KeyWithShift(egui::Key::ArrowLeft),
+            Action::PanLeft,
+        );
+        self.add_binding(
+            InputBinding::KeyWithShift(egui::Key::ArrowRight),
+            Action::PanRight,
+        );
+        self.add_binding(
+            InputBinding::KeyWithShift(egui::Key::ArrowUp),
+            Action::PanUp,
+        );
+        self.add_binding(
+            InputBinding::KeyWithShift(egui::Key::ArrowDown),
+            Action::PanDown,
+        );
+        self.add_binding(InputBinding::Key(egui::Key::A), Action::ToggleAnnotations);
+        self.add_binding(InputBinding::Key(egui::Key::E), Action::ToggleAdjustments);
+        self.add_binding(InputBinding::Key(egui::Key::B), Action::ToggleCheckerboard);
+        self.add_binding(InputBinding::Key(egui::Key::G), Action::ToggleThirds);
+        self.add_binding(InputBinding::KeyWithShift(egui::Key::G), Action::ToggleGrid);
+        self.add_binding(InputBinding::Key(egui::Key::C), Action::CompareImages);
+        self.add_binding(
+            InputBinding::KeyWithShift(egui::Key::C),
+            Action::CopyViewToClipboard,
+        );
+        self.add_binding(InputBinding::Key(egui::Key::S), Action::Straighten);
+        self.add_binding(InputBinding::Key(egui::Key::K), Action::Perspective);
+        self.add_binding(
+            InputBinding::KeyWithAlt(egui::Key::C),
+            Action::ToggleEyedropper,
+        );
+        self.add_binding(InputBinding::KeyWithShift(egui::Key::Z), Action::Magnifier);
+        self.add_binding(
+            InputBinding::Key(egui::Key::Backslash),
+            Action::ShowOriginal,
+        );
+        self.add_binding(InputBinding::Key(egui::Key::D), Action::ScanCleanup);
+        self.add_binding(InputBinding::KeyWithCtrl(egui::Key::P), Action::ExportPdf);
+        self.add_binding(
+            InputBinding::KeyWithShift(egui::Key::F2),
+            Action::BatchRename,
+        );
+        self.add_binding(InputBinding::Key(egui::Key::W), Action::MonitorWall);
+        self.add_binding(InputBinding::Key(egui::Key::V), Action::CycleFitMode);
+        self.add_binding(InputBinding::Key(egui::Key::Z), Action::CycleZoomPresets);
+        self.add_binding(InputBinding::Key(egui::Key::Num0), Action::ClearRating);
+        self.add_binding(InputBinding::Key(egui::Key::Num1), Action::Rate1);
+        self.add_binding(InputBinding::Key(egui::Key::Num2), Action::Rate2);
+        self.add_binding(InputBinding::Key(egui::Key::Num3), Action::Rate3);
+        self.add_binding(InputBinding::Key(egui::Key::Num4), Action::Rate4);
+        self.add_binding(InputBinding::Key(egui::Key::Num5), Action::Rate5);
+        self.add_binding(InputBinding::Key(egui::Key::P), Action::TogglePick);
+        self.add_binding(InputBinding::Key(egui::Key::X), Action::ToggleReject);
+        self.add_binding(
+            InputBinding::KeyWithShift(egui::Key::F),
+            Action::CycleRatingFilter,
+        );
+        self.add_binding(InputBinding::Key(egui::Key::F1), Action::SortToFolder1);
+        self.add_binding(InputBinding::Key(egui::Key::F2), Action::SortToFolder2);
+        self.add_binding(InputBinding::Key(egui::Key::F3), Action::SortToFolder3);
+        self.add_binding(InputBinding::Key(egui::Key::F4), Action::SortToFolder4);
+        self.add_binding(
+            InputBinding::KeyWithShift(egui::Key::E),
+            Action::OpenInEditor,
+        );
+        self.add_binding(InputBinding::KeyWithCtrl(egui::Key::N), Action::NewWindow);
+        self.add_binding(
+            InputBinding::KeyWithCtrl(egui::Key::Equals),
+            Action::UiScaleUp,
+        );
+        self.add_binding(
+            InputBinding::KeyWithCtrl(egui::Key::Minus),
+            Action::UiScaleDown,
+        );
+        self.add_binding(
+            InputBinding::KeyWithCtrl(egui::Key::Num0),
+            Action::ResetUiScale,
+        );
 
         // Zoom
         self.add_binding(InputBinding::ScrollUp, Action::ZoomIn);
@@ -884,6 +2468,9 @@ impl Config {
 
         // Video controls
         self.add_binding(InputBinding::Key(egui::Key::M), Action::VideoMute);
+        self.add_binding(InputBinding::Key(egui::Key::I), Action::VideoTrimIn);
+        self.add_binding(InputBinding::Key(egui::Key::O), Action::VideoTrimOut);
+        self.add_binding(InputBinding::KeyWithCtrl(egui::Key::T), Action::VideoTrim);
 
         // Long strip shortcuts
         self.add_binding(InputBinding::MouseLeft, Action::MangaPan);
@@ -918,6 +2505,15 @@ impl Config {
         self.add_binding(InputBinding::ScrollDown, Action::MangaScrollDown);
         self.add_binding(InputBinding::CtrlScrollUp, Action::MangaZoomIn);
         self.add_binding(InputBinding::CtrlScrollDown, Action::MangaZoomOut);
+        self.add_binding(
+            InputBinding::Key(egui::Key::W),
+            Action::MangaTogglePageWidth,
+        );
+        self.add_binding(InputBinding::Key(egui::Key::R), Action::MangaRotatePage);
+        self.add_binding(
+            InputBinding::KeyWithShift(egui::Key::W),
+            Action::MangaCyclePageFit,
+        );
 
         // Masonry shortcuts
         self.add_binding(InputBinding::MouseLeft, Action::MasonryPan);
@@ -949,6 +2545,18 @@ impl Config {
         self.add_binding(InputBinding::ScrollDown, Action::MasonryScrollDown);
         self.add_binding(InputBinding::CtrlScrollUp, Action::MasonryZoomIn);
         self.add_binding(InputBinding::CtrlScrollDown, Action::MasonryZoomOut);
+
+        // Mouse gestures
+        for (key, action) in [
+            ("gesture_left", Action::PreviousImage),
+            ("gesture_right", Action::NextImage),
+            ("gesture_up", Action::ToggleFullscreen),
+            ("gesture_down_right", Action::Exit),
+        ] {
+            if let Some(gesture) = MouseGesture::from_ini_key(key) {
+                self.mouse_gestures.insert(gesture, action);
+            }
+        }
     }
 
     /// Add a binding
@@ -1015,6 +2623,21 @@ impl Config {
         self.replace_action_bindings(Action::ToggleFullscreen, &replacement_bindings);
     }
 
+    /// Escape used to quit outright; it now belongs to `exit_fullscreen_or_quit`, which leaves
+    /// fullscreen first. Configs still on the old `exit = ctrl+w, escape` default lose the
+    /// Escape there so the new action gets it.
+    fn migrate_legacy_exit_escape_binding(&mut self) {
+        let legacy_bindings = [
+            InputBinding::KeyWithCtrl(egui::Key::W),
+            InputBinding::Key(egui::Key::Escape),
+        ];
+        if !self.action_bindings_match_exact(Action::Exit, &legacy_bindings) {
+            return;
+        }
+
+        self.replace_action_bindings(Action::Exit, &[InputBinding::KeyWithCtrl(egui::Key::W)]);
+    }
+
     fn action_bindings_match_exact(&self, action: Action, expected: &[InputBinding]) -> bool {
         let Some(actual_bindings) = self.action_bindings.get(&action) else {
             return expected.is_empty();
@@ -1089,43 +2712,14 @@ impl Config {
         }
     }
 
-    /// Get the configuration directory in AppData/Roaming.
-    /// Creates the directory if it doesn't exist.
-    fn config_dir() -> PathBuf {
-        let config_dir = if cfg!(target_os = "windows") {
-            app_dirs::app_config_dir().unwrap_or_else(|| {
-                std::env::current_exe()
-                    .ok()
-                    .and_then(|p| p.parent().map(|p| p.join(app_dirs::APP_DIR_NAME)))
-                    .unwrap_or_else(|| PathBuf::from(".").join(app_dirs::APP_DIR_NAME))
-            })
-        } else {
-            // On Unix-like systems, use ~/.config
-            let base_dir = std::env::var("XDG_CONFIG_HOME")
-                .ok()
-                .map(PathBuf::from)
-                .or_else(|| {
-                    std::env::var("HOME")
-                        .ok()
-                        .map(|h| PathBuf::from(h).join(".config"))
-                })
-                .unwrap_or_else(|| PathBuf::from("."));
-            base_dir.join(app_dirs::APP_DIR_NAME)
-        };
-
-        // Create directory if it doesn't exist
-        let _ = fs::create_dir_all(&config_dir);
-
-        config_dir
-    }
-
     /// Get settings file path.
     ///
-    /// Uses `config.ini` in AppData/Roaming/rust-image-viewer/ on Windows.
+    /// Uses `config.ini` in AppData/Roaming/rust-image-viewer/ on Windows, or next to the
+    /// executable in portable mode (see [`storage`]).
     ///
     /// Migrates from legacy locations (`rust-image-viewer-config.ini` / `setting.ini`) if needed.
     pub fn config_path() -> PathBuf {
-        let config_dir = Self::config_dir();
+        let config_dir = storage::config_dir();
         let config = config_dir.join(CONFIG_FILE_NAME);
 
         // Migration from legacy AppData filename
@@ -1196,10 +2790,12 @@ impl Config {
         let mut config = Self::default_without_bindings();
 
         let mut in_shortcuts_section = false;
+        let mut shortcuts_context = None;
         let mut in_settings_section = false;
         let mut in_video_section = false;
         let mut in_quality_section = false;
         let mut in_state_section = false;
+        let mut in_gestures_section = false;
 
         for line in content.lines() {
             let line = line.trim();
@@ -1213,6 +2809,7 @@ impl Config {
             if line.starts_with('[') && line.ends_with(']') {
                 let section = &line[1..line.len() - 1];
                 in_shortcuts_section = section.eq_ignore_ascii_case("shortcuts");
+                shortcuts_context = BindingContext::from_section(section);
                 in_settings_section = section.eq_ignore_ascii_case("settings");
                 in_video_section = section.eq_ignore_ascii_case("video");
                 in_quality_section = section.eq_ignore_ascii_case("quality")
@@ -1221,6 +2818,8 @@ impl Config {
                     || section.eq_ignore_ascii_case("filters");
                 in_state_section = section.eq_ignore_ascii_case("state")
                     || section.eq_ignore_ascii_case("video_state");
+                in_gestures_section = section.eq_ignore_ascii_case("gestures")
+                    || section.eq_ignore_ascii_case("mouse_gestures");
                 continue;
             }
 
@@ -1293,6 +2892,19 @@ impl Config {
                 }
             }
 
+            // Per-media shortcut sections only take action names.
+            if let Some(context) = shortcuts_context {
+                if let Some((key, value)) = line.split_once('=') {
+                    if let Some(action) = Action::from_str(key.trim()) {
+                        config
+                            .context_bindings
+                            .entry(context)
+                            .or_default()
+                            .insert(action, parse_binding_list(value.trim()));
+                    }
+                }
+            }
+
             // Parse key=value pairs in settings section
             if in_settings_section {
                 if let Some((key, value)) = line.split_once('=') {
@@ -1305,27 +2917,149 @@ impl Config {
                                 config.controls_hide_delay = v.max(0.1);
                             }
                         }
-                        "bottom_overlay_hide_delay"
-                        | "bottom_controls_hide_delay"
-                        | "bottom_hud_hide_delay" => {
-                            if let Ok(v) = value.parse::<f32>() {
-                                config.bottom_overlay_hide_delay = v.max(0.1);
+                        "bottom_overlay_hide_delay"
+                        | "bottom_controls_hide_delay"
+                        | "bottom_hud_hide_delay" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.bottom_overlay_hide_delay = v.max(0.1);
+                            }
+                        }
+                        "cursor_idle_hide_delay"
+                        | "idle_cursor_hide_delay"
+                        | "mouse_idle_hide_delay" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                if v.is_finite() {
+                                    config.cursor_idle_hide_delay = v.max(0.0);
+                                }
+                            }
+                        }
+                        "double_click_grace_period"
+                        | "double_click_delay"
+                        | "double_click_grace_seconds" => {
+                            if let Ok(v) = value.parse::<f64>() {
+                                config.double_click_grace_period = v.clamp(0.1, 1.2);
+                            }
+                        }
+                        "touch_pinch_zoom" | "pinch_zoom" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.touch_pinch_zoom = v;
+                            }
+                        }
+                        "touch_two_finger_pan" | "two_finger_pan" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.touch_two_finger_pan = v;
+                            }
+                        }
+                        "touch_swipe_navigation" | "swipe_navigation" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.touch_swipe_navigation = v;
+                            }
+                        }
+                        "touch_double_tap_fullscreen" | "double_tap_fullscreen" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.touch_double_tap_fullscreen = v;
+                            }
+                        }
+                        "edge_pan" | "edge_panning" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.edge_pan = v;
+                            }
+                        }
+                        "edge_pan_margin" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.edge_pan_margin = v.clamp(4.0, 200.0);
+                            }
+                        }
+                        "edge_pan_speed" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.edge_pan_speed = v.clamp(50.0, 5000.0);
+                            }
+                        }
+                        "click_zone_button" | "click_zones_button" => {
+                            if let Some(button) = ClickZoneButton::from_str(value) {
+                                config.click_zone_button = button;
+                            }
+                        }
+                        "click_zones" | "click_zone_layout" => {
+                            if let Some(zones) = ClickZone::parse_list(value) {
+                                config.click_zones = zones;
+                            }
+                        }
+                        "always_on_top_compact" | "picture_in_picture_compact" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.always_on_top_compact = v;
+                            }
+                        }
+                        "always_on_top_compact_size" | "picture_in_picture_size" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.always_on_top_compact_size = v.clamp(10.0, 60.0);
+                            }
+                        }
+                        "wallpaper_interval_minutes" | "wallpaper_interval" => {
+                            if let Ok(v) = value.parse::<u32>() {
+                                config.wallpaper_interval_minutes = v.clamp(1, 1440);
+                            }
+                        }
+                        "slideshow_interval_seconds" | "slideshow_interval" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.slideshow_interval_seconds = v.clamp(1.0, 3600.0);
+                            }
+                        }
+                        "transition_effect" | "transition" => {
+                            if let Some(effect) = TransitionEffect::from_str(value) {
+                                config.transition_effect = effect;
+                            }
+                        }
+                        "transition_duration_ms" | "transition_duration" => {
+                            if let Ok(v) = value.parse::<u32>() {
+                                config.transition_duration_ms = v.clamp(50, 2000);
+                            }
+                        }
+                        "transition_easing" => {
+                            if let Some(easing) = TransitionEasing::from_str(value) {
+                                config.transition_easing = easing;
+                            }
+                        }
+                        "playlist_repeat" | "repeat" => {
+                            if let Some(repeat) = PlaylistRepeat::from_str(value) {
+                                config.playlist_repeat = repeat;
+                            }
+                        }
+                        "playlist_shuffle" | "shuffle" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.playlist_shuffle = v;
+                            }
+                        }
+                        "url_prefetch_count" | "prefetch_count" | "url_prefetch" => {
+                            if let Ok(v) = value.parse::<usize>() {
+                                config.url_prefetch_count = v.min(20);
+                            }
+                        }
+                        "tray_icon" | "system_tray" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.tray_icon = v;
+                            }
+                        }
+                        "minimize_to_tray" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.minimize_to_tray = v;
                             }
                         }
-                        "cursor_idle_hide_delay"
-                        | "idle_cursor_hide_delay"
-                        | "mouse_idle_hide_delay" => {
-                            if let Ok(v) = value.parse::<f32>() {
-                                if v.is_finite() {
-                                    config.cursor_idle_hide_delay = v.max(0.0);
-                                }
+                        "remote_control" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.remote_control = v;
                             }
                         }
-                        "double_click_grace_period"
-                        | "double_click_delay"
-                        | "double_click_grace_seconds" => {
-                            if let Ok(v) = value.parse::<f64>() {
-                                config.double_click_grace_period = v.clamp(0.1, 1.2);
+                        "remote_control_allow" => {
+                            config.remote_control_allow = value
+                                .split(',')
+                                .map(|name| name.trim().to_ascii_lowercase())
+                                .filter(|name| !name.is_empty())
+                                .collect();
+                        }
+                        "confirm_exit" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.confirm_exit = v;
                             }
                         }
                         "resize_border_size" => {
@@ -1333,6 +3067,11 @@ impl Config {
                                 config.resize_border_size = v.clamp(2.0, 20.0);
                             }
                         }
+                        "low_power_mode" | "low_power" | "power_saving" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.low_power_mode = v;
+                            }
+                        }
                         "show_fps" | "show_fps_overlay" | "fps_overlay" => {
                             if let Some(v) = parse_bool(value) {
                                 config.show_fps = v;
@@ -1365,6 +3104,188 @@ impl Config {
                                 config.background_rgb[2] = v;
                             }
                         }
+                        "language" | "ui_language" | "lang" => {
+                            if value.trim().eq_ignore_ascii_case("auto") {
+                                config.language = None;
+                            } else if let Some(language) = Language::from_str(value) {
+                                config.language = Some(language);
+                            }
+                        }
+                        "ui_scale" | "ui_zoom" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.ui_scale = v.clamp(0.5, 3.0);
+                            }
+                        }
+                        "show_osd" | "osd" | "action_feedback" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.show_osd = v;
+                            }
+                        }
+                        "checkerboard_background"
+                        | "checkerboard"
+                        | "transparency_checkerboard" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.checkerboard_background = v;
+                            }
+                        }
+                        "checkerboard_size" => {
+                            if let Ok(v) = value.parse::<u32>() {
+                                config.checkerboard_size = v.clamp(2, 128);
+                            }
+                        }
+                        "checkerboard_light_rgb" => {
+                            if let Some(rgb) = parse_rgb_triplet(value) {
+                                config.checkerboard_light_rgb = rgb;
+                            }
+                        }
+                        "checkerboard_dark_rgb" => {
+                            if let Some(rgb) = parse_rgb_triplet(value) {
+                                config.checkerboard_dark_rgb = rgb;
+                            }
+                        }
+                        "overlay_thirds" | "rule_of_thirds" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.overlay_thirds = v;
+                            }
+                        }
+                        "overlay_grid" | "grid_overlay" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.overlay_grid = v;
+                            }
+                        }
+                        "grid_divisions" | "grid_size" => {
+                            if let Ok(v) = value.parse::<u32>() {
+                                config.grid_divisions = v.clamp(2, 64);
+                            }
+                        }
+                        "overlay_crosshair" | "center_crosshair" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.overlay_crosshair = v;
+                            }
+                        }
+                        "pixel_grid" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.pixel_grid = v;
+                            }
+                        }
+                        "overlay_theme" | "theme" => {
+                            if let Some(mode) = OverlayThemeMode::from_str(value) {
+                                config.overlay_theme = mode;
+                            }
+                        }
+                        "overlay_opacity" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.overlay_opacity = v.clamp(0.2, 1.0);
+                            }
+                        }
+                        "overlay_corner_radius" | "overlay_rounding" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.overlay_corner_radius = v.clamp(0.0, 24.0);
+                            }
+                        }
+                        "overlay_background_rgb" | "overlay_text_rgb" => {
+                            let rgb = if value.trim().eq_ignore_ascii_case("auto") {
+                                Some(None)
+                            } else {
+                                parse_rgb_triplet(value).map(Some)
+                            };
+                            if let Some(rgb) = rgb {
+                                if key == "overlay_background_rgb" {
+                                    config.overlay_background_rgb = rgb;
+                                } else {
+                                    config.overlay_text_rgb = rgb;
+                                }
+                            }
+                        }
+                        "overlay_accent_rgb" | "accent_rgb" => {
+                            if let Some(rgb) = parse_rgb_triplet(value) {
+                                config.overlay_accent_rgb = rgb;
+                            }
+                        }
+                        "overlay_animation_accent_rgb" => {
+                            if let Some(rgb) = parse_rgb_triplet(value) {
+                                config.overlay_animation_accent_rgb = rgb;
+                            }
+                        }
+                        "title_bar_height" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.title_bar_height = v.clamp(24.0, 64.0);
+                            }
+                        }
+                        "video_bar_height" | "video_controls_height" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.video_bar_height = v.clamp(40.0, 120.0);
+                            }
+                        }
+                        "pdf_page_size" | "pdf_paper_size" => {
+                            if let Some(size) = PdfPageSize::from_str(value) {
+                                config.pdf_page_size = size;
+                            }
+                        }
+                        "pdf_margin_mm" | "pdf_margin" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.pdf_margin_mm = v.clamp(0.0, 50.0);
+                            }
+                        }
+                        "animation_export_quality" | "animation_quality" => {
+                            if let Ok(v) = value.parse::<u8>() {
+                                config.animation_export_quality = v.clamp(1, 100);
+                            }
+                        }
+                        "animation_export_fps" | "animation_fps" => {
+                            if let Ok(v) = value.parse::<u32>() {
+                                config.animation_export_fps = v.clamp(1, 60);
+                            }
+                        }
+                        "export_alpha" | "export_transparency" => {
+                            if let Some(alpha) = ExportAlpha::from_str(value) {
+                                config.export_alpha = alpha;
+                            }
+                        }
+                        "export_background_rgb" | "flatten_background_rgb" => {
+                            if let Some(rgb) = parse_rgb_triplet(value) {
+                                config.export_background_rgb = rgb;
+                            }
+                        }
+                        "monitor_wall_count" | "monitor_wall_size" => {
+                            if let Ok(v) = value.parse::<usize>() {
+                                config.monitor_wall_count = v.clamp(1, 64);
+                            }
+                        }
+                        "annotation_stroke_prediction" | "stroke_prediction" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.annotation_stroke_prediction = v;
+                            }
+                        }
+                        "sort_folder_1" | "sort_folder_2" | "sort_folder_3" | "sort_folder_4" => {
+                            let slot = key.as_bytes()[key.len() - 1] - b'1';
+                            let value = value.trim().trim_matches('"');
+                            config.sort_folders[slot as usize] =
+                                (!value.is_empty()).then(|| PathBuf::from(value));
+                        }
+                        "external_editor" | "editor" => {
+                            let value = value.trim().trim_matches('"');
+                            config.external_editor =
+                                (!value.is_empty()).then(|| PathBuf::from(value));
+                        }
+                        "plugin_filters" => {
+                            config.plugin_filters = value
+                                .split(',')
+                                .map(str::trim)
+                                .filter(|name| !name.is_empty())
+                                .map(str::to_string)
+                                .collect();
+                        }
+                        "sort_operation" | "sort_mode" => {
+                            if let Some(operation) = SortOperation::from_str(value) {
+                                config.sort_operation = operation;
+                            }
+                        }
+                        "rating_filter" | "navigation_filter" => {
+                            if let Some(filter) = RatingFilter::from_str(value) {
+                                config.rating_filter = filter;
+                            }
+                        }
                         "marked_file_border_rgb" | "marked_item_border_rgb" | "mark_border_rgb" => {
                             if let Some(rgb) = parse_rgb_triplet(value) {
                                 config.marked_file_border_rgb = rgb;
@@ -1390,6 +3311,19 @@ impl Config {
                                 config.fullscreen_reset_fit_on_enter = v;
                             }
                         }
+                        "fullscreen_fit_mode" | "fullscreen_fit" => {
+                            if let Some(mode) = FitMode::from_str(value) {
+                                config.fullscreen_fit_mode = mode;
+                            }
+                        }
+                        "floating_fit_mode" | "floating_fit" => {
+                            if let Some(mode) = FitMode::from_str(value) {
+                                config.floating_fit_mode = mode;
+                            }
+                        }
+                        "window_size_presets" => {
+                            config.window_size_presets = window_snap::parse_size_presets(value);
+                        }
                         "fullscreen_native_window_transition"
                         | "fullscreen_native_transition"
                         | "fullscreen_animated_window_transition"
@@ -1441,6 +3375,39 @@ impl Config {
                                 config.zoom_step = v.clamp(1.01, 2.0);
                             }
                         }
+                        "zoom_anchor" | "zoom_to" | "zoom_origin" => {
+                            if let Some(anchor) = ZoomAnchor::from_str(value) {
+                                config.zoom_anchor = anchor;
+                            }
+                        }
+                        "zoom_presets" | "zoom_preset_cycle" => {
+                            if let Some(presets) = ZoomPreset::parse_list(value) {
+                                config.zoom_presets = presets;
+                            }
+                        }
+                        "pan_min_visible" | "pan_clamp" | "pan_min_visible_fraction" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.pan_min_visible = v.clamp(0.0, 1.0);
+                            }
+                        }
+                        "keyboard_pan_step" | "keyboard_pan_step_px" | "pan_step" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.keyboard_pan_step = v.clamp(1.0, 1000.0);
+                            }
+                        }
+                        "keyboard_pan_acceleration" | "pan_acceleration" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.keyboard_pan_acceleration = v.clamp(1.0, 20.0);
+                            }
+                        }
+                        "magnifier_zoom" | "magnifier_factor" | "loupe_zoom" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.magnifier_zoom = v.clamp(
+                                    crate::magnifier::MIN_FACTOR,
+                                    crate::magnifier::MAX_FACTOR,
+                                );
+                            }
+                        }
                         "ctrl_scroll_up_pan_speed_px_per_step"
                         | "ctrl_scroll_up_pan_speed"
                         | "ctrl_scroll_up_pan_px"
@@ -1525,6 +3492,38 @@ impl Config {
                                 config.manga_arrow_scroll_speed = v.clamp(1.0, 5000.0);
                             }
                         }
+                        "manga_page_width_min_percent" | "manga_page_width_min" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.manga_page_width_min_percent = v.clamp(25.0, 100.0);
+                            }
+                        }
+                        "manga_page_width_max_percent" | "manga_page_width_max" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.manga_page_width_max_percent = v.clamp(100.0, 400.0);
+                            }
+                        }
+                        "manga_page_width_step_percent" | "manga_page_width_step" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.manga_page_width_step_percent = v.clamp(0.5, 50.0);
+                            }
+                        }
+                        "manga_blur_placeholders" | "manga_blur_up" | "blur_placeholders" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.manga_blur_placeholders = v;
+                            }
+                        }
+                        "manga_panel_snap" | "webtoon_panel_snap" | "panel_snap" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.manga_panel_snap = v;
+                            }
+                        }
+                        "manga_segment_tall_pages"
+                        | "manga_split_tall_pages"
+                        | "split_tall_pages" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.manga_segment_tall_pages = v;
+                            }
+                        }
                         "masonry_items_per_row" | "manga_masonry_items_per_row" => {
                             if let Ok(v) = value.parse::<usize>() {
                                 config.masonry_items_per_row = v.clamp(2, 10);
@@ -1657,6 +3656,16 @@ impl Config {
                                 config.single_instance = v;
                             }
                         }
+                        "crash_reports" | "crash_report" | "crash_handler" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.crash_reports = v;
+                            }
+                        }
+                        "usage_stats" | "usage_statistics" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.usage_stats = v;
+                            }
+                        }
                         "window_title_show_full_path"
                         | "show_full_path_in_title"
                         | "title_show_full_path"
@@ -1670,6 +3679,12 @@ impl Config {
                                 config.vsync = v;
                             }
                         }
+                        "cache_dir" | "cache_directory" | "data_dir" => {
+                            config.cache_dir = storage::parse_dir_setting(value);
+                        }
+                        "temp_dir" | "temp_directory" | "tmp_dir" => {
+                            config.temp_dir = storage::parse_dir_setting(value);
+                        }
                         "metadata_cache_max_size_mb"
                         | "metadata_cache_limit_mb"
                         | "metadata_cache_max_mb"
@@ -1688,6 +3703,11 @@ impl Config {
                                 config.masonry_metadata_ram_cache_limit_mb = v.clamp(1, 1_048_576);
                             }
                         }
+                        "decoded_image_cache_mb" | "image_cache_mb" | "decoded_cache_mb" => {
+                            if let Ok(v) = value.parse::<u64>() {
+                                config.decoded_image_cache_mb = v.min(65_536);
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -1723,6 +3743,71 @@ impl Config {
                                 config.video_loop = v;
                             }
                         }
+                        "advance_on_video_end" | "advance_on_end" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.advance_on_video_end = v;
+                            }
+                        }
+                        "loudness_normalization" | "normalize_loudness" | "replaygain" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.loudness_normalization = v;
+                            }
+                        }
+                        "loudness_target_lufs" | "loudness_target" => {
+                            if let Ok(v) = value.parse::<f32>() {
+                                config.loudness_target_lufs = v.clamp(-40.0, -5.0);
+                            }
+                        }
+                        "preload_next_video" | "preload_video" | "gapless_video" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.preload_next_video = v;
+                            }
+                        }
+                        "frame_pacing" | "video_frame_pacing" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.video_frame_pacing = v;
+                            }
+                        }
+                        "repaint_at_deadline" | "video_repaint_at_deadline" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.video_repaint_at_deadline = v;
+                            }
+                        }
+                        "seek_thumbnails" | "seek_preview" | "video_seek_thumbnails" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.video_seek_thumbnails = v;
+                            }
+                        }
+                        "resume_playback" | "resume" | "video_resume" => {
+                            if let Some(policy) = ResumePolicy::from_str(value) {
+                                config.video_resume = policy;
+                            }
+                        }
+                        "deinterlace" | "video_deinterlace" => {
+                            if let Some(mode) = DeinterlaceMode::from_str(value) {
+                                config.video_deinterlace = mode;
+                            }
+                        }
+                        "trim_mode" | "video_trim_mode" | "trim_format" => {
+                            if let Some(mode) = TrimMode::from_str(value) {
+                                config.video_trim_mode = mode;
+                            }
+                        }
+                        "ffmpeg_path" | "ffmpeg" | "ffmpeg_executable" => {
+                            config.ffmpeg_path = storage::parse_dir_setting(value);
+                        }
+                        "safe_areas" | "video_safe_areas" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.video_safe_areas = v;
+                            }
+                        }
+                        "aspect_mask" | "video_aspect_mask" | "letterbox" => {
+                            if matches!(value.trim().to_ascii_lowercase().as_str(), "" | "off") {
+                                config.video_aspect_mask = None;
+                            } else if let Some(ratio) = crate::video_guides::parse_aspect(value) {
+                                config.video_aspect_mask = Some(ratio);
+                            }
+                        }
                         "seek_policy" | "seek_mode" | "seek_behavior" => {
                             if let Some(policy) = VideoSeekPolicy::from_str(value) {
                                 config.video_seek_policy = policy;
@@ -1848,6 +3933,11 @@ impl Config {
                                 config.enable_d3d12 = v;
                             }
                         }
+                        "low_power_mode" | "low_power" | "power_saving" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.low_power_mode = v;
+                            }
+                        }
                         "show_fps" | "show_fps_overlay" | "fps_overlay" => {
                             if let Some(v) = parse_bool(value) {
                                 config.show_fps = v;
@@ -1860,11 +3950,58 @@ impl Config {
                                 config.show_fps_update_interval_ms = v.clamp(50, 10_000);
                             }
                         }
+                        "log_level" | "logging_level" => {
+                            if let Some(level) = LogLevel::from_str(value) {
+                                config.log_level = level;
+                            }
+                        }
+                        "log_to_file" | "log_file" | "file_logging" => {
+                            if let Some(v) = parse_bool(value) {
+                                config.log_to_file = v;
+                            }
+                        }
+                        "log_file_max_size_mb" | "log_max_size_mb" => {
+                            if let Ok(v) = value.parse::<u64>() {
+                                config.log_file_max_size_mb = v.clamp(1, 1024);
+                            }
+                        }
+                        "log_file_max_files" | "log_max_files" | "log_file_count" => {
+                            if let Ok(v) = value.parse::<usize>() {
+                                config.log_file_max_files = v.clamp(1, 50);
+                            }
+                        }
                         _ => {}
                     }
                 }
             }
 
+            // Parse key=value pairs in gestures section
+            if in_gestures_section {
+                if let Some((key, value)) = line.split_once('=') {
+                    let key = key.trim().to_lowercase();
+                    let value = value.trim();
+
+                    if matches!(
+                        key.as_str(),
+                        "mouse_gestures" | "mouse_gestures_enabled" | "gestures"
+                    ) {
+                        if let Some(v) = parse_bool(value) {
+                            config.mouse_gestures_enabled = v;
+                        }
+                    } else if let Some(gesture) = MouseGesture::from_ini_key(&key) {
+                        // Empty or unknown action names leave the gesture unbound.
+                        match Action::from_str(value) {
+                            Some(action) => {
+                                config.mouse_gestures.insert(gesture, action);
+                            }
+                            None => {
+                                config.mouse_gestures.remove(&gesture);
+                            }
+                        }
+                    }
+                }
+            }
+
             // Parse key=value pairs in state section
             if in_state_section {
                 if let Some((key, value)) = line.split_once('=') {
@@ -1887,6 +4024,27 @@ impl Config {
                                 config.state_show_breadcrumb_bar = v;
                             }
                         }
+                        "recent_files" => {
+                            config.state_recent_files = value
+                                .split('|')
+                                .map(str::trim)
+                                .filter(|path| !path.is_empty())
+                                .take(MAX_RECENT_FILES)
+                                .map(PathBuf::from)
+                                .collect();
+                        }
+                        "recent_luts" => {
+                            config.state_recent_luts = value
+                                .split('|')
+                                .map(str::trim)
+                                .filter(|path| !path.is_empty())
+                                .take(MAX_RECENT_LUTS)
+                                .map(PathBuf::from)
+                                .collect();
+                        }
+                        "floating_placement" => {
+                            config.state_floating_placement = SavedPlacement::parse(value);
+                        }
                         _ => {}
                     }
                 }
@@ -1904,11 +4062,39 @@ impl Config {
         }
 
         config.migrate_legacy_toggle_fullscreen_binding();
+        config.migrate_legacy_exit_escape_binding();
         config.migrate_legacy_modifier_wheel_defaults();
 
         config
     }
 
+    /// Color PNG exports are flattened onto, or `None` to keep alpha.
+    pub fn export_flatten_background(&self) -> Option<[u8; 3]> {
+        (self.export_alpha == ExportAlpha::Flatten).then_some(self.export_background_rgb)
+    }
+
+    /// Moves `path` to the front of the recent files. Returns `false` when it already was.
+    pub fn push_recent_file(&mut self, path: &std::path::Path) -> bool {
+        if self
+            .state_recent_files
+            .first()
+            .is_some_and(|first| first == path)
+        {
+            return false;
+        }
+        self.state_recent_files.retain(|recent| recent != path);
+        self.state_recent_files.insert(0, path.to_path_buf());
+        self.state_recent_files.truncate(MAX_RECENT_FILES);
+        true
+    }
+
+    /// Moves `path` to the front of the recent LUTs.
+    pub fn push_recent_lut(&mut self, path: &std::path::Path) {
+        self.state_recent_luts.retain(|recent| recent != path);
+        self.state_recent_luts.insert(0, path.to_path_buf());
+        self.state_recent_luts.truncate(MAX_RECENT_LUTS);
+    }
+
     /// Save configuration to INI file
     pub fn save(&self) {
         let content = self.render_ini_from_template();
@@ -1937,15 +4123,28 @@ impl Config {
         }
     }
 
-    fn render_ini_from_template(&self) -> String {
+    /// The config as it would be saved: template order, comments, every binding spelled out.
+    pub fn render_ini_from_template(&self) -> String {
         let values = self.ini_value_replacements();
         let default_config = default_config_ini();
         let mut rendered = String::with_capacity(default_config.len() + 256);
+        let mut shortcuts_context = None;
 
         for line in default_config.split_inclusive('\n') {
             let (line_body, line_ending) = split_line_ending(line);
             let trimmed = line_body.trim_start();
 
+            if trimmed.starts_with('[') {
+                if let Some(context) = shortcuts_context.take() {
+                    self.render_context_bindings(context, &mut rendered);
+                }
+                shortcuts_context = trimmed
+                    .trim_end()
+                    .strip_prefix('[')
+                    .and_then(|section| section.strip_suffix(']'))
+                    .and_then(BindingContext::from_section);
+            }
+
             if trimmed.starts_with(';') || trimmed.starts_with('#') || trimmed.starts_with('[') {
                 rendered.push_str(line_body);
                 rendered.push_str(line_ending);
@@ -1980,11 +4179,121 @@ impl Config {
                 }
             }
 
-            rendered.push_str(line_body);
-            rendered.push_str(line_ending);
+            rendered.push_str(line_body);
+            rendered.push_str(line_ending);
+        }
+        if let Some(context) = shortcuts_context {
+            self.render_context_bindings(context, &mut rendered);
+        }
+
+        rendered
+    }
+
+    /// Appends the `context` overrides as `action = bindings` lines followed by a blank line,
+    /// in [`Action::ALL`] order.
+    fn render_context_bindings(&self, context: BindingContext, rendered: &mut String) {
+        let Some(overrides) = self.context_bindings.get(&context) else {
+            return;
+        };
+        for action in Action::ALL {
+            if let Some(bindings) = overrides.get(&action) {
+                let bindings = bindings
+                    .iter()
+                    .map(binding_to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                rendered.push_str(&format!("{} = {bindings}\n", action.as_str()));
+            }
+        }
+        if !overrides.is_empty() {
+            rendered.push('\n');
+        }
+    }
+
+    /// Template sections with this config's current values, for the settings window.
+    ///
+    /// `[State]` and alias keys (e.g. `gallery_*`, `background_r`) are left out; editing the
+    /// canonical key updates them too.
+    pub fn editable_ini_sections(&self) -> Vec<IniSection> {
+        let values = self.ini_value_replacements();
+        let mut sections: Vec<IniSection> = Vec::new();
+        let mut comment_lines: Vec<&str> = Vec::new();
+        let mut previous_was_key = false;
+
+        for line in default_config_ini().lines() {
+            let trimmed = line.trim();
+
+            if trimmed.is_empty() {
+                comment_lines.clear();
+                previous_was_key = false;
+                continue;
+            }
+
+            if let Some(comment) = trimmed.strip_prefix(';') {
+                if previous_was_key {
+                    comment_lines.clear();
+                    previous_was_key = false;
+                }
+                let comment = comment.trim();
+                if !comment.starts_with("===") {
+                    comment_lines.push(comment);
+                }
+                continue;
+            }
+
+            if trimmed.starts_with('[') && trimmed.ends_with(']') {
+                let name = &trimmed[1..trimmed.len() - 1];
+                sections.push(IniSection {
+                    name: name.to_string(),
+                    entries: Vec::new(),
+                });
+                comment_lines.clear();
+                previous_was_key = false;
+                continue;
+            }
+
+            let Some((key, template_value)) = trimmed.split_once('=') else {
+                continue;
+            };
+            // Consecutive keys share the comment block above the first one.
+            previous_was_key = true;
+
+            let key = key.trim();
+            let Some(section) = sections.last_mut() else {
+                continue;
+            };
+            if section.name.eq_ignore_ascii_case("state") || ini_key_is_alias(key) {
+                continue;
+            }
+
+            section.entries.push(IniEntry {
+                key: key.to_string(),
+                value: values
+                    .get(key)
+                    .cloned()
+                    .unwrap_or_else(|| template_value.trim().to_string()),
+                comment: comment_lines.join("\n"),
+            });
         }
 
-        rendered
+        sections.retain(|section| !section.entries.is_empty());
+        sections
+    }
+
+    /// Re-parse this config with `overrides` applied on top.
+    ///
+    /// Values go through the regular INI parser, so they are validated and clamped exactly
+    /// like hand edits to config.ini. Later sections win, which also keeps alias keys in sync.
+    pub fn with_ini_overrides(&self, overrides: &[IniOverride]) -> Self {
+        let mut content = self.render_ini_from_template();
+        for entry in overrides {
+            content.push_str(&format!(
+                "\n[{}]\n{} = {}\n",
+                entry.section, entry.key, entry.value
+            ));
+        }
+
+        Self::parse_ini(&content)
     }
 
     fn cuda_enable_runtime_comment(&self) -> Option<&'static str> {
@@ -2014,12 +4323,105 @@ impl Config {
             "double_click_grace_period",
             format_with_optional_trailing_zero_f64(self.double_click_grace_period),
         );
+        values.insert(
+            "low_power_mode",
+            bool_to_ini(self.low_power_mode).to_string(),
+        );
         values.insert("show_fps", bool_to_ini(self.show_fps).to_string());
         values.insert(
             "show_fps_update_interval_ms",
             format!("{}", self.show_fps_update_interval_ms),
         );
+        values.insert("log_level", self.log_level.as_str().to_string());
+        values.insert("log_to_file", bool_to_ini(self.log_to_file).to_string());
+        values.insert(
+            "log_file_max_size_mb",
+            format!("{}", self.log_file_max_size_mb),
+        );
+        values.insert("log_file_max_files", format!("{}", self.log_file_max_files));
         values.insert("resize_border_size", format!("{}", self.resize_border_size));
+        values.insert(
+            "touch_pinch_zoom",
+            bool_to_ini(self.touch_pinch_zoom).to_string(),
+        );
+        values.insert(
+            "touch_two_finger_pan",
+            bool_to_ini(self.touch_two_finger_pan).to_string(),
+        );
+        values.insert(
+            "touch_swipe_navigation",
+            bool_to_ini(self.touch_swipe_navigation).to_string(),
+        );
+        values.insert(
+            "touch_double_tap_fullscreen",
+            bool_to_ini(self.touch_double_tap_fullscreen).to_string(),
+        );
+        values.insert("edge_pan", bool_to_ini(self.edge_pan).to_string());
+        values.insert(
+            "edge_pan_margin",
+            format_with_optional_trailing_zero_f32(self.edge_pan_margin),
+        );
+        values.insert(
+            "edge_pan_speed",
+            format_with_optional_trailing_zero_f32(self.edge_pan_speed),
+        );
+        values.insert(
+            "click_zone_button",
+            self.click_zone_button.as_str().to_string(),
+        );
+        values.insert(
+            "click_zones",
+            self.click_zones
+                .iter()
+                .map(|zone| zone.to_config_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+        values.insert(
+            "always_on_top_compact",
+            bool_to_ini(self.always_on_top_compact).to_string(),
+        );
+        values.insert(
+            "always_on_top_compact_size",
+            format_with_optional_trailing_zero_f32(self.always_on_top_compact_size),
+        );
+        values.insert(
+            "wallpaper_interval_minutes",
+            format!("{}", self.wallpaper_interval_minutes),
+        );
+        values.insert(
+            "slideshow_interval_seconds",
+            format_with_optional_trailing_zero_f32(self.slideshow_interval_seconds),
+        );
+        values.insert(
+            "transition_effect",
+            self.transition_effect.as_str().to_string(),
+        );
+        values.insert(
+            "transition_duration_ms",
+            format!("{}", self.transition_duration_ms),
+        );
+        values.insert(
+            "transition_easing",
+            self.transition_easing.as_str().to_string(),
+        );
+        values.insert("playlist_repeat", self.playlist_repeat.as_str().to_string());
+        values.insert(
+            "playlist_shuffle",
+            bool_to_ini(self.playlist_shuffle).to_string(),
+        );
+        values.insert("url_prefetch_count", format!("{}", self.url_prefetch_count));
+        values.insert("tray_icon", bool_to_ini(self.tray_icon).to_string());
+        values.insert(
+            "minimize_to_tray",
+            bool_to_ini(self.minimize_to_tray).to_string(),
+        );
+        values.insert(
+            "remote_control",
+            bool_to_ini(self.remote_control).to_string(),
+        );
+        values.insert("remote_control_allow", self.remote_control_allow.join(", "));
+        values.insert("confirm_exit", bool_to_ini(self.confirm_exit).to_string());
         values.insert(
             "startup_window_mode",
             self.startup_window_mode.as_str().to_string(),
@@ -2028,6 +4430,19 @@ impl Config {
             "single_instance",
             bool_to_ini(self.single_instance).to_string(),
         );
+        values.insert("crash_reports", bool_to_ini(self.crash_reports).to_string());
+        values.insert("usage_stats", bool_to_ini(self.usage_stats).to_string());
+        values.insert(
+            "mouse_gestures",
+            bool_to_ini(self.mouse_gestures_enabled).to_string(),
+        );
+        for (gesture, key) in MouseGesture::all() {
+            let action = self
+                .mouse_gestures
+                .get(&gesture)
+                .map(|action| action.as_str());
+            values.insert(key, action.unwrap_or_default().to_string());
+        }
         values.insert(
             "window_title_show_full_path",
             self.window_title_show_full_path.as_str().to_string(),
@@ -2039,6 +4454,14 @@ impl Config {
         );
         values.insert("enable_d3d12", bool_to_ini(self.enable_d3d12).to_string());
         values.insert("enable_cuda", bool_to_ini(self.enable_cuda).to_string());
+        for (key, dir) in [("cache_dir", &self.cache_dir), ("temp_dir", &self.temp_dir)] {
+            values.insert(
+                key,
+                dir.as_ref()
+                    .map(|dir| dir.display().to_string())
+                    .unwrap_or_default(),
+            );
+        }
         values.insert(
             "metadata_cache_max_size_mb",
             format!("{}", self.metadata_cache_max_size_mb),
@@ -2047,6 +4470,21 @@ impl Config {
             "masonry_metadata_ram_cache_limit_mb",
             format!("{}", self.masonry_metadata_ram_cache_limit_mb),
         );
+        values.insert(
+            "decoded_image_cache_mb",
+            format!("{}", self.decoded_image_cache_mb),
+        );
+        values.insert("show_osd", bool_to_ini(self.show_osd).to_string());
+        values.insert(
+            "ui_scale",
+            format_with_optional_trailing_zero_f32(self.ui_scale),
+        );
+        values.insert(
+            "language",
+            self.language
+                .map_or("auto", |language| language.as_str())
+                .to_string(),
+        );
         values.insert(
             "background_rgb",
             format!(
@@ -2057,6 +4495,108 @@ impl Config {
         values.insert("background_r", format!("{}", self.background_rgb[0]));
         values.insert("background_g", format!("{}", self.background_rgb[1]));
         values.insert("background_b", format!("{}", self.background_rgb[2]));
+        values.insert(
+            "checkerboard_background",
+            bool_to_ini(self.checkerboard_background).to_string(),
+        );
+        values.insert("checkerboard_size", format!("{}", self.checkerboard_size));
+        for (key, enabled) in [
+            ("overlay_thirds", self.overlay_thirds),
+            ("overlay_grid", self.overlay_grid),
+            ("overlay_crosshair", self.overlay_crosshair),
+            ("pixel_grid", self.pixel_grid),
+        ] {
+            values.insert(key, bool_to_ini(enabled).to_string());
+        }
+        values.insert("grid_divisions", format!("{}", self.grid_divisions));
+        for (key, rgb) in [
+            ("checkerboard_light_rgb", self.checkerboard_light_rgb),
+            ("checkerboard_dark_rgb", self.checkerboard_dark_rgb),
+            ("overlay_accent_rgb", self.overlay_accent_rgb),
+            (
+                "overlay_animation_accent_rgb",
+                self.overlay_animation_accent_rgb,
+            ),
+        ] {
+            values.insert(key, format!("{}, {}, {}", rgb[0], rgb[1], rgb[2]));
+        }
+        for (key, rgb) in [
+            ("overlay_background_rgb", self.overlay_background_rgb),
+            ("overlay_text_rgb", self.overlay_text_rgb),
+        ] {
+            values.insert(
+                key,
+                rgb.map_or_else(
+                    || "auto".to_string(),
+                    |rgb| format!("{}, {}, {}", rgb[0], rgb[1], rgb[2]),
+                ),
+            );
+        }
+        values.insert("overlay_theme", self.overlay_theme.as_str().to_string());
+        values.insert(
+            "overlay_opacity",
+            format_with_optional_trailing_zero_f32(self.overlay_opacity),
+        );
+        values.insert(
+            "overlay_corner_radius",
+            format_with_optional_trailing_zero_f32(self.overlay_corner_radius),
+        );
+        values.insert(
+            "title_bar_height",
+            format_with_optional_trailing_zero_f32(self.title_bar_height),
+        );
+        values.insert(
+            "video_bar_height",
+            format_with_optional_trailing_zero_f32(self.video_bar_height),
+        );
+        values.insert("pdf_page_size", self.pdf_page_size.as_str().to_string());
+        values.insert(
+            "pdf_margin_mm",
+            format_with_optional_trailing_zero_f32(self.pdf_margin_mm),
+        );
+        values.insert(
+            "animation_export_quality",
+            format!("{}", self.animation_export_quality),
+        );
+        values.insert(
+            "animation_export_fps",
+            format!("{}", self.animation_export_fps),
+        );
+        values.insert("export_alpha", self.export_alpha.as_str().to_string());
+        let [r, g, b] = self.export_background_rgb;
+        values.insert("export_background_rgb", format!("{r}, {g}, {b}"));
+        values.insert("monitor_wall_count", format!("{}", self.monitor_wall_count));
+        values.insert(
+            "annotation_stroke_prediction",
+            bool_to_ini(self.annotation_stroke_prediction).to_string(),
+        );
+        values.insert("rating_filter", self.rating_filter.as_str().to_string());
+        for (key, folder) in [
+            "sort_folder_1",
+            "sort_folder_2",
+            "sort_folder_3",
+            "sort_folder_4",
+        ]
+        .into_iter()
+        .zip(&self.sort_folders)
+        {
+            values.insert(
+                key,
+                folder
+                    .as_ref()
+                    .map(|folder| folder.display().to_string())
+                    .unwrap_or_default(),
+            );
+        }
+        values.insert("sort_operation", self.sort_operation.as_str().to_string());
+        values.insert("plugin_filters", self.plugin_filters.join(", "));
+        values.insert(
+            "external_editor",
+            self.external_editor
+                .as_ref()
+                .map(|editor| editor.display().to_string())
+                .unwrap_or_default(),
+        );
         values.insert(
             "marked_file_border_rgb",
             format!(
@@ -2082,6 +4622,22 @@ impl Config {
             "fullscreen_reset_fit_on_enter",
             bool_to_ini(self.fullscreen_reset_fit_on_enter).to_string(),
         );
+        values.insert(
+            "fullscreen_fit_mode",
+            self.fullscreen_fit_mode.as_str().to_string(),
+        );
+        values.insert(
+            "floating_fit_mode",
+            self.floating_fit_mode.as_str().to_string(),
+        );
+        values.insert(
+            "window_size_presets",
+            self.window_size_presets
+                .iter()
+                .map(SizePreset::label)
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
         values.insert(
             "fullscreen_native_window_transition",
             bool_to_ini(self.fullscreen_native_window_transition).to_string(),
@@ -2139,6 +4695,31 @@ impl Config {
             format_with_optional_trailing_zero_f32(self.precise_rotation_step_degrees),
         );
         values.insert("zoom_step", format!("{}", self.zoom_step));
+        values.insert("zoom_anchor", self.zoom_anchor.as_str().to_string());
+        values.insert(
+            "zoom_presets",
+            self.zoom_presets
+                .iter()
+                .map(|preset| preset.to_config_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+        values.insert(
+            "pan_min_visible",
+            format_with_optional_trailing_zero_f32(self.pan_min_visible),
+        );
+        values.insert(
+            "keyboard_pan_step",
+            format_with_optional_trailing_zero_f32(self.keyboard_pan_step),
+        );
+        values.insert(
+            "keyboard_pan_acceleration",
+            format_with_optional_trailing_zero_f32(self.keyboard_pan_acceleration),
+        );
+        values.insert(
+            "magnifier_zoom",
+            format_with_optional_trailing_zero_f32(self.magnifier_zoom),
+        );
         values.insert(
             "ctrl_scroll_up_pan_speed_px_per_step",
             format_with_optional_trailing_zero_f32(self.ctrl_scroll_up_pan_speed_px_per_step),
@@ -2184,6 +4765,30 @@ impl Config {
             "manga_arrow_scroll_speed",
             format!("{}", self.manga_arrow_scroll_speed),
         );
+        values.insert(
+            "manga_page_width_min_percent",
+            format!("{}", self.manga_page_width_min_percent),
+        );
+        values.insert(
+            "manga_page_width_max_percent",
+            format!("{}", self.manga_page_width_max_percent),
+        );
+        values.insert(
+            "manga_page_width_step_percent",
+            format!("{}", self.manga_page_width_step_percent),
+        );
+        values.insert(
+            "manga_blur_placeholders",
+            bool_to_ini(self.manga_blur_placeholders).to_string(),
+        );
+        values.insert(
+            "manga_panel_snap",
+            bool_to_ini(self.manga_panel_snap).to_string(),
+        );
+        values.insert(
+            "manga_segment_tall_pages",
+            bool_to_ini(self.manga_segment_tall_pages).to_string(),
+        );
         values.insert(
             "masonry_items_per_row",
             format!("{}", self.masonry_items_per_row),
@@ -2269,6 +4874,50 @@ impl Config {
             },
         );
         values.insert("loop", bool_to_ini(self.video_loop).to_string());
+        values.insert(
+            "advance_on_video_end",
+            bool_to_ini(self.advance_on_video_end).to_string(),
+        );
+        values.insert(
+            "loudness_normalization",
+            bool_to_ini(self.loudness_normalization).to_string(),
+        );
+        values.insert(
+            "loudness_target_lufs",
+            format_with_optional_trailing_zero_f32(self.loudness_target_lufs),
+        );
+        values.insert(
+            "preload_next_video",
+            bool_to_ini(self.preload_next_video).to_string(),
+        );
+        values.insert(
+            "frame_pacing",
+            bool_to_ini(self.video_frame_pacing).to_string(),
+        );
+        values.insert(
+            "repaint_at_deadline",
+            bool_to_ini(self.video_repaint_at_deadline).to_string(),
+        );
+        values.insert(
+            "seek_thumbnails",
+            bool_to_ini(self.video_seek_thumbnails).to_string(),
+        );
+        values.insert("resume_playback", self.video_resume.as_str().to_string());
+        values.insert("deinterlace", self.video_deinterlace.as_str().to_string());
+        values.insert("trim_mode", self.video_trim_mode.as_str().to_string());
+        values.insert(
+            "ffmpeg_path",
+            self.ffmpeg_path
+                .as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default(),
+        );
+        values.insert("safe_areas", bool_to_ini(self.video_safe_areas).to_string());
+        values.insert(
+            "aspect_mask",
+            self.video_aspect_mask
+                .map_or_else(|| "off".to_string(), crate::video_guides::aspect_label),
+        );
         values.insert("seek_policy", self.video_seek_policy.as_str().to_string());
         values.insert(
             "prefer_hardware_decode",
@@ -2303,6 +4952,28 @@ impl Config {
             "show_breadcrumb_bar",
             bool_to_ini(self.state_show_breadcrumb_bar).to_string(),
         );
+        values.insert(
+            "recent_files",
+            self.state_recent_files
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(" | "),
+        );
+        values.insert(
+            "recent_luts",
+            self.state_recent_luts
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(" | "),
+        );
+        values.insert(
+            "floating_placement",
+            self.state_floating_placement
+                .map(SavedPlacement::to_ini_string)
+                .unwrap_or_default(),
+        );
 
         values.insert("upscale_filter", self.upscale_filter.as_str().to_string());
         values.insert(
@@ -2369,15 +5040,194 @@ impl Config {
             "precise_rotation_counterclockwise",
             self.action_bindings_csv(Action::PreciseRotationCounterClockwise),
         );
+        values.insert("free_rotate", self.action_bindings_csv(Action::FreeRotate));
         values.insert("zoom_in", self.action_bindings_csv(Action::ZoomIn));
         values.insert("zoom_out", self.action_bindings_csv(Action::ZoomOut));
         values.insert("exit", self.action_bindings_csv(Action::Exit));
+        values.insert(
+            "exit_fullscreen_or_quit",
+            self.action_bindings_csv(Action::ExitFullscreenOrQuit),
+        );
+        values.insert(
+            "open_settings",
+            self.action_bindings_csv(Action::OpenSettings),
+        );
+        values.insert(
+            "command_palette",
+            self.action_bindings_csv(Action::CommandPalette),
+        );
+        values.insert(
+            "filter_folder",
+            self.action_bindings_csv(Action::FilterFolder),
+        );
+        values.insert(
+            "move_to_next_monitor",
+            self.action_bindings_csv(Action::MoveToNextMonitor),
+        );
+        for (key, action) in [
+            ("snap_window_left", Action::SnapWindowLeft),
+            ("snap_window_right", Action::SnapWindowRight),
+            ("snap_window_up", Action::SnapWindowUp),
+            ("snap_window_down", Action::SnapWindowDown),
+            ("window_size_preset_1", Action::WindowSizePreset1),
+            ("window_size_preset_2", Action::WindowSizePreset2),
+            ("window_size_preset_3", Action::WindowSizePreset3),
+        ] {
+            values.insert(key, self.action_bindings_csv(action));
+        }
+        values.insert(
+            "toggle_always_on_top",
+            self.action_bindings_csv(Action::ToggleAlwaysOnTop),
+        );
+        values.insert(
+            "toggle_slideshow",
+            self.action_bindings_csv(Action::ToggleSlideshow),
+        );
+        for (key, action) in [
+            ("toggle_playlist", Action::TogglePlaylistPanel),
+            ("toggle_shuffle", Action::TogglePlaylistShuffle),
+            ("cycle_repeat", Action::CyclePlaylistRepeat),
+        ] {
+            values.insert(key, self.action_bindings_csv(action));
+        }
         values.insert("pan", self.action_bindings_csv(Action::Pan));
+        values.insert(
+            "save_oriented_copy",
+            self.action_bindings_csv(Action::SaveOrientedCopy),
+        );
+        values.insert(
+            "toggle_annotations",
+            self.action_bindings_csv(Action::ToggleAnnotations),
+        );
+        values.insert(
+            "toggle_adjustments",
+            self.action_bindings_csv(Action::ToggleAdjustments),
+        );
+        values.insert(
+            "toggle_checkerboard",
+            self.action_bindings_csv(Action::ToggleCheckerboard),
+        );
+        values.insert(
+            "toggle_thirds",
+            self.action_bindings_csv(Action::ToggleThirds),
+        );
+        values.insert("toggle_grid", self.action_bindings_csv(Action::ToggleGrid));
+        values.insert(
+            "toggle_crosshair",
+            self.action_bindings_csv(Action::ToggleCrosshair),
+        );
+        values.insert("straighten", self.action_bindings_csv(Action::Straighten));
+        values.insert("perspective", self.action_bindings_csv(Action::Perspective));
+        values.insert(
+            "toggle_eyedropper",
+            self.action_bindings_csv(Action::ToggleEyedropper),
+        );
+        values.insert("magnifier", self.action_bindings_csv(Action::Magnifier));
+        values.insert(
+            "show_original",
+            self.action_bindings_csv(Action::ShowOriginal),
+        );
+        values.insert(
+            "stereo_export",
+            self.action_bindings_csv(Action::StereoExport),
+        );
+        values.insert(
+            "scan_cleanup",
+            self.action_bindings_csv(Action::ScanCleanup),
+        );
+        values.insert("export_pdf", self.action_bindings_csv(Action::ExportPdf));
+        values.insert(
+            "convert_animation",
+            self.action_bindings_csv(Action::ConvertAnimation),
+        );
+        values.insert(
+            "batch_rename",
+            self.action_bindings_csv(Action::BatchRename),
+        );
+        values.insert(
+            "monitor_wall",
+            self.action_bindings_csv(Action::MonitorWall),
+        );
+        values.insert(
+            "cycle_fit_mode",
+            self.action_bindings_csv(Action::CycleFitMode),
+        );
+        values.insert("fit_whole", self.action_bindings_csv(Action::FitWhole));
+        values.insert("fit_width", self.action_bindings_csv(Action::FitWidth));
+        values.insert("fit_height", self.action_bindings_csv(Action::FitHeight));
+        values.insert("fit_fill", self.action_bindings_csv(Action::FitFill));
+        values.insert(
+            "fit_actual_size",
+            self.action_bindings_csv(Action::FitActualSize),
+        );
+        values.insert(
+            "cycle_zoom_presets",
+            self.action_bindings_csv(Action::CycleZoomPresets),
+        );
+        values.insert("zoom_25", self.action_bindings_csv(Action::Zoom25));
+        values.insert("zoom_50", self.action_bindings_csv(Action::Zoom50));
+        values.insert("zoom_100", self.action_bindings_csv(Action::Zoom100));
+        values.insert("zoom_200", self.action_bindings_csv(Action::Zoom200));
+        values.insert("zoom_400", self.action_bindings_csv(Action::Zoom400));
+        values.insert("pan_left", self.action_bindings_csv(Action::PanLeft));
+        values.insert("pan_right", self.action_bindings_csv(Action::PanRight));
+        values.insert("pan_up", self.action_bindings_csv(Action::PanUp));
+        values.insert("pan_down", self.action_bindings_csv(Action::PanDown));
+        values.insert(
+            "clear_rating",
+            self.action_bindings_csv(Action::ClearRating),
+        );
+        for (key, action) in [
+            ("rate_1", Action::Rate1),
+            ("rate_2", Action::Rate2),
+            ("rate_3", Action::Rate3),
+            ("rate_4", Action::Rate4),
+            ("rate_5", Action::Rate5),
+            ("toggle_pick", Action::TogglePick),
+            ("toggle_reject", Action::ToggleReject),
+            ("cycle_rating_filter", Action::CycleRatingFilter),
+            ("sort_to_folder_1", Action::SortToFolder1),
+            ("sort_to_folder_2", Action::SortToFolder2),
+            ("sort_to_folder_3", Action::SortToFolder3),
+            ("sort_to_folder_4", Action::SortToFolder4),
+            ("open_in_editor", Action::OpenInEditor),
+        ] {
+            values.insert(key, self.action_bindings_csv(action));
+        }
+        values.insert(
+            "compare_images",
+            self.action_bindings_csv(Action::CompareImages),
+        );
+        values.insert(
+            "copy_view",
+            self.action_bindings_csv(Action::CopyViewToClipboard),
+        );
+        values.insert("new_window", self.action_bindings_csv(Action::NewWindow));
+        values.insert(
+            "toggle_zoom_sync",
+            self.action_bindings_csv(Action::ToggleZoomSync),
+        );
+        for (key, action) in [
+            ("ui_scale_up", Action::UiScaleUp),
+            ("ui_scale_down", Action::UiScaleDown),
+            ("reset_ui_scale", Action::ResetUiScale),
+        ] {
+            values.insert(key, self.action_bindings_csv(action));
+        }
         values.insert(
             "video_play_pause",
             self.action_bindings_csv(Action::VideoPlayPause),
         );
         values.insert("video_mute", self.action_bindings_csv(Action::VideoMute));
+        values.insert(
+            "video_trim_in",
+            self.action_bindings_csv(Action::VideoTrimIn),
+        );
+        values.insert(
+            "video_trim_out",
+            self.action_bindings_csv(Action::VideoTrimOut),
+        );
+        values.insert("video_trim", self.action_bindings_csv(Action::VideoTrim));
         values.insert(
             "manga_zoom_in",
             self.action_bindings_csv(Action::MangaZoomIn),
@@ -2386,6 +5236,18 @@ impl Config {
             "manga_zoom_out",
             self.action_bindings_csv(Action::MangaZoomOut),
         );
+        values.insert(
+            "manga_toggle_page_width",
+            self.action_bindings_csv(Action::MangaTogglePageWidth),
+        );
+        values.insert(
+            "manga_rotate_page",
+            self.action_bindings_csv(Action::MangaRotatePage),
+        );
+        values.insert(
+            "manga_cycle_page_fit",
+            self.action_bindings_csv(Action::MangaCyclePageFit),
+        );
         values.insert("manga_pan", self.action_bindings_csv(Action::MangaPan));
         values.insert(
             "manga_goto_file",
@@ -2539,13 +5401,6 @@ impl Config {
             .unwrap_or_default()
     }
 
-    /// Check if an input matches a specific action
-    pub fn is_action(&self, input: &InputBinding, action: Action) -> bool {
-        self.action_bindings
-            .get(&action)
-            .is_some_and(|bindings| bindings.contains(input))
-    }
-
     /// Get all bindings for an action
     pub fn get_bindings(&self, action: Action) -> Vec<InputBinding> {
         self.action_bindings
@@ -2554,14 +5409,45 @@ impl Config {
             .unwrap_or_default()
     }
 
-    pub fn action_uses_binding(&self, action: Action, binding: &InputBinding) -> bool {
-        self.is_action(binding, action)
+    /// Bindings of `action` while `context` media is shown. An action listed in the context
+    /// section uses that list; any other action keeps its `[Shortcuts]` bindings except those
+    /// the context section gives to another action.
+    pub fn bindings_in(&self, context: BindingContext, action: Action) -> Cow<'_, [InputBinding]> {
+        let general = self
+            .action_bindings
+            .get(&action)
+            .map_or(&[][..], Vec::as_slice);
+        let Some(overrides) = self.context_bindings.get(&context) else {
+            return Cow::Borrowed(general);
+        };
+        if let Some(own) = overrides.get(&action) {
+            return Cow::Borrowed(own);
+        }
+        let claimed = |binding: &InputBinding| {
+            overrides
+                .values()
+                .any(|bindings| bindings.contains(binding))
+        };
+        if general.iter().any(claimed) {
+            Cow::Owned(general.iter().filter(|b| !claimed(b)).cloned().collect())
+        } else {
+            Cow::Borrowed(general)
+        }
+    }
+
+    pub fn action_uses_binding(
+        &self,
+        context: BindingContext,
+        action: Action,
+        binding: &InputBinding,
+    ) -> bool {
+        self.bindings_in(context, action).contains(binding)
     }
 
-    pub fn any_action_uses_binding(&self, binding: &InputBinding) -> bool {
-        self.action_bindings
-            .values()
-            .any(|bindings| bindings.contains(binding))
+    pub fn any_action_uses_binding(&self, context: BindingContext, binding: &InputBinding) -> bool {
+        Action::ALL
+            .into_iter()
+            .any(|action| self.action_uses_binding(context, action, binding))
     }
 
     pub fn update_video_state(&mut self, muted: bool, volume: f64) {
@@ -2586,7 +5472,7 @@ fn parse_binding_list(value: &str) -> Vec<InputBinding> {
 }
 
 /// Convert InputBinding back to string representation
-fn binding_to_string(binding: &InputBinding) -> String {
+pub fn binding_to_string(binding: &InputBinding) -> String {
     match binding {
         InputBinding::Key(key) => key_to_string(key),
         InputBinding::MouseLeft => "mouse_left".to_string(),
@@ -2603,6 +5489,7 @@ fn binding_to_string(binding: &InputBinding) -> String {
         InputBinding::KeyWithCtrl(key) => format!("ctrl+{}", key_to_string(key)),
         InputBinding::KeyWithShift(key) => format!("shift+{}", key_to_string(key)),
         InputBinding::KeyWithAlt(key) => format!("alt+{}", key_to_string(key)),
+        InputBinding::KeyWithCtrlShift(key) => format!("ctrl+shift+{}", key_to_string(key)),
     }
 }
 
@@ -2627,6 +5514,65 @@ fn key_to_string(key: &egui::Key) -> String {
     format!("{:?}", key).to_lowercase()
 }
 
+/// Keys that only mirror another key in the template (legacy names).
+fn ini_key_is_alias(key: &str) -> bool {
+    key.starts_with("gallery_") || matches!(key, "background_r" | "background_g" | "background_b")
+}
+
+/// Allowed values for enum-like keys, in config.ini syntax.
+pub fn ini_value_choices(section: &str, key: &str) -> Option<&'static [&'static str]> {
+    const IMAGE_FILTERS: &[&str] = &["nearest", "triangle", "catmullrom", "gaussian", "lanczos3"];
+    const TEXTURE_FILTERS: &[&str] = &["nearest", "linear"];
+    const MODIFIERS: &[&str] = &["none", "ctrl", "shift", "alt"];
+
+    match (section.to_ascii_lowercase().as_str(), key) {
+        ("settings", "startup_window_mode") => Some(&["floating", "fullscreen"]),
+        ("settings", "window_title_show_full_path") => Some(&["auto", "true", "false"]),
+        ("settings", "language") => Some(&["auto", "en", "ja", "zh"]),
+        ("settings", "overlay_theme") => Some(&["dark", "light", "auto"]),
+        ("settings", "manga_virtualization_backend") => Some(&["rtree", "linear", "auto"]),
+        ("settings", "playlist_repeat") => Some(&["off", "all", "one"]),
+        ("settings", "export_alpha") => Some(&["keep", "flatten"]),
+        ("settings", "transition_effect") => Some(&["off", "crossfade", "slide", "zoom"]),
+        ("settings", "transition_easing") => Some(&["linear", "ease_out", "ease_in_out"]),
+        ("shortcuts", key) if key.ends_with("toggle_mark_file") => Some(MODIFIERS),
+        ("video", "muted_by_default") => Some(&["true", "false", "remember"]),
+        ("video", "seek_policy") => Some(&["adaptive", "accurate", "keyframe"]),
+        ("video", "resume_playback") => Some(&["always", "ask", "never"]),
+        ("video", "deinterlace") => Some(&["auto", "on", "off"]),
+        ("video", "trim_mode") => Some(&["copy", "mp4"]),
+        ("performance", "log_level") => Some(&["off", "error", "warn", "info", "debug", "trace"]),
+        ("performance", "upscale_filter" | "downscale_filter" | "gif_resize_filter") => {
+            Some(IMAGE_FILTERS)
+        }
+        ("performance", key) if key.starts_with("texture_filter_") => Some(TEXTURE_FILTERS),
+        _ => None,
+    }
+}
+
+/// Keys that are only read at startup; the settings window flags them as needing a restart.
+pub fn ini_key_requires_restart(key: &str) -> bool {
+    matches!(
+        key,
+        "startup_window_mode"
+            | "single_instance"
+            | "vsync"
+            | "use_hardware_acceleration"
+            | "enable_d3d12"
+            | "enable_cuda"
+            | "prefer_hardware_decode"
+            | "disable_hardware_decode"
+            | "log_level"
+            | "log_to_file"
+            | "log_file_max_size_mb"
+            | "log_file_max_files"
+            | "masonry_metadata_ram_cache_limit_mb"
+            | "tray_icon"
+            | "cache_dir"
+            | "temp_dir"
+    )
+}
+
 fn bool_to_ini(value: bool) -> &'static str {
     if value {
         "true"
@@ -2769,3 +5715,35 @@ fn parse_u8_clamped(value: &str) -> Option<u8> {
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Action, Config, InputBinding, DEFAULT_CONFIG_TEMPLATE};
+
+    #[test]
+    fn old_default_exit_binding_gives_escape_to_exit_fullscreen_or_quit() {
+        let old_default = DEFAULT_CONFIG_TEMPLATE
+            .replace("exit = ctrl+w\n", "exit = ctrl+w, escape\n")
+            .replace("exit_fullscreen_or_quit = escape\n", "");
+        assert!(old_default.contains("exit = ctrl+w, escape"));
+
+        let config = Config::parse_ini(&old_default);
+        assert_eq!(
+            config.action_bindings[&Action::Exit],
+            vec![InputBinding::KeyWithCtrl(egui::Key::W)]
+        );
+        assert_eq!(
+            config.action_bindings[&Action::ExitFullscreenOrQuit],
+            vec![InputBinding::Key(egui::Key::Escape)]
+        );
+    }
+
+    #[test]
+    fn customized_exit_binding_keeps_escape() {
+        let content = DEFAULT_CONFIG_TEMPLATE.replace("exit = ctrl+w\n", "exit = ctrl+q, escape\n");
+        let config = Config::parse_ini(&content);
+        assert!(
+            config.action_bindings[&Action::Exit].contains(&InputBinding::Key(egui::Key::Escape))
+        );
+    }
+}
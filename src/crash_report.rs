@@ -0,0 +1,188 @@
+//! Crash reports: leave something behind for the bug report instead of silently vanishing.
+//!
+//! A panic hook (all platforms) and an unhandled SEH exception filter (Windows) write a
+//! `crashes/crash-<unix time>-<pid>` folder under the local app data directory containing
+//! a description, a minidump (Windows) and the tail of the active log file, then show a
+//! message box pointing at it. `crash_reports = false` in config.ini opts out; the panic
+//! hook then only writes the legacy `%TEMP%\rust-image-viewer\panic.log`.
+
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+use crate::logging;
+use crate::storage;
+
+#[cfg(target_os = "windows")]
+type ExceptionPointers = *mut winapi::um::winnt::EXCEPTION_POINTERS;
+#[cfg(not(target_os = "windows"))]
+type ExceptionPointers = *mut std::ffi::c_void;
+
+const CRASHES_DIR_NAME: &str = "crashes";
+/// How much of the active log file is copied into a report.
+const RECENT_LOG_MAX_BYTES: u64 = 256 * 1024;
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+/// Set by the first crash being reported so nested failures do not recurse.
+static REPORTING: AtomicBool = AtomicBool::new(false);
+
+/// Install the panic hook. Call as early as possible; reports are enabled until
+/// [`set_enabled`] says otherwise.
+pub fn install_panic_hook() {
+    static INIT: OnceLock<()> = OnceLock::new();
+
+    INIT.get_or_init(|| {
+        let previous_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |panic_info| {
+            let payload = if let Some(msg) = panic_info.payload().downcast_ref::<&str>() {
+                (*msg).to_string()
+            } else if let Some(msg) = panic_info.payload().downcast_ref::<String>() {
+                msg.clone()
+            } else {
+                "<non-string panic payload>".to_string()
+            };
+
+            let location = panic_info
+                .location()
+                .map(|loc| format!("{}:{}:{}", loc.file(), loc.line(), loc.column()))
+                .unwrap_or_else(|| "<unknown location>".to_string());
+
+            let current_thread = std::thread::current();
+            let thread_name = current_thread.name().unwrap_or("unnamed");
+            let backtrace = std::backtrace::Backtrace::force_capture();
+            let timestamp = format!("{:?}", std::time::SystemTime::now());
+
+            let log_file = logging::active_log_file_path()
+                .map(|path| path.display().to_string())
+                .unwrap_or_else(|| "<file logging disabled>".to_string());
+
+            let panic_report = format!(
+                "[{timestamp}] thread='{thread_name}'\nlocation: {location}\npayload: {payload}\nlog: {log_file}\n\nbacktrace:\n{backtrace}\n"
+            );
+
+            if ENABLED.load(Ordering::Relaxed) {
+                tracing::error!(target: "crash", %location, %payload, "panic");
+                report_crash("panic.txt", &panic_report, std::ptr::null_mut());
+            } else {
+                let _ = std::fs::write(storage::temp_dir().join("panic.log"), panic_report);
+            }
+
+            previous_hook(panic_info);
+        }));
+    });
+}
+
+/// Apply the `crash_reports` config flag. On Windows, enabling also installs the
+/// unhandled-exception filter that catches crashes outside Rust panics (e.g. in drivers
+/// or GStreamer plugins).
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+
+    #[cfg(target_os = "windows")]
+    if enabled {
+        crate::windows_env::install_unhandled_exception_filter(unhandled_exception_filter);
+    }
+}
+
+#[cfg(target_os = "windows")]
+unsafe extern "system" fn unhandled_exception_filter(info: ExceptionPointers) -> i32 {
+    // Terminate after reporting instead of handing over to Windows Error Reporting,
+    // which would show a second, less helpful dialog.
+    const EXCEPTION_EXECUTE_HANDLER: i32 = 1;
+    const EXCEPTION_CONTINUE_SEARCH: i32 = 0;
+
+    if !ENABLED.load(Ordering::Relaxed) {
+        return EXCEPTION_CONTINUE_SEARCH;
+    }
+
+    let (code, address) = if !info.is_null() && !(*info).ExceptionRecord.is_null() {
+        let record = &*(*info).ExceptionRecord;
+        (record.ExceptionCode, record.ExceptionAddress as usize)
+    } else {
+        (0, 0)
+    };
+    let description = format!(
+        "[{:?}] unhandled exception 0x{code:08X} at 0x{address:X}\nthread: {:?}\nlog: {}\n",
+        std::time::SystemTime::now(),
+        std::thread::current().name().unwrap_or("unnamed"),
+        logging::active_log_file_path()
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| "<file logging disabled>".to_string()),
+    );
+
+    report_crash("exception.txt", &description, info);
+    EXCEPTION_EXECUTE_HANDLER
+}
+
+/// Write the report folder and tell the user where it is. Best effort throughout: the
+/// process is already going down, so every failure is swallowed.
+fn report_crash(description_file_name: &str, description: &str, exception: ExceptionPointers) {
+    if REPORTING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let Some(report_dir) = create_report_dir() else {
+        return;
+    };
+
+    let _ = std::fs::write(report_dir.join(description_file_name), description);
+    copy_recent_log(report_dir.as_path());
+
+    #[cfg(target_os = "windows")]
+    {
+        crate::windows_env::write_minidump(report_dir.join("crash.dmp").as_path(), exception);
+        crate::windows_env::show_error_message_box(
+            "rust-image-viewer crashed",
+            &format!(
+                "Sorry, the viewer ran into a fatal error and has to close.\n\n\
+                 A crash report was saved to:\n{}\n\n\
+                 Please attach this folder when reporting the problem.\n\
+                 (Set crash_reports = false in config.ini to turn these reports off.)",
+                report_dir.display()
+            ),
+        );
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = exception;
+        eprintln!("crash report saved to {}", report_dir.display());
+    }
+}
+
+fn crashes_dir() -> PathBuf {
+    storage::data_dir()
+        .unwrap_or_else(storage::temp_dir)
+        .join(CRASHES_DIR_NAME)
+}
+
+fn create_report_dir() -> Option<PathBuf> {
+    let unix_secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    let dir = crashes_dir().join(format!("crash-{unix_secs}-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).ok()?;
+    Some(dir)
+}
+
+fn copy_recent_log(report_dir: &Path) {
+    let Some(log_path) = logging::active_log_file_path() else {
+        return;
+    };
+    let Ok(mut file) = std::fs::File::open(&log_path) else {
+        return;
+    };
+
+    let len = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+    let start = len.saturating_sub(RECENT_LOG_MAX_BYTES);
+    if file.seek(SeekFrom::Start(start)).is_err() {
+        return;
+    }
+
+    let mut tail = Vec::with_capacity((len - start) as usize);
+    if file.read_to_end(&mut tail).is_ok() {
+        let _ = std::fs::write(report_dir.join("recent.log"), tail);
+    }
+}
@@ -0,0 +1,100 @@
+//! `--bench-decode [folder]`: times the JPEG decoders on the JPEGs of a folder (the current
+//! directory by default) and reports the result on stderr and, on Windows, in a message box.
+//!
+//! Every file is decoded [`ROUNDS`] times per decoder and the fastest round counts, so disk
+//! caching and warm-up do not favour whichever decoder runs second.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::image_loader::{decode_jpeg_with_zune, get_media_in_directory};
+
+/// JPEGs timed per run; the rest of a large folder is skipped.
+const MAX_FILES: usize = 20;
+const ROUNDS: usize = 3;
+
+type Decoder = fn(&Path) -> Result<(u32, u32, Vec<u8>), String>;
+
+pub fn run(folder: Option<&Path>) {
+    let folder = folder
+        .map(Path::to_path_buf)
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_default();
+    let report = bench_folder(&folder);
+    eprintln!("{report}");
+
+    #[cfg(target_os = "windows")]
+    crate::windows_env::show_info_message_box("rust-image-viewer", &report);
+}
+
+fn bench_folder(folder: &Path) -> String {
+    let files: Vec<PathBuf> = get_media_in_directory(folder)
+        .into_iter()
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| {
+                    ext.eq_ignore_ascii_case("jpg") || ext.eq_ignore_ascii_case("jpeg")
+                })
+        })
+        .take(MAX_FILES)
+        .collect();
+    if files.is_empty() {
+        return format!("No JPEG files to decode in {}", folder.display());
+    }
+
+    let mut report = format!(
+        "Decoded {} JPEGs from {} (fastest of {ROUNDS} rounds each):\n",
+        files.len(),
+        folder.display()
+    );
+    let zune = time_decoder(&files, decode_jpeg_with_zune);
+    report.push_str(&format!("\nzune-jpeg (pure Rust): {}", describe(&zune)));
+
+    #[cfg(feature = "turbojpeg")]
+    {
+        let turbo = time_decoder(&files, crate::turbo_jpeg::decode_file);
+        report.push_str(&format!("\nlibjpeg-turbo: {}", describe(&turbo)));
+        if let (Ok((zune, _)), Ok((turbo, _))) = (&zune, &turbo) {
+            report.push_str(&format!(
+                "\n\nlibjpeg-turbo is {:.2}x the speed of zune-jpeg",
+                zune.as_secs_f64() / turbo.as_secs_f64().max(f64::EPSILON)
+            ));
+        }
+    }
+    #[cfg(not(feature = "turbojpeg"))]
+    report.push_str("\nlibjpeg-turbo: not built in (build with --features turbojpeg)");
+
+    report
+}
+
+/// Total of each file's fastest decode, and the number of pixels in one round.
+fn time_decoder(files: &[PathBuf], decode: Decoder) -> Result<(Duration, u64), String> {
+    let mut total = Duration::ZERO;
+    let mut pixels = 0u64;
+    for path in files {
+        let mut fastest = Duration::MAX;
+        let mut size = (0u32, 0u32);
+        for _ in 0..ROUNDS {
+            let started = Instant::now();
+            let (width, height, _) =
+                decode(path).map_err(|err| format!("{}: {err}", path.display()))?;
+            fastest = fastest.min(started.elapsed());
+            size = (width, height);
+        }
+        total += fastest;
+        pixels += u64::from(size.0) * u64::from(size.1);
+    }
+    Ok((total, pixels))
+}
+
+fn describe(result: &Result<(Duration, u64), String>) -> String {
+    match result {
+        Ok((elapsed, pixels)) => format!(
+            "{:.0} ms, {:.1} megapixels/s",
+            elapsed.as_secs_f64() * 1000.0,
+            *pixels as f64 / 1e6 / elapsed.as_secs_f64().max(f64::EPSILON)
+        ),
+        Err(err) => format!("failed ({err})"),
+    }
+}
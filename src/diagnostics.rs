@@ -0,0 +1,485 @@
+//! `--diagnose`: collect a support bundle for bug reports.
+//!
+//! Writes a single zip with system, GPU and monitor details, GStreamer codec availability,
+//! the effective config (rendered from the template, so every binding is spelled out) next
+//! to the file on disk, and the tail of the most recent log files. Nothing is uploaded; the
+//! user attaches the zip to the report themselves.
+
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use gstreamer as gst;
+use gstreamer::prelude::*;
+
+use crate::config::Config;
+use crate::logging;
+use crate::video_player;
+
+/// How much of each log file goes into the bundle (its tail).
+const BUNDLE_LOG_MAX_BYTES: u64 = 1024 * 1024;
+const BUNDLE_MAX_LOG_FILES: usize = 4;
+
+/// Pipeline and decoder elements worth reporting. The hardware decoders match the rank
+/// overrides in `video_player`.
+const PROBED_ELEMENTS: &[&str] = &[
+    "playbin3",
+    "playbin",
+    "decodebin3",
+    "appsink",
+    "videoconvert",
+    "avdec_h264",
+    "avdec_h265",
+    "avdec_vp9",
+    "avdec_av1",
+    "dav1ddec",
+    "vp8dec",
+    "vp9dec",
+    "d3d11h264dec",
+    "d3d11h265dec",
+    "d3d11vp9dec",
+    "d3d11av1dec",
+    "d3d12h264dec",
+    "d3d12h265dec",
+    "d3d12av1dec",
+    "nvh264dec",
+    "nvh265dec",
+    "cudah264dec",
+    "vah264dec",
+    "vah265dec",
+];
+
+/// Environment variables that change how GStreamer and logging behave.
+const REPORTED_ENV_VARS: &[&str] = &[
+    "GSTREAMER_1_0_ROOT_MSVC_X86_64",
+    "GST_PLUGIN_PATH",
+    "GST_PLUGIN_SYSTEM_PATH",
+    "GST_PLUGIN_FEATURE_RANK",
+    "RIV_LOG",
+    "RUST_LOG",
+];
+
+/// Entry point for `--diagnose [output]`. `output` may be a `.zip` path or a folder; by
+/// default the bundle goes to the current directory, or the temp directory if that is not
+/// writable. Reports the result on stderr and, on Windows, in a message box.
+pub fn run(config: &Config, output: Option<&Path>) {
+    let target = bundle_path(output);
+    let result = write_support_bundle(config, &target);
+
+    let message = match &result {
+        Ok(()) => format!(
+            "Diagnostics saved to:\n{}\n\nAttach this file to your bug report. It contains your \
+             config.ini and recent logs, which may include file paths.",
+            target.display()
+        ),
+        Err(err) => format!("Could not write the diagnostics bundle:\n{err}"),
+    };
+    eprintln!("{message}");
+
+    #[cfg(target_os = "windows")]
+    match result {
+        Ok(()) => crate::windows_env::show_info_message_box("rust-image-viewer", &message),
+        Err(_) => crate::windows_env::show_error_message_box("rust-image-viewer", &message),
+    }
+}
+
+fn bundle_path(output: Option<&Path>) -> PathBuf {
+    let file_name = format!("rust-image-viewer-diagnostics-{}.zip", unix_seconds());
+    match output {
+        Some(path)
+            if path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("zip")) =>
+        {
+            path.to_path_buf()
+        }
+        Some(dir) => dir.join(file_name),
+        None => {
+            let dir = std::env::current_dir()
+                .ok()
+                .filter(|dir| dir_is_writable(dir))
+                .unwrap_or_else(std::env::temp_dir);
+            dir.join(file_name)
+        }
+    }
+}
+
+fn dir_is_writable(dir: &Path) -> bool {
+    let probe = dir.join(format!(".riv-write-probe-{}", std::process::id()));
+    let writable = std::fs::write(&probe, b"").is_ok();
+    let _ = std::fs::remove_file(&probe);
+    writable
+}
+
+pub fn write_support_bundle(config: &Config, target: &Path) -> Result<(), String> {
+    if let Some(parent) = target
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+    }
+    let file = std::fs::File::create(target)
+        .map_err(|err| format!("Failed to create {}: {err}", target.display()))?;
+    let mut zip = ZipWriter::new(std::io::BufWriter::new(file));
+    let write_err = |err: std::io::Error| format!("Failed to write {}: {err}", target.display());
+
+    zip.add("system.txt", system_report().as_bytes())
+        .map_err(write_err)?;
+    zip.add("codecs.txt", codec_report().as_bytes())
+        .map_err(write_err)?;
+    zip.add(
+        "config.resolved.ini",
+        config.render_ini_from_template().as_bytes(),
+    )
+    .map_err(write_err)?;
+    if let Ok(on_disk) = std::fs::read(Config::config_path()) {
+        zip.add("config.ini", &on_disk).map_err(write_err)?;
+    }
+    for path in logging::existing_log_files()
+        .into_iter()
+        .take(BUNDLE_MAX_LOG_FILES)
+    {
+        let Some(tail) = read_tail(&path, BUNDLE_LOG_MAX_BYTES) else {
+            continue;
+        };
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        zip.add(&format!("logs/{name}"), &tail).map_err(write_err)?;
+    }
+
+    zip.finish()
+        .and_then(|mut writer| writer.flush())
+        .map_err(write_err)
+}
+
+fn system_report() -> String {
+    let mut report = String::new();
+    let mut line = |text: String| {
+        report.push_str(&text);
+        report.push('\n');
+    };
+
+    line(format!(
+        "rust-image-viewer {} ({} build)",
+        env!("CARGO_PKG_VERSION"),
+        if cfg!(debug_assertions) {
+            "debug"
+        } else {
+            "release"
+        }
+    ));
+    line(format!(
+        "os: {} {} ({})",
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        std::env::consts::FAMILY
+    ));
+    line(format!(
+        "logical cpus: {}",
+        std::thread::available_parallelism().map_or(0, |count| count.get())
+    ));
+    line(format!(
+        "executable: {}",
+        std::env::current_exe()
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|_| "unknown".to_string())
+    ));
+    line(format!("config: {}", Config::config_path().display()));
+    line(format!(
+        "storage: {}, caches in {}",
+        if crate::storage::is_portable() {
+            "portable"
+        } else {
+            "installed"
+        },
+        crate::storage::data_dir()
+            .map(|dir| dir.display().to_string())
+            .unwrap_or_else(|| "none".to_string())
+    ));
+    line(format!("generated: unix {}", unix_seconds()));
+
+    line(String::new());
+    line("[display adapters]".to_string());
+    #[cfg(target_os = "windows")]
+    for adapter in crate::windows_env::describe_display_adapters() {
+        line(adapter);
+    }
+    #[cfg(not(target_os = "windows"))]
+    line("not collected on this platform; see the OpenGL line in the logs".to_string());
+
+    line(String::new());
+    line("[monitors]".to_string());
+    #[cfg(target_os = "windows")]
+    for monitor in crate::windows_env::describe_monitors() {
+        line(monitor);
+    }
+    #[cfg(not(target_os = "windows"))]
+    line("not collected on this platform".to_string());
+
+    line(String::new());
+    line("[plugins]".to_string());
+    let plugins = crate::plugins::registry();
+    for name in plugins.plugin_names() {
+        line(name.clone());
+    }
+    let mut extensions: Vec<&str> = plugins.plugin_extensions().collect();
+    extensions.sort_unstable();
+    line(format!("formats: {}", extensions.join(", ")));
+
+    line(String::new());
+    line("[environment]".to_string());
+    for name in REPORTED_ENV_VARS {
+        if let Some(value) = std::env::var_os(name) {
+            line(format!("{name}={}", value.to_string_lossy()));
+        }
+    }
+
+    report
+}
+
+fn codec_report() -> String {
+    let mut report = String::new();
+    let mut line = |text: String| {
+        report.push_str(&text);
+        report.push('\n');
+    };
+
+    line(format!(
+        "video acceleration: {:?}",
+        video_player::detect_video_acceleration_capabilities()
+    ));
+    if !video_player::gstreamer_runtime_available() {
+        line("GStreamer runtime: not found".to_string());
+        return report;
+    }
+    if let Err(err) = gst::init() {
+        line(format!("GStreamer runtime: failed to initialize: {err}"));
+        return report;
+    }
+    line(format!("GStreamer runtime: {}", gst::version_string()));
+
+    line(String::new());
+    line("[elements]".to_string());
+    for name in PROBED_ELEMENTS {
+        match gst::ElementFactory::find(name) {
+            Some(factory) => line(format!(
+                "{name}: available (rank {:?}, plugin {})",
+                factory.rank(),
+                factory
+                    .plugin_name()
+                    .map(|plugin| plugin.to_string())
+                    .unwrap_or_default()
+            )),
+            None => line(format!("{name}: missing")),
+        }
+    }
+
+    report
+}
+
+/// Logs the OpenGL driver strings once at startup so they end up in the log files that
+/// support bundles and crash reports collect.
+pub fn log_gl_context_info(gl: &eframe::glow::Context) {
+    use eframe::glow::{self, HasContext as _};
+
+    let (vendor, renderer, version) = unsafe {
+        (
+            gl.get_parameter_string(glow::VENDOR),
+            gl.get_parameter_string(glow::RENDERER),
+            gl.get_parameter_string(glow::VERSION),
+        )
+    };
+    tracing::info!(target: "startup", %vendor, %renderer, %version, "OpenGL context");
+}
+
+fn read_tail(path: &Path, max_bytes: u64) -> Option<Vec<u8>> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    let start = len.saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(start)).ok()?;
+    let mut tail = Vec::with_capacity((len - start) as usize);
+    file.read_to_end(&mut tail).ok()?;
+    Some(tail)
+}
+
+fn unix_seconds() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+/// MS-DOS `(time, date)` fields for a Unix timestamp, in UTC.
+fn dos_timestamp(unix_secs: u64) -> (u16, u16) {
+    let (year, month, day) = crate::usage_stats::civil_date((unix_secs / 86_400) as u32);
+    let secs_of_day = unix_secs % 86_400;
+    let time =
+        ((secs_of_day / 3_600) << 11) | (((secs_of_day / 60) % 60) << 5) | (secs_of_day % 60 / 2);
+    // DOS dates start in 1980.
+    let date = ((year.clamp(1980, 2107) - 1980) << 9) as u32 | (month << 5) | day;
+    (time as u16, date as u16)
+}
+
+struct ZipEntry {
+    name: String,
+    crc32: u32,
+    compressed_size: u32,
+    uncompressed_size: u32,
+    local_header_offset: u32,
+}
+
+/// Minimal deflate-only zip writer; enough for a handful of text files well under 4 GiB.
+struct ZipWriter<W: Write> {
+    writer: W,
+    offset: u32,
+    entries: Vec<ZipEntry>,
+    dos_time: u16,
+    dos_date: u16,
+}
+
+const ZIP_VERSION: u16 = 20;
+/// General purpose flag bit 11: file names are UTF-8.
+const ZIP_FLAG_UTF8: u16 = 1 << 11;
+const ZIP_METHOD_DEFLATE: u16 = 8;
+
+impl<W: Write> ZipWriter<W> {
+    fn new(writer: W) -> Self {
+        let (dos_time, dos_date) = dos_timestamp(unix_seconds());
+        Self {
+            writer,
+            offset: 0,
+            entries: Vec::new(),
+            dos_time,
+            dos_date,
+        }
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+        self.writer.write_all(bytes)?;
+        self.offset = self.offset.wrapping_add(bytes.len() as u32);
+        Ok(())
+    }
+
+    fn add(&mut self, name: &str, data: &[u8]) -> std::io::Result<()> {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data)?;
+        let compressed = encoder.finish()?;
+
+        let entry = ZipEntry {
+            name: name.to_string(),
+            crc32: crc32fast::hash(data),
+            compressed_size: compressed.len() as u32,
+            uncompressed_size: data.len() as u32,
+            local_header_offset: self.offset,
+        };
+
+        let mut header = Vec::with_capacity(30 + name.len());
+        header.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        header.extend_from_slice(&ZIP_VERSION.to_le_bytes());
+        header.extend_from_slice(&ZIP_FLAG_UTF8.to_le_bytes());
+        header.extend_from_slice(&ZIP_METHOD_DEFLATE.to_le_bytes());
+        header.extend_from_slice(&self.dos_time.to_le_bytes());
+        header.extend_from_slice(&self.dos_date.to_le_bytes());
+        header.extend_from_slice(&entry.crc32.to_le_bytes());
+        header.extend_from_slice(&entry.compressed_size.to_le_bytes());
+        header.extend_from_slice(&entry.uncompressed_size.to_le_bytes());
+        header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes());
+        header.extend_from_slice(name.as_bytes());
+
+        self.write_bytes(&header)?;
+        self.write_bytes(&compressed)?;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Writes the central directory and returns the underlying writer.
+    fn finish(mut self) -> std::io::Result<W> {
+        let directory_offset = self.offset;
+        let entries = std::mem::take(&mut self.entries);
+        for entry in &entries {
+            let mut record = Vec::with_capacity(46 + entry.name.len());
+            record.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+            record.extend_from_slice(&ZIP_VERSION.to_le_bytes());
+            record.extend_from_slice(&ZIP_VERSION.to_le_bytes());
+            record.extend_from_slice(&ZIP_FLAG_UTF8.to_le_bytes());
+            record.extend_from_slice(&ZIP_METHOD_DEFLATE.to_le_bytes());
+            record.extend_from_slice(&self.dos_time.to_le_bytes());
+            record.extend_from_slice(&self.dos_date.to_le_bytes());
+            record.extend_from_slice(&entry.crc32.to_le_bytes());
+            record.extend_from_slice(&entry.compressed_size.to_le_bytes());
+            record.extend_from_slice(&entry.uncompressed_size.to_le_bytes());
+            record.extend_from_slice(&(entry.name.len() as u16).to_le_bytes());
+            // Extra field, comment, disk number, internal and external attributes.
+            record.extend_from_slice(&[0; 12]);
+            record.extend_from_slice(&entry.local_header_offset.to_le_bytes());
+            record.extend_from_slice(entry.name.as_bytes());
+            self.write_bytes(&record)?;
+        }
+        let directory_size = self.offset - directory_offset;
+
+        let mut end = Vec::with_capacity(22);
+        end.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        end.extend_from_slice(&[0; 4]);
+        end.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        end.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        end.extend_from_slice(&directory_size.to_le_bytes());
+        end.extend_from_slice(&directory_offset.to_le_bytes());
+        end.extend_from_slice(&0u16.to_le_bytes());
+        self.write_bytes(&end)?;
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u16_at(bytes: &[u8], at: usize) -> u16 {
+        u16::from_le_bytes([bytes[at], bytes[at + 1]])
+    }
+
+    fn u32_at(bytes: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn zip_writer_produces_a_readable_archive() {
+        let mut zip = ZipWriter::new(Vec::new());
+        zip.add("system.txt", b"hello hello hello").unwrap();
+        zip.add("logs/viewer.log", b"").unwrap();
+        let bytes = zip.finish().unwrap();
+
+        let end = bytes.len() - 22;
+        assert_eq!(u32_at(&bytes, end), 0x0605_4b50);
+        assert_eq!(u16_at(&bytes, end + 10), 2);
+        let directory_offset = u32_at(&bytes, end + 16) as usize;
+        assert_eq!(u32_at(&bytes, directory_offset), 0x0201_4b50);
+
+        // First entry: local header at 0, deflated payload right after the name.
+        assert_eq!(u32_at(&bytes, 0), 0x0403_4b50);
+        let compressed_size = u32_at(&bytes, 18) as usize;
+        let name_len = u16_at(&bytes, 26) as usize;
+        assert_eq!(&bytes[30..30 + name_len], b"system.txt");
+        let payload = &bytes[30 + name_len..30 + name_len + compressed_size];
+        let mut inflated = Vec::new();
+        flate2::read::DeflateDecoder::new(payload)
+            .read_to_end(&mut inflated)
+            .unwrap();
+        assert_eq!(inflated, b"hello hello hello");
+        assert_eq!(u32_at(&bytes, 14), crc32fast::hash(b"hello hello hello"));
+    }
+
+    #[test]
+    fn dos_timestamps_pack_date_and_time() {
+        // 2024-02-29 13:45:30 UTC.
+        let (time, date) = dos_timestamp(1_709_214_330);
+        assert_eq!(date >> 9, 2024 - 1980);
+        assert_eq!((date >> 5) & 0xF, 2);
+        assert_eq!(date & 0x1F, 29);
+        assert_eq!(time >> 11, 13);
+        assert_eq!((time >> 5) & 0x3F, 45);
+        assert_eq!((time & 0x1F) * 2, 30);
+    }
+}
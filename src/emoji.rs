@@ -0,0 +1,169 @@
+//! Emoji-aware text helpers for file names shown in the UI.
+//!
+//! One emoji can be several code points: a base plus a skin tone, a variation selector, a
+//! keycap mark, or several people joined with zero-width joiners. Truncating by `char` can
+//! cut such a sequence in half, and counting by `char` underestimates how wide emoji (and
+//! CJK text) are. These helpers work on clusters instead: a base character with everything
+//! attached to it, measured in columns where emoji and CJK count double.
+//!
+//! egui draws emoji as monochrome outlines; the fallback font comes from
+//! [`crate::font_fallback`]. Variation selectors and tag characters have no glyph in those
+//! fonts and would show as boxes, so [`display_text`] drops them before drawing.
+
+use std::borrow::Cow;
+
+use crate::font_fallback::Script;
+
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+
+/// Code points that attach to the character before them.
+fn is_extender(ch: char) -> bool {
+    matches!(
+        ch as u32,
+        0x0300..=0x036F // combining diacritics
+            | 0x1AB0..=0x1AFF
+            | 0x1DC0..=0x1DFF
+            | 0x20D0..=0x20FF // combining marks for symbols, including the keycap
+            | 0xFE00..=0xFE0F // variation selectors
+            | 0xFE20..=0xFE2F
+            | 0x1F3FB..=0x1F3FF // skin tone modifiers
+            | 0xE0020..=0xE007F // tags (subdivision flags)
+    ) || ch == ZERO_WIDTH_JOINER
+}
+
+fn is_regional_indicator(ch: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&ch)
+}
+
+/// Whether `ch` starts an emoji presentation by default.
+pub fn is_emoji(ch: char) -> bool {
+    matches!(
+        ch as u32,
+        0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0x231A..=0x23FF
+    )
+}
+
+/// `text` split into clusters: a base character and everything attached to it.
+pub fn clusters(text: &str) -> Vec<&str> {
+    let mut clusters = Vec::new();
+    let mut start = 0;
+    let mut previous: Option<char> = None;
+    let mut regional_run = 0;
+    for (index, ch) in text.char_indices() {
+        let joins = previous.is_some_and(|previous| {
+            is_extender(ch)
+                || previous == ZERO_WIDTH_JOINER
+                || (is_regional_indicator(ch) && regional_run % 2 == 1)
+        });
+        if !joins && index > start {
+            clusters.push(&text[start..index]);
+            start = index;
+        }
+        regional_run = if is_regional_indicator(ch) {
+            regional_run + 1
+        } else {
+            0
+        };
+        previous = Some(ch);
+    }
+    if start < text.len() {
+        clusters.push(&text[start..]);
+    }
+    clusters
+}
+
+/// Columns one cluster takes: two for emoji and CJK, one for everything else.
+fn cluster_width(cluster: &str) -> usize {
+    let Some(first) = cluster.chars().next() else {
+        return 0;
+    };
+    let emoji = is_emoji(first)
+        || is_regional_indicator(first)
+        || cluster.contains('\u{FE0F}')
+        || cluster.contains('\u{20E3}');
+    if emoji || Script::of(first) == Some(Script::Cjk) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Width of `text` in columns.
+pub fn width(text: &str) -> usize {
+    clusters(text).into_iter().map(cluster_width).sum()
+}
+
+/// The longest start of `text` that fits in `max_width` columns, never splitting a cluster.
+pub fn prefix_within(text: &str, max_width: usize) -> &str {
+    let mut used = 0;
+    let mut end = 0;
+    for cluster in clusters(text) {
+        used += cluster_width(cluster);
+        if used > max_width {
+            break;
+        }
+        end += cluster.len();
+    }
+    &text[..end]
+}
+
+/// The longest end of `text` that fits in `max_width` columns, never splitting a cluster.
+pub fn suffix_within(text: &str, max_width: usize) -> &str {
+    let mut used = 0;
+    let mut start = text.len();
+    for cluster in clusters(text).into_iter().rev() {
+        used += cluster_width(cluster);
+        if used > max_width {
+            break;
+        }
+        start -= cluster.len();
+    }
+    &text[start..]
+}
+
+/// `text` without the emoji code points egui cannot draw (variation selectors, tags, skin
+/// tone modifiers), which would otherwise show up as boxes next to the emoji.
+pub fn display_text(text: &str) -> Cow<'_, str> {
+    let hidden = |ch: char| {
+        matches!(
+            ch as u32,
+            0xFE0E | 0xFE0F | 0xE0020..=0xE007F | 0x1F3FB..=0x1F3FF
+        )
+    };
+    if text.chars().any(hidden) {
+        Cow::Owned(text.chars().filter(|ch| !hidden(*ch)).collect())
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clusters, display_text, prefix_within, suffix_within, width};
+
+    #[test]
+    fn clusters_keep_multi_code_point_emoji_together() {
+        let family = "👨\u{200D}👩\u{200D}👧";
+        let thumbs = "👍\u{1F3FD}";
+        let flag = "🇯🇵";
+        let keycap = "1\u{FE0F}\u{20E3}";
+        let text = format!("a{family}{thumbs}{flag}{flag}{keycap}é");
+        assert_eq!(
+            clusters(&text),
+            ["a", family, thumbs, flag, flag, keycap, "é"]
+        );
+        assert_eq!(width(&text), 1 + 2 * 5 + 1);
+        assert_eq!(width("写真.jpg"), 8);
+    }
+
+    #[test]
+    fn truncation_never_splits_an_emoji() {
+        let name = "trip_👨\u{200D}👩\u{200D}👧_beach.png";
+        assert_eq!(prefix_within(name, 6), "trip_");
+        assert_eq!(prefix_within(name, 7), "trip_👨\u{200D}👩\u{200D}👧");
+        assert_eq!(suffix_within(name, 10), "_beach.png");
+        assert_eq!(suffix_within(name, 11), "_beach.png");
+        assert_eq!(display_text("❤\u{FE0F} 👍\u{1F3FD}"), "❤ 👍");
+        assert_eq!(display_text("plain"), "plain");
+    }
+}
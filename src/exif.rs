@@ -0,0 +1,143 @@
+//! Minimal EXIF reading: finding the EXIF block of a JPEG and walking its TIFF-structured
+//! IFDs. Used for capture dates in batch rename and for the thumbnail cameras embed in
+//! their JPEGs.
+
+const TAG_THUMBNAIL_OFFSET: u16 = 0x0201;
+const TAG_THUMBNAIL_LENGTH: u16 = 0x0202;
+
+/// TIFF structure reader over EXIF bytes, in the byte order the header declares.
+pub struct Tiff<'a> {
+    data: &'a [u8],
+    little_endian: bool,
+}
+
+impl<'a> Tiff<'a> {
+    /// Reads the byte order from the TIFF header; `None` when `data` has none.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let little_endian = match data.get(..4)? {
+            [b'I', b'I', 42, 0] => true,
+            [b'M', b'M', 0, 42] => false,
+            _ => return None,
+        };
+        Some(Self {
+            data,
+            little_endian,
+        })
+    }
+
+    /// Offset of the first IFD, which describes the main image.
+    pub fn ifd0(&self) -> Option<usize> {
+        self.u32_at(4).map(|offset| offset as usize)
+    }
+
+    /// Offset of the IFD linked after the one at `ifd`; IFD1 describes the thumbnail.
+    pub fn next_ifd(&self, ifd: usize) -> Option<usize> {
+        let count = self.u16_at(ifd)? as usize;
+        let next = self.u32_at(ifd + 2 + count * 12)? as usize;
+        (next != 0).then_some(next)
+    }
+
+    fn u16_at(&self, offset: usize) -> Option<u16> {
+        let bytes: [u8; 2] = self.data.get(offset..offset + 2)?.try_into().ok()?;
+        Some(if self.little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    }
+
+    pub fn u32_at(&self, offset: usize) -> Option<u32> {
+        let bytes: [u8; 4] = self.data.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    /// Offset of the value of `tag` in the IFD at `ifd`, and its component count.
+    pub fn find_tag(&self, ifd: usize, tag: u16) -> Option<(usize, usize)> {
+        let count = self.u16_at(ifd)? as usize;
+        (0..count).find_map(|index| {
+            let entry = ifd + 2 + index * 12;
+            if self.u16_at(entry)? != tag {
+                return None;
+            }
+            let components = self.u32_at(entry + 4)? as usize;
+            // Values over four bytes live at an offset; shorter ones sit in the entry.
+            let value = if components > 4 {
+                self.u32_at(entry + 8)? as usize
+            } else {
+                entry + 8
+            };
+            Some((value, components))
+        })
+    }
+
+    pub fn ascii_tag(&self, ifd: usize, tag: u16) -> Option<&'a [u8]> {
+        let (offset, length) = self.find_tag(ifd, tag)?;
+        self.data.get(offset..offset + length)
+    }
+
+    /// The JPEG thumbnail stored with IFD1.
+    pub fn thumbnail(&self) -> Option<&'a [u8]> {
+        let ifd1 = self.next_ifd(self.ifd0()?)?;
+        let (offset, _) = self.find_tag(ifd1, TAG_THUMBNAIL_OFFSET)?;
+        let (length, _) = self.find_tag(ifd1, TAG_THUMBNAIL_LENGTH)?;
+        let offset = self.u32_at(offset)? as usize;
+        let length = self.u32_at(length)? as usize;
+        self.data
+            .get(offset..offset.checked_add(length)?)
+            .filter(|jpeg| jpeg.starts_with(&[0xFF, 0xD8]))
+    }
+}
+
+/// The TIFF-structured EXIF block in the APP1 segment at the start of a JPEG.
+pub fn jpeg_exif_block(bytes: &[u8]) -> Option<&[u8]> {
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+    let mut offset = 2;
+    while offset + 4 <= bytes.len() && bytes[offset] == 0xFF {
+        let marker = bytes[offset + 1];
+        let length = u16::from_be_bytes([bytes[offset + 2], bytes[offset + 3]]) as usize;
+        let segment = bytes.get(offset + 4..offset + 2 + length)?;
+        if marker == 0xE1 && segment.starts_with(b"Exif\0\0") {
+            return Some(&segment[6..]);
+        }
+        // Start of scan: no metadata segments follow.
+        if marker == 0xDA {
+            return None;
+        }
+        offset += 2 + length;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{jpeg_exif_block, Tiff};
+
+    #[test]
+    fn finds_the_ifd1_thumbnail() {
+        // Little-endian TIFF: an empty IFD0 at 8 linking to IFD1 at 14, whose two entries
+        // point at a four-byte "JPEG" at 44.
+        let mut tiff = b"II*\0\x08\0\0\0".to_vec();
+        tiff.extend_from_slice(&[0, 0, 14, 0, 0, 0]);
+        tiff.extend_from_slice(&[2, 0]);
+        tiff.extend_from_slice(&[0x01, 0x02, 4, 0, 1, 0, 0, 0, 44, 0, 0, 0]);
+        tiff.extend_from_slice(&[0x02, 0x02, 4, 0, 1, 0, 0, 0, 4, 0, 0, 0]);
+        tiff.extend_from_slice(&[0, 0, 0, 0]);
+        tiff.extend_from_slice(&[0xFF, 0xD8, 0xFF, 0xD9]);
+
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        jpeg.extend_from_slice(&((tiff.len() + 8) as u16).to_be_bytes());
+        jpeg.extend_from_slice(b"Exif\0\0");
+        jpeg.extend_from_slice(&tiff);
+
+        let block = jpeg_exif_block(&jpeg).unwrap();
+        let thumbnail = Tiff::parse(block).and_then(|tiff| tiff.thumbnail());
+        assert_eq!(thumbnail, Some(&[0xFF, 0xD8, 0xFF, 0xD9][..]));
+        assert!(jpeg_exif_block(b"not a jpeg").is_none());
+    }
+}
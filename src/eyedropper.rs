@@ -0,0 +1,144 @@
+//! Eyedropper: the color of the image pixel under the pointer.
+//!
+//! Colors are read from the decoded frame in memory, not from the texture on screen, so zoom
+//! filtering, color management and the adjustments shader never change the reported value.
+//! [`loupe`] returns the pixels around the picked one for the magnified view next to the
+//! pointer.
+
+use crate::image_loader::ImageFrame;
+
+/// Pixels shown on each side of the picked one in the loupe.
+pub const LOUPE_RADIUS: i32 = 5;
+
+/// A pixel picked from the image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PickedColor {
+    /// Position in image pixels.
+    pub x: u32,
+    pub y: u32,
+    pub rgba: [u8; 4],
+}
+
+impl PickedColor {
+    /// `#RRGGBB`, or `#RRGGBBAA` when the pixel is not opaque.
+    pub fn hex(&self) -> String {
+        let [r, g, b, a] = self.rgba;
+        if a == u8::MAX {
+            format!("#{r:02X}{g:02X}{b:02X}")
+        } else {
+            format!("#{r:02X}{g:02X}{b:02X}{a:02X}")
+        }
+    }
+
+    /// `RGB 255, 128, 0`, with the alpha appended when the pixel is not opaque.
+    pub fn rgb_text(&self) -> String {
+        let [r, g, b, a] = self.rgba;
+        if a == u8::MAX {
+            format!("RGB {r}, {g}, {b}")
+        } else {
+            format!("RGBA {r}, {g}, {b}, {a}")
+        }
+    }
+
+    pub fn color32(&self) -> egui::Color32 {
+        let [r, g, b, a] = self.rgba;
+        egui::Color32::from_rgba_unmultiplied(r, g, b, a)
+    }
+}
+
+/// Frame pixel under `pos`, given in the coordinates of an image `image_size` large. Frames
+/// of oversized images are stored downscaled, so positions are scaled to the frame.
+fn frame_pixel(frame: &ImageFrame, image_size: egui::Vec2, pos: egui::Pos2) -> Option<(i64, i64)> {
+    if frame.width == 0
+        || frame.height == 0
+        || !(0.0..image_size.x).contains(&pos.x)
+        || !(0.0..image_size.y).contains(&pos.y)
+    {
+        return None;
+    }
+    let x = (pos.x * frame.width as f32 / image_size.x).floor() as i64;
+    let y = (pos.y * frame.height as f32 / image_size.y).floor() as i64;
+    Some((
+        x.min(frame.width as i64 - 1),
+        y.min(frame.height as i64 - 1),
+    ))
+}
+
+fn pixel_at(frame: &ImageFrame, x: i64, y: i64) -> Option<[u8; 4]> {
+    if x < 0 || y < 0 || x >= frame.width as i64 || y >= frame.height as i64 {
+        return None;
+    }
+    let offset = (y as usize * frame.width as usize + x as usize) * 4;
+    let pixel = frame.pixels.get(offset..offset + 4)?;
+    Some([pixel[0], pixel[1], pixel[2], pixel[3]])
+}
+
+/// The pixel under `pos`, or `None` outside the image.
+pub fn pick(frame: &ImageFrame, image_size: egui::Vec2, pos: egui::Pos2) -> Option<PickedColor> {
+    let (fx, fy) = frame_pixel(frame, image_size, pos)?;
+    Some(PickedColor {
+        x: pos.x as u32,
+        y: pos.y as u32,
+        rgba: pixel_at(frame, fx, fy)?,
+    })
+}
+
+/// The square of frame pixels centered on the one under `pos`, row by row, `None` where it
+/// runs past the frame edge. Empty outside the image.
+pub fn loupe(frame: &ImageFrame, image_size: egui::Vec2, pos: egui::Pos2) -> Vec<Option<[u8; 4]>> {
+    let Some((cx, cy)) = frame_pixel(frame, image_size, pos) else {
+        return Vec::new();
+    };
+    let radius = LOUPE_RADIUS as i64;
+    (-radius..=radius)
+        .flat_map(|dy| (-radius..=radius).map(move |dx| (cx + dx, cy + dy)))
+        .map(|(x, y)| pixel_at(frame, x, y))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{loupe, pick, LOUPE_RADIUS};
+    use crate::image_loader::ImageFrame;
+
+    fn frame() -> ImageFrame {
+        // 2×2: red, green / blue, half-transparent white.
+        ImageFrame {
+            pixels: vec![
+                255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 255, 255, 255, 128,
+            ],
+            width: 2,
+            height: 2,
+            delay_ms: 0,
+        }
+    }
+
+    #[test]
+    fn picks_from_the_stored_frame() {
+        let frame = frame();
+        let size = egui::vec2(2.0, 2.0);
+        let red = pick(&frame, size, egui::pos2(0.5, 0.5)).unwrap();
+        assert_eq!((red.x, red.y), (0, 0));
+        assert_eq!(red.hex(), "#FF0000");
+        assert_eq!(red.rgb_text(), "RGB 255, 0, 0");
+        let white = pick(&frame, size, egui::pos2(1.9, 1.2)).unwrap();
+        assert_eq!(white.hex(), "#FFFFFF80");
+        assert_eq!(pick(&frame, size, egui::pos2(2.0, 0.5)), None);
+
+        // A frame stored at half the image size.
+        let green = pick(&frame, egui::vec2(4.0, 4.0), egui::pos2(3.5, 1.0)).unwrap();
+        assert_eq!((green.x, green.y, green.rgba), (3, 1, [0, 255, 0, 255]));
+    }
+
+    #[test]
+    fn loupe_is_centered_on_the_picked_pixel() {
+        let frame = frame();
+        let cells = loupe(&frame, egui::vec2(2.0, 2.0), egui::pos2(0.0, 0.0));
+        let side = (LOUPE_RADIUS * 2 + 1) as usize;
+        assert_eq!(cells.len(), side * side);
+        let center = side * LOUPE_RADIUS as usize + LOUPE_RADIUS as usize;
+        assert_eq!(cells[center], Some([255, 0, 0, 255]));
+        assert_eq!(cells[center + side + 1], Some([255, 255, 255, 128]));
+        assert_eq!(cells[center - 1], None);
+    }
+}
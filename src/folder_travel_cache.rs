@@ -1,4 +1,7 @@
 //! Persistent folder-travel position cache for manga long-strip and masonry modes.
+//!
+//! The same database also remembers the long-strip page-width zoom and the per-page
+//! rotation/fit overrides chosen for each folder.
 
 use std::fs::OpenOptions;
 use std::io;
@@ -6,14 +9,19 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, OnceLock};
 
+use hashbrown::HashMap;
 use parking_lot::Mutex;
 use redb::backends::FileBackend;
 use redb::{Database, DatabaseError, StorageBackend, TableDefinition};
 
-use crate::app_dirs;
+use crate::storage;
 
 const FOLDER_TRAVEL_TABLE: TableDefinition<&str, &[u8]> =
     TableDefinition::new("folder_travel_positions");
+const MANGA_PAGE_WIDTH_TABLE: TableDefinition<&str, f32> =
+    TableDefinition::new("manga_page_width_zoom");
+const MANGA_PAGE_OVERRIDES_TABLE: TableDefinition<&str, &[u8]> =
+    TableDefinition::new("manga_page_overrides");
 const CACHE_FILE_NAME: &str = "folder_travel_cache.redb";
 const CACHE_SCHEMA_VERSION: u8 = 1;
 const FOLDER_TRAVEL_CACHE_DEFAULT_MAX_SIZE_BYTES: u64 = 64 * 1024 * 1024;
@@ -33,6 +41,59 @@ impl FolderTravelLayoutMode {
     }
 }
 
+/// Per-page long-strip fit override.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MangaPageFit {
+    /// Follow the strip layout (fit-to-height or page-width zoom).
+    #[default]
+    Auto,
+    /// Stretch the page to the strip column width (the window width outside page-width zoom).
+    Width,
+    /// Scale the page to the window height.
+    Height,
+}
+
+impl MangaPageFit {
+    pub fn next(self) -> Self {
+        match self {
+            MangaPageFit::Auto => MangaPageFit::Width,
+            MangaPageFit::Width => MangaPageFit::Height,
+            MangaPageFit::Height => MangaPageFit::Auto,
+        }
+    }
+
+    fn to_byte(self) -> u8 {
+        match self {
+            MangaPageFit::Auto => 0,
+            MangaPageFit::Width => 1,
+            MangaPageFit::Height => 2,
+        }
+    }
+
+    fn from_byte(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(MangaPageFit::Auto),
+            1 => Some(MangaPageFit::Width),
+            2 => Some(MangaPageFit::Height),
+            _ => None,
+        }
+    }
+}
+
+/// Rotation and fit chosen for a single long-strip page (e.g. a sideways double spread).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MangaPageOverride {
+    /// Clockwise quarter turns, 0..=3.
+    pub quarter_turns: u8,
+    pub fit: MangaPageFit,
+}
+
+impl MangaPageOverride {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct FolderTravelPosition {
     pub current_path: PathBuf,
@@ -97,6 +158,90 @@ impl FolderTravelCache {
 
         let _ = write_txn.commit();
     }
+
+    fn lookup_page_width(&self, directory: &Path) -> Option<f32> {
+        let key = normalize_path_key(directory)?;
+
+        let read_txn = self.db.begin_read().ok()?;
+        let table = read_txn.open_table(MANGA_PAGE_WIDTH_TABLE).ok()?;
+        let value = table.get(key.as_str()).ok()??.value();
+        value.is_finite().then_some(value)
+    }
+
+    fn store_page_width(&mut self, directory: &Path, page_width: Option<f32>) {
+        let Some(key) = normalize_path_key(directory) else {
+            return;
+        };
+
+        let Ok(write_txn) = self.db.begin_write() else {
+            return;
+        };
+
+        {
+            let Ok(mut table) = write_txn.open_table(MANGA_PAGE_WIDTH_TABLE) else {
+                return;
+            };
+
+            let result = match page_width.filter(|value| value.is_finite()) {
+                Some(value) => table.insert(key.as_str(), value).map(|_| ()),
+                None => table.remove(key.as_str()).map(|_| ()),
+            };
+            if result.is_err() {
+                return;
+            }
+        }
+
+        let _ = write_txn.commit();
+    }
+
+    fn lookup_page_overrides(&self, directory: &Path) -> HashMap<String, MangaPageOverride> {
+        let Some(key) = normalize_path_key(directory) else {
+            return HashMap::new();
+        };
+
+        let Ok(read_txn) = self.db.begin_read() else {
+            return HashMap::new();
+        };
+        let Ok(table) = read_txn.open_table(MANGA_PAGE_OVERRIDES_TABLE) else {
+            return HashMap::new();
+        };
+        let Ok(Some(raw)) = table.get(key.as_str()) else {
+            return HashMap::new();
+        };
+        decode_page_overrides_record(raw.value()).unwrap_or_default()
+    }
+
+    fn store_page_overrides(
+        &mut self,
+        directory: &Path,
+        overrides: &HashMap<String, MangaPageOverride>,
+    ) {
+        let Some(key) = normalize_path_key(directory) else {
+            return;
+        };
+
+        let Ok(write_txn) = self.db.begin_write() else {
+            return;
+        };
+
+        {
+            let Ok(mut table) = write_txn.open_table(MANGA_PAGE_OVERRIDES_TABLE) else {
+                return;
+            };
+
+            let result = if overrides.values().all(MangaPageOverride::is_default) {
+                table.remove(key.as_str()).map(|_| ())
+            } else {
+                let encoded = encode_page_overrides_record(overrides);
+                table.insert(key.as_str(), encoded.as_slice()).map(|_| ())
+            };
+            if result.is_err() {
+                return;
+            }
+        }
+
+        let _ = write_txn.commit();
+    }
 }
 
 static GLOBAL_FOLDER_TRAVEL_CACHE: OnceLock<Option<Arc<Mutex<FolderTravelCache>>>> =
@@ -131,6 +276,44 @@ pub fn store_folder_travel_position(
     cache.lock().store(directory, layout_mode, position);
 }
 
+/// Look up the long-strip page-width zoom (fraction of the viewport width) saved for a folder.
+pub fn lookup_manga_page_width_zoom(directory: &Path) -> Option<f32> {
+    let cache = global_folder_travel_cache_handle()?;
+    cache.lock().lookup_page_width(directory)
+}
+
+/// Save the long-strip page-width zoom for a folder. `None` clears the entry so the folder
+/// falls back to the default fit-to-height strip layout.
+pub fn store_manga_page_width_zoom(directory: &Path, page_width: Option<f32>) {
+    let Some(cache) = global_folder_travel_cache_handle() else {
+        return;
+    };
+
+    cache.lock().store_page_width(directory, page_width);
+}
+
+/// Look up the per-page overrides saved for a folder, keyed by file name.
+pub fn lookup_manga_page_overrides(directory: &Path) -> HashMap<String, MangaPageOverride> {
+    let Some(cache) = global_folder_travel_cache_handle() else {
+        return HashMap::new();
+    };
+
+    cache.lock().lookup_page_overrides(directory)
+}
+
+/// Replace the per-page overrides saved for a folder. Default entries are dropped; an empty
+/// map clears the folder's record.
+pub fn store_manga_page_overrides(
+    directory: &Path,
+    overrides: &HashMap<String, MangaPageOverride>,
+) {
+    let Some(cache) = global_folder_travel_cache_handle() else {
+        return;
+    };
+
+    cache.lock().store_page_overrides(directory, overrides);
+}
+
 fn folder_travel_key(directory: &Path, layout_mode: FolderTravelLayoutMode) -> Option<String> {
     let normalized = normalize_path_key(directory)?;
     Some(format!("{}#{}", normalized, layout_mode.key_suffix()))
@@ -182,22 +365,52 @@ fn decode_position_record(raw: &[u8]) -> Option<FolderTravelPosition> {
     })
 }
 
-fn default_cache_path() -> Option<PathBuf> {
-    #[cfg(target_os = "windows")]
-    {
-        if let Some(base_dir) = app_dirs::app_local_data_dir() {
-            if std::fs::create_dir_all(&base_dir).is_ok() {
-                return Some(base_dir.join(CACHE_FILE_NAME));
-            }
+fn encode_page_overrides_record(overrides: &HashMap<String, MangaPageOverride>) -> Vec<u8> {
+    let mut encoded = vec![CACHE_SCHEMA_VERSION];
+    for (file_name, page_override) in overrides {
+        if page_override.is_default() {
+            continue;
         }
+        let Ok(name_len) = u16::try_from(file_name.len()) else {
+            continue;
+        };
+
+        encoded.extend_from_slice(&name_len.to_le_bytes());
+        encoded.extend_from_slice(file_name.as_bytes());
+        encoded.push(page_override.quarter_turns % 4);
+        encoded.push(page_override.fit.to_byte());
     }
+    encoded
+}
 
-    let base_dir = std::env::temp_dir().join(app_dirs::APP_DIR_NAME);
-    if std::fs::create_dir_all(&base_dir).is_ok() {
-        return Some(base_dir.join(CACHE_FILE_NAME));
+fn decode_page_overrides_record(raw: &[u8]) -> Option<HashMap<String, MangaPageOverride>> {
+    if raw.first() != Some(&CACHE_SCHEMA_VERSION) {
+        return None;
+    }
+
+    let mut overrides = HashMap::new();
+    let mut rest = &raw[1..];
+    while !rest.is_empty() {
+        let name_len = u16::from_le_bytes(rest.get(..2)?.try_into().ok()?) as usize;
+        let name = std::str::from_utf8(rest.get(2..2 + name_len)?).ok()?;
+        let quarter_turns = *rest.get(2 + name_len)?;
+        let fit = MangaPageFit::from_byte(*rest.get(3 + name_len)?)?;
+
+        overrides.insert(
+            name.to_string(),
+            MangaPageOverride {
+                quarter_turns: quarter_turns % 4,
+                fit,
+            },
+        );
+        rest = &rest[4 + name_len..];
     }
 
-    None
+    Some(overrides)
+}
+
+fn default_cache_path() -> Option<PathBuf> {
+    storage::data_file(CACHE_FILE_NAME)
 }
 
 fn normalize_path_key(path: &Path) -> Option<String> {
@@ -0,0 +1,223 @@
+//! Script-aware fallback fonts for file names and UI text.
+//!
+//! egui's bundled fonts cover Latin, Greek, Cyrillic and a subset of emoji, so names in
+//! other scripts and newer emoji show as tofu boxes. The viewer checks which scripts the current path uses and loads matching
+//! system fonts on a worker the first time a script shows up. System fonts can be tens of
+//! megabytes (the CJK collections), so nothing is loaded until a name needs it. Loaded fonts
+//! are appended after egui's own, so Latin text keeps its look and only missing glyphs fall
+//! through to them.
+
+use std::collections::{HashMap, HashSet};
+
+/// `(font name, file bytes)` pairs read by the loader.
+type LoadedFonts = Vec<(String, Vec<u8>)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Script {
+    /// Han, Hiragana, Katakana and Hangul.
+    Cjk,
+    Arabic,
+    Hebrew,
+    Thai,
+    Devanagari,
+    /// Cyrillic and Greek; egui's fonts cover the common letters, system fonts the rest.
+    CyrillicGreek,
+    /// Emoji newer than egui's bundled emoji font. Drawn as monochrome outlines.
+    Emoji,
+}
+
+impl Script {
+    pub fn of(ch: char) -> Option<Self> {
+        let c = ch as u32;
+        let script = match c {
+            0x0370..=0x03FF | 0x1F00..=0x1FFF => Self::CyrillicGreek,
+            0x0400..=0x052F | 0x1C80..=0x1C8F | 0x2DE0..=0x2DFF | 0xA640..=0xA69F => {
+                Self::CyrillicGreek
+            }
+            0x0590..=0x05FF | 0xFB1D..=0xFB4F => Self::Hebrew,
+            0x0600..=0x06FF | 0x0750..=0x077F | 0x08A0..=0x08FF | 0xFB50..=0xFDFF => {
+                Self::Arabic
+            }
+            0xFE70..=0xFEFF => Self::Arabic,
+            0x0900..=0x097F => Self::Devanagari,
+            0x0E00..=0x0E7F => Self::Thai,
+            0x1100..=0x11FF // Hangul Jamo
+            | 0x3040..=0x30FF // Hiragana, Katakana
+            | 0x31F0..=0x31FF // Katakana Phonetic Extensions
+            | 0x3400..=0x4DBF // CJK Unified Ideographs Extension A
+            | 0x4E00..=0x9FFF // CJK Unified Ideographs
+            | 0xAC00..=0xD7AF // Hangul Syllables
+            | 0xF900..=0xFAFF => Self::Cjk, // CJK Compatibility Ideographs
+            _ if crate::emoji::is_emoji(ch) => Self::Emoji,
+            _ => return None,
+        };
+        Some(script)
+    }
+
+    /// `(font name, file in the system font folder)` to try, most preferred first.
+    fn font_files(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Cjk => &[
+                // Japanese
+                ("cjk_meiryo", "meiryo.ttc"),
+                ("cjk_msgothic", "msgothic.ttc"),
+                // Simplified Chinese
+                ("cjk_msyh", "msyh.ttc"),
+                // Traditional Chinese
+                ("cjk_msjh", "msjh.ttc"),
+                // Korean
+                ("cjk_malgun", "malgun.ttf"),
+            ],
+            Self::Arabic | Self::Hebrew | Self::CyrillicGreek => {
+                &[("segoeui", "segoeui.ttf"), ("tahoma", "tahoma.ttf")]
+            }
+            Self::Thai => &[("leelawadee_ui", "LeelawUI.ttf"), ("tahoma", "tahoma.ttf")],
+            Self::Devanagari => &[("nirmala_ui", "Nirmala.ttf")],
+            Self::Emoji => &[("segoe_ui_emoji", "seguiemj.ttf")],
+        }
+    }
+}
+
+/// Scripts in `text` that may need a fallback font.
+pub fn scripts_in(text: &str) -> HashSet<Script> {
+    text.chars().filter_map(Script::of).collect()
+}
+
+/// The system font folder, where fallback fonts are looked up.
+#[cfg(target_os = "windows")]
+fn system_font_dir() -> Option<std::path::PathBuf> {
+    let windows = std::env::var_os("WINDIR").unwrap_or_else(|| r"C:\Windows".into());
+    Some(std::path::PathBuf::from(windows).join("Fonts"))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn system_font_dir() -> Option<std::path::PathBuf> {
+    None
+}
+
+/// Reads the fonts for `scripts`, skipping names in `loaded` and files that do not exist.
+fn load_font_files(scripts: &[Script], loaded: &HashSet<String>) -> LoadedFonts {
+    let Some(dir) = system_font_dir() else {
+        return Vec::new();
+    };
+    let mut fonts: LoadedFonts = Vec::new();
+    for (name, file) in scripts.iter().flat_map(|script| script.font_files()) {
+        if loaded.contains(*name) || fonts.iter().any(|(loaded, _)| loaded == name) {
+            continue;
+        }
+        if let Ok(bytes) = std::fs::read(dir.join(file)) {
+            fonts.push((name.to_string(), bytes));
+        }
+    }
+    fonts
+}
+
+/// Which scripts have fallback fonts, and the worker loading the next ones.
+#[derive(Default)]
+pub struct FontFallback {
+    /// Scripts already requested; a script with no font on this system stays here too, so
+    /// it is not retried every frame.
+    requested: HashSet<Script>,
+    /// Installed font bytes by name, kept so later loads can rebuild the full font set.
+    fonts: Vec<(String, egui::FontData)>,
+    pending: Option<crossbeam_channel::Receiver<LoadedFonts>>,
+}
+
+impl FontFallback {
+    /// Starts loading fonts for scripts in `text` that have none yet. One load runs at a
+    /// time; scripts seen meanwhile are picked up on a later call.
+    pub fn request_for_text(&mut self, text: &str) {
+        if self.pending.is_some() || system_font_dir().is_none() {
+            return;
+        }
+        let missing: Vec<Script> = scripts_in(text)
+            .into_iter()
+            .filter(|script| !self.requested.contains(script))
+            .collect();
+        if missing.is_empty() {
+            return;
+        }
+        self.requested.extend(missing.iter().copied());
+        let loaded: HashSet<String> = self.fonts.iter().map(|(name, _)| name.clone()).collect();
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        crate::async_runtime::spawn_blocking_or_thread("font-fallback-load", move || {
+            let _ = tx.send(load_font_files(&missing, &loaded));
+        });
+        self.pending = Some(rx);
+    }
+
+    /// Installs finished loads. Returns `true` when the font set changed.
+    pub fn poll(&mut self, ctx: &egui::Context) -> bool {
+        let Some(rx) = self.pending.as_ref() else {
+            return false;
+        };
+        let loaded = match rx.try_recv() {
+            Ok(loaded) => loaded,
+            Err(crossbeam_channel::TryRecvError::Empty) => return false,
+            Err(crossbeam_channel::TryRecvError::Disconnected) => Vec::new(),
+        };
+        self.pending = None;
+        if loaded.is_empty() {
+            return false;
+        }
+        for (name, bytes) in loaded {
+            tracing::debug!("Installing fallback font {name}");
+            self.fonts.push((name, egui::FontData::from_owned(bytes)));
+        }
+        ctx.set_fonts(self.font_definitions());
+        true
+    }
+
+    /// Installs the fonts loaded so far on a new context.
+    pub fn install(&self, ctx: &egui::Context) {
+        if !self.fonts.is_empty() {
+            ctx.set_fonts(self.font_definitions());
+        }
+    }
+
+    fn font_definitions(&self) -> egui::FontDefinitions {
+        let mut fonts = egui::FontDefinitions::default();
+        let data: HashMap<String, egui::FontData> = self.fonts.iter().cloned().collect();
+        fonts.font_data.extend(data);
+        for family in [egui::FontFamily::Proportional, egui::FontFamily::Monospace] {
+            if let Some(names) = fonts.families.get_mut(&family) {
+                names.extend(self.fonts.iter().map(|(name, _)| name.clone()));
+            }
+        }
+        fonts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{scripts_in, FontFallback, Script};
+    use std::collections::HashSet;
+
+    #[test]
+    fn detects_scripts_in_file_names() {
+        assert!(scripts_in("C:/Photos/holiday_2024.jpg").is_empty());
+        assert_eq!(
+            scripts_in("写真/سفر/שלום.png"),
+            HashSet::from([Script::Cjk, Script::Arabic, Script::Hebrew])
+        );
+        assert_eq!(
+            scripts_in("ภาพ_नमस्ते_фото.jpg"),
+            HashSet::from([Script::Thai, Script::Devanagari, Script::CyrillicGreek])
+        );
+        assert_eq!(Script::of('한'), Some(Script::Cjk));
+        assert_eq!(Script::of('🦩'), Some(Script::Emoji));
+    }
+
+    #[test]
+    fn fallback_fonts_follow_the_defaults() {
+        let mut fallback = FontFallback::default();
+        fallback
+            .fonts
+            .push(("segoeui".to_string(), egui::FontData::from_static(&[])));
+        let fonts = fallback.font_definitions();
+        let proportional = &fonts.families[&egui::FontFamily::Proportional];
+        assert_eq!(proportional.last().map(String::as_str), Some("segoeui"));
+        assert_ne!(proportional.first().map(String::as_str), Some("segoeui"));
+        assert!(fonts.font_data.contains_key("segoeui"));
+    }
+}
@@ -0,0 +1,218 @@
+//! Recovery from GPU device loss (driver updates, TDR resets).
+//!
+//! A reset destroys the OpenGL context and every texture in it. Drivers with robustness
+//! support report the loss through `GL_CONTEXT_LOST`, which [`Session`] polls about once a
+//! second; on others the next buffer swap fails and `eframe::run_native` ends with a glutin
+//! error. Either way the viewer itself survives: the session hands it back to `main`, which
+//! runs eframe again for a new window, context and egui painter, and the viewer uploads its
+//! textures again from the decoded images it still holds. Open files, marks, the
+//! single-instance lock and hardware video decode all carry over.
+//!
+//! Losses that come less than [`STABLE_PERIOD`] apart count as one streak; after
+//! [`MAX_RECOVERIES`] in a streak `main` gives up, so a broken driver cannot spin the viewer
+//! in a rebuild loop.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use eframe::glow::{self, HasContext};
+use parking_lot::Mutex;
+
+const MAX_RECOVERIES: u32 = 3;
+/// A renderer that ran this long without a loss resets the streak.
+const STABLE_PERIOD: Duration = Duration::from_secs(5 * 60);
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Whether the context reports a reset. Drains a few queued errors to find it.
+pub fn context_lost(gl: &glow::Context) -> bool {
+    for _ in 0..8 {
+        match unsafe { gl.get_error() } {
+            glow::NO_ERROR => return false,
+            glow::CONTEXT_LOST => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Whether `run_native` ended because the surface or context went away.
+pub fn is_device_loss(err: &eframe::Error) -> bool {
+    matches!(err, eframe::Error::Glutin(_) | eframe::Error::OpenGL(_))
+}
+
+/// Repaint requests from background threads, routed to whichever egui context is current.
+/// Threads that outlive a renderer rebuild hold this instead of an `egui::Context`.
+#[derive(Clone, Default)]
+pub struct RepaintTarget(Arc<Mutex<Option<egui::Context>>>);
+
+impl RepaintTarget {
+    pub fn attach(&self, ctx: &egui::Context) {
+        *self.0.lock() = Some(ctx.clone());
+    }
+
+    pub fn request_repaint(&self) {
+        if let Some(ctx) = self.0.lock().as_ref() {
+            ctx.request_repaint();
+        }
+    }
+}
+
+/// What a [`Session`] leaves behind when eframe drops it.
+struct Handoff<A> {
+    app: Option<A>,
+    device_lost: bool,
+    /// Inner and outer rect of the window, in points, as of the last check.
+    window: Option<(egui::Rect, egui::Rect)>,
+}
+
+/// Carries the app across `run_native` calls. Create one per process and wrap the app with
+/// [`Recovery::session`] in every app creator.
+pub struct Recovery<A> {
+    handoff: Rc<RefCell<Handoff<A>>>,
+    losses: u32,
+    last_loss: Option<Instant>,
+}
+
+impl<A> Default for Recovery<A> {
+    fn default() -> Self {
+        Self {
+            handoff: Rc::new(RefCell::new(Handoff {
+                app: None,
+                device_lost: false,
+                window: None,
+            })),
+            losses: 0,
+            last_loss: None,
+        }
+    }
+}
+
+impl<A: eframe::App> Recovery<A> {
+    pub fn session(&self, app: A) -> Session<A> {
+        Session {
+            app: Some(app),
+            handoff: Rc::clone(&self.handoff),
+            lost: false,
+            checked_at: Instant::now(),
+        }
+    }
+
+    /// After `run_native` returned: the app to attach to a new renderer when the run ended
+    /// in a device loss and the streak allows another try. Otherwise the app is dropped here.
+    pub fn take_lost_app(&mut self, result: &eframe::Result) -> Option<A> {
+        let (app, device_lost) = {
+            let mut handoff = self.handoff.borrow_mut();
+            let device_lost = std::mem::take(&mut handoff.device_lost);
+            (handoff.app.take()?, device_lost)
+        };
+        if !device_lost && !result.as_ref().is_err_and(is_device_loss) {
+            return None;
+        }
+        if self
+            .last_loss
+            .is_some_and(|at| at.elapsed() >= STABLE_PERIOD)
+        {
+            self.losses = 0;
+        }
+        self.losses += 1;
+        self.last_loss = Some(Instant::now());
+        if self.losses > MAX_RECOVERIES {
+            tracing::error!("GPU device lost {MAX_RECOVERIES} times in a row; giving up");
+            return None;
+        }
+        tracing::warn!("GPU device lost; rebuilding the renderer");
+        Some(app)
+    }
+
+    /// Where the window that lost its renderer was, as (inner, outer) rect in points.
+    pub fn last_window(&self) -> Option<(egui::Rect, egui::Rect)> {
+        self.handoff.borrow().window
+    }
+}
+
+/// The app as eframe sees it for one `run_native` call. Watches for a lost context and
+/// closes the window when it finds one, so `main` can take the app back.
+pub struct Session<A> {
+    app: Option<A>,
+    handoff: Rc<RefCell<Handoff<A>>>,
+    lost: bool,
+    checked_at: Instant,
+}
+
+impl<A> Drop for Session<A> {
+    fn drop(&mut self) {
+        self.handoff.borrow_mut().app = self.app.take();
+    }
+}
+
+impl<A: eframe::App> eframe::App for Session<A> {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        if !self.lost && self.checked_at.elapsed() >= CHECK_INTERVAL {
+            self.checked_at = Instant::now();
+            let window = ctx.input(|input| {
+                let viewport = input.viewport();
+                viewport.inner_rect.zip(viewport.outer_rect)
+            });
+            let mut handoff = self.handoff.borrow_mut();
+            if window.is_some() {
+                handoff.window = window;
+            }
+            if frame.gl().is_some_and(|gl| context_lost(gl)) {
+                tracing::warn!("OpenGL context lost");
+                self.lost = true;
+                handoff.device_lost = true;
+            }
+        }
+        if self.lost {
+            // Nothing drawn on a lost context reaches the screen; close without asking.
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            return;
+        }
+        if let Some(app) = self.app.as_mut() {
+            app.update(ctx, frame);
+        }
+    }
+
+    fn clear_color(&self, visuals: &egui::Visuals) -> [f32; 4] {
+        self.app
+            .as_ref()
+            .map_or([0.0; 4], |app| app.clear_color(visuals))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Recovery;
+
+    struct NoApp;
+
+    impl eframe::App for NoApp {
+        fn update(&mut self, _ctx: &egui::Context, _frame: &mut eframe::Frame) {}
+    }
+
+    #[test]
+    fn hands_the_app_back_only_after_a_device_loss() {
+        let mut recovery = Recovery::default();
+        drop(recovery.session(NoApp));
+        assert!(recovery.take_lost_app(&Ok(())).is_none());
+
+        drop(recovery.session(NoApp));
+        recovery.handoff.borrow_mut().device_lost = true;
+        assert!(recovery.take_lost_app(&Ok(())).is_some());
+    }
+
+    #[test]
+    fn gives_up_after_a_streak_of_losses() {
+        let mut recovery = Recovery::default();
+        for _ in 0..super::MAX_RECOVERIES {
+            drop(recovery.session(NoApp));
+            recovery.handoff.borrow_mut().device_lost = true;
+            assert!(recovery.take_lost_app(&Ok(())).is_some());
+        }
+        drop(recovery.session(NoApp));
+        recovery.handoff.borrow_mut().device_lost = true;
+        assert!(recovery.take_lost_app(&Ok(())).is_none());
+    }
+}
@@ -0,0 +1,104 @@
+//! Composition guides over the solo-view image: rule-of-thirds lines, an even grid, a center
+//! crosshair, and an outline around every pixel at high zoom.
+//!
+//! Lines are built in image pixel coordinates; the caller maps them to the screen through the
+//! current view, so they follow zoom, pan, rotation and flips.
+
+/// Zoom from which the pixel grid is drawn (800%).
+pub const PIXEL_GRID_MIN_ZOOM: f32 = 8.0;
+/// Pixel-grid lines drawn at most; a larger view skips the grid instead of flooding the
+/// painter.
+const MAX_PIXEL_GRID_LINES: i64 = 2000;
+
+/// A line from one image position to another.
+pub type Line = [egui::Pos2; 2];
+
+/// Lines splitting the image into `divisions` equal columns and rows.
+pub fn grid_lines(image_size: egui::Vec2, divisions: u32) -> Vec<Line> {
+    (1..divisions.max(1))
+        .flat_map(|index| {
+            let t = index as f32 / divisions as f32;
+            let (x, y) = (image_size.x * t, image_size.y * t);
+            [
+                [egui::pos2(x, 0.0), egui::pos2(x, image_size.y)],
+                [egui::pos2(0.0, y), egui::pos2(image_size.x, y)],
+            ]
+        })
+        .collect()
+}
+
+pub fn thirds_lines(image_size: egui::Vec2) -> Vec<Line> {
+    grid_lines(image_size, 3)
+}
+
+/// A cross through the image center, `arm` image pixels long on each side.
+pub fn crosshair_lines(image_size: egui::Vec2, arm: f32) -> [Line; 2] {
+    let center = (image_size * 0.5).to_pos2();
+    [
+        [center - egui::vec2(arm, 0.0), center + egui::vec2(arm, 0.0)],
+        [center - egui::vec2(0.0, arm), center + egui::vec2(0.0, arm)],
+    ]
+}
+
+/// Boundaries between pixels inside `visible`, a region in image coordinates. Empty when the
+/// region holds too many pixels to outline.
+pub fn pixel_grid_lines(image_size: egui::Vec2, visible: egui::Rect) -> Vec<Line> {
+    let visible = visible.intersect(egui::Rect::from_min_size(egui::Pos2::ZERO, image_size));
+    if !visible.is_positive() {
+        return Vec::new();
+    }
+    let (x0, x1) = (visible.min.x.ceil() as i64, visible.max.x.floor() as i64);
+    let (y0, y1) = (visible.min.y.ceil() as i64, visible.max.y.floor() as i64);
+    if (x1 - x0 + 1) + (y1 - y0 + 1) > MAX_PIXEL_GRID_LINES {
+        return Vec::new();
+    }
+    let columns = (x0..=x1).map(|x| {
+        [
+            egui::pos2(x as f32, visible.min.y),
+            egui::pos2(x as f32, visible.max.y),
+        ]
+    });
+    let rows = (y0..=y1).map(|y| {
+        [
+            egui::pos2(visible.min.x, y as f32),
+            egui::pos2(visible.max.x, y as f32),
+        ]
+    });
+    columns.chain(rows).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{grid_lines, pixel_grid_lines, thirds_lines};
+
+    #[test]
+    fn thirds_and_grid_split_the_image_evenly() {
+        let size = egui::vec2(300.0, 150.0);
+        let thirds = thirds_lines(size);
+        assert_eq!(thirds.len(), 4);
+        assert_eq!(
+            thirds[0],
+            [egui::pos2(100.0, 0.0), egui::pos2(100.0, 150.0)]
+        );
+        assert_eq!(
+            thirds[3],
+            [egui::pos2(0.0, 100.0), egui::pos2(300.0, 100.0)]
+        );
+        assert_eq!(grid_lines(size, 8).len(), 14);
+        assert!(grid_lines(size, 1).is_empty());
+    }
+
+    #[test]
+    fn pixel_grid_covers_only_the_visible_pixels() {
+        let size = egui::vec2(1000.0, 1000.0);
+        let visible = egui::Rect::from_min_max(egui::pos2(-2.5, 10.2), egui::pos2(3.5, 12.8));
+        let lines = pixel_grid_lines(size, visible);
+        // Columns 0..=3 and rows 11..=12, clipped to the image edge on the left.
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], [egui::pos2(0.0, 10.2), egui::pos2(0.0, 12.8)]);
+        assert_eq!(lines[4], [egui::pos2(0.0, 11.0), egui::pos2(3.5, 11.0)]);
+
+        let whole = egui::Rect::from_min_size(egui::Pos2::ZERO, size);
+        assert!(pixel_grid_lines(size, whole).is_empty());
+    }
+}
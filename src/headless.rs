@@ -0,0 +1,155 @@
+//! `--thumbnail <in> <out> [--size N]` and `--probe <in>`: thumbnails and metadata without a
+//! window, decoded by the same loaders, plugins and filters as the viewer.
+//!
+//! `--thumbnail` writes a PNG whose longer side is at most `--size` pixels
+//! ([`DEFAULT_THUMBNAIL_SIZE`] by default); animations use their first frame and videos the
+//! frame the Windows shell picks. `--probe` prints
+//! one JSON line on stdout, shaped like the remote control replies: `{"ok":true,...}` with
+//! the media type, dimensions, file size, frame count and EXIF capture time, or
+//! `{"ok":false,"error":"..."}`. Both exit with status 1 when the input cannot be read.
+
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::batch_rename;
+use crate::config::Config;
+use crate::image_loader::{get_media_type, LoadedImage, MediaType};
+use crate::png_export;
+use crate::remote_control::{reply_error, reply_ok, Value};
+use crate::video_thumbnail;
+
+pub const DEFAULT_THUMBNAIL_SIZE: u32 = 256;
+
+/// Longest side `--probe` decodes at; the frame count does not need full-size frames.
+const PROBE_DECODE_SIDE: u32 = 64;
+
+pub enum HeadlessCommand {
+    Thumbnail {
+        input: PathBuf,
+        output: PathBuf,
+        size: u32,
+    },
+    Probe(PathBuf),
+}
+
+/// Runs `command` and returns the process exit code.
+pub fn run(config: &Config, command: HeadlessCommand) -> i32 {
+    match command {
+        HeadlessCommand::Thumbnail {
+            input,
+            output,
+            size,
+        } => match write_thumbnail(config, &input, &output, size) {
+            Ok(()) => 0,
+            Err(err) => {
+                eprintln!("{err}");
+                1
+            }
+        },
+        HeadlessCommand::Probe(input) => {
+            let (reply, code) = match probe(config, &input) {
+                Ok(reply) => (reply, 0),
+                Err(err) => (reply_error(&err), 1),
+            };
+            println!("{reply}");
+            code
+        }
+    }
+}
+
+fn media_type(path: &Path) -> Result<MediaType, String> {
+    if !path.is_file() {
+        return Err(format!("{} is not a file", path.display()));
+    }
+    get_media_type(path).ok_or_else(|| format!("{} is not a supported format", path.display()))
+}
+
+fn load_image(config: &Config, path: &Path, max_side: u32) -> Result<LoadedImage, String> {
+    LoadedImage::load_with_max_texture_side(
+        path,
+        Some(max_side),
+        config.downscale_filter.to_image_filter(),
+        config.gif_resize_filter.to_image_filter(),
+    )
+}
+
+fn write_thumbnail(config: &Config, input: &Path, output: &Path, size: u32) -> Result<(), String> {
+    let (pixels, width, height) = match media_type(input)? {
+        MediaType::Image => {
+            let image = load_image(config, input, size)?;
+            let frame = image.current_frame_data();
+            (frame.pixels.clone(), frame.width, frame.height)
+        }
+        MediaType::Video => {
+            let (pixels, width, height, _, _) =
+                video_thumbnail::extract_video_first_frame_without_gstreamer(input, size)
+                    .ok_or_else(|| format!("Cannot extract a frame from {}", input.display()))?;
+            (pixels, width, height)
+        }
+    };
+    png_export::write_png(pixels, width, height, None, output)
+}
+
+fn probe(config: &Config, input: &Path) -> Result<String, String> {
+    let media = media_type(input)?;
+    let metadata = std::fs::metadata(input)
+        .map_err(|err| format!("Failed to read {}: {err}", input.display()))?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|since| since.as_secs() as f64);
+    let path = input.to_string_lossy();
+    let format = input
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+
+    let (dimensions, frames, duration_ms) = match media {
+        MediaType::Image => {
+            let image = load_image(config, input, PROBE_DECODE_SIDE)?;
+            (
+                Some((image.original_width, image.original_height)),
+                Some(image.frame_count()),
+                Some(image.total_duration_ms()).filter(|_| image.is_animated()),
+            )
+        }
+        MediaType::Video => (
+            video_thumbnail::probe_video_dimensions_with_gstreamer(input),
+            None,
+            None,
+        ),
+    };
+    let taken = batch_rename::exif_taken_at_path(input).map(|taken| {
+        format!(
+            "{}T{:02}:{:02}:{:02}",
+            taken.date(),
+            taken.hour,
+            taken.minute,
+            taken.second
+        )
+    });
+
+    let number = |value: Option<f64>| value.map_or(Value::Null, Value::Number);
+    Ok(reply_ok(&[
+        ("path", Value::Str(&path)),
+        (
+            "media",
+            Value::Str(match media {
+                MediaType::Image => "image",
+                MediaType::Video => "video",
+            }),
+        ),
+        ("format", Value::Str(&format)),
+        ("size", Value::Number(metadata.len() as f64)),
+        ("modified", number(modified)),
+        ("width", number(dimensions.map(|(width, _)| width as f64))),
+        (
+            "height",
+            number(dimensions.map(|(_, height)| height as f64)),
+        ),
+        ("frames", number(frames.map(|frames| frames as f64))),
+        ("duration_ms", number(duration_ms.map(f64::from))),
+        ("taken", taken.as_deref().map_or(Value::Null, Value::Str)),
+    ]))
+}
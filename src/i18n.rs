@@ -0,0 +1,475 @@
+//! Localized UI strings.
+//!
+//! Messages are looked up by key in the table of the current language, and keys a table
+//! lacks fall back to English. The language is `language` from config.ini; `auto` follows
+//! the Windows display locale (`LC_ALL`, `LC_MESSAGES` or `LANG` elsewhere). Japanese and
+//! Chinese text draws with the CJK system fonts that [`crate::font_fallback`] loads on demand.
+//!
+//! Placeholders are written `{name}` and filled by [`tr_args`].
+
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    English,
+    Japanese,
+    /// Simplified Chinese.
+    Chinese,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Self::English, Self::Japanese, Self::Chinese];
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "en" | "english" => Some(Self::English),
+            "ja" | "jp" | "japanese" | "日本語" => Some(Self::Japanese),
+            "zh" | "cn" | "chinese" | "中文" => Some(Self::Chinese),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Japanese => "ja",
+            Self::Chinese => "zh",
+        }
+    }
+
+    /// Language of a locale name such as `ja-JP` or `zh_CN.UTF-8`; English when there is no
+    /// table for it.
+    pub fn from_locale(locale: &str) -> Self {
+        let primary = locale
+            .split(['-', '_', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match primary.as_str() {
+            "ja" => Self::Japanese,
+            "zh" => Self::Chinese,
+            _ => Self::English,
+        }
+    }
+
+    fn table(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::English => ENGLISH,
+            Self::Japanese => JAPANESE,
+            Self::Chinese => CHINESE,
+        }
+    }
+}
+
+static LANGUAGE: AtomicU8 = AtomicU8::new(0);
+
+/// Switches the UI language; `None` follows the system locale.
+pub fn set_language(language: Option<Language>) {
+    let language = language.unwrap_or_else(system_language);
+    let index = Language::ALL
+        .iter()
+        .position(|candidate| *candidate == language)
+        .unwrap_or(0);
+    LANGUAGE.store(index as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    Language::ALL
+        .get(LANGUAGE.load(Ordering::Relaxed) as usize)
+        .copied()
+        .unwrap_or(Language::English)
+}
+
+/// Language of the user's display locale.
+pub fn system_language() -> Language {
+    #[cfg(target_os = "windows")]
+    {
+        crate::windows_env::user_locale_name()
+            .map(|locale| Language::from_locale(&locale))
+            .unwrap_or(Language::English)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map(|locale| Language::from_locale(&locale))
+            .unwrap_or(Language::English)
+    }
+}
+
+/// The message for `key` in the current language.
+pub fn tr(key: &'static str) -> &'static str {
+    lookup(language(), key)
+}
+
+/// [`tr`] with each `{name}` placeholder replaced by its value.
+pub fn tr_args(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = tr(key).to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    text
+}
+
+fn lookup(language: Language, key: &'static str) -> &'static str {
+    let find = |table: &'static [(&'static str, &'static str)]| {
+        table
+            .iter()
+            .find(|(candidate, _)| *candidate == key)
+            .map(|(_, text)| *text)
+    };
+    find(language.table())
+        .or_else(|| find(ENGLISH))
+        .unwrap_or(key)
+}
+
+const ENGLISH: &[(&str, &str)] = &[
+    ("files.one", "1 file"),
+    ("files.many", "{count} files"),
+    ("osd.zoom", "Zoom {percent}%"),
+    ("osd.ui_scale", "UI scale {percent}%"),
+    ("osd.rotated_clockwise", "Rotated 90° clockwise"),
+    (
+        "osd.rotated_counterclockwise",
+        "Rotated 90° counterclockwise",
+    ),
+    ("osd.rotation", "Rotation {degrees}°"),
+    ("osd.flipped_horizontally", "Flipped horizontally"),
+    ("osd.horizontal_flip_off", "Horizontal flip off"),
+    ("osd.flipped_vertically", "Flipped vertically"),
+    ("osd.vertical_flip_off", "Vertical flip off"),
+    ("osd.muted", "Muted"),
+    ("osd.unmuted", "Unmuted"),
+    ("osd.trim_in", "In point {time}"),
+    ("osd.trim_out", "Out point {time}"),
+    ("osd.trim_in_cleared", "In point cleared"),
+    ("osd.trim_out_cleared", "Out point cleared"),
+    ("osd.lut_loaded", "LUT: {name}"),
+    ("osd.window_size", "Window {size}"),
+    ("osd.external_edit_reloaded", "Reloaded after edit"),
+    ("view.original", "Original"),
+    ("osd.eyedropper_on", "Eyedropper: click to copy a color"),
+    ("osd.eyedropper_off", "Eyedropper off"),
+    ("osd.color_copied", "Copied {color}"),
+    ("osd.checkerboard_on", "Checkerboard on"),
+    ("osd.checkerboard_off", "Checkerboard off"),
+    ("osd.thirds_on", "Rule of thirds on"),
+    ("osd.thirds_off", "Rule of thirds off"),
+    ("osd.grid_on", "Grid on"),
+    ("osd.grid_off", "Grid off"),
+    ("osd.crosshair_on", "Crosshair on"),
+    ("osd.crosshair_off", "Crosshair off"),
+    ("osd.zoom_sync_on", "Zoom sync on"),
+    ("osd.zoom_sync_off", "Zoom sync off"),
+    ("osd.shuffle_on", "Shuffle on"),
+    ("osd.shuffle_off", "Shuffle off"),
+    ("osd.repeat_off", "Repeat off"),
+    ("osd.repeat_all", "Repeat all"),
+    ("osd.repeat_one", "Repeat one"),
+    ("osd.copied", "Copied {files}"),
+    ("osd.cut", "Cut {files}"),
+    ("osd.recycled", "Moved {files} to the Recycle Bin"),
+    ("osd.view_copied", "Copied the view ({size})"),
+    (
+        "toast.gpu_recovered",
+        "Recovered from a graphics driver reset",
+    ),
+    ("toast.file_missing", "{path} no longer exists"),
+    ("toast.folder_listing_timed_out", "Gave up listing {path}"),
+    (
+        "toast.not_animated_gif",
+        "Only animated GIFs and videos can be converted",
+    ),
+    ("status.listing_folder", "Listing folder..."),
+    ("status.downloading", "Downloading {name}"),
+    ("status.prefetching", "Prefetching {count} ({amount})"),
+    ("prompt.cancel", "Cancel"),
+    ("jobs.title", "Jobs"),
+    ("jobs.running", "Jobs ({count} running)"),
+    ("jobs.clear_finished", "Clear finished"),
+    ("toast.resumed_at", "Resumed at {time}"),
+    ("prompt.resume_from", "Resume from {time}?"),
+    ("prompt.resume", "Resume"),
+    ("prompt.start_over", "Start over"),
+    ("toast.slideshow_stopped", "Slideshow stopped"),
+    (
+        "toast.no_playlist",
+        "No playlist: open an .m3u file or drop several files",
+    ),
+    ("toast.slideshow_started", "Slideshow: every {seconds} s"),
+    ("error.folder_missing", "Folder does not exist: {path}"),
+    (
+        "error.folder_timed_out",
+        "Listing the folder took too long: {path}",
+    ),
+    ("error.download_failed", "Failed to open {url}: {error}"),
+    (
+        "error.folder_empty",
+        "No supported media files found in folder: {path}",
+    ),
+    (
+        "error.unsupported_format",
+        "Unsupported file format: {path}",
+    ),
+    (
+        "error.sort_folder_unset",
+        "No folder is set for sort_to_folder_{slot}; set sort_folder_{slot} in config.ini",
+    ),
+    ("error.create_folder", "Failed to create {path}: {error}"),
+    (
+        "error.open_config",
+        "Failed to open config file ({path}): {error}",
+    ),
+    (
+        "error.open_editor",
+        "Failed to open {path} in the editor: {error}",
+    ),
+    (
+        "error.open_location",
+        "Failed to open file location ({path}): {error}",
+    ),
+    (
+        "error.playlist_read",
+        "Failed to read playlist {path}: {error}",
+    ),
+    ("error.playlist_empty", "No media files to play in {path}"),
+];
+
+const JAPANESE: &[(&str, &str)] = &[
+    ("files.one", "1 個のファイル"),
+    ("files.many", "{count} 個のファイル"),
+    ("osd.zoom", "ズーム {percent}%"),
+    ("osd.ui_scale", "UI の大きさ {percent}%"),
+    ("osd.rotated_clockwise", "時計回りに 90° 回転"),
+    ("osd.rotated_counterclockwise", "反時計回りに 90° 回転"),
+    ("osd.rotation", "回転 {degrees}°"),
+    ("osd.flipped_horizontally", "左右反転"),
+    ("osd.horizontal_flip_off", "左右反転を解除"),
+    ("osd.flipped_vertically", "上下反転"),
+    ("osd.vertical_flip_off", "上下反転を解除"),
+    ("osd.muted", "ミュート"),
+    ("osd.unmuted", "ミュート解除"),
+    ("osd.trim_in", "開始点 {time}"),
+    ("osd.trim_out", "終了点 {time}"),
+    ("osd.trim_in_cleared", "開始点を解除"),
+    ("osd.trim_out_cleared", "終了点を解除"),
+    ("osd.lut_loaded", "LUT: {name}"),
+    ("osd.window_size", "ウィンドウ {size}"),
+    ("osd.external_edit_reloaded", "編集後に再読み込みしました"),
+    ("view.original", "元の画像"),
+    ("osd.eyedropper_on", "スポイト: クリックで色をコピー"),
+    ("osd.eyedropper_off", "スポイト: オフ"),
+    ("osd.color_copied", "{color} をコピーしました"),
+    ("osd.checkerboard_on", "市松模様: オン"),
+    ("osd.checkerboard_off", "市松模様: オフ"),
+    ("osd.thirds_on", "三分割ガイド: オン"),
+    ("osd.thirds_off", "三分割ガイド: オフ"),
+    ("osd.grid_on", "グリッド: オン"),
+    ("osd.grid_off", "グリッド: オフ"),
+    ("osd.crosshair_on", "中心十字線: オン"),
+    ("osd.crosshair_off", "中心十字線: オフ"),
+    ("osd.zoom_sync_on", "ズーム同期: オン"),
+    ("osd.zoom_sync_off", "ズーム同期: オフ"),
+    ("osd.shuffle_on", "シャッフル: オン"),
+    ("osd.shuffle_off", "シャッフル: オフ"),
+    ("osd.repeat_off", "リピート: オフ"),
+    ("osd.repeat_all", "リピート: すべて"),
+    ("osd.repeat_one", "リピート: 1 件"),
+    ("osd.copied", "{files}をコピーしました"),
+    ("osd.cut", "{files}を切り取りました"),
+    ("osd.recycled", "{files}をごみ箱に移動しました"),
+    ("osd.view_copied", "表示をコピーしました ({size})"),
+    (
+        "toast.gpu_recovered",
+        "グラフィックス ドライバーのリセットから復旧しました",
+    ),
+    ("toast.file_missing", "{path} は存在しません"),
+    ("toast.folder_listing_timed_out", "{path} の一覧の取得を中止しました"),
+    ("toast.not_animated_gif", "変換できるのはアニメーション GIF と動画だけです"),
+    ("status.listing_folder", "フォルダーを読み込み中..."),
+    ("status.downloading", "{name} をダウンロード中"),
+    ("status.prefetching", "{count} 件を先読み中 ({amount})"),
+    ("prompt.cancel", "キャンセル"),
+    ("jobs.title", "ジョブ"),
+    ("jobs.running", "ジョブ ({count} 件実行中)"),
+    ("jobs.clear_finished", "完了分を消去"),
+    ("toast.resumed_at", "{time} から再開しました"),
+    ("prompt.resume_from", "{time} から再開しますか？"),
+    ("prompt.resume", "再開"),
+    ("prompt.start_over", "最初から"),
+    ("toast.slideshow_stopped", "スライドショーを停止しました"),
+    (
+        "toast.no_playlist",
+        "プレイリストがありません: .m3u ファイルを開くか、複数のファイルをドロップしてください",
+    ),
+    ("toast.slideshow_started", "スライドショー: {seconds} 秒ごと"),
+    ("error.folder_missing", "フォルダーが存在しません: {path}"),
+    (
+        "error.folder_timed_out",
+        "フォルダーの一覧の取得に時間がかかりすぎました: {path}",
+    ),
+    ("error.download_failed", "{url} を開けませんでした: {error}"),
+    (
+        "error.folder_empty",
+        "フォルダーに対応するメディア ファイルがありません: {path}",
+    ),
+    (
+        "error.unsupported_format",
+        "対応していないファイル形式です: {path}",
+    ),
+    (
+        "error.sort_folder_unset",
+        "sort_to_folder_{slot} のフォルダーが未設定です。config.ini で sort_folder_{slot} を設定してください",
+    ),
+    ("error.create_folder", "{path} を作成できませんでした: {error}"),
+    (
+        "error.open_config",
+        "設定ファイルを開けませんでした ({path}): {error}",
+    ),
+    (
+        "error.open_editor",
+        "{path} をエディターで開けませんでした: {error}",
+    ),
+    (
+        "error.open_location",
+        "ファイルの場所を開けませんでした ({path}): {error}",
+    ),
+    (
+        "error.playlist_read",
+        "プレイリスト {path} を読み込めませんでした: {error}",
+    ),
+    ("error.playlist_empty", "{path} に再生できるメディア ファイルがありません"),
+];
+
+const CHINESE: &[(&str, &str)] = &[
+    ("files.one", "1 个文件"),
+    ("files.many", "{count} 个文件"),
+    ("osd.zoom", "缩放 {percent}%"),
+    ("osd.ui_scale", "界面缩放 {percent}%"),
+    ("osd.rotated_clockwise", "已顺时针旋转 90°"),
+    ("osd.rotated_counterclockwise", "已逆时针旋转 90°"),
+    ("osd.rotation", "旋转 {degrees}°"),
+    ("osd.flipped_horizontally", "已水平翻转"),
+    ("osd.horizontal_flip_off", "已取消水平翻转"),
+    ("osd.flipped_vertically", "已垂直翻转"),
+    ("osd.vertical_flip_off", "已取消垂直翻转"),
+    ("osd.muted", "已静音"),
+    ("osd.unmuted", "已取消静音"),
+    ("osd.trim_in", "入点 {time}"),
+    ("osd.trim_out", "出点 {time}"),
+    ("osd.trim_in_cleared", "已清除入点"),
+    ("osd.trim_out_cleared", "已清除出点"),
+    ("osd.lut_loaded", "LUT：{name}"),
+    ("osd.window_size", "窗口 {size}"),
+    ("osd.external_edit_reloaded", "编辑后已重新加载"),
+    ("view.original", "原图"),
+    ("osd.eyedropper_on", "取色器: 单击复制颜色"),
+    ("osd.eyedropper_off", "取色器: 关"),
+    ("osd.color_copied", "已复制 {color}"),
+    ("osd.checkerboard_on", "棋盘格背景: 开"),
+    ("osd.checkerboard_off", "棋盘格背景: 关"),
+    ("osd.thirds_on", "三分法参考线: 开"),
+    ("osd.thirds_off", "三分法参考线: 关"),
+    ("osd.grid_on", "网格: 开"),
+    ("osd.grid_off", "网格: 关"),
+    ("osd.crosshair_on", "中心十字线: 开"),
+    ("osd.crosshair_off", "中心十字线: 关"),
+    ("osd.zoom_sync_on", "缩放同步: 开"),
+    ("osd.zoom_sync_off", "缩放同步: 关"),
+    ("osd.shuffle_on", "随机播放: 开"),
+    ("osd.shuffle_off", "随机播放: 关"),
+    ("osd.repeat_off", "循环: 关"),
+    ("osd.repeat_all", "循环: 全部"),
+    ("osd.repeat_one", "循环: 单个"),
+    ("osd.copied", "已复制 {files}"),
+    ("osd.cut", "已剪切 {files}"),
+    ("osd.recycled", "已将 {files}移到回收站"),
+    ("osd.view_copied", "已复制当前视图 ({size})"),
+    ("toast.gpu_recovered", "已从图形驱动程序重置中恢复"),
+    ("toast.file_missing", "{path} 已不存在"),
+    ("toast.folder_listing_timed_out", "已放弃列出 {path}"),
+    ("toast.not_animated_gif", "只能转换 GIF 动画和视频"),
+    ("status.listing_folder", "正在列出文件夹..."),
+    ("status.downloading", "正在下载 {name}"),
+    ("status.prefetching", "正在预取 {count} 项 ({amount})"),
+    ("prompt.cancel", "取消"),
+    ("jobs.title", "任务"),
+    ("jobs.running", "任务（{count} 个进行中）"),
+    ("jobs.clear_finished", "清除已完成"),
+    ("toast.resumed_at", "已从 {time} 继续播放"),
+    ("prompt.resume_from", "从 {time} 继续播放？"),
+    ("prompt.resume", "继续"),
+    ("prompt.start_over", "从头开始"),
+    ("toast.slideshow_stopped", "幻灯片已停止"),
+    (
+        "toast.no_playlist",
+        "没有播放列表: 请打开 .m3u 文件或拖入多个文件",
+    ),
+    ("toast.slideshow_started", "幻灯片: 每 {seconds} 秒"),
+    ("error.folder_missing", "文件夹不存在: {path}"),
+    ("error.folder_timed_out", "列出文件夹耗时过长: {path}"),
+    ("error.download_failed", "无法打开 {url}: {error}"),
+    ("error.folder_empty", "文件夹中没有支持的媒体文件: {path}"),
+    ("error.unsupported_format", "不支持的文件格式: {path}"),
+    (
+        "error.sort_folder_unset",
+        "未设置 sort_to_folder_{slot} 的文件夹；请在 config.ini 中设置 sort_folder_{slot}",
+    ),
+    ("error.create_folder", "无法创建 {path}: {error}"),
+    ("error.open_config", "无法打开配置文件 ({path}): {error}"),
+    ("error.open_editor", "无法在编辑器中打开 {path}: {error}"),
+    ("error.open_location", "无法打开文件位置 ({path}): {error}"),
+    ("error.playlist_read", "无法读取播放列表 {path}: {error}"),
+    ("error.playlist_empty", "{path} 中没有可播放的媒体文件"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{lookup, Language, ENGLISH};
+
+    /// `{name}` placeholders of a message, sorted.
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = text
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    #[test]
+    fn translations_cover_english_keys_with_the_same_placeholders() {
+        for language in Language::ALL {
+            let table = language.table();
+            for (key, text) in table {
+                let english = ENGLISH.iter().find(|(candidate, _)| candidate == key);
+                let (_, english) = english.unwrap_or_else(|| panic!("{key} is not in English"));
+                assert_eq!(placeholders(text), placeholders(english), "{key}");
+            }
+            for (key, _) in ENGLISH {
+                assert!(
+                    table.iter().any(|(candidate, _)| candidate == key),
+                    "{} lacks {key}",
+                    language.as_str()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn locales_pick_a_language_and_unknown_keys_fall_back() {
+        assert_eq!(Language::from_locale("ja-JP"), Language::Japanese);
+        assert_eq!(Language::from_locale("zh_CN.UTF-8"), Language::Chinese);
+        assert_eq!(Language::from_locale("zh-Hant-TW"), Language::Chinese);
+        assert_eq!(Language::from_locale("de-DE"), Language::English);
+        assert_eq!(Language::from_locale(""), Language::English);
+        assert_eq!(lookup(Language::Japanese, "osd.muted"), "ミュート");
+        assert_eq!(lookup(Language::Chinese, "no.such.key"), "no.such.key");
+    }
+}
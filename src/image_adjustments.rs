@@ -0,0 +1,540 @@
+//! Non-destructive brightness/contrast/saturation/gamma adjustments.
+//!
+//! The solo view applies them on the GPU: instead of an egui mesh, the image quad is drawn
+//! through an `egui_glow` paint callback with a small shader that takes the adjustments as
+//! uniforms, so slider changes show up on the next frame without re-uploading the texture.
+//! A loaded `.cube` LUT is applied in the same pass, before the adjustments.
+//! `ImageAdjustments::apply_to_rgba` is the CPU twin used for PNG export.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use eframe::egui_glow;
+use eframe::glow::{self, HasContext as _};
+
+use crate::lut::CubeLut;
+
+pub const BRIGHTNESS_RANGE: std::ops::RangeInclusive<f32> = -1.0..=1.0;
+pub const CONTRAST_RANGE: std::ops::RangeInclusive<f32> = 0.0..=3.0;
+pub const SATURATION_RANGE: std::ops::RangeInclusive<f32> = 0.0..=3.0;
+pub const GAMMA_RANGE: std::ops::RangeInclusive<f32> = 0.2..=5.0;
+
+/// View adjustments, applied in that order: saturation (or grayscale), contrast, brightness,
+/// gamma. All math happens on gamma-encoded sRGB values in `0..=1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageAdjustments {
+    /// Added to every channel.
+    pub brightness: f32,
+    /// Scales the distance from mid-gray.
+    pub contrast: f32,
+    /// Scales the distance from the pixel's luma; 0 is grayscale.
+    pub saturation: f32,
+    /// Output is `value^(1 / gamma)`, so values above 1 brighten the midtones.
+    pub gamma: f32,
+    /// Overrides `saturation` with 0.
+    pub grayscale: bool,
+}
+
+impl Default for ImageAdjustments {
+    fn default() -> Self {
+        Self {
+            brightness: 0.0,
+            contrast: 1.0,
+            saturation: 1.0,
+            gamma: 1.0,
+            grayscale: false,
+        }
+    }
+}
+
+impl ImageAdjustments {
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn effective_saturation(&self) -> f32 {
+        if self.grayscale {
+            0.0
+        } else {
+            self.saturation
+        }
+    }
+
+    /// Mirrors `adjust()` in the fragment shader.
+    fn adjust_rgb(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let luma = 0.2126 * rgb[0] + 0.7152 * rgb[1] + 0.0722 * rgb[2];
+        let saturation = self.effective_saturation();
+        let inv_gamma = 1.0 / self.gamma.max(0.01);
+        rgb.map(|channel| {
+            let saturated = luma + (channel - luma) * saturation;
+            let contrasted = (saturated - 0.5) * self.contrast + 0.5 + self.brightness;
+            contrasted.clamp(0.0, 1.0).powf(inv_gamma)
+        })
+    }
+
+    /// Applies the adjustments to straight (non-premultiplied) RGBA8 pixels in place.
+    pub fn apply_to_rgba(&self, pixels: &mut [u8]) {
+        if self.is_identity() {
+            return;
+        }
+        for pixel in pixels.chunks_exact_mut(4) {
+            let rgb = [pixel[0], pixel[1], pixel[2]].map(|value| value as f32 / 255.0);
+            let adjusted = self.adjust_rgb(rgb);
+            for (channel, value) in pixel.iter_mut().zip(adjusted) {
+                *channel = (value * 255.0).round() as u8;
+            }
+        }
+    }
+}
+
+/// `photo.jpg` -> `photo.adjusted.png`, or `photo.adjusted-2.png` and so on if taken.
+pub fn export_path(image_path: &Path) -> PathBuf {
+    crate::image_loader::sibling_export_path(image_path, "adjusted", "png")
+}
+
+pub fn export_adjusted_png(
+    adjustments: ImageAdjustments,
+    lut: Option<&CubeLut>,
+    mut pixels: Vec<u8>,
+    width: u32,
+    height: u32,
+    background: Option<[u8; 3]>,
+    target: &Path,
+) -> Result<(), String> {
+    if let Some(lut) = lut {
+        lut.apply_to_rgba(&mut pixels);
+    }
+    adjustments.apply_to_rgba(&mut pixels);
+    crate::png_export::write_png(pixels, width, height, background, target)
+}
+
+const VERTEX_SHADER: &str = r#"
+#if NEW_SHADER_INTERFACE
+    #define I in
+    #define O out
+#else
+    #define I attribute
+    #define O varying
+#endif
+
+I vec2 a_pos;
+I vec2 a_tc;
+O vec2 v_tc;
+
+void main() {
+    gl_Position = vec4(a_pos, 0.0, 1.0);
+    v_tc = a_tc;
+}
+"#;
+
+const FRAGMENT_SHADER: &str = r#"
+#ifdef GL_ES
+    precision mediump float;
+#endif
+
+uniform sampler2D u_sampler;
+uniform float u_brightness;
+uniform float u_contrast;
+uniform float u_saturation;
+uniform float u_gamma;
+uniform sampler2D u_lut;
+uniform float u_lut_size;
+uniform vec3 u_lut_min;
+uniform vec3 u_lut_max;
+
+#if NEW_SHADER_INTERFACE
+    in vec2 v_tc;
+    out vec4 f_color;
+    #define gl_FragColor f_color
+    #define texture2D texture
+#else
+    varying vec2 v_tc;
+#endif
+
+// Same conversion as egui's own fragment shader.
+vec3 srgb_gamma_from_linear(vec3 rgb) {
+    bvec3 cutoff = lessThan(rgb, vec3(0.0031308));
+    vec3 lower = rgb * vec3(12.92);
+    vec3 higher = vec3(1.055) * pow(rgb, vec3(1.0 / 2.4)) - vec3(0.055);
+    return mix(higher, lower, vec3(cutoff));
+}
+
+// The LUT strip holds one size × size tile per blue slice; the texture filter interpolates
+// red and green, and two taps blend between blue slices.
+vec3 apply_lut(vec3 rgb) {
+    vec3 coords = clamp((rgb - u_lut_min) / (u_lut_max - u_lut_min), 0.0, 1.0)
+        * (u_lut_size - 1.0);
+    float lower = floor(coords.b);
+    float upper = min(lower + 1.0, u_lut_size - 1.0);
+    vec2 uv = vec2((coords.r + 0.5) / (u_lut_size * u_lut_size), (coords.g + 0.5) / u_lut_size);
+    vec3 below = texture2D(u_lut, uv + vec2(lower / u_lut_size, 0.0)).rgb;
+    vec3 above = texture2D(u_lut, uv + vec2(upper / u_lut_size, 0.0)).rgb;
+    return mix(below, above, coords.b - lower);
+}
+
+vec3 adjust(vec3 rgb) {
+    float luma = dot(rgb, vec3(0.2126, 0.7152, 0.0722));
+    rgb = vec3(luma) + (rgb - vec3(luma)) * u_saturation;
+    rgb = (rgb - vec3(0.5)) * u_contrast + vec3(0.5 + u_brightness);
+    return pow(clamp(rgb, 0.0, 1.0), vec3(1.0 / u_gamma));
+}
+
+void main() {
+    vec4 texel = texture2D(u_sampler, v_tc);
+#if SRGB_TEXTURES
+    texel.rgb = srgb_gamma_from_linear(texel.rgb);
+#endif
+    // egui textures are premultiplied; adjust the straight color and premultiply again.
+    vec3 straight = texel.a > 0.0 ? texel.rgb / texel.a : vec3(0.0);
+    if (u_lut_size > 1.0) {
+        straight = apply_lut(straight);
+    }
+    gl_FragColor = vec4(adjust(straight) * texel.a, texel.a);
+}
+"#;
+
+struct AdjustmentShader {
+    program: glow::Program,
+    vbo: glow::Buffer,
+    vao: Option<glow::VertexArray>,
+    a_pos: u32,
+    a_tc: u32,
+    u_sampler: Option<glow::UniformLocation>,
+    u_brightness: Option<glow::UniformLocation>,
+    u_contrast: Option<glow::UniformLocation>,
+    u_saturation: Option<glow::UniformLocation>,
+    u_gamma: Option<glow::UniformLocation>,
+    u_lut: Option<glow::UniformLocation>,
+    u_lut_size: Option<glow::UniformLocation>,
+    u_lut_min: Option<glow::UniformLocation>,
+    u_lut_max: Option<glow::UniformLocation>,
+    /// Id of the uploaded LUT and its strip texture.
+    lut_texture: Option<(u64, glow::Texture)>,
+}
+
+impl AdjustmentShader {
+    unsafe fn new(gl: &glow::Context) -> Result<Self, String> {
+        let version = egui_glow::ShaderVersion::get(gl);
+        let new_interface = version.is_new_shader_interface();
+        // Matches egui_glow's own detection, which decides how textures are uploaded.
+        let srgb_textures = version == egui_glow::ShaderVersion::Es300
+            || gl
+                .supported_extensions()
+                .iter()
+                .any(|extension| extension.contains("sRGB"));
+        let header = format!(
+            "{}\n#define NEW_SHADER_INTERFACE {}\n#define SRGB_TEXTURES {}\n",
+            version.version_declaration(),
+            new_interface as i32,
+            srgb_textures as i32,
+        );
+
+        let program = gl.create_program()?;
+        let mut shaders = Vec::with_capacity(2);
+        for (kind, source) in [
+            (glow::VERTEX_SHADER, VERTEX_SHADER),
+            (glow::FRAGMENT_SHADER, FRAGMENT_SHADER),
+        ] {
+            let shader = gl.create_shader(kind)?;
+            gl.shader_source(shader, &format!("{header}{source}"));
+            gl.compile_shader(shader);
+            if !gl.get_shader_compile_status(shader) {
+                let log = gl.get_shader_info_log(shader);
+                gl.delete_shader(shader);
+                for shader in shaders {
+                    gl.delete_shader(shader);
+                }
+                gl.delete_program(program);
+                return Err(format!("adjustment shader failed to compile: {log}"));
+            }
+            gl.attach_shader(program, shader);
+            shaders.push(shader);
+        }
+        gl.link_program(program);
+        for shader in shaders {
+            gl.detach_shader(program, shader);
+            gl.delete_shader(shader);
+        }
+        if !gl.get_program_link_status(program) {
+            let log = gl.get_program_info_log(program);
+            gl.delete_program(program);
+            return Err(format!("adjustment shader failed to link: {log}"));
+        }
+
+        let (Some(a_pos), Some(a_tc)) = (
+            gl.get_attrib_location(program, "a_pos"),
+            gl.get_attrib_location(program, "a_tc"),
+        ) else {
+            gl.delete_program(program);
+            return Err("adjustment shader is missing its vertex attributes".to_string());
+        };
+
+        Ok(Self {
+            vbo: gl.create_buffer()?,
+            // GL 3+ and GLES 3 core profiles need a bound VAO; older contexts may lack them.
+            vao: if new_interface {
+                Some(gl.create_vertex_array()?)
+            } else {
+                None
+            },
+            a_pos,
+            a_tc,
+            u_sampler: gl.get_uniform_location(program, "u_sampler"),
+            u_brightness: gl.get_uniform_location(program, "u_brightness"),
+            u_contrast: gl.get_uniform_location(program, "u_contrast"),
+            u_saturation: gl.get_uniform_location(program, "u_saturation"),
+            u_gamma: gl.get_uniform_location(program, "u_gamma"),
+            u_lut: gl.get_uniform_location(program, "u_lut"),
+            u_lut_size: gl.get_uniform_location(program, "u_lut_size"),
+            u_lut_min: gl.get_uniform_location(program, "u_lut_min"),
+            u_lut_max: gl.get_uniform_location(program, "u_lut_max"),
+            lut_texture: None,
+            program,
+        })
+    }
+
+    /// The strip texture for `lut`, uploaded the first time it is drawn. Only the latest LUT
+    /// is kept.
+    unsafe fn lut_texture(&mut self, gl: &glow::Context, lut: &CubeLut) -> Option<glow::Texture> {
+        if let Some((id, texture)) = self.lut_texture {
+            if id == lut.id {
+                return Some(texture);
+            }
+            gl.delete_texture(texture);
+            self.lut_texture = None;
+        }
+        let texture = gl.create_texture().ok()?;
+        gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+        for (parameter, value) in [
+            (glow::TEXTURE_MIN_FILTER, glow::LINEAR),
+            (glow::TEXTURE_MAG_FILTER, glow::LINEAR),
+            (glow::TEXTURE_WRAP_S, glow::CLAMP_TO_EDGE),
+            (glow::TEXTURE_WRAP_T, glow::CLAMP_TO_EDGE),
+        ] {
+            gl.tex_parameter_i32(glow::TEXTURE_2D, parameter, value as i32);
+        }
+        gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 1);
+        // Plain (not sRGB) RGBA so the shader reads the stored values unchanged.
+        gl.tex_image_2d(
+            glow::TEXTURE_2D,
+            0,
+            glow::RGBA as i32,
+            (lut.size * lut.size) as i32,
+            lut.size as i32,
+            0,
+            glow::RGBA,
+            glow::UNSIGNED_BYTE,
+            Some(&lut.strip_rgba()),
+        );
+        self.lut_texture = Some((lut.id, texture));
+        Some(texture)
+    }
+
+    /// Draws a textured quad given as interleaved `[x, y, u, v]` vertices in clip space,
+    /// ordered around the quad. egui restores its own GL state after every callback.
+    unsafe fn paint(
+        &mut self,
+        gl: &glow::Context,
+        texture: glow::Texture,
+        vertices: &[f32; 16],
+        adjustments: &ImageAdjustments,
+        lut: Option<&CubeLut>,
+    ) {
+        let lut_texture = lut.and_then(|lut| Some((lut, self.lut_texture(gl, lut)?)));
+
+        gl.use_program(Some(self.program));
+        gl.uniform_1_i32(self.u_sampler.as_ref(), 0);
+        gl.uniform_1_f32(self.u_brightness.as_ref(), adjustments.brightness);
+        gl.uniform_1_f32(self.u_contrast.as_ref(), adjustments.contrast);
+        gl.uniform_1_f32(
+            self.u_saturation.as_ref(),
+            adjustments.effective_saturation(),
+        );
+        gl.uniform_1_f32(self.u_gamma.as_ref(), adjustments.gamma.max(0.01));
+
+        match lut_texture {
+            Some((lut, lut_texture)) => {
+                gl.active_texture(glow::TEXTURE1);
+                gl.bind_texture(glow::TEXTURE_2D, Some(lut_texture));
+                gl.uniform_1_i32(self.u_lut.as_ref(), 1);
+                gl.uniform_1_f32(self.u_lut_size.as_ref(), lut.size as f32);
+                let [r, g, b] = lut.domain_min;
+                gl.uniform_3_f32(self.u_lut_min.as_ref(), r, g, b);
+                let [r, g, b] = lut.domain_max;
+                gl.uniform_3_f32(self.u_lut_max.as_ref(), r, g, b);
+            }
+            None => gl.uniform_1_f32(self.u_lut_size.as_ref(), 0.0),
+        }
+
+        gl.active_texture(glow::TEXTURE0);
+        gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+
+        if let Some(vao) = self.vao {
+            gl.bind_vertex_array(Some(vao));
+        }
+        let bytes: Vec<u8> = vertices.iter().flat_map(|v| v.to_ne_bytes()).collect();
+        gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.vbo));
+        gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, &bytes, glow::STREAM_DRAW);
+        let stride = 4 * std::mem::size_of::<f32>() as i32;
+        gl.enable_vertex_attrib_array(self.a_pos);
+        gl.vertex_attrib_pointer_f32(self.a_pos, 2, glow::FLOAT, false, stride, 0);
+        gl.enable_vertex_attrib_array(self.a_tc);
+        gl.vertex_attrib_pointer_f32(
+            self.a_tc,
+            2,
+            glow::FLOAT,
+            false,
+            stride,
+            2 * std::mem::size_of::<f32>() as i32,
+        );
+
+        gl.draw_arrays(glow::TRIANGLE_FAN, 0, 4);
+
+        if lut_texture.is_some() {
+            gl.active_texture(glow::TEXTURE1);
+            gl.bind_texture(glow::TEXTURE_2D, None);
+            gl.active_texture(glow::TEXTURE0);
+        }
+
+        gl.disable_vertex_attrib_array(self.a_pos);
+        gl.disable_vertex_attrib_array(self.a_tc);
+        if self.vao.is_some() {
+            gl.bind_vertex_array(None);
+        }
+    }
+}
+
+/// Shared between the app and its paint callbacks. The shader is compiled on first use on
+/// the render thread; if that fails the renderer reports itself unavailable and the view
+/// falls back to unadjusted painting.
+#[derive(Default)]
+pub struct AdjustmentRenderer {
+    shader: Mutex<Option<AdjustmentShader>>,
+    failed: AtomicBool,
+}
+
+impl AdjustmentRenderer {
+    pub fn is_available(&self) -> bool {
+        !self.failed.load(Ordering::Relaxed)
+    }
+
+    /// Builds a paint callback that draws `texture_id` on the quad `corners` (clockwise from
+    /// top-left, in points) with `uvs` per corner. `clip_rect` bounds the callback viewport.
+    pub fn paint_callback(
+        self: &Arc<Self>,
+        clip_rect: egui::Rect,
+        texture_id: egui::TextureId,
+        corners: [egui::Pos2; 4],
+        uvs: [egui::Pos2; 4],
+        adjustments: ImageAdjustments,
+        lut: Option<Arc<CubeLut>>,
+    ) -> egui::PaintCallback {
+        let renderer = Arc::clone(self);
+        let callback = egui_glow::CallbackFn::new(move |info, painter| {
+            let Some(texture) = painter.texture(texture_id) else {
+                return;
+            };
+            let viewport = info.viewport;
+            let mut vertices = [0.0f32; 16];
+            for (index, (corner, uv)) in corners.iter().zip(uvs).enumerate() {
+                vertices[index * 4] = 2.0 * (corner.x - viewport.min.x) / viewport.width() - 1.0;
+                vertices[index * 4 + 1] =
+                    1.0 - 2.0 * (corner.y - viewport.min.y) / viewport.height();
+                vertices[index * 4 + 2] = uv.x;
+                vertices[index * 4 + 3] = uv.y;
+            }
+            renderer.paint(
+                painter.gl(),
+                texture,
+                &vertices,
+                &adjustments,
+                lut.as_deref(),
+            );
+        });
+        egui::PaintCallback {
+            rect: clip_rect,
+            callback: Arc::new(callback),
+        }
+    }
+
+    fn paint(
+        &self,
+        gl: &glow::Context,
+        texture: glow::Texture,
+        vertices: &[f32; 16],
+        adjustments: &ImageAdjustments,
+        lut: Option<&CubeLut>,
+    ) {
+        if self.failed.load(Ordering::Relaxed) {
+            return;
+        }
+        let Ok(mut shader) = self.shader.lock() else {
+            return;
+        };
+        if shader.is_none() {
+            match unsafe { AdjustmentShader::new(gl) } {
+                Ok(created) => *shader = Some(created),
+                Err(err) => {
+                    tracing::warn!("image adjustments disabled: {}", err);
+                    self.failed.store(true, Ordering::Relaxed);
+                    return;
+                }
+            }
+        }
+        if let Some(shader) = shader.as_mut() {
+            unsafe { shader.paint(gl, texture, vertices, adjustments, lut) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_adjustments_leave_pixels_untouched() {
+        let original = vec![0, 64, 128, 255, 200, 10, 99, 128];
+        let mut pixels = original.clone();
+        ImageAdjustments::default().apply_to_rgba(&mut pixels);
+        assert_eq!(pixels, original);
+
+        // Non-identity settings that cancel out must also round-trip exactly.
+        let mut pixels = original.clone();
+        ImageAdjustments {
+            saturation: 1.0 + f32::EPSILON,
+            ..ImageAdjustments::default()
+        }
+        .apply_to_rgba(&mut pixels);
+        assert_eq!(pixels, original);
+    }
+
+    #[test]
+    fn grayscale_brightness_and_gamma_behave() {
+        let mut pixels = vec![255, 0, 0, 255];
+        ImageAdjustments {
+            grayscale: true,
+            ..ImageAdjustments::default()
+        }
+        .apply_to_rgba(&mut pixels);
+        assert_eq!(pixels[0], pixels[1]);
+        assert_eq!(pixels[1], pixels[2]);
+        assert_eq!(pixels[3], 255);
+
+        let mut pixels = vec![100, 100, 100, 40];
+        ImageAdjustments {
+            brightness: 1.0,
+            ..ImageAdjustments::default()
+        }
+        .apply_to_rgba(&mut pixels);
+        assert_eq!(pixels, vec![255, 255, 255, 40]);
+
+        let mut pixels = vec![64, 64, 64, 255];
+        ImageAdjustments {
+            gamma: 2.0,
+            ..ImageAdjustments::default()
+        }
+        .apply_to_rgba(&mut pixels);
+        assert!(pixels[0] > 64);
+    }
+}
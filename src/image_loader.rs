@@ -19,6 +19,8 @@ use zune_core::options::DecoderOptions;
 use zune_image::image::Image as ZuneImage;
 
 use crate::image_resize::resize_rgba;
+use crate::path_access;
+use crate::plugins;
 
 #[cfg(target_os = "windows")]
 use windows::{
@@ -35,9 +37,13 @@ use windows::{
 
 // Keep a generous decode budget so very large static images can load at full quality.
 // Header-based probing and dimension checks still guard against invalid/corrupt inputs.
-const DEFAULT_MAX_DECODE_ALLOC_BYTES: u64 = 2 * 1024 * 1024 * 1024; // 2 GiB
+pub const DEFAULT_MAX_DECODE_ALLOC_BYTES: u64 = 2 * 1024 * 1024 * 1024; // 2 GiB
 const ZUNE_STATIC_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp", "bmp", "psd"];
 const ZUNE_JPEG_EXTENSIONS: &[&str] = &["jpg", "jpeg"];
+/// JPEGs from this many pixels up show their embedded EXIF thumbnail while they decode.
+const JPEG_PREVIEW_MIN_PIXELS: u64 = 12_000_000;
+/// JPEG keeps EXIF in an APP1 segment of at most 64 KiB near the start of the file.
+const JPEG_EXIF_PROBE_BYTES: u64 = 128 * 1024;
 const WEBP_STREAM_CHANNEL_CAPACITY: usize = 96;
 const GIF_FRAME_WINDOW_SIZE: usize = 72;
 const GIF_WINDOW_MODE_THRESHOLD_BYTES: usize = 96 * 1024 * 1024;
@@ -48,6 +54,19 @@ impl<T: BufRead + Seek> BufReadSeek for T {}
 fn open_media_reader(path: &Path) -> Result<Box<dyn BufReadSeek>, String> {
     let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
 
+    if path_access::is_network_path(path) {
+        let len = file.metadata().map_or(0, |metadata| metadata.len());
+        let mut reader = BufReader::with_capacity(path_access::NETWORK_READ_AHEAD, file);
+        if len > path_access::NETWORK_READ_WHOLE_MAX_BYTES {
+            return Ok(Box::new(reader));
+        }
+        let mut bytes = Vec::with_capacity(len as usize);
+        reader
+            .read_to_end(&mut bytes)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        return Ok(Box::new(Cursor::new(bytes)));
+    }
+
     // SAFETY: We keep the mapping owned inside `Cursor<Mmap>` and never mutate through it.
     // If memory mapping fails (e.g. permission/platform constraints), we fall back to buffered I/O.
     match unsafe { MmapOptions::new().map(&file) } {
@@ -294,7 +313,33 @@ fn decode_static_with_image_reader_limits(path: &Path) -> Result<(u32, u32, Vec<
     Ok((width, height, rgba.into_raw()))
 }
 
+/// Decodes a JPEG with the pure-Rust zune-jpeg path, whichever backend is built in.
+pub fn decode_jpeg_with_zune(path: &Path) -> Result<(u32, u32, Vec<u8>), String> {
+    decode_static_with_zune_limits(path)
+}
+
 fn open_image_with_reasonable_limits(path: &Path) -> Result<(u32, u32, Vec<u8>), String> {
+    plugins::registry()
+        .decode(path)
+        .unwrap_or_else(|| decode_builtin_image(path))
+}
+
+/// The built-in still-image decoder the format registry holds for
+/// [`SUPPORTED_IMAGE_EXTENSIONS`]; also the fallback for files with unregistered extensions.
+pub fn decode_builtin_image(path: &Path) -> Result<(u32, u32, Vec<u8>), String> {
+    #[cfg(feature = "turbojpeg")]
+    if extension_matches(path, ZUNE_JPEG_EXTENSIONS) {
+        match crate::turbo_jpeg::decode_file(path) {
+            Ok(decoded) => return Ok(decoded),
+            Err(err) => tracing::debug!(
+                target: "image",
+                path = %path.display(),
+                error = %err,
+                "libjpeg-turbo decode failed; falling back to zune-jpeg"
+            ),
+        }
+    }
+
     if should_decode_static_with_zune(path) {
         decode_static_with_zune_limits(path)
     } else {
@@ -302,23 +347,16 @@ fn open_image_with_reasonable_limits(path: &Path) -> Result<(u32, u32, Vec<u8>),
     }
 }
 
-/// Supported image extensions
+/// Image extensions with a built-in decoder
 pub const SUPPORTED_IMAGE_EXTENSIONS: &[&str] = &[
     "jpg", "jpeg", "png", "webp", "gif", "bmp", "psd", "ico", "tiff", "tif",
 ];
 
-/// Supported video extensions
+/// Video extensions played through GStreamer
 pub const SUPPORTED_VIDEO_EXTENSIONS: &[&str] = &[
     "mp4", "mkv", "webm", "avi", "mov", "wmv", "flv", "m4v", "3gp", "ogv",
 ];
 
-/// All supported media extensions (images + videos)
-pub const SUPPORTED_EXTENSIONS: &[&str] = &[
-    // Images
-    "jpg", "jpeg", "png", "webp", "gif", "bmp", "psd", "ico", "tiff", "tif", // Videos
-    "mp4", "mkv", "webm", "avi", "mov", "wmv", "flv", "m4v", "3gp", "ogv",
-];
-
 /// Synthetic entry name used to navigate to the parent directory.
 pub const FOLDER_UP_ENTRY_NAME: &str = "[]...]";
 
@@ -329,30 +367,53 @@ pub enum MediaType {
     Video,
 }
 
-/// Check if a file is a supported image
+/// Check if a file is a supported image, built in or opened by a plugin
 pub fn is_supported_image(path: &Path) -> bool {
-    extension_matches(path, SUPPORTED_IMAGE_EXTENSIONS)
+    get_media_type(path) == Some(MediaType::Image)
 }
 
 /// Check if a file is a supported video
 pub fn is_supported_video(path: &Path) -> bool {
-    extension_matches(path, SUPPORTED_VIDEO_EXTENSIONS)
+    get_media_type(path) == Some(MediaType::Video)
+}
+
+/// First free `<stem>.<tag>.<extension>` next to `path`, then `<stem>.<tag>-2.<extension>`
+/// and so on. Used for exports that must never overwrite the source or an earlier export.
+pub fn sibling_export_path(path: &Path, tag: &str, extension: &str) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "image".to_string());
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut candidate = dir.join(format!("{stem}.{tag}.{extension}"));
+    let mut counter = 2;
+    while candidate.exists() {
+        candidate = dir.join(format!("{stem}.{tag}-{counter}.{extension}"));
+        counter += 1;
+    }
+    candidate
 }
 
 /// Check if a file is any supported media (image or video)
 pub fn is_supported_media(path: &Path) -> bool {
-    extension_matches(path, SUPPORTED_EXTENSIONS)
+    get_media_type(path).is_some()
 }
 
-/// Get the media type for a file
+/// Get the media type for a file, from the format registry
 pub fn get_media_type(path: &Path) -> Option<MediaType> {
-    if is_supported_image(path) {
-        Some(MediaType::Image)
-    } else if is_supported_video(path) {
-        Some(MediaType::Video)
-    } else {
-        None
+    plugins::registry().media_type(path)
+}
+
+/// [`get_media_in_directory`], given up after [`path_access::LISTING_TIMEOUT`] on network
+/// shares. `None` when the listing timed out.
+pub fn get_media_in_directory_bounded(path: &Path) -> Option<Vec<PathBuf>> {
+    if !path_access::is_network_path(path) {
+        return Some(get_media_in_directory(path));
     }
+    let path = path.to_path_buf();
+    path_access::list_with_timeout("media-directory-listing", move || {
+        get_media_in_directory(&path)
+    })
 }
 
 /// Get all media files (images and videos) in the same directory as the given path
@@ -453,6 +514,13 @@ pub struct ImageFrame {
     pub delay_ms: u32,
 }
 
+impl ImageFrame {
+    /// True if any pixel is not fully opaque.
+    pub fn has_transparency(&self) -> bool {
+        self.pixels.chunks_exact(4).any(|pixel| pixel[3] != u8::MAX)
+    }
+}
+
 enum AnimationStorage {
     FullyDecoded,
     GifWindow(GifWindowState),
@@ -558,6 +626,64 @@ impl LoadedImage {
         }
     }
 
+    /// The thumbnail a camera embedded in a large JPEG, stretched to the full image size, to
+    /// show while the full decode runs. `None` for smaller images, files without an EXIF
+    /// thumbnail and thumbnails whose aspect ratio does not match (crops, letterboxing).
+    pub fn load_embedded_jpeg_preview(path: &Path) -> Option<Self> {
+        if !extension_matches(path, ZUNE_JPEG_EXTENSIONS) {
+            return None;
+        }
+        let (width, height) = probe_image_dimensions(path)?;
+        if u64::from(width) * u64::from(height) < JPEG_PREVIEW_MIN_PIXELS {
+            return None;
+        }
+        let frame = Self::embedded_jpeg_thumbnail(path, width, height)?;
+        Some(Self::from_single_frame(
+            path.to_path_buf(),
+            frame,
+            width,
+            height,
+        ))
+    }
+
+    /// The EXIF thumbnail of the `width` x `height` JPEG at `path`, decoded to RGBA at its own
+    /// small size; `None` when its aspect ratio does not match. Only the start of the file is
+    /// read.
+    pub fn embedded_jpeg_thumbnail(path: &Path, width: u32, height: u32) -> Option<ImageFrame> {
+        if !extension_matches(path, ZUNE_JPEG_EXTENSIONS) || width == 0 || height == 0 {
+            return None;
+        }
+        let mut head = Vec::new();
+        File::open(path)
+            .ok()?
+            .take(JPEG_EXIF_PROBE_BYTES)
+            .read_to_end(&mut head)
+            .ok()?;
+        let exif = crate::exif::jpeg_exif_block(&head)?;
+        let thumbnail = crate::exif::Tiff::parse(exif)?.thumbnail()?;
+
+        let options = DecoderOptions::new_fast().jpeg_set_out_colorspace(ColorSpace::RGBA);
+        let mut img = ZuneImage::read(Cursor::new(thumbnail), options).ok()?;
+        img.convert_color(ColorSpace::RGBA).ok()?;
+        let (thumb_width, thumb_height) = img.dimensions();
+        let pixels = img.flatten_to_u8().into_iter().next()?;
+        if thumb_width == 0 || thumb_height == 0 || pixels.len() != thumb_width * thumb_height * 4 {
+            return None;
+        }
+        let thumb_aspect = thumb_width as f64 / thumb_height as f64;
+        let aspect = f64::from(width) / f64::from(height);
+        if (thumb_aspect / aspect - 1.0).abs() > 0.02 {
+            return None;
+        }
+
+        Some(ImageFrame {
+            pixels,
+            width: thumb_width as u32,
+            height: thumb_height as u32,
+            delay_ms: 0,
+        })
+    }
+
     /// Start streaming WebP animation frames one-by-one through an `mpsc` channel.
     ///
     /// The **first frame** is intentionally skipped because the caller already
@@ -772,6 +898,7 @@ impl LoadedImage {
         downscale_filter: FilterType,
     ) -> Result<Self, String> {
         let (mut width, mut height, mut pixels) = open_image_with_reasonable_limits(path)?;
+        plugins::registry().apply_filters(width, height, &mut pixels);
         let source_width = width;
         let source_height = height;
 
@@ -1216,33 +1343,47 @@ impl LoadedImage {
         }
     }
 
-    /// Get current position as a fraction (0.0 to 1.0) based on frame index
-    pub fn position_fraction(&self) -> f64 {
-        if self.frame_count() <= 1 {
-            return 0.0;
+    /// Delays of all frames in milliseconds, in frame order.
+    fn frame_delays_ms(&self) -> Vec<u32> {
+        match &self.animation_storage {
+            AnimationStorage::FullyDecoded => self.frames.iter().map(|f| f.delay_ms).collect(),
+            AnimationStorage::GifWindow(state) => state.frame_delays_ms.clone(),
         }
+    }
 
-        // Calculate position based on cumulative time of frames before current
-        let total_duration = self.total_duration_ms() as f64;
-        if total_duration <= 0.0 {
-            return self.current_frame_index() as f64 / (self.frame_count() - 1) as f64;
-        }
+    /// Start time of every frame in milliseconds, in frame order.
+    pub fn frame_timestamps_ms(&self) -> Vec<u64> {
+        let mut elapsed = 0u64;
+        self.frame_delays_ms()
+            .into_iter()
+            .map(|delay| {
+                let start = elapsed;
+                elapsed += u64::from(delay);
+                start
+            })
+            .collect()
+    }
 
-        let mut cumulative_time: f64 = 0.0;
-        let current = self.current_frame_index();
-        match &self.animation_storage {
-            AnimationStorage::FullyDecoded => {
-                for i in 0..current {
-                    cumulative_time += self.frames[i].delay_ms as f64;
-                }
-            }
-            AnimationStorage::GifWindow(state) => {
-                for i in 0..current {
-                    cumulative_time += state.frame_delays_ms[i] as f64;
-                }
-            }
-        }
-        cumulative_time / total_duration
+    /// Start time of the current frame in milliseconds.
+    pub fn position_ms(&self) -> u64 {
+        self.frame_timestamps_ms()
+            .get(self.current_frame_index())
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Index of the frame on screen `time_ms` into the animation. Times past the end give
+    /// the last frame.
+    pub fn frame_at_time_ms(&self, time_ms: u64) -> usize {
+        let timestamps = self.frame_timestamps_ms();
+        timestamps
+            .partition_point(|start| *start <= time_ms)
+            .saturating_sub(1)
+    }
+
+    /// Seek to the frame on screen `time_ms` into the animation.
+    pub fn seek_to_time_ms(&mut self, time_ms: u64) {
+        self.set_frame(self.frame_at_time_ms(time_ms));
     }
 
     pub fn current_delay_ms(&self) -> u32 {
@@ -1299,7 +1440,8 @@ impl LoadedImage {
 
     /// Get display dimensions after rotation
     /// Since we physically rotate the pixel data, the dimensions are simply
-    /// the current original_width and original_height (which get swapped during rotation)
+    /// the current original_width and original_height (which get swapped during rotation).
+    /// Flips are applied when drawing and never change them.
     pub fn display_dimensions(&self) -> (u32, u32) {
         // The original_width/height are already updated when we rotate,
         // so just return them directly
@@ -1316,6 +1458,12 @@ impl LoadedImage {
         self.apply_rotation_ccw();
     }
 
+    /// The current frame as shown: 90° rotations are already in the pixels, the view's flips
+    /// are applied to the copy.
+    pub fn oriented_frame(&self, flip_horizontal: bool, flip_vertical: bool) -> ImageFrame {
+        flip_frame(self.current_frame_data(), flip_horizontal, flip_vertical)
+    }
+
     /// Apply rotation to all frames (clockwise)
     fn apply_rotation(&mut self) {
         for frame in &mut self.frames {
@@ -1387,6 +1535,32 @@ fn rotate_frame_90_ccw(frame: &ImageFrame) -> ImageFrame {
     }
 }
 
+/// Mirror a frame left-right and/or top-bottom
+fn flip_frame(frame: &ImageFrame, horizontal: bool, vertical: bool) -> ImageFrame {
+    let width = frame.width as usize;
+    let height = frame.height as usize;
+    let mut new_pixels = Vec::with_capacity(frame.pixels.len());
+
+    for y in 0..height {
+        let src_y = if vertical { height - 1 - y } else { y };
+        let row = &frame.pixels[src_y * width * 4..(src_y + 1) * width * 4];
+        if horizontal {
+            for pixel in row.chunks_exact(4).rev() {
+                new_pixels.extend_from_slice(pixel);
+            }
+        } else {
+            new_pixels.extend_from_slice(row);
+        }
+    }
+
+    ImageFrame {
+        pixels: new_pixels,
+        width: frame.width,
+        height: frame.height,
+        delay_ms: frame.delay_ms,
+    }
+}
+
 /// Simple natural sort comparison for filenames
 pub mod natord {
     pub fn compare(a: &str, b: &str) -> std::cmp::Ordering {
@@ -1435,7 +1609,7 @@ pub mod natord {
 
 #[cfg(test)]
 mod tests {
-    use super::{get_media_in_directory, static_zune_decoder_options, LoadedImage};
+    use super::{get_media_in_directory, static_zune_decoder_options, ImageFrame, LoadedImage};
     use image::imageops::FilterType;
     use std::fs;
     use std::path::{Path, PathBuf};
@@ -1529,4 +1703,56 @@ mod tests {
 
         let _ = fs::remove_dir_all(&root);
     }
+
+    #[test]
+    fn seeking_by_time_follows_frame_delays() {
+        let frame = |delay_ms: u32| ImageFrame {
+            pixels: vec![0, 0, 0, 255],
+            width: 1,
+            height: 1,
+            delay_ms,
+        };
+        let mut image = LoadedImage::from_single_frame(PathBuf::from("a.gif"), frame(100), 1, 1);
+        image.frames.extend([frame(900), frame(50)]);
+
+        assert_eq!(image.frame_timestamps_ms(), [0, 100, 1000]);
+        assert_eq!(image.frame_at_time_ms(0), 0);
+        assert_eq!(image.frame_at_time_ms(999), 1);
+        assert_eq!(image.frame_at_time_ms(5000), 2);
+
+        image.seek_to_time_ms(500);
+        assert_eq!(image.current_frame_index(), 1);
+        assert_eq!(image.position_ms(), 100);
+    }
+
+    #[test]
+    fn oriented_frame_mirrors_without_resizing() {
+        // 2×2: 1 2 / 3 4, one red value per pixel.
+        let frame = ImageFrame {
+            pixels: vec![1, 0, 0, 255, 2, 0, 0, 255, 3, 0, 0, 255, 4, 0, 0, 255],
+            width: 2,
+            height: 2,
+            delay_ms: 0,
+        };
+        let image = LoadedImage::from_single_frame(PathBuf::from("a.png"), frame, 2, 2);
+        let reds = |frame: ImageFrame| frame.pixels.iter().step_by(4).copied().collect::<Vec<_>>();
+
+        assert_eq!(reds(image.oriented_frame(false, false)), [1, 2, 3, 4]);
+        assert_eq!(reds(image.oriented_frame(true, false)), [2, 1, 4, 3]);
+        assert_eq!(reds(image.oriented_frame(false, true)), [3, 4, 1, 2]);
+        assert_eq!(reds(image.oriented_frame(true, true)), [4, 3, 2, 1]);
+        assert_eq!(image.display_dimensions(), (2, 2));
+    }
+
+    #[test]
+    fn has_transparency_checks_the_alpha_channel_only() {
+        let frame = |pixels: Vec<u8>| ImageFrame {
+            pixels,
+            width: 2,
+            height: 1,
+            delay_ms: 0,
+        };
+        assert!(!frame(vec![0, 0, 0, 255, 10, 20, 30, 255]).has_transparency());
+        assert!(frame(vec![0, 0, 0, 255, 255, 255, 255, 254]).has_transparency());
+    }
 }
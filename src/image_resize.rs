@@ -83,13 +83,62 @@ pub(crate) fn downscale_rgba_if_needed<'a>(
     (new_w, new_h, Cow::Owned(resized.into_raw()))
 }
 
+/// Separable box blur for small RGBA previews. Edges clamp, so borders do not darken.
+/// Buffers that do not match `width * height * 4` are left untouched.
+pub(crate) fn box_blur_rgba_in_place(width: u32, height: u32, pixels: &mut [u8], radius: u32) {
+    let (w, h, r) = (width as usize, height as usize, radius as usize);
+    if r == 0 || w == 0 || h == 0 || pixels.len() != w * h * 4 {
+        return;
+    }
+
+    let window = (2 * r + 1) as u32;
+    let mut scratch = vec![0_u8; pixels.len()];
+
+    // Horizontal pass: pixels -> scratch.
+    for y in 0..h {
+        let row = y * w;
+        for x in 0..w {
+            let mut sum = [0_u32; 4];
+            for dx in 0..=2 * r {
+                let sx = (x + dx).saturating_sub(r).min(w - 1);
+                let src = (row + sx) * 4;
+                for (acc, &value) in sum.iter_mut().zip(&pixels[src..src + 4]) {
+                    *acc += value as u32;
+                }
+            }
+            let dst = (row + x) * 4;
+            for (out, acc) in scratch[dst..dst + 4].iter_mut().zip(sum) {
+                *out = (acc / window) as u8;
+            }
+        }
+    }
+
+    // Vertical pass: scratch -> pixels.
+    for y in 0..h {
+        for x in 0..w {
+            let mut sum = [0_u32; 4];
+            for dy in 0..=2 * r {
+                let sy = (y + dy).saturating_sub(r).min(h - 1);
+                let src = (sy * w + x) * 4;
+                for (acc, &value) in sum.iter_mut().zip(&scratch[src..src + 4]) {
+                    *acc += value as u32;
+                }
+            }
+            let dst = (y * w + x) * 4;
+            for (out, acc) in pixels[dst..dst + 4].iter_mut().zip(sum) {
+                *out = (acc / window) as u8;
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::borrow::Cow;
 
     use image::imageops::FilterType;
 
-    use super::{downscale_rgba_if_needed, resize_rgba_with_fir};
+    use super::{box_blur_rgba_in_place, downscale_rgba_if_needed, resize_rgba_with_fir};
 
     #[test]
     fn fir_resize_rejects_mismatched_rgba_buffer() {
@@ -108,4 +157,19 @@ mod tests {
         assert_eq!((width, height), (2, 2));
         assert!(matches!(resized, Cow::Borrowed(_)));
     }
+
+    #[test]
+    fn box_blur_spreads_a_single_bright_pixel_and_keeps_flat_areas() {
+        let mut pixels = vec![0_u8; 3 * 3 * 4];
+        pixels[(3 + 1) * 4..(3 + 1) * 4 + 4].copy_from_slice(&[255, 255, 255, 255]);
+
+        box_blur_rgba_in_place(3, 3, &mut pixels, 1);
+
+        // 255 spread over a 3x3 window.
+        assert!(pixels.chunks_exact(4).all(|px| px[0] == 28));
+
+        let mut flat = vec![200_u8; 4 * 2 * 4];
+        box_blur_rgba_in_place(4, 2, &mut flat, 2);
+        assert!(flat.iter().all(|&v| v == 200));
+    }
 }
@@ -0,0 +1,331 @@
+//! Background jobs: conversions, trims, exports and other work that takes longer than a frame.
+//!
+//! [`JobManager::spawn`] queues a job and runs it on the shared worker pool, at most
+//! [`MAX_RUNNING_JOBS`] at a time so encodes do not starve image decoding. A job reports how
+//! far it got and checks for cancellation through its [`JobContext`]; the manager picks the
+//! events up in [`JobManager::poll`] and keeps the list shown in the jobs panel. Jobs keep
+//! running after the dialog that started them is closed.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Jobs running at the same time; later ones wait in the queue.
+pub const MAX_RUNNING_JOBS: usize = 2;
+/// Finished jobs kept in the list; older ones are dropped.
+const MAX_FINISHED_JOBS: usize = 20;
+
+pub type JobId = u64;
+
+/// Work run by a job. `Ok` holds the line shown when it is done, `Err` why it failed.
+type JobFn = Box<dyn FnOnce(&JobContext) -> Result<String, String> + Send>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobState {
+    /// Waiting for a free worker.
+    Queued,
+    Running,
+    /// Finished, with a line describing the result.
+    Done(String),
+    Failed(String),
+    Cancelled,
+}
+
+impl JobState {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Done(_) | Self::Failed(_) | Self::Cancelled)
+    }
+}
+
+pub struct Job {
+    pub id: JobId,
+    pub title: String,
+    /// Fraction done, once the job has reported any.
+    pub progress: Option<f32>,
+    pub state: JobState,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Job {
+    /// One line for the jobs panel and dialogs.
+    pub fn status_text(&self) -> String {
+        match &self.state {
+            JobState::Queued => "Waiting…".to_string(),
+            JobState::Running => match self.progress {
+                Some(done) => format!("{:.0}%", done.clamp(0.0, 1.0) * 100.0),
+                None => "Running…".to_string(),
+            },
+            JobState::Done(message) | JobState::Failed(message) => message.clone(),
+            JobState::Cancelled => "Cancelled".to_string(),
+        }
+    }
+}
+
+enum JobEvent {
+    Progress(JobId, f32),
+    Finished(JobId, Result<String, String>),
+}
+
+/// Handed to a running job.
+pub struct JobContext {
+    id: JobId,
+    events: crossbeam_channel::Sender<JobEvent>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl JobContext {
+    /// Reports the fraction done.
+    pub fn set_progress(&self, done: f32) {
+        let _ = self.events.send(JobEvent::Progress(self.id, done));
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// The flag set when the job is cancelled, for functions that poll it themselves.
+    pub fn cancel_flag(&self) -> &AtomicBool {
+        &self.cancelled
+    }
+}
+
+pub struct JobManager {
+    jobs: Vec<Job>,
+    queue: VecDeque<(JobId, JobFn)>,
+    next_id: JobId,
+    events_tx: crossbeam_channel::Sender<JobEvent>,
+    events_rx: crossbeam_channel::Receiver<JobEvent>,
+}
+
+impl Default for JobManager {
+    fn default() -> Self {
+        let (events_tx, events_rx) = crossbeam_channel::unbounded();
+        Self {
+            jobs: Vec::new(),
+            queue: VecDeque::new(),
+            next_id: 1,
+            events_tx,
+            events_rx,
+        }
+    }
+}
+
+impl JobManager {
+    /// Queues `work` under `title` and starts it when a worker is free.
+    pub fn spawn(
+        &mut self,
+        title: impl Into<String>,
+        work: impl FnOnce(&JobContext) -> Result<String, String> + Send + 'static,
+    ) -> JobId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.jobs.push(Job {
+            id,
+            title: title.into(),
+            progress: None,
+            state: JobState::Queued,
+            cancelled: Arc::new(AtomicBool::new(false)),
+        });
+        self.queue.push_back((id, Box::new(work)));
+        self.start_queued();
+        id
+    }
+
+    pub fn job(&self, id: JobId) -> Option<&Job> {
+        self.jobs.iter().find(|job| job.id == id)
+    }
+
+    /// Running and queued jobs first, then finished ones, each in the order they were started.
+    pub fn jobs(&self) -> impl Iterator<Item = &Job> {
+        let active = self.jobs.iter().filter(|job| !job.state.is_finished());
+        active.chain(self.jobs.iter().filter(|job| job.state.is_finished()))
+    }
+
+    /// Whether the job `id` is queued or running.
+    pub fn is_active(&self, id: Option<JobId>) -> bool {
+        id.and_then(|id| self.job(id))
+            .is_some_and(|job| !job.state.is_finished())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// Jobs that are queued or running.
+    pub fn active_count(&self) -> usize {
+        self.jobs
+            .iter()
+            .filter(|job| !job.state.is_finished())
+            .count()
+    }
+
+    /// Asks the job to stop. A queued job is dropped on the next [`poll`](Self::poll); a
+    /// running one stops at its next check.
+    pub fn cancel(&self, id: JobId) {
+        if let Some(job) = self.job(id) {
+            job.cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+
+    pub fn clear_finished(&mut self) {
+        self.jobs.retain(|job| !job.state.is_finished());
+    }
+
+    /// Applies worker events and starts queued jobs. Returns the jobs that finished since the
+    /// last call.
+    pub fn poll(&mut self) -> Vec<JobId> {
+        let mut finished = Vec::new();
+        while let Ok(event) = self.events_rx.try_recv() {
+            match event {
+                JobEvent::Progress(id, done) => {
+                    if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+                        job.progress = Some(done.clamp(0.0, 1.0));
+                    }
+                }
+                JobEvent::Finished(id, result) => {
+                    if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+                        job.state = match result {
+                            _ if job.cancelled.load(Ordering::Relaxed) => JobState::Cancelled,
+                            Ok(message) => JobState::Done(message),
+                            Err(err) => JobState::Failed(err),
+                        };
+                        finished.push(id);
+                    }
+                }
+            }
+        }
+
+        let jobs = &mut self.jobs;
+        self.queue.retain(|(id, _)| {
+            let Some(job) = jobs.iter_mut().find(|job| job.id == *id) else {
+                return false;
+            };
+            if !job.cancelled.load(Ordering::Relaxed) {
+                return true;
+            }
+            job.state = JobState::Cancelled;
+            finished.push(*id);
+            false
+        });
+        self.start_queued();
+
+        let finished_count = self
+            .jobs
+            .iter()
+            .filter(|job| job.state.is_finished())
+            .count();
+        let mut excess = finished_count.saturating_sub(MAX_FINISHED_JOBS);
+        self.jobs.retain(|job| {
+            if excess > 0 && job.state.is_finished() {
+                excess -= 1;
+                return false;
+            }
+            true
+        });
+        finished
+    }
+
+    fn start_queued(&mut self) {
+        while self.running_count() < MAX_RUNNING_JOBS {
+            let Some((id, work)) = self.queue.pop_front() else {
+                break;
+            };
+            let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) else {
+                continue;
+            };
+            job.state = JobState::Running;
+            let context = JobContext {
+                id,
+                events: self.events_tx.clone(),
+                cancelled: Arc::clone(&job.cancelled),
+            };
+            // Failures come back as `Err`; a panic is a crash like anywhere else and goes
+            // through the crash reporter.
+            crate::async_runtime::spawn_blocking_or_thread("background-job", move || {
+                let result = work(&context);
+                let _ = context.events.send(JobEvent::Finished(context.id, result));
+            });
+        }
+    }
+
+    fn running_count(&self) -> usize {
+        self.jobs
+            .iter()
+            .filter(|job| job.state == JobState::Running)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crossbeam_channel::RecvTimeoutError;
+
+    use super::{JobManager, JobState, MAX_RUNNING_JOBS};
+
+    fn poll_until_idle(jobs: &mut JobManager) -> Vec<u64> {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut finished = Vec::new();
+        while jobs.active_count() > 0 && Instant::now() < deadline {
+            finished.extend(jobs.poll());
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        finished
+    }
+
+    #[test]
+    fn jobs_report_progress_and_results() {
+        let mut jobs = JobManager::default();
+        let done = jobs.spawn("Export", |context| {
+            context.set_progress(0.5);
+            Ok("Exported a.pdf".to_string())
+        });
+        let failed = jobs.spawn("Convert", |_| Err("No frames".to_string()));
+        let finished = poll_until_idle(&mut jobs);
+        assert_eq!(finished.len(), 2);
+
+        let job = jobs.job(done).unwrap();
+        assert_eq!(job.state, JobState::Done("Exported a.pdf".to_string()));
+        assert_eq!(job.progress, Some(0.5));
+        assert_eq!(jobs.job(failed).unwrap().status_text(), "No frames");
+
+        jobs.clear_finished();
+        assert!(jobs.is_empty());
+    }
+
+    #[test]
+    fn queued_jobs_wait_and_can_be_cancelled() {
+        let mut jobs = JobManager::default();
+        let (release_tx, release_rx) = crossbeam_channel::unbounded::<()>();
+        let running: Vec<u64> = (0..MAX_RUNNING_JOBS)
+            .map(|_| {
+                let release_rx = release_rx.clone();
+                jobs.spawn("Trim", move |context| loop {
+                    match release_rx.recv_timeout(Duration::from_millis(5)) {
+                        Err(RecvTimeoutError::Timeout) if context.is_cancelled() => {
+                            return Err("Cancelled".to_string());
+                        }
+                        Err(RecvTimeoutError::Timeout) => {}
+                        _ => return Ok("Saved".to_string()),
+                    }
+                })
+            })
+            .collect();
+        let queued = jobs.spawn("Convert", |_| Ok("Saved".to_string()));
+        assert_eq!(jobs.job(queued).unwrap().state, JobState::Queued);
+
+        jobs.cancel(queued);
+        jobs.cancel(running[0]);
+        assert!(jobs.poll().contains(&queued));
+        assert_eq!(jobs.job(queued).unwrap().state, JobState::Cancelled);
+
+        drop(release_tx);
+        poll_until_idle(&mut jobs);
+        assert_eq!(jobs.job(running[0]).unwrap().state, JobState::Cancelled);
+        assert_eq!(
+            jobs.job(running[1]).unwrap().state,
+            JobState::Done("Saved".to_string())
+        );
+    }
+}
@@ -0,0 +1,277 @@
+//! Runtime logging: compact console output plus a size-rotated log file.
+//!
+//! The log file lives in a `logs` folder in [`storage::data_dir`] so users can attach it to
+//! bug reports: the local app data folder or `cache_dir`, and the `cache` folder next to the
+//! executable in portable mode. When that folder is not writable the `logs` folder in
+//! [`storage::temp_dir`] is used instead.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+
+use parking_lot::Mutex;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+use crate::config::LogLevel;
+use crate::storage;
+
+const LOG_DIR_NAME: &str = "logs";
+const LOG_FILE_STEM: &str = "rust-image-viewer";
+
+static ACTIVE_LOG_FILE: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug)]
+pub struct LoggingOptions {
+    pub level: LogLevel,
+    /// `--verbose` on the command line: forces debug output regardless of config.
+    pub verbose: bool,
+    pub log_to_file: bool,
+    pub max_file_size_bytes: u64,
+    pub max_files: usize,
+}
+
+/// Install the global tracing subscriber. Only the first call has any effect.
+///
+/// Filter precedence: `RIV_LOG` / `RUST_LOG` env vars, then `--verbose`, then `log_level`.
+pub fn init(options: LoggingOptions) {
+    let mut log_file_path = None;
+
+    ACTIVE_LOG_FILE.get_or_init(|| {
+        let directive = filter_directive(&options);
+        let env_filter = EnvFilter::try_new(&directive).unwrap_or_else(|_| EnvFilter::new("warn"));
+
+        let file = if options.log_to_file && options.level != LogLevel::Off {
+            open_rotating_log_file(options.max_file_size_bytes, options.max_files)
+        } else {
+            None
+        };
+        let active_path = file.as_ref().map(|file| file.path.clone());
+
+        let console_layer = tracing_subscriber::fmt::layer()
+            .with_target(false)
+            .compact()
+            .with_writer(io::stderr);
+        let file_layer = file.map(|file| {
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_thread_names(true)
+                .with_writer(RotatingFileMakeWriter(file))
+        });
+
+        let _ = tracing_subscriber::registry()
+            .with(env_filter)
+            .with(console_layer)
+            .with(file_layer)
+            .try_init();
+
+        log_file_path = active_path.clone();
+        active_path
+    });
+
+    if let Some(path) = log_file_path {
+        tracing::info!(
+            target: "logging",
+            path = %path.display(),
+            version = env!("CARGO_PKG_VERSION"),
+            "logging to file"
+        );
+    }
+}
+
+/// Path of the log file currently being written, if file logging is active.
+pub fn active_log_file_path() -> Option<PathBuf> {
+    ACTIVE_LOG_FILE.get().cloned().flatten()
+}
+
+/// Active and rotated log files from every candidate log folder, newest first.
+pub fn existing_log_files() -> Vec<PathBuf> {
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = candidate_log_dirs()
+        .into_iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with(LOG_FILE_STEM) && name.ends_with(".log")
+        })
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .collect();
+    files.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    files.into_iter().map(|(_, path)| path).collect()
+}
+
+fn filter_directive(options: &LoggingOptions) -> String {
+    if let Ok(from_env) = std::env::var("RIV_LOG").or_else(|_| std::env::var("RUST_LOG")) {
+        return from_env;
+    }
+
+    if options.verbose {
+        return LogLevel::Debug.as_str().to_string();
+    }
+
+    options.level.as_str().to_string()
+}
+
+fn candidate_log_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::with_capacity(2);
+
+    if let Some(data_dir) = storage::data_dir() {
+        dirs.push(data_dir.join(LOG_DIR_NAME));
+    }
+    dirs.push(storage::temp_dir().join(LOG_DIR_NAME));
+
+    dirs
+}
+
+fn open_rotating_log_file(max_file_size_bytes: u64, max_files: usize) -> Option<Arc<RotatingFile>> {
+    candidate_log_dirs().into_iter().find_map(|dir| {
+        std::fs::create_dir_all(&dir).ok()?;
+        RotatingFile::open(
+            dir.join(format!("{LOG_FILE_STEM}.log")),
+            max_file_size_bytes,
+            max_files,
+        )
+        .ok()
+        .map(Arc::new)
+    })
+}
+
+struct RotatingFileState {
+    file: Option<File>,
+    len: u64,
+}
+
+/// Append-only log file that is renamed to `<stem>.1.log` (shifting older ones up)
+/// once it grows past `max_bytes`.
+struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    state: Mutex<RotatingFileState>,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf, max_bytes: u64, max_files: usize) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let len = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+
+        Ok(Self {
+            path,
+            max_bytes: max_bytes.max(1),
+            max_files: max_files.max(1),
+            state: Mutex::new(RotatingFileState {
+                file: Some(file),
+                len,
+            }),
+        })
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        self.path
+            .with_file_name(format!("{LOG_FILE_STEM}.{index}.log"))
+    }
+
+    fn rotate(&self, state: &mut RotatingFileState) {
+        // Close the active handle first; Windows refuses to rename open files.
+        state.file = None;
+
+        for index in (1..self.max_files).rev() {
+            let from = self.rotated_path(index);
+            if from.exists() {
+                let _ = std::fs::rename(&from, self.rotated_path(index + 1));
+            }
+        }
+        let _ = std::fs::rename(&self.path, self.rotated_path(1));
+
+        state.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)
+            .ok();
+        state.len = 0;
+    }
+
+    fn write_record(&self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.state.lock();
+
+        if state.len > 0 && state.len.saturating_add(buf.len() as u64) > self.max_bytes {
+            self.rotate(&mut state);
+        }
+
+        let Some(file) = state.file.as_mut() else {
+            // Logging must never take the app down; drop records until rotation recovers.
+            return Ok(buf.len());
+        };
+
+        file.write_all(buf)?;
+        state.len = state.len.saturating_add(buf.len() as u64);
+        Ok(buf.len())
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        match self.state.lock().file.as_mut() {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+struct RotatingFileMakeWriter(Arc<RotatingFile>);
+
+struct RotatingFileWriter<'a>(&'a RotatingFile);
+
+impl Write for RotatingFileWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write_record(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl<'a> MakeWriter<'a> for RotatingFileMakeWriter {
+    type Writer = RotatingFileWriter<'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RotatingFileWriter(self.0.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotating_file_shifts_old_logs_and_caps_count() {
+        let dir = std::env::temp_dir().join(format!(
+            "riv-logging-test-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or(0)
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let file = RotatingFile::open(dir.join(format!("{LOG_FILE_STEM}.log")), 16, 2).unwrap();
+        for record in ["first record\n", "second record\n", "third record\n"] {
+            file.write_record(record.as_bytes()).unwrap();
+        }
+        file.flush().unwrap();
+
+        let read = |path: PathBuf| std::fs::read_to_string(path).unwrap_or_default();
+        assert_eq!(read(file.path.clone()), "third record\n");
+        assert_eq!(read(file.rotated_path(1)), "second record\n");
+        assert_eq!(read(file.rotated_path(2)), "first record\n");
+        assert!(!file.rotated_path(3).exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
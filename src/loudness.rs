@@ -0,0 +1,189 @@
+//! Loudness normalization for video audio.
+//!
+//! The first [`ANALYZED_SECONDS`] of a file's audio run through GStreamer's ReplayGain
+//! analyzer on a worker, faster than real time, while the video already plays. The track
+//! gain it reports is relative to the ReplayGain reference, which sits at about -18 LUFS;
+//! the viewer turns it into a volume factor for `loudness_target_lufs` that never lifts the
+//! measured peak past full scale. Results are kept per path for the session, so going back
+//! to a clip applies its gain right away.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use parking_lot::Mutex;
+
+/// Audio analyzed from the start of each file.
+const ANALYZED_SECONDS: u64 = 30;
+/// Analysis is abandoned after this long, for example on a stalled network share.
+const ANALYSIS_TIMEOUT: Duration = Duration::from_secs(20);
+/// Loudness of the ReplayGain reference level, in LUFS.
+const REPLAYGAIN_REFERENCE_LUFS: f64 = -18.0;
+/// Upper bound of the volume factor, the limit of GStreamer's `volume` element.
+const MAX_GAIN: f64 = 10.0;
+/// Paths remembered before the cache starts over.
+const MAX_CACHED: usize = 512;
+
+/// Measured loudness of one file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Loudness {
+    /// ReplayGain track gain, in dB.
+    pub track_gain_db: f64,
+    /// Sample peak, 1.0 being full scale.
+    pub peak: f64,
+}
+
+impl Loudness {
+    /// Volume factor that brings this file to `target_lufs` without clipping its peak.
+    pub fn gain_for_target(&self, target_lufs: f32) -> f64 {
+        let db = self.track_gain_db + f64::from(target_lufs) - REPLAYGAIN_REFERENCE_LUFS;
+        let mut gain = 10f64.powf(db / 20.0);
+        if self.peak > 0.0 {
+            gain = gain.min(1.0 / self.peak);
+        }
+        gain.clamp(0.0, MAX_GAIN)
+    }
+}
+
+enum Entry {
+    Analyzing,
+    /// `None` when the file has no audio or could not be analyzed.
+    Done(Option<Loudness>),
+}
+
+fn cache() -> &'static Mutex<HashMap<PathBuf, Entry>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Entry>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// The measured loudness of `path`: `Some` once known, `None` while it is being analyzed.
+/// The first call starts the analysis, and `wake` runs when it finishes.
+pub fn lookup_or_analyze(
+    path: &Path,
+    wake: impl Fn() + Send + 'static,
+) -> Option<Option<Loudness>> {
+    {
+        let mut cache = cache().lock();
+        match cache.get(path) {
+            Some(Entry::Done(loudness)) => return Some(*loudness),
+            Some(Entry::Analyzing) => return None,
+            None => {}
+        }
+        if cache.len() >= MAX_CACHED {
+            cache.retain(|_, entry| matches!(entry, Entry::Analyzing));
+        }
+        cache.insert(path.to_path_buf(), Entry::Analyzing);
+    }
+
+    let path = path.to_path_buf();
+    crate::async_runtime::spawn_blocking_or_thread("loudness-analysis", move || {
+        let loudness = analyze(&path);
+        tracing::debug!("Loudness of {}: {loudness:?}", path.display());
+        cache().lock().insert(path, Entry::Done(loudness));
+        wake();
+    });
+    None
+}
+
+/// Runs the start of `path`'s audio through `rganalysis`.
+fn analyze(path: &Path) -> Option<Loudness> {
+    static GST_INIT: OnceLock<Result<(), ()>> = OnceLock::new();
+    if GST_INIT
+        .get_or_init(|| gst::init().map_err(|_| ()))
+        .is_err()
+    {
+        return None;
+    }
+
+    let uri = gst::glib::filename_to_uri(path, None).ok()?;
+    let playbin = gst::ElementFactory::make("playbin")
+        .property("uri", uri.as_str())
+        .build()
+        .ok()?;
+    // Decode audio only.
+    playbin.set_property_from_str("flags", "audio");
+    let audio_sink = gst::parse::bin_from_description(
+        "audioconvert ! audioresample ! rganalysis ! fakesink sync=false",
+        true,
+    )
+    .ok()?;
+    playbin.set_property("audio-sink", &audio_sink);
+    let pipeline = playbin.downcast::<gst::Pipeline>().ok()?;
+
+    if pipeline.set_state(gst::State::Playing).is_err() {
+        let _ = pipeline.set_state(gst::State::Null);
+        return None;
+    }
+
+    let bus = pipeline.bus()?;
+    let deadline = Instant::now() + ANALYSIS_TIMEOUT;
+    let mut track_gain_db = None;
+    let mut peak = 0.0;
+    let mut stop_sent = false;
+    while Instant::now() < deadline {
+        if !stop_sent {
+            let analyzed = pipeline.query_position::<gst::ClockTime>();
+            if analyzed.is_some_and(|position| position.seconds() >= ANALYZED_SECONDS) {
+                // The analyzer reports when it sees the end of the stream.
+                pipeline.send_event(gst::event::Eos::new());
+                stop_sent = true;
+            }
+        }
+        let Some(msg) = bus.timed_pop(gst::ClockTime::from_mseconds(50)) else {
+            continue;
+        };
+        match msg.view() {
+            gst::MessageView::Tag(tag) => {
+                let tags = tag.tags();
+                if let Some(gain) = tags.get::<gst::tags::TrackGain>() {
+                    track_gain_db = Some(gain.get());
+                }
+                if let Some(value) = tags.get::<gst::tags::TrackPeak>() {
+                    peak = value.get();
+                }
+            }
+            gst::MessageView::Eos(_) | gst::MessageView::Error(_) => break,
+            _ => {}
+        }
+    }
+
+    let _ = pipeline.set_state(gst::State::Null);
+    Some(Loudness {
+        track_gain_db: track_gain_db?,
+        peak,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Loudness;
+
+    #[test]
+    fn gain_reaches_the_target_without_clipping() {
+        // A clip at the reference level needs no change for a -18 LUFS target.
+        let reference = Loudness {
+            track_gain_db: 0.0,
+            peak: 0.5,
+        };
+        assert!((reference.gain_for_target(-18.0) - 1.0).abs() < 1e-9);
+        // 6 dB quieter target halves the volume.
+        assert!((reference.gain_for_target(-24.0) - 0.501).abs() < 1e-3);
+
+        // A quiet clip is lifted only until its peak reaches full scale.
+        let quiet = Loudness {
+            track_gain_db: 20.0,
+            peak: 0.25,
+        };
+        assert_eq!(quiet.gain_for_target(-18.0), 4.0);
+
+        // Without a peak the lift is capped by the volume element's range.
+        let silent = Loudness {
+            track_gain_db: 60.0,
+            peak: 0.0,
+        };
+        assert_eq!(silent.gain_for_target(-18.0), 10.0);
+    }
+}
@@ -0,0 +1,216 @@
+//! 3D color lookup tables in the `.cube` format (Adobe/Resolve), for previewing a grade or a
+//! log-to-display conversion on images and videos.
+//!
+//! The view applies a LUT in the adjustments shader. It is uploaded as a 2D strip of `size`
+//! tiles, one per blue slice, so it also works where 3D textures are missing (GLES 2).
+//! [`CubeLut::apply_to_rgba`] is the CPU twin used for PNG export.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Largest `LUT_3D_SIZE` accepted; the GPU strip is `size²` texels wide.
+pub const MAX_SIZE: usize = 128;
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// A parsed 3D LUT. Entries are stored red-fastest, as in the file.
+#[derive(Debug, Clone)]
+pub struct CubeLut {
+    /// Distinguishes loaded LUTs, so the renderer uploads each one once.
+    pub id: u64,
+    pub path: PathBuf,
+    /// `TITLE` from the file, or its file name.
+    pub title: String,
+    pub size: usize,
+    pub domain_min: [f32; 3],
+    pub domain_max: [f32; 3],
+    pub table: Vec<[f32; 3]>,
+}
+
+fn parse_triplet(fields: &[&str]) -> Option<[f32; 3]> {
+    match fields {
+        [r, g, b] => Some([r.parse().ok()?, g.parse().ok()?, b.parse().ok()?]),
+        _ => None,
+    }
+}
+
+impl CubeLut {
+    pub fn parse(text: &str, path: &Path) -> Result<Self, String> {
+        let mut title = None;
+        let mut size = None;
+        let mut domain_min = [0.0; 3];
+        let mut domain_max = [1.0; 3];
+        let mut table = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let bad_line = || format!("Line {}: cannot read `{line}`", index + 1);
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields[0] {
+                "TITLE" => {
+                    title = Some(line["TITLE".len()..].trim().trim_matches('"').to_string());
+                }
+                "LUT_3D_SIZE" => {
+                    let value = fields
+                        .get(1)
+                        .and_then(|value| value.parse::<usize>().ok())
+                        .ok_or_else(bad_line)?;
+                    if !(2..=MAX_SIZE).contains(&value) {
+                        return Err(format!("LUT_3D_SIZE {value} is outside 2-{MAX_SIZE}"));
+                    }
+                    size = Some(value);
+                    table.reserve(value * value * value);
+                }
+                "LUT_1D_SIZE" => return Err("1D LUTs are not supported".to_string()),
+                "DOMAIN_MIN" => domain_min = parse_triplet(&fields[1..]).ok_or_else(bad_line)?,
+                "DOMAIN_MAX" => domain_max = parse_triplet(&fields[1..]).ok_or_else(bad_line)?,
+                // Other keywords (LUT_3D_INPUT_RANGE and vendor extensions) do not change the
+                // table.
+                keyword if keyword.starts_with(|c: char| c.is_ascii_alphabetic()) => {}
+                _ => table.push(parse_triplet(&fields).ok_or_else(bad_line)?),
+            }
+        }
+
+        let size = size.ok_or_else(|| "Missing LUT_3D_SIZE".to_string())?;
+        if table.len() != size * size * size {
+            return Err(format!(
+                "Expected {} entries for LUT_3D_SIZE {size}, found {}",
+                size * size * size,
+                table.len()
+            ));
+        }
+        if (0..3).any(|channel| domain_max[channel] <= domain_min[channel]) {
+            return Err("DOMAIN_MAX must be above DOMAIN_MIN".to_string());
+        }
+        let title = title.filter(|title| !title.is_empty()).unwrap_or_else(|| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        });
+        Ok(Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            path: path.to_path_buf(),
+            title,
+            size,
+            domain_min,
+            domain_max,
+            table,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+        Self::parse(&text, path)
+    }
+
+    fn entry(&self, r: usize, g: usize, b: usize) -> [f32; 3] {
+        self.table[(b * self.size + g) * self.size + r]
+    }
+
+    /// Looks up `rgb` (gamma-encoded, `0..=1`) with trilinear interpolation. Mirrors
+    /// `apply_lut()` in the adjustments shader.
+    pub fn sample(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let last = (self.size - 1) as f32;
+        let coords: [f32; 3] = std::array::from_fn(|channel| {
+            let range = self.domain_max[channel] - self.domain_min[channel];
+            ((rgb[channel] - self.domain_min[channel]) / range).clamp(0.0, 1.0) * last
+        });
+        let lower = coords.map(|coord| coord.floor() as usize);
+        let upper = lower.map(|index| (index + 1).min(self.size - 1));
+        let [fr, fg, fb] = std::array::from_fn(|channel| coords[channel] - lower[channel] as f32);
+
+        let lerp = |a: [f32; 3], b: [f32; 3], t: f32| -> [f32; 3] {
+            std::array::from_fn(|channel| a[channel] + (b[channel] - a[channel]) * t)
+        };
+        let plane = |b: usize| {
+            let bottom = lerp(
+                self.entry(lower[0], lower[1], b),
+                self.entry(upper[0], lower[1], b),
+                fr,
+            );
+            let top = lerp(
+                self.entry(lower[0], upper[1], b),
+                self.entry(upper[0], upper[1], b),
+                fr,
+            );
+            lerp(bottom, top, fg)
+        };
+        lerp(plane(lower[2]), plane(upper[2]), fb).map(|value| value.clamp(0.0, 1.0))
+    }
+
+    /// Applies the LUT to straight (non-premultiplied) RGBA8 pixels in place.
+    pub fn apply_to_rgba(&self, pixels: &mut [u8]) {
+        for pixel in pixels.chunks_exact_mut(4) {
+            let rgb = [pixel[0], pixel[1], pixel[2]].map(|value| value as f32 / 255.0);
+            for (channel, value) in pixel.iter_mut().zip(self.sample(rgb)) {
+                *channel = (value * 255.0).round() as u8;
+            }
+        }
+    }
+
+    /// RGBA8 texels for the GPU: `size` rows of `size²` texels, where row `g` holds the
+    /// blue slices side by side and red runs along each slice.
+    pub fn strip_rgba(&self) -> Vec<u8> {
+        let size = self.size;
+        let mut texels = Vec::with_capacity(size * size * size * 4);
+        for g in 0..size {
+            for b in 0..size {
+                for r in 0..size {
+                    let [red, green, blue] = self.entry(r, g, b);
+                    texels.extend(
+                        [red, green, blue]
+                            .map(|value| (value.clamp(0.0, 1.0) * 255.0).round() as u8),
+                    );
+                    texels.push(u8::MAX);
+                }
+            }
+        }
+        texels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CubeLut;
+    use std::path::Path;
+
+    /// A 2×2×2 LUT that inverts every channel.
+    const INVERT: &str = "# comment\nTITLE \"Invert\"\nLUT_3D_SIZE 2\n\
+        1 1 1\n0 1 1\n1 0 1\n0 0 1\n1 1 0\n0 1 0\n1 0 0\n0 0 0\n";
+
+    #[test]
+    fn parses_and_samples_a_cube_file() {
+        let lut = CubeLut::parse(INVERT, Path::new("invert.cube")).unwrap();
+        assert_eq!((lut.title.as_str(), lut.size), ("Invert", 2));
+        assert_eq!(lut.sample([0.0, 0.0, 0.0]), [1.0, 1.0, 1.0]);
+        let mid = lut.sample([0.25, 0.5, 1.0]);
+        assert!((mid[0] - 0.75).abs() < 1e-6 && (mid[1] - 0.5).abs() < 1e-6 && mid[2] == 0.0);
+
+        let mut pixels = vec![255, 0, 51, 128];
+        lut.apply_to_rgba(&mut pixels);
+        assert_eq!(pixels, [0, 255, 204, 128]);
+
+        // Row g = 0: blue slice 0 then blue slice 1, red fastest.
+        let strip = lut.strip_rgba();
+        assert_eq!(strip.len(), 2 * 4 * 4);
+        assert_eq!(&strip[..8], &[255, 255, 255, 255, 0, 255, 255, 255]);
+        assert_eq!(&strip[8..12], &[255, 255, 0, 255]);
+    }
+
+    #[test]
+    fn rejects_incomplete_or_unsupported_files() {
+        let path = Path::new("bad.cube");
+        assert!(CubeLut::parse("LUT_3D_SIZE 2\n0 0 0\n", path)
+            .unwrap_err()
+            .contains("Expected 8"));
+        assert!(CubeLut::parse("LUT_1D_SIZE 4\n", path).is_err());
+        assert!(CubeLut::parse("0 0 0\n", path).is_err());
+        assert!(CubeLut::parse("LUT_3D_SIZE 2\n0 0 x\n", path)
+            .unwrap_err()
+            .starts_with("Line 2"));
+    }
+}
@@ -0,0 +1,130 @@
+//! Magnifier: a round loupe that follows the pointer while its key is held.
+//!
+//! The loupe is sampled straight from the decoded frame rather than scaled up from the
+//! texture on screen, so it stays sharp when the main view shows the image downscaled. Each
+//! screen pixel of the loupe is mapped back through the current zoom, rotation and flips,
+//! which keeps the loupe lined up with the view.
+
+use crate::annotations::AnnotationView;
+use crate::image_loader::ImageFrame;
+
+/// Range of `magnifier_zoom`, relative to the current view.
+pub const MIN_FACTOR: f32 = 2.0;
+pub const MAX_FACTOR: f32 = 8.0;
+/// Diameter of the loupe in points.
+pub const LOUPE_DIAMETER: f32 = 220.0;
+
+/// The loupe image: `side` × `side` physical pixels showing the area around `pointer`
+/// enlarged `factor` times. Pixels outside the circle or the image are transparent.
+pub fn render(
+    frame: &ImageFrame,
+    view: AnnotationView,
+    pointer: egui::Pos2,
+    factor: f32,
+    pixels_per_point: f32,
+) -> egui::ColorImage {
+    let side = (LOUPE_DIAMETER * pixels_per_point).round().max(1.0) as usize;
+    let mut image = egui::ColorImage::new([side, side], egui::Color32::TRANSPARENT);
+    if frame.width == 0 || frame.height == 0 || view.image_size.min_elem() <= 0.0 {
+        return image;
+    }
+
+    // Screen-to-image mapping is affine, so walk it with per-pixel steps instead of
+    // transforming every pixel.
+    let points_per_pixel = 1.0 / (pixels_per_point * factor.max(1.0));
+    let half = side as f32 * 0.5;
+    let screen_at = |x: f32, y: f32| pointer + egui::vec2(x - half, y - half) * points_per_pixel;
+    let origin = view.to_image_unclamped(screen_at(0.5, 0.5));
+    let step_x = view.to_image_unclamped(screen_at(1.5, 0.5)) - origin;
+    let step_y = view.to_image_unclamped(screen_at(0.5, 1.5)) - origin;
+    let scale = egui::vec2(
+        frame.width as f32 / view.image_size.x,
+        frame.height as f32 / view.image_size.y,
+    );
+
+    let radius_sq = half * half;
+    for y in 0..side {
+        let dy = y as f32 + 0.5 - half;
+        let row = origin + step_y * y as f32;
+        for x in 0..side {
+            let dx = x as f32 + 0.5 - half;
+            if dx * dx + dy * dy > radius_sq {
+                continue;
+            }
+            let p = row + step_x * x as f32;
+            let fx = (p.x * scale.x).floor();
+            let fy = (p.y * scale.y).floor();
+            if fx < 0.0 || fy < 0.0 || fx >= frame.width as f32 || fy >= frame.height as f32 {
+                continue;
+            }
+            let offset = (fy as usize * frame.width as usize + fx as usize) * 4;
+            if let Some(pixel) = frame.pixels.get(offset..offset + 4) {
+                image.pixels[y * side + x] =
+                    egui::Color32::from_rgba_unmultiplied(pixel[0], pixel[1], pixel[2], pixel[3]);
+            }
+        }
+    }
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render;
+    use crate::annotations::AnnotationView;
+    use crate::image_loader::ImageFrame;
+
+    #[test]
+    fn samples_the_stored_frame_around_the_pointer() {
+        // 4×4 frame, left half black and right half white, shown at 10% zoom so the whole
+        // image is 0.4 points wide on screen.
+        let mut pixels = Vec::new();
+        for _ in 0..4 {
+            pixels.extend([
+                0, 0, 0, 255, 0, 0, 0, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+            ]);
+        }
+        let frame = ImageFrame {
+            pixels,
+            width: 4,
+            height: 4,
+            delay_ms: 0,
+        };
+        let view = AnnotationView {
+            center: egui::pos2(100.0, 100.0),
+            zoom: 0.1,
+            image_size: egui::vec2(4.0, 4.0),
+            angle_radians: 0.0,
+            flip_horizontal: false,
+            flip_vertical: false,
+        };
+        let image = render(&frame, view, egui::pos2(100.0, 100.0), 500.0, 1.0);
+        let side = image.size[0];
+        let middle = side / 2;
+        assert_eq!(
+            image.pixels[middle * side + middle - 20],
+            egui::Color32::BLACK
+        );
+        assert_eq!(
+            image.pixels[middle * side + middle + 20],
+            egui::Color32::WHITE
+        );
+        // Corners are outside the circle; far edges are outside the image.
+        assert_eq!(image.pixels[0], egui::Color32::TRANSPARENT);
+        assert_eq!(image.pixels[middle * side + 1], egui::Color32::TRANSPARENT);
+
+        let mirrored = render(
+            &frame,
+            AnnotationView {
+                flip_horizontal: true,
+                ..view
+            },
+            egui::pos2(100.0, 100.0),
+            500.0,
+            1.0,
+        );
+        assert_eq!(
+            mirrored.pixels[middle * side + middle - 20],
+            egui::Color32::WHITE
+        );
+    }
+}
@@ -3,21 +3,81 @@
 
 #![windows_subsystem = "windows"]
 
+mod accessibility;
+mod animation_export;
+mod annotations;
 mod app_dirs;
 mod async_runtime;
+mod batch_rename;
+mod command_palette;
+mod compare;
 mod config;
+mod crash_report;
+mod decode_bench;
+mod diagnostics;
+mod emoji;
+mod exif;
+mod eyedropper;
 mod folder_travel_cache;
+mod font_fallback;
+mod gpu_recovery;
+mod guides;
+mod headless;
+mod i18n;
+mod image_adjustments;
 mod image_loader;
 mod image_resize;
+mod jobs;
+mod logging;
+mod loudness;
+mod lut;
+mod magnifier;
 mod manga_loader;
 mod manga_spatial;
+mod media_controls;
 mod media_index;
 mod metadata_cache;
+mod monitor_wall;
+mod monitors;
+mod mouse_gestures;
+mod osd;
+mod panel_detection;
+mod path_access;
+mod pdf_export;
+mod pdf_writer;
 mod perf_metrics;
+mod perspective;
+mod playlist;
+mod plugins;
+mod png_export;
+mod quick_filter;
+mod ratings;
+mod remote;
+mod remote_control;
+mod scan_cleanup;
+mod seek_thumbnails;
+mod settings_window;
 #[cfg(target_os = "windows")]
 mod single_instance;
+mod stereo_export;
+mod storage;
+mod straighten;
+mod taskbar;
+mod theme;
+mod touch_input;
+mod transition;
+mod tray;
+#[cfg(feature = "turbojpeg")]
+mod turbo_jpeg;
+mod usage_stats;
+mod video_guides;
 mod video_player;
+mod video_resume;
 mod video_thumbnail;
+mod video_trim;
+mod viewer_window;
+mod wallpaper;
+mod window_snap;
 #[cfg(target_os = "windows")]
 mod windows_env;
 
@@ -25,17 +85,22 @@ mod windows_env;
 #[global_allocator]
 static GLOBAL_ALLOCATOR: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
+use annotations::{AnnotationEditor, AnnotationTool, AnnotationView};
 use config::{
-    Action, Config, InputBinding, MangaVirtualizationBackend, ShortcutModifier, StartupWindowMode,
-    VideoSeekPolicy, WindowTitlePathMode,
+    Action, BindingContext, ClickZone, ClickZoneButton, Config, FitMode, InputBinding,
+    MangaVirtualizationBackend, OverlayThemeMode, PdfPageSize, PlaylistRepeat, RatingFilter,
+    ResumePolicy, ShortcutModifier, SortOperation, StartupWindowMode, TrimMode, VideoSeekPolicy,
+    WindowTitlePathMode, ZoomPreset,
 };
 use folder_travel_cache::{
-    lookup_folder_travel_position, store_folder_travel_position, FolderTravelLayoutMode,
-    FolderTravelPosition,
+    lookup_folder_travel_position, lookup_manga_page_overrides, lookup_manga_page_width_zoom,
+    store_folder_travel_position, store_manga_page_overrides, store_manga_page_width_zoom,
+    FolderTravelLayoutMode, FolderTravelPosition, MangaPageFit, MangaPageOverride,
 };
 use hashbrown::{HashMap, HashSet};
+use image_adjustments::{AdjustmentRenderer, ImageAdjustments};
 use image_loader::{
-    get_media_in_directory, get_media_type, is_supported_video, probe_image_dimensions,
+    get_media_in_directory_bounded, get_media_type, is_supported_video, probe_image_dimensions,
     resolve_folder_shortcut_target, ImageFrame, LoadedImage, MediaType, FOLDER_UP_ENTRY_NAME,
 };
 use image_resize::downscale_rgba_if_needed;
@@ -50,9 +115,15 @@ use metadata_cache::{
     store_cached_dimensions, store_cached_static_thumbnail, store_cached_video_thumbnail,
     CachedImageThumbnail, CachedMediaKind, CachedVideoThumbnail,
 };
+use monitors::SavedPlacement;
+use mouse_gestures::GestureTracker;
 use perf_metrics::PerfMetrics;
+use seek_thumbnails::SeekThumbnails;
+use settings_window::SettingsWindow;
 #[cfg(target_os = "windows")]
 use single_instance::{FileReceiver, SingleInstanceResult};
+use touch_input::{SwipeDirection, TouchFrame, TouchTracker};
+use transition::{TransitionEasing, TransitionEffect, TransitionFrame};
 use video_player::{
     detect_video_acceleration_capabilities, format_duration, gstreamer_runtime_available,
     VideoPlayer, VideoSeekMode, VideoSubtitleSelection, VideoTrackInfo,
@@ -61,6 +132,7 @@ use video_thumbnail::{
     extract_video_first_frame_without_gstreamer, probe_video_dimensions_with_gstreamer,
     probe_video_dimensions_without_gstreamer,
 };
+use window_snap::{SizePreset, SnapDirection, SnapRegion};
 
 use bytes::Bytes;
 use eframe::egui;
@@ -89,7 +161,6 @@ use eframe::glow::HasContext;
 
 const GSTREAMER_MISSING_VIDEO_ERROR_TEXT: &str =
     "Cannot open video files because the GStreamer library is not installed. Please install GStreamer to enable video playback.";
-const TITLEBAR_CONTROL_ICON_COLOR: egui::Color32 = egui::Color32::WHITE;
 
 /// Paint a smooth, semi-transparent loading spinner in the bottom-right corner
 /// of the given rectangle.  The spinner is a rotating arc that indicates
@@ -162,6 +233,15 @@ fn rotate_quad_point(center: egui::Pos2, local: egui::Vec2, angle_radians: f32)
     center + egui::vec2(local.x * cos - local.y * sin, local.x * sin + local.y * cos)
 }
 
+/// "1 file" or "3 files", in the UI language.
+fn file_count_label(count: usize) -> String {
+    if count == 1 {
+        i18n::tr("files.one").to_string()
+    } else {
+        i18n::tr_args("files.many", &[("count", &count)])
+    }
+}
+
 fn rotated_bounding_size(size: egui::Vec2, angle_radians: f32) -> egui::Vec2 {
     let sin = angle_radians.sin().abs();
     let cos = angle_radians.cos().abs();
@@ -169,16 +249,34 @@ fn rotated_bounding_size(size: egui::Vec2, angle_radians: f32) -> egui::Vec2 {
     egui::vec2(size.x * cos + size.y * sin, size.x * sin + size.y * cos)
 }
 
-fn paint_rotated_texture(
-    painter: &egui::Painter,
-    texture_id: egui::TextureId,
+/// Pan offset limited so that at least `min_visible` of an image of `display_size`, centered
+/// in a `view` at `offset`, stays on screen along each axis. The visible share is capped at the
+/// view, so 1.0 keeps a larger image covering the view and a smaller one fully inside it.
+fn clamped_pan_offset(
+    offset: egui::Vec2,
+    display_size: egui::Vec2,
+    view: egui::Vec2,
+    min_visible: f32,
+) -> egui::Vec2 {
+    let clamp_axis = |offset: f32, image: f32, view: f32| {
+        let visible = (image * min_visible).min(image).min(view);
+        let max_offset = ((image + view) / 2.0 - visible).max(0.0);
+        offset.clamp(-max_offset, max_offset)
+    };
+    egui::vec2(
+        clamp_axis(offset.x, display_size.x, view.x),
+        clamp_axis(offset.y, display_size.y, view.y),
+    )
+}
+
+/// Corners (clockwise from top-left) and matching UVs of a rotated, optionally flipped quad.
+fn rotated_texture_quad(
     center: egui::Pos2,
     size: egui::Vec2,
     angle_radians: f32,
     flip_horizontal: bool,
     flip_vertical: bool,
-    tint: egui::Color32,
-) {
+) -> ([egui::Pos2; 4], [egui::Pos2; 4]) {
     let half = size * 0.5;
     let local_corners = [
         egui::vec2(-half.x, -half.y),
@@ -197,11 +295,30 @@ fn paint_rotated_texture(
         egui::pos2(u_min, v_max),
     ];
 
+    (
+        local_corners.map(|local| rotate_quad_point(center, local, angle_radians)),
+        uvs,
+    )
+}
+
+fn paint_rotated_texture(
+    painter: &egui::Painter,
+    texture_id: egui::TextureId,
+    center: egui::Pos2,
+    size: egui::Vec2,
+    angle_radians: f32,
+    flip_horizontal: bool,
+    flip_vertical: bool,
+    tint: egui::Color32,
+) {
+    let (corners, uvs) =
+        rotated_texture_quad(center, size, angle_radians, flip_horizontal, flip_vertical);
+
     let mut mesh = egui::epaint::Mesh::with_texture(texture_id);
     let base = mesh.vertices.len() as u32;
-    for (local, uv) in local_corners.into_iter().zip(uvs) {
+    for (pos, uv) in corners.into_iter().zip(uvs) {
         mesh.vertices.push(egui::epaint::Vertex {
-            pos: rotate_quad_point(center, local, angle_radians),
+            pos,
             uv,
             color: tint,
         });
@@ -212,6 +329,36 @@ fn paint_rotated_texture(
     painter.add(egui::Shape::mesh(mesh));
 }
 
+/// Tiles the 2x2 repeat-wrapped checkerboard texture over a (possibly rotated) quad so each
+/// square is `cell_size` points, anchored at the quad's top-left corner.
+fn paint_checkerboard_quad(
+    painter: &egui::Painter,
+    texture_id: egui::TextureId,
+    corners: [egui::Pos2; 4],
+    size: egui::Vec2,
+    cell_size: f32,
+) {
+    let repeat = size / (2.0 * cell_size.max(1.0));
+    let uvs = [
+        egui::pos2(0.0, 0.0),
+        egui::pos2(repeat.x, 0.0),
+        egui::pos2(repeat.x, repeat.y),
+        egui::pos2(0.0, repeat.y),
+    ];
+
+    let mut mesh = egui::epaint::Mesh::with_texture(texture_id);
+    for (pos, uv) in corners.into_iter().zip(uvs) {
+        mesh.vertices.push(egui::epaint::Vertex {
+            pos,
+            uv,
+            color: egui::Color32::WHITE,
+        });
+    }
+    mesh.indices.extend_from_slice(&[0, 1, 2, 0, 2, 3]);
+
+    painter.add(egui::Shape::mesh(mesh));
+}
+
 fn try_color_image_from_opaque_rgba_bytes(
     size: [usize; 2],
     pixels: Bytes,
@@ -243,6 +390,21 @@ fn try_color_image_from_opaque_rgba_bytes(
     Ok(egui::ColorImage { size, pixels })
 }
 
+/// Uploads a video frame into `texture`, in place while the frame size is unchanged. A full
+/// `set` makes the renderer reallocate the GPU storage (`glTexImage2D`) on every frame; a
+/// partial update covering the whole texture only writes the pixels (`glTexSubImage2D`).
+fn upload_video_frame(
+    texture: &mut egui::TextureHandle,
+    frame: egui::ColorImage,
+    options: egui::TextureOptions,
+) {
+    if texture.size() == frame.size {
+        texture.set_partial([0, 0], frame, options);
+    } else {
+        texture.set(frame, options);
+    }
+}
+
 fn cached_or_probe_video_dimensions(path: &Path) -> Option<(u32, u32)> {
     if let Some(dims) = lookup_cached_dimensions(path, CachedMediaKind::Video) {
         return Some(dims);
@@ -297,76 +459,16 @@ fn video_output_dimensions_for_bounds(
     ))
 }
 
-#[cfg(target_os = "windows")]
-fn windows_cjk_font_candidates() -> [(&'static str, &'static str); 6] {
-    [
-        // Japanese
-        ("cjk_meiryo", r"C:\Windows\Fonts\meiryo.ttc"),
-        ("cjk_msgothic", r"C:\Windows\Fonts\msgothic.ttc"),
-        // Simplified Chinese
-        ("cjk_msyh", r"C:\Windows\Fonts\msyh.ttc"),
-        // Traditional Chinese
-        ("cjk_msjh", r"C:\Windows\Fonts\msjh.ttc"),
-        // Korean
-        ("cjk_malgun", r"C:\Windows\Fonts\malgun.ttf"),
-        // Broad fallback (varies by Windows install)
-        ("cjk_segoeui", r"C:\Windows\Fonts\segoeui.ttf"),
-    ]
-}
-
-#[cfg(target_os = "windows")]
-fn load_windows_cjk_font_data() -> Vec<(String, Vec<u8>)> {
-    let mut loaded = Vec::new();
-    for (name, path) in windows_cjk_font_candidates() {
-        if let Ok(bytes) = std::fs::read(path) {
-            loaded.push((name.to_owned(), bytes));
-        }
-    }
-    loaded
-}
-
-#[cfg(target_os = "windows")]
-fn apply_windows_cjk_fonts(ctx: &egui::Context, font_data: Vec<(String, Vec<u8>)>) -> bool {
-    // egui's default font set is Latin-focused; without adding a font that contains
-    // CJK glyphs, filenames will show as tofu boxes in our custom title bar.
-    let mut fonts = egui::FontDefinitions::default();
-
-    let mut loaded_any = false;
-    for (name, bytes) in font_data {
-        fonts
-            .font_data
-            .insert(name.clone(), egui::FontData::from_owned(bytes));
-
-        // Put CJK fonts first so they are preferred for matching glyphs.
-        if let Some(family) = fonts.families.get_mut(&egui::FontFamily::Proportional) {
-            if !family.iter().any(|f| f == &name) {
-                family.insert(0, name.clone());
-            }
-        }
-        if let Some(family) = fonts.families.get_mut(&egui::FontFamily::Monospace) {
-            if !family.iter().any(|f| f == &name) {
-                family.push(name.clone());
-            }
-        }
-
-        loaded_any = true;
-    }
-
-    if loaded_any {
-        ctx.set_fonts(fonts);
-    }
-
-    loaded_any
-}
-
-#[cfg(not(target_os = "windows"))]
-fn load_windows_cjk_font_data() -> Vec<(String, Vec<u8>)> {
-    Vec::new()
-}
-
-#[cfg(not(target_os = "windows"))]
-fn apply_windows_cjk_fonts(_ctx: &egui::Context, _font_data: Vec<(String, Vec<u8>)>) -> bool {
-    false
+/// Starts `command` without waiting for it. A helper thread waits for the process instead,
+/// so it does not linger as a zombie on Unix once it exits.
+fn spawn_detached(command: &mut std::process::Command) -> std::io::Result<()> {
+    let mut child = command.spawn()?;
+    let _ = std::thread::Builder::new()
+        .name("child-wait".to_string())
+        .spawn(move || {
+            let _ = child.wait();
+        });
+    Ok(())
 }
 
 fn open_path_in_default_app(path: &std::path::Path) -> std::io::Result<()> {
@@ -416,7 +518,7 @@ fn sh_open_folder_and_select_item(path: &Path) -> std::io::Result<()> {
         }
     }
 
-    let wide_path: Vec<u16> = path
+    let wide_path: Vec<u16> = path_access::extended_length(path)
         .as_os_str()
         .encode_wide()
         .chain(std::iter::once(0))
@@ -612,6 +714,8 @@ enum MenuActionIcon {
     OpenLocation,
     Config,
     Help,
+    Stats,
+    NewWindow,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -624,6 +728,7 @@ enum VideoControlIcon {
     Next,
     AudioTracks,
     SubtitleTracks,
+    FrameGuides,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -684,6 +789,76 @@ fn write_shell_file_list_to_clipboard(
     Err("Shell file clipboard operations are only implemented on Windows".to_string())
 }
 
+/// Places a `width` x `height` RGBA image on the clipboard as a device-independent bitmap.
+#[cfg(target_os = "windows")]
+fn write_image_to_clipboard(width: usize, height: usize, rgba: &[u8]) -> Result<(), String> {
+    use clipboard_win::{raw, Clipboard};
+
+    const FILE_HEADER_LEN: u32 = 14;
+    const INFO_HEADER_LEN: u32 = 40;
+    let pixels_len = (width * height * 4) as u32;
+    let mut bmp = Vec::with_capacity((FILE_HEADER_LEN + INFO_HEADER_LEN + pixels_len) as usize);
+    bmp.extend_from_slice(b"BM");
+    bmp.extend_from_slice(&(FILE_HEADER_LEN + INFO_HEADER_LEN + pixels_len).to_le_bytes());
+    bmp.extend_from_slice(&0u32.to_le_bytes());
+    bmp.extend_from_slice(&(FILE_HEADER_LEN + INFO_HEADER_LEN).to_le_bytes());
+    bmp.extend_from_slice(&INFO_HEADER_LEN.to_le_bytes());
+    bmp.extend_from_slice(&(width as i32).to_le_bytes());
+    bmp.extend_from_slice(&(height as i32).to_le_bytes());
+    bmp.extend_from_slice(&1u16.to_le_bytes());
+    bmp.extend_from_slice(&32u16.to_le_bytes());
+    // BI_RGB, the pixel data size, then resolution and palette left at zero.
+    bmp.extend_from_slice(&0u32.to_le_bytes());
+    bmp.extend_from_slice(&pixels_len.to_le_bytes());
+    bmp.extend_from_slice(&[0; 16]);
+    // Rows are stored bottom-up, in BGRA order.
+    for row in rgba.chunks_exact(width * 4).rev() {
+        for pixel in row.chunks_exact(4) {
+            bmp.extend_from_slice(&[pixel[2], pixel[1], pixel[0], pixel[3]]);
+        }
+    }
+
+    let _clipboard =
+        Clipboard::new_attempts(10).map_err(|err| format!("Failed to open clipboard: {err}"))?;
+    raw::set_bitmap(&bmp).map_err(|err| format!("Failed to place image on clipboard: {err}"))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn write_image_to_clipboard(_width: usize, _height: usize, _rgba: &[u8]) -> Result<(), String> {
+    Err("Copying images to the clipboard is only implemented on Windows".to_string())
+}
+
+/// The pixels of `image` under `rect` (in points), as opaque RGBA rows, with their width and
+/// height. `None` when `rect` is outside the image.
+fn crop_screenshot(
+    image: &egui::ColorImage,
+    rect: egui::Rect,
+    pixels_per_point: f32,
+) -> Option<(usize, usize, Vec<u8>)> {
+    let [image_width, image_height] = image.size;
+    let to_pixels = |points: f32, limit: usize| {
+        ((points * pixels_per_point).round().max(0.0) as usize).min(limit)
+    };
+    let (left, right) = (
+        to_pixels(rect.min.x, image_width),
+        to_pixels(rect.max.x, image_width),
+    );
+    let (top, bottom) = (
+        to_pixels(rect.min.y, image_height),
+        to_pixels(rect.max.y, image_height),
+    );
+    if right <= left || bottom <= top {
+        return None;
+    }
+    let mut rgba = Vec::with_capacity((right - left) * (bottom - top) * 4);
+    for y in top..bottom {
+        for color in &image.pixels[y * image_width + left..y * image_width + right] {
+            rgba.extend_from_slice(&[color.r(), color.g(), color.b(), 255]);
+        }
+    }
+    Some((right - left, bottom - top, rgba))
+}
+
 #[cfg(target_os = "windows")]
 fn clear_system_clipboard() -> Result<(), String> {
     use clipboard_win::{raw, Clipboard};
@@ -741,6 +916,61 @@ fn read_drop_effect_from_clipboard() -> Result<FileClipboardOperation, String> {
     Ok(FileClipboardOperation::Copy)
 }
 
+/// `source_path`'s file name in `directory`, or the first free `name (1).ext`, `name (2).ext`...
+fn free_destination_path(directory: &Path, source_path: &Path) -> PathBuf {
+    let file_name = source_path.file_name().unwrap_or_default();
+    let mut dest_path = directory.join(file_name);
+    let mut suffix = 1;
+    while dest_path.exists() {
+        let stem = source_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("file");
+        let ext = source_path.extension().and_then(|e| e.to_str());
+        let new_name = if let Some(ext) = ext {
+            format!("{} ({}).{}", stem, suffix, ext)
+        } else {
+            format!("{} ({})", stem, suffix)
+        };
+        dest_path = directory.join(&new_name);
+        suffix += 1;
+        if suffix > 1000 {
+            break;
+        }
+    }
+    dest_path
+}
+
+/// Copies or moves one file. Moves that cannot rename (e.g. across drives) copy and then
+/// remove the original.
+fn transfer_file(
+    source_path: &Path,
+    dest_path: &Path,
+    operation: FileClipboardOperation,
+) -> Result<(), String> {
+    let file_name = source_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy();
+    match operation {
+        FileClipboardOperation::Copy => std::fs::copy(source_path, dest_path)
+            .map(|_| ())
+            .map_err(|err| format!("Failed to copy '{}': {}", file_name, err)),
+        FileClipboardOperation::Cut => match std::fs::rename(source_path, dest_path) {
+            Ok(()) => Ok(()),
+            Err(_) => match std::fs::copy(source_path, dest_path) {
+                Ok(_) => std::fs::remove_file(source_path).map_err(|e| {
+                    format!(
+                        "Copied '{}' but failed to remove original: {}",
+                        file_name, e
+                    )
+                }),
+                Err(copy_err) => Err(format!("Failed to move '{}': {}", file_name, copy_err)),
+            },
+        },
+    }
+}
+
 fn move_paths_to_recycle_bin(paths: &[PathBuf]) -> Result<(), String> {
     if paths.is_empty() {
         return Ok(());
@@ -859,6 +1089,17 @@ struct MasonryReturnState {
     list_signature: u64,
 }
 
+/// Borderless fullscreen moving to another monitor. Windows rescales the window when it
+/// crosses into a monitor with a different DPI, so the placement is re-sent until it sticks.
+#[derive(Clone, Copy, Debug)]
+struct FullscreenMonitorMove {
+    monitor: monitors::Monitor,
+    frames_left: u8,
+    /// The window was natively maximized: it was restored for the move and is maximized
+    /// again on the next frame.
+    remaximize: bool,
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 struct PendingMasonrySoloReentry {
     index: usize,
@@ -1056,6 +1297,106 @@ struct FullscreenViewState {
     flip_vertical: bool,
 }
 
+/// File handed to the external editor by `open_in_editor`, polled for saves on a helper
+/// thread while it stays the current file. Dropping the watch stops the thread.
+struct ExternalEditWatch {
+    path: PathBuf,
+    /// Gets a message once a save has settled.
+    saved: crossbeam_channel::Receiver<()>,
+    stop: Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl ExternalEditWatch {
+    fn start(path: PathBuf, repaint_target: gpu_recovery::RepaintTarget) -> Self {
+        let (saved_tx, saved) = crossbeam_channel::bounded(1);
+        let stop = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let worker_stop = Arc::clone(&stop);
+        let worker_path = path.clone();
+        let _ = std::thread::Builder::new()
+            .name("external-edit-watch".to_string())
+            .spawn(move || {
+                // Stamp of the version on screen.
+                let mut shown = file_stamp_for_path(&worker_path);
+                // Newer stamp seen on the last poll. The file is reloaded once it holds for
+                // another poll, so a save that is still being written is not decoded half-way.
+                let mut changed = None;
+                loop {
+                    std::thread::sleep(EXTERNAL_EDIT_POLL_INTERVAL);
+                    if worker_stop.load(std::sync::atomic::Ordering::Relaxed) {
+                        return;
+                    }
+                    let stamp = file_stamp_for_path(&worker_path);
+                    if stamp.is_none() || stamp == shown {
+                        // Editors that save by replacing the file leave it missing for a moment.
+                        changed = None;
+                    } else if stamp != changed {
+                        changed = stamp;
+                    } else {
+                        shown = stamp;
+                        changed = None;
+                        if let Err(crossbeam_channel::TrySendError::Disconnected(_)) =
+                            saved_tx.try_send(())
+                        {
+                            return;
+                        }
+                        repaint_target.request_repaint();
+                    }
+                }
+            });
+        Self { path, saved, stop }
+    }
+}
+
+impl Drop for ExternalEditWatch {
+    fn drop(&mut self) {
+        self.stop.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+const EXTERNAL_EDIT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Image left by next/previous, kept on screen by `transition_effect` while the next image
+/// comes in.
+struct ImageTransition {
+    /// The outgoing image. Holding the handle keeps the GPU texture alive.
+    texture: egui::TextureHandle,
+    /// Where the outgoing image was last drawn.
+    rect: egui::Rect,
+    forward: bool,
+    /// The image being navigated to.
+    target: PathBuf,
+    requested_at: Instant,
+    /// When the incoming image first showed; `None` while it still decodes.
+    started_at: Option<Instant>,
+}
+
+/// A transition whose incoming image takes longer than this to decode is dropped; the old
+/// image would otherwise fade over an image the user has been looking at for a while.
+const IMAGE_TRANSITION_MAX_WAIT: Duration = Duration::from_millis(1500);
+
+/// A 90° rotation easing into place instead of snapping.
+struct RotationTurn {
+    clockwise: bool,
+    /// Screen point the view turns around, so what is under the pointer stays there; `None`
+    /// turns the image about its own center.
+    pivot: Option<egui::Pos2>,
+    /// Zoom before the turn over zoom after it; the drawn size eases from one to the other.
+    zoom_ratio: f32,
+    /// When the rotated media was laid out; `None` until then.
+    started_at: Option<Instant>,
+}
+
+const ROTATION_TURN_DURATION: Duration = Duration::from_millis(150);
+
+/// How far a [`RotationTurn`] still is from the settled view this frame.
+struct RotationTurnFrame {
+    /// Added to the drawn rotation; positive is clockwise.
+    degrees: f32,
+    pivot: Option<egui::Pos2>,
+    /// Multiplies the drawn size.
+    scale: f32,
+}
+
 impl Default for FullscreenViewState {
     fn default() -> Self {
         Self {
@@ -1093,6 +1434,21 @@ struct PendingMediaLoad {
     started_at: Instant,
 }
 
+/// Decoder settings a preloaded video was built with. It is only swapped in while the
+/// current settings still match.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct VideoPreloadSettings {
+    decoder_preferences: (bool, bool, bool, bool),
+    output_bounds: Option<(u32, u32)>,
+}
+
+/// The next solo video, opened on a worker and paused at its first frame.
+struct PreloadedVideo {
+    path: PathBuf,
+    settings: VideoPreloadSettings,
+    result_rx: crossbeam_channel::Receiver<Result<VideoPlayer, String>>,
+}
+
 #[derive(Clone, Debug)]
 struct PendingMangaFocusedVideoLoad {
     request_id: u64,
@@ -1108,6 +1464,23 @@ struct AsyncImageLoad {
     gif_filter: FilterType,
 }
 
+/// Saved position offered for the video that just opened, under `resume_playback = ask`.
+struct VideoResumeOffer {
+    path: PathBuf,
+    position: Duration,
+    /// The prompt goes away by itself after this.
+    until: Instant,
+}
+
+/// Progress of [`Action::CopyViewToClipboard`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ViewCapture {
+    /// The next frame is drawn without the title bar, video bar and notices, then captured.
+    Pending,
+    /// The capture was requested; it is cropped to this rect, in points.
+    Requested(egui::Rect),
+}
+
 #[derive(Clone)]
 struct PendingVideoThumbnailPlaceholder {
     path: PathBuf,
@@ -1222,6 +1595,12 @@ enum MediaLoadRequest {
 }
 
 enum MediaLoadResult {
+    /// A low-resolution stand-in sent ahead of the `Image` result of the same request.
+    ImagePreview {
+        request_id: u64,
+        path: PathBuf,
+        image: LoadedImage,
+    },
     Image {
         request_id: u64,
         path: PathBuf,
@@ -1317,14 +1696,33 @@ fn run_media_load_coordinator(
                 break;
             };
 
+            if let MediaLoadRequest::Image {
+                request_id, path, ..
+            } = &request
+            {
+                if let Some(image) = LoadedImage::load_embedded_jpeg_preview(path) {
+                    let preview = MediaLoadResult::ImagePreview {
+                        request_id: *request_id,
+                        path: path.clone(),
+                        image,
+                    };
+                    if result_tx.send(preview).is_err() {
+                        return;
+                    }
+                }
+            }
+
             let result = process_media_load_request(request);
+
+            // The user moved on while this decoded; drop the stale result here rather than
+            // handing it (and a stale video pipeline's teardown) to the UI thread.
+            if latest_request.lock().is_some() {
+                continue;
+            }
             if result_tx.send(result).is_err() {
                 return;
             }
-
-            if latest_request.lock().is_none() {
-                break;
-            }
+            break;
         }
     }
 }
@@ -1641,6 +2039,28 @@ fn load_solo_probe_image(
     })
 }
 
+/// Loader for secondary viewer windows: goes through the shared decoded-image cache first.
+fn load_viewer_window_frame(
+    path: &Path,
+    settings: &viewer_window::ViewerWindowSettings,
+) -> Result<ImageFrame, String> {
+    if let Some(decoded) = load_solo_probe_image(
+        path,
+        settings.max_texture_side,
+        settings.downscale_filter,
+        settings.gif_filter,
+    ) {
+        return Ok(decoded.first_frame);
+    }
+    LoadedImage::load_first_frame_only(
+        path,
+        Some(settings.max_texture_side),
+        settings.downscale_filter,
+        settings.gif_filter,
+    )
+    .map(|image| image.current_frame_data().clone())
+}
+
 fn extract_video_first_frame_thumbnail(
     path: &Path,
     max_texture_side: u32,
@@ -1979,7 +2399,17 @@ fn process_manga_focused_video_load_request(
     }
 }
 
-const DECODED_IMAGE_CACHE_MAX_BYTES: u64 = 384 * 1024 * 1024;
+/// Recently viewed single-view images, decoded, within `decoded_image_cache_mb`.
+fn build_decoded_image_cache(budget_mb: u64) -> moka::sync::Cache<String, Arc<CachedDecodedImage>> {
+    moka::sync::Cache::builder()
+        .max_capacity(budget_mb.saturating_mul(1024 * 1024))
+        .weigher(|_, value: &Arc<CachedDecodedImage>| {
+            let frame_bytes = value.first_frame.pixels.len().min(u32::MAX as usize) as u32;
+            frame_bytes.saturating_add(256)
+        })
+        .build()
+}
+
 const DECODED_IMAGE_CACHE_SKIP_ENTRY_BYTES: usize = 96 * 1024 * 1024;
 const STATIC_THUMBNAIL_CACHE_SKIP_ENTRY_BYTES: usize = 96 * 1024 * 1024;
 
@@ -2138,6 +2568,11 @@ struct ImageViewer {
     solo_probe_coordinator: SoloProbeCoordinator,
     /// Active non-blocking media load (if any).
     pending_media_load: Option<PendingMediaLoad>,
+    /// Next video in the folder, pre-rolled while the current one plays.
+    preloaded_video: Option<PreloadedVideo>,
+    /// A preloaded video taken for `pending_media_load`, applied on the next poll in place
+    /// of a coordinator result.
+    preloaded_video_result: Option<MediaLoadResult>,
     /// One-shot flag: suppress auto-showing the bottom video controls
     /// for the next solo video load request.
     suppress_video_controls_for_next_video_load: bool,
@@ -2171,6 +2606,130 @@ struct ImageViewer {
     shortcuts_help_modal_open: bool,
     /// Skips one outside-click close check right after opening the shortcuts/help modal.
     shortcuts_help_modal_skip_outside_click_once: bool,
+    /// In-app config editor; `Some` while the settings window is open.
+    settings_window: Option<SettingsWindow>,
+    /// Settings edits not yet written to config.ini (deferred while a widget is dragged).
+    settings_window_save_pending: bool,
+    /// Hold-right-drag gesture in progress.
+    mouse_gesture: GestureTracker,
+    /// Touch contacts and tap history for touchscreen gestures.
+    touch_tracker: TouchTracker,
+    /// Usage statistics window; holds the counters shown while it is open.
+    usage_stats_window: Option<usage_stats::UsageSnapshot>,
+    /// Additional viewer windows opened with `new_window` or "Open in new window".
+    viewer_windows: Vec<viewer_window::ViewerWindow>,
+    /// Serial for the next viewer window's viewport id.
+    viewer_window_serial: u64,
+    /// Window icon for viewer windows, built on first use.
+    viewer_window_icon: Option<Arc<egui::IconData>>,
+    /// Zoom and pan in one viewer window (or the fullscreen main view) move the others too.
+    zoom_sync: bool,
+    /// View last shared between synced windows, to notice when the main view changes.
+    zoom_sync_last_view: Option<viewer_window::SyncedView>,
+    /// Stereo export dialog for the current side-by-side image, while it is open.
+    stereo_export: Option<stereo_export::StereoExportDialog>,
+    /// Scan cleanup dialog for the current document photo, while it is open.
+    scan_cleanup: Option<scan_cleanup::ScanCleanupDialog>,
+    /// PDF export dialog for the marked images or the folder list, while it is open.
+    pdf_export: Option<pdf_export::PdfExportDialog>,
+    /// Animation conversion dialog, while it is open.
+    animation_export: Option<animation_export::AnimationExportDialog>,
+    /// Conversions, trims, exports and other background work, listed in the jobs panel.
+    jobs: jobs::JobManager,
+    /// Whether the jobs panel lists the jobs or only its header.
+    jobs_panel_expanded: bool,
+    /// In and out marks set on the current video.
+    video_trim_marks: Option<video_trim::TrimMarks>,
+    /// Video trim dialog, while it is open.
+    video_trim: Option<video_trim::TrimDialog>,
+    /// Batch rename dialog for the folder list, while it is open.
+    batch_rename: Option<batch_rename::BatchRenameDialog>,
+    /// `(original, renamed)` pairs of the last batch rename, for undo.
+    last_batch_rename: Option<Vec<(PathBuf, PathBuf)>>,
+    monitor_wall: Option<monitor_wall::MonitorWall>,
+    /// Command palette listing every action, while it is open.
+    command_palette: Option<command_palette::CommandPalette>,
+    /// Filter narrowing `image_list` to matching files of the folder, while one is active.
+    quick_filter: Option<quick_filter::QuickFilter>,
+    /// Active playlist; navigation follows its queue instead of the folder.
+    playlist: Option<playlist::Playlist>,
+    /// Image being downloaded from a URL; it opens when the download ends.
+    download: Option<remote::Download>,
+    /// Images coming up in a playlist of URLs, downloaded ahead.
+    url_prefetch: remote::Prefetcher,
+    /// Screen rect of the queue panel last frame, so presses on it don't reach the viewer.
+    playlist_panel_rect: Option<egui::Rect>,
+    /// Screen rect of the video resume prompt last frame.
+    video_resume_offer_rect: Option<egui::Rect>,
+    /// Copy of the current view to the clipboard in progress.
+    view_capture: Option<ViewCapture>,
+    /// Brief notices drawn at the bottom of the window.
+    osd: osd::Osd,
+    /// Colors and sizes of the title bar, video bar and notices.
+    overlay_theme: theme::OverlayTheme,
+    /// A control held keyboard focus at the end of the last frame, so Enter, Space, Escape
+    /// and the arrows go to it instead of the viewer shortcuts.
+    keyboard_focus_active: bool,
+    /// When `overlay_theme` was last resolved; `None` re-resolves it on the next frame.
+    overlay_theme_checked_at: Option<Instant>,
+    /// Notification-area icon, when `tray_icon` is on.
+    tray: Option<tray::TrayIcon>,
+    /// Local control socket, when `remote_control` is on.
+    remote_control: Option<remote_control::ControlServer>,
+    /// The window was hidden to the tray icon instead of minimized.
+    hidden_in_tray: bool,
+    /// Open dialog running on a worker thread; sends the picked file, or `None` on cancel.
+    pending_open_dialog: Option<crossbeam_channel::Receiver<Option<PathBuf>>>,
+    /// Running slideshow: the index on screen and when it was first shown.
+    slideshow: Option<(usize, Instant)>,
+    /// Video progress and thumbnail buttons on the taskbar button, once a video was shown.
+    taskbar: Option<taskbar::TaskbarControls>,
+    /// Media keys and the shell's media flyout, once a video was shown.
+    media_controls: Option<media_controls::MediaControls>,
+    /// Repaint target for background threads; it follows the renderer across a GPU device
+    /// loss, see [`gpu_recovery`].
+    repaint_target: gpu_recovery::RepaintTarget,
+    ratings: ratings::RatingStore,
+    /// Compare mode for two images, while it is open.
+    compare_view: Option<compare::CompareView>,
+    /// Last file counted as viewed, so reloads of the same file are not counted again.
+    usage_stats_last_viewed_path: Option<PathBuf>,
+    /// Annotation mode: active tool and the per-image annotation layers of this session.
+    annotations: AnnotationEditor,
+    /// Straighten mode: the level line being dragged and export progress.
+    straighten: straighten::StraightenTool,
+    /// Perspective correction mode: corner quad, preview toggle and export progress.
+    perspective: perspective::PerspectiveTool,
+    /// Eyedropper mode: shows and copies the color of the pixel under the pointer.
+    eyedropper_active: bool,
+    /// Whether the `free_rotate` key is held, and the pointer position of the rotate drag.
+    free_rotate_held: bool,
+    free_rotate_last_pos: Option<egui::Pos2>,
+    /// Loupe image while the `magnifier` key is held; dropped on release.
+    magnifier_texture: Option<egui::TextureHandle>,
+    /// Flattened-PNG export running on a worker; yields the written path.
+    annotation_export_rx: Option<crossbeam_channel::Receiver<Result<PathBuf, String>>>,
+    /// Solo-view brightness/contrast/saturation/gamma, drawn through `adjustment_renderer`.
+    image_adjustments: ImageAdjustments,
+    adjustment_renderer: Arc<AdjustmentRenderer>,
+    adjustments_panel_open: bool,
+    /// Screen rect of the adjustments panel last frame, so presses on it don't pan the image.
+    adjustments_panel_rect: Option<egui::Rect>,
+    /// Adjusted-PNG export running on a worker; yields the written path.
+    adjustment_export_rx: Option<crossbeam_channel::Receiver<Result<PathBuf, String>>>,
+    /// Last export result shown in the adjustments panel.
+    adjustment_status: Option<String>,
+    /// `.cube` LUT applied with the adjustments, to images and videos alike.
+    lut: Option<Arc<lut::CubeLut>>,
+    /// System Open dialog for a LUT, running on its own thread (Windows only).
+    pending_lut_dialog: Option<crossbeam_channel::Receiver<Option<PathBuf>>>,
+    /// 2x2 checkerboard tile and the light/dark colors it was built from.
+    checkerboard_texture: Option<(egui::TextureHandle, [[u8; 3]; 2])>,
+    /// Whether the current image has transparent pixels, cached per path.
+    current_image_transparency: Option<(PathBuf, bool)>,
+    /// The export transparency choice was changed from a toolbar or dialog and is saved with
+    /// the next export.
+    export_alpha_unsaved: bool,
     /// Tracks Ctrl+V hold state so paste triggers once per key press even if key_pressed is swallowed.
     paste_shortcut_ctrl_v_was_down: bool,
     /// Cached thumbnail textures used by delete/rename dialogs.
@@ -2271,6 +2830,23 @@ struct ImageViewer {
     toggle_fullscreen_force_borderless: bool,
     /// True when fullscreen toggle was requested by the title-bar maximize/restore button.
     toggle_fullscreen_from_titlebar: bool,
+    /// Request moving the window to the next monitor.
+    move_to_next_monitor: bool,
+    /// Request snapping the floating window toward a screen edge.
+    pending_window_snap: Option<SnapDirection>,
+    /// Request applying `window_size_presets[index]`.
+    pending_size_preset: Option<usize>,
+    /// Half or quarter the floating window is snapped to, until it is moved or resized.
+    window_snap_region: Option<SnapRegion>,
+    /// When true, the floating window keeps its size and the media is fitted inside it
+    /// instead of the window following the zoom. Set by snapping and pixel size presets.
+    floating_size_pinned: bool,
+    /// Request toggling the always-on-top window level.
+    toggle_always_on_top: bool,
+    always_on_top: bool,
+    /// Window rect and zoom before the compact always-on-top layout, while it is active.
+    always_on_top_compact_restore: Option<(egui::Rect, f32)>,
+    fullscreen_monitor_move: Option<FullscreenMonitorMove>,
     /// Request minimize
     request_minimize: bool,
     /// Request native maximize (`Some(true)`) or restore (`Some(false)`) for the root window.
@@ -2311,6 +2887,10 @@ struct ImageViewer {
     /// Maps image paths to their saved view states (zoom, pan, rotation, flip).
     /// Only active in fullscreen mode; cleared when exiting fullscreen.
     fullscreen_view_states: HashMap<PathBuf, FullscreenViewState>,
+    /// File opened with `open_in_editor`, reloaded when the editor saves it.
+    external_edit: Option<ExternalEditWatch>,
+    /// View to put back once the reloaded external edit is on screen.
+    external_edit_reload_view: Option<(PathBuf, FullscreenViewState)>,
     /// True when the current fullscreen solo-media view was explicitly changed by the user.
     /// Default fit-to-screen transitions keep this false so next/previous only restore real
     /// pan/zoom/rotation memories instead of whatever transform happened to be visible.
@@ -2345,6 +2925,12 @@ struct ImageViewer {
     /// True while a previous solo-media texture is intentionally kept visible
     /// until a replacement load finishes.
     retained_media_placeholder_visible: bool,
+    /// Next/previous animation in progress, see `transition_effect`.
+    image_transition: Option<ImageTransition>,
+    /// Where the current image texture was drawn last frame, for the next transition.
+    last_image_rect: Option<egui::Rect>,
+    /// Quarter turn from the rotate actions, animating into place.
+    rotation_turn: Option<RotationTurn>,
     /// Defers zoom/pan reset while a retained solo-media placeholder is still visible.
     defer_media_view_reset: bool,
     /// Index allowed to reuse the pre-strip solo texture/video as a temporary fallback.
@@ -2370,6 +2956,13 @@ struct ImageViewer {
     /// Whether the pointer is over selectable title-bar text (filename, resolution, zoom, etc.).
     /// Used to suppress drag/pan/double-click gestures while selecting/copying title text.
     mouse_over_title_text: bool,
+    /// Text of the title-bar zoom box; shows the current zoom unless it is being edited.
+    zoom_input_text: String,
+    /// Whether the title-bar zoom box has keyboard focus this frame; shortcuts are ignored
+    /// meanwhile.
+    zoom_input_focused: bool,
+    /// When the current `pan_*` key hold started.
+    keyboard_pan_held_since: Option<Instant>,
     /// Whether the title-bar FAB menu button or popup is active.
     /// Used to keep the title bar visible and block click-through into the main view.
     title_bar_menu_active: bool,
@@ -2382,6 +2975,10 @@ struct ImageViewer {
     seek_preview_fraction: Option<f32>,
     /// Last fraction actually sent to the player during an active drag.
     seek_last_requested_fraction: Option<f32>,
+    /// Hover thumbnails of the current video's timeline, built as the seek bar is hovered.
+    seek_thumbnails: Option<SeekThumbnails>,
+    /// Prompt to continue the current video from where it was left last time.
+    video_resume_offer: Option<VideoResumeOffer>,
     /// Rate-limit continuous seeks while dragging
     last_seek_sent_at: Instant,
     /// Whether the video was playing when a seek interaction started
@@ -2423,6 +3020,10 @@ struct ImageViewer {
     is_idle: bool,
     /// Idle repaint interval counter - skip unnecessary repaints when truly idle
     idle_frame_skip_counter: u32,
+    /// When polling for a pending media layout began; the poll slows down after a while.
+    pending_media_layout_since: Option<Instant>,
+    /// Since when the solo video has sat paused with nothing else going on.
+    video_paused_since: Option<Instant>,
 
     /// Rolling runtime metrics used for perf diagnostics.
     perf_metrics: PerfMetrics,
@@ -2447,11 +3048,8 @@ struct ImageViewer {
     /// Primary monitor refresh rate used to keep FPS overlay vsync-aware.
     fps_display_refresh_hz: Option<f32>,
 
-    /// Whether we've installed extra Windows fonts for CJK filename rendering.
-    /// These font files can be quite large, so we install them lazily only when needed.
-    windows_cjk_fonts_installed: bool,
-    /// Pending background load for Windows CJK font bytes.
-    pending_windows_cjk_font_load: Option<crossbeam_channel::Receiver<Vec<(String, Vec<u8>)>>>,
+    /// System fonts for non-Latin scripts, loaded lazily when a file name needs them.
+    font_fallback: font_fallback::FontFallback,
     /// Pending background metadata probe for title-bar file size text.
     pending_file_size_probe: Option<crossbeam_channel::Receiver<(PathBuf, Option<String>)>>,
     /// Path associated with `pending_file_size_probe`.
@@ -2557,12 +3155,26 @@ struct ImageViewer {
     manga_autoscroll_middle_hold_started_at: Option<Instant>,
     /// Horizontal velocity used for smooth shift+wheel panning in strip/masonry layouts.
     manga_shift_wheel_pan_velocity_x: f32,
+    /// Long-strip page-width zoom as a fraction of the viewport width.
+    /// `None` keeps the default fit-to-height strip layout driven by `zoom`.
+    manga_page_width_zoom: Option<f32>,
+    /// Folder page-width zoom waiting to be written to the folder travel cache once idle.
+    pending_manga_page_width_zoom_store: Option<(PathBuf, Option<f32>)>,
+    /// Long-strip per-page rotation/fit overrides for the current folder, keyed by file name.
+    manga_page_overrides: HashMap<String, MangaPageOverride>,
+    /// Folder the overrides above belong to.
+    manga_page_overrides_directory: Option<PathBuf>,
+    /// Overrides changed since they were last written to the folder travel cache.
+    pending_manga_page_overrides_store: bool,
+    /// Detected panel gutters per decoded page (fractions of its height), for panel snap.
+    manga_panel_breaks: HashMap<PathBuf, Vec<f32>>,
 
     /// Cached total height of all pages in manga mode for the current zoom/screen height.
     /// This avoids an O(n) scan on every scroll tick for large folders.
     manga_total_height_cache: f32,
     manga_total_height_cache_zoom: f32,
     manga_total_height_cache_screen_y: f32,
+    manga_total_height_cache_page_width: f32,
     manga_total_height_cache_len: usize,
     manga_total_height_cache_valid: bool,
 
@@ -2823,13 +3435,7 @@ impl Default for ImageViewer {
             texture_frame: 0,
             image_list: Vec::new(),
             image_list_signature: 0,
-            decoded_image_cache: moka::sync::Cache::builder()
-                .max_capacity(DECODED_IMAGE_CACHE_MAX_BYTES)
-                .weigher(|_, value: &Arc<CachedDecodedImage>| {
-                    let frame_bytes = value.first_frame.pixels.len().min(u32::MAX as usize) as u32;
-                    frame_bytes.saturating_add(256)
-                })
-                .build(),
+            decoded_image_cache: build_decoded_image_cache(config.decoded_image_cache_mb),
             solo_image_texture_cache: HashMap::new(),
             solo_image_texture_cache_order: VecDeque::new(),
             media_directory_index: MediaDirectoryIndex::default(),
@@ -2841,6 +3447,8 @@ impl Default for ImageViewer {
             media_load_coordinator: MediaLoadCoordinator::new(),
             solo_probe_coordinator: SoloProbeCoordinator::new(),
             pending_media_load: None,
+            preloaded_video: None,
+            preloaded_video_result: None,
             suppress_video_controls_for_next_video_load: false,
             suppress_video_controls_for_request_id: None,
             next_manga_video_load_request_id: 1,
@@ -2857,6 +3465,69 @@ impl Default for ImageViewer {
             pending_exit_confirmation: false,
             shortcuts_help_modal_open: false,
             shortcuts_help_modal_skip_outside_click_once: false,
+            settings_window: None,
+            settings_window_save_pending: false,
+            mouse_gesture: GestureTracker::default(),
+            touch_tracker: TouchTracker::default(),
+            annotations: AnnotationEditor::default(),
+            straighten: straighten::StraightenTool::default(),
+            perspective: perspective::PerspectiveTool::default(),
+            eyedropper_active: false,
+            free_rotate_held: false,
+            free_rotate_last_pos: None,
+            magnifier_texture: None,
+            usage_stats_window: None,
+            viewer_windows: Vec::new(),
+            viewer_window_serial: 0,
+            viewer_window_icon: None,
+            zoom_sync: false,
+            zoom_sync_last_view: None,
+            stereo_export: None,
+            scan_cleanup: None,
+            pdf_export: None,
+            animation_export: None,
+            jobs: jobs::JobManager::default(),
+            jobs_panel_expanded: true,
+            video_trim_marks: None,
+            video_trim: None,
+            batch_rename: None,
+            last_batch_rename: None,
+            command_palette: None,
+            quick_filter: None,
+            playlist: None,
+            download: None,
+            url_prefetch: remote::Prefetcher::default(),
+            playlist_panel_rect: None,
+            video_resume_offer_rect: None,
+            view_capture: None,
+            osd: osd::Osd::default(),
+            overlay_theme: theme::OverlayTheme::default(),
+            overlay_theme_checked_at: None,
+            keyboard_focus_active: false,
+            tray: None,
+            remote_control: None,
+            hidden_in_tray: false,
+            pending_open_dialog: None,
+            slideshow: None,
+            taskbar: None,
+            media_controls: None,
+            repaint_target: gpu_recovery::RepaintTarget::default(),
+            monitor_wall: None,
+            ratings: ratings::RatingStore::default(),
+            compare_view: None,
+            usage_stats_last_viewed_path: None,
+            annotation_export_rx: None,
+            image_adjustments: ImageAdjustments::default(),
+            adjustment_renderer: Arc::new(AdjustmentRenderer::default()),
+            adjustments_panel_open: false,
+            adjustments_panel_rect: None,
+            adjustment_export_rx: None,
+            adjustment_status: None,
+            lut: None,
+            pending_lut_dialog: None,
+            checkerboard_texture: None,
+            current_image_transparency: None,
+            export_alpha_unsaved: false,
             paste_shortcut_ctrl_v_was_down: false,
             modal_thumbnail_cache: HashMap::new(),
             folder_placeholder_preview_scan_pending: HashSet::new(),
@@ -2903,6 +3574,15 @@ impl Default for ImageViewer {
             should_exit: false,
             toggle_fullscreen: false,
             toggle_fullscreen_force_borderless: false,
+            move_to_next_monitor: false,
+            pending_window_snap: None,
+            pending_size_preset: None,
+            window_snap_region: None,
+            floating_size_pinned: false,
+            toggle_always_on_top: false,
+            always_on_top: false,
+            always_on_top_compact_restore: None,
+            fullscreen_monitor_move: None,
             toggle_fullscreen_from_titlebar: false,
             request_minimize: false,
             request_native_maximize: None,
@@ -2920,6 +3600,8 @@ impl Default for ImageViewer {
             pending_fullscreen_layout: false,
             pending_maximized_layout: false,
             fullscreen_view_states: HashMap::new(),
+            external_edit: None,
+            external_edit_reload_view: None,
             current_fullscreen_view_has_memory: false,
             strip_open_force_fit_path: None,
             // Video-specific fields
@@ -2932,6 +3614,9 @@ impl Default for ImageViewer {
             pending_video_thumbnail_placeholder: None,
             pending_mode_switch_placeholder: None,
             retained_media_placeholder_visible: false,
+            image_transition: None,
+            last_image_rect: None,
+            rotation_turn: None,
             defer_media_view_reset: false,
             strip_entry_placeholder_index: None,
             strip_entry_placeholder_path: None,
@@ -2942,11 +3627,16 @@ impl Default for ImageViewer {
             mouse_over_video_controls: false,
             mouse_over_window_buttons: false,
             mouse_over_title_text: false,
+            zoom_input_text: String::new(),
+            zoom_input_focused: false,
+            keyboard_pan_held_since: None,
             title_bar_menu_active: false,
             title_text_dragging: false,
             is_seeking: false,
             seek_preview_fraction: None,
             seek_last_requested_fraction: None,
+            seek_thumbnails: None,
+            video_resume_offer: None,
             last_seek_sent_at: Instant::now(),
             seek_was_playing: false,
             is_volume_dragging: false,
@@ -2968,6 +3658,8 @@ impl Default for ImageViewer {
             last_activity_time: Instant::now(),
             is_idle: true,
             idle_frame_skip_counter: 0,
+            pending_media_layout_since: None,
+            video_paused_since: None,
             perf_metrics: PerfMetrics::default(),
 
             fps_last_frame_at: Instant::now(),
@@ -2980,8 +3672,7 @@ impl Default for ImageViewer {
             fps_overlay_last_update_at: Instant::now(),
             fps_display_refresh_hz: get_primary_monitor_refresh_hz(),
 
-            windows_cjk_fonts_installed: false,
-            pending_windows_cjk_font_load: None,
+            font_fallback: font_fallback::FontFallback::default(),
             pending_file_size_probe: None,
             pending_file_size_probe_path: None,
             current_file_size_label: None,
@@ -3034,10 +3725,17 @@ impl Default for ImageViewer {
             manga_autoscroll_cancel_on_middle_release: false,
             manga_autoscroll_middle_hold_started_at: None,
             manga_shift_wheel_pan_velocity_x: 0.0,
+            manga_page_width_zoom: None,
+            pending_manga_page_width_zoom_store: None,
+            manga_page_overrides: HashMap::new(),
+            manga_page_overrides_directory: None,
+            pending_manga_page_overrides_store: false,
+            manga_panel_breaks: HashMap::new(),
 
             manga_total_height_cache: 0.0,
             manga_total_height_cache_zoom: 1.0,
             manga_total_height_cache_screen_y: 0.0,
+            manga_total_height_cache_page_width: 0.0,
             manga_total_height_cache_len: 0,
             manga_total_height_cache_valid: false,
             manga_layout_offsets: Vec::new(),
@@ -3145,9 +3843,10 @@ impl Default for ImageViewer {
 }
 
 impl ImageViewer {
-    const TITLE_BAR_HEIGHT: f32 = 32.0;
     const BREADCRUMB_BAR_HEIGHT: f32 = 30.0;
     const TOP_CONTROLS_HOTZONE_EXTRA: f32 = 18.0;
+    /// Change of `ui_scale` per `ui_scale_up` / `ui_scale_down` press.
+    const UI_SCALE_STEP: f32 = 0.1;
     const BOTTOM_RIGHT_OVERLAY_MARGIN: f32 = 16.0;
     const BOTTOM_RIGHT_OVERLAY_SCROLLBAR_PADDING: f32 = 35.0;
     const MANGA_HUD_PANEL_WIDTH: f32 = 224.0;
@@ -3192,7 +3891,13 @@ impl ImageViewer {
     const MANGA_PAGE_NAV_REPEAT_INITIAL_DELAY_MS: u64 = 260;
     const MANGA_PAGE_NAV_REPEAT_INTERVAL_MS: u64 = 45;
     const VIDEO_RESUME_MIN_SECONDS: f64 = 0.25;
+    /// How long the "resume from" prompt stays up when not answered.
+    const VIDEO_RESUME_OFFER_DURATION: Duration = Duration::from_secs(8);
     const VIDEO_RESUME_SEEK_EPSILON_SECONDS: f64 = 0.50;
+    const VIDEO_PRELOAD_PREROLL_TIMEOUT: Duration = Duration::from_secs(5);
+    const KEYBOARD_PAN_REPEAT_DELAY: Duration = Duration::from_millis(250);
+    const KEYBOARD_PAN_RAMP: Duration = Duration::from_secs(1);
+    const KEYBOARD_PAN_STEPS_PER_SECOND: f32 = 12.0;
     const FPS_IDLE_RESET_AFTER_MS: u64 = 350;
     const FOLDER_PLACEHOLDER_STAMP_CACHE_TTL: Duration = Duration::from_secs(2);
     const FOLDER_PLACEHOLDER_PREVIEW_SCAN_PENDING_SOFT_LIMIT: usize = 32;
@@ -3208,6 +3913,13 @@ impl ImageViewer {
     const ANIMATED_GIF_CUSTOM_DEFAULT_FPS: u32 = 12;
     const ANIMATED_IMAGE_CUSTOM_MAX_FPS: u32 = 240;
     const MEDIA_SLIDER_WHEEL_GUARD_DURATION: Duration = Duration::from_millis(500);
+    const SLOW_MEDIA_LOAD_SPINNER_DELAY: Duration = Duration::from_millis(300);
+    const PENDING_LAYOUT_FAST_POLL: Duration = Duration::from_secs(2);
+    const PENDING_LAYOUT_SLOW_POLL_MS: u64 = 250;
+    const VIDEO_SUSPEND_AFTER: Duration = Duration::from_secs(60);
+    const LOW_POWER_VIDEO_SUSPEND_AFTER: Duration = Duration::from_secs(10);
+    /// Redraw interval of playback and animations in `low_power_mode`.
+    const LOW_POWER_FRAME_MS: u64 = 16;
 
     fn folder_navigation_ui_enabled(&self) -> bool {
         self.manga_mode && self.is_fullscreen
@@ -3230,7 +3942,7 @@ impl ImageViewer {
     }
 
     fn top_controls_visible_height(&self) -> f32 {
-        Self::TITLE_BAR_HEIGHT
+        self.overlay_theme.title_bar_height
             + if self.folder_navigation_ui_enabled() && self.show_breadcrumb_bar {
                 Self::BREADCRUMB_BAR_HEIGHT
             } else {
@@ -3797,6 +4509,8 @@ impl ImageViewer {
 
     fn set_image_list(&mut self, files: Vec<PathBuf>) {
         let files = self.normalize_image_list_for_folder_navigation(files);
+        // A folder listing replaces the playlist queue.
+        self.playlist = None;
 
         self.folder_placeholder_thumbnail_cache
             .retain(|directory, _| directory.exists());
@@ -3808,11 +4522,22 @@ impl ImageViewer {
             .retain(|path, _| path.exists());
         self.folder_placeholder_stamp_cache.clear();
 
+        let files = match self.quick_filter.as_mut() {
+            Some(filter) if filter.covers(&files) => {
+                filter.source = files;
+                self.quick_filtered_list()
+            }
+            Some(_) => {
+                self.quick_filter = None;
+                files
+            }
+            None => files,
+        };
         self.set_image_list_raw(files);
     }
 
     fn try_append_new_entries_in_strip_mode(&mut self, scanned_files: &[PathBuf]) -> bool {
-        if !self.manga_mode || self.image_list.is_empty() {
+        if !self.manga_mode || self.image_list.is_empty() || self.quick_filter.is_some() {
             return false;
         }
 
@@ -4742,7 +5467,10 @@ impl ImageViewer {
         history_navigation: FolderHistoryNavigationKind,
     ) -> bool {
         if !directory.exists() || !directory.is_dir() {
-            self.error_message = Some(format!("Folder does not exist: {}", directory.display()));
+            self.error_message = Some(i18n::tr_args(
+                "error.folder_missing",
+                &[("path", &directory.display())],
+            ));
             return false;
         }
 
@@ -4753,11 +5481,17 @@ impl ImageViewer {
         // Persist the current folder viewport state before any folder-travel jump.
         self.store_folder_travel_position_for_current_folder();
 
-        let mut files = get_media_in_directory(directory);
+        let Some(mut files) = get_media_in_directory_bounded(directory) else {
+            self.error_message = Some(i18n::tr_args(
+                "error.folder_timed_out",
+                &[("path", &directory.display())],
+            ));
+            return false;
+        };
         if files.is_empty() {
-            self.error_message = Some(format!(
-                "No supported media files found in folder: {}",
-                directory.display()
+            self.error_message = Some(i18n::tr_args(
+                "error.folder_empty",
+                &[("path", &directory.display())],
             ));
             return false;
         }
@@ -4774,9 +5508,9 @@ impl ImageViewer {
             });
 
         if files.is_empty() {
-            self.error_message = Some(format!(
-                "No supported media files found in folder: {}",
-                directory.display()
+            self.error_message = Some(i18n::tr_args(
+                "error.folder_empty",
+                &[("path", &directory.display())],
             ));
             return false;
         }
@@ -4844,6 +5578,7 @@ impl ImageViewer {
                 self.reset_masonry_metadata_preload();
             }
 
+            self.restore_manga_folder_view_state();
             self.manga_update_preload_queue();
             if !self.restore_folder_travel_position_for_directory(directory) {
                 self.manga_scroll_offset = 0.0;
@@ -4956,18 +5691,247 @@ impl ImageViewer {
             || self.pending_single_delete_target.is_some()
             || !self.pending_marked_delete_targets.is_empty()
             || self.pending_exit_confirmation
-            || self.shortcuts_help_modal_open
+            || self.input_blocking_overlay_open()
+    }
+
+    /// Full-window overlays that own all input while they are open.
+    fn input_blocking_overlay_open(&self) -> bool {
+        self.shortcuts_help_modal_open
+            || self.settings_window.is_some()
+            || self.usage_stats_window.is_some()
+            || self.stereo_export.is_some()
+            || self.scan_cleanup.is_some()
+            || self.pdf_export.is_some()
+            || self.animation_export.is_some()
+            || self.video_trim.is_some()
+            || self.batch_rename.is_some()
+            || self.monitor_wall.is_some()
+            || self.compare_view.is_some()
+            || self.command_palette.is_some()
+    }
+
+    /// Shows `text` at the bottom of the window for a few seconds.
+    fn show_toast(&mut self, text: impl Into<String>) {
+        self.osd.push(text, osd::MESSAGE_DURATION);
+    }
+
+    /// Briefly confirms an action, replacing the previous notice of the same `kind`. Off
+    /// with `show_osd = false`.
+    fn show_action_osd(&mut self, kind: &'static str, text: impl Into<String>) {
+        if self.config.show_osd {
+            self.osd.replace(kind, text, osd::ACTION_DURATION);
+        }
+    }
+
+    /// The notice confirming `action`, read from the state it left behind.
+    fn action_osd_text(&self, action: Action) -> Option<(&'static str, String)> {
+        let solo = !self.manga_mode && self.current_media_type.is_some();
+        let key = match action {
+            Action::ZoomIn
+            | Action::ZoomOut
+            | Action::ResetZoom
+            | Action::CycleZoomPresets
+            | Action::Zoom25
+            | Action::Zoom50
+            | Action::Zoom100
+            | Action::Zoom200
+            | Action::Zoom400
+                if solo =>
+            {
+                let percent = format!("{:.0}", self.zoom_target * 100.0);
+                return Some(("zoom", i18n::tr_args("osd.zoom", &[("percent", &percent)])));
+            }
+            Action::RotateClockwise if self.image.is_some() || self.video_player.is_some() => {
+                "osd.rotated_clockwise"
+            }
+            Action::RotateCounterClockwise
+                if self.image.is_some() || self.video_player.is_some() =>
+            {
+                "osd.rotated_counterclockwise"
+            }
+            Action::PreciseRotationClockwise | Action::PreciseRotationCounterClockwise if solo => {
+                let degrees = format!("{:.1}", self.precise_rotation_target_degrees);
+                return Some((
+                    "view",
+                    i18n::tr_args("osd.rotation", &[("degrees", &degrees)]),
+                ));
+            }
+            Action::FlipHorizontally if solo => {
+                if self.flip_horizontal {
+                    "osd.flipped_horizontally"
+                } else {
+                    "osd.horizontal_flip_off"
+                }
+            }
+            Action::FlipVertically if solo => {
+                if self.flip_vertical {
+                    "osd.flipped_vertically"
+                } else {
+                    "osd.vertical_flip_off"
+                }
+            }
+            Action::VideoMute => {
+                let muted = self.video_player.as_ref()?.is_muted();
+                let key = if muted { "osd.muted" } else { "osd.unmuted" };
+                return Some(("mute", i18n::tr(key).to_string()));
+            }
+            Action::VideoTrimIn | Action::VideoTrimOut => {
+                let marks = self.video_trim_marks.as_ref()?;
+                let (mark, set_key, cleared_key) = if action == Action::VideoTrimIn {
+                    (marks.start, "osd.trim_in", "osd.trim_in_cleared")
+                } else {
+                    (marks.end, "osd.trim_out", "osd.trim_out_cleared")
+                };
+                let text = match mark {
+                    Some(time) => {
+                        i18n::tr_args(set_key, &[("time", &video_player::format_duration(time))])
+                    }
+                    None => i18n::tr(cleared_key).to_string(),
+                };
+                return Some(("trim", text));
+            }
+            Action::UiScaleUp | Action::UiScaleDown | Action::ResetUiScale => {
+                let percent = format!("{:.0}", self.config.ui_scale * 100.0);
+                return Some((
+                    "ui_scale",
+                    i18n::tr_args("osd.ui_scale", &[("percent", &percent)]),
+                ));
+            }
+            Action::ToggleEyedropper => {
+                if self.eyedropper_active {
+                    "osd.eyedropper_on"
+                } else {
+                    "osd.eyedropper_off"
+                }
+            }
+            Action::ToggleCheckerboard => {
+                if self.config.checkerboard_background {
+                    "osd.checkerboard_on"
+                } else {
+                    "osd.checkerboard_off"
+                }
+            }
+            Action::ToggleThirds => {
+                if self.config.overlay_thirds {
+                    "osd.thirds_on"
+                } else {
+                    "osd.thirds_off"
+                }
+            }
+            Action::ToggleGrid => {
+                if self.config.overlay_grid {
+                    "osd.grid_on"
+                } else {
+                    "osd.grid_off"
+                }
+            }
+            Action::ToggleCrosshair => {
+                if self.config.overlay_crosshair {
+                    "osd.crosshair_on"
+                } else {
+                    "osd.crosshair_off"
+                }
+            }
+            Action::ToggleZoomSync => {
+                if self.zoom_sync {
+                    "osd.zoom_sync_on"
+                } else {
+                    "osd.zoom_sync_off"
+                }
+            }
+            Action::TogglePlaylistShuffle => {
+                if self.config.playlist_shuffle {
+                    "osd.shuffle_on"
+                } else {
+                    "osd.shuffle_off"
+                }
+            }
+            Action::CyclePlaylistRepeat => match self.config.playlist_repeat {
+                PlaylistRepeat::Off => "osd.repeat_off",
+                PlaylistRepeat::All => "osd.repeat_all",
+                PlaylistRepeat::One => "osd.repeat_one",
+            },
+            _ => return None,
+        };
+        Some(("view", i18n::tr(key).to_string()))
     }
 
-    fn request_app_exit(&mut self) {
-        if self.has_marked_files() {
-            self.pending_exit_confirmation = true;
-            self.file_action_menu = None;
-            self.show_controls = true;
-            self.controls_show_time = Instant::now();
-        } else {
-            self.should_exit = true;
-        }
+    /// Polls for GPU device loss about once a second and relaunches on the current file
+    /// when the context is gone.
+    /// Re-resolves the overlay theme every few seconds, so `overlay_theme = auto` follows a
+    /// change of the Windows app theme.
+    fn refresh_overlay_theme(&mut self, ctx: &egui::Context) {
+        if self
+            .overlay_theme_checked_at
+            .is_some_and(|at| at.elapsed() < Duration::from_secs(2))
+        {
+            return;
+        }
+        self.overlay_theme_checked_at = Some(Instant::now());
+        let system_light =
+            self.config.overlay_theme == OverlayThemeMode::Auto && theme::system_prefers_light();
+        let theme = theme::OverlayTheme::resolve(&self.config, system_light);
+        if theme != self.overlay_theme {
+            self.overlay_theme = theme;
+            ctx.request_repaint();
+        }
+    }
+
+    /// Shows the title bar and video bar while Tab moves keyboard focus through them, so
+    /// they can be reached without the mouse.
+    fn keep_overlays_for_keyboard_focus(&mut self, ctx: &egui::Context) {
+        let tab_pressed =
+            !ctx.wants_keyboard_input() && ctx.input(|input| input.key_pressed(egui::Key::Tab));
+        if tab_pressed || accessibility::control_has_focus(ctx) {
+            self.show_controls = true;
+            self.controls_show_time = Instant::now();
+            self.touch_bottom_overlays();
+        }
+    }
+
+    /// Reasons to ask before quitting, one sentence each. Marked files always count; unsaved
+    /// work and a playing video only with `confirm_exit`.
+    fn exit_confirmation_reasons(&self) -> Vec<String> {
+        let mut reasons = Vec::new();
+        let marked_count = self.marked_files.len();
+        if marked_count == 1 {
+            reasons.push("One file is still marked. Exiting now will discard the current marked, cut, and copy preparation state.".to_string());
+        } else if marked_count > 1 {
+            reasons.push(format!(
+                "{} files are still marked. Exiting now will discard the current marked, cut, and copy preparation state.",
+                marked_count
+            ));
+        }
+        if !self.config.confirm_exit {
+            return reasons;
+        }
+        match self.annotations.unsaved_count() {
+            0 => {}
+            1 => reasons.push("One image has unsaved annotations.".to_string()),
+            count => reasons.push(format!("{count} images have unsaved annotations.")),
+        }
+        if !self.image_adjustments.is_identity() {
+            reasons.push("The current adjustments have not been exported.".to_string());
+        }
+        if self
+            .video_player
+            .as_ref()
+            .is_some_and(|player| player.is_playing())
+        {
+            reasons.push("A video is playing.".to_string());
+        }
+        reasons
+    }
+
+    fn request_app_exit(&mut self) {
+        if !self.exit_confirmation_reasons().is_empty() {
+            self.pending_exit_confirmation = true;
+            self.file_action_menu = None;
+            self.show_controls = true;
+            self.controls_show_time = Instant::now();
+        } else {
+            self.should_exit = true;
+        }
     }
 
     fn prepared_clipboard_operation_for_path(&self, path: &Path) -> Option<FileClipboardOperation> {
@@ -5119,7 +6083,7 @@ impl ImageViewer {
         }
         self.last_missing_media_refresh_check = Instant::now();
 
-        if self.pending_media_directory_scan.is_some() {
+        if self.pending_media_directory_scan.is_some() || self.playlist.is_some() {
             return;
         }
 
@@ -5291,7 +6255,13 @@ impl ImageViewer {
         self.pending_media_directory_scan_kind = None;
         self.pending_media_directory_started_at = None;
 
-        let files = get_media_in_directory(&directory);
+        let Some(files) = get_media_in_directory_bounded(&directory) else {
+            self.error_message = Some(i18n::tr_args(
+                "error.folder_timed_out",
+                &[("path", &directory.display())],
+            ));
+            return;
+        };
         let modified_at = std::fs::metadata(&directory)
             .ok()
             .and_then(|metadata| metadata.modified().ok());
@@ -5302,7 +6272,14 @@ impl ImageViewer {
                 files,
                 modified_at,
             });
-        self.set_image_list(files);
+        if let Some(playlist) = self.playlist.as_mut() {
+            // Files removed from disk leave the queue; the folder does not replace it.
+            playlist.retain_existing();
+            let items = playlist.items().to_vec();
+            self.set_image_list_raw(items);
+        } else {
+            self.set_image_list(files);
+        }
         self.clear_stale_marked_files();
         self.clear_stale_prepared_clipboard_paths();
         self.modal_thumbnail_cache.retain(|path, _| path.exists());
@@ -5387,6 +6364,9 @@ impl ImageViewer {
             "Mark"
         });
         labels.extend(["Cut", "Copy", "Delete", "Rename", "Open file location"]);
+        if self.can_open_index_in_new_window(target_index) {
+            labels.push("Open in new window");
+        }
 
         let has_marked_paths = !self.collect_marked_paths_in_current_order().is_empty();
         if has_marked_paths {
@@ -5655,6 +6635,20 @@ impl ImageViewer {
             return;
         }
 
+        if let Err(err) = self.apply_path_renames(&changed_paths) {
+            if let Some(rename_state) = self.rename_overlay.as_mut() {
+                rename_state.error_message = Some(err);
+            }
+            return;
+        }
+        self.rename_overlay = None;
+    }
+
+    /// Renames every `(original, new)` pair through temporary names, so swaps and rotations
+    /// within the set work, and rolls everything back if any step fails. Afterwards marks,
+    /// the prepared clipboard and the caches follow the new names and the media list is
+    /// refreshed with the current item kept selected.
+    fn apply_path_renames(&mut self, changed_paths: &[(PathBuf, PathBuf)]) -> Result<(), String> {
         let current_path_before = self.current_media_path();
         let staged_paths: Vec<(PathBuf, PathBuf, PathBuf)> = changed_paths
             .iter()
@@ -5675,11 +6669,7 @@ impl ImageViewer {
                         let _ = fs::rename(rollback_temp, rollback_original);
                     }
                 }
-
-                if let Some(rename_state) = self.rename_overlay.as_mut() {
-                    rename_state.error_message = Some(format!("Rename failed: {err}"));
-                }
-                return;
+                return Err(format!("Rename failed: {err}"));
             }
         }
 
@@ -5695,18 +6685,14 @@ impl ImageViewer {
                         let _ = fs::rename(rollback_temp, rollback_original);
                     }
                 }
-
-                if let Some(rename_state) = self.rename_overlay.as_mut() {
-                    rename_state.error_message = Some(format!("Rename failed: {err}"));
-                }
-                return;
+                return Err(format!("Rename failed: {err}"));
             }
 
             completed_final_paths.push((original_path.clone(), new_path.clone()));
         }
 
         let mut prepared_clipboard_changed = false;
-        for (original_path, new_path) in &changed_paths {
+        for (original_path, new_path) in changed_paths {
             if self.marked_files.remove(original_path) {
                 self.marked_files.insert(new_path.clone());
             }
@@ -5722,7 +6708,6 @@ impl ImageViewer {
             self.sync_prepared_clipboard_with_system();
         }
 
-        self.rename_overlay = None;
         self.modal_thumbnail_cache.clear();
 
         let renamed_current = current_path_before.as_ref().and_then(|current_path| {
@@ -5741,6 +6726,7 @@ impl ImageViewer {
         } else {
             self.refresh_media_list_after_path_mutation(current_path_before);
         }
+        Ok(())
     }
 
     fn collect_keyboard_file_action_targets(&self) -> Vec<PathBuf> {
@@ -5798,6 +6784,13 @@ impl ImageViewer {
                 }
             }
             self.set_prepared_clipboard_targets(&paths, operation);
+            let key = if operation == FileClipboardOperation::Cut {
+                "osd.cut"
+            } else {
+                "osd.copied"
+            };
+            let files = file_count_label(paths.len());
+            self.show_action_osd("clipboard", i18n::tr_args(key, &[("files", &files)]));
         }
     }
 
@@ -5933,61 +6926,14 @@ impl ImageViewer {
                 continue;
             }
 
-            let file_name = match source_path.file_name() {
-                Some(name) => name,
-                None => continue,
-            };
-
-            let mut dest_path = target_directory.join(file_name);
-            let mut suffix = 1;
-            while dest_path.exists() {
-                let stem = source_path
-                    .file_stem()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("file");
-                let ext = source_path.extension().and_then(|e| e.to_str());
-                let new_name = if let Some(ext) = ext {
-                    format!("{} ({}).{}", stem, suffix, ext)
-                } else {
-                    format!("{} ({})", stem, suffix)
-                };
-                dest_path = target_directory.join(&new_name);
-                suffix += 1;
-                if suffix > 1000 {
-                    break;
-                }
+            if source_path.file_name().is_none() {
+                continue;
             }
 
-            match operation {
-                FileClipboardOperation::Copy => match std::fs::copy(source_path, &dest_path) {
-                    Ok(_) => new_paths.push(dest_path),
-                    Err(err) => errors.push(format!(
-                        "Failed to copy '{}': {}",
-                        file_name.to_string_lossy(),
-                        err
-                    )),
-                },
-                FileClipboardOperation::Cut => match std::fs::rename(source_path, &dest_path) {
-                    Ok(_) => new_paths.push(dest_path.clone()),
-                    Err(_) => match std::fs::copy(source_path, &dest_path) {
-                        Ok(_) => {
-                            if let Err(e) = std::fs::remove_file(source_path) {
-                                errors.push(format!(
-                                    "Copied '{}' but failed to remove original: {}",
-                                    file_name.to_string_lossy(),
-                                    e
-                                ));
-                            } else {
-                                new_paths.push(dest_path.clone());
-                            }
-                        }
-                        Err(copy_err) => errors.push(format!(
-                            "Failed to move '{}': {}",
-                            file_name.to_string_lossy(),
-                            copy_err
-                        )),
-                    },
-                },
+            let dest_path = free_destination_path(&target_directory, source_path);
+            match transfer_file(source_path, &dest_path, operation) {
+                Ok(()) => new_paths.push(dest_path),
+                Err(err) => errors.push(err),
             }
         }
 
@@ -6009,6 +6955,77 @@ impl ImageViewer {
             self.refresh_media_list_after_path_mutation(preferred_anchor);
         }
     }
+    /// Moves or copies the current file into `sort_folder_<slot + 1>` and goes on to the next
+    /// file, for triaging a folder with one key per destination.
+    fn sort_current_file_to_folder(&mut self, slot: usize) {
+        let Some(target_directory) = self.config.sort_folders.get(slot).cloned().flatten() else {
+            self.error_message = Some(i18n::tr_args(
+                "error.sort_folder_unset",
+                &[("slot", &(slot + 1))],
+            ));
+            return;
+        };
+        let Some(source_path) = self.current_media_path().filter(|path| path.is_file()) else {
+            return;
+        };
+        if source_path.parent() == Some(target_directory.as_path()) {
+            return;
+        }
+        if let Err(err) = fs::create_dir_all(&target_directory) {
+            self.error_message = Some(i18n::tr_args(
+                "error.create_folder",
+                &[("path", &target_directory.display()), ("error", &err)],
+            ));
+            return;
+        }
+        let dest_path = free_destination_path(&target_directory, &source_path);
+        let transfer = [(source_path.clone(), dest_path.clone())];
+
+        if self.config.sort_operation == SortOperation::Copy {
+            match transfer_file(&source_path, &dest_path, FileClipboardOperation::Copy) {
+                Ok(()) => {
+                    batch_rename::copy_companion_files(&transfer);
+                    self.ratings.forget_directory(&target_directory);
+                    self.next_image();
+                }
+                Err(err) => self.error_message = Some(err),
+            }
+            return;
+        }
+
+        let removed_paths = HashSet::from([source_path.clone()]);
+        let fallback_path = self.choose_fallback_path_after_removal(&removed_paths);
+        self.release_video_resources_for_paths(std::slice::from_ref(&source_path));
+        if let Err(err) = transfer_file(&source_path, &dest_path, FileClipboardOperation::Cut) {
+            self.error_message = Some(err);
+            return;
+        }
+        batch_rename::move_companion_files(&transfer);
+        if let Some(directory) = source_path.parent() {
+            self.ratings.forget_directory(directory);
+        }
+        self.ratings.forget_directory(&target_directory);
+        self.annotations.rename_path(&source_path, &dest_path);
+        self.marked_files.remove(&source_path);
+        if self.clear_prepared_clipboard_for_path(&source_path) {
+            self.sync_prepared_clipboard_with_system();
+        }
+        self.modal_thumbnail_cache.remove(&source_path);
+
+        if self.manga_mode {
+            self.refresh_media_list_after_path_mutation(fallback_path);
+        } else if let Some(path) = fallback_path {
+            self.refresh_media_list_after_path_mutation(Some(path.clone()));
+            if self.image_list.iter().any(|candidate| candidate == &path) {
+                self.load_media(&path);
+            } else {
+                self.clear_current_media_after_all_files_removed();
+            }
+        } else {
+            self.clear_current_media_after_all_files_removed();
+        }
+    }
+
     fn perform_delete_targets(&mut self, paths: Vec<PathBuf>) {
         let existing_paths: Vec<PathBuf> = paths.into_iter().filter(|path| path.exists()).collect();
         if existing_paths.is_empty() {
@@ -6027,6 +7044,11 @@ impl ImageViewer {
 
         match move_paths_to_recycle_bin(&existing_paths) {
             Ok(()) => {
+                let files = file_count_label(existing_paths.len());
+                self.show_action_osd(
+                    "delete",
+                    i18n::tr_args("osd.recycled", &[("files", &files)]),
+                );
                 let mut prepared_clipboard_changed = false;
                 for path in &existing_paths {
                     self.marked_files.remove(path);
@@ -6213,7 +7235,7 @@ impl ImageViewer {
                 let (texture, dims) = if let Some(tex) = self.video_texture.as_ref() {
                     let d = self.video_texture_dims.or_else(|| {
                         self.video_player.as_ref().and_then(|player| {
-                            let dims = player.dimensions();
+                            let dims = player.display_dimensions();
                             (dims.0 > 0 && dims.1 > 0).then_some(dims)
                         })
                     })?;
@@ -6749,9 +7771,8 @@ impl ImageViewer {
                     .map(|state| state.zoom.max(state.zoom_target))
             };
 
-            saved_zoom.unwrap_or_else(|| {
-                self.fit_zoom_for_target_bounds(viewport, egui::vec2(img_w, img_h))
-            })
+            saved_zoom
+                .unwrap_or_else(|| self.fullscreen_fit_zoom(viewport, egui::vec2(img_w, img_h)))
         } else {
             self.floating_layout_size_for_media(img_w, img_h, viewport)
                 .map(|(zoom, _)| zoom)
@@ -6803,6 +7824,25 @@ impl ImageViewer {
         self.solo_quantize_target_texture_side(target, source_dims)
     }
 
+    /// Texture side for frames of the solo video at `path`. Like the image LOD, it grows with
+    /// the live zoom once the video is showing, so zooming in does not magnify a frame that was
+    /// scaled down for the fit size.
+    fn solo_video_target_texture_side(&self, path: &PathBuf) -> u32 {
+        let fit_side = self.solo_target_texture_side_for_path(path, MediaType::Video, false);
+        if self.current_video_path.as_ref() != Some(path) || self.video_player.is_none() {
+            return fit_side;
+        }
+        let Some(display_size) = self.image_display_size_at_zoom() else {
+            return fit_side;
+        };
+        let source_dims = self.solo_known_media_dimensions(path, MediaType::Video, false);
+        let zoomed_side = self.solo_quantize_target_texture_side(
+            self.manga_strip_target_texture_side_from_display_side(display_size.max_elem()),
+            source_dims,
+        );
+        fit_side.max(zoomed_side)
+    }
+
     fn solo_image_load_texture_side(target_lod_side: u32, max_texture_side: u32) -> u32 {
         let max_texture_side = max_texture_side.max(1);
         if target_lod_side > 0 {
@@ -6915,13 +7955,10 @@ impl ImageViewer {
     }
 
     fn solo_current_display_min_side(&self) -> f32 {
-        let current_display = if matches!(self.current_media_type, Some(MediaType::Image)) {
+        let current_display = if matches!(self.current_media_type, Some(MediaType::Image))
+            || self.media_display_dimensions().is_some()
+        {
             self.image_display_size_at_zoom()
-        } else if let Some((width, height)) = self.media_display_dimensions() {
-            Some(egui::vec2(
-                width as f32 * self.zoom.max(0.0001),
-                height as f32 * self.zoom.max(0.0001),
-            ))
         } else {
             self.image_list.get(self.current_index).and_then(|path| {
                 self.current_media_type.map(|media_type| {
@@ -7785,6 +8822,21 @@ impl ImageViewer {
         };
         text.push_str(&decode_text);
 
+        if let Some(player) = self.video_player.as_ref() {
+            let stats = player.frame_pacing_stats();
+            text.push_str(&format!(
+                " | VID shown {} dropped {} late {}{}",
+                stats.presented,
+                stats.dropped,
+                stats.late,
+                if self.config.video_frame_pacing {
+                    " (paced)"
+                } else {
+                    ""
+                }
+            ));
+        }
+
         if self.manga_mode {
             if let Some((p50, p95, samples)) = self.manga_ttv_percentiles_ms() {
                 text.push_str(&format!(
@@ -8352,6 +9404,10 @@ impl ImageViewer {
         egui::Id::new("solo_video_audio_tracks_popup")
     }
 
+    fn solo_video_frame_guides_popup_id() -> egui::Id {
+        egui::Id::new("solo_video_frame_guides_popup")
+    }
+
     fn solo_video_subtitle_popup_id() -> egui::Id {
         egui::Id::new("solo_video_subtitle_tracks_popup")
     }
@@ -8838,6 +9894,15 @@ impl ImageViewer {
                     stroke,
                 );
             }
+            VideoControlIcon::FrameGuides => {
+                let outer = egui::Rect::from_center_size(
+                    rect.center(),
+                    egui::vec2(rect.width() - 1.0, rect.height() - 5.0),
+                );
+                painter.rect_stroke(outer, 2.0, egui::Stroke::new(1.4, color));
+                let inner = egui::Rect::from_center_size(outer.center(), outer.size() * 0.55);
+                painter.rect_stroke(inner, 0.0, egui::Stroke::new(1.0, color));
+            }
             _ => {}
         }
     }
@@ -8876,6 +9941,7 @@ impl ImageViewer {
         );
 
         let (rect, response) = ui.allocate_exact_size(desired_size, egui::Sense::click());
+        accessibility::describe_button(&response, tooltip);
         let visuals = if !ui.is_enabled() {
             &ui.visuals().widgets.noninteractive
         } else if response.is_pointer_button_down_on() || active {
@@ -8919,7 +9985,9 @@ impl ImageViewer {
     ) -> egui::Response {
         if matches!(
             icon,
-            VideoControlIcon::AudioTracks | VideoControlIcon::SubtitleTracks
+            VideoControlIcon::AudioTracks
+                | VideoControlIcon::SubtitleTracks
+                | VideoControlIcon::FrameGuides
         ) {
             return Self::video_control_vector_icon_button(ui, icon, tooltip, label, active);
         }
@@ -8931,7 +9999,9 @@ impl ImageViewer {
             VideoControlIcon::VolumeOff => "\u{1F507}",
             VideoControlIcon::Previous => "\u{23EE}",
             VideoControlIcon::Next => "\u{23ED}",
-            VideoControlIcon::AudioTracks | VideoControlIcon::SubtitleTracks => "",
+            VideoControlIcon::AudioTracks
+            | VideoControlIcon::SubtitleTracks
+            | VideoControlIcon::FrameGuides => "",
         };
 
         let button_text = label.filter(|text| !text.is_empty()).map_or_else(
@@ -8939,8 +10009,9 @@ impl ImageViewer {
             |text| format!("{} {}", icon_text, text),
         );
 
-        ui.add(egui::Button::new(button_text).min_size(egui::vec2(32.0, 24.0)))
-            .on_hover_text(tooltip)
+        let response = ui.add(egui::Button::new(button_text).min_size(egui::vec2(32.0, 24.0)));
+        accessibility::describe_button(&response, tooltip);
+        response.on_hover_text(tooltip)
     }
 
     fn draw_audio_track_popup(
@@ -8997,6 +10068,59 @@ impl ImageViewer {
         selected_track
     }
 
+    /// Safe-area toggle and aspect mask choices for the frame guides button. Returns whether
+    /// anything changed.
+    fn draw_frame_guides_popup(
+        ui: &mut egui::Ui,
+        popup_id: egui::Id,
+        button_response: &egui::Response,
+        safe_areas: &mut bool,
+        aspect_mask: &mut Option<f32>,
+    ) -> bool {
+        let mut changed = false;
+        let close_on_click_outside = egui::popup::PopupCloseBehavior::CloseOnClickOutside;
+
+        let _ = egui::popup::popup_below_widget(
+            ui,
+            popup_id,
+            button_response,
+            close_on_click_outside,
+            |ui| {
+                ui.set_min_width(200.0);
+
+                changed |= ui
+                    .checkbox(safe_areas, "Safe areas (action / title)")
+                    .changed();
+                ui.add_space(4.0);
+                ui.label(egui::RichText::new("Aspect mask").weak());
+
+                let options = std::iter::once(("Off".to_string(), None)).chain(
+                    video_guides::ASPECT_PRESETS
+                        .iter()
+                        .map(|(label, ratio)| (label.to_string(), Some(*ratio))),
+                );
+                for (label, ratio) in options {
+                    let is_selected = *aspect_mask == ratio;
+                    let row = ui.selectable_label(
+                        is_selected,
+                        Self::popup_track_row_label(is_selected, &label),
+                    );
+                    if row.clicked() {
+                        if !is_selected {
+                            *aspect_mask = ratio;
+                            changed = true;
+                        }
+                        ui.memory_mut(|mem| mem.close_popup());
+                    }
+                }
+
+                ui.rect_contains_pointer(ui.min_rect())
+            },
+        );
+
+        changed
+    }
+
     fn draw_subtitle_track_popup(
         ui: &mut egui::Ui,
         popup_id: egui::Id,
@@ -9176,7 +10300,7 @@ impl ImageViewer {
 
         // Track whether the pointer is currently over the bottom video controls region.
         // (Used for input suppression and for keeping overlays alive while hovering.)
-        let bar_height = 56.0;
+        let bar_height = self.overlay_theme.video_bar_height;
         let over_controls_bar = mouse_pos
             .map(|p| p.y > screen_rect.height() - bar_height)
             .unwrap_or(false);
@@ -9234,6 +10358,11 @@ impl ImageViewer {
             || self.mouse_over_video_controls
             || self.file_action_menu.is_some()
             || self.any_modal_dialog_open()
+            || self.annotation_mode_active()
+            || self.straighten_mode_active()
+            || self.perspective_mode_active()
+            || self.eyedropper_mode_active()
+            || self.free_rotate_held
         {
             return true;
         }
@@ -9242,8 +10371,17 @@ impl ImageViewer {
             return false;
         };
 
+        if self
+            .adjustments_panel_rect
+            .or(self.playlist_panel_rect)
+            .or(self.video_resume_offer_rect)
+            .is_some_and(|rect| rect.contains(pos))
+        {
+            return true;
+        }
+
         if self.show_video_controls {
-            let bar_height = 56.0;
+            let bar_height = self.overlay_theme.video_bar_height;
             if pos.y > screen_rect.height() - bar_height {
                 return true;
             }
@@ -9256,7 +10394,7 @@ impl ImageViewer {
         let scrollbar_padding = Self::BOTTOM_RIGHT_OVERLAY_SCROLLBAR_PADDING;
         let margin = Self::BOTTOM_RIGHT_OVERLAY_MARGIN;
         let video_controls_offset = if self.show_video_controls {
-            56.0 + 8.0
+            self.overlay_theme.video_bar_height + 8.0
         } else {
             0.0
         };
@@ -9359,7 +10497,18 @@ impl ImageViewer {
             .min(self.max_zoom_factor())
     }
 
-    fn fit_zoom_for_target_bounds(&self, target_size: egui::Vec2, media_size: egui::Vec2) -> f32 {
+    /// The area fullscreen fits media into: the monitor, or the viewport if it is larger.
+    fn fullscreen_target_bounds(&self, ctx: &egui::Context) -> egui::Vec2 {
+        let viewport_bounds = ctx.screen_rect().size().max(egui::vec2(1.0, 1.0));
+        let monitor = self.monitor_size_points(ctx);
+        egui::vec2(
+            monitor.x.max(viewport_bounds.x),
+            monitor.y.max(viewport_bounds.y),
+        )
+    }
+
+    /// Fullscreen zoom for `media_size` in `target_size` under `fullscreen_fit_mode`.
+    fn fullscreen_fit_zoom(&self, target_size: egui::Vec2, media_size: egui::Vec2) -> f32 {
         if target_size.x <= 0.0
             || target_size.y <= 0.0
             || media_size.x <= 0.0
@@ -9368,11 +10517,69 @@ impl ImageViewer {
             return 1.0;
         }
 
-        let fit_x = target_size.x / media_size.x;
-        let fit_y = target_size.y / media_size.y;
+        self.config
+            .fullscreen_fit_mode
+            .zoom_for(target_size, media_size)
+            .max(0.0001)
+            .min(self.max_zoom_factor())
+    }
+
+    /// Fit mode of the current window mode (fullscreen or floating).
+    fn active_fit_mode(&self) -> FitMode {
+        if self.is_fullscreen {
+            self.config.fullscreen_fit_mode
+        } else {
+            self.config.floating_fit_mode
+        }
+    }
+
+    /// Switches the current window mode to `mode`, saves it and re-lays out the image.
+    fn set_fit_mode(&mut self, mode: FitMode) {
+        if self.is_fullscreen {
+            self.config.fullscreen_fit_mode = mode;
+            self.clear_current_fullscreen_view_memory();
+        } else {
+            self.config.floating_fit_mode = mode;
+        }
+        self.pending_idle_config_sync = true;
+        self.offset = egui::Vec2::ZERO;
+        self.zoom_velocity = 0.0;
+        self.pending_media_layout = true;
+    }
+
+    /// Moves to the next `zoom_presets` step. Fit re-runs the layout of the current fit mode;
+    /// percentages zoom around the view center.
+    fn cycle_zoom_presets(&mut self) {
+        let Some(preset) = ZoomPreset::next_in(&self.config.zoom_presets, self.zoom) else {
+            return;
+        };
+        self.zoom_velocity = 0.0;
+        match preset {
+            ZoomPreset::Fit => {
+                if self.is_fullscreen {
+                    self.clear_current_fullscreen_view_memory();
+                }
+                self.offset = egui::Vec2::ZERO;
+                self.pending_media_layout = true;
+            }
+            ZoomPreset::Zoom(zoom) => self.set_zoom_level(zoom),
+        }
+    }
 
-        // Fit to whichever axis is limiting first.
-        fit_x.min(fit_y).max(0.0001).min(self.max_zoom_factor())
+    /// Zooms to `zoom` (1.0 = 100%) around the view center. Fullscreen switches at once; a
+    /// floating window animates to it and resizes around the image as with wheel zoom.
+    fn set_zoom_level(&mut self, zoom: f32) {
+        let zoom = self.clamp_zoom(zoom);
+        self.zoom_velocity = 0.0;
+        if self.is_fullscreen {
+            self.offset *= zoom / self.zoom;
+            self.zoom = zoom;
+            self.zoom_target = zoom;
+            self.remember_current_fullscreen_view_state();
+            self.maybe_refresh_current_solo_image_lod();
+        } else {
+            self.zoom_target = zoom;
+        }
     }
 
     fn startup_ready_to_show(&self) -> bool {
@@ -9429,67 +10636,25 @@ impl ImageViewer {
         self.needs_repaint = true;
     }
 
-    fn text_needs_cjk_fonts(text: &str) -> bool {
-        // Check common CJK Unicode blocks (Han, Hiragana, Katakana, Hangul).
-        text.chars().any(|ch| {
-            let c = ch as u32;
-            (0x3400..=0x4DBF).contains(&c) // CJK Unified Ideographs Extension A
-                || (0x4E00..=0x9FFF).contains(&c) // CJK Unified Ideographs
-                || (0xF900..=0xFAFF).contains(&c) // CJK Compatibility Ideographs
-                || (0x3040..=0x309F).contains(&c) // Hiragana
-                || (0x30A0..=0x30FF).contains(&c) // Katakana
-                || (0x31F0..=0x31FF).contains(&c) // Katakana Phonetic Extensions
-                || (0x1100..=0x11FF).contains(&c) // Hangul Jamo
-                || (0xAC00..=0xD7AF).contains(&c) // Hangul Syllables
-        })
-    }
-
-    fn path_needs_cjk_fonts(path: &Path) -> bool {
-        Self::text_needs_cjk_fonts(path.as_os_str().to_string_lossy().as_ref())
-    }
-
-    fn ensure_windows_cjk_fonts_if_needed(&mut self, ctx: &egui::Context) {
-        #[cfg(target_os = "windows")]
-        {
-            if self.windows_cjk_fonts_installed {
-                return;
-            }
-
-            if let Some(rx) = self.pending_windows_cjk_font_load.as_ref() {
-                match rx.try_recv() {
-                    Ok(font_data) => {
-                        self.pending_windows_cjk_font_load = None;
-                        let _ = apply_windows_cjk_fonts(ctx, font_data);
-                        self.windows_cjk_fonts_installed = true;
-                        self.needs_repaint = true;
-                        return;
-                    }
-                    Err(crossbeam_channel::TryRecvError::Empty) => return,
-                    Err(crossbeam_channel::TryRecvError::Disconnected) => {
-                        self.pending_windows_cjk_font_load = None;
-                        self.windows_cjk_fonts_installed = true;
-                        return;
-                    }
-                }
-            }
-
-            let Some(path) = self.image_list.get(self.current_index) else {
-                return;
-            };
-
-            // Include parent directories, not just filename: breadcrumbs and child-folder popups
-            // can contain CJK even when the current file name is ASCII.
-            if Self::path_needs_cjk_fonts(path.as_path()) {
-                let (tx, rx) = crossbeam_channel::bounded::<Vec<(String, Vec<u8>)>>(1);
-                self.pending_windows_cjk_font_load = Some(rx);
-                crate::async_runtime::spawn_blocking_or_thread(
-                    "windows-cjk-font-load",
-                    move || {
-                        let _ = tx.send(load_windows_cjk_font_data());
-                    },
-                );
+    /// Loads fallback fonts for scripts in the current path (parents included, for the
+    /// breadcrumbs), in rename drafts being typed and in the UI language, then installs them
+    /// once loaded.
+    fn ensure_fallback_fonts(&mut self, ctx: &egui::Context) {
+        if self.font_fallback.poll(ctx) {
+            self.needs_repaint = true;
+        }
+        if let Some(path) = self.image_list.get(self.current_index) {
+            self.font_fallback
+                .request_for_text(path.as_os_str().to_string_lossy().as_ref());
+        }
+        if let Some(rename) = self.rename_overlay.as_ref() {
+            for item in &rename.items {
+                self.font_fallback.request_for_text(&item.draft_name);
             }
         }
+        // Japanese and Chinese UI text needs the CJK fonts before any message shows.
+        self.font_fallback
+            .request_for_text(i18n::tr("toast.slideshow_stopped"));
     }
 
     fn in_floating_mode(&self) -> bool {
@@ -9548,21 +10713,14 @@ impl ImageViewer {
         Self::title_char_budget_from_width(available_width, FALLBACK_CHARS)
     }
 
+    /// The end of `text` that fits in `char_count` columns. Counts in [`emoji::width`]
+    /// columns and keeps multi-code-point emoji whole.
     fn take_last_chars(text: &str, char_count: usize) -> String {
-        if char_count == 0 {
-            return String::new();
-        }
-
-        let total_chars = text.chars().count();
-        if total_chars <= char_count {
-            return text.to_string();
-        }
-
-        text.chars().skip(total_chars - char_count).collect()
+        emoji::suffix_within(text, char_count).to_string()
     }
 
     fn truncate_with_prefix_ellipsis(text: &str, max_chars: usize) -> String {
-        if text.chars().count() <= max_chars {
+        if emoji::width(text) <= max_chars {
             return text.to_string();
         }
 
@@ -9575,7 +10733,7 @@ impl ImageViewer {
     }
 
     fn truncate_with_suffix_ellipsis(text: &str, max_chars: usize) -> String {
-        if text.chars().count() <= max_chars {
+        if emoji::width(text) <= max_chars {
             return text.to_string();
         }
 
@@ -9583,12 +10741,12 @@ impl ImageViewer {
             return "...".chars().take(max_chars).collect();
         }
 
-        let prefix: String = text.chars().take(max_chars - 3).collect();
+        let prefix = emoji::prefix_within(text, max_chars - 3);
         format!("{}...", prefix)
     }
 
     fn truncate_path_for_window_title(path_text: &str, max_chars: usize) -> String {
-        if path_text.chars().count() <= max_chars {
+        if emoji::width(path_text) <= max_chars {
             return path_text.to_string();
         }
 
@@ -9620,7 +10778,7 @@ impl ImageViewer {
                 format!("{}{}{}", segment, separator, tail)
             };
 
-            if candidate.chars().count() > max_tail_chars {
+            if emoji::width(&candidate) > max_tail_chars {
                 break;
             }
 
@@ -9635,7 +10793,7 @@ impl ImageViewer {
     }
 
     fn truncate_window_title_for_char_budget(&self, title: String, max_chars: usize) -> String {
-        if title.chars().count() <= max_chars {
+        if emoji::width(&title) <= max_chars {
             return title;
         }
 
@@ -9684,7 +10842,7 @@ impl ImageViewer {
     fn delete_modal_item_info(&self, path: &PathBuf) -> DeleteModalItemInfo {
         let display_name = path
             .file_name()
-            .map(|name| name.to_string_lossy().to_string())
+            .map(|name| emoji::display_text(&name.to_string_lossy()).into_owned())
             .unwrap_or_else(|| path.to_string_lossy().to_string());
         let file_size_label =
             Self::file_size_label_for_path(path).unwrap_or_else(|| "Unknown size".to_string());
@@ -10088,6 +11246,26 @@ impl ImageViewer {
                 );
                 painter.circle_filled(egui::pos2(rect.center().x, rect.bottom() - 3.5), 1.3, color);
             }
+            MenuActionIcon::Stats => {
+                let base_y = rect.bottom() - 3.0;
+                for (i, height) in [5.0, 9.0, 7.0].into_iter().enumerate() {
+                    let x = rect.center().x - 4.0 + i as f32 * 4.0;
+                    painter.line_segment(
+                        [egui::pos2(x, base_y), egui::pos2(x, base_y - height)],
+                        stroke,
+                    );
+                }
+            }
+            MenuActionIcon::NewWindow => {
+                let back = egui::Rect::from_min_max(
+                    egui::pos2(rect.left() + 5.0, rect.top() + 2.5),
+                    egui::pos2(rect.right() - 2.5, rect.bottom() - 6.0),
+                );
+                let front = back.translate(egui::vec2(-3.0, 3.5));
+                painter.rect_stroke(back, 2.0, stroke);
+                painter.rect_filled(front, 2.0, egui::Color32::from_rgb(18, 22, 28));
+                painter.rect_stroke(front, 2.0, stroke);
+            }
         }
     }
 
@@ -10260,6 +11438,15 @@ impl ImageViewer {
             activated = true;
         }
 
+        if self.can_open_index_in_new_window(target_index)
+            && self
+                .menu_action_row(ui, "Open in new window", MenuActionIcon::NewWindow)
+                .clicked()
+        {
+            self.open_index_in_new_window(target_index);
+            activated = true;
+        }
+
         activated
     }
 
@@ -10301,6 +11488,13 @@ impl ImageViewer {
                 activated = true;
             }
         }
+        if self
+            .menu_action_row(ui, "Batch Rename Folder", MenuActionIcon::Rename)
+            .clicked()
+        {
+            self.open_batch_rename();
+            activated = true;
+        }
         if self
             .menu_action_row(ui, "Mark All", MenuActionIcon::MarkAll)
             .clicked()
@@ -10358,6 +11552,8 @@ impl ImageViewer {
             Copy,
             Cut,
             Paste,
+            /// Text pasted into the window that is an http(s) URL.
+            OpenUrl(String),
             Delete,
         }
 
@@ -10382,6 +11578,10 @@ impl ImageViewer {
                 .events
                 .iter()
                 .any(|event| matches!(event, egui::Event::Paste(_)));
+            let pasted_url = input.raw.events.iter().find_map(|event| match event {
+                egui::Event::Paste(text) => remote::parse_url(text),
+                _ => None,
+            });
             let saw_ctrl_v_key_event = input.raw.events.iter().any(|event| {
                 matches!(
                     event,
@@ -10394,7 +11594,9 @@ impl ImageViewer {
                 )
             });
 
-            if (shortcut_mod && input.key_pressed(egui::Key::C)) || saw_copy_event {
+            if let Some(url) = pasted_url {
+                Some(MarkedFileShortcut::OpenUrl(url))
+            } else if (shortcut_mod && input.key_pressed(egui::Key::C)) || saw_copy_event {
                 Some(MarkedFileShortcut::Copy)
             } else if (shortcut_mod && input.key_pressed(egui::Key::X)) || saw_cut_event {
                 Some(MarkedFileShortcut::Cut)
@@ -10415,6 +11617,10 @@ impl ImageViewer {
             self.request_paste_marked_files_into_current_folder();
             return true;
         }
+        if let Some(MarkedFileShortcut::OpenUrl(url)) = shortcut {
+            self.load_media(&PathBuf::from(url));
+            return true;
+        }
 
         if self.title_bar_ui_blocking() {
             return false;
@@ -11443,16 +12649,12 @@ impl ImageViewer {
             return;
         }
 
-        let marked_paths = self.collect_marked_paths_in_current_order();
-        let marked_count = marked_paths.len();
-        let summary = if marked_count == 1 {
-            "One file is still marked. Exiting now will discard the current marked, cut, and copy preparation state.".to_string()
+        let title = if self.has_marked_files() {
+            "Exit With Marked Files?"
         } else {
-            format!(
-                "{} files are still marked. Exiting now will discard the current marked, cut, and copy preparation state.",
-                marked_count
-            )
+            "Exit Viewer?"
         };
+        let summary = self.exit_confirmation_reasons().join(" ");
 
         let mut cancel = ctx.input(|input| input.key_pressed(egui::Key::Escape));
         let mut confirm = false;
@@ -11487,7 +12689,7 @@ impl ImageViewer {
                     .inner_margin(egui::Margin::same(18.0))
                     .show(ui, |ui| {
                         ui.label(
-                            egui::RichText::new("Exit With Marked Files?")
+                            egui::RichText::new(title)
                                 .color(egui::Color32::WHITE)
                                 .strong()
                                 .size(18.0),
@@ -11589,6 +12791,9 @@ impl ImageViewer {
             InputBinding::KeyWithAlt(key) => {
                 format!("Alt + {}", Self::key_to_help_label(*key))
             }
+            InputBinding::KeyWithCtrlShift(key) => {
+                format!("Ctrl + Shift + {}", Self::key_to_help_label(*key))
+            }
             InputBinding::MouseLeft => "Left Click".to_string(),
             InputBinding::MouseRight => "Right Click".to_string(),
             InputBinding::MouseMiddle => "Middle Click".to_string(),
@@ -11604,7 +12809,7 @@ impl ImageViewer {
     }
 
     fn action_bindings_help_label(&self, action: Action) -> String {
-        let bindings = self.config.get_bindings(action);
+        let bindings = self.config.bindings_in(self.binding_context(), action);
         if bindings.is_empty() {
             "Unbound".to_string()
         } else {
@@ -11697,31 +12902,70 @@ impl ImageViewer {
         title
     }
 
-    fn draw_shortcuts_help_config_rows(&self, ui: &mut egui::Ui) {
-        let mut actions: Vec<Action> = self.config.action_bindings.keys().copied().collect();
-        actions.sort_by_key(|action| format!("{:?}", action));
-
-        for action in actions {
-            let trigger = self.action_bindings_help_label(action);
-            let title = Self::action_title_for_help(action);
-            Self::draw_shortcuts_help_row(
-                ui,
-                trigger.as_str(),
-                title.as_str(),
-                "Loaded from your user config.ini action bindings.",
-            );
-        }
+    /// Opens the command palette with every action that can run from a single press.
+    fn open_command_palette(&mut self) {
+        self.file_action_menu = None;
+        let help_rows = Self::GENERAL_HELP_ROWS
+            .iter()
+            .chain(Self::MANGA_HELP_ROWS)
+            .chain(Self::MASONRY_HELP_ROWS);
+        let entries = Action::ALL
+            .into_iter()
+            .filter(|action| command_palette::is_runnable(*action))
+            .map(|action| {
+                let (title, detail) = help_rows
+                    .clone()
+                    .find(|(row_action, _, _)| *row_action == action)
+                    .map(|(_, title, detail)| (title.to_string(), detail.to_string()))
+                    .unwrap_or_else(|| (Self::action_title_for_help(action), String::new()));
+                let shortcut = if self.config.get_bindings(action).is_empty() {
+                    String::new()
+                } else {
+                    self.action_bindings_help_label(action)
+                };
+                command_palette::PaletteEntry {
+                    action,
+                    title,
+                    detail,
+                    shortcut,
+                }
+            })
+            .collect();
+        self.command_palette = Some(command_palette::CommandPalette::new(entries));
     }
 
-    fn draw_shortcuts_help_modal(&mut self, ctx: &egui::Context) {
-        if !self.shortcuts_help_modal_open {
+    fn draw_command_palette(&mut self, ctx: &egui::Context) {
+        let Some(palette) = self.command_palette.as_mut() else {
             return;
-        }
+        };
 
-        let mut close_modal = ctx.input(|input| input.key_pressed(egui::Key::Escape));
+        // Taken before the search field sees them: Enter would drop its focus and the
+        // arrows would move its cursor.
+        let (mut close, up, down, enter) = ctx.input_mut(|input| {
+            (
+                input.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+                input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                input.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+            )
+        });
+        let mut matches = palette.matches();
+        if up {
+            palette.move_selection(-1, matches.len());
+        }
+        if down {
+            palette.move_selection(1, matches.len());
+        }
+        let mut run = if enter {
+            matches
+                .get(palette.selected)
+                .map(|&index| palette.entries[index].action)
+        } else {
+            None
+        };
         let screen_rect = ctx.screen_rect();
 
-        egui::Area::new(egui::Id::new("shortcuts_help_backdrop"))
+        egui::Area::new(egui::Id::new("command_palette_backdrop"))
             .fixed_pos(screen_rect.min)
             .order(egui::Order::Foreground)
             .show(ctx, |ui| {
@@ -11733,233 +12977,16 @@ impl ImageViewer {
                 );
             });
 
-        let modal_size = egui::vec2(
-            (screen_rect.width() - 60.0).clamp(560.0, 960.0),
-            (screen_rect.height() - 44.0).clamp(440.0, 780.0),
-        );
-        let modal_pos = screen_rect.center() - modal_size * 0.5;
-        let config_path_label = Config::config_path().display().to_string();
-
-        let general_rows: &[(Action, &'static str, &'static str)] = &[
-            (
-                Action::ToggleFullscreen,
-                "Toggle fullscreen/window mode",
-                "Switch between floating and fullscreen viewer modes.",
-            ),
-            (
-                Action::Exit,
-                "Exit viewer",
-                "Close the app. If files are marked, you will get a confirmation modal.",
-            ),
-            (
-                Action::Pan,
-                "Pan image/video",
-                "Drag the media while in floating/fullscreen view.",
-            ),
-            (
-                Action::SelectArea,
-                "Edge navigation/select-area behavior",
-                "Uses left/right edge right-click zones for previous/next image navigation.",
-            ),
-            (
-                Action::GotoFile,
-                "Toggle fullscreen via media click zone",
-                "When bound to right click, the center media zone toggles fullscreen.",
-            ),
-            (
-                Action::FreehandAutoscroll,
-                "Freehand autoscroll",
-                "Start pointer-anchored autoscroll in solo view.",
-            ),
-            (
-                Action::NextImage,
-                "Next file",
-                "Move to the next file in the current directory list.",
-            ),
-            (
-                Action::PreviousImage,
-                "Previous file",
-                "Move to the previous file in the current directory list.",
-            ),
-            (
-                Action::RotateClockwise,
-                "Rotate clockwise",
-                "Rotate current media by 90 degrees clockwise.",
-            ),
-            (
-                Action::RotateCounterClockwise,
-                "Rotate counterclockwise",
-                "Rotate current media by 90 degrees counterclockwise.",
-            ),
-            (
-                Action::PreciseRotationClockwise,
-                "Precise rotate clockwise",
-                "Apply fine-grained clockwise rotation in fullscreen.",
-            ),
-            (
-                Action::PreciseRotationCounterClockwise,
-                "Precise rotate counterclockwise",
-                "Apply fine-grained counterclockwise rotation in fullscreen.",
-            ),
-            (
-                Action::ZoomIn,
-                "Zoom in",
-                "Zoom current media in floating/fullscreen mode.",
-            ),
-            (
-                Action::ZoomOut,
-                "Zoom out",
-                "Zoom current media in floating/fullscreen mode.",
-            ),
-            (
-                Action::VideoMute,
-                "Mute/unmute video",
-                "Toggle audio mute for the active video player.",
-            ),
-            (
-                Action::VideoPlayPause,
-                "Play/pause video",
-                "Toggle playback for the active video when this action is bound.",
-            ),
-        ];
-
-        let manga_rows: &[(Action, &'static str, &'static str)] = &[
-            (
-                Action::MangaPan,
-                "Pan manga strip",
-                "Drag and pan in fullscreen strip mode.",
-            ),
-            (
-                Action::MangaGotoFile,
-                "Open strip item in solo fullscreen",
-                "Open the hovered strip item directly in solo fullscreen.",
-            ),
-            (
-                Action::MangaFreehandAutoscroll,
-                "Manga freehand autoscroll",
-                "Start manga autoscroll anchored to pointer direction.",
-            ),
-            (Action::MangaPanUp, "Pan up", "Move strip viewport upward."),
-            (
-                Action::MangaPanDown,
-                "Pan down",
-                "Move strip viewport downward.",
-            ),
-            (
-                Action::MangaPreviousImageFit,
-                "Previous fit page",
-                "Smoothly move to previous fitted manga page.",
-            ),
-            (
-                Action::MangaNextImageFit,
-                "Next fit page",
-                "Smoothly move to next fitted manga page.",
-            ),
-            (
-                Action::MangaPreviousImage,
-                "Previous strip file",
-                "Jump to previous file in strip mode.",
-            ),
-            (
-                Action::MangaNextImage,
-                "Next strip file",
-                "Jump to next file in strip mode.",
-            ),
-            (
-                Action::MangaScrollUp,
-                "Wheel scroll up",
-                "Scroll strip content upward.",
-            ),
-            (
-                Action::MangaScrollDown,
-                "Wheel scroll down",
-                "Scroll strip content downward.",
-            ),
-            (
-                Action::MangaZoomIn,
-                "Strip zoom in",
-                "Zoom manga strip thumbnails/layout in.",
-            ),
-            (
-                Action::MangaZoomOut,
-                "Strip zoom out",
-                "Zoom manga strip thumbnails/layout out.",
-            ),
-        ];
-
-        let masonry_rows: &[(Action, &'static str, &'static str)] = &[
-            (
-                Action::MasonryPan,
-                "Pan masonry layout",
-                "Drag/pan in masonry mode.",
-            ),
-            (
-                Action::MasonryGotoFile,
-                "Open masonry item in solo fullscreen",
-                "Open hovered masonry item in solo fullscreen.",
-            ),
-            (
-                Action::MasonryFreehandAutoscroll,
-                "Masonry freehand autoscroll",
-                "Start masonry autoscroll anchored to pointer direction.",
-            ),
-            (
-                Action::MasonryPanUp,
-                "Masonry pan up",
-                "Move masonry viewport upward.",
-            ),
-            (
-                Action::MasonryPanDown,
-                "Masonry pan down",
-                "Move masonry viewport downward.",
-            ),
-            (
-                Action::MasonryPanUp2,
-                "Masonry pan up (fast)",
-                "Move masonry viewport up with increased speed.",
-            ),
-            (
-                Action::MasonryPanDown2,
-                "Masonry pan down (fast)",
-                "Move masonry viewport down with increased speed.",
-            ),
-            (
-                Action::MasonryPanUp3,
-                "Masonry pan up (faster)",
-                "Move masonry viewport up with highest speed tier.",
-            ),
-            (
-                Action::MasonryPanDown3,
-                "Masonry pan down (faster)",
-                "Move masonry viewport down with highest speed tier.",
-            ),
-            (
-                Action::MasonryScrollUp,
-                "Masonry wheel up",
-                "Scroll masonry layout upward.",
-            ),
-            (
-                Action::MasonryScrollDown,
-                "Masonry wheel down",
-                "Scroll masonry layout downward.",
-            ),
-            (
-                Action::MasonryZoomIn,
-                "Masonry zoom in",
-                "Zoom masonry thumbnails/layout in.",
-            ),
-            (
-                Action::MasonryZoomOut,
-                "Masonry zoom out",
-                "Zoom masonry thumbnails/layout out.",
-            ),
-        ];
-
-        let modal_response = egui::Area::new(egui::Id::new("shortcuts_help_modal"))
-            .fixed_pos(modal_pos)
+        let modal_width = (screen_rect.width() - 60.0).clamp(320.0, 620.0);
+        let row_height = 46.0;
+        egui::Area::new(egui::Id::new("command_palette_window"))
             .order(egui::Order::Foreground)
+            .anchor(
+                egui::Align2::CENTER_TOP,
+                egui::vec2(0.0, screen_rect.height() * 0.12),
+            )
             .show(ctx, |ui| {
-                ui.set_min_size(modal_size);
+                ui.set_width(modal_width);
                 egui::Frame::none()
                     .fill(egui::Color32::from_rgba_unmultiplied(16, 23, 31, 252))
                     .stroke(egui::Stroke::new(
@@ -11969,3280 +12996,10013 @@ impl ImageViewer {
                     .rounding(18.0)
                     .inner_margin(egui::Margin::same(18.0))
                     .show(ui, |ui| {
-                        ui.vertical(|ui| {
-                            ui.horizontal(|ui| {
-                                ui.vertical(|ui| {
-                                    ui.label(
-                                        egui::RichText::new("Shortcuts & Features")
-                                            .color(egui::Color32::WHITE)
-                                            .strong()
-                                            .size(22.0),
+                        let search = ui.add(
+                            egui::TextEdit::singleline(&mut palette.query)
+                                .hint_text("Search actions…")
+                                .font(egui::TextStyle::Heading)
+                                .desired_width(f32::INFINITY),
+                        );
+                        if !search.has_focus() {
+                            search.request_focus();
+                        }
+                        if search.changed() {
+                            palette.selected = 0;
+                            matches = palette.matches();
+                        }
+                        ui.add_space(10.0);
+
+                        if matches.is_empty() {
+                            ui.label(egui::RichText::new("No matching actions.").weak());
+                            return;
+                        }
+                        let selected = palette.selected.min(matches.len() - 1);
+                        let scroll_to_selected = palette.scroll_to_selected;
+                        egui::ScrollArea::vertical()
+                            .max_height(row_height * 8.0)
+                            .auto_shrink([false, true])
+                            .show_rows(ui, row_height, matches.len(), |ui, rows| {
+                                for row in rows {
+                                    let entry = &palette.entries[matches[row]];
+                                    let (rect, response) = ui.allocate_exact_size(
+                                        egui::vec2(ui.available_width(), row_height),
+                                        egui::Sense::click(),
                                     );
-                                    ui.add_space(2.0);
-                                    ui.label(
-                                        egui::RichText::new(
-                                            "All bindings below reflect your current config.ini, plus built-in mouse gestures and context menu capabilities.",
-                                        )
-                                        .color(egui::Color32::from_rgb(170, 190, 212))
-                                        .size(12.5),
+                                    if row == selected && scroll_to_selected {
+                                        response.scroll_to_me(None);
+                                    }
+                                    let painter = ui.painter_at(rect);
+                                    if row == selected || response.hovered() {
+                                        let alpha = if row == selected { 44 } else { 22 };
+                                        painter.rect_filled(
+                                            rect.shrink(1.0),
+                                            8.0,
+                                            egui::Color32::from_rgba_unmultiplied(
+                                                62, 138, 222, alpha,
+                                            ),
+                                        );
+                                    }
+                                    painter.text(
+                                        rect.left_top() + egui::vec2(12.0, 6.0),
+                                        egui::Align2::LEFT_TOP,
+                                        &entry.title,
+                                        egui::FontId::proportional(14.0),
+                                        egui::Color32::WHITE,
                                     );
-                                    ui.add_space(4.0);
-                                    ui.label(
-                                        egui::RichText::new(format!(
-                                            "Config source: {}",
-                                            config_path_label
-                                        ))
-                                        .monospace()
-                                        .color(egui::Color32::from_rgb(128, 165, 198))
-                                        .size(11.0),
+                                    painter.text(
+                                        rect.left_top() + egui::vec2(12.0, 26.0),
+                                        egui::Align2::LEFT_TOP,
+                                        &entry.detail,
+                                        egui::FontId::proportional(11.5),
+                                        egui::Color32::from_rgb(150, 170, 192),
                                     );
-                                });
-
-                                ui.with_layout(
-                                    egui::Layout::right_to_left(egui::Align::Center),
-                                    |ui| {
-                                        let close_button = ui.add(
-                                            egui::Button::new(
-                                                egui::RichText::new("Close")
-                                                    .color(egui::Color32::WHITE),
-                                            )
-                                            .min_size(egui::vec2(88.0, 30.0))
-                                            .fill(egui::Color32::from_rgba_unmultiplied(
-                                                255, 255, 255, 24,
-                                            ))
-                                            .stroke(egui::Stroke::new(
-                                                1.0,
-                                                egui::Color32::from_rgba_unmultiplied(
-                                                    255, 255, 255, 56,
-                                                ),
-                                            ))
-                                            .rounding(7.0),
-                                        );
-                                        if close_button.clicked() {
-                                            close_modal = true;
-                                        }
-                                    },
-                                );
+                                    painter.text(
+                                        rect.right_top() + egui::vec2(-12.0, 7.0),
+                                        egui::Align2::RIGHT_TOP,
+                                        &entry.shortcut,
+                                        egui::FontId::monospace(12.0),
+                                        egui::Color32::from_rgb(208, 228, 252),
+                                    );
+                                    if response.clicked() {
+                                        run = Some(entry.action);
+                                    }
+                                }
                             });
+                        palette.scroll_to_selected = false;
+                    });
+            });
 
-                            ui.add_space(10.0);
-                            ui.separator();
-                            ui.add_space(8.0);
+        if run.is_some() {
+            close = true;
+        }
+        if close {
+            self.command_palette = None;
+        }
+        if let Some(action) = run {
+            self.run_action(action);
+        }
+    }
 
-                            egui::ScrollArea::vertical()
-                                .max_height((modal_size.y - 152.0).max(220.0))
-                                .auto_shrink([false, false])
-                                .show(ui, |ui| {
-                                    Self::draw_shortcuts_help_section_header(
-                                        ui,
-                                        "Quick Gestures (Built-in)",
-                                        "These are always available and not tied to configurable action names.",
-                                    );
-                                    Self::draw_shortcuts_help_row(
-                                        ui,
-                                        "Space",
-                                        "Mark/unmark current target",
-                                        "Marks hovered strip/masonry item when available, otherwise the current solo file.",
-                                    );
-                                    Self::draw_shortcuts_help_row(
-                                        ui,
-                                        "Ctrl + Left Click",
-                                        "Toggle mark for current media",
-                                        "Quickly mark/unmark the current media under pointer focus.",
-                                    );
-                                    Self::draw_shortcuts_help_row(
-                                        ui,
-                                        "Ctrl + Right Click",
-                                        "Open file actions context menu",
-                                        "Spawns the right-click file action menu for the current file.",
-                                    );
-                                    Self::draw_shortcuts_help_row(
-                                        ui,
-                                        "Ctrl + Drag (strip/masonry)",
-                                        "Marquee mark selection",
-                                        "Drag a selection box to mark or unmark multiple files in strip and masonry modes.",
-                                    );
-                                    Self::draw_shortcuts_help_row(
-                                        ui,
-                                        "Right Click (center media area)",
-                                        "Toggle GIF/video play-pause",
-                                        "When not consumed by edge navigation or fullscreen actions, center right-click toggles playback.",
-                                    );
-                                    Self::draw_shortcuts_help_row(
-                                        ui,
-                                        "Ctrl + C / Ctrl + X / Delete",
-                                        "Marked-file keyboard actions",
-                                        "Copy, cut, or delete marked files (falls back to current file target when no marks are active).",
-                                    );
-
-                                    ui.add_space(8.0);
-                                    ui.separator();
-
-                                    Self::draw_shortcuts_help_section_header(
-                                        ui,
-                                        "General Viewer Actions",
-                                        "Floating and fullscreen controls for image/video viewing.",
-                                    );
-                                    self.draw_shortcuts_help_action_rows(ui, general_rows);
-
-                                    ui.add_space(8.0);
-                                    ui.separator();
-
-                                    Self::draw_shortcuts_help_section_header(
-                                        ui,
-                                        "Manga Strip Actions",
-                                        "Bindings active in fullscreen strip reading mode.",
-                                    );
-                                    self.draw_shortcuts_help_action_rows(ui, manga_rows);
-
-                                    ui.add_space(8.0);
-                                    ui.separator();
-
-                                    Self::draw_shortcuts_help_section_header(
-                                        ui,
-                                        "Masonry Actions",
-                                        "Bindings active in masonry grid mode.",
-                                    );
-                                    self.draw_shortcuts_help_action_rows(ui, masonry_rows);
+    fn open_quick_filter(&mut self) {
+        if let Some(filter) = self.quick_filter.as_mut() {
+            filter.editing = true;
+            filter.swallow_opening_key = true;
+            return;
+        }
+        let folder = self
+            .current_media_path()
+            .and_then(|path| path.parent().map(Path::to_path_buf));
+        self.quick_filter = Some(quick_filter::QuickFilter::new(
+            folder,
+            self.image_list.clone(),
+        ));
+    }
 
-                                    ui.add_space(8.0);
-                                    ui.separator();
+    /// The filtered folder listing, recording the match count on the filter. Falls back to
+    /// the whole listing when nothing matches so there is always something to show.
+    fn quick_filtered_list(&mut self) -> Vec<PathBuf> {
+        let Some(filter) = self.quick_filter.as_ref() else {
+            return self.image_list.clone();
+        };
+        let query = quick_filter::Query::parse(&filter.text);
+        let source = filter.source.clone();
+        // Folder navigation entries are not media and never match.
+        let media: Vec<PathBuf> = source
+            .iter()
+            .filter(|path| get_media_type(path).is_some())
+            .cloned()
+            .collect();
+        let media_count = media.len();
+        let ratings = &mut self.ratings;
+        let matches: Vec<PathBuf> = media
+            .into_iter()
+            .filter(|path| query.matches(path, |path| ratings.get(path)))
+            .collect();
+        if let Some(filter) = self.quick_filter.as_mut() {
+            filter.media_count = media_count;
+            filter.match_count = matches.len();
+        }
+        if query.is_empty() || matches.is_empty() {
+            source
+        } else {
+            matches
+        }
+    }
 
-                                    Self::draw_shortcuts_help_section_header(
-                                        ui,
-                                        "Menu & Workflow Features",
-                                        "Commands available from context menus and title-bar controls.",
-                                    );
-                                    Self::draw_shortcuts_help_row(
-                                        ui,
-                                        "Right-click menu",
-                                        "Single-file actions",
-                                        "Mark/Unmark, Cut, Copy, Delete, Rename, and Open file location for the selected file.",
-                                    );
-                                    Self::draw_shortcuts_help_row(
-                                        ui,
-                                        "Right-click menu",
-                                        "Marked-file bulk actions",
-                                        "Cut/Copy/Delete/Rename marked files, plus Mark All and Unmark All.",
-                                    );
-                                    Self::draw_shortcuts_help_row(
-                                        ui,
-                                        "Open file location",
-                                        "Reveal file in OS explorer",
-                                        "Selects the file in Windows Explorer (or opens containing folder on other platforms).",
-                                    );
-                                    Self::draw_shortcuts_help_row(
-                                        ui,
-                                        "Three-stripes title-bar menu",
-                                        "Quick command center",
-                                        "Contains current-file actions, marked-file actions, this Help dialog, and Edit config.ini.",
-                                    );
+    /// Re-filters the folder after the query changed. The current item stays when it still
+    /// matches; otherwise the next match in folder order is shown.
+    fn apply_quick_filter(&mut self) {
+        let Some(source) = self
+            .quick_filter
+            .as_ref()
+            .map(|filter| filter.source.clone())
+        else {
+            return;
+        };
+        let files = self.quick_filtered_list();
+        self.show_filtered_list(files, &source);
+    }
 
-                                    ui.add_space(8.0);
-                                    ui.separator();
+    /// Drops the filter and restores the whole folder, keeping the current item.
+    fn clear_quick_filter(&mut self) {
+        let Some(filter) = self.quick_filter.take() else {
+            return;
+        };
+        let source = filter.source;
+        self.show_filtered_list(source.clone(), &source);
+    }
 
-                                    Self::draw_shortcuts_help_section_header(
-                                        ui,
-                                        "AppData config.ini Bindings",
-                                        "Complete action list loaded from your user config file.",
-                                    );
-                                    self.draw_shortcuts_help_config_rows(ui);
-                                });
-                        });
-                    });
-            });
+    fn show_filtered_list(&mut self, files: Vec<PathBuf>, source: &[PathBuf]) {
+        if files.is_empty() {
+            return;
+        }
+        let previous = self.current_media_path();
+        self.set_image_list_raw(files);
 
-        let modal_rect = modal_response.response.rect;
-        if self.shortcuts_help_modal_skip_outside_click_once {
-            self.shortcuts_help_modal_skip_outside_click_once = false;
-        } else {
-            let clicked_outside_modal = ctx.input(|input| {
-                let primary_clicked = input.pointer.button_clicked(egui::PointerButton::Primary);
-                let secondary_clicked =
-                    input.pointer.button_clicked(egui::PointerButton::Secondary);
-                let pointer_pos = input
-                    .pointer
-                    .interact_pos()
-                    .or_else(|| input.pointer.hover_pos());
+        let target = {
+            let listed: HashSet<&PathBuf> = self.image_list.iter().collect();
+            previous
+                .as_ref()
+                .and_then(|current| {
+                    let start = source.iter().position(|path| path == current)?;
+                    source[start..]
+                        .iter()
+                        .chain(&source[..start])
+                        .find(|path| listed.contains(path))
+                })
+                .unwrap_or(&self.image_list[0])
+                .clone()
+        };
+        let index = self
+            .image_list
+            .iter()
+            .position(|path| *path == target)
+            .unwrap_or(0);
+        self.set_current_index_clamped(index);
 
-                (primary_clicked || secondary_clicked)
-                    && pointer_pos.is_some_and(|pos| !modal_rect.contains(pos))
-            });
-            if clicked_outside_modal {
-                close_modal = true;
+        if self.manga_mode {
+            self.manga_clear_cache();
+            self.ensure_manga_loader();
+            if Self::layout_mode_is_grid(self.manga_layout_mode) {
+                self.restore_masonry_folder_metadata_snapshot();
+                self.mark_manga_dimension_cache_current_if_complete();
+            }
+            if self.is_fullscreen {
+                let scroll_to = self.manga_get_scroll_offset_for_index(index);
+                self.manga_scroll_offset = scroll_to;
+                self.manga_scroll_target = scroll_to;
+                self.manga_scroll_velocity = 0.0;
             }
+            self.manga_update_preload_queue();
         }
-
-        if close_modal {
-            self.shortcuts_help_modal_open = false;
-            self.shortcuts_help_modal_skip_outside_click_once = false;
+        if previous.as_ref() != Some(&target) {
+            self.pending_window_title = Some(self.compute_window_title_for_path(&target));
+            if !(self.manga_mode && self.is_fullscreen) {
+                self.load_image_retaining_visible_media(&target);
+            }
         }
     }
 
-    fn apply_pending_window_title(&mut self, ctx: &egui::Context) {
-        if let Some(title) = self.pending_window_title.take() {
-            let title = self.truncate_window_title_for_viewport(ctx, title);
-            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title));
+    /// Opens the playlist file at `path` as the queue.
+    fn open_playlist_file(&mut self, path: &Path) {
+        match playlist::read_m3u(path) {
+            Ok(items) => {
+                let name = path.file_name().map_or_else(
+                    || path.display().to_string(),
+                    |name| name.to_string_lossy().into_owned(),
+                );
+                self.start_playlist(name, items);
+                self.remember_recent_file(path);
+            }
+            Err(err) => self.show_toast(i18n::tr_args(
+                "error.playlist_read",
+                &[("path", &path.display()), ("error", &err)],
+            )),
         }
     }
 
-    fn open_config_file_in_editor(&mut self) {
-        let config_path = Config::config_path();
-        if let Err(e) = open_path_in_default_app(config_path.as_path()) {
-            self.error_message = Some(format!(
-                "Failed to open config file ({}): {}",
-                config_path.display(),
-                e
-            ));
+    /// Makes the media files among `items` the queue and shows the first one.
+    fn start_playlist(&mut self, name: String, items: Vec<PathBuf>) {
+        let items: Vec<PathBuf> = items
+            .into_iter()
+            .filter(|path| {
+                remote::is_url(path) || (path.is_file() && get_media_type(path).is_some())
+            })
+            .collect();
+        if items.is_empty() {
+            self.show_toast(i18n::tr_args("error.playlist_empty", &[("path", &name)]));
+            return;
         }
+        let mut playlist = playlist::Playlist::new(name, items);
+        playlist.set_shuffled(self.config.playlist_shuffle, None);
+        let first = playlist.items()[0].clone();
+        self.quick_filter = None;
+        self.playlist = Some(playlist);
+        self.load_image(&first);
     }
 
-    fn open_file_location_for_index(&mut self, target_index: usize) {
-        let Some(path) = self.image_list.get(target_index).cloned() else {
+    /// Moves the queue on once the current video played to its end.
+    fn advance_playlist_after_video_end(&mut self) {
+        if self.playlist.is_none() {
             return;
-        };
+        }
+        let next = playlist::next_after_end(
+            self.current_index,
+            self.image_list.len(),
+            self.config.playlist_repeat,
+        );
+        if let Some(index) = next.filter(|index| *index != self.current_index) {
+            self.show_playlist_item(index);
+        }
+    }
 
-        if let Err(e) = reveal_path_in_file_explorer(path.as_path()) {
-            self.error_message = Some(format!(
-                "Failed to open file location ({}): {}",
-                path.display(),
-                e
-            ));
+    fn show_playlist_item(&mut self, index: usize) {
+        if index >= self.image_list.len() {
+            return;
+        }
+        if self.manga_mode && self.is_fullscreen {
+            self.set_current_index_clamped(index);
+            self.manga_scroll_target = self.manga_get_scroll_offset_for_index(index);
+            self.manga_update_preload_queue();
+            return;
         }
+        self.save_current_fullscreen_view_state();
+        self.set_current_index_clamped(index);
+        let path = self.image_list[index].clone();
+        self.load_image_retaining_visible_media(&path);
     }
 
-    fn send_outer_position(&mut self, ctx: &egui::Context, pos: egui::Pos2) {
-        ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(pos));
+    fn toggle_playlist_panel(&mut self) {
+        match self.playlist.as_mut() {
+            Some(playlist) => playlist.panel_open = !playlist.panel_open,
+            None => self.show_toast(i18n::tr("toast.no_playlist")),
+        }
     }
 
-    fn reset_floating_window_drag_anchor(&mut self) {
-        self.floating_drag_start_outer_pos = None;
-        self.floating_drag_start_cursor_screen = None;
+    /// Flips `playlist_shuffle` and reorders the open queue, keeping the current item.
+    fn toggle_playlist_shuffle(&mut self) {
+        self.config.playlist_shuffle = !self.config.playlist_shuffle;
+        self.config.save();
+        let current = self.current_media_path();
+        let Some(playlist) = self.playlist.as_mut() else {
+            return;
+        };
+        playlist.set_shuffled(self.config.playlist_shuffle, current.as_deref());
+        let items = playlist.items().to_vec();
+        self.show_filtered_list(items.clone(), &items);
     }
 
-    fn floating_zoom_inside_window_active(&self, ctx: &egui::Context) -> bool {
-        if self.is_fullscreen {
-            return false;
+    /// The queue panel on the left: the playlist in play order with the current item
+    /// highlighted, plus its shuffle and repeat buttons. Returns the panel's screen rect.
+    fn draw_playlist_panel(&mut self, ctx: &egui::Context) -> Option<egui::Rect> {
+        if self.input_blocking_overlay_open() {
+            return None;
         }
-
-        let Some(display_size) = self.image_display_size_at_zoom() else {
-            return false;
+        let playlist = self
+            .playlist
+            .as_ref()
+            .filter(|playlist| playlist.panel_open)?;
+        let theme = self.overlay_theme;
+        let current = self.current_index;
+        let follow_current = playlist.scrolled_to != Some(current);
+        let repeat_label = match self.config.playlist_repeat {
+            PlaylistRepeat::Off => "Repeat: off",
+            PlaylistRepeat::All => "Repeat: all",
+            PlaylistRepeat::One => "Repeat: one",
         };
+        let mut jump_to = None;
+        let mut close = false;
+        let mut shuffle = false;
+        let mut repeat = false;
+        let response = egui::Area::new(egui::Id::new("playlist_panel"))
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::LEFT_CENTER, egui::vec2(16.0, 0.0))
+            .show(ctx, |ui| {
+                egui::Frame::none()
+                    .fill(theme.panel_fill)
+                    .stroke(egui::Stroke::new(1.0, theme.panel_stroke))
+                    .rounding(theme.corner_radius)
+                    .inner_margin(egui::Margin::same(10.0))
+                    .show(ui, |ui| {
+                        ui.set_width(280.0);
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                egui::RichText::new(&playlist.name)
+                                    .strong()
+                                    .color(theme.text),
+                            );
+                            ui.with_layout(
+                                egui::Layout::right_to_left(egui::Align::Center),
+                                |ui| {
+                                    close = ui.small_button("✕").clicked();
+                                },
+                            );
+                        });
+                        ui.horizontal(|ui| {
+                            shuffle = ui
+                                .selectable_label(self.config.playlist_shuffle, "Shuffle")
+                                .clicked();
+                            repeat = ui.button(repeat_label).clicked();
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "{} / {}",
+                                    current + 1,
+                                    playlist.items().len()
+                                ))
+                                .color(theme.muted_text),
+                            );
+                        });
+                        ui.separator();
+                        egui::ScrollArea::vertical()
+                            .max_height(ctx.screen_rect().height() * 0.6)
+                            .show(ui, |ui| {
+                                for (index, path) in playlist.items().iter().enumerate() {
+                                    let name = path
+                                        .file_name()
+                                        .map(|name| name.to_string_lossy())
+                                        .unwrap_or_default();
+                                    let item = ui.selectable_label(
+                                        index == current,
+                                        format!("{}. {name}", index + 1),
+                                    );
+                                    if index == current && follow_current {
+                                        item.scroll_to_me(Some(egui::Align::Center));
+                                    }
+                                    if item.clicked() {
+                                        jump_to = Some(index);
+                                    }
+                                }
+                            });
+                    });
+            });
 
-        ctx.input(|i| i.raw.viewport().inner_rect)
-            .map(|inner_rect| {
-                display_size.x > inner_rect.width() + 1.0
-                    || display_size.y > inner_rect.height() + 1.0
-            })
-            .unwrap_or(false)
+        if let Some(playlist) = self.playlist.as_mut() {
+            playlist.scrolled_to = Some(current);
+            playlist.panel_open = !close;
+        }
+        if shuffle {
+            self.run_action(Action::TogglePlaylistShuffle);
+        }
+        if repeat {
+            self.run_action(Action::CyclePlaylistRepeat);
+        }
+        if let Some(index) = jump_to.filter(|index| *index != self.current_index) {
+            self.show_playlist_item(index);
+        }
+        Some(response.response.rect)
     }
 
-    fn drag_floating_window_without_native_snap(&mut self, ctx: &egui::Context) {
-        if self.floating_zoom_inside_window_active(ctx) {
-            self.floating_zoom_inside_window_locked = true;
+    /// The filter field under the title bar, with the number of matching files.
+    fn draw_quick_filter_bar(&mut self, ctx: &egui::Context) {
+        if self.input_blocking_overlay_open() {
+            return;
         }
-
-        let Some(current_cursor_screen) = get_global_cursor_pos() else {
-            // Fallback for platforms where global cursor coordinates are unavailable.
-            ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
+        let Some(filter) = self.quick_filter.as_mut() else {
             return;
         };
 
-        let (start_outer_pos, start_cursor_screen) = match (
-            self.floating_drag_start_outer_pos,
-            self.floating_drag_start_cursor_screen,
-        ) {
-            (Some(outer), Some(cursor)) => (outer, cursor),
-            _ => {
-                let outer_pos = ctx
-                    .input(|i| i.raw.viewport().outer_rect)
-                    .map(|r| r.min)
-                    .unwrap_or(egui::Pos2::ZERO);
-                self.floating_drag_start_outer_pos = Some(outer_pos);
-                self.floating_drag_start_cursor_screen = Some(current_cursor_screen);
-                return;
-            }
-        };
-
-        let delta = current_cursor_screen - start_cursor_screen;
-        let new_pos = start_outer_pos + delta;
-        self.send_outer_position(ctx, new_pos);
-    }
-
-    fn apply_manga_pan_step(&mut self, direction: f32, multiplier: f32) {
-        let scroll_amount = self.config.manga_arrow_scroll_speed * 0.5 * multiplier;
-        if self.manga_add_scroll_target_delta(direction * scroll_amount) {
-            self.manga_update_preload_queue();
+        if std::mem::take(&mut filter.swallow_opening_key) {
+            ctx.input_mut(|input| {
+                input
+                    .events
+                    .retain(|event| !matches!(event, egui::Event::Text(_)))
+            });
         }
-    }
-
-    fn modifier_wheel_pan_step(
-        &self,
-        wheel_steps: f32,
-        horizontal: bool,
-        viewport_span: f32,
-    ) -> f32 {
-        let configured = if horizontal {
-            if wheel_steps >= 0.0 {
-                self.config.shift_scroll_up_pan_speed_px_per_step
-            } else {
-                self.config.shift_scroll_down_pan_speed_px_per_step
-            }
-        } else if wheel_steps >= 0.0 {
-            self.config.ctrl_scroll_up_pan_speed_px_per_step
+        let (escape, enter) = if filter.editing {
+            ctx.input_mut(|input| {
+                (
+                    input.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+                    input.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+                )
+            })
         } else {
-            self.config.ctrl_scroll_down_pan_speed_px_per_step
+            (false, false)
         };
 
-        if horizontal {
-            // Normalize horizontal wheel-pan by viewport width so it feels consistent across
-            // different resolutions and independent of image dimensions.
-            let baseline_config = 20.0f32;
-            let scale = (configured / baseline_config).max(0.05);
-            (viewport_span.max(1.0) * 0.08 * scale).max(0.1)
-        } else {
-            configured.max(0.1)
-        }
-    }
+        let theme = self.overlay_theme;
+        let total = filter.media_count;
+        let mut changed = false;
+        egui::Area::new(egui::Id::new("quick_filter_bar"))
+            .order(egui::Order::Foreground)
+            .anchor(
+                egui::Align2::CENTER_TOP,
+                egui::vec2(0.0, theme.title_bar_height + 8.0),
+            )
+            .show(ctx, |ui| {
+                egui::Frame::none()
+                    .fill(theme.panel_fill)
+                    .stroke(egui::Stroke::new(1.0, theme.panel_stroke))
+                    .rounding(theme.corner_radius)
+                    .inner_margin(egui::Margin::symmetric(12.0, 8.0))
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(egui::RichText::new("Filter").color(theme.muted_text));
+                            let field = ui.add(
+                                egui::TextEdit::singleline(&mut filter.text)
+                                    .hint_text("name .ext type:video rating:3")
+                                    .desired_width(260.0),
+                            );
+                            if field.lost_focus() {
+                                filter.editing = false;
+                            } else if filter.editing && !field.has_focus() {
+                                field.request_focus();
+                            }
+                            if field.gained_focus() {
+                                filter.editing = true;
+                            }
+                            changed = field.changed();
+                            let query = quick_filter::Query::parse(&filter.text);
+                            let status = if query.is_empty() {
+                                format!("{total} files")
+                            } else if filter.match_count == 0 {
+                                "No matches".to_string()
+                            } else {
+                                format!("{} of {total}", filter.match_count)
+                            };
+                            ui.label(egui::RichText::new(status).color(theme.text));
+                        });
+                    });
+            });
 
-    fn manga_layout_goto_file_action(&self) -> Action {
-        if self.is_masonry_mode() {
-            Action::MasonryGotoFile
-        } else {
-            Action::MangaGotoFile
+        if enter {
+            filter.editing = false;
+            ctx.memory_mut(|memory| memory.stop_text_input());
+            if filter.text.trim().is_empty() {
+                self.clear_quick_filter();
+                return;
+            }
         }
-    }
-
-    fn manga_layout_pan_action(&self) -> Action {
-        if self.is_masonry_mode() {
-            Action::MasonryPan
-        } else {
-            Action::MangaPan
+        if escape {
+            ctx.memory_mut(|memory| memory.stop_text_input());
+            self.clear_quick_filter();
+            return;
         }
-    }
-
-    fn manga_layout_freehand_autoscroll_action(&self) -> Action {
-        if self.is_masonry_mode() {
-            Action::MasonryFreehandAutoscroll
-        } else {
-            Action::MangaFreehandAutoscroll
+        if changed {
+            self.apply_quick_filter();
         }
     }
 
-    fn run_action(&mut self, action: Action) {
-        match action {
-            Action::Exit => self.request_app_exit(),
-            Action::ToggleFullscreen => self.request_shortcut_fullscreen_toggle(),
-            Action::GotoFile => {
-                if !self.manga_mode {
-                    self.request_goto_file_fullscreen_toggle();
-                }
-            }
-            Action::NextImage => self.next_image(),
-            Action::PreviousImage => self.prev_image(),
-            Action::RotateClockwise => {
-                if let Some(ref mut img) = self.image {
-                    img.rotate_clockwise();
-                    self.texture = None;
-                    self.image_rotated = true;
-                    self.zoom_velocity = 0.0;
-                    // Track rotation in fullscreen state
-                    self.update_fullscreen_rotation(true);
-                }
-            }
-            Action::RotateCounterClockwise => {
-                if let Some(ref mut img) = self.image {
-                    img.rotate_counter_clockwise();
-                    self.texture = None;
-                    self.image_rotated = true;
-                    self.zoom_velocity = 0.0;
-                    // Track rotation in fullscreen state
-                    self.update_fullscreen_rotation(false);
-                }
-            }
-            Action::PreciseRotationClockwise => {
-                if !self.manga_mode && self.current_media_type.is_some() {
-                    self.update_precise_rotation(self.config.precise_rotation_step_degrees);
-                }
-            }
-            Action::PreciseRotationCounterClockwise => {
-                if !self.manga_mode && self.current_media_type.is_some() {
-                    self.update_precise_rotation(-self.config.precise_rotation_step_degrees);
-                }
-            }
-            Action::FlipVertically => self.toggle_media_flip(false, true),
-            Action::FlipHorizontally => self.toggle_media_flip(true, false),
-            Action::ResetZoom => {
-                self.offset = egui::Vec2::ZERO;
-                self.zoom_target = 1.0;
-                self.zoom_velocity = 0.0;
-                if self.is_fullscreen {
-                    self.zoom = 1.0;
-                    self.remember_current_fullscreen_view_state();
-                }
-            }
-            Action::ZoomIn => {
-                let step = self.config.zoom_step;
-                if self.is_fullscreen && self.manga_mode {
-                    self.apply_manga_zoom_step(true);
-                } else if self.is_fullscreen {
-                    self.zoom = (self.zoom * step).min(self.max_zoom_factor());
-                    self.zoom_target = self.zoom;
-                    self.zoom_velocity = 0.0;
-                    self.remember_current_fullscreen_view_state();
-                    self.maybe_refresh_current_solo_image_lod();
-                } else {
-                    self.zoom_target = (self.zoom_target * step).min(self.max_zoom_factor());
-                    self.zoom_velocity = 0.0;
-                }
-            }
-            Action::ZoomOut => {
-                let step = self.config.zoom_step;
-                if self.is_fullscreen && self.manga_mode {
-                    self.apply_manga_zoom_step(false);
-                } else if self.is_fullscreen {
-                    self.zoom = (self.zoom / step).max(0.1);
-                    self.zoom_target = self.zoom;
-                    self.zoom_velocity = 0.0;
-                    self.remember_current_fullscreen_view_state();
-                    self.maybe_refresh_current_solo_image_lod();
-                } else {
-                    self.zoom_target = (self.zoom_target / step).max(0.1);
-                    self.zoom_velocity = 0.0;
-                }
-            }
-            Action::MangaPanUp => self.apply_manga_pan_step(-1.0, 1.0),
-            Action::MangaPanDown => self.apply_manga_pan_step(1.0, 1.0),
-            Action::MangaNextImageFit => self.manga_page_down_smooth(),
-            Action::MangaPreviousImageFit => self.manga_page_up_smooth(),
-            Action::MangaNextImage => self.manga_page_down(),
-            Action::MangaPreviousImage => self.manga_page_up(),
-            Action::MangaZoomIn | Action::MasonryZoomIn => {
-                if self.manga_mode && self.is_fullscreen {
-                    self.apply_manga_zoom_step(true);
-                }
-            }
-            Action::MangaZoomOut | Action::MasonryZoomOut => {
-                if self.manga_mode && self.is_fullscreen {
-                    self.apply_manga_zoom_step(false);
-                }
-            }
-            Action::MasonryPanUp => self.apply_manga_pan_step(-1.0, 1.0),
-            Action::MasonryPanDown => self.apply_manga_pan_step(1.0, 1.0),
-            Action::MasonryPanUp2 => self.apply_manga_pan_step(-1.0, 1.5),
-            Action::MasonryPanDown2 => self.apply_manga_pan_step(1.0, 1.5),
-            Action::MasonryPanUp3 => self.apply_manga_pan_step(-1.0, 2.0),
-            Action::MasonryPanDown3 => self.apply_manga_pan_step(1.0, 2.0),
-            Action::VideoPlayPause => {
-                self.try_toggle_solo_video_play_pause();
-            }
-            Action::VideoMute => {
-                if let Some(ref mut player) = self.video_player {
-                    player.toggle_mute();
-                }
-            }
-            _ => {}
-        }
-    }
+    fn draw_shortcuts_help_config_rows(&self, ui: &mut egui::Ui) {
+        let mut actions: Vec<Action> = self.config.action_bindings.keys().copied().collect();
+        actions.sort_by_key(|action| format!("{:?}", action));
 
-    fn stop_manga_autoscroll(&mut self) {
-        self.manga_autoscroll_active = false;
-        self.manga_autoscroll_anchor = None;
-        self.manga_autoscroll_middle_hold_tracking = false;
-        self.manga_autoscroll_cancel_on_middle_release = false;
-        self.manga_autoscroll_middle_hold_started_at = None;
-        self.masonry_autoscroll_last_motion_at = None;
+        for action in actions {
+            let trigger = self.action_bindings_help_label(action);
+            let title = Self::action_title_for_help(action);
+            Self::draw_shortcuts_help_row(
+                ui,
+                trigger.as_str(),
+                title.as_str(),
+                "Loaded from your user config.ini action bindings.",
+            );
+        }
     }
 
-    fn paint_manga_autoscroll_indicator(
-        &self,
-        painter: &egui::Painter,
-        anchor: egui::Pos2,
-        pointer_pos: Option<egui::Pos2>,
-    ) {
-        let fill_alpha = self.config.manga_autoscroll_circle_fill_alpha;
-        let [arrow_r, arrow_g, arrow_b] = self.config.manga_autoscroll_arrow_rgb;
-        let arrow_alpha = self.config.manga_autoscroll_arrow_alpha;
-
-        painter.circle_filled(
-            anchor,
-            18.0,
-            egui::Color32::from_rgba_unmultiplied(35, 35, 35, fill_alpha),
-        );
-        painter.circle_stroke(
-            anchor,
-            18.0,
-            egui::Stroke::new(
-                1.6,
-                egui::Color32::from_rgba_unmultiplied(210, 210, 210, 190),
-            ),
-        );
-        painter.circle_filled(
-            anchor,
-            4.5,
-            egui::Color32::from_rgba_unmultiplied(245, 245, 245, 205),
-        );
-        painter.line_segment(
-            [
-                egui::pos2(anchor.x - 7.0, anchor.y),
-                egui::pos2(anchor.x + 7.0, anchor.y),
-            ],
-            egui::Stroke::new(
-                1.2,
-                egui::Color32::from_rgba_unmultiplied(210, 210, 210, 180),
-            ),
-        );
-        painter.line_segment(
-            [
-                egui::pos2(anchor.x, anchor.y - 7.0),
-                egui::pos2(anchor.x, anchor.y + 7.0),
-            ],
-            egui::Stroke::new(
-                1.2,
-                egui::Color32::from_rgba_unmultiplied(210, 210, 210, 180),
-            ),
+    const GENERAL_HELP_ROWS: &'static [(Action, &'static str, &'static str)] = &[
+        (
+            Action::ToggleFullscreen,
+            "Toggle fullscreen/window mode",
+            "Switch between floating and fullscreen viewer modes.",
+        ),
+        (
+            Action::Exit,
+            "Exit viewer",
+            "Close the app. Marked files, and with confirm_exit unsaved work or a playing video, ask first.",
+        ),
+        (
+            Action::ExitFullscreenOrQuit,
+            "Leave fullscreen or exit",
+            "Leave fullscreen; in the floating window, exit the viewer.",
+        ),
+        (
+            Action::OpenSettings,
+            "Open settings",
+            "Edit every config.ini option in-app, including press-to-bind shortcuts.",
+        ),
+        (
+            Action::CommandPalette,
+            "Command palette",
+            "Search every action by name, see its shortcut and run it with Enter.",
+        ),
+        (
+            Action::FilterFolder,
+            "Filter folder",
+            "Narrow the folder to names, .ext, type:video or rating:3 matches; Escape clears it.",
+        ),
+        (
+            Action::MoveToNextMonitor,
+            "Move to next monitor",
+            "Move the window to the next monitor; fullscreen covers that monitor.",
+        ),
+        (
+            Action::SnapWindowLeft,
+            "Snap window left",
+            "Fill the left half of the monitor; with up or down, the matching quarter.",
+        ),
+        (
+            Action::SnapWindowRight,
+            "Snap window right",
+            "Fill the right half of the monitor; with up or down, the matching quarter.",
+        ),
+        (
+            Action::SnapWindowUp,
+            "Snap window up",
+            "Fill the top half of the monitor; with left or right, the matching quarter.",
+        ),
+        (
+            Action::SnapWindowDown,
+            "Snap window down",
+            "Fill the bottom half of the monitor; with left or right, the matching quarter.",
+        ),
+        (
+            Action::WindowSizePreset1,
+            "Window size preset 1",
+            "Resize the floating window to the first window_size_presets entry.",
+        ),
+        (
+            Action::WindowSizePreset2,
+            "Window size preset 2",
+            "Resize the floating window to the second window_size_presets entry.",
+        ),
+        (
+            Action::WindowSizePreset3,
+            "Window size preset 3",
+            "Resize the floating window to the third window_size_presets entry.",
+        ),
+        (
+            Action::ToggleAlwaysOnTop,
+            "Always on top",
+            "Keep the window above other apps; optionally shrunk into a corner.",
+        ),
+        (
+            Action::ToggleSlideshow,
+            "Slideshow",
+            "Go to the next file every few seconds; videos play to their end.",
+        ),
+        (
+            Action::TogglePlaylistPanel,
+            "Playlist queue",
+            "Show the queue of an open .m3u playlist or of several dropped files.",
+        ),
+        (
+            Action::TogglePlaylistShuffle,
+            "Shuffle playlist",
+            "Play the playlist in a random order, or back in its listed order.",
+        ),
+        (
+            Action::CyclePlaylistRepeat,
+            "Playlist repeat mode",
+            "Cycle what happens when a video ends: stop after the last item, start over, or repeat.",
+        ),
+        (
+            Action::Pan,
+            "Pan image/video",
+            "Drag the media while in floating/fullscreen view.",
+        ),
+        (
+            Action::SelectArea,
+            "Edge navigation/select-area behavior",
+            "Keeps the right-click zones from click_zones (previous/next at the edges) active.",
+        ),
+        (
+            Action::GotoFile,
+            "Toggle fullscreen via media click zone",
+            "When bound to right click, the center media zone toggles fullscreen.",
+        ),
+        (
+            Action::FreehandAutoscroll,
+            "Freehand autoscroll",
+            "Start pointer-anchored autoscroll in solo view.",
+        ),
+        (
+            Action::NextImage,
+            "Next file",
+            "Move to the next file in the current directory list.",
+        ),
+        (
+            Action::PreviousImage,
+            "Previous file",
+            "Move to the previous file in the current directory list.",
+        ),
+        (
+            Action::RotateClockwise,
+            "Rotate clockwise",
+            "Rotate current media by 90 degrees clockwise.",
+        ),
+        (
+            Action::RotateCounterClockwise,
+            "Rotate counterclockwise",
+            "Rotate current media by 90 degrees counterclockwise.",
+        ),
+        (
+            Action::PreciseRotationClockwise,
+            "Precise rotate clockwise",
+            "Apply fine-grained clockwise rotation in fullscreen.",
+        ),
+        (
+            Action::PreciseRotationCounterClockwise,
+            "Precise rotate counterclockwise",
+            "Apply fine-grained counterclockwise rotation in fullscreen.",
+        ),
+        (
+            Action::FreeRotate,
+            "Rotate freely (hold)",
+            "Hold and drag around the image to rotate it by any angle, over a level grid.",
+        ),
+        (
+            Action::FlipVertically,
+            "Flip vertically",
+            "Mirror current media top to bottom.",
+        ),
+        (
+            Action::FlipHorizontally,
+            "Flip horizontally",
+            "Mirror current media left to right.",
+        ),
+        (
+            Action::SaveOrientedCopy,
+            "Save oriented copy",
+            "Write the current image as PNG with its 90° rotations and flips applied.",
+        ),
+        (
+            Action::ToggleAnnotations,
+            "Annotation mode",
+            "Draw pen strokes, arrows and rectangles over the image; export PNG or save a sidecar.",
+        ),
+        (
+            Action::ToggleAdjustments,
+            "Adjustments panel",
+            "Brightness, contrast, saturation, gamma and grayscale for the view; export PNG.",
+        ),
+        (
+            Action::ToggleCheckerboard,
+            "Transparency checkerboard",
+            "Show a checkerboard under images with transparent pixels instead of the background color.",
+        ),
+        (
+            Action::ToggleThirds,
+            "Rule-of-thirds guides",
+            "Lines dividing the image into thirds, for checking composition.",
+        ),
+        (
+            Action::ToggleGrid,
+            "Grid overlay",
+            "An even grid over the image; grid_divisions sets the number of columns and rows.",
+        ),
+        (
+            Action::ToggleCrosshair,
+            "Center crosshair",
+            "A crosshair marking the center of the image.",
+        ),
+        (
+            Action::Straighten,
+            "Straighten mode",
+            "Drag along a horizon or upright edge to level the image; export a cropped PNG.",
+        ),
+        (
+            Action::Perspective,
+            "Perspective correction",
+            "Drag four corner handles onto a document or whiteboard to rectify it; preview and export PNG.",
+        ),
+        (
+            Action::ToggleEyedropper,
+            "Eyedropper",
+            "Show the RGB and hex value of the pixel under the pointer with a magnified loupe; click to copy it.",
+        ),
+        (
+            Action::Magnifier,
+            "Magnifier (hold)",
+            "While held, a round loupe enlarges the area under the pointer from the full-resolution image.",
+        ),
+        (
+            Action::ShowOriginal,
+            "Before/after (hold)",
+            "While held, show the image without adjustments or annotations.",
+        ),
+        (
+            Action::StereoExport,
+            "Stereo export",
+            "Save a side-by-side stereo pair as a red/cyan anaglyph or cross-eye image with adjustable parallax.",
+        ),
+        (
+            Action::ScanCleanup,
+            "Scan cleanup",
+            "Deskew a document photo, whiten the paper, boost contrast or threshold it; export PNG or PDF.",
+        ),
+        (
+            Action::ExportPdf,
+            "Export to PDF",
+            "Save the marked images (or the whole folder list) as one PDF, one image per page.",
+        ),
+        (
+            Action::ConvertAnimation,
+            "Convert animation",
+            "Re-encode the animated GIF as animated WebP, or a range of the video as GIF or WebP.",
+        ),
+        (
+            Action::BatchRename,
+            "Batch rename",
+            "Rename every file in the folder list from a pattern: counter, original name, date taken.",
+        ),
+        (
+            Action::MonitorWall,
+            "Monitor wall",
+            "Tile the newest images of the folder in a grid that updates live as new files appear.",
+        ),
+        (
+            Action::CompareImages,
+            "Compare images",
+            "Compare the current image with a marked one (or two marked images): side by side, wipe or difference.",
+        ),
+        (
+            Action::CopyViewToClipboard,
+            "Copy view",
+            "Copy what is on screen of the current image or video frame, as zoomed, panned and adjusted, to the clipboard.",
+        ),
+        (
+            Action::NewWindow,
+            "Open in new window",
+            "Open the current or hovered image in an additional viewer window.",
+        ),
+        (
+            Action::ToggleZoomSync,
+            "Zoom sync",
+            "Keep zoom and pan aligned across viewer windows and the fullscreen main view.",
+        ),
+        (
+            Action::UiScaleUp,
+            "Larger UI",
+            "Scale the title bar, video bar, menus and text up by 10%; saved to ui_scale.",
+        ),
+        (Action::UiScaleDown, "Smaller UI", "Scale the UI down by 10%."),
+        (Action::ResetUiScale, "Reset UI scale", "Return the UI to the Windows DPI scale."),
+        (
+            Action::CycleFitMode,
+            "Cycle fit mode",
+            "Fit, fit width, fit height, fill or 100%; saved separately for fullscreen and floating.",
+        ),
+        (
+            Action::FitWhole,
+            "Fit whole image",
+            "Switch the current window mode to fitting the whole image.",
+        ),
+        (
+            Action::FitWidth,
+            "Fit width",
+            "Switch the current window mode to fitting the image width.",
+        ),
+        (
+            Action::FitHeight,
+            "Fit height",
+            "Switch the current window mode to fitting the image height.",
+        ),
+        (
+            Action::FitFill,
+            "Fill",
+            "Switch the current window mode to filling the view, cropping the overflow.",
+        ),
+        (
+            Action::FitActualSize,
+            "Actual size",
+            "Switch the current window mode to showing images at 100%.",
+        ),
+        (
+            Action::CycleZoomPresets,
+            "Zoom presets",
+            "Step through zoom_presets: fit, 100%, 200% and back to fit by default.",
+        ),
+        (Action::Zoom25, "Zoom 25%", "Zoom to 25% around the view center."),
+        (Action::Zoom50, "Zoom 50%", "Zoom to 50% around the view center."),
+        (Action::Zoom100, "Zoom 100%", "Zoom to 100% without changing the fit mode."),
+        (Action::Zoom200, "Zoom 200%", "Zoom to 200% around the view center."),
+        (Action::Zoom400, "Zoom 400%", "Zoom to 400% around the view center."),
+        (Action::PanLeft, "Pan left", "Show more of the left side; hold to keep panning."),
+        (Action::PanRight, "Pan right", "Show more of the right side; hold to keep panning."),
+        (Action::PanUp, "Pan up", "Show more of the top; hold to keep panning."),
+        (Action::PanDown, "Pan down", "Show more of the bottom; hold to keep panning."),
+        (
+            Action::ClearRating,
+            "Clear rating",
+            "Remove the star rating of the current item.",
+        ),
+        (Action::Rate1, "Rate 1 star", "Rate the current item 1 star (XMP sidecar)."),
+        (Action::Rate2, "Rate 2 stars", "Rate the current item 2 stars."),
+        (Action::Rate3, "Rate 3 stars", "Rate the current item 3 stars."),
+        (Action::Rate4, "Rate 4 stars", "Rate the current item 4 stars."),
+        (Action::Rate5, "Rate 5 stars", "Rate the current item 5 stars."),
+        (
+            Action::TogglePick,
+            "Pick",
+            "Flag the current item as a pick, or clear the flag.",
+        ),
+        (
+            Action::ToggleReject,
+            "Reject",
+            "Flag the current item as rejected, or clear the flag.",
+        ),
+        (
+            Action::CycleRatingFilter,
+            "Rating filter",
+            "Cycle which items next/previous visits: all, rated, picked, rejected or not rejected.",
+        ),
+        (
+            Action::SortToFolder1,
+            "Sort to folder 1",
+            "Move or copy the current file to sort_folder_1 and go to the next file.",
+        ),
+        (
+            Action::SortToFolder2,
+            "Sort to folder 2",
+            "Move or copy the current file to sort_folder_2 and go to the next file.",
+        ),
+        (
+            Action::SortToFolder3,
+            "Sort to folder 3",
+            "Move or copy the current file to sort_folder_3 and go to the next file.",
+        ),
+        (
+            Action::SortToFolder4,
+            "Sort to folder 4",
+            "Move or copy the current file to sort_folder_4 and go to the next file.",
+        ),
+        (
+            Action::OpenInEditor,
+            "Open in editor",
+            "Open the current file in external_editor and reload it, keeping the view, on save.",
+        ),
+        (
+            Action::ZoomIn,
+            "Zoom in",
+            "Zoom current media in floating/fullscreen mode.",
+        ),
+        (
+            Action::ZoomOut,
+            "Zoom out",
+            "Zoom current media in floating/fullscreen mode.",
+        ),
+        (
+            Action::VideoMute,
+            "Mute/unmute video",
+            "Toggle audio mute for the active video player.",
+        ),
+        (
+            Action::VideoPlayPause,
+            "Play/pause video",
+            "Toggle playback for the active video when this action is bound.",
+        ),
+        (
+            Action::VideoTrimIn,
+            "Set trim in point",
+            "Mark where the trimmed clip starts; again at the same spot clears it.",
+        ),
+        (
+            Action::VideoTrimOut,
+            "Set trim out point",
+            "Mark where the trimmed clip ends; again at the same spot clears it.",
+        ),
+        (
+            Action::VideoTrim,
+            "Trim video",
+            "Export the range between the in and out points with ffmpeg.",
+        ),
+    ];
+
+    const MANGA_HELP_ROWS: &'static [(Action, &'static str, &'static str)] = &[
+        (
+            Action::MangaPan,
+            "Pan manga strip",
+            "Drag and pan in fullscreen strip mode.",
+        ),
+        (
+            Action::MangaGotoFile,
+            "Open strip item in solo fullscreen",
+            "Open the hovered strip item directly in solo fullscreen.",
+        ),
+        (
+            Action::MangaFreehandAutoscroll,
+            "Manga freehand autoscroll",
+            "Start manga autoscroll anchored to pointer direction.",
+        ),
+        (Action::MangaPanUp, "Pan up", "Move strip viewport upward."),
+        (
+            Action::MangaPanDown,
+            "Pan down",
+            "Move strip viewport downward.",
+        ),
+        (
+            Action::MangaPreviousImageFit,
+            "Previous fit page",
+            "Smoothly move to previous fitted manga page.",
+        ),
+        (
+            Action::MangaNextImageFit,
+            "Next fit page",
+            "Smoothly move to next fitted manga page.",
+        ),
+        (
+            Action::MangaPreviousImage,
+            "Previous strip file",
+            "Jump to previous file in strip mode.",
+        ),
+        (
+            Action::MangaNextImage,
+            "Next strip file",
+            "Jump to next file in strip mode.",
+        ),
+        (
+            Action::MangaScrollUp,
+            "Wheel scroll up",
+            "Scroll strip content upward.",
+        ),
+        (
+            Action::MangaScrollDown,
+            "Wheel scroll down",
+            "Scroll strip content downward.",
+        ),
+        (
+            Action::MangaZoomIn,
+            "Strip zoom in",
+            "Zoom manga strip thumbnails/layout in.",
+        ),
+        (
+            Action::MangaZoomOut,
+            "Strip zoom out",
+            "Zoom manga strip thumbnails/layout out.",
+        ),
+        (
+            Action::MangaTogglePageWidth,
+            "Toggle page-width zoom",
+            "Lay strip pages out at a percentage of the window width (Ctrl+wheel adjusts it).",
+        ),
+        (
+            Action::MangaRotatePage,
+            "Rotate page",
+            "Rotate the page at the screen center by 90 degrees; remembered for the folder.",
+        ),
+        (
+            Action::MangaCyclePageFit,
+            "Cycle page fit",
+            "Switch the centered page between auto, fit width and fit height; remembered for the folder.",
+        ),
+    ];
+
+    const MASONRY_HELP_ROWS: &'static [(Action, &'static str, &'static str)] = &[
+        (
+            Action::MasonryPan,
+            "Pan masonry layout",
+            "Drag/pan in masonry mode.",
+        ),
+        (
+            Action::MasonryGotoFile,
+            "Open masonry item in solo fullscreen",
+            "Open hovered masonry item in solo fullscreen.",
+        ),
+        (
+            Action::MasonryFreehandAutoscroll,
+            "Masonry freehand autoscroll",
+            "Start masonry autoscroll anchored to pointer direction.",
+        ),
+        (
+            Action::MasonryPanUp,
+            "Masonry pan up",
+            "Move masonry viewport upward.",
+        ),
+        (
+            Action::MasonryPanDown,
+            "Masonry pan down",
+            "Move masonry viewport downward.",
+        ),
+        (
+            Action::MasonryPanUp2,
+            "Masonry pan up (fast)",
+            "Move masonry viewport up with increased speed.",
+        ),
+        (
+            Action::MasonryPanDown2,
+            "Masonry pan down (fast)",
+            "Move masonry viewport down with increased speed.",
+        ),
+        (
+            Action::MasonryPanUp3,
+            "Masonry pan up (faster)",
+            "Move masonry viewport up with highest speed tier.",
+        ),
+        (
+            Action::MasonryPanDown3,
+            "Masonry pan down (faster)",
+            "Move masonry viewport down with highest speed tier.",
+        ),
+        (
+            Action::MasonryScrollUp,
+            "Masonry wheel up",
+            "Scroll masonry layout upward.",
+        ),
+        (
+            Action::MasonryScrollDown,
+            "Masonry wheel down",
+            "Scroll masonry layout downward.",
+        ),
+        (
+            Action::MasonryZoomIn,
+            "Masonry zoom in",
+            "Zoom masonry thumbnails/layout in.",
+        ),
+        (
+            Action::MasonryZoomOut,
+            "Masonry zoom out",
+            "Zoom masonry thumbnails/layout out.",
+        ),
+    ];
+
+    fn draw_shortcuts_help_modal(&mut self, ctx: &egui::Context) {
+        if !self.shortcuts_help_modal_open {
+            return;
+        }
+
+        let mut close_modal = ctx.input(|input| input.key_pressed(egui::Key::Escape));
+        let screen_rect = ctx.screen_rect();
+
+        egui::Area::new(egui::Id::new("shortcuts_help_backdrop"))
+            .fixed_pos(screen_rect.min)
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, screen_rect.size());
+                ui.painter().rect_filled(
+                    rect,
+                    0.0,
+                    egui::Color32::from_rgba_unmultiplied(4, 8, 13, 214),
+                );
+            });
+
+        let modal_size = egui::vec2(
+            (screen_rect.width() - 60.0).clamp(560.0, 960.0),
+            (screen_rect.height() - 44.0).clamp(440.0, 780.0),
         );
+        let modal_pos = screen_rect.center() - modal_size * 0.5;
+        let config_path_label = Config::config_path().display().to_string();
+        let general_rows = Self::GENERAL_HELP_ROWS;
+        let manga_rows = Self::MANGA_HELP_ROWS;
+        let masonry_rows = Self::MASONRY_HELP_ROWS;
+
+        let modal_response = egui::Area::new(egui::Id::new("shortcuts_help_modal"))
+            .fixed_pos(modal_pos)
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.set_min_size(modal_size);
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgba_unmultiplied(16, 23, 31, 252))
+                    .stroke(egui::Stroke::new(
+                        1.0,
+                        egui::Color32::from_rgba_unmultiplied(166, 207, 255, 62),
+                    ))
+                    .rounding(18.0)
+                    .inner_margin(egui::Margin::same(18.0))
+                    .show(ui, |ui| {
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                ui.vertical(|ui| {
+                                    ui.label(
+                                        egui::RichText::new("Shortcuts & Features")
+                                            .color(egui::Color32::WHITE)
+                                            .strong()
+                                            .size(22.0),
+                                    );
+                                    ui.add_space(2.0);
+                                    ui.label(
+                                        egui::RichText::new(
+                                            "All bindings below reflect your current config.ini, plus built-in mouse gestures and context menu capabilities.",
+                                        )
+                                        .color(egui::Color32::from_rgb(170, 190, 212))
+                                        .size(12.5),
+                                    );
+                                    ui.add_space(4.0);
+                                    ui.label(
+                                        egui::RichText::new(format!(
+                                            "Config source: {}",
+                                            config_path_label
+                                        ))
+                                        .monospace()
+                                        .color(egui::Color32::from_rgb(128, 165, 198))
+                                        .size(11.0),
+                                    );
+                                });
+
+                                ui.with_layout(
+                                    egui::Layout::right_to_left(egui::Align::Center),
+                                    |ui| {
+                                        let close_button = ui.add(
+                                            egui::Button::new(
+                                                egui::RichText::new("Close")
+                                                    .color(egui::Color32::WHITE),
+                                            )
+                                            .min_size(egui::vec2(88.0, 30.0))
+                                            .fill(egui::Color32::from_rgba_unmultiplied(
+                                                255, 255, 255, 24,
+                                            ))
+                                            .stroke(egui::Stroke::new(
+                                                1.0,
+                                                egui::Color32::from_rgba_unmultiplied(
+                                                    255, 255, 255, 56,
+                                                ),
+                                            ))
+                                            .rounding(7.0),
+                                        );
+                                        if close_button.clicked() {
+                                            close_modal = true;
+                                        }
+                                    },
+                                );
+                            });
+
+                            ui.add_space(10.0);
+                            ui.separator();
+                            ui.add_space(8.0);
+
+                            egui::ScrollArea::vertical()
+                                .max_height((modal_size.y - 152.0).max(220.0))
+                                .auto_shrink([false, false])
+                                .show(ui, |ui| {
+                                    Self::draw_shortcuts_help_section_header(
+                                        ui,
+                                        "Quick Gestures (Built-in)",
+                                        "These are always available and not tied to configurable action names.",
+                                    );
+                                    Self::draw_shortcuts_help_row(
+                                        ui,
+                                        "Space",
+                                        "Mark/unmark current target",
+                                        "Marks hovered strip/masonry item when available, otherwise the current solo file.",
+                                    );
+                                    Self::draw_shortcuts_help_row(
+                                        ui,
+                                        "Ctrl + Left Click",
+                                        "Toggle mark for current media",
+                                        "Quickly mark/unmark the current media under pointer focus.",
+                                    );
+                                    Self::draw_shortcuts_help_row(
+                                        ui,
+                                        "Ctrl + Right Click",
+                                        "Open file actions context menu",
+                                        "Spawns the right-click file action menu for the current file.",
+                                    );
+                                    Self::draw_shortcuts_help_row(
+                                        ui,
+                                        "Ctrl + Drag (strip/masonry)",
+                                        "Marquee mark selection",
+                                        "Drag a selection box to mark or unmark multiple files in strip and masonry modes.",
+                                    );
+                                    Self::draw_shortcuts_help_row(
+                                        ui,
+                                        "Right Click (center media area)",
+                                        "Toggle GIF/video play-pause",
+                                        "When not consumed by edge navigation or fullscreen actions, center right-click toggles playback.",
+                                    );
+                                    Self::draw_shortcuts_help_row(
+                                        ui,
+                                        "Ctrl + C / Ctrl + X / Delete",
+                                        "Marked-file keyboard actions",
+                                        "Copy, cut, or delete marked files (falls back to current file target when no marks are active).",
+                                    );
+
+                                    ui.add_space(8.0);
+                                    ui.separator();
+
+                                    Self::draw_shortcuts_help_section_header(
+                                        ui,
+                                        "General Viewer Actions",
+                                        "Floating and fullscreen controls for image/video viewing.",
+                                    );
+                                    self.draw_shortcuts_help_action_rows(ui, general_rows);
+
+                                    ui.add_space(8.0);
+                                    ui.separator();
+
+                                    Self::draw_shortcuts_help_section_header(
+                                        ui,
+                                        "Manga Strip Actions",
+                                        "Bindings active in fullscreen strip reading mode.",
+                                    );
+                                    self.draw_shortcuts_help_action_rows(ui, manga_rows);
+
+                                    ui.add_space(8.0);
+                                    ui.separator();
+
+                                    Self::draw_shortcuts_help_section_header(
+                                        ui,
+                                        "Masonry Actions",
+                                        "Bindings active in masonry grid mode.",
+                                    );
+                                    self.draw_shortcuts_help_action_rows(ui, masonry_rows);
+
+                                    ui.add_space(8.0);
+                                    ui.separator();
+
+                                    Self::draw_shortcuts_help_section_header(
+                                        ui,
+                                        "Menu & Workflow Features",
+                                        "Commands available from context menus and title-bar controls.",
+                                    );
+                                    Self::draw_shortcuts_help_row(
+                                        ui,
+                                        "Right-click menu",
+                                        "Single-file actions",
+                                        "Mark/Unmark, Cut, Copy, Delete, Rename, and Open file location for the selected file.",
+                                    );
+                                    Self::draw_shortcuts_help_row(
+                                        ui,
+                                        "Right-click menu",
+                                        "Marked-file bulk actions",
+                                        "Cut/Copy/Delete/Rename marked files, plus Mark All and Unmark All.",
+                                    );
+                                    Self::draw_shortcuts_help_row(
+                                        ui,
+                                        "Open file location",
+                                        "Reveal file in OS explorer",
+                                        "Selects the file in Windows Explorer (or opens containing folder on other platforms).",
+                                    );
+                                    Self::draw_shortcuts_help_row(
+                                        ui,
+                                        "Three-stripes title-bar menu",
+                                        "Quick command center",
+                                        "Contains current-file actions, marked-file actions, this Help dialog, Settings, Usage Statistics, and Edit config.ini.",
+                                    );
+
+                                    ui.add_space(8.0);
+                                    ui.separator();
+
+                                    Self::draw_shortcuts_help_section_header(
+                                        ui,
+                                        "AppData config.ini Bindings",
+                                        "Complete action list loaded from your user config file.",
+                                    );
+                                    self.draw_shortcuts_help_config_rows(ui);
+                                });
+                        });
+                    });
+            });
+
+        let modal_rect = modal_response.response.rect;
+        if self.shortcuts_help_modal_skip_outside_click_once {
+            self.shortcuts_help_modal_skip_outside_click_once = false;
+        } else {
+            let clicked_outside_modal = ctx.input(|input| {
+                let primary_clicked = input.pointer.button_clicked(egui::PointerButton::Primary);
+                let secondary_clicked =
+                    input.pointer.button_clicked(egui::PointerButton::Secondary);
+                let pointer_pos = input
+                    .pointer
+                    .interact_pos()
+                    .or_else(|| input.pointer.hover_pos());
+
+                (primary_clicked || secondary_clicked)
+                    && pointer_pos.is_some_and(|pos| !modal_rect.contains(pos))
+            });
+            if clicked_outside_modal {
+                close_modal = true;
+            }
+        }
+
+        if close_modal {
+            self.shortcuts_help_modal_open = false;
+            self.shortcuts_help_modal_skip_outside_click_once = false;
+        }
+    }
+
+    fn apply_pending_window_title(&mut self, ctx: &egui::Context) {
+        if let Some(title) = self.pending_window_title.take() {
+            let title = self.truncate_window_title_for_viewport(ctx, title);
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title));
+        }
+    }
+
+    fn open_settings_window(&mut self) {
+        if self.settings_window.is_none() {
+            self.settings_window = Some(SettingsWindow::new(&self.config));
+        }
+        self.shortcuts_help_modal_open = false;
+        self.file_action_menu = None;
+        self.show_controls = true;
+        self.controls_show_time = Instant::now();
+    }
+
+    fn draw_settings_window(&mut self, ctx: &egui::Context) {
+        let Some(window) = self.settings_window.as_mut() else {
+            return;
+        };
+        let response = window.show(ctx, &self.config);
+
+        if !response.changes.is_empty() {
+            let previous_decoded_image_cache_mb = self.config.decoded_image_cache_mb;
+            self.config = self.config.with_ini_overrides(&response.changes);
+            self.settings_window_save_pending = true;
+            if self.config.decoded_image_cache_mb != previous_decoded_image_cache_mb {
+                self.decoded_image_cache =
+                    build_decoded_image_cache(self.config.decoded_image_cache_mb);
+            }
+
+            // Most settings are read from `self.config` every frame; these are cached elsewhere.
+            configure_metadata_cache_size_limit(self.config.metadata_cache_max_size_mb);
+            crash_report::set_enabled(self.config.crash_reports);
+            usage_stats::set_enabled(self.config.usage_stats);
+            i18n::set_language(self.config.language);
+            self.overlay_theme_checked_at = None;
+            self.set_masonry_items_per_row(self.config.masonry_items_per_row);
+            self.invalidate_manga_layout_cache();
+
+            if let Some(window) = self.settings_window.as_mut() {
+                window.refresh(&self.config);
+            }
+            ctx.request_repaint();
+        }
+
+        // Sliders and color pickers change every frame while dragged; write once on release.
+        let pointer_down = ctx.input(|input| input.pointer.any_down());
+        if self.settings_window_save_pending && (!pointer_down || response.close) {
+            self.config.save();
+            self.settings_window_save_pending = false;
+        }
+
+        if response.open_config_file {
+            self.open_config_file_in_editor();
+        }
+        if response.close {
+            self.settings_window = None;
+        }
+    }
+
+    fn open_usage_stats_window(&mut self) {
+        self.usage_stats_window = Some(usage_stats::snapshot());
+        self.shortcuts_help_modal_open = false;
+        self.file_action_menu = None;
+        self.show_controls = true;
+        self.controls_show_time = Instant::now();
+    }
+
+    fn draw_usage_stats_window(&mut self, ctx: &egui::Context) {
+        let Some(snapshot) = self.usage_stats_window.as_ref() else {
+            return;
+        };
+
+        let mut close = ctx.input(|input| input.key_pressed(egui::Key::Escape));
+        let mut clear = false;
+        let screen_rect = ctx.screen_rect();
+
+        egui::Area::new(egui::Id::new("usage_stats_backdrop"))
+            .fixed_pos(screen_rect.min)
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, screen_rect.size());
+                ui.painter().rect_filled(
+                    rect,
+                    0.0,
+                    egui::Color32::from_rgba_unmultiplied(4, 8, 13, 214),
+                );
+            });
+
+        let modal_width = (screen_rect.width() - 60.0).clamp(320.0, 440.0);
+        egui::Area::new(egui::Id::new("usage_stats_window"))
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.set_width(modal_width);
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgba_unmultiplied(16, 23, 31, 252))
+                    .stroke(egui::Stroke::new(
+                        1.0,
+                        egui::Color32::from_rgba_unmultiplied(166, 207, 255, 62),
+                    ))
+                    .rounding(18.0)
+                    .inner_margin(egui::Margin::same(18.0))
+                    .show(ui, |ui| {
+                        ui.label(
+                            egui::RichText::new("Usage Statistics")
+                                .size(20.0)
+                                .strong()
+                                .color(egui::Color32::WHITE),
+                        );
+                        ui.label(
+                            egui::RichText::new("Stored only on this computer.")
+                                .color(egui::Color32::from_rgb(150, 170, 192)),
+                        );
+                        if !usage_stats::is_enabled() {
+                            ui.label(
+                                egui::RichText::new(
+                                    "Recording is off (usage_stats = false in config.ini).",
+                                )
+                                .color(egui::Color32::from_rgb(255, 190, 135)),
+                            );
+                        }
+                        ui.add_space(10.0);
+
+                        egui::Grid::new("usage_stats_totals")
+                            .num_columns(2)
+                            .spacing([24.0, 6.0])
+                            .show(ui, |ui| {
+                                let rows = [
+                                    ("Files viewed today", snapshot.viewed_today.to_string()),
+                                    (
+                                        "Files viewed in the last 7 days",
+                                        snapshot.viewed_last_7_days.to_string(),
+                                    ),
+                                    ("Images viewed", snapshot.images_viewed.to_string()),
+                                    ("Videos viewed", snapshot.videos_viewed.to_string()),
+                                    (
+                                        "Tracking since",
+                                        snapshot
+                                            .tracking_since
+                                            .clone()
+                                            .unwrap_or_else(|| "-".to_string()),
+                                    ),
+                                ];
+                                for (label, value) in rows {
+                                    ui.label(label);
+                                    ui.label(egui::RichText::new(value).strong());
+                                    ui.end_row();
+                                }
+                            });
+
+                        ui.add_space(12.0);
+                        ui.label(egui::RichText::new("Most-used actions").strong());
+                        if snapshot.top_actions.is_empty() {
+                            ui.label(
+                                egui::RichText::new("No shortcut actions recorded yet.").weak(),
+                            );
+                        } else {
+                            egui::Grid::new("usage_stats_actions")
+                                .num_columns(2)
+                                .spacing([24.0, 4.0])
+                                .show(ui, |ui| {
+                                    for (name, count) in &snapshot.top_actions {
+                                        ui.monospace(name);
+                                        ui.label(count.to_string());
+                                        ui.end_row();
+                                    }
+                                });
+                        }
+
+                        ui.add_space(14.0);
+                        ui.horizontal(|ui| {
+                            clear = ui.button("Clear statistics").clicked();
+                            close |= ui.button("Close").clicked();
+                        });
+                    });
+            });
+
+        if clear {
+            match usage_stats::clear() {
+                Ok(()) => self.usage_stats_window = Some(usage_stats::snapshot()),
+                Err(err) => self.error_message = Some(err),
+            }
+        }
+        if close {
+            self.usage_stats_window = None;
+        }
+    }
+
+    fn open_config_file_in_editor(&mut self) {
+        let config_path = Config::config_path();
+        if let Err(e) = open_path_in_default_app(config_path.as_path()) {
+            self.error_message = Some(i18n::tr_args(
+                "error.open_config",
+                &[("path", &config_path.display()), ("error", &e)],
+            ));
+        }
+    }
+
+    fn open_current_file_in_editor(&mut self) {
+        let Some(path) = self.current_media_path().filter(|path| path.is_file()) else {
+            return;
+        };
+        let launched = match &self.config.external_editor {
+            Some(editor) => spawn_detached(std::process::Command::new(editor).arg(&path)),
+            None => open_path_in_default_app(&path),
+        };
+        if let Err(e) = launched {
+            self.error_message = Some(i18n::tr_args(
+                "error.open_editor",
+                &[("path", &path.display()), ("error", &e)],
+            ));
+            return;
+        }
+        self.external_edit = Some(ExternalEditWatch::start(path, self.repaint_target.clone()));
+    }
+
+    /// Reloads the file opened with `open_in_editor` after the editor saves it, keeping the
+    /// view. Watching stops once another file is shown.
+    fn watch_external_edit(&mut self) {
+        let current_path = self.current_media_path();
+        let Some(watch) = self.external_edit.as_ref() else {
+            return;
+        };
+        if current_path.as_ref() != Some(&watch.path) {
+            self.external_edit = None;
+            return;
+        }
+        if watch.saved.try_recv().is_err() {
+            return;
+        }
+        let path = watch.path.clone();
+
+        self.external_edit_reload_view = Some((path.clone(), self.current_view_state()));
+        self.load_image_retaining_visible_media(&path);
+        self.show_action_osd("external_edit", i18n::tr("osd.external_edit_reloaded"));
+    }
+
+    /// The view saved before reloading an external edit, once the reload is on screen.
+    fn take_external_edit_reload_view(&mut self) -> Option<FullscreenViewState> {
+        let (path, state) = self.external_edit_reload_view.take()?;
+        (self.current_media_path() == Some(path) && !self.retained_media_placeholder_visible)
+            .then_some(state)
+    }
+
+    fn open_file_location_for_index(&mut self, target_index: usize) {
+        let Some(path) = self.image_list.get(target_index).cloned() else {
+            return;
+        };
+
+        if let Err(e) = reveal_path_in_file_explorer(path.as_path()) {
+            self.error_message = Some(i18n::tr_args(
+                "error.open_location",
+                &[("path", &path.display()), ("error", &e)],
+            ));
+        }
+    }
+
+    fn send_outer_position(&mut self, ctx: &egui::Context, pos: egui::Pos2) {
+        ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(pos));
+    }
+
+    fn reset_floating_window_drag_anchor(&mut self) {
+        self.floating_drag_start_outer_pos = None;
+        self.floating_drag_start_cursor_screen = None;
+    }
+
+    fn floating_zoom_inside_window_active(&self, ctx: &egui::Context) -> bool {
+        if self.is_fullscreen {
+            return false;
+        }
+
+        let Some(display_size) = self.image_display_size_at_zoom() else {
+            return false;
+        };
+
+        ctx.input(|i| i.raw.viewport().inner_rect)
+            .map(|inner_rect| {
+                display_size.x > inner_rect.width() + 1.0
+                    || display_size.y > inner_rect.height() + 1.0
+            })
+            .unwrap_or(false)
+    }
+
+    fn drag_floating_window_without_native_snap(&mut self, ctx: &egui::Context) {
+        if self.floating_zoom_inside_window_active(ctx) {
+            self.floating_zoom_inside_window_locked = true;
+        }
+
+        let Some(current_cursor_screen) = get_global_cursor_pos() else {
+            // Fallback for platforms where global cursor coordinates are unavailable.
+            ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
+            return;
+        };
+
+        let (start_outer_pos, start_cursor_screen) = match (
+            self.floating_drag_start_outer_pos,
+            self.floating_drag_start_cursor_screen,
+        ) {
+            (Some(outer), Some(cursor)) => (outer, cursor),
+            _ => {
+                let outer_pos = ctx
+                    .input(|i| i.raw.viewport().outer_rect)
+                    .map(|r| r.min)
+                    .unwrap_or(egui::Pos2::ZERO);
+                self.floating_drag_start_outer_pos = Some(outer_pos);
+                self.window_snap_region = None;
+                self.floating_drag_start_cursor_screen = Some(current_cursor_screen);
+                return;
+            }
+        };
+
+        let delta = current_cursor_screen - start_cursor_screen;
+        let new_pos = start_outer_pos + delta;
+        self.send_outer_position(ctx, new_pos);
+    }
+
+    fn apply_manga_pan_step(&mut self, direction: f32, multiplier: f32) {
+        let scroll_amount = self.config.manga_arrow_scroll_speed * 0.5 * multiplier;
+        if self.manga_add_scroll_target_delta(direction * scroll_amount) {
+            self.manga_update_preload_queue();
+        }
+    }
+
+    fn modifier_wheel_pan_step(
+        &self,
+        wheel_steps: f32,
+        horizontal: bool,
+        viewport_span: f32,
+    ) -> f32 {
+        let configured = if horizontal {
+            if wheel_steps >= 0.0 {
+                self.config.shift_scroll_up_pan_speed_px_per_step
+            } else {
+                self.config.shift_scroll_down_pan_speed_px_per_step
+            }
+        } else if wheel_steps >= 0.0 {
+            self.config.ctrl_scroll_up_pan_speed_px_per_step
+        } else {
+            self.config.ctrl_scroll_down_pan_speed_px_per_step
+        };
+
+        if horizontal {
+            // Normalize horizontal wheel-pan by viewport width so it feels consistent across
+            // different resolutions and independent of image dimensions.
+            let baseline_config = 20.0f32;
+            let scale = (configured / baseline_config).max(0.05);
+            (viewport_span.max(1.0) * 0.08 * scale).max(0.1)
+        } else {
+            configured.max(0.1)
+        }
+    }
+
+    fn manga_layout_goto_file_action(&self) -> Action {
+        if self.is_masonry_mode() {
+            Action::MasonryGotoFile
+        } else {
+            Action::MangaGotoFile
+        }
+    }
+
+    fn manga_layout_pan_action(&self) -> Action {
+        if self.is_masonry_mode() {
+            Action::MasonryPan
+        } else {
+            Action::MangaPan
+        }
+    }
+
+    fn manga_layout_freehand_autoscroll_action(&self) -> Action {
+        if self.is_masonry_mode() {
+            Action::MasonryFreehandAutoscroll
+        } else {
+            Action::MangaFreehandAutoscroll
+        }
+    }
+
+    fn run_action(&mut self, action: Action) {
+        usage_stats::record_action(action.as_str());
+        match action {
+            Action::Exit => self.request_app_exit(),
+            Action::ExitFullscreenOrQuit => {
+                if self.is_fullscreen {
+                    self.request_shortcut_fullscreen_toggle();
+                } else {
+                    self.request_app_exit();
+                }
+            }
+            Action::OpenSettings => self.open_settings_window(),
+            Action::CommandPalette => self.open_command_palette(),
+            Action::FilterFolder => self.open_quick_filter(),
+            Action::MoveToNextMonitor => self.move_to_next_monitor = true,
+            Action::SnapWindowLeft => self.pending_window_snap = Some(SnapDirection::Left),
+            Action::SnapWindowRight => self.pending_window_snap = Some(SnapDirection::Right),
+            Action::SnapWindowUp => self.pending_window_snap = Some(SnapDirection::Up),
+            Action::SnapWindowDown => self.pending_window_snap = Some(SnapDirection::Down),
+            Action::WindowSizePreset1 => self.pending_size_preset = Some(0),
+            Action::WindowSizePreset2 => self.pending_size_preset = Some(1),
+            Action::WindowSizePreset3 => self.pending_size_preset = Some(2),
+            Action::ToggleAlwaysOnTop => self.toggle_always_on_top = true,
+            Action::ToggleSlideshow => self.toggle_slideshow(),
+            Action::TogglePlaylistPanel => self.toggle_playlist_panel(),
+            Action::TogglePlaylistShuffle => self.toggle_playlist_shuffle(),
+            Action::CyclePlaylistRepeat => {
+                self.config.playlist_repeat = self.config.playlist_repeat.next();
+                self.config.save();
+            }
+            Action::UiScaleUp | Action::UiScaleDown | Action::ResetUiScale => {
+                let scale = match action {
+                    Action::UiScaleUp => self.config.ui_scale + Self::UI_SCALE_STEP,
+                    Action::UiScaleDown => self.config.ui_scale - Self::UI_SCALE_STEP,
+                    _ => 1.0,
+                };
+                // Round so repeated steps land on 110%, 120%, ... instead of drifting.
+                self.config.ui_scale = ((scale * 100.0).round() / 100.0).clamp(0.5, 3.0);
+                self.config.save();
+            }
+            Action::NewWindow => {
+                let index = if self.manga_mode {
+                    self.manga_hovered_media_index.unwrap_or(self.current_index)
+                } else {
+                    self.current_index
+                };
+                self.open_index_in_new_window(index);
+            }
+            Action::ToggleZoomSync => self.toggle_zoom_sync(),
+            Action::ToggleFullscreen => self.request_shortcut_fullscreen_toggle(),
+            Action::GotoFile => {
+                if !self.manga_mode {
+                    self.request_goto_file_fullscreen_toggle();
+                }
+            }
+            Action::NextImage => self.next_image(),
+            Action::PreviousImage => self.prev_image(),
+            Action::RotateClockwise => {
+                if let Some(ref mut img) = self.image {
+                    img.rotate_clockwise();
+                    self.texture = None;
+                    self.image_rotated = true;
+                    self.zoom_velocity = 0.0;
+                    self.begin_rotation_turn(true);
+                    // Track rotation in fullscreen state
+                    self.update_fullscreen_rotation(true);
+                } else if let Some(player) = self.video_player.as_mut() {
+                    player.rotate(true);
+                    self.zoom_velocity = 0.0;
+                    self.begin_rotation_turn(true);
+                }
+            }
+            Action::RotateCounterClockwise => {
+                if let Some(ref mut img) = self.image {
+                    img.rotate_counter_clockwise();
+                    self.texture = None;
+                    self.image_rotated = true;
+                    self.zoom_velocity = 0.0;
+                    self.begin_rotation_turn(false);
+                    // Track rotation in fullscreen state
+                    self.update_fullscreen_rotation(false);
+                } else if let Some(player) = self.video_player.as_mut() {
+                    player.rotate(false);
+                    self.zoom_velocity = 0.0;
+                    self.begin_rotation_turn(false);
+                }
+            }
+            Action::PreciseRotationClockwise => {
+                if !self.manga_mode && self.current_media_type.is_some() {
+                    self.update_precise_rotation(self.config.precise_rotation_step_degrees);
+                }
+            }
+            Action::PreciseRotationCounterClockwise => {
+                if !self.manga_mode && self.current_media_type.is_some() {
+                    self.update_precise_rotation(-self.config.precise_rotation_step_degrees);
+                }
+            }
+            Action::FlipVertically => self.toggle_media_flip(false, true),
+            Action::FlipHorizontally => self.toggle_media_flip(true, false),
+            Action::SaveOrientedCopy => self.start_oriented_copy_export(),
+            Action::ToggleAnnotations => self.toggle_annotation_mode(),
+            Action::ToggleAdjustments => self.toggle_adjustments_panel(),
+            Action::ToggleCheckerboard => {
+                self.config.checkerboard_background = !self.config.checkerboard_background;
+                self.config.save();
+            }
+            Action::ToggleThirds => {
+                self.config.overlay_thirds = !self.config.overlay_thirds;
+                self.config.save();
+            }
+            Action::ToggleGrid => {
+                self.config.overlay_grid = !self.config.overlay_grid;
+                self.config.save();
+            }
+            Action::ToggleCrosshair => {
+                self.config.overlay_crosshair = !self.config.overlay_crosshair;
+                self.config.save();
+            }
+            Action::Straighten => self.toggle_straighten_mode(),
+            Action::Perspective => self.toggle_perspective_mode(),
+            Action::ToggleEyedropper => self.toggle_eyedropper_mode(),
+            Action::StereoExport => self.open_stereo_export(),
+            Action::ScanCleanup => self.open_scan_cleanup(),
+            Action::ExportPdf => self.open_pdf_export(),
+            Action::ConvertAnimation => self.open_animation_export(),
+            Action::BatchRename => self.open_batch_rename(),
+            Action::MonitorWall => self.open_monitor_wall(),
+            Action::CycleFitMode => self.set_fit_mode(self.active_fit_mode().next()),
+            Action::FitWhole => self.set_fit_mode(FitMode::Fit),
+            Action::FitWidth => self.set_fit_mode(FitMode::FitWidth),
+            Action::FitHeight => self.set_fit_mode(FitMode::FitHeight),
+            Action::FitFill => self.set_fit_mode(FitMode::Fill),
+            Action::FitActualSize => self.set_fit_mode(FitMode::ActualSize),
+            Action::CycleZoomPresets => self.cycle_zoom_presets(),
+            Action::Zoom25
+            | Action::Zoom50
+            | Action::Zoom100
+            | Action::Zoom200
+            | Action::Zoom400 => {
+                if let Some(zoom) = action.preset_zoom() {
+                    self.set_zoom_level(zoom);
+                }
+            }
+            Action::PanLeft | Action::PanRight | Action::PanUp | Action::PanDown => {
+                let delta = Self::keyboard_pan_direction(action) * self.config.keyboard_pan_step;
+                self.pan_within_overflow(delta, self.screen_size);
+            }
+            Action::ClearRating => self.update_current_rating(|rating| rating.stars = 0),
+            Action::Rate1 => self.update_current_rating(|rating| rating.stars = 1),
+            Action::Rate2 => self.update_current_rating(|rating| rating.stars = 2),
+            Action::Rate3 => self.update_current_rating(|rating| rating.stars = 3),
+            Action::Rate4 => self.update_current_rating(|rating| rating.stars = 4),
+            Action::Rate5 => self.update_current_rating(|rating| rating.stars = 5),
+            Action::TogglePick => self.update_current_rating(|rating| {
+                rating.flag = if rating.flag == ratings::Flag::Pick {
+                    ratings::Flag::None
+                } else {
+                    ratings::Flag::Pick
+                };
+            }),
+            Action::ToggleReject => self.update_current_rating(|rating| {
+                rating.flag = if rating.flag == ratings::Flag::Reject {
+                    ratings::Flag::None
+                } else {
+                    ratings::Flag::Reject
+                };
+            }),
+            Action::CycleRatingFilter => {
+                self.config.rating_filter = self.config.rating_filter.next();
+                self.config.save();
+            }
+            Action::SortToFolder1 => self.sort_current_file_to_folder(0),
+            Action::SortToFolder2 => self.sort_current_file_to_folder(1),
+            Action::SortToFolder3 => self.sort_current_file_to_folder(2),
+            Action::SortToFolder4 => self.sort_current_file_to_folder(3),
+            Action::OpenInEditor => self.open_current_file_in_editor(),
+            Action::CompareImages => self.open_compare_view(),
+            Action::CopyViewToClipboard => self.copy_view_to_clipboard(),
+            Action::ResetZoom => {
+                self.offset = egui::Vec2::ZERO;
+                self.zoom_target = 1.0;
+                self.zoom_velocity = 0.0;
+                if self.is_fullscreen {
+                    self.zoom = 1.0;
+                    self.remember_current_fullscreen_view_state();
+                }
+            }
+            Action::ZoomIn => {
+                let step = self.config.zoom_step;
+                if self.is_fullscreen && self.manga_mode {
+                    self.apply_manga_zoom_step(true);
+                } else if self.is_fullscreen {
+                    self.zoom = (self.zoom * step).min(self.max_zoom_factor());
+                    self.zoom_target = self.zoom;
+                    self.zoom_velocity = 0.0;
+                    self.remember_current_fullscreen_view_state();
+                    self.maybe_refresh_current_solo_image_lod();
+                } else {
+                    self.zoom_target = (self.zoom_target * step).min(self.max_zoom_factor());
+                    self.zoom_velocity = 0.0;
+                }
+            }
+            Action::ZoomOut => {
+                let step = self.config.zoom_step;
+                if self.is_fullscreen && self.manga_mode {
+                    self.apply_manga_zoom_step(false);
+                } else if self.is_fullscreen {
+                    self.zoom = (self.zoom / step).max(0.1);
+                    self.zoom_target = self.zoom;
+                    self.zoom_velocity = 0.0;
+                    self.remember_current_fullscreen_view_state();
+                    self.maybe_refresh_current_solo_image_lod();
+                } else {
+                    self.zoom_target = (self.zoom_target / step).max(0.1);
+                    self.zoom_velocity = 0.0;
+                }
+            }
+            Action::MangaPanUp => self.apply_manga_pan_step(-1.0, 1.0),
+            Action::MangaPanDown => self.apply_manga_pan_step(1.0, 1.0),
+            Action::MangaNextImageFit => self.manga_page_down_smooth(),
+            Action::MangaPreviousImageFit => self.manga_page_up_smooth(),
+            Action::MangaNextImage => self.manga_page_down(),
+            Action::MangaPreviousImage => self.manga_page_up(),
+            Action::MangaZoomIn | Action::MasonryZoomIn => {
+                if self.manga_mode && self.is_fullscreen {
+                    self.apply_manga_zoom_step(true);
+                }
+            }
+            Action::MangaZoomOut | Action::MasonryZoomOut => {
+                if self.manga_mode && self.is_fullscreen {
+                    self.apply_manga_zoom_step(false);
+                }
+            }
+            Action::MangaTogglePageWidth => self.toggle_manga_page_width_zoom(),
+            Action::MangaRotatePage => self.update_centered_manga_page_override(|page| {
+                page.quarter_turns = (page.quarter_turns + 1) % 4;
+            }),
+            Action::MangaCyclePageFit => {
+                self.update_centered_manga_page_override(|page| page.fit = page.fit.next())
+            }
+            Action::MasonryPanUp => self.apply_manga_pan_step(-1.0, 1.0),
+            Action::MasonryPanDown => self.apply_manga_pan_step(1.0, 1.0),
+            Action::MasonryPanUp2 => self.apply_manga_pan_step(-1.0, 1.5),
+            Action::MasonryPanDown2 => self.apply_manga_pan_step(1.0, 1.5),
+            Action::MasonryPanUp3 => self.apply_manga_pan_step(-1.0, 2.0),
+            Action::MasonryPanDown3 => self.apply_manga_pan_step(1.0, 2.0),
+            Action::VideoPlayPause => {
+                self.try_toggle_solo_video_play_pause();
+            }
+            Action::VideoMute => {
+                if let Some(ref mut player) = self.video_player {
+                    player.toggle_mute();
+                }
+            }
+            Action::VideoTrimIn => self.toggle_video_trim_mark(true),
+            Action::VideoTrimOut => self.toggle_video_trim_mark(false),
+            Action::VideoTrim => self.open_video_trim(),
+            _ => {}
+        }
+        if let Some((kind, text)) = self.action_osd_text(action) {
+            self.show_action_osd(kind, text);
+        }
+    }
+
+    fn stop_manga_autoscroll(&mut self) {
+        self.manga_autoscroll_active = false;
+        self.manga_autoscroll_anchor = None;
+        self.manga_autoscroll_middle_hold_tracking = false;
+        self.manga_autoscroll_cancel_on_middle_release = false;
+        self.manga_autoscroll_middle_hold_started_at = None;
+        self.masonry_autoscroll_last_motion_at = None;
+    }
+
+    fn paint_manga_autoscroll_indicator(
+        &self,
+        painter: &egui::Painter,
+        anchor: egui::Pos2,
+        pointer_pos: Option<egui::Pos2>,
+    ) {
+        let fill_alpha = self.config.manga_autoscroll_circle_fill_alpha;
+        let [arrow_r, arrow_g, arrow_b] = self.config.manga_autoscroll_arrow_rgb;
+        let arrow_alpha = self.config.manga_autoscroll_arrow_alpha;
+
+        painter.circle_filled(
+            anchor,
+            18.0,
+            egui::Color32::from_rgba_unmultiplied(35, 35, 35, fill_alpha),
+        );
+        painter.circle_stroke(
+            anchor,
+            18.0,
+            egui::Stroke::new(
+                1.6,
+                egui::Color32::from_rgba_unmultiplied(210, 210, 210, 190),
+            ),
+        );
+        painter.circle_filled(
+            anchor,
+            4.5,
+            egui::Color32::from_rgba_unmultiplied(245, 245, 245, 205),
+        );
+        painter.line_segment(
+            [
+                egui::pos2(anchor.x - 7.0, anchor.y),
+                egui::pos2(anchor.x + 7.0, anchor.y),
+            ],
+            egui::Stroke::new(
+                1.2,
+                egui::Color32::from_rgba_unmultiplied(210, 210, 210, 180),
+            ),
+        );
+        painter.line_segment(
+            [
+                egui::pos2(anchor.x, anchor.y - 7.0),
+                egui::pos2(anchor.x, anchor.y + 7.0),
+            ],
+            egui::Stroke::new(
+                1.2,
+                egui::Color32::from_rgba_unmultiplied(210, 210, 210, 180),
+            ),
+        );
+
+        if let Some(cursor) = pointer_pos {
+            let delta = cursor - anchor;
+            let len = delta.length();
+            if len > 2.0 {
+                let direction = delta / len;
+                let tip = anchor + direction * len.min(44.0);
+                let perp = egui::vec2(-direction.y, direction.x);
+                let stroke = egui::Stroke::new(
+                    2.0,
+                    egui::Color32::from_rgba_unmultiplied(arrow_r, arrow_g, arrow_b, arrow_alpha),
+                );
+
+                painter.line_segment([anchor, tip], stroke);
+
+                let head_a = tip - direction * 8.0 + perp * 5.0;
+                let head_b = tip - direction * 8.0 - perp * 5.0;
+                painter.line_segment([tip, head_a], stroke);
+                painter.line_segment([tip, head_b], stroke);
+            }
+        }
+    }
+
+    fn strip_item_open_uses_right_click(&self) -> bool {
+        self.action_uses_binding(
+            self.manga_layout_goto_file_action(),
+            InputBinding::MouseRight,
+        )
+    }
+
+    fn strip_item_open_binding_triggered(
+        &self,
+        input: &egui::InputState,
+        ctrl: bool,
+        shift: bool,
+        alt: bool,
+    ) -> bool {
+        self.action_binding_triggered(
+            self.manga_layout_goto_file_action(),
+            input,
+            ctrl,
+            shift,
+            alt,
+        )
+    }
+
+    /// Which `[Shortcuts.*]` section applies to what is on screen.
+    fn binding_context(&self) -> BindingContext {
+        if self.manga_mode {
+            BindingContext::Manga
+        } else if matches!(self.current_media_type, Some(MediaType::Video)) {
+            BindingContext::Video
+        } else {
+            BindingContext::Image
+        }
+    }
+
+    fn action_uses_binding(&self, action: Action, binding: InputBinding) -> bool {
+        self.config
+            .action_uses_binding(self.binding_context(), action, &binding)
+    }
+
+    fn action_binding_triggered(
+        &self,
+        action: Action,
+        input: &egui::InputState,
+        ctrl: bool,
+        shift: bool,
+        alt: bool,
+    ) -> bool {
+        self.config
+            .bindings_in(self.binding_context(), action)
+            .iter()
+            .any(|binding| self.binding_triggered(binding, input, ctrl, shift, alt))
+    }
+
+    fn action_binding_down(
+        &self,
+        action: Action,
+        input: &egui::InputState,
+        ctrl: bool,
+        shift: bool,
+        alt: bool,
+    ) -> bool {
+        self.config
+            .bindings_in(self.binding_context(), action)
+            .iter()
+            .any(|binding| self.binding_down(binding, input, ctrl, shift, alt))
+    }
+
+    fn action_mouse_binding_down(&self, action: Action, input: &egui::InputState) -> bool {
+        self.config
+            .bindings_in(self.binding_context(), action)
+            .iter()
+            .any(|binding| Self::mouse_binding_down(binding, input))
+    }
+
+    fn action_mouse_binding_triggered(&self, action: Action, input: &egui::InputState) -> bool {
+        self.config
+            .bindings_in(self.binding_context(), action)
+            .iter()
+            .any(|binding| Self::mouse_binding_triggered(binding, input))
+    }
+
+    fn solo_video_playback_mode_active(&self) -> bool {
+        !self.manga_mode
+            && matches!(self.current_media_type, Some(MediaType::Video))
+            && self.video_player.is_some()
+    }
+
+    fn solo_video_playing_active(&self) -> bool {
+        self.solo_video_playback_mode_active()
+            && self
+                .video_player
+                .as_ref()
+                .is_some_and(|player| player.is_playing())
+    }
+
+    fn try_handle_video_priority_shortcuts(&mut self, ctx: &egui::Context) -> bool {
+        if self.manga_mode || !self.video_navigation_mode_active() {
+            return false;
+        }
+
+        let media_playing = if self.solo_video_playback_mode_active() {
+            self.solo_video_playing_active()
+        } else {
+            self.image.as_ref().is_some_and(|img| img.is_animated()) && !self.gif_paused
+        };
+        let (prev_pressed, next_pressed, pause_pressed) = ctx.input(|input| {
+            let ctrl = input.modifiers.ctrl;
+            let shift = input.modifiers.shift;
+            let alt = input.modifiers.alt;
+
+            let prev_pressed = media_playing
+                && self
+                    .config
+                    .video_priority_previous_file_binding
+                    .as_ref()
+                    .is_some_and(|binding| {
+                        self.binding_triggered(binding, input, ctrl, shift, alt)
+                    });
+            let next_pressed = media_playing
+                && self
+                    .config
+                    .video_priority_next_file_binding
+                    .as_ref()
+                    .is_some_and(|binding| {
+                        self.binding_triggered(binding, input, ctrl, shift, alt)
+                    });
+            let pause_pressed = self.solo_video_playback_mode_active()
+                && self
+                    .config
+                    .video_priority_play_pause_binding
+                    .as_ref()
+                    .is_some_and(|binding| {
+                        self.binding_triggered(binding, input, ctrl, shift, alt)
+                    });
+
+            (prev_pressed, next_pressed, pause_pressed)
+        });
+
+        if prev_pressed {
+            if self.config.videos_only_navigation {
+                self.suppress_video_controls_for_next_video_load = true;
+            }
+            self.navigate_prev_for_video_mode();
+            return true;
+        }
+        if next_pressed {
+            if self.config.videos_only_navigation {
+                self.suppress_video_controls_for_next_video_load = true;
+            }
+            self.navigate_next_for_video_mode();
+            return true;
+        }
+        if pause_pressed {
+            self.try_toggle_solo_video_play_pause();
+            return true;
+        }
+
+        false
+    }
+
+    fn binding_triggered(
+        &self,
+        binding: &InputBinding,
+        input: &egui::InputState,
+        ctrl: bool,
+        shift: bool,
+        alt: bool,
+    ) -> bool {
+        match binding {
+            InputBinding::Key(key) => !ctrl && !shift && !alt && input.key_pressed(*key),
+            InputBinding::KeyWithCtrl(key) => ctrl && !shift && !alt && input.key_pressed(*key),
+            InputBinding::KeyWithShift(key) => !ctrl && shift && !alt && input.key_pressed(*key),
+            InputBinding::KeyWithAlt(key) => !ctrl && !shift && alt && input.key_pressed(*key),
+            InputBinding::KeyWithCtrlShift(key) => ctrl && shift && !alt && input.key_pressed(*key),
+            InputBinding::MouseLeft => input.pointer.button_pressed(egui::PointerButton::Primary),
+            InputBinding::MouseRight => {
+                input.pointer.button_clicked(egui::PointerButton::Secondary)
+            }
+            InputBinding::MouseMiddle => input.pointer.button_pressed(egui::PointerButton::Middle),
+            InputBinding::Mouse4 => input.pointer.button_pressed(egui::PointerButton::Extra1),
+            InputBinding::Mouse5 => input.pointer.button_pressed(egui::PointerButton::Extra2),
+            InputBinding::ScrollUp => input.smooth_scroll_delta.y > 0.0,
+            InputBinding::ScrollDown => input.smooth_scroll_delta.y < 0.0,
+            InputBinding::CtrlScrollUp
+            | InputBinding::CtrlScrollDown
+            | InputBinding::ShiftScrollUp
+            | InputBinding::ShiftScrollDown => false,
+        }
+    }
+
+    fn binding_down(
+        &self,
+        binding: &InputBinding,
+        input: &egui::InputState,
+        ctrl: bool,
+        shift: bool,
+        alt: bool,
+    ) -> bool {
+        match binding {
+            InputBinding::Key(key) => !ctrl && !shift && !alt && input.key_down(*key),
+            InputBinding::KeyWithCtrl(key) => ctrl && !shift && !alt && input.key_down(*key),
+            InputBinding::KeyWithShift(key) => !ctrl && shift && !alt && input.key_down(*key),
+            InputBinding::KeyWithAlt(key) => !ctrl && !shift && alt && input.key_down(*key),
+            InputBinding::KeyWithCtrlShift(key) => ctrl && shift && !alt && input.key_down(*key),
+            InputBinding::MouseLeft => input.pointer.button_down(egui::PointerButton::Primary),
+            InputBinding::MouseRight => input.pointer.button_down(egui::PointerButton::Secondary),
+            InputBinding::MouseMiddle => input.pointer.button_down(egui::PointerButton::Middle),
+            InputBinding::Mouse4 => input.pointer.button_down(egui::PointerButton::Extra1),
+            InputBinding::Mouse5 => input.pointer.button_down(egui::PointerButton::Extra2),
+            InputBinding::ScrollUp
+            | InputBinding::ScrollDown
+            | InputBinding::CtrlScrollUp
+            | InputBinding::CtrlScrollDown
+            | InputBinding::ShiftScrollUp
+            | InputBinding::ShiftScrollDown => false,
+        }
+    }
+
+    fn mouse_binding_down(binding: &InputBinding, input: &egui::InputState) -> bool {
+        match binding {
+            InputBinding::MouseLeft => input.pointer.button_down(egui::PointerButton::Primary),
+            InputBinding::MouseRight => input.pointer.button_down(egui::PointerButton::Secondary),
+            InputBinding::MouseMiddle => input.pointer.button_down(egui::PointerButton::Middle),
+            InputBinding::Mouse4 => input.pointer.button_down(egui::PointerButton::Extra1),
+            InputBinding::Mouse5 => input.pointer.button_down(egui::PointerButton::Extra2),
+            _ => false,
+        }
+    }
+
+    fn mouse_binding_triggered(binding: &InputBinding, input: &egui::InputState) -> bool {
+        match binding {
+            InputBinding::MouseLeft => input.pointer.button_pressed(egui::PointerButton::Primary),
+            InputBinding::MouseRight => {
+                input.pointer.button_clicked(egui::PointerButton::Secondary)
+            }
+            InputBinding::MouseMiddle => input.pointer.button_pressed(egui::PointerButton::Middle),
+            InputBinding::Mouse4 => input.pointer.button_pressed(egui::PointerButton::Extra1),
+            InputBinding::Mouse5 => input.pointer.button_pressed(egui::PointerButton::Extra2),
+            _ => false,
+        }
+    }
+
+    fn manga_page_mouse_repeat_trigger(
+        repeat_at: &mut Option<Instant>,
+        mouse_down: bool,
+        pressed: bool,
+        ctx: &egui::Context,
+    ) -> bool {
+        if !mouse_down {
+            *repeat_at = None;
+            return false;
+        }
+
+        let now = Instant::now();
+        let initial_delay = Duration::from_millis(Self::MANGA_PAGE_NAV_REPEAT_INITIAL_DELAY_MS);
+        let repeat_interval = Duration::from_millis(Self::MANGA_PAGE_NAV_REPEAT_INTERVAL_MS);
+
+        if pressed {
+            *repeat_at = Some(now + initial_delay);
+            ctx.request_repaint_after(initial_delay);
+            return false;
+        }
+
+        match *repeat_at {
+            Some(due_at) if now >= due_at => {
+                *repeat_at = Some(now + repeat_interval);
+                ctx.request_repaint_after(repeat_interval);
+                true
+            }
+            Some(due_at) => {
+                ctx.request_repaint_after(due_at.saturating_duration_since(now));
+                false
+            }
+            None => {
+                *repeat_at = Some(now + initial_delay);
+                ctx.request_repaint_after(initial_delay);
+                false
+            }
+        }
+    }
+
+    fn manga_autoscroll_axis_speed(
+        &self,
+        delta: f32,
+        base_speed: f32,
+        max_axis_distance: f32,
+        axis_multiplier: f32,
+    ) -> f32 {
+        let dead_zone = self.config.manga_autoscroll_dead_zone_px.max(0.0);
+        let magnitude = delta.abs();
+        if magnitude <= dead_zone {
+            return 0.0;
+        }
+
+        let base = (base_speed * self.config.manga_autoscroll_base_speed_multiplier).max(1.0);
+        let normalized_denominator = (max_axis_distance.max(1.0) - dead_zone).max(1.0);
+        let t = ((magnitude - dead_zone) / normalized_denominator).clamp(0.0, 1.0);
+        let curved = t.powf(self.config.manga_autoscroll_curve_power.clamp(0.5, 6.0));
+
+        let min_speed = (base * self.config.manga_autoscroll_min_speed_multiplier)
+            .max(self.config.manga_autoscroll_min_speed_px_per_sec)
+            .max(0.0);
+        let mut max_speed = (base * self.config.manga_autoscroll_max_speed_multiplier)
+            .min(self.config.manga_autoscroll_max_speed_px_per_sec)
+            .max(1.0);
+
+        if max_speed < min_speed {
+            max_speed = min_speed;
+        }
+
+        let axis_multiplier = axis_multiplier.max(0.05);
+        let speed = (min_speed + (max_speed - min_speed) * curved) * axis_multiplier;
+        speed.copysign(delta)
+    }
+
+    fn stop_fullscreen_video_playback(&mut self) {
+        self.remember_video_position();
+        video_resume::flush_in_background();
+        self.video_resume_offer = None;
+        if let Some(player) = self.video_player.take() {
+            drop(player);
+        }
+        self.show_video_controls = false;
+    }
+
+    /// Saves where the solo video is, so it can be resumed when opened again.
+    fn remember_video_position(&self) {
+        if self.config.video_resume == ResumePolicy::Never {
+            return;
+        }
+        let (Some(player), Some(path)) = (&self.video_player, &self.current_video_path) else {
+            return;
+        };
+        if let (Some(position), Some(duration)) = (player.position(), player.duration()) {
+            video_resume::record(path, position, duration);
+        }
+    }
+
+    /// The "resume from" prompt for the current video, above the video controls. Returns its
+    /// rect so presses on it don't reach the viewer.
+    /// Starts copying the current view: the next frame is drawn without overlays and captured.
+    fn copy_view_to_clipboard(&mut self) {
+        if self.current_media_type.is_some() && !self.input_blocking_overlay_open() {
+            self.view_capture = Some(ViewCapture::Pending);
+        }
+    }
+
+    /// Asks for a capture of the frame just drawn, cropped to the visible part of the media:
+    /// the whole window in manga mode.
+    fn request_view_capture(&mut self, ctx: &egui::Context) {
+        let screen_rect = ctx.screen_rect();
+        let crop = if self.manga_mode {
+            Some(screen_rect)
+        } else {
+            self.current_media_rect(screen_rect)
+                .map(|media_rect| media_rect.intersect(screen_rect))
+        };
+        self.view_capture = crop
+            .filter(|rect| rect.is_positive())
+            .map(ViewCapture::Requested);
+        if self.view_capture.is_some() {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot);
+        }
+    }
+
+    /// Puts the capture asked for by [`Self::request_view_capture`] on the clipboard once it
+    /// arrives.
+    fn receive_view_capture(&mut self, ctx: &egui::Context) {
+        let Some(ViewCapture::Requested(crop)) = self.view_capture else {
+            if self.view_capture.is_some() {
+                ctx.request_repaint();
+            }
+            return;
+        };
+        let screenshot = ctx.input(|input| {
+            input.raw.events.iter().find_map(|event| match event {
+                egui::Event::Screenshot { image, .. } => Some(Arc::clone(image)),
+                _ => None,
+            })
+        });
+        let Some(screenshot) = screenshot else {
+            ctx.request_repaint();
+            return;
+        };
+        self.view_capture = None;
+        let Some((width, height, rgba)) =
+            crop_screenshot(&screenshot, crop, ctx.pixels_per_point())
+        else {
+            return;
+        };
+        match write_image_to_clipboard(width, height, &rgba) {
+            Ok(()) => self.show_action_osd(
+                "clipboard",
+                i18n::tr_args(
+                    "osd.view_copied",
+                    &[("size", &format!("{width} x {height} px"))],
+                ),
+            ),
+            Err(err) => self.error_message = Some(err),
+        }
+    }
+
+    fn draw_video_resume_offer(&mut self, ctx: &egui::Context) -> Option<egui::Rect> {
+        let offer = self.video_resume_offer.as_ref()?;
+        let now = Instant::now();
+        if now >= offer.until || self.current_video_path.as_ref() != Some(&offer.path) {
+            self.video_resume_offer = None;
+            return None;
+        }
+        ctx.request_repaint_after(offer.until - now);
+        let position = offer.position;
+        let theme = self.overlay_theme;
+        let mut resume = false;
+        let mut dismiss = false;
+        let response = egui::Area::new(egui::Id::new("video_resume_offer"))
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -96.0))
+            .show(ctx, |ui| {
+                egui::Frame::none()
+                    .fill(theme.panel_fill)
+                    .stroke(egui::Stroke::new(1.0, theme.panel_stroke))
+                    .rounding(theme.corner_radius)
+                    .inner_margin(egui::Margin::same(10.0))
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                egui::RichText::new(i18n::tr_args(
+                                    "prompt.resume_from",
+                                    &[("time", &format_duration(position))],
+                                ))
+                                .color(theme.text),
+                            );
+                            resume = ui.button(i18n::tr("prompt.resume")).clicked();
+                            dismiss = ui.button(i18n::tr("prompt.start_over")).clicked();
+                        });
+                    });
+            });
+        if resume {
+            if let Some(player) = self.video_player.as_mut() {
+                let _ =
+                    player.seek_to_time_with_mode(position.as_secs_f64(), VideoSeekMode::Accurate);
+            }
+        }
+        if resume || dismiss {
+            self.video_resume_offer = None;
+            return None;
+        }
+        Some(response.response.rect)
+    }
+
+    fn reset_fullscreen_anim_stream_state(&mut self) {
+        self.anim_stream_rx = None;
+        self.anim_stream_path = None;
+        self.anim_stream_done = true;
+        self.anim_seekbar_total_frames = None;
+    }
+
+    fn reset_gif_seek_interaction_state(&mut self) {
+        self.gif_seeking = false;
+        self.gif_seek_preview_frame = None;
+    }
+
+    fn ensure_manga_loader(&mut self) {
+        if self.manga_loader.is_none() {
+            self.manga_loader = Some(MangaLoader::new());
+        }
+    }
+
+    fn reset_manga_video_user_preferences(&mut self) {
+        self.manga_video_user_muted = None;
+        self.manga_video_user_volume = None;
+    }
+
+    fn set_strip_entry_placeholder_from_current_media(
+        &mut self,
+        current_media_type: Option<MediaType>,
+    ) {
+        let placeholder_path = match current_media_type {
+            Some(MediaType::Image) if self.texture.is_some() => self
+                .image
+                .as_ref()
+                .map(|img| img.path.clone())
+                .or_else(|| self.current_media_path()),
+            Some(MediaType::Video) if self.video_texture.is_some() => self
+                .current_video_path
+                .clone()
+                .or_else(|| self.current_media_path()),
+            _ => None,
+        };
+
+        self.strip_entry_placeholder_index = placeholder_path.as_ref().and_then(|path| {
+            self.image_list
+                .iter()
+                .position(|candidate| candidate == path)
+        });
+        self.strip_entry_placeholder_path = placeholder_path;
+    }
+
+    fn strip_entry_placeholder_matches(&self, index: usize) -> bool {
+        self.strip_entry_placeholder_index == Some(index)
+            && self
+                .strip_entry_placeholder_path
+                .as_ref()
+                .is_some_and(|path| self.image_list.get(index) == Some(path))
+    }
+
+    fn strip_entry_video_texture_matches_placeholder_path(&self) -> bool {
+        self.video_texture_source_path
+            .as_ref()
+            .and_then(|texture_path| {
+                self.strip_entry_placeholder_path
+                    .as_ref()
+                    .map(|placeholder_path| texture_path == placeholder_path)
+            })
+            .unwrap_or(false)
+    }
+
+    fn strip_entry_image_texture_matches_placeholder_path(&self) -> bool {
+        self.image
+            .as_ref()
+            .and_then(|img| {
+                self.strip_entry_placeholder_path
+                    .as_ref()
+                    .map(|placeholder_path| &img.path == placeholder_path)
+            })
+            .unwrap_or(false)
+    }
+
+    fn manga_video_texture_matches(&self, index: usize) -> bool {
+        self.manga_video_texture_paths
+            .get(&index)
+            .is_some_and(|path| self.image_list.get(index) == Some(path))
+    }
+
+    fn manga_video_player_matches(&self, index: usize) -> bool {
+        self.manga_video_player_paths
+            .get(&index)
+            .is_some_and(|path| self.image_list.get(index) == Some(path))
+    }
+
+    fn remove_manga_video_player(&mut self, index: usize) -> Option<VideoPlayer> {
+        self.manga_video_player_paths.remove(&index);
+        self.manga_video_players.remove(&index)
+    }
+
+    fn clear_manga_video_players(&mut self) {
+        self.manga_video_players.clear();
+        self.manga_video_player_paths.clear();
+    }
+
+    fn remove_manga_video_texture(&mut self, index: usize) {
+        self.manga_video_textures.remove(&index);
+        self.manga_video_texture_paths.remove(&index);
+    }
+
+    fn clear_manga_video_textures(&mut self) {
+        self.manga_video_textures.clear();
+        self.manga_video_texture_paths.clear();
+    }
+
+    fn manga_media_type_for_current_media(
+        media_type: MediaType,
+        current_image_is_animated: bool,
+    ) -> MangaMediaType {
+        match media_type {
+            MediaType::Video => MangaMediaType::Video,
+            MediaType::Image => {
+                if current_image_is_animated {
+                    MangaMediaType::AnimatedImage
+                } else {
+                    MangaMediaType::StaticImage
+                }
+            }
+        }
+    }
+
+    fn cache_current_media_dimensions_for_manga(
+        &mut self,
+        current_media_dims: Option<(u32, u32)>,
+        current_media_type: Option<MediaType>,
+        current_image_is_animated: bool,
+    ) -> bool {
+        if self.is_masonry_mode() && self.masonry_authoritative_dimension_lock_active() {
+            return false;
+        }
+
+        let (Some((w, h)), Some(media_type)) = (current_media_dims, current_media_type) else {
+            return false;
+        };
+
+        let manga_media_type =
+            Self::manga_media_type_for_current_media(media_type, current_image_is_animated);
+
+        if let Some(ref mut loader) = self.manga_loader {
+            let new_entry = (w, h, manga_media_type);
+
+            if media_type == MediaType::Video {
+                if let Some((cached_w, cached_h, MangaMediaType::Video)) =
+                    loader.dimension_cache.get(&self.current_index).copied()
+                {
+                    let cached_pixels = cached_w as u64 * cached_h as u64;
+                    let new_pixels = w as u64 * h as u64;
+                    let cached_aspect = cached_w as f32 / cached_h.max(1) as f32;
+                    let new_aspect = w as f32 / h.max(1) as f32;
+
+                    if cached_w > 0
+                        && cached_h > 0
+                        && new_pixels < cached_pixels
+                        && (cached_aspect - new_aspect).abs() <= 0.01
+                    {
+                        return false;
+                    }
+                }
+            }
+
+            let changed =
+                loader.dimension_cache.get(&self.current_index).copied() != Some(new_entry);
+            loader.dimension_cache.insert(self.current_index, new_entry);
+            return changed;
+        }
+
+        false
+    }
+
+    fn prepare_enter_manga_mode_state(&mut self, current_media_type: Option<MediaType>) {
+        self.set_strip_entry_placeholder_from_current_media(current_media_type);
+        self.stop_manga_wheel_scroll();
+        self.stop_manga_autoscroll();
+        self.reset_gif_seek_interaction_state();
+        if self.manga_layout_mode == MangaLayoutMode::Masonry {
+            self.pause_masonry_metadata_preload();
+        } else {
+            self.reset_masonry_metadata_preload();
+        }
+        self.manga_mode = true;
+        set_metadata_cache_enabled(Self::layout_mode_uses_metadata_cache(
+            self.manga_layout_mode,
+        ));
+        self.stop_fullscreen_video_playback();
+        self.reset_fullscreen_anim_stream_state();
+        self.reset_manga_video_user_preferences();
+        self.restore_manga_folder_view_state();
+        self.ensure_manga_loader();
+    }
+
+    fn reset_masonry_metadata_preload(&mut self) {
+        self.masonry_metadata_preload_active = false;
+        self.masonry_metadata_preload_total = 0;
+        self.masonry_metadata_preload_loaded = 0;
+        self.masonry_metadata_preload_cursor = 0;
+        self.masonry_metadata_preload_list_signature = 0;
+        self.masonry_metadata_preload_restore_index = None;
+        self.masonry_metadata_preload_overlay_hold_until = None;
+        self.masonry_metadata_preload_defer_first_tick = false;
+        self.masonry_metadata_preload_stall_since = None;
+        self.pending_masonry_folder_travel_restore = None;
+    }
+
+    fn pause_masonry_metadata_preload(&mut self) {
+        let total = self.masonry_metadata_preload_total;
+        let can_resume = total > 0
+            && self.masonry_metadata_preload_loaded < total
+            && self.masonry_metadata_preload_list_signature == self.image_list_signature;
+
+        self.masonry_metadata_preload_active = false;
+        self.masonry_metadata_preload_overlay_hold_until = None;
+        self.masonry_metadata_preload_defer_first_tick = false;
+        self.masonry_metadata_preload_stall_since = None;
+
+        if !can_resume {
+            self.masonry_metadata_preload_total = 0;
+            self.masonry_metadata_preload_loaded = 0;
+            self.masonry_metadata_preload_cursor = 0;
+            self.masonry_metadata_preload_list_signature = 0;
+            self.masonry_metadata_preload_restore_index = None;
+            self.pending_masonry_folder_travel_restore = None;
+        }
+    }
+
+    fn begin_masonry_metadata_preload(&mut self) {
+        let total = self.image_list.len();
+        let resume_preload = self.masonry_metadata_preload_total == total
+            && self.masonry_metadata_preload_loaded < total
+            && self.masonry_metadata_preload_list_signature == self.image_list_signature;
+
+        self.masonry_metadata_preload_total = total;
+        self.masonry_metadata_preload_list_signature = self.image_list_signature;
+
+        if resume_preload {
+            self.masonry_metadata_preload_loaded = self.masonry_metadata_preload_loaded.min(total);
+            self.masonry_metadata_preload_cursor = self
+                .masonry_metadata_preload_cursor
+                .min(total.saturating_sub(1));
+            self.masonry_metadata_preload_restore_index = self
+                .masonry_metadata_preload_restore_index
+                .map(|index| index.min(total.saturating_sub(1)))
+                .or_else(|| Some(self.current_index.min(total.saturating_sub(1))));
+        } else {
+            self.masonry_metadata_preload_loaded = 0;
+            self.masonry_metadata_preload_restore_index = if self.image_list.is_empty() {
+                None
+            } else {
+                Some(
+                    self.current_index
+                        .min(self.image_list.len().saturating_sub(1)),
+                )
+            };
+            let preload_window = 96usize.max(self.masonry_items_per_row.clamp(2, 10) * 48);
+            self.masonry_metadata_preload_cursor = self
+                .current_index
+                .min(self.masonry_metadata_preload_total.saturating_sub(1))
+                .saturating_sub(preload_window / 2);
+            self.pending_masonry_folder_travel_restore = None;
+        }
+
+        self.masonry_metadata_preload_active = self.manga_mode
+            && self.is_masonry_mode()
+            && self.masonry_metadata_preload_total > 0
+            && self.manga_loader.is_some();
+
+        if !self.masonry_metadata_preload_active {
+            self.masonry_metadata_preload_restore_index = None;
+            self.masonry_metadata_preload_overlay_hold_until = None;
+            self.masonry_metadata_preload_defer_first_tick = false;
+            return;
+        }
+
+        self.masonry_metadata_preload_overlay_hold_until =
+            Some(Instant::now() + Duration::from_millis(220));
+        self.masonry_metadata_preload_defer_first_tick = true;
+        self.masonry_metadata_preload_stall_since = None;
+
+        self.manga_scrollbar_dragging = false;
+        self.is_panning = false;
+        self.last_mouse_pos = None;
+        self.manga_hovered_media_index = None;
+        self.manga_zoom_plus_held = false;
+        self.manga_zoom_minus_held = false;
+        self.manga_video_seeking = false;
+        self.manga_video_volume_dragging = false;
+        self.gif_seeking = false;
+        self.manga_scroll_target = self.manga_scroll_offset;
+        self.manga_scroll_velocity = 0.0;
+        self.stop_manga_wheel_scroll();
+        self.stop_manga_autoscroll();
+    }
+
+    fn masonry_metadata_overlay_visible(&self) -> bool {
+        if self.masonry_metadata_preload_active {
+            return true;
+        }
+
+        self.masonry_metadata_preload_overlay_hold_until
+            .is_some_and(|hold_until| Instant::now() < hold_until)
+    }
+
+    fn maybe_begin_masonry_metadata_preload(&mut self, allow_startup_preload: bool) {
+        if self.image_list.is_empty() {
+            self.reset_masonry_metadata_preload();
+            return;
+        }
+        if self.manga_layout_mode != MangaLayoutMode::Masonry {
+            self.pause_masonry_metadata_preload();
+            return;
+        }
+
+        let total = self.image_list.len();
+        let folder_ready = self
+            .image_list
+            .iter()
+            .filter(|path| self.is_folder_navigation_entry_path(path.as_path()))
+            .count();
+        let fully_warm = self.manga_loader.as_ref().is_some_and(|loader| {
+            loader
+                .cached_dimensions_count(total)
+                .saturating_add(folder_ready)
+                >= total
+                && loader.pending_dimension_probe_count() == 0
+                && loader.pending_dimension_results_count() == 0
+        });
+
+        if fully_warm || !allow_startup_preload {
+            self.reset_masonry_metadata_preload();
+        } else {
+            self.begin_masonry_metadata_preload();
+        }
+    }
+
+    fn restore_masonry_scroll_after_metadata_preload(&mut self) {
+        if !self.manga_mode || !self.is_masonry_mode() || self.image_list.is_empty() {
+            self.masonry_metadata_preload_restore_index = None;
+            self.pending_masonry_folder_travel_restore = None;
+            return;
+        }
+
+        if let Some(target_index) = self.masonry_metadata_preload_restore_index.take() {
+            let target_index = target_index.min(self.image_list.len().saturating_sub(1));
+            self.set_current_index_clamped(target_index);
+
+            let max_scroll = (self.manga_total_height() - self.screen_size.y).max(0.0);
+            let scroll_to = self
+                .masonry_scroll_offset_for_index_centered(target_index)
+                .unwrap_or_else(|| {
+                    self.manga_get_scroll_offset_for_index(target_index)
+                        .clamp(0.0, max_scroll)
+                });
+
+            self.manga_scroll_offset = scroll_to;
+            self.manga_scroll_target = scroll_to;
+            self.manga_scroll_velocity = 0.0;
+            self.manga_scrollbar_dragging = false;
+            self.masonry_scrollbar_last_motion_at = None;
+            self.masonry_autoscroll_last_motion_at = None;
+            self.is_panning = false;
+            self.last_mouse_pos = None;
+            self.manga_hovered_media_index = None;
+            self.stop_manga_wheel_scroll();
+        }
+
+        if let Some((target_index, restored_offset)) =
+            self.pending_masonry_folder_travel_restore.take()
+        {
+            let target_index = target_index.min(self.image_list.len().saturating_sub(1));
+            self.set_current_index_clamped(target_index);
+
+            let max_scroll = (self.manga_total_height() - self.screen_size.y).max(0.0);
+            let restored_offset = restored_offset.clamp(0.0, max_scroll);
+            self.manga_scroll_offset = restored_offset;
+            self.manga_scroll_target = restored_offset;
+            self.manga_scroll_velocity = 0.0;
+            self.manga_scrollbar_dragging = false;
+            self.masonry_scrollbar_last_motion_at = None;
+            self.masonry_autoscroll_last_motion_at = None;
+            self.is_panning = false;
+            self.last_mouse_pos = None;
+            self.manga_hovered_media_index = None;
+            self.stop_manga_wheel_scroll();
+        }
+    }
+
+    fn tick_masonry_metadata_preload(&mut self) {
+        if !self.masonry_metadata_preload_active {
+            return;
+        }
+
+        if !self.manga_mode || !self.is_masonry_mode() {
+            self.reset_masonry_metadata_preload();
+            return;
+        }
+
+        let total = self
+            .masonry_metadata_preload_total
+            .min(self.image_list.len());
+        if total == 0 {
+            self.reset_masonry_metadata_preload();
+            return;
+        }
+
+        if self.masonry_metadata_preload_defer_first_tick {
+            self.masonry_metadata_preload_defer_first_tick = false;
+            return;
+        }
+
+        let navigation_active = self.masonry_navigation_active_for_heavy_work();
+        let mut allow_preload_step = !navigation_active;
+        let now = Instant::now();
+        let preload_cursor = self
+            .masonry_metadata_preload_cursor
+            .min(total.saturating_sub(1));
+        let preload_window = 96usize.max(self.masonry_items_per_row.clamp(2, 10) * 48);
+        let preload_end = (preload_cursor + preload_window).min(total);
+        let folder_ready = self
+            .image_list
+            .iter()
+            .take(total)
+            .filter(|path| self.is_folder_navigation_entry_path(path.as_path()))
+            .count();
+
+        let (mut loaded_count, mut pending_probe_count, mut pending_result_count) = {
+            let Some(loader) = self.manga_loader.as_mut() else {
+                self.reset_masonry_metadata_preload();
+                return;
+            };
+
+            if allow_preload_step {
+                loader.request_dimensions_range_background(
+                    &self.image_list,
+                    preload_cursor,
+                    preload_end,
+                );
+            }
+
+            (
+                loader
+                    .cached_dimensions_count(total)
+                    .saturating_add(folder_ready)
+                    .min(total),
+                loader.pending_dimension_probe_count(),
+                loader.pending_dimension_results_count(),
+            )
+        };
+
+        let previous_loaded = self.masonry_metadata_preload_loaded.min(total);
+        let mut progress_advanced = loaded_count > previous_loaded;
+
+        if progress_advanced || loaded_count >= total {
+            self.masonry_metadata_preload_stall_since = None;
+        } else {
+            let stall_since = self.masonry_metadata_preload_stall_since.get_or_insert(now);
+            let stall_elapsed = now.saturating_duration_since(*stall_since);
+            if stall_elapsed >= Duration::from_millis(900) {
+                allow_preload_step = true;
+
+                let (next_loaded, next_pending_probe, next_pending_result, fallback_seeded) = {
+                    let Some(loader) = self.manga_loader.as_mut() else {
+                        self.reset_masonry_metadata_preload();
+                        return;
+                    };
+
+                    loader.request_dimensions_range_background(
+                        &self.image_list,
+                        preload_cursor,
+                        preload_end,
+                    );
+                    let fallback_seeded = loader.seed_fallback_dimensions_for_range(
+                        &self.image_list,
+                        preload_cursor,
+                        preload_end,
+                        24,
+                    );
+
+                    (
+                        loader
+                            .cached_dimensions_count(total)
+                            .saturating_add(folder_ready)
+                            .min(total),
+                        loader.pending_dimension_probe_count(),
+                        loader.pending_dimension_results_count(),
+                        fallback_seeded,
+                    )
+                };
+
+                loaded_count = next_loaded;
+                pending_probe_count = next_pending_probe;
+                pending_result_count = next_pending_result;
+                progress_advanced = loaded_count > previous_loaded || fallback_seeded > 0;
+
+                if progress_advanced {
+                    self.masonry_metadata_preload_stall_since = None;
+                } else {
+                    // Keep retry cadence bounded instead of retrying every frame.
+                    self.masonry_metadata_preload_stall_since = Some(now);
+                }
+            }
+        }
+
+        if allow_preload_step {
+            self.masonry_metadata_preload_cursor =
+                if preload_end >= total { 0 } else { preload_end };
+        }
+
+        self.masonry_metadata_preload_loaded = loaded_count;
+
+        let scan_complete =
+            loaded_count >= total && pending_probe_count == 0 && pending_result_count == 0;
+
+        if scan_complete {
+            self.masonry_metadata_preload_loaded = total;
+            self.masonry_metadata_preload_active = false;
+            self.masonry_metadata_preload_stall_since = None;
+            self.manga_update_preload_queue();
+            if self.masonry_pending_dimension_updates.is_empty() {
+                self.restore_masonry_scroll_after_metadata_preload();
+            }
+        }
+    }
+
+    fn draw_masonry_metadata_loading_overlay(&self, ctx: &egui::Context) {
+        if !self.masonry_metadata_overlay_visible() {
+            return;
+        }
+
+        let total = self.masonry_metadata_preload_total.max(1);
+        let loaded = self.masonry_metadata_preload_loaded.min(total);
+        let progress_ratio = (loaded as f32 / total as f32).clamp(0.0, 1.0);
+        let progress_text = format!("Warming layout  {} / {}", loaded, total);
+        let screen_rect = ctx.screen_rect();
+        let panel_width = (screen_rect.width() - 48.0).clamp(280.0, 420.0);
+        let panel_size = egui::vec2(panel_width, 144.0);
+
+        egui::Area::new(egui::Id::new("masonry_metadata_loading_overlay"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(screen_rect.min)
+            .show(ctx, |ui| {
+                let overlay_rect = egui::Rect::from_min_size(egui::Pos2::ZERO, screen_rect.size());
+                let _ = ui.allocate_rect(overlay_rect, egui::Sense::click_and_drag());
+                ui.painter().rect_filled(
+                    overlay_rect,
+                    0.0,
+                    egui::Color32::from_rgba_unmultiplied(5, 8, 12, 150),
+                );
+
+                let panel_rect = egui::Rect::from_center_size(overlay_rect.center(), panel_size);
+                ui.painter().rect_filled(
+                    panel_rect,
+                    18.0,
+                    egui::Color32::from_rgba_unmultiplied(18, 22, 28, 240),
+                );
+                ui.painter().rect_stroke(
+                    panel_rect,
+                    18.0,
+                    egui::Stroke::new(
+                        1.0,
+                        egui::Color32::from_rgba_unmultiplied(130, 188, 255, 72),
+                    ),
+                );
+
+                ui.painter().text(
+                    egui::pos2(panel_rect.center().x, panel_rect.min.y + 34.0),
+                    egui::Align2::CENTER_CENTER,
+                    "Preparing masonry layout",
+                    egui::FontId::proportional(20.0),
+                    egui::Color32::WHITE,
+                );
+                ui.painter().text(
+                    egui::pos2(panel_rect.center().x, panel_rect.min.y + 64.0),
+                    egui::Align2::CENTER_CENTER,
+                    progress_text,
+                    egui::FontId::proportional(14.0),
+                    egui::Color32::from_gray(214),
+                );
+                ui.painter().text(
+                    egui::pos2(panel_rect.center().x, panel_rect.min.y + 88.0),
+                    egui::Align2::CENTER_CENTER,
+                    "Navigation is paused until the layout stabilizes.",
+                    egui::FontId::proportional(12.0),
+                    egui::Color32::from_gray(170),
+                );
+
+                let bar_rect = egui::Rect::from_min_size(
+                    egui::pos2(panel_rect.min.x + 24.0, panel_rect.max.y - 30.0),
+                    egui::vec2(panel_rect.width() - 48.0, 10.0),
+                );
+                ui.painter().rect_filled(
+                    bar_rect,
+                    5.0,
+                    egui::Color32::from_rgba_unmultiplied(255, 255, 255, 30),
+                );
+                if progress_ratio > 0.0 {
+                    let fill_rect = egui::Rect::from_min_max(
+                        bar_rect.min,
+                        egui::pos2(
+                            bar_rect.min.x + bar_rect.width() * progress_ratio,
+                            bar_rect.max.y,
+                        ),
+                    );
+                    ui.painter().rect_filled(
+                        fill_rect,
+                        5.0,
+                        egui::Color32::from_rgb(104, 184, 255),
+                    );
+                }
+            });
+    }
+
+    fn clear_manga_runtime_workloads(&mut self) {
+        self.clear_pending_manga_video_load();
+        self.manga_decoded_mailbox.clear();
+        self.clear_manga_video_players();
+        self.manga_video_failed.clear();
+        self.manga_focused_video_index = None;
+        self.manga_hovered_media_index = None;
+        self.manga_hover_autoplay_resume_at = Instant::now();
+        self.manga_anim_streams.clear();
+        self.manga_anim_stream_done.clear();
+        self.manga_focused_anim_index = None;
+    }
+
+    fn apply_video_audio_overrides(
+        player: &mut VideoPlayer,
+        muted_override: Option<bool>,
+        volume_override: Option<f64>,
+    ) {
+        if let Some(muted) = muted_override {
+            player.set_muted(muted);
+        }
+        if let Some(volume) = volume_override {
+            player.set_volume(volume);
+        }
+    }
+
+    fn use_hardware_acceleration_enabled(&self) -> bool {
+        if !self.config.use_hardware_acceleration {
+            return false;
+        }
+
+        detect_video_acceleration_capabilities().hardware_decode_available
+    }
+
+    fn use_cuda_decode_enabled(&self) -> bool {
+        self.use_hardware_acceleration_enabled()
+            && self.config.enable_cuda
+            && detect_video_acceleration_capabilities().cuda_available
+    }
+
+    fn effective_video_decoder_preferences(&self) -> (bool, bool, bool, bool) {
+        if !self.use_hardware_acceleration_enabled() {
+            return (false, true, false, false);
+        }
+
+        let disable_hardware_decode = self.config.video_disable_hardware_decode;
+        let prefer_hardware_decode = self.config.video_prefer_hardware_decode;
+        let enable_cuda_decode = !disable_hardware_decode && self.use_cuda_decode_enabled();
+        let enable_d3d12_decode = !disable_hardware_decode
+            && self.config.enable_d3d12
+            && detect_video_acceleration_capabilities().d3d12_available;
+
+        (
+            prefer_hardware_decode,
+            disable_hardware_decode,
+            enable_cuda_decode,
+            enable_d3d12_decode,
+        )
+    }
+
+    fn mipmap_static_enabled(&self) -> bool {
+        self.config.manga_mipmap_static && self.config.use_hardware_acceleration
+    }
+
+    fn mipmap_video_thumbnail_enabled(&self) -> bool {
+        self.config.manga_mipmap_video_thumbnails && self.config.use_hardware_acceleration
+    }
+
+    /// Create new viewer with an image path
+    /// `start_visible`: true if window was created visible (images), false if hidden (videos)
+    #[cfg(target_os = "windows")]
+    fn new(
+        cc: &eframe::CreationContext<'_>,
+        path: Option<PathBuf>,
+        start_visible: bool,
+        file_receiver: Option<FileReceiver>,
+    ) -> Self {
+        let mut viewer = Self::default();
+
+        // Store the file receiver for single-instance mode
+        viewer.file_receiver = file_receiver;
+
+        Self::init_viewer(&mut viewer, cc, path, start_visible);
+        viewer
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn new(cc: &eframe::CreationContext<'_>, path: Option<PathBuf>, start_visible: bool) -> Self {
+        let mut viewer = Self::default();
+        Self::init_viewer(&mut viewer, cc, path, start_visible);
+        viewer
+    }
+
+    fn init_viewer(
+        viewer: &mut Self,
+        cc: &eframe::CreationContext<'_>,
+        path: Option<PathBuf>,
+        start_visible: bool,
+    ) {
+        viewer.repaint_target.attach(&cc.egui_ctx);
+        #[cfg(target_os = "windows")]
+        if let Some(receiver) = viewer.file_receiver.as_ref() {
+            let repaint_target = viewer.repaint_target.clone();
+            receiver.set_wake_callback(move || {
+                repaint_target.request_repaint();
+            });
+        }
+
+        // If window started visible, mark it as shown already
+        viewer.startup_window_shown = start_visible;
+
+        // Mark the start of the hidden startup period.
+        viewer.startup_hide_started_at = Instant::now();
+
+        viewer.configure_renderer(cc);
+
+        // Get screen size from monitor info if available
+        #[cfg(target_os = "windows")]
+        {
+            let primary_monitor = get_primary_monitor_size();
+            viewer.screen_size = primary_monitor;
+            viewer.last_known_monitor_size = primary_monitor;
+        }
+
+        if viewer.config.tray_icon {
+            let repaint_target = viewer.repaint_target.clone();
+            viewer.tray =
+                tray::TrayIcon::spawn("Rust Image Viewer", viewer.tray_menu(), move || {
+                    repaint_target.request_repaint();
+                });
+        }
+        if viewer.config.remote_control {
+            let repaint_target = viewer.repaint_target.clone();
+            viewer.remote_control = remote_control::ControlServer::start(
+                viewer.config.remote_control_allow.clone(),
+                move || repaint_target.request_repaint(),
+            );
+        }
+
+        if let Some(path) = path {
+            viewer.load_image(&path);
+            if !viewer.remember_recent_file(&path) {
+                viewer.refresh_jump_list();
+            }
+        }
+    }
+
+    /// Texture limit and egui settings for a new renderer, at startup or after a GPU device loss.
+    fn configure_renderer(&mut self, cc: &eframe::CreationContext<'_>) {
+        // Determine the maximum texture size supported by the active backend.
+        // This viewer uses eframe's OpenGL (glow) integration; oversized textures can crash.
+        let queried_max_texture_side = cc
+            .gl
+            .as_ref()
+            .and_then(|gl| unsafe {
+                gl.get_parameter_i32(eframe::glow::MAX_TEXTURE_SIZE)
+                    .try_into()
+                    .ok()
+            })
+            .filter(|side: &u32| *side >= 512);
+
+        // Fall back to a modern-safe default when the backend cannot report limits.
+        self.max_texture_side = queried_max_texture_side.unwrap_or(8192);
+
+        // Configure visuals (background driven by config)
+        let mut visuals = egui::Visuals::dark();
+        let bg = self.background_color32();
+        visuals.window_fill = bg;
+        visuals.panel_fill = bg;
+        cc.egui_ctx.set_visuals(visuals);
+
+        // Give users a more forgiving double-click detection window.
+        cc.egui_ctx.options_mut(|opt| {
+            opt.input_options.max_double_click_delay = self.config.double_click_grace_period;
+            // ui_scale_up / ui_scale_down are bindable actions instead of egui's fixed keys.
+            opt.zoom_with_keyboard = false;
+        });
+        cc.egui_ctx.set_zoom_factor(self.config.ui_scale);
+    }
+
+    /// Moves the viewer onto the renderer `main` built after a GPU device loss. Textures of
+    /// the lost context are all dropped and uploaded again from the decoded images over the
+    /// next frames; the window picks up its title and level again.
+    fn attach_renderer(&mut self, cc: &eframe::CreationContext<'_>) {
+        self.repaint_target.attach(&cc.egui_ctx);
+        self.configure_renderer(cc);
+        self.font_fallback.install(&cc.egui_ctx);
+        self.drop_gpu_textures();
+        // The taskbar button and media session belong to the old window.
+        self.taskbar = None;
+        self.media_controls = None;
+        self.startup_window_shown = true;
+        if self.always_on_top {
+            cc.egui_ctx
+                .send_viewport_cmd(egui::ViewportCommand::WindowLevel(
+                    egui::WindowLevel::AlwaysOnTop,
+                ));
+        }
+        if let Some(path) = self.current_media_path() {
+            self.pending_window_title = Some(self.compute_window_title_for_path(&path));
+        }
+        self.image_changed = true;
+        self.show_toast(i18n::tr("toast.gpu_recovered"));
+    }
+
+    fn drop_gpu_textures(&mut self) {
+        // The shader program, buffers and LUT texture belong to the lost context too.
+        self.adjustment_renderer = Arc::new(AdjustmentRenderer::default());
+        self.clear_current_image_texture_upload();
+        self.solo_image_texture_cache.clear();
+        self.solo_image_texture_cache_order.clear();
+        self.image_transition = None;
+        self.pending_mode_switch_placeholder = None;
+        self.magnifier_texture = None;
+        self.checkerboard_texture = None;
+        self.modal_thumbnail_cache.clear();
+        self.seek_thumbnails = None;
+
+        self.video_texture = None;
+        if let Some(player) = self.video_player.as_mut() {
+            player.redraw_paused_frame();
+        }
+
+        self.manga_texture_cache.clear();
+        self.clear_manga_video_textures();
+        if let Some(loader) = self.manga_loader.as_mut() {
+            loader.forget_uploaded_textures();
+        }
+
+        if let Some(mut view) = self.compare_view.take() {
+            if matches!(view.state, compare::CompareState::Ready(_)) {
+                view.state = self.load_compare_pair(view.a.clone(), view.b.clone());
+            }
+            self.compare_view = Some(view);
+        }
+        if let Some(wall) = self.monitor_wall.as_mut() {
+            wall.forget_textures();
+        }
+        for window in &mut self.viewer_windows {
+            window.reload();
+        }
+        if let Some(dialog) = self.scan_cleanup.as_mut() {
+            dialog.preview = None;
+        }
+        if let Some(dialog) = self.stereo_export.as_mut() {
+            dialog.preview = None;
+        }
+    }
+
+    /// Drops a background folder listing that has run past [`path_access::LISTING_TIMEOUT`],
+    /// typically on an unreachable share; the current file stays open on its own.
+    fn give_up_slow_media_directory_scan(&mut self, ctx: &egui::Context) {
+        let Some(elapsed) = self
+            .pending_media_directory_started_at
+            .map(|started_at| started_at.elapsed())
+        else {
+            return;
+        };
+        if elapsed < path_access::LISTING_TIMEOUT {
+            // Keep polling, and bring up the spinner once it is due.
+            ctx.request_repaint_after(
+                path_access::LISTING_SPINNER_DELAY.min(path_access::LISTING_TIMEOUT - elapsed),
+            );
+            return;
+        }
+        let directory = self
+            .pending_media_directory_target
+            .as_deref()
+            .and_then(Path::parent)
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        tracing::warn!("listing {} timed out", directory.display());
+        self.pending_media_directory_scan = None;
+        self.pending_media_directory_target = None;
+        self.pending_media_directory_scan_kind = None;
+        self.pending_media_directory_started_at = None;
+        self.show_toast(i18n::tr_args(
+            "toast.folder_listing_timed_out",
+            &[("path", &directory.display())],
+        ));
+    }
+
+    /// Opens a finished URL download, or shows why it failed.
+    fn poll_download(&mut self, ctx: &egui::Context) {
+        let Some(result) = self.download.as_ref().map(remote::Download::try_finish) else {
+            return;
+        };
+        let Some(result) = result else {
+            ctx.request_repaint_after(Duration::from_millis(100));
+            return;
+        };
+        let Some(download) = self.download.take() else {
+            return;
+        };
+        match result {
+            Ok(path) => {
+                self.replace_playlist_url(&download.url, &path);
+                self.load_media(&path);
+                ctx.request_repaint();
+            }
+            Err(_) if download.is_cancelled() => {}
+            Err(error) => {
+                self.error_message = Some(i18n::tr_args(
+                    "error.download_failed",
+                    &[("url", &download.url), ("error", &error)],
+                ));
+            }
+        }
+    }
+
+    /// Opens the image at `url`: its saved copy when there is one, otherwise once it has
+    /// been downloaded, continuing a download started ahead of time.
+    fn open_image_url(&mut self, url: String) {
+        if let Some(path) = remote::cached_download(&url) {
+            self.replace_playlist_url(&url, &path);
+            self.load_media(&path);
+            return;
+        }
+        if self
+            .download
+            .as_ref()
+            .is_some_and(|download| download.url == url)
+        {
+            return;
+        }
+        if let Some(previous) = self.download.take() {
+            self.url_prefetch.adopt(previous);
+        }
+        let download = self
+            .url_prefetch
+            .take(&url)
+            .unwrap_or_else(|| remote::Download::start(url));
+        self.download = Some(download);
+    }
+
+    /// Puts the downloaded copy `path` of `url` in its place in the playlist.
+    fn replace_playlist_url(&mut self, url: &str, path: &Path) {
+        let url = PathBuf::from(url);
+        let Some(playlist) = self.playlist.as_mut() else {
+            return;
+        };
+        if !playlist.replace(&url, path) {
+            return;
+        }
+        // Patched in place: the other items are unchanged, so their cached textures stay.
+        for item in self.image_list.iter_mut().filter(|item| **item == url) {
+            *item = path.to_path_buf();
+        }
+        self.image_list_signature = Self::compute_image_list_signature(&self.image_list);
+    }
+
+    /// Image URLs of the playlist after the item being shown, up to `url_prefetch_count`.
+    fn upcoming_playlist_image_urls(&self) -> Vec<String> {
+        let Some(playlist) = self.playlist.as_ref() else {
+            return Vec::new();
+        };
+        let items = playlist.items();
+        let shown = self
+            .download
+            .as_ref()
+            .map(|download| PathBuf::from(&download.url))
+            .or_else(|| self.current_media_path());
+        let start = shown
+            .and_then(|shown| items.iter().position(|item| *item == shown))
+            .map_or(0, |index| index + 1);
+        items[start..]
+            .iter()
+            .filter_map(|item| item.to_str().and_then(remote::parse_url))
+            .filter(|url| !remote::is_video_url(url))
+            .take(self.config.url_prefetch_count)
+            .collect()
+    }
+
+    /// Keeps the next images of a playlist of URLs downloading, and swaps finished ones in.
+    fn update_url_prefetch(&mut self, ctx: &egui::Context) {
+        let upcoming = self.upcoming_playlist_image_urls();
+        self.url_prefetch.update(&upcoming);
+        for (url, result) in self.url_prefetch.poll() {
+            match result {
+                Ok(path) => self.replace_playlist_url(&url, &path),
+                Err(err) => tracing::debug!("prefetching {url} failed: {err}"),
+            }
+        }
+        if self.url_prefetch.progress().is_some() {
+            ctx.request_repaint_after(Duration::from_millis(250));
+        }
+    }
+
+    /// Progress of the URL download, with a button to cancel it.
+    fn draw_download_overlay(&self, ctx: &egui::Context) {
+        let Some(download) = self.download.as_ref() else {
+            return;
+        };
+        let (received, total) = download.progress();
+        let megabytes = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+        let (fraction, amount) = match total {
+            Some(total) => (
+                (received as f32 / total as f32).clamp(0.0, 1.0),
+                format!("{:.1} / {:.1} MB", megabytes(received), megabytes(total)),
+            ),
+            None => (0.0, format!("{:.1} MB", megabytes(received))),
+        };
+        let screen_rect = ctx.screen_rect();
+        egui::Area::new(egui::Id::new("url_download_overlay"))
+            .order(egui::Order::Foreground)
+            .pivot(egui::Align2::CENTER_BOTTOM)
+            .fixed_pos(screen_rect.center_bottom() - egui::vec2(0.0, 48.0))
+            .show(ctx, |ui| {
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgba_unmultiplied(18, 22, 28, 235))
+                    .rounding(12.0)
+                    .inner_margin(egui::Margin::same(12.0))
+                    .show(ui, |ui| {
+                        ui.set_width(320.0);
+                        ui.label(
+                            egui::RichText::new(i18n::tr_args(
+                                "status.downloading",
+                                &[("name", &download.name)],
+                            ))
+                            .color(egui::Color32::WHITE),
+                        );
+                        ui.add(egui::ProgressBar::new(fraction).text(amount));
+                        if ui.button(i18n::tr("prompt.cancel")).clicked() {
+                            download.cancel();
+                        }
+                    });
+            });
+    }
+
+    /// Picks up progress of background jobs and announces the ones that finished.
+    fn poll_jobs(&mut self, ctx: &egui::Context) {
+        for id in self.jobs.poll() {
+            if let Some(job) = self.jobs.job(id) {
+                let text = format!("{}: {}", job.title, job.status_text());
+                self.show_toast(text);
+            }
+        }
+        if self.jobs.active_count() > 0 {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+    }
+
+    /// Collapsible list of background jobs in the bottom-left corner, with a cancel button on
+    /// each running one. Shown while there are jobs to list.
+    fn draw_jobs_panel(&mut self, ctx: &egui::Context) {
+        if self.jobs.is_empty() {
+            return;
+        }
+        let mut clear_finished = false;
+        let screen_rect = ctx.screen_rect();
+        egui::Area::new(egui::Id::new("jobs_panel"))
+            .order(egui::Order::Foreground)
+            .pivot(egui::Align2::LEFT_BOTTOM)
+            .fixed_pos(screen_rect.left_bottom() + egui::vec2(16.0, -96.0))
+            .show(ctx, |ui| {
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgba_unmultiplied(18, 22, 28, 235))
+                    .rounding(12.0)
+                    .inner_margin(egui::Margin::same(10.0))
+                    .show(ui, |ui| {
+                        ui.set_width(300.0);
+                        let active = self.jobs.active_count();
+                        ui.horizontal(|ui| {
+                            let arrow = if self.jobs_panel_expanded {
+                                "▾"
+                            } else {
+                                "▸"
+                            };
+                            let header = if active > 0 {
+                                i18n::tr_args("jobs.running", &[("count", &active.to_string())])
+                            } else {
+                                i18n::tr("jobs.title").to_string()
+                            };
+                            if ui
+                                .add(
+                                    egui::Label::new(
+                                        egui::RichText::new(format!("{arrow} {header}"))
+                                            .color(egui::Color32::WHITE),
+                                    )
+                                    .sense(egui::Sense::click()),
+                                )
+                                .clicked()
+                            {
+                                self.jobs_panel_expanded = !self.jobs_panel_expanded;
+                            }
+                            if active < self.jobs.jobs().count() {
+                                ui.with_layout(
+                                    egui::Layout::right_to_left(egui::Align::Center),
+                                    |ui| {
+                                        clear_finished = ui
+                                            .small_button(i18n::tr("jobs.clear_finished"))
+                                            .clicked();
+                                    },
+                                );
+                            }
+                        });
+                        if !self.jobs_panel_expanded {
+                            return;
+                        }
+                        for job in self.jobs.jobs() {
+                            ui.separator();
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    egui::Label::new(
+                                        egui::RichText::new(&job.title)
+                                            .size(12.0)
+                                            .color(egui::Color32::from_gray(214)),
+                                    )
+                                    .truncate(),
+                                );
+                                if !job.state.is_finished() {
+                                    ui.with_layout(
+                                        egui::Layout::right_to_left(egui::Align::Center),
+                                        |ui| {
+                                            if ui.small_button(i18n::tr("prompt.cancel")).clicked()
+                                            {
+                                                self.jobs.cancel(job.id);
+                                            }
+                                        },
+                                    );
+                                }
+                            });
+                            match &job.state {
+                                jobs::JobState::Running => {
+                                    ui.add(
+                                        egui::ProgressBar::new(job.progress.unwrap_or(0.0))
+                                            .text(job.status_text()),
+                                    );
+                                }
+                                state => {
+                                    let color = match state {
+                                        jobs::JobState::Failed(_) => {
+                                            egui::Color32::from_rgb(255, 140, 130)
+                                        }
+                                        _ => egui::Color32::from_gray(160),
+                                    };
+                                    ui.label(
+                                        egui::RichText::new(job.status_text())
+                                            .size(12.0)
+                                            .color(color),
+                                    );
+                                }
+                            }
+                        }
+                    });
+            });
+        if clear_finished {
+            self.jobs.clear_finished();
+        }
+    }
+
+    /// Spinner in the bottom-left corner while a background folder listing takes a while.
+    fn draw_media_directory_scan_spinner(&self, ctx: &egui::Context) {
+        let listing_slowly = self
+            .pending_media_directory_started_at
+            .is_some_and(|started_at| started_at.elapsed() >= path_access::LISTING_SPINNER_DELAY);
+        if !listing_slowly {
+            return;
+        }
+        let screen_rect = ctx.screen_rect();
+        egui::Area::new(egui::Id::new("media_directory_scan_spinner"))
+            .order(egui::Order::Foreground)
+            .interactable(false)
+            .pivot(egui::Align2::LEFT_BOTTOM)
+            .fixed_pos(screen_rect.left_bottom() + egui::vec2(16.0, -16.0))
+            .show(ctx, |ui| {
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgba_unmultiplied(18, 22, 28, 220))
+                    .rounding(12.0)
+                    .inner_margin(egui::Margin::symmetric(10.0, 6.0))
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.add(egui::Spinner::new().size(14.0));
+                            ui.label(
+                                egui::RichText::new(i18n::tr("status.listing_folder"))
+                                    .size(12.0)
+                                    .color(egui::Color32::from_gray(214)),
+                            );
+                        });
+                    });
+            });
+    }
+
+    fn poll_pending_media_directory_scan(&mut self, ctx: &egui::Context) {
+        let Some(rx) = self.pending_media_directory_scan.as_ref() else {
+            return;
+        };
+
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(crossbeam_channel::TryRecvError::Empty) => {
+                self.give_up_slow_media_directory_scan(ctx);
+                return;
+            }
+            Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                self.pending_media_directory_scan = None;
+                self.pending_media_directory_target = None;
+                self.pending_media_directory_scan_kind = None;
+                self.pending_media_directory_started_at = None;
+                return;
+            }
+        };
+
+        self.pending_media_directory_scan = None;
+        let scan_kind = self
+            .pending_media_directory_scan_kind
+            .take()
+            .unwrap_or(PendingMediaDirectoryScanKind::InitialLoad);
+        let Some(target_path) = self.pending_media_directory_target.take() else {
+            self.pending_media_directory_started_at = None;
+            return;
+        };
+
+        if let Some(started_at) = self.pending_media_directory_started_at.take() {
+            self.perf_metrics
+                .record_duration("media_index_async_scan_ms", started_at.elapsed());
+        }
+
+        let scanned_directory = result.directory.clone();
+        let mut files = self
+            .media_directory_index
+            .apply_directory_scan_result(result);
+
+        match scan_kind {
+            PendingMediaDirectoryScanKind::InitialLoad => {
+                if files.is_empty() {
+                    files.push(target_path.clone());
+                }
+
+                let current_path = self.image_list.get(self.current_index).cloned();
+                if current_path.as_ref() != Some(&target_path) {
+                    return;
+                }
+
+                self.set_image_list(files);
+                let resolved_index = self
+                    .image_list
+                    .iter()
+                    .position(|candidate| candidate == &target_path)
+                    .unwrap_or(0);
+                self.set_current_index_clamped(resolved_index);
+                if !self.defer_directory_work_for_fast_startup() {
+                    self.schedule_solo_probe_window(&target_path, self.current_media_type);
+                }
+                ctx.request_repaint();
+            }
+            PendingMediaDirectoryScanKind::ExternalRefresh => {
+                let current_path_before = self.current_media_path();
+                let current_index_before = self.current_index;
+                let current_directory = current_path_before
+                    .as_ref()
+                    .and_then(|path| path.parent().map(Path::to_path_buf))
+                    .or_else(|| {
+                        self.image_list
+                            .first()
+                            .and_then(|path| path.parent().map(Path::to_path_buf))
+                    });
+
+                if current_directory.as_deref() != Some(scanned_directory.as_path()) {
+                    return;
+                }
+
+                if self.try_append_new_entries_in_strip_mode(&files) {
+                    self.clear_stale_marked_files();
+                    self.clear_stale_prepared_clipboard_paths();
+                    self.modal_thumbnail_cache.retain(|path, _| path.exists());
+                    ctx.request_repaint();
+                    return;
+                }
+
+                if self.manga_mode && self.is_true_masonry_mode() {
+                    self.persist_current_masonry_folder_metadata_snapshot();
+                }
+
+                self.set_image_list(files);
+                self.clear_stale_marked_files();
+                self.clear_stale_prepared_clipboard_paths();
+                self.modal_thumbnail_cache.retain(|path, _| path.exists());
+
+                if self.image_list.is_empty() {
+                    self.clear_current_media_after_all_files_removed();
+                    ctx.request_repaint();
+                    return;
+                }
+
+                let previous_was_folder_entry = current_path_before
+                    .as_ref()
+                    .is_some_and(|path| self.is_folder_navigation_entry_path(path.as_path()));
+                let same_path_index = current_path_before.as_ref().and_then(|path| {
+                    self.image_list
+                        .iter()
+                        .position(|candidate| candidate == path)
+                });
+                let first_media_index = self
+                    .image_list
+                    .iter()
+                    .position(|path| !self.is_folder_navigation_entry_path(path.as_path()));
+
+                let resolved_index = if previous_was_folder_entry {
+                    first_media_index.or(same_path_index).unwrap_or_else(|| {
+                        current_index_before.min(self.image_list.len().saturating_sub(1))
+                    })
+                } else {
+                    same_path_index.or(first_media_index).unwrap_or_else(|| {
+                        current_index_before.min(self.image_list.len().saturating_sub(1))
+                    })
+                };
+                self.set_current_index_clamped(resolved_index);
+
+                if let Some(path) = self.current_media_path() {
+                    self.pending_window_title = Some(self.compute_window_title_for_path(&path));
+                }
+
+                if self.manga_mode {
+                    self.manga_clear_cache();
+                    self.ensure_manga_loader();
+                    if Self::layout_mode_is_grid(self.manga_layout_mode) {
+                        self.restore_masonry_folder_metadata_snapshot();
+                        self.mark_manga_dimension_cache_current_if_complete();
+                    }
+                    self.manga_update_preload_queue();
+                }
+
+                ctx.request_repaint();
+            }
+        }
+    }
+
+    fn clear_pending_media_load(&mut self) {
+        self.pending_media_load = None;
+        self.retained_media_placeholder_visible = false;
+        self.defer_media_view_reset = false;
+    }
+
+    fn clear_pending_manga_video_load(&mut self) {
+        self.pending_manga_video_load = None;
+    }
+
+    fn manga_video_load_pending_for_index(&self, index: usize) -> bool {
+        self.pending_manga_video_load
+            .as_ref()
+            .is_some_and(|pending| {
+                pending.index == index
+                    && self
+                        .image_list
+                        .get(index)
+                        .is_some_and(|current_path| current_path == &pending.path)
+            })
+    }
+
+    fn start_async_manga_focused_video_load(
+        &mut self,
+        index: usize,
+        path: PathBuf,
+        muted: bool,
+        initial_volume: f64,
+        autoplay: bool,
+        seamless_lod_refresh: bool,
+    ) {
+        if !gstreamer_runtime_available() {
+            self.clear_pending_manga_video_load();
+            self.remove_manga_video_player(index);
+            self.remove_manga_video_texture(index);
+            self.manga_video_preview_resume_secs.remove(&index);
+            if self.manga_focused_video_index == Some(index) {
+                self.manga_focused_video_index = None;
+            }
+            self.video_playback_unavailable_reason =
+                Some(Self::gstreamer_missing_video_error_text().to_string());
+            return;
+        }
+
+        let request_id = self.next_manga_video_load_request_id;
+        self.next_manga_video_load_request_id = self
+            .next_manga_video_load_request_id
+            .saturating_add(1)
+            .max(1);
+        let output_bounds = if self.is_masonry_mode() {
+            self.manga_video_output_bounds_for_index(index)
+        } else {
+            // Long-strip focused playback stays at source quality.
+            None
+        };
+
+        self.pending_manga_video_load = Some(PendingMangaFocusedVideoLoad {
+            request_id,
+            index,
+            path: path.clone(),
+            started_at: Instant::now(),
+        });
+
+        let saved_position = self.manga_video_preview_resume_by_path.get(&path).copied();
+
+        let (
+            prefer_hardware_decode,
+            disable_hardware_decode,
+            enable_cuda_decode,
+            enable_d3d12_decode,
+        ) = self.effective_video_decoder_preferences();
+        self.manga_video_load_coordinator
+            .submit(MangaFocusedVideoLoadRequest {
+                request_id,
+                index,
+                path,
+                muted,
+                initial_volume,
+                prefer_hardware_decode,
+                disable_hardware_decode,
+                enable_cuda_decode,
+                enable_d3d12_decode,
+                output_bounds,
+                autoplay,
+                seamless_lod_refresh,
+                resume_position_secs: saved_position,
+            });
+    }
+
+    fn poll_pending_manga_video_load(&mut self, ctx: &egui::Context) {
+        let mut applied_any = false;
+        let mut pending_dimension_updates = Vec::new();
+
+        loop {
+            let result = match self.manga_video_load_coordinator.try_recv() {
+                Ok(result) => result,
+                Err(crossbeam_channel::TryRecvError::Empty) => break,
+                Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                    self.clear_pending_manga_video_load();
+                    break;
+                }
+            };
+
+            let (result_request_id, result_index, result_path, worker_elapsed) = (
+                result.request_id,
+                result.index,
+                &result.path,
+                result.worker_elapsed,
+            );
+
+            let Some(pending) = self.pending_manga_video_load.as_ref() else {
+                continue;
+            };
+
+            if result_request_id != pending.request_id
+                || result_index != pending.index
+                || result_path != &pending.path
+            {
+                self.perf_metrics
+                    .increment_counter("manga_video_async_stale", 1);
+                continue;
+            }
 
-        if let Some(cursor) = pointer_pos {
-            let delta = cursor - anchor;
-            let len = delta.length();
-            if len > 2.0 {
-                let direction = delta / len;
-                let tip = anchor + direction * len.min(44.0);
-                let perp = egui::vec2(-direction.y, direction.x);
-                let stroke = egui::Stroke::new(
-                    2.0,
-                    egui::Color32::from_rgba_unmultiplied(arrow_r, arrow_g, arrow_b, arrow_alpha),
-                );
+            let Some(pending) = self.pending_manga_video_load.take() else {
+                continue;
+            };
 
-                painter.line_segment([anchor, tip], stroke);
+            let total_elapsed = pending.started_at.elapsed();
+            self.perf_metrics
+                .record_duration("manga_video_async_ms", total_elapsed);
+            self.perf_metrics
+                .record_duration("manga_video_async_worker_ms", worker_elapsed);
+            self.perf_metrics.record_duration(
+                "manga_video_async_queue_ms",
+                total_elapsed.saturating_sub(worker_elapsed),
+            );
 
-                let head_a = tip - direction * 8.0 + perp * 5.0;
-                let head_b = tip - direction * 8.0 - perp * 5.0;
-                painter.line_segment([tip, head_a], stroke);
-                painter.line_segment([tip, head_b], stroke);
+            let still_targeted = self.manga_mode
+                && self.manga_focused_video_index == Some(result_index)
+                && self
+                    .image_list
+                    .get(result_index)
+                    .is_some_and(|current_path| current_path == result_path);
+
+            if !still_targeted {
+                self.perf_metrics
+                    .increment_counter("manga_video_async_stale", 1);
+                continue;
             }
-        }
-    }
 
-    fn strip_item_open_uses_right_click(&self) -> bool {
-        self.config.action_uses_binding(
-            self.manga_layout_goto_file_action(),
-            &InputBinding::MouseRight,
-        )
-    }
+            match result {
+                MangaFocusedVideoLoadResult {
+                    index,
+                    path,
+                    autoplay,
+                    seamless_lod_refresh,
+                    result: Ok(mut player),
+                    ..
+                } => {
+                    if self.manga_video_players.contains_key(&index)
+                        && !self.manga_video_player_matches(index)
+                    {
+                        self.remove_manga_video_player(index);
+                        self.remove_manga_video_texture(index);
+                    }
 
-    fn strip_item_open_binding_triggered(
-        &self,
-        input: &egui::InputState,
-        ctrl: bool,
-        shift: bool,
-        alt: bool,
-    ) -> bool {
-        self.action_binding_triggered(
-            self.manga_layout_goto_file_action(),
-            input,
-            ctrl,
-            shift,
-            alt,
-        )
-    }
+                    let mut synchronized_state = false;
+                    if seamless_lod_refresh && self.manga_video_player_matches(index) {
+                        if let Some(current_player) = self.manga_video_players.get_mut(&index) {
+                            let current_position = current_player.displayed_position();
+                            let current_was_playing = current_player.is_playing();
+                            let current_muted = current_player.is_muted();
+                            let current_volume = current_player.volume();
 
-    fn action_uses_binding(&self, action: Action, binding: InputBinding) -> bool {
-        self.config.action_uses_binding(action, &binding)
-    }
+                            if let Some(position) = current_position {
+                                let _ = player.seek_to_time_with_mode(
+                                    position.as_secs_f64(),
+                                    VideoSeekMode::Accurate,
+                                );
+                            }
 
-    fn action_binding_triggered(
-        &self,
-        action: Action,
-        input: &egui::InputState,
-        ctrl: bool,
-        shift: bool,
-        alt: bool,
-    ) -> bool {
-        self.config
-            .get_bindings(action)
-            .iter()
-            .any(|binding| self.binding_triggered(binding, input, ctrl, shift, alt))
-    }
+                            if current_was_playing {
+                                if !player.is_playing() {
+                                    let _ = player.play();
+                                }
+                            } else if player.is_playing() {
+                                let _ = player.pause();
+                            }
 
-    fn action_binding_down(
-        &self,
-        action: Action,
-        input: &egui::InputState,
-        ctrl: bool,
-        shift: bool,
-        alt: bool,
-    ) -> bool {
-        self.config
-            .get_bindings(action)
-            .iter()
-            .any(|binding| self.binding_down(binding, input, ctrl, shift, alt))
-    }
+                            player.set_muted(current_muted);
+                            player.set_volume(current_volume);
+                            synchronized_state = true;
+                        }
+                    }
+
+                    if !synchronized_state {
+                        Self::apply_video_audio_overrides(
+                            &mut player,
+                            self.manga_video_user_muted,
+                            self.manga_video_user_volume,
+                        );
+
+                        if autoplay && !player.is_playing() {
+                            if let Err(err) = player.play() {
+                                self.manga_video_failed.insert(index);
+                                self.video_playback_unavailable_reason = Some(err);
+                                self.manga_focused_video_index = None;
+                                continue;
+                            }
+                        }
+                    }
+
+                    // Re-check resume position at apply-time to cover races where
+                    // fullscreen/preview position was recorded after this async load started.
+                    let resume_position = self.manga_resume_position_for_index(index);
+                    Self::seek_video_player_to_resume_position(&mut player, resume_position);
+                    if let Some(position) = player.displayed_position() {
+                        self.manga_record_video_preview_resume_secs(index, position);
+                    }
+
+                    let dims = player.display_dimensions();
+                    if dims.0 > 0 && dims.1 > 0 {
+                        if !self.masonry_authoritative_dimension_lock_active() {
+                            if let Some(ref mut loader) = self.manga_loader {
+                                if loader.update_video_dimensions(index, dims.0, dims.1) {
+                                    pending_dimension_updates.push(index);
+                                }
+                            }
+                        }
+                    }
+
+                    if !self.is_masonry_mode() {
+                        if let Some(frame) = player.get_frame() {
+                            let displayed_position = frame.pts;
+                            let target_side = self.manga_target_texture_side_for_dynamic_media(
+                                index,
+                                MangaMediaType::Video,
+                            );
+                            let no_downscale =
+                                frame.width <= target_side && frame.height <= target_side;
+                            let (w, h, color_image) = if no_downscale {
+                                let size = [frame.width as usize, frame.height as usize];
+                                match try_color_image_from_opaque_rgba_bytes(size, frame.pixels) {
+                                    Ok(color_image) => (frame.width, frame.height, color_image),
+                                    Err(pixels) => (
+                                        frame.width,
+                                        frame.height,
+                                        egui::ColorImage::from_rgba_unmultiplied(size, &pixels),
+                                    ),
+                                }
+                            } else {
+                                let (w, h, pixels) = downscale_rgba_if_needed(
+                                    frame.width,
+                                    frame.height,
+                                    &frame.pixels,
+                                    target_side,
+                                    self.config.downscale_filter.to_image_filter(),
+                                );
+                                (
+                                    w,
+                                    h,
+                                    egui::ColorImage::from_rgba_unmultiplied(
+                                        [w as usize, h as usize],
+                                        pixels.as_ref(),
+                                    ),
+                                )
+                            };
+                            let texture_options =
+                                self.config.texture_filter_video.to_egui_options();
+
+                            if let Some((texture, stored_w, stored_h)) =
+                                self.manga_video_textures.get_mut(&index)
+                            {
+                                upload_video_frame(texture, color_image, texture_options);
+                                *stored_w = w;
+                                *stored_h = h;
+                            } else {
+                                let texture = ctx.load_texture(
+                                    format!("manga_video_{}", index),
+                                    color_image,
+                                    texture_options,
+                                );
+                                self.manga_video_textures.insert(index, (texture, w, h));
+                            }
+                            if let Some(path) = self.image_list.get(index).cloned() {
+                                self.manga_video_texture_paths.insert(index, path);
+                            }
+                            if let Some(position) = displayed_position {
+                                self.manga_record_video_preview_resume_secs(index, position);
+                            }
+                        }
+                    }
+
+                    self.manga_video_player_paths.insert(index, path);
+                    self.manga_video_players.insert(index, player);
+                    self.error_message = None;
+                    self.manga_evict_distant_video_players(index, None);
+                    applied_any = true;
+                }
+                MangaFocusedVideoLoadResult {
+                    index,
+                    path,
+                    result: Err(err),
+                    ..
+                } => {
+                    self.manga_video_failed.insert(index);
+                    self.video_playback_unavailable_reason =
+                        Some(format!("Failed to load video: {}", err));
+                    tracing::warn!(
+                        target: "video",
+                        index,
+                        path = %path.display(),
+                        error = %err,
+                        "failed to create video player for strip item"
+                    );
+
+                    if self.manga_focused_video_index == Some(index)
+                        && !self.manga_video_players.contains_key(&index)
+                    {
+                        self.manga_focused_video_index = None;
+                    }
+                }
+            }
+        }
+
+        if self.is_masonry_mode()
+            && !self.masonry_authoritative_dimension_lock_active()
+            && !pending_dimension_updates.is_empty()
+        {
+            self.masonry_queue_dimension_updates(pending_dimension_updates);
+            if !self.masonry_navigation_active_for_heavy_work() {
+                let force_flush = !self.masonry_metadata_preload_active;
+                self.masonry_flush_pending_dimension_updates(force_flush);
+            }
+        }
 
-    fn action_mouse_binding_down(&self, action: Action, input: &egui::InputState) -> bool {
-        self.config
-            .get_bindings(action)
-            .iter()
-            .any(|binding| Self::mouse_binding_down(binding, input))
+        if applied_any {
+            ctx.request_repaint();
+        }
     }
 
-    fn action_mouse_binding_triggered(&self, action: Action, input: &egui::InputState) -> bool {
-        self.config
-            .get_bindings(action)
-            .iter()
-            .any(|binding| Self::mouse_binding_triggered(binding, input))
+    fn start_async_image_load(
+        &mut self,
+        path: PathBuf,
+        max_texture_side: u32,
+        downscale_filter: FilterType,
+        gif_filter: FilterType,
+    ) {
+        let request_id = self.next_media_load_request_id;
+        self.next_media_load_request_id = self.next_media_load_request_id.saturating_add(1).max(1);
+
+        self.pending_media_load = Some(PendingMediaLoad {
+            request_id,
+            path: path.clone(),
+            kind: PendingMediaLoadKind::Image,
+            max_texture_side: Some(max_texture_side),
+            started_at: Instant::now(),
+        });
+
+        self.media_load_coordinator.submit(MediaLoadRequest::Image {
+            request_id,
+            path,
+            max_texture_side,
+            downscale_filter,
+            gif_filter,
+        });
     }
 
-    fn solo_video_playback_mode_active(&self) -> bool {
-        !self.manga_mode
-            && matches!(self.current_media_type, Some(MediaType::Video))
-            && self.video_player.is_some()
+    fn live_video_output_bounds_for_solo(&self) -> Option<(u32, u32)> {
+        let viewport = self.solo_viewport_size_for_lod();
+        let max_side = self.max_texture_side.max(1);
+        let width = (viewport.x.ceil() as u32).clamp(1, max_side);
+        let height = (viewport.y.ceil() as u32).clamp(1, max_side);
+        Some((width, height))
     }
 
-    fn solo_video_playing_active(&self) -> bool {
-        self.solo_video_playback_mode_active()
-            && self
-                .video_player
-                .as_ref()
-                .is_some_and(|player| player.is_playing())
+    fn async_video_output_bounds_for_solo(&self) -> Option<(u32, u32)> {
+        let max_side = self.max_texture_side.max(1);
+        let monitor = get_primary_monitor_size();
+        if monitor.x > 0.0 && monitor.y > 0.0 {
+            let width = (monitor.x.ceil() as u32).clamp(1, max_side);
+            let height = (monitor.y.ceil() as u32).clamp(1, max_side);
+            Some((width, height))
+        } else {
+            self.live_video_output_bounds_for_solo()
+        }
     }
 
-    fn try_handle_video_priority_shortcuts(&mut self, ctx: &egui::Context) -> bool {
-        if self.manga_mode || !self.video_navigation_mode_active() {
-            return false;
+    fn start_async_video_load(&mut self, path: PathBuf) {
+        if !gstreamer_runtime_available() {
+            self.suppress_video_controls_for_next_video_load = false;
+            self.suppress_video_controls_for_request_id = None;
+            self.pending_media_load = None;
+            self.drop_retained_media_placeholder();
+            self.set_video_playback_unavailable_for_path(
+                &path,
+                Self::gstreamer_missing_video_error_text().to_string(),
+            );
+            return;
         }
 
-        let media_playing = if self.solo_video_playback_mode_active() {
-            self.solo_video_playing_active()
+        let request_id = self.next_media_load_request_id;
+        self.next_media_load_request_id = self.next_media_load_request_id.saturating_add(1).max(1);
+
+        if self.suppress_video_controls_for_next_video_load {
+            self.suppress_video_controls_for_request_id = Some(request_id);
         } else {
-            self.image.as_ref().is_some_and(|img| img.is_animated()) && !self.gif_paused
-        };
-        let (prev_pressed, next_pressed, pause_pressed) = ctx.input(|input| {
-            let ctrl = input.modifiers.ctrl;
-            let shift = input.modifiers.shift;
-            let alt = input.modifiers.alt;
+            self.suppress_video_controls_for_request_id = None;
+        }
+        self.suppress_video_controls_for_next_video_load = false;
 
-            let prev_pressed = media_playing
-                && self
-                    .config
-                    .video_priority_previous_file_binding
-                    .as_ref()
-                    .is_some_and(|binding| {
-                        self.binding_triggered(binding, input, ctrl, shift, alt)
-                    });
-            let next_pressed = media_playing
-                && self
-                    .config
-                    .video_priority_next_file_binding
-                    .as_ref()
-                    .is_some_and(|binding| {
-                        self.binding_triggered(binding, input, ctrl, shift, alt)
-                    });
-            let pause_pressed = self.solo_video_playback_mode_active()
-                && self
-                    .config
-                    .video_priority_play_pause_binding
-                    .as_ref()
-                    .is_some_and(|binding| {
-                        self.binding_triggered(binding, input, ctrl, shift, alt)
-                    });
+        let (muted, initial_volume) = self.initial_video_audio();
+        let (
+            prefer_hardware_decode,
+            disable_hardware_decode,
+            enable_cuda_decode,
+            enable_d3d12_decode,
+        ) = self.effective_video_decoder_preferences();
+        let output_bounds = self.async_video_output_bounds_for_solo();
+        let preload_settings = VideoPreloadSettings {
+            decoder_preferences: (
+                prefer_hardware_decode,
+                disable_hardware_decode,
+                enable_cuda_decode,
+                enable_d3d12_decode,
+            ),
+            output_bounds,
+        };
 
-            (prev_pressed, next_pressed, pause_pressed)
+        self.pending_media_load = Some(PendingMediaLoad {
+            request_id,
+            path: path.clone(),
+            kind: PendingMediaLoadKind::Video,
+            max_texture_side: output_bounds.map(|(width, height)| width.max(height)),
+            started_at: Instant::now(),
         });
 
-        if prev_pressed {
-            if self.config.videos_only_navigation {
-                self.suppress_video_controls_for_next_video_load = true;
-            }
-            self.navigate_prev_for_video_mode();
-            return true;
-        }
-        if next_pressed {
-            if self.config.videos_only_navigation {
-                self.suppress_video_controls_for_next_video_load = true;
-            }
-            self.navigate_next_for_video_mode();
-            return true;
-        }
-        if pause_pressed {
-            self.try_toggle_solo_video_play_pause();
-            return true;
-        }
+        let saved_position = self.manga_video_preview_resume_by_path.get(&path).copied();
 
-        false
-    }
+        // FIX: Destroy the 1st-frame thumbnail so the UI is forced to use our seamless masonry frame!
+        if saved_position.is_some() || self.pending_mode_switch_placeholder.is_some() {
+            self.pending_video_thumbnail_placeholder = None;
+        }
 
-    fn binding_triggered(
-        &self,
-        binding: &InputBinding,
-        input: &egui::InputState,
-        ctrl: bool,
-        shift: bool,
-        alt: bool,
-    ) -> bool {
-        match binding {
-            InputBinding::Key(key) => !ctrl && !shift && !alt && input.key_pressed(*key),
-            InputBinding::KeyWithCtrl(key) => ctrl && !shift && !alt && input.key_pressed(*key),
-            InputBinding::KeyWithShift(key) => !ctrl && shift && !alt && input.key_pressed(*key),
-            InputBinding::KeyWithAlt(key) => !ctrl && !shift && alt && input.key_pressed(*key),
-            InputBinding::MouseLeft => input.pointer.button_pressed(egui::PointerButton::Primary),
-            InputBinding::MouseRight => {
-                input.pointer.button_clicked(egui::PointerButton::Secondary)
+        if let Some(mut player) = self.take_preloaded_video(&path, preload_settings) {
+            player.set_muted(muted);
+            player.set_volume(initial_volume);
+            if let Some(seconds) = saved_position {
+                let _ = player.seek_to_time_with_mode(seconds, VideoSeekMode::Accurate);
+            }
+            match player.play() {
+                Ok(()) => {
+                    self.perf_metrics
+                        .increment_counter("load_media_preloaded_video", 1);
+                    self.preloaded_video_result = Some(MediaLoadResult::Video {
+                        request_id,
+                        path,
+                        result: Ok(player),
+                        worker_elapsed: Duration::ZERO,
+                    });
+                    return;
+                }
+                Err(err) => {
+                    tracing::debug!("Preloaded video failed to start, reopening: {err}");
+                }
             }
-            InputBinding::MouseMiddle => input.pointer.button_pressed(egui::PointerButton::Middle),
-            InputBinding::Mouse4 => input.pointer.button_pressed(egui::PointerButton::Extra1),
-            InputBinding::Mouse5 => input.pointer.button_pressed(egui::PointerButton::Extra2),
-            InputBinding::ScrollUp => input.smooth_scroll_delta.y > 0.0,
-            InputBinding::ScrollDown => input.smooth_scroll_delta.y < 0.0,
-            InputBinding::CtrlScrollUp
-            | InputBinding::CtrlScrollDown
-            | InputBinding::ShiftScrollUp
-            | InputBinding::ShiftScrollDown => false,
         }
-    }
 
-    fn binding_down(
-        &self,
-        binding: &InputBinding,
-        input: &egui::InputState,
-        ctrl: bool,
-        shift: bool,
-        alt: bool,
-    ) -> bool {
-        match binding {
-            InputBinding::Key(key) => !ctrl && !shift && !alt && input.key_down(*key),
-            InputBinding::KeyWithCtrl(key) => ctrl && !shift && !alt && input.key_down(*key),
-            InputBinding::KeyWithShift(key) => !ctrl && shift && !alt && input.key_down(*key),
-            InputBinding::KeyWithAlt(key) => !ctrl && !shift && alt && input.key_down(*key),
-            InputBinding::MouseLeft => input.pointer.button_down(egui::PointerButton::Primary),
-            InputBinding::MouseRight => input.pointer.button_down(egui::PointerButton::Secondary),
-            InputBinding::MouseMiddle => input.pointer.button_down(egui::PointerButton::Middle),
-            InputBinding::Mouse4 => input.pointer.button_down(egui::PointerButton::Extra1),
-            InputBinding::Mouse5 => input.pointer.button_down(egui::PointerButton::Extra2),
-            InputBinding::ScrollUp
-            | InputBinding::ScrollDown
-            | InputBinding::CtrlScrollUp
-            | InputBinding::CtrlScrollDown
-            | InputBinding::ShiftScrollUp
-            | InputBinding::ShiftScrollDown => false,
-        }
+        self.media_load_coordinator.submit(MediaLoadRequest::Video {
+            request_id,
+            path,
+            muted,
+            initial_volume,
+            prefer_hardware_decode,
+            disable_hardware_decode,
+            enable_cuda_decode,
+            enable_d3d12_decode,
+            output_bounds,
+            resume_position_secs: saved_position,
+        });
     }
 
-    fn mouse_binding_down(binding: &InputBinding, input: &egui::InputState) -> bool {
-        match binding {
-            InputBinding::MouseLeft => input.pointer.button_down(egui::PointerButton::Primary),
-            InputBinding::MouseRight => input.pointer.button_down(egui::PointerButton::Secondary),
-            InputBinding::MouseMiddle => input.pointer.button_down(egui::PointerButton::Middle),
-            InputBinding::Mouse4 => input.pointer.button_down(egui::PointerButton::Extra1),
-            InputBinding::Mouse5 => input.pointer.button_down(egui::PointerButton::Extra2),
-            _ => false,
-        }
+    /// Muted state and volume a newly opened video starts with.
+    fn initial_video_audio(&self) -> (bool, f64) {
+        let muted = if self.config.video_muted_remember {
+            self.config.state_muted
+        } else {
+            self.config.video_muted_by_default
+        };
+        let initial_volume = if self.config.video_volume_remember {
+            self.config.state_volume
+        } else {
+            self.config.video_default_volume
+        };
+        (muted, initial_volume)
     }
 
-    fn mouse_binding_triggered(binding: &InputBinding, input: &egui::InputState) -> bool {
-        match binding {
-            InputBinding::MouseLeft => input.pointer.button_pressed(egui::PointerButton::Primary),
-            InputBinding::MouseRight => {
-                input.pointer.button_clicked(egui::PointerButton::Secondary)
-            }
-            InputBinding::MouseMiddle => input.pointer.button_pressed(egui::PointerButton::Middle),
-            InputBinding::Mouse4 => input.pointer.button_pressed(egui::PointerButton::Extra1),
-            InputBinding::Mouse5 => input.pointer.button_pressed(egui::PointerButton::Extra2),
-            _ => false,
+    /// The preloaded player for `path` when it has finished pre-rolling with `settings`.
+    /// Any other preload is dropped.
+    fn take_preloaded_video(
+        &mut self,
+        path: &Path,
+        settings: VideoPreloadSettings,
+    ) -> Option<VideoPlayer> {
+        let preload = self.preloaded_video.take()?;
+        if preload.path != path || preload.settings != settings {
+            return None;
         }
+        preload.result_rx.try_recv().ok()?.ok()
     }
 
-    fn manga_page_mouse_repeat_trigger(
-        repeat_at: &mut Option<Instant>,
-        mouse_down: bool,
-        pressed: bool,
-        ctx: &egui::Context,
-    ) -> bool {
-        if !mouse_down {
-            *repeat_at = None;
-            return false;
+    /// Opens the next video in the folder on a worker and pauses it at its first frame, so
+    /// navigating to it swaps the player in without waiting for the pipeline to start.
+    fn preload_next_video(&mut self) {
+        if !self.config.preload_next_video || self.manga_mode {
+            self.preloaded_video = None;
+            return;
         }
+        let next_path = self
+            .rating_filtered_neighbor(true)
+            .filter(|index| *index != self.current_index)
+            .and_then(|index| self.image_list.get(index).cloned())
+            .filter(|path| get_media_type(path) == Some(MediaType::Video));
+        let Some(path) = next_path else {
+            self.preloaded_video = None;
+            return;
+        };
 
-        let now = Instant::now();
-        let initial_delay = Duration::from_millis(Self::MANGA_PAGE_NAV_REPEAT_INITIAL_DELAY_MS);
-        let repeat_interval = Duration::from_millis(Self::MANGA_PAGE_NAV_REPEAT_INTERVAL_MS);
-
-        if pressed {
-            *repeat_at = Some(now + initial_delay);
-            ctx.request_repaint_after(initial_delay);
-            return false;
+        let (muted, initial_volume) = self.initial_video_audio();
+        let decoder_preferences = self.effective_video_decoder_preferences();
+        let settings = VideoPreloadSettings {
+            decoder_preferences,
+            output_bounds: self.async_video_output_bounds_for_solo(),
+        };
+        if self
+            .preloaded_video
+            .as_ref()
+            .is_some_and(|preload| preload.path == path && preload.settings == settings)
+        {
+            return;
         }
 
-        match *repeat_at {
-            Some(due_at) if now >= due_at => {
-                *repeat_at = Some(now + repeat_interval);
-                ctx.request_repaint_after(repeat_interval);
-                true
-            }
-            Some(due_at) => {
-                ctx.request_repaint_after(due_at.saturating_duration_since(now));
-                false
-            }
-            None => {
-                *repeat_at = Some(now + initial_delay);
-                ctx.request_repaint_after(initial_delay);
-                false
+        let (result_tx, result_rx) = crossbeam_channel::bounded(1);
+        self.preloaded_video = Some(PreloadedVideo {
+            path: path.clone(),
+            settings,
+            result_rx,
+        });
+        crate::async_runtime::spawn_blocking_or_thread("video-preload", move || {
+            let (
+                prefer_hardware_decode,
+                disable_hardware_decode,
+                enable_cuda_decode,
+                enable_d3d12_decode,
+            ) = decoder_preferences;
+            let source_dimensions = cached_or_probe_video_dimensions(&path);
+            let output_dimensions =
+                video_output_dimensions_for_bounds(source_dimensions, settings.output_bounds);
+            let result = VideoPlayer::new(
+                &path,
+                muted,
+                initial_volume,
+                prefer_hardware_decode,
+                disable_hardware_decode,
+                enable_cuda_decode,
+                enable_d3d12_decode,
+                source_dimensions,
+                output_dimensions,
+            )
+            .and_then(|mut player| {
+                player.preroll(Self::VIDEO_PRELOAD_PREROLL_TIMEOUT)?;
+                Ok(player)
+            });
+            if let Err(err) = &result {
+                tracing::debug!("Could not preload {}: {err}", path.display());
             }
-        }
+            let _ = result_tx.send(result);
+        });
     }
 
-    fn manga_autoscroll_axis_speed(
-        &self,
-        delta: f32,
-        base_speed: f32,
-        max_axis_distance: f32,
-        axis_multiplier: f32,
-    ) -> f32 {
-        let dead_zone = self.config.manga_autoscroll_dead_zone_px.max(0.0);
-        let magnitude = delta.abs();
-        if magnitude <= dead_zone {
-            return 0.0;
-        }
+    fn poll_pending_media_load(&mut self, ctx: &egui::Context) {
+        let mut applied_any = false;
 
-        let base = (base_speed * self.config.manga_autoscroll_base_speed_multiplier).max(1.0);
-        let normalized_denominator = (max_axis_distance.max(1.0) - dead_zone).max(1.0);
-        let t = ((magnitude - dead_zone) / normalized_denominator).clamp(0.0, 1.0);
-        let curved = t.powf(self.config.manga_autoscroll_curve_power.clamp(0.5, 6.0));
+        loop {
+            let result = if let Some(result) = self.preloaded_video_result.take() {
+                result
+            } else {
+                match self.media_load_coordinator.try_recv() {
+                    Ok(result) => result,
+                    Err(crossbeam_channel::TryRecvError::Empty) => break,
+                    Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                        self.drop_retained_media_placeholder();
+                        self.clear_pending_media_load();
+                        break;
+                    }
+                }
+            };
 
-        let min_speed = (base * self.config.manga_autoscroll_min_speed_multiplier)
-            .max(self.config.manga_autoscroll_min_speed_px_per_sec)
-            .max(0.0);
-        let mut max_speed = (base * self.config.manga_autoscroll_max_speed_multiplier)
-            .min(self.config.manga_autoscroll_max_speed_px_per_sec)
-            .max(1.0);
+            let (result_request_id, result_path, worker_elapsed) = match &result {
+                MediaLoadResult::ImagePreview {
+                    request_id, path, ..
+                } => (*request_id, path, Duration::ZERO),
+                MediaLoadResult::Image {
+                    request_id,
+                    path,
+                    worker_elapsed,
+                    ..
+                } => (*request_id, path, *worker_elapsed),
+                MediaLoadResult::Video {
+                    request_id,
+                    path,
+                    worker_elapsed,
+                    ..
+                } => (*request_id, path, *worker_elapsed),
+            };
 
-        if max_speed < min_speed {
-            max_speed = min_speed;
-        }
+            let Some(pending) = self.pending_media_load.as_ref() else {
+                continue;
+            };
 
-        let axis_multiplier = axis_multiplier.max(0.05);
-        let speed = (min_speed + (max_speed - min_speed) * curved) * axis_multiplier;
-        speed.copysign(delta)
-    }
+            if result_request_id != pending.request_id || result_path != &pending.path {
+                self.perf_metrics
+                    .increment_counter("load_media_async_stale", 1);
+                continue;
+            }
 
-    fn stop_fullscreen_video_playback(&mut self) {
-        if let Some(player) = self.video_player.take() {
-            drop(player);
-        }
-        self.show_video_controls = false;
-    }
+            if let MediaLoadResult::ImagePreview { image, .. } = result {
+                // Show the preview and keep waiting for the full decode.
+                self.consume_deferred_media_view_reset();
+                self.clear_current_image_texture_upload();
+                self.image = Some(image);
+                self.retained_media_placeholder_visible = false;
+                self.image_changed = true;
+                self.pending_media_layout = false;
+                self.error_message = None;
+                applied_any = true;
+                continue;
+            }
 
-    fn reset_fullscreen_anim_stream_state(&mut self) {
-        self.anim_stream_rx = None;
-        self.anim_stream_path = None;
-        self.anim_stream_done = true;
-        self.anim_seekbar_total_frames = None;
-    }
+            let Some(pending) = self.pending_media_load.take() else {
+                continue;
+            };
 
-    fn reset_gif_seek_interaction_state(&mut self) {
-        self.gif_seeking = false;
-        self.gif_seek_preview_frame = None;
-    }
+            let total_elapsed = pending.started_at.elapsed();
+            self.perf_metrics
+                .record_duration("load_media_async_ms", total_elapsed);
+            self.perf_metrics
+                .record_duration("load_media_async_worker_ms", worker_elapsed);
+            self.perf_metrics.record_duration(
+                "load_media_async_queue_ms",
+                total_elapsed.saturating_sub(worker_elapsed),
+            );
+
+            match result {
+                // Handled above, without taking the pending load.
+                MediaLoadResult::ImagePreview { .. } => {}
+                MediaLoadResult::Image { path, result, .. } => match result {
+                    Ok(loaded) => {
+                        self.consume_deferred_media_view_reset();
+                        self.retained_media_placeholder_visible = false;
+                        let (display_w, display_h) = loaded.image.display_dimensions();
+                        if display_w > 0 && display_h > 0 {
+                            store_cached_dimensions(
+                                &path,
+                                CachedMediaKind::Image,
+                                display_w,
+                                display_h,
+                            );
+                        }
+
+                        self.cache_loaded_image_first_frame(
+                            &path,
+                            loaded.max_texture_side,
+                            &loaded.image,
+                            loaded.is_animated_webp,
+                        );
+                        self.clear_current_image_texture_upload();
+                        self.image = Some(loaded.image);
+                        self.image_changed = true;
+                        self.pending_media_layout = false;
+                        self.error_message = None;
+                        self.clear_video_playback_unavailable_state();
+                        if !self.defer_directory_work_for_fast_startup() {
+                            self.schedule_solo_probe_window(&path, Some(MediaType::Image));
+                        }
+
+                        if loaded.is_animated_webp {
+                            if let Some(rx) = LoadedImage::start_streaming_webp(
+                                &path,
+                                Some(loaded.max_texture_side),
+                                loaded.gif_filter,
+                            ) {
+                                self.anim_stream_rx = Some(rx);
+                                self.anim_stream_path = Some(path);
+                                self.anim_stream_done = false;
+                                self.anim_seekbar_total_frames = Some(
+                                    self.image
+                                        .as_ref()
+                                        .map(|image| image.frame_count())
+                                        .unwrap_or(1),
+                                );
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        self.drop_retained_media_placeholder();
+                        self.error_message = Some(err);
+                    }
+                },
+                MediaLoadResult::Video { path, result, .. } => {
+                    let suppress_controls_reveal =
+                        self.suppress_video_controls_for_request_id == Some(result_request_id);
+                    if suppress_controls_reveal {
+                        self.suppress_video_controls_for_request_id = None;
+                    }
+
+                    match result {
+                        Ok(mut player) => {
+                            let resume_position_secs = self
+                                .manga_video_preview_resume_by_path
+                                .get(&path)
+                                .copied()
+                                .or_else(|| {
+                                    self.image_list
+                                        .iter()
+                                        .position(|candidate| candidate == &path)
+                                        .and_then(|idx| {
+                                            self.manga_video_preview_resume_secs.get(&idx).copied()
+                                        })
+                                })
+                                .filter(|secs| secs.is_finite() && *secs >= 0.0);
+                            let mut resume_position =
+                                resume_position_secs.map(Duration::from_secs_f64);
+                            self.video_resume_offer = None;
+                            if resume_position.is_none() {
+                                match self.config.video_resume {
+                                    ResumePolicy::Always => {
+                                        resume_position = video_resume::lookup(&path);
+                                        if let Some(position) = resume_position {
+                                            self.show_toast(i18n::tr_args(
+                                                "toast.resumed_at",
+                                                &[("time", &format_duration(position))],
+                                            ));
+                                        }
+                                    }
+                                    ResumePolicy::Ask => {
+                                        self.video_resume_offer =
+                                            video_resume::lookup(&path).map(|position| {
+                                                VideoResumeOffer {
+                                                    path: path.clone(),
+                                                    position,
+                                                    until: Instant::now()
+                                                        + Self::VIDEO_RESUME_OFFER_DURATION,
+                                                }
+                                            });
+                                    }
+                                    ResumePolicy::Never => {}
+                                }
+                            }
+                            Self::seek_video_player_to_resume_position(
+                                &mut player,
+                                resume_position,
+                            );
 
-    fn ensure_manga_loader(&mut self) {
-        if self.manga_loader.is_none() {
-            self.manga_loader = Some(MangaLoader::new());
-        }
-    }
+                            let dims = player.dimensions();
+                            if dims.0 > 0 && dims.1 > 0 {
+                                store_cached_dimensions(
+                                    &path,
+                                    CachedMediaKind::Video,
+                                    dims.0,
+                                    dims.1,
+                                );
+                            }
 
-    fn reset_manga_video_user_preferences(&mut self) {
-        self.manga_video_user_muted = None;
-        self.manga_video_user_volume = None;
-    }
+                            self.video_player = Some(player);
+                            self.current_video_path = Some(path.clone());
+                            self.error_message = None;
+                            self.clear_video_playback_unavailable_state();
+                            if !suppress_controls_reveal {
+                                self.show_video_controls = true;
+                                self.touch_bottom_overlays();
+                            }
 
-    fn set_strip_entry_placeholder_from_current_media(
-        &mut self,
-        current_media_type: Option<MediaType>,
-    ) {
-        let placeholder_path = match current_media_type {
-            Some(MediaType::Image) if self.texture.is_some() => self
-                .image
-                .as_ref()
-                .map(|img| img.path.clone())
-                .or_else(|| self.current_media_path()),
-            Some(MediaType::Video) if self.video_texture.is_some() => self
-                .current_video_path
-                .clone()
-                .or_else(|| self.current_media_path()),
-            _ => None,
-        };
+                            if self.defer_media_view_reset {
+                                self.pending_media_layout = false;
+                            } else {
+                                self.retained_media_placeholder_visible = false;
+                                self.image_changed = true;
+                                self.pending_media_layout = true;
+                            }
 
-        self.strip_entry_placeholder_index = placeholder_path.as_ref().and_then(|path| {
-            self.image_list
-                .iter()
-                .position(|candidate| candidate == path)
-        });
-        self.strip_entry_placeholder_path = placeholder_path;
-    }
+                            if !self.defer_directory_work_for_fast_startup() {
+                                self.schedule_solo_probe_window(&path, Some(MediaType::Video));
+                            }
+                            self.preload_next_video();
+                        }
+                        Err(err) => {
+                            if self.retained_media_placeholder_visible {
+                                self.drop_retained_media_placeholder();
+                            }
+                            self.error_message = None;
+                            self.set_video_playback_unavailable_for_path(
+                                &path,
+                                format!("Failed to load video: {}", err),
+                            );
+                            if !suppress_controls_reveal {
+                                self.show_video_controls = true;
+                                self.touch_bottom_overlays();
+                            }
+                        }
+                    }
+                }
+            }
 
-    fn strip_entry_placeholder_matches(&self, index: usize) -> bool {
-        self.strip_entry_placeholder_index == Some(index)
-            && self
-                .strip_entry_placeholder_path
-                .as_ref()
-                .is_some_and(|path| self.image_list.get(index) == Some(path))
-    }
+            applied_any = true;
+        }
 
-    fn strip_entry_video_texture_matches_placeholder_path(&self) -> bool {
-        self.video_texture_source_path
-            .as_ref()
-            .and_then(|texture_path| {
-                self.strip_entry_placeholder_path
-                    .as_ref()
-                    .map(|placeholder_path| texture_path == placeholder_path)
-            })
-            .unwrap_or(false)
+        if applied_any {
+            ctx.request_repaint();
+        }
     }
 
-    fn strip_entry_image_texture_matches_placeholder_path(&self) -> bool {
-        self.image
-            .as_ref()
-            .and_then(|img| {
-                self.strip_entry_placeholder_path
-                    .as_ref()
-                    .map(|placeholder_path| &img.path == placeholder_path)
-            })
-            .unwrap_or(false)
+    fn load_image_retaining_visible_media(&mut self, path: &PathBuf) {
+        self.load_media_internal(path, true);
     }
 
-    fn manga_video_texture_matches(&self, index: usize) -> bool {
-        self.manga_video_texture_paths
-            .get(&index)
-            .is_some_and(|path| self.image_list.get(index) == Some(path))
+    /// Load an image from path
+    fn load_image(&mut self, path: &PathBuf) {
+        self.load_media_internal(path, false);
     }
 
-    fn manga_video_player_matches(&self, index: usize) -> bool {
-        self.manga_video_player_paths
-            .get(&index)
-            .is_some_and(|path| self.image_list.get(index) == Some(path))
+    /// Load any media (image or video) from path
+    fn load_media(&mut self, path: &PathBuf) {
+        self.load_media_internal(path, false);
     }
 
-    fn remove_manga_video_player(&mut self, index: usize) -> Option<VideoPlayer> {
-        self.manga_video_player_paths.remove(&index);
-        self.manga_video_players.remove(&index)
-    }
+    fn load_media_internal(&mut self, path: &PathBuf, retain_visible_media_until_ready: bool) {
+        if playlist::is_playlist_file(path) {
+            self.open_playlist_file(path);
+            return;
+        }
+        if let Some(url) = path
+            .to_str()
+            .and_then(remote::parse_url)
+            .filter(|url| !remote::is_video_url(url))
+        {
+            self.open_image_url(url);
+            return;
+        }
+        if let Some(download) = self.download.take() {
+            // Another item was opened; the download may still be wanted ahead of time.
+            self.url_prefetch.adopt(download);
+        }
+        let load_media_start = Instant::now();
+        if !retain_visible_media_until_ready {
+            self.set_solo_preload_momentum(SoloPreloadMomentum::Neutral);
+        }
 
-    fn clear_manga_video_players(&mut self) {
-        self.manga_video_players.clear();
-        self.manga_video_player_paths.clear();
-    }
+        if !self.manga_mode
+            && self.manga_layout_mode == MangaLayoutMode::Masonry
+            && self.has_resident_masonry_runtime_cache()
+        {
+            self.pause_masonry_metadata_preload();
+        } else {
+            self.reset_masonry_metadata_preload();
+        }
+        self.clear_pending_media_load();
+        self.pending_video_thumbnail_placeholder = None;
+        self.clear_video_playback_unavailable_state();
 
-    fn remove_manga_video_texture(&mut self, index: usize) {
-        self.manga_video_textures.remove(&index);
-        self.manga_video_texture_paths.remove(&index);
-    }
+        self.current_file_size_label = None;
+        self.current_file_size_label_path = None;
+        self.pending_file_size_probe = None;
+        self.pending_file_size_probe_path = None;
 
-    fn clear_manga_video_textures(&mut self) {
-        self.manga_video_textures.clear();
-        self.manga_video_texture_paths.clear();
-    }
+        // Update the native window title (taskbar title) using Unicode-safe conversion.
+        self.pending_window_title = Some(self.compute_window_title_for_path(path));
 
-    fn manga_media_type_for_current_media(
-        media_type: MediaType,
-        current_image_is_animated: bool,
-    ) -> MangaMediaType {
-        match media_type {
-            MediaType::Video => MangaMediaType::Video,
-            MediaType::Image => {
-                if current_image_is_animated {
-                    MangaMediaType::AnimatedImage
+        // Determine media type up-front so we can decide whether to keep a placeholder frame.
+        let is_folder_entry = self.is_folder_navigation_entry_path(path.as_path());
+        let media_type = if is_folder_entry {
+            Some(MediaType::Image)
+        } else if remote::is_url(path) {
+            // Only video URLs get here; images are downloaded first.
+            Some(MediaType::Video)
+        } else {
+            get_media_type(path)
+        };
+        self.current_media_type = media_type;
+        self.current_video_path =
+            matches!(media_type, Some(MediaType::Video)).then(|| path.clone());
+        if !is_folder_entry
+            && media_type.is_some()
+            && self.usage_stats_last_viewed_path.as_ref() != Some(path)
+        {
+            usage_stats::record_file_view(matches!(media_type, Some(MediaType::Video)));
+            self.usage_stats_last_viewed_path = Some(path.clone());
+        }
+
+        let mut used_mode_switch_placeholder = false;
+        let transition_placeholder = self
+            .pending_mode_switch_placeholder
+            .take()
+            .filter(|placeholder| {
+                let matches_target = Some(placeholder.media_type) == media_type;
+                if matches_target {
+                    used_mode_switch_placeholder = true;
+                }
+                matches_target
+            })
+            .or_else(|| {
+                if retain_visible_media_until_ready
+                    && Self::retain_visible_media_placeholder_for_swap(
+                        self.is_fullscreen,
+                        media_type,
+                    )
+                {
+                    self.capture_current_media_placeholder(media_type)
                 } else {
-                    MangaMediaType::StaticImage
+                    None
                 }
-            }
+            });
+        let keep_current_view_until_swap =
+            retain_visible_media_until_ready && transition_placeholder.is_some();
+
+        // Clear previous media state.
+        // When a placeholder was captured above we immediately restore it after clearing
+        // the current decode state so the visible frame stays on screen during navigation.
+        // MEMORY OPTIMIZATION: Explicitly drop textures to release GPU memory immediately.
+        // Setting to None allows Rust to drop the TextureHandle, which signals egui to
+        // free the underlying GPU texture on the next frame.
+        self.stop_fullscreen_video_playback();
+        if let Some(texture) = self.video_texture.take() {
+            drop(texture);
         }
-    }
+        self.video_texture_source_path = None;
+        self.video_texture_dims = None;
+        if let Some(texture) = self.texture.take() {
+            drop(texture);
+        }
+        self.image_texture_dims = None;
+        self.image = None;
+        self.retained_media_placeholder_visible = transition_placeholder.is_some();
 
-    fn cache_current_media_dimensions_for_manga(
-        &mut self,
-        current_media_dims: Option<(u32, u32)>,
-        current_media_type: Option<MediaType>,
-        current_image_is_animated: bool,
-    ) -> bool {
-        if self.is_masonry_mode() && self.masonry_authoritative_dimension_lock_active() {
-            return false;
+        if let Some(placeholder) = transition_placeholder {
+            match placeholder.media_type {
+                MediaType::Image => {
+                    self.texture = Some(placeholder.texture);
+                    self.image_texture_dims = Some(placeholder.dims);
+                }
+                MediaType::Video => {
+                    self.video_texture = Some(placeholder.texture);
+                    self.video_texture_source_path = self
+                        .current_video_path
+                        .clone()
+                        .or_else(|| self.current_media_path());
+                    self.video_texture_dims = Some(placeholder.dims);
+                }
+            }
         }
 
-        let (Some((w, h)), Some(media_type)) = (current_media_dims, current_media_type) else {
-            return false;
-        };
-
-        let manga_media_type =
-            Self::manga_media_type_for_current_media(media_type, current_image_is_animated);
+        // Cancel any in-flight background animation stream.
+        self.reset_fullscreen_anim_stream_state();
 
-        if let Some(ref mut loader) = self.manga_loader {
-            let new_entry = (w, h, manga_media_type);
+        // Reset GIF playback state for new media
+        self.gif_paused = false;
+        self.gif_seeking = false;
+        self.gif_seek_preview_frame = None;
 
-            if media_type == MediaType::Video {
-                if let Some((cached_w, cached_h, MangaMediaType::Video)) =
-                    loader.dimension_cache.get(&self.current_index).copied()
-                {
-                    let cached_pixels = cached_w as u64 * cached_h as u64;
-                    let new_pixels = w as u64 * h as u64;
-                    let cached_aspect = cached_w as f32 / cached_h.max(1) as f32;
-                    let new_aspect = w as f32 / h.max(1) as f32;
+        if keep_current_view_until_swap {
+            self.freeze_current_media_view();
+            self.defer_media_view_reset = true;
+        } else {
+            self.reset_media_view_for_swap();
+            self.defer_media_view_reset = false;
 
-                    if cached_w > 0
-                        && cached_h > 0
-                        && new_pixels < cached_pixels
-                        && (cached_aspect - new_aspect).abs() <= 0.01
-                    {
-                        return false;
-                    }
-                }
+            if used_mode_switch_placeholder {
+                self.image_changed = true;
             }
+        }
+        self.error_message = None;
 
-            let changed =
-                loader.dimension_cache.get(&self.current_index).copied() != Some(new_entry);
-            loader.dimension_cache.insert(self.current_index, new_entry);
-            return changed;
+        let defer_directory_work_for_fast_startup = self.defer_directory_work_for_fast_startup();
+        if !defer_directory_work_for_fast_startup {
+            self.start_async_file_size_probe(path.clone());
         }
 
-        false
-    }
+        // Reuse cached directory listing when the parent folder is unchanged.
+        let index_stats_before = self.media_directory_index.stats();
+        let index_lookup_start = Instant::now();
 
-    fn prepare_enter_manga_mode_state(&mut self, current_media_type: Option<MediaType>) {
-        self.set_strip_entry_placeholder_from_current_media(current_media_type);
-        self.stop_manga_wheel_scroll();
-        self.stop_manga_autoscroll();
-        self.reset_gif_seek_interaction_state();
-        if self.manga_layout_mode == MangaLayoutMode::Masonry {
-            self.pause_masonry_metadata_preload();
+        self.pending_media_directory_scan = None;
+        self.pending_media_directory_target = None;
+        self.pending_media_directory_scan_kind = None;
+        self.pending_media_directory_started_at = None;
+
+        let playlist_items = self
+            .playlist
+            .as_ref()
+            .filter(|playlist| playlist.contains(path))
+            .map(|playlist| playlist.items().to_vec());
+        if let Some(items) = playlist_items {
+            self.set_image_list_raw(items);
+        } else if remote::is_url(path) || remote::is_download(path) {
+            // Streamed and downloaded media have no folder to browse.
+            self.set_image_list_raw(vec![path.clone()]);
+        } else if defer_directory_work_for_fast_startup {
+            self.set_image_list(vec![path.clone()]);
         } else {
-            self.reset_masonry_metadata_preload();
+            if let Some(files) = self.media_directory_index.try_cached_media_for_path(path) {
+                self.set_image_list(files);
+            } else {
+                // Keep current media navigable immediately while the full directory scan runs in background.
+                self.set_image_list(vec![path.clone()]);
+                let _ = self
+                    .begin_media_directory_scan(path, PendingMediaDirectoryScanKind::InitialLoad);
+            }
         }
-        self.manga_mode = true;
-        set_metadata_cache_enabled(Self::layout_mode_uses_metadata_cache(
-            self.manga_layout_mode,
-        ));
-        self.stop_fullscreen_video_playback();
-        self.reset_fullscreen_anim_stream_state();
-        self.reset_manga_video_user_preferences();
-        self.ensure_manga_loader();
-    }
 
-    fn reset_masonry_metadata_preload(&mut self) {
-        self.masonry_metadata_preload_active = false;
-        self.masonry_metadata_preload_total = 0;
-        self.masonry_metadata_preload_loaded = 0;
-        self.masonry_metadata_preload_cursor = 0;
-        self.masonry_metadata_preload_list_signature = 0;
-        self.masonry_metadata_preload_restore_index = None;
-        self.masonry_metadata_preload_overlay_hold_until = None;
-        self.masonry_metadata_preload_defer_first_tick = false;
-        self.masonry_metadata_preload_stall_since = None;
-        self.pending_masonry_folder_travel_restore = None;
-    }
+        if self.image_list.is_empty() {
+            self.set_image_list(vec![path.clone()]);
+        }
 
-    fn pause_masonry_metadata_preload(&mut self) {
-        let total = self.masonry_metadata_preload_total;
-        let can_resume = total > 0
-            && self.masonry_metadata_preload_loaded < total
-            && self.masonry_metadata_preload_list_signature == self.image_list_signature;
+        self.perf_metrics
+            .record_duration("media_index_lookup_ms", index_lookup_start.elapsed());
+        let index_stats_after = self.media_directory_index.stats();
+        if index_stats_after.hits > index_stats_before.hits {
+            self.perf_metrics.increment_counter(
+                "media_index_hits",
+                index_stats_after.hits - index_stats_before.hits,
+            );
+        }
+        if index_stats_after.misses > index_stats_before.misses {
+            self.perf_metrics.increment_counter(
+                "media_index_misses",
+                index_stats_after.misses - index_stats_before.misses,
+            );
+        }
+        self.set_current_index_clamped(
+            self.image_list
+                .iter()
+                .position(|candidate| candidate == path)
+                .unwrap_or(0),
+        );
 
-        self.masonry_metadata_preload_active = false;
-        self.masonry_metadata_preload_overlay_hold_until = None;
-        self.masonry_metadata_preload_defer_first_tick = false;
-        self.masonry_metadata_preload_stall_since = None;
+        match media_type {
+            Some(MediaType::Video) => {
+                if !gstreamer_runtime_available() {
+                    self.gstreamer_initialized = false;
+                    self.drop_retained_media_placeholder();
+                    self.set_video_playback_unavailable_for_path(
+                        path,
+                        Self::gstreamer_missing_video_error_text().to_string(),
+                    );
+                    self.show_video_controls = false;
+                    self.image_changed = true;
+                    self.pending_media_layout = false;
+                    self.perf_metrics
+                        .record_duration("load_media_prepare_ms", load_media_start.elapsed());
+                    return;
+                }
 
-        if !can_resume {
-            self.masonry_metadata_preload_total = 0;
-            self.masonry_metadata_preload_loaded = 0;
-            self.masonry_metadata_preload_cursor = 0;
-            self.masonry_metadata_preload_list_signature = 0;
-            self.masonry_metadata_preload_restore_index = None;
-            self.pending_masonry_folder_travel_restore = None;
-        }
-    }
+                // Mark GStreamer as initialized (it will be lazily initialized on first use)
+                self.gstreamer_initialized = true;
 
-    fn begin_masonry_metadata_preload(&mut self) {
-        let total = self.image_list.len();
-        let resume_preload = self.masonry_metadata_preload_total == total
-            && self.masonry_metadata_preload_loaded < total
-            && self.masonry_metadata_preload_list_signature == self.image_list_signature;
+                self.start_async_video_load(path.clone());
+            }
+            Some(MediaType::Image) => {
+                if is_folder_entry {
+                    self.consume_deferred_media_view_reset();
+                    self.drop_retained_media_placeholder();
+                    self.image = Some(Self::build_folder_placeholder_image(
+                        path.clone(),
+                        Self::is_up_navigation_entry_path(path.as_path()),
+                    ));
+                    self.texture = None;
+                    self.image_texture_dims = Some((512, 512));
+                    self.show_video_controls = false;
+                    self.error_message = None;
+                    self.image_changed = true;
+                    self.pending_media_layout = false;
+                    self.perf_metrics
+                        .record_duration("load_media_prepare_ms", load_media_start.elapsed());
+                    return;
+                }
 
-        self.masonry_metadata_preload_total = total;
-        self.masonry_metadata_preload_list_signature = self.image_list_signature;
+                // Load as image with configured filters.
+                // For animated WebP we only decode the FIRST frame here so the
+                // window appears instantly, then start streaming remaining frames
+                // in the background so the animation begins playing progressively.
+                let downscale_filter = self.config.downscale_filter.to_image_filter();
+                let gif_filter = self.config.gif_resize_filter.to_image_filter();
+                let target_lod_side =
+                    self.solo_target_texture_side_for_path(path, MediaType::Image, true);
+                let max_tex =
+                    Self::solo_image_load_texture_side(target_lod_side, self.max_texture_side);
 
-        if resume_preload {
-            self.masonry_metadata_preload_loaded = self.masonry_metadata_preload_loaded.min(total);
-            self.masonry_metadata_preload_cursor = self
-                .masonry_metadata_preload_cursor
-                .min(total.saturating_sub(1));
-            self.masonry_metadata_preload_restore_index = self
-                .masonry_metadata_preload_restore_index
-                .map(|index| index.min(total.saturating_sub(1)))
-                .or_else(|| Some(self.current_index.min(total.saturating_sub(1))));
-        } else {
-            self.masonry_metadata_preload_loaded = 0;
-            self.masonry_metadata_preload_restore_index = if self.image_list.is_empty() {
-                None
-            } else {
-                Some(
-                    self.current_index
-                        .min(self.image_list.len().saturating_sub(1)),
-                )
-            };
-            let preload_window = 96usize.max(self.masonry_items_per_row.clamp(2, 10) * 48);
-            self.masonry_metadata_preload_cursor = self
-                .current_index
-                .min(self.masonry_metadata_preload_total.saturating_sub(1))
-                .saturating_sub(preload_window / 2);
-            self.pending_masonry_folder_travel_restore = None;
+                if self.try_load_image_from_decoded_cache(path, max_tex, gif_filter) {
+                    if !defer_directory_work_for_fast_startup {
+                        self.schedule_solo_probe_window(path, media_type);
+                    }
+                    self.perf_metrics
+                        .record_duration("load_media_prepare_ms", load_media_start.elapsed());
+                    return;
+                }
+
+                if self.try_load_image_from_thumbnail_cache(path, max_tex) {
+                    if !defer_directory_work_for_fast_startup {
+                        self.schedule_solo_probe_window(path, media_type);
+                    }
+                    self.perf_metrics
+                        .record_duration("load_media_prepare_ms", load_media_start.elapsed());
+                    return;
+                }
+
+                if !self.is_fullscreen {
+                    self.pending_media_layout = true;
+                }
+                self.start_async_image_load(path.clone(), max_tex, downscale_filter, gif_filter);
+            }
+            None => {
+                self.drop_retained_media_placeholder();
+                self.error_message = Some(i18n::tr_args(
+                    "error.unsupported_format",
+                    &[("path", &path.display())],
+                ));
+            }
         }
 
-        self.masonry_metadata_preload_active = self.manga_mode
-            && self.is_masonry_mode()
-            && self.masonry_metadata_preload_total > 0
-            && self.manga_loader.is_some();
+        if media_type.is_some()
+            && !is_folder_entry
+            && !defer_directory_work_for_fast_startup
+            && self.pending_media_load.is_none()
+        {
+            self.schedule_solo_probe_window(path, media_type);
+        }
 
-        if !self.masonry_metadata_preload_active {
-            self.masonry_metadata_preload_restore_index = None;
-            self.masonry_metadata_preload_overlay_hold_until = None;
-            self.masonry_metadata_preload_defer_first_tick = false;
+        self.perf_metrics
+            .record_duration("load_media_prepare_ms", load_media_start.elapsed());
+    }
+
+    /// Save the current view state for the current image (fullscreen only).
+    /// This allows restoring zoom, pan, and rotation when returning to this image.
+    fn save_current_fullscreen_view_state(&mut self) {
+        if !self.is_fullscreen || !self.current_fullscreen_view_has_memory {
             return;
         }
 
-        self.masonry_metadata_preload_overlay_hold_until =
-            Some(Instant::now() + Duration::from_millis(220));
-        self.masonry_metadata_preload_defer_first_tick = true;
-        self.masonry_metadata_preload_stall_since = None;
+        let Some(path) = self.image_list.get(self.current_index).cloned() else {
+            return;
+        };
 
-        self.manga_scrollbar_dragging = false;
-        self.is_panning = false;
-        self.last_mouse_pos = None;
-        self.manga_hovered_media_index = None;
-        self.manga_zoom_plus_held = false;
-        self.manga_zoom_minus_held = false;
-        self.manga_video_seeking = false;
-        self.manga_video_volume_dragging = false;
-        self.gif_seeking = false;
-        self.manga_scroll_target = self.manga_scroll_offset;
-        self.manga_scroll_velocity = 0.0;
-        self.stop_manga_wheel_scroll();
-        self.stop_manga_autoscroll();
+        let state = self.current_view_state();
+        self.fullscreen_view_states.insert(path, state);
     }
 
-    fn masonry_metadata_overlay_visible(&self) -> bool {
-        if self.masonry_metadata_preload_active {
-            return true;
+    fn current_view_state(&self) -> FullscreenViewState {
+        FullscreenViewState {
+            zoom: self.zoom,
+            zoom_target: self.zoom_target,
+            offset: self.offset,
+            precise_rotation_degrees: self.precise_rotation_degrees,
+            precise_rotation_target_degrees: self.precise_rotation_target_degrees,
+            rotation_steps: self.current_rotation_steps,
+            flip_horizontal: self.flip_horizontal,
+            flip_vertical: self.flip_vertical,
         }
+    }
 
-        self.masonry_metadata_preload_overlay_hold_until
-            .is_some_and(|hold_until| Instant::now() < hold_until)
+    /// Puts back a saved view, rotating the freshly loaded image to match it.
+    fn apply_view_state(&mut self, state: &FullscreenViewState) {
+        self.zoom = state.zoom;
+        self.zoom_target = state.zoom_target;
+        self.offset = state.offset;
+        self.zoom_velocity = 0.0;
+        self.current_rotation_steps = state.rotation_steps;
+        self.precise_rotation_degrees = state.precise_rotation_degrees;
+        self.precise_rotation_target_degrees = state.precise_rotation_target_degrees;
+        self.precise_rotation_velocity = 0.0;
+        self.flip_horizontal = state.flip_horizontal;
+        self.flip_vertical = state.flip_vertical;
+
+        // Apply saved rotations if image was reloaded
+        if let Some(ref mut img) = self.image {
+            for _ in 0..state.rotation_steps {
+                img.rotate_clockwise();
+            }
+            if state.rotation_steps > 0 {
+                self.texture = None; // Force texture rebuild
+            }
+        }
     }
 
-    fn maybe_begin_masonry_metadata_preload(&mut self, allow_startup_preload: bool) {
-        if self.image_list.is_empty() {
-            self.reset_masonry_metadata_preload();
+    fn remember_current_fullscreen_view_state(&mut self) {
+        if !self.is_fullscreen || self.manga_mode {
             return;
         }
-        if self.manga_layout_mode != MangaLayoutMode::Masonry {
-            self.pause_masonry_metadata_preload();
+
+        self.current_fullscreen_view_has_memory = true;
+        self.save_current_fullscreen_view_state();
+    }
+
+    fn clear_current_fullscreen_view_memory(&mut self) {
+        self.current_fullscreen_view_has_memory = false;
+
+        let Some(path) = self.image_list.get(self.current_index).cloned() else {
             return;
+        };
+
+        self.fullscreen_view_states.remove(&path);
+    }
+
+    /// Restore the saved view state for a given image path (fullscreen only).
+    /// Returns true if state was restored, false if no saved state exists.
+    fn restore_fullscreen_view_state(&mut self, path: &PathBuf) -> bool {
+        if !self.is_fullscreen {
+            return false;
         }
 
-        let total = self.image_list.len();
-        let folder_ready = self
-            .image_list
-            .iter()
-            .filter(|path| self.is_folder_navigation_entry_path(path.as_path()))
-            .count();
-        let fully_warm = self.manga_loader.as_ref().is_some_and(|loader| {
-            loader
-                .cached_dimensions_count(total)
-                .saturating_add(folder_ready)
-                >= total
-                && loader.pending_dimension_probe_count() == 0
-                && loader.pending_dimension_results_count() == 0
-        });
+        if let Some(state) = self.fullscreen_view_states.get(path).cloned() {
+            self.apply_view_state(&state);
+            self.current_fullscreen_view_has_memory = true;
 
-        if fully_warm || !allow_startup_preload {
-            self.reset_masonry_metadata_preload();
+            true
         } else {
-            self.begin_masonry_metadata_preload();
+            false
         }
     }
 
-    fn restore_masonry_scroll_after_metadata_preload(&mut self) {
-        if !self.manga_mode || !self.is_masonry_mode() || self.image_list.is_empty() {
-            self.masonry_metadata_preload_restore_index = None;
-            self.pending_masonry_folder_travel_restore = None;
+    /// Update the discrete 90° rotation count for the current image.
+    /// When fullscreen is active, also sync it into the per-image fullscreen state cache.
+    fn update_fullscreen_rotation(&mut self, clockwise: bool) {
+        if clockwise {
+            self.current_rotation_steps = (self.current_rotation_steps + 1) % 4;
+        } else {
+            self.current_rotation_steps = (self.current_rotation_steps + 3) % 4;
+        }
+
+        if !self.is_fullscreen {
             return;
         }
 
-        if let Some(target_index) = self.masonry_metadata_preload_restore_index.take() {
-            let target_index = target_index.min(self.image_list.len().saturating_sub(1));
-            self.set_current_index_clamped(target_index);
+        self.remember_current_fullscreen_view_state();
+    }
 
-            let max_scroll = (self.manga_total_height() - self.screen_size.y).max(0.0);
-            let scroll_to = self
-                .masonry_scroll_offset_for_index_centered(target_index)
-                .unwrap_or_else(|| {
-                    self.manga_get_scroll_offset_for_index(target_index)
-                        .clamp(0.0, max_scroll)
-                });
+    fn normalize_precise_rotation_degrees(degrees: f32) -> f32 {
+        (degrees + 180.0).rem_euclid(360.0) - 180.0
+    }
 
-            self.manga_scroll_offset = scroll_to;
-            self.manga_scroll_target = scroll_to;
-            self.manga_scroll_velocity = 0.0;
-            self.manga_scrollbar_dragging = false;
-            self.masonry_scrollbar_last_motion_at = None;
-            self.masonry_autoscroll_last_motion_at = None;
-            self.is_panning = false;
-            self.last_mouse_pos = None;
-            self.manga_hovered_media_index = None;
-            self.stop_manga_wheel_scroll();
+    fn current_precise_rotation_angle_degrees(&self) -> f32 {
+        if !self.manga_mode && self.current_media_type.is_some() {
+            Self::normalize_precise_rotation_degrees(self.precise_rotation_degrees)
+        } else {
+            0.0
         }
+    }
 
-        if let Some((target_index, restored_offset)) =
-            self.pending_masonry_folder_travel_restore.take()
-        {
-            let target_index = target_index.min(self.image_list.len().saturating_sub(1));
-            self.set_current_index_clamped(target_index);
-
-            let max_scroll = (self.manga_total_height() - self.screen_size.y).max(0.0);
-            let restored_offset = restored_offset.clamp(0.0, max_scroll);
-            self.manga_scroll_offset = restored_offset;
-            self.manga_scroll_target = restored_offset;
-            self.manga_scroll_velocity = 0.0;
-            self.manga_scrollbar_dragging = false;
-            self.masonry_scrollbar_last_motion_at = None;
-            self.masonry_autoscroll_last_motion_at = None;
-            self.is_panning = false;
-            self.last_mouse_pos = None;
-            self.manga_hovered_media_index = None;
-            self.stop_manga_wheel_scroll();
-        }
+    fn begin_rotation_turn(&mut self, clockwise: bool) {
+        self.rotation_turn = (!self.manga_mode).then_some(RotationTurn {
+            clockwise,
+            pivot: None,
+            zoom_ratio: 1.0,
+            started_at: None,
+        });
     }
 
-    fn tick_masonry_metadata_preload(&mut self) {
-        if !self.masonry_metadata_preload_active {
-            return;
+    /// Lays out the view after a rotate action. Returns false when the view was fitted to
+    /// the old orientation (or the window is maximized), for the caller to fit it again.
+    /// Otherwise the zoom stays and the pan offset turns with the image around the pointer,
+    /// so the point under it stays put; panning momentum carries on.
+    fn turn_view_with_rotation(
+        &mut self,
+        ctx: &egui::Context,
+        clockwise: bool,
+        window_is_maximized: bool,
+    ) -> bool {
+        if window_is_maximized {
+            return false;
         }
-
-        if !self.manga_mode || !self.is_masonry_mode() {
-            self.reset_masonry_metadata_preload();
-            return;
+        let Some((media_w, media_h)) = self.media_display_dimensions() else {
+            return false;
+        };
+        // Dimensions are already swapped; these are the ones the view was laid out for.
+        let before = egui::vec2(media_h as f32, media_w as f32);
+        let screen = ctx.screen_rect();
+        let fitted = if self.is_fullscreen {
+            self.offset.length() < 0.5
+                && (self.zoom
+                    - self.fullscreen_fit_zoom(self.fullscreen_target_bounds(ctx), before))
+                .abs()
+                    < 1e-3
+        } else {
+            let shown = before * self.zoom;
+            shown.x <= screen.width() + 0.5 && shown.y <= screen.height() + 0.5
+        };
+        if fitted {
+            return false;
         }
 
-        let total = self
-            .masonry_metadata_preload_total
-            .min(self.image_list.len());
-        if total == 0 {
-            self.reset_masonry_metadata_preload();
-            return;
+        let pivot = ctx
+            .input(|i| i.pointer.hover_pos())
+            .filter(|pos| screen.contains(*pos))
+            .unwrap_or_else(|| screen.center());
+        let from_pivot = screen.center() + self.offset - pivot;
+        let turned = if clockwise {
+            egui::vec2(-from_pivot.y, from_pivot.x)
+        } else {
+            egui::vec2(from_pivot.y, -from_pivot.x)
+        };
+        self.offset = pivot + turned - screen.center();
+        self.zoom_target = self.zoom;
+        if let Some(turn) = self.rotation_turn.as_mut() {
+            turn.pivot = Some(pivot);
         }
+        self.remember_current_fullscreen_view_state();
+        true
+    }
 
-        if self.masonry_metadata_preload_defer_first_tick {
-            self.masonry_metadata_preload_defer_first_tick = false;
-            return;
+    /// The running rotation turn this frame, if any.
+    fn rotation_turn_frame(&mut self, ctx: &egui::Context) -> Option<RotationTurnFrame> {
+        let turn = self.rotation_turn.as_ref()?;
+        let started_at = turn.started_at?;
+        let elapsed = started_at.elapsed();
+        if elapsed >= ROTATION_TURN_DURATION || self.manga_mode {
+            self.rotation_turn = None;
+            return None;
         }
+        ctx.request_repaint();
+        let remaining = 1.0
+            - TransitionEasing::EaseOut
+                .apply(elapsed.as_secs_f32() / ROTATION_TURN_DURATION.as_secs_f32());
+        Some(RotationTurnFrame {
+            degrees: if turn.clockwise { -90.0 } else { 90.0 } * remaining,
+            pivot: turn.pivot,
+            scale: 1.0 + (turn.zoom_ratio - 1.0) * remaining,
+        })
+    }
 
-        let navigation_active = self.masonry_navigation_active_for_heavy_work();
-        let mut allow_preload_step = !navigation_active;
-        let now = Instant::now();
-        let preload_cursor = self
-            .masonry_metadata_preload_cursor
-            .min(total.saturating_sub(1));
-        let preload_window = 96usize.max(self.masonry_items_per_row.clamp(2, 10) * 48);
-        let preload_end = (preload_cursor + preload_window).min(total);
-        let folder_ready = self
-            .image_list
-            .iter()
-            .take(total)
-            .filter(|path| self.is_folder_navigation_entry_path(path.as_path()))
-            .count();
+    fn reset_precise_rotation(&mut self) {
+        self.precise_rotation_degrees = 0.0;
+        self.precise_rotation_target_degrees = 0.0;
+        self.precise_rotation_velocity = 0.0;
+    }
 
-        let (mut loaded_count, mut pending_probe_count, mut pending_result_count) = {
-            let Some(loader) = self.manga_loader.as_mut() else {
-                self.reset_masonry_metadata_preload();
-                return;
-            };
+    fn reset_discrete_rotation(&mut self, ctx: &egui::Context) {
+        let steps = self.current_rotation_steps % 4;
+        if steps == 0 {
+            self.current_rotation_steps = 0;
+            return;
+        }
 
-            if allow_preload_step {
-                loader.request_dimensions_range_background(
-                    &self.image_list,
-                    preload_cursor,
-                    preload_end,
-                );
+        if let Some(ref mut img) = self.image {
+            match steps {
+                1 => img.rotate_counter_clockwise(),
+                2 => {
+                    img.rotate_clockwise();
+                    img.rotate_clockwise();
+                }
+                3 => img.rotate_clockwise(),
+                _ => {}
             }
 
-            (
-                loader
-                    .cached_dimensions_count(total)
-                    .saturating_add(folder_ready)
-                    .min(total),
-                loader.pending_dimension_probe_count(),
-                loader.pending_dimension_results_count(),
-            )
-        };
-
-        let previous_loaded = self.masonry_metadata_preload_loaded.min(total);
-        let mut progress_advanced = loaded_count > previous_loaded;
+            self.texture_frame = usize::MAX;
+            let _ = self.update_texture(ctx);
+        }
 
-        if progress_advanced || loaded_count >= total {
-            self.masonry_metadata_preload_stall_since = None;
-        } else {
-            let stall_since = self.masonry_metadata_preload_stall_since.get_or_insert(now);
-            let stall_elapsed = now.saturating_duration_since(*stall_since);
-            if stall_elapsed >= Duration::from_millis(900) {
-                allow_preload_step = true;
+        self.current_rotation_steps = 0;
+    }
 
-                let (next_loaded, next_pending_probe, next_pending_result, fallback_seeded) = {
-                    let Some(loader) = self.manga_loader.as_mut() else {
-                        self.reset_masonry_metadata_preload();
-                        return;
-                    };
+    fn reset_current_view_rotation(&mut self, ctx: &egui::Context) {
+        self.reset_discrete_rotation(ctx);
+        self.reset_precise_rotation();
+    }
 
-                    loader.request_dimensions_range_background(
-                        &self.image_list,
-                        preload_cursor,
-                        preload_end,
-                    );
-                    let fallback_seeded = loader.seed_fallback_dimensions_for_range(
-                        &self.image_list,
-                        preload_cursor,
-                        preload_end,
-                        24,
-                    );
+    fn update_precise_rotation(&mut self, delta_degrees: f32) {
+        if self.manga_mode || self.current_media_type.is_none() {
+            return;
+        }
 
-                    (
-                        loader
-                            .cached_dimensions_count(total)
-                            .saturating_add(folder_ready)
-                            .min(total),
-                        loader.pending_dimension_probe_count(),
-                        loader.pending_dimension_results_count(),
-                        fallback_seeded,
-                    )
-                };
+        self.precise_rotation_target_degrees = Self::normalize_precise_rotation_degrees(
+            self.precise_rotation_target_degrees + delta_degrees,
+        );
 
-                loaded_count = next_loaded;
-                pending_probe_count = next_pending_probe;
-                pending_result_count = next_pending_result;
-                progress_advanced = loaded_count > previous_loaded || fallback_seeded > 0;
+        if self.is_fullscreen {
+            self.remember_current_fullscreen_view_state();
+        }
+    }
 
-                if progress_advanced {
-                    self.masonry_metadata_preload_stall_since = None;
-                } else {
-                    // Keep retry cadence bounded instead of retrying every frame.
-                    self.masonry_metadata_preload_stall_since = Some(now);
-                }
-            }
+    fn toggle_media_flip(&mut self, horizontal: bool, vertical: bool) {
+        if self.manga_mode || self.current_media_type.is_none() {
+            return;
         }
 
-        if allow_preload_step {
-            self.masonry_metadata_preload_cursor =
-                if preload_end >= total { 0 } else { preload_end };
+        if horizontal {
+            self.flip_horizontal = !self.flip_horizontal;
+        }
+        if vertical {
+            self.flip_vertical = !self.flip_vertical;
         }
 
-        self.masonry_metadata_preload_loaded = loaded_count;
+        if self.is_fullscreen {
+            self.remember_current_fullscreen_view_state();
+        }
+    }
 
-        let scan_complete =
-            loaded_count >= total && pending_probe_count == 0 && pending_result_count == 0;
+    /// Writes the current image next to its source as `<name>.oriented.png`, the way it is
+    /// shown after 90° rotations and flips, in a background job.
+    fn start_oriented_copy_export(&mut self) {
+        if self.manga_mode || !matches!(self.current_media_type, Some(MediaType::Image)) {
+            return;
+        }
+        let Some(img) = self.image.as_ref() else {
+            return;
+        };
+        let frame = img.oriented_frame(self.flip_horizontal, self.flip_vertical);
+        let target = image_loader::sibling_export_path(&img.path, "oriented", "png");
+        let background = self.export_background();
 
-        if scan_complete {
-            self.masonry_metadata_preload_loaded = total;
-            self.masonry_metadata_preload_active = false;
-            self.masonry_metadata_preload_stall_since = None;
-            self.manga_update_preload_queue();
-            if self.masonry_pending_dimension_updates.is_empty() {
-                self.restore_masonry_scroll_after_metadata_preload();
-            }
+        self.jobs.spawn("Save oriented copy", move |_| {
+            png_export::write_png(frame.pixels, frame.width, frame.height, background, &target).map(
+                |()| {
+                    format!(
+                        "Saved {}",
+                        target.file_name().unwrap_or_default().to_string_lossy()
+                    )
+                },
+            )
+        });
+    }
+
+    /// Annotation mode only applies to still images in the solo view.
+    fn annotation_mode_active(&self) -> bool {
+        self.annotations.active
+            && !self.manga_mode
+            && matches!(self.current_media_type, Some(MediaType::Image))
+    }
+
+    fn toggle_annotation_mode(&mut self) {
+        if self.annotations.active {
+            self.annotations.active = false;
+            self.annotations.cancel_shape();
+        } else if !self.manga_mode && matches!(self.current_media_type, Some(MediaType::Image)) {
+            self.annotations.active = true;
+            self.straighten.active = false;
+            self.perspective.active = false;
+            self.eyedropper_active = false;
+            self.annotations.status = None;
+            self.is_panning = false;
+            self.last_mouse_pos = None;
         }
     }
 
-    fn draw_masonry_metadata_loading_overlay(&self, ctx: &egui::Context) {
-        if !self.masonry_metadata_overlay_visible() {
+    /// Paints the current image's annotations and feeds the primary button to the active tool.
+    /// Called from `draw_image` right after the image itself has been painted.
+    fn draw_annotation_layer(
+        &mut self,
+        ctx: &egui::Context,
+        painter: &egui::Painter,
+        view: AnnotationView,
+    ) {
+        let Some(path) = self.image.as_ref().map(|img| img.path.clone()) else {
             return;
+        };
+        self.annotations.layer_mut(&path, view.image_size);
+        self.poll_annotation_export(ctx);
+        let toolbar_rect = self.draw_annotation_toolbar(ctx, &path);
+
+        let (pressed, down, pointer_pos, moves, time, frame_time, undo) = ctx.input_mut(|input| {
+            // Every move since the last frame, not just the latest position, so fast strokes
+            // keep their curve instead of turning into one straight segment per frame.
+            let moves: Vec<egui::Pos2> = input
+                .events
+                .iter()
+                .filter_map(|event| match event {
+                    egui::Event::PointerMoved(pos) => Some(*pos),
+                    _ => None,
+                })
+                .collect();
+            (
+                input.pointer.button_pressed(egui::PointerButton::Primary),
+                input.pointer.button_down(egui::PointerButton::Primary),
+                input.pointer.interact_pos(),
+                moves,
+                input.time,
+                input.predicted_dt,
+                input.consume_key(egui::Modifiers::COMMAND, egui::Key::Z),
+            )
+        });
+        if undo {
+            self.annotations.undo(&path);
         }
 
-        let total = self.masonry_metadata_preload_total.max(1);
-        let loaded = self.masonry_metadata_preload_loaded.min(total);
-        let progress_ratio = (loaded as f32 / total as f32).clamp(0.0, 1.0);
-        let progress_text = format!("Warming layout  {} / {}", loaded, total);
-        let screen_rect = ctx.screen_rect();
-        let panel_width = (screen_rect.width() - 48.0).clamp(280.0, 420.0);
-        let panel_size = egui::vec2(panel_width, 144.0);
+        let over_ui = pointer_pos.map_or(true, |pos| toolbar_rect.contains(pos))
+            || self.title_bar_ui_blocking()
+            || self.any_modal_dialog_open()
+            || self.file_action_menu.is_some();
+        let was_drawing = self.annotations.is_drawing();
+        if pressed && !over_ui {
+            if let Some(pos) = pointer_pos {
+                self.annotations.begin_shape(&view, pos);
+            }
+        }
+        if self.annotations.is_drawing() {
+            if was_drawing {
+                for pos in &moves {
+                    self.annotations.extend_shape(&view, *pos);
+                }
+            }
+            if let Some(pos) = pointer_pos {
+                self.annotations.extend_shape(&view, pos);
+                self.annotations.record_pointer_sample(time, pos);
+            }
+            if !down {
+                self.annotations.finish_shape(&path, view.image_size);
+            }
+            // Repaint right away rather than on the next input-driven frame, so the stroke
+            // keeps up with the pointer.
+            ctx.request_repaint();
+        }
+        if !over_ui {
+            ctx.set_cursor_icon(egui::CursorIcon::Crosshair);
+        }
 
-        egui::Area::new(egui::Id::new("masonry_metadata_loading_overlay"))
+        if let Some(layer) = self.annotations.layer(&path) {
+            for shape in &layer.shapes {
+                annotations::paint_shape(painter, &view, shape);
+            }
+        }
+        let predicted = self
+            .config
+            .annotation_stroke_prediction
+            .then(|| self.annotations.predicted_shape(&view, frame_time as f64))
+            .flatten();
+        if let Some(shape) = predicted.as_ref().or(self.annotations.in_progress()) {
+            annotations::paint_shape(painter, &view, shape);
+        }
+    }
+
+    /// Tool, color and width pickers plus undo/clear/save/export. Returns the toolbar rect so
+    /// presses on it don't start a stroke.
+    fn draw_annotation_toolbar(&mut self, ctx: &egui::Context, path: &Path) -> egui::Rect {
+        let (shape_count, dirty) = self
+            .annotations
+            .layer(path)
+            .map_or((0, false), |layer| (layer.shapes.len(), layer.dirty));
+        let exporting = self.annotation_export_rx.is_some();
+        let has_transparency = self.current_image_has_transparency();
+        let mut save_clicked = false;
+        let mut export_clicked = false;
+        let mut close_clicked = false;
+
+        let response = egui::Area::new(egui::Id::new("annotation_toolbar"))
             .order(egui::Order::Foreground)
-            .fixed_pos(screen_rect.min)
+            .anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -16.0))
             .show(ctx, |ui| {
-                let overlay_rect = egui::Rect::from_min_size(egui::Pos2::ZERO, screen_rect.size());
-                let _ = ui.allocate_rect(overlay_rect, egui::Sense::click_and_drag());
-                ui.painter().rect_filled(
-                    overlay_rect,
-                    0.0,
-                    egui::Color32::from_rgba_unmultiplied(5, 8, 12, 150),
-                );
-
-                let panel_rect = egui::Rect::from_center_size(overlay_rect.center(), panel_size);
-                ui.painter().rect_filled(
-                    panel_rect,
-                    18.0,
-                    egui::Color32::from_rgba_unmultiplied(18, 22, 28, 240),
-                );
-                ui.painter().rect_stroke(
-                    panel_rect,
-                    18.0,
-                    egui::Stroke::new(
-                        1.0,
-                        egui::Color32::from_rgba_unmultiplied(130, 188, 255, 72),
-                    ),
-                );
-
-                ui.painter().text(
-                    egui::pos2(panel_rect.center().x, panel_rect.min.y + 34.0),
-                    egui::Align2::CENTER_CENTER,
-                    "Preparing masonry layout",
-                    egui::FontId::proportional(20.0),
-                    egui::Color32::WHITE,
-                );
-                ui.painter().text(
-                    egui::pos2(panel_rect.center().x, panel_rect.min.y + 64.0),
-                    egui::Align2::CENTER_CENTER,
-                    progress_text,
-                    egui::FontId::proportional(14.0),
-                    egui::Color32::from_gray(214),
-                );
-                ui.painter().text(
-                    egui::pos2(panel_rect.center().x, panel_rect.min.y + 88.0),
-                    egui::Align2::CENTER_CENTER,
-                    "Navigation is paused until the layout stabilizes.",
-                    egui::FontId::proportional(12.0),
-                    egui::Color32::from_gray(170),
-                );
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        for tool in AnnotationTool::ALL {
+                            if ui
+                                .selectable_label(self.annotations.tool == tool, tool.label())
+                                .clicked()
+                            {
+                                self.annotations.tool = tool;
+                            }
+                        }
+                        ui.separator();
 
-                let bar_rect = egui::Rect::from_min_size(
-                    egui::pos2(panel_rect.min.x + 24.0, panel_rect.max.y - 30.0),
-                    egui::vec2(panel_rect.width() - 48.0, 10.0),
-                );
-                ui.painter().rect_filled(
-                    bar_rect,
-                    5.0,
-                    egui::Color32::from_rgba_unmultiplied(255, 255, 255, 30),
-                );
-                if progress_ratio > 0.0 {
-                    let fill_rect = egui::Rect::from_min_max(
-                        bar_rect.min,
-                        egui::pos2(
-                            bar_rect.min.x + bar_rect.width() * progress_ratio,
-                            bar_rect.max.y,
-                        ),
-                    );
-                    ui.painter().rect_filled(
-                        fill_rect,
-                        5.0,
-                        egui::Color32::from_rgb(104, 184, 255),
-                    );
-                }
-            });
-    }
+                        for color in annotations::ANNOTATION_COLORS {
+                            let (rect, swatch) = ui
+                                .allocate_exact_size(egui::vec2(18.0, 18.0), egui::Sense::click());
+                            ui.painter().rect_filled(rect, 3.0, color);
+                            let selected = self.annotations.color == color;
+                            ui.painter().rect_stroke(
+                                rect,
+                                3.0,
+                                egui::Stroke::new(
+                                    if selected { 2.0 } else { 1.0 },
+                                    if selected {
+                                        egui::Color32::WHITE
+                                    } else {
+                                        egui::Color32::from_gray(90)
+                                    },
+                                ),
+                            );
+                            if swatch.clicked() {
+                                self.annotations.color = color;
+                            }
+                        }
+                        ui.separator();
 
-    fn clear_manga_runtime_workloads(&mut self) {
-        self.clear_pending_manga_video_load();
-        self.manga_decoded_mailbox.clear();
-        self.clear_manga_video_players();
-        self.manga_video_failed.clear();
-        self.manga_focused_video_index = None;
-        self.manga_hovered_media_index = None;
-        self.manga_hover_autoplay_resume_at = Instant::now();
-        self.manga_anim_streams.clear();
-        self.manga_anim_stream_done.clear();
-        self.manga_focused_anim_index = None;
-    }
+                        ui.add(
+                            egui::DragValue::new(&mut self.annotations.width)
+                                .range(
+                                    annotations::MIN_STROKE_WIDTH..=annotations::MAX_STROKE_WIDTH,
+                                )
+                                .speed(0.2)
+                                .suffix(" px"),
+                        )
+                        .on_hover_text("Stroke width on screen");
+                        ui.separator();
 
-    fn apply_video_audio_overrides(
-        player: &mut VideoPlayer,
-        muted_override: Option<bool>,
-        volume_override: Option<f64>,
-    ) {
-        if let Some(muted) = muted_override {
-            player.set_muted(muted);
+                        if ui
+                            .add_enabled(shape_count > 0, egui::Button::new("Undo"))
+                            .on_hover_text("Ctrl+Z")
+                            .clicked()
+                        {
+                            self.annotations.undo(path);
+                        }
+                        if ui
+                            .add_enabled(shape_count > 0, egui::Button::new("Clear"))
+                            .clicked()
+                        {
+                            self.annotations.clear(path);
+                        }
+                        save_clicked = ui
+                            .add_enabled(dirty, egui::Button::new("Save"))
+                            .on_hover_text("Write the annotations to a .annotations sidecar file")
+                            .clicked();
+                        if has_transparency {
+                            self.export_alpha_unsaved |= png_export::alpha_option_ui(
+                                ui,
+                                &mut self.config.export_alpha,
+                                &mut self.config.export_background_rgb,
+                            );
+                        }
+                        export_clicked = ui
+                            .add_enabled(
+                                shape_count > 0 && !exporting,
+                                egui::Button::new("Export PNG"),
+                            )
+                            .on_hover_text("Save a flattened copy next to the image")
+                            .clicked();
+                        close_clicked = ui
+                            .button("✕")
+                            .on_hover_text("Leave annotation mode")
+                            .clicked();
+
+                        if let Some(status) = self.annotations.status.as_ref() {
+                            ui.separator();
+                            ui.label(egui::RichText::new(status).weak());
+                        }
+                    });
+                });
+            });
+
+        if save_clicked {
+            self.annotations.status = Some(match self.annotations.save_sidecar(path) {
+                Ok(sidecar) => format!(
+                    "Saved {}",
+                    sidecar.file_name().unwrap_or_default().to_string_lossy()
+                ),
+                Err(err) => err,
+            });
         }
-        if let Some(volume) = volume_override {
-            player.set_volume(volume);
+        if export_clicked {
+            self.start_annotation_export(path);
         }
+        if close_clicked {
+            self.toggle_annotation_mode();
+        }
+        response.response.rect
     }
 
-    fn use_hardware_acceleration_enabled(&self) -> bool {
-        if !self.config.use_hardware_acceleration {
-            return false;
+    /// Flattens the annotations onto a copy of the current frame and writes it as PNG on a
+    /// worker thread.
+    fn start_annotation_export(&mut self, path: &Path) {
+        if self.annotation_export_rx.is_some() {
+            return;
         }
-
-        detect_video_acceleration_capabilities().hardware_decode_available
+        let (Some(img), Some(layer)) = (self.image.as_ref(), self.annotations.layer(path)) else {
+            return;
+        };
+        let frame = img.current_frame_data();
+        let (pixels, width, height) = (frame.pixels.clone(), frame.width, frame.height);
+        let layer = layer.clone();
+        let target = annotations::export_path(path);
+        let background = self.export_background();
+
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        crate::async_runtime::spawn_blocking_or_thread("annotation-export", move || {
+            let result = annotations::export_flattened_png(
+                &layer, pixels, width, height, background, &target,
+            )
+            .map(|()| target);
+            let _ = tx.send(result);
+        });
+        self.annotation_export_rx = Some(rx);
+        self.annotations.status = Some("Exporting…".to_string());
     }
 
-    fn use_cuda_decode_enabled(&self) -> bool {
-        self.use_hardware_acceleration_enabled()
-            && self.config.enable_cuda
-            && detect_video_acceleration_capabilities().cuda_available
+    fn poll_annotation_export(&mut self, ctx: &egui::Context) {
+        let Some(rx) = self.annotation_export_rx.as_ref() else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(result) => {
+                self.annotations.status = Some(match result {
+                    Ok(target) => format!(
+                        "Exported {}",
+                        target.file_name().unwrap_or_default().to_string_lossy()
+                    ),
+                    Err(err) => err,
+                });
+                self.annotation_export_rx = None;
+            }
+            Err(crossbeam_channel::TryRecvError::Empty) => {
+                ctx.request_repaint_after(Duration::from_millis(50));
+            }
+            Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                self.annotations.status = Some("Export failed".to_string());
+                self.annotation_export_rx = None;
+            }
+        }
     }
 
-    fn effective_video_decoder_preferences(&self) -> (bool, bool, bool, bool) {
-        if !self.use_hardware_acceleration_enabled() {
-            return (false, true, false, false);
+    fn toggle_adjustments_panel(&mut self) {
+        if self.adjustments_panel_open {
+            self.adjustments_panel_open = false;
+            self.adjustments_panel_rect = None;
+        } else if !self.manga_mode && self.current_media_type.is_some() {
+            self.adjustments_panel_open = true;
+            self.adjustment_status = None;
         }
+    }
 
-        let disable_hardware_decode = self.config.video_disable_hardware_decode;
-        let prefer_hardware_decode = self.config.video_prefer_hardware_decode;
-        let enable_cuda_decode = !disable_hardware_decode && self.use_cuda_decode_enabled();
-        let enable_d3d12_decode = !disable_hardware_decode
-            && self.config.enable_d3d12
-            && detect_video_acceleration_capabilities().d3d12_available;
+    /// Brightness/contrast/saturation/gamma sliders, the LUT picker and reset/export. The
+    /// adjustments stay applied after the panel is closed. Returns the panel rect.
+    fn draw_adjustments_panel(&mut self, ctx: &egui::Context) -> egui::Rect {
+        self.poll_adjustment_export(ctx);
+        self.poll_lut_dialog();
+        let identity = self.image_adjustments.is_identity();
+        let can_export = (!identity || self.lut.is_some())
+            && self.adjustment_export_rx.is_none()
+            && matches!(self.current_media_type, Some(MediaType::Image))
+            && self.image.is_some();
+        let shader_available = self.adjustment_renderer.is_available();
+        let has_transparency = self.current_image_has_transparency();
+        let mut reset_clicked = false;
+        let mut export_clicked = false;
+        let mut close_clicked = false;
+        let mut lut_choice: Option<Option<PathBuf>> = None;
+        let mut open_lut_clicked = false;
 
-        (
-            prefer_hardware_decode,
-            disable_hardware_decode,
-            enable_cuda_decode,
-            enable_d3d12_decode,
-        )
+        let response = egui::Area::new(egui::Id::new("image_adjustments_panel"))
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::RIGHT_CENTER, egui::vec2(-16.0, 0.0))
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Adjustments").strong());
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            close_clicked = ui
+                                .small_button("✕")
+                                .on_hover_text("Hide the panel; adjustments stay applied")
+                                .clicked();
+                        });
+                    });
+                    ui.separator();
+
+                    let adjustments = &mut self.image_adjustments;
+                    egui::Grid::new("image_adjustments_grid")
+                        .num_columns(2)
+                        .spacing(egui::vec2(10.0, 6.0))
+                        .show(ui, |ui| {
+                            ui.label("Brightness");
+                            ui.add(
+                                egui::Slider::new(
+                                    &mut adjustments.brightness,
+                                    image_adjustments::BRIGHTNESS_RANGE,
+                                )
+                                .fixed_decimals(2),
+                            );
+                            ui.end_row();
+
+                            ui.label("Contrast");
+                            ui.add(
+                                egui::Slider::new(
+                                    &mut adjustments.contrast,
+                                    image_adjustments::CONTRAST_RANGE,
+                                )
+                                .fixed_decimals(2),
+                            );
+                            ui.end_row();
+
+                            ui.label("Saturation");
+                            ui.add_enabled(
+                                !adjustments.grayscale,
+                                egui::Slider::new(
+                                    &mut adjustments.saturation,
+                                    image_adjustments::SATURATION_RANGE,
+                                )
+                                .fixed_decimals(2),
+                            );
+                            ui.end_row();
+
+                            ui.label("Gamma");
+                            ui.add(
+                                egui::Slider::new(
+                                    &mut adjustments.gamma,
+                                    image_adjustments::GAMMA_RANGE,
+                                )
+                                .logarithmic(true)
+                                .fixed_decimals(2),
+                            );
+                            ui.end_row();
+                        });
+                    ui.checkbox(&mut adjustments.grayscale, "Grayscale");
+
+                    ui.horizontal(|ui| {
+                        ui.label("LUT");
+                        let selected = self
+                            .lut
+                            .as_ref()
+                            .map_or_else(|| "None".to_string(), |lut| lut.title.clone());
+                        egui::ComboBox::from_id_salt("image_adjustments_lut")
+                            .selected_text(selected)
+                            .width(150.0)
+                            .show_ui(ui, |ui| {
+                                if ui.selectable_label(self.lut.is_none(), "None").clicked() {
+                                    lut_choice = Some(None);
+                                }
+                                for path in &self.config.state_recent_luts {
+                                    let current =
+                                        self.lut.as_ref().is_some_and(|lut| &lut.path == path);
+                                    let name = path
+                                        .file_name()
+                                        .unwrap_or(path.as_os_str())
+                                        .to_string_lossy();
+                                    if ui
+                                        .selectable_label(current, name)
+                                        .on_hover_text(path.display().to_string())
+                                        .clicked()
+                                    {
+                                        lut_choice = Some(Some(path.clone()));
+                                    }
+                                }
+                            });
+                        if cfg!(target_os = "windows") {
+                            open_lut_clicked = ui
+                                .add_enabled(
+                                    self.pending_lut_dialog.is_none(),
+                                    egui::Button::new("Open…"),
+                                )
+                                .clicked();
+                        }
+                    })
+                    .response
+                    .on_hover_text("Drop a .cube file on the window to load it");
+
+                    if !shader_available {
+                        ui.label(
+                            egui::RichText::new(
+                                "GPU shader unavailable: adjustments only apply to exports.",
+                            )
+                            .weak(),
+                        );
+                    }
+                    ui.separator();
+
+                    if has_transparency {
+                        ui.horizontal(|ui| {
+                            self.export_alpha_unsaved |= png_export::alpha_option_ui(
+                                ui,
+                                &mut self.config.export_alpha,
+                                &mut self.config.export_background_rgb,
+                            );
+                        });
+                    }
+                    ui.horizontal(|ui| {
+                        reset_clicked = ui
+                            .add_enabled(!identity, egui::Button::new("Reset"))
+                            .clicked();
+                        export_clicked = ui
+                            .add_enabled(can_export, egui::Button::new("Export PNG"))
+                            .on_hover_text("Save an adjusted copy next to the image")
+                            .clicked();
+                        if let Some(status) = self.adjustment_status.as_ref() {
+                            ui.label(egui::RichText::new(status).weak());
+                        }
+                    });
+                });
+            });
+
+        if reset_clicked {
+            self.image_adjustments = ImageAdjustments::default();
+        }
+        match lut_choice {
+            Some(Some(path)) => self.load_lut(&path),
+            Some(None) => self.lut = None,
+            None => {}
+        }
+        if open_lut_clicked {
+            self.start_lut_dialog();
+        }
+        if export_clicked {
+            self.start_adjustment_export();
+        }
+        if close_clicked {
+            self.toggle_adjustments_panel();
+        }
+        response.response.rect
     }
 
-    fn mipmap_static_enabled(&self) -> bool {
-        self.config.manga_mipmap_static && self.config.use_hardware_acceleration
+    /// Loads a `.cube` LUT and applies it to the view, remembering it in the recent LUTs.
+    fn load_lut(&mut self, path: &Path) {
+        match lut::CubeLut::load(path) {
+            Ok(loaded) => {
+                self.show_action_osd(
+                    "lut",
+                    i18n::tr_args("osd.lut_loaded", &[("name", &loaded.title)]),
+                );
+                self.lut = Some(Arc::new(loaded));
+                self.config.push_recent_lut(path);
+                self.config.save();
+            }
+            Err(err) => self.show_toast(err),
+        }
     }
 
-    fn mipmap_video_thumbnail_enabled(&self) -> bool {
-        self.config.manga_mipmap_video_thumbnails && self.config.use_hardware_acceleration
+    /// Opens the system file dialog for a `.cube` file on a worker thread.
+    fn start_lut_dialog(&mut self) {
+        #[cfg(target_os = "windows")]
+        if self.pending_lut_dialog.is_none() {
+            let folder = self
+                .config
+                .state_recent_luts
+                .first()
+                .and_then(|path| path.parent())
+                .map(Path::to_path_buf);
+            let (tx, rx) = crossbeam_channel::bounded(1);
+            let spawned = std::thread::Builder::new()
+                .name("lut-dialog".to_string())
+                .spawn(move || {
+                    let _ = tx.send(windows_env::pick_file_to_open(folder.as_deref(), &["cube"]));
+                });
+            if spawned.is_ok() {
+                self.pending_lut_dialog = Some(rx);
+            }
+        }
     }
 
-    /// Create new viewer with an image path
-    /// `start_visible`: true if window was created visible (images), false if hidden (videos)
-    #[cfg(target_os = "windows")]
-    fn new(
-        cc: &eframe::CreationContext<'_>,
-        path: Option<PathBuf>,
-        start_visible: bool,
-        file_receiver: Option<FileReceiver>,
-    ) -> Self {
-        let mut viewer = Self::default();
+    fn poll_lut_dialog(&mut self) {
+        let Some(rx) = self.pending_lut_dialog.as_ref() else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(picked) => {
+                self.pending_lut_dialog = None;
+                if let Some(path) = picked {
+                    self.load_lut(&path);
+                }
+            }
+            Err(crossbeam_channel::TryRecvError::Empty) => {}
+            Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                self.pending_lut_dialog = None;
+            }
+        }
+    }
 
-        // Store the file receiver for single-instance mode
-        viewer.file_receiver = file_receiver;
+    /// Applies the adjustments to a copy of the current frame and writes it as PNG on a
+    /// worker thread.
+    fn start_adjustment_export(&mut self) {
+        if self.adjustment_export_rx.is_some() {
+            return;
+        }
+        let Some(img) = self.image.as_ref() else {
+            return;
+        };
+        let frame = img.current_frame_data();
+        let (pixels, width, height) = (frame.pixels.clone(), frame.width, frame.height);
+        let adjustments = self.image_adjustments;
+        let lut = self.lut.clone();
+        let target = image_adjustments::export_path(&img.path);
+        let background = self.export_background();
+
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        crate::async_runtime::spawn_blocking_or_thread("adjustment-export", move || {
+            let result = image_adjustments::export_adjusted_png(
+                adjustments,
+                lut.as_deref(),
+                pixels,
+                width,
+                height,
+                background,
+                &target,
+            )
+            .map(|()| target);
+            let _ = tx.send(result);
+        });
+        self.adjustment_export_rx = Some(rx);
+        self.adjustment_status = Some("Exporting…".to_string());
+    }
 
-        Self::init_viewer(&mut viewer, cc, path, start_visible);
-        viewer
+    fn poll_adjustment_export(&mut self, ctx: &egui::Context) {
+        let Some(rx) = self.adjustment_export_rx.as_ref() else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(result) => {
+                self.adjustment_status = Some(match result {
+                    Ok(target) => format!(
+                        "Exported {}",
+                        target.file_name().unwrap_or_default().to_string_lossy()
+                    ),
+                    Err(err) => err,
+                });
+                self.adjustment_export_rx = None;
+            }
+            Err(crossbeam_channel::TryRecvError::Empty) => {
+                ctx.request_repaint_after(Duration::from_millis(50));
+            }
+            Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                self.adjustment_status = Some("Export failed".to_string());
+                self.adjustment_export_rx = None;
+            }
+        }
     }
 
-    #[cfg(not(target_os = "windows"))]
-    fn new(cc: &eframe::CreationContext<'_>, path: Option<PathBuf>, start_visible: bool) -> Self {
-        let mut viewer = Self::default();
-        Self::init_viewer(&mut viewer, cc, path, start_visible);
-        viewer
+    /// Opens the stereo export dialog for the current image, treated as a parallel
+    /// side-by-side pair.
+    fn open_stereo_export(&mut self) {
+        if self.manga_mode || !matches!(self.current_media_type, Some(MediaType::Image)) {
+            return;
+        }
+        let Some(img) = self.image.as_ref() else {
+            return;
+        };
+        let frame = img.current_frame_data();
+        if frame.width < 2 {
+            return;
+        }
+        let (width, height, pixels) = downscale_rgba_if_needed(
+            frame.width,
+            frame.height,
+            &frame.pixels,
+            stereo_export::PREVIEW_MAX_SIDE,
+            FilterType::Triangle,
+        );
+        let preview_source = stereo_export::RgbaBuffer {
+            pixels: pixels.into_owned(),
+            width,
+            height,
+        };
+        self.stereo_export = Some(stereo_export::StereoExportDialog::new(
+            img.path.clone(),
+            frame.width,
+            preview_source,
+        ));
     }
 
-    fn init_viewer(
-        viewer: &mut Self,
-        cc: &eframe::CreationContext<'_>,
-        path: Option<PathBuf>,
-        start_visible: bool,
-    ) {
-        #[cfg(target_os = "windows")]
-        if let Some(receiver) = viewer.file_receiver.as_ref() {
-            let egui_ctx = cc.egui_ctx.clone();
-            receiver.set_wake_callback(move || {
-                egui_ctx.request_repaint();
-            });
+    fn draw_stereo_export_window(&mut self, ctx: &egui::Context) {
+        let has_transparency =
+            self.stereo_export.is_some() && self.current_image_has_transparency();
+        let Some(dialog) = self.stereo_export.as_mut() else {
+            return;
+        };
+
+        if let Some(rx) = dialog.export_rx.as_ref() {
+            match rx.try_recv() {
+                Ok(result) => {
+                    dialog.status = Some(match result {
+                        Ok(target) => format!(
+                            "Exported {}",
+                            target.file_name().unwrap_or_default().to_string_lossy()
+                        ),
+                        Err(err) => err,
+                    });
+                    dialog.export_rx = None;
+                }
+                Err(crossbeam_channel::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(Duration::from_millis(50));
+                }
+                Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                    dialog.status = Some("Export failed".to_string());
+                    dialog.export_rx = None;
+                }
+            }
         }
 
-        // If window started visible, mark it as shown already
-        viewer.startup_window_shown = start_visible;
+        let mut close = ctx.input(|input| input.key_pressed(egui::Key::Escape));
+        let mut export = false;
+        let screen_rect = ctx.screen_rect();
+        let max_parallax = stereo_export::max_parallax(dialog.source_width);
 
-        // Mark the start of the hidden startup period.
-        viewer.startup_hide_started_at = Instant::now();
+        egui::Area::new(egui::Id::new("stereo_export_backdrop"))
+            .fixed_pos(screen_rect.min)
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, screen_rect.size());
+                ui.painter().rect_filled(
+                    rect,
+                    0.0,
+                    egui::Color32::from_rgba_unmultiplied(4, 8, 13, 214),
+                );
+            });
 
-        // Determine the maximum texture size supported by the active backend.
-        // This viewer uses eframe's OpenGL (glow) integration; oversized textures can crash.
-        let queried_max_texture_side = cc
-            .gl
-            .as_ref()
-            .and_then(|gl| unsafe {
-                gl.get_parameter_i32(eframe::glow::MAX_TEXTURE_SIZE)
-                    .try_into()
-                    .ok()
-            })
-            .filter(|side: &u32| *side >= 512);
+        let modal_width = (screen_rect.width() - 60.0).clamp(320.0, 520.0);
+        egui::Area::new(egui::Id::new("stereo_export_window"))
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.set_width(modal_width);
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgba_unmultiplied(16, 23, 31, 252))
+                    .stroke(egui::Stroke::new(
+                        1.0,
+                        egui::Color32::from_rgba_unmultiplied(166, 207, 255, 62),
+                    ))
+                    .rounding(18.0)
+                    .inner_margin(egui::Margin::same(18.0))
+                    .show(ui, |ui| {
+                        ui.label(
+                            egui::RichText::new("Stereo Export")
+                                .size(20.0)
+                                .strong()
+                                .color(egui::Color32::WHITE),
+                        );
+                        ui.label(
+                            egui::RichText::new(
+                                "The image is read as a side-by-side pair: left eye on the left.",
+                            )
+                            .color(egui::Color32::from_rgb(150, 170, 192)),
+                        );
+                        ui.add_space(10.0);
 
-        // Fall back to a modern-safe default when the backend cannot report limits.
-        viewer.max_texture_side = queried_max_texture_side.unwrap_or(8192);
+                        egui::Grid::new("stereo_export_settings")
+                            .num_columns(2)
+                            .spacing([24.0, 6.0])
+                            .show(ui, |ui| {
+                                ui.label("Output");
+                                ui.horizontal(|ui| {
+                                    for output in stereo_export::StereoOutput::ALL {
+                                        ui.radio_value(
+                                            &mut dialog.settings.output,
+                                            output,
+                                            output.label(),
+                                        );
+                                    }
+                                });
+                                ui.end_row();
 
-        // Configure visuals (background driven by config)
-        let mut visuals = egui::Visuals::dark();
-        let bg = viewer.background_color32();
-        visuals.window_fill = bg;
-        visuals.panel_fill = bg;
-        cc.egui_ctx.set_visuals(visuals);
+                                ui.label("Parallax");
+                                ui.add(
+                                    egui::Slider::new(
+                                        &mut dialog.settings.parallax,
+                                        -max_parallax..=max_parallax,
+                                    )
+                                    .suffix(" px"),
+                                )
+                                .on_hover_text(
+                                    "Shift the right eye; positive values push the scene back",
+                                );
+                                ui.end_row();
+
+                                ui.label("Eyes");
+                                ui.checkbox(
+                                    &mut dialog.settings.swap_eyes,
+                                    "Swap (source is cross-eye)",
+                                );
+                                ui.end_row();
+                            });
+
+                        ui.add_space(10.0);
+                        match dialog.preview_texture(ui.ctx()) {
+                            Some(texture) => {
+                                let size = texture.size_vec2();
+                                let scale = (ui.available_width() / size.x).min(280.0 / size.y);
+                                ui.vertical_centered(|ui| {
+                                    ui.image((texture.id(), size * scale.min(1.0)));
+                                });
+                            }
+                            None => {
+                                ui.label(
+                                    egui::RichText::new("Nothing left after cropping.").weak(),
+                                );
+                            }
+                        }
 
-        // Give users a more forgiving double-click detection window.
-        cc.egui_ctx.options_mut(|opt| {
-            opt.input_options.max_double_click_delay = viewer.config.double_click_grace_period;
-        });
+                        ui.add_space(14.0);
+                        if has_transparency {
+                            ui.horizontal(|ui| {
+                                self.export_alpha_unsaved |= png_export::alpha_option_ui(
+                                    ui,
+                                    &mut self.config.export_alpha,
+                                    &mut self.config.export_background_rgb,
+                                );
+                            });
+                        }
+                        ui.horizontal(|ui| {
+                            export = ui
+                                .add_enabled(
+                                    dialog.export_rx.is_none() && dialog.preview.is_some(),
+                                    egui::Button::new("Export PNG"),
+                                )
+                                .on_hover_text("Save the result next to the image")
+                                .clicked();
+                            close |= ui.button("Close").clicked();
+                            if let Some(status) = dialog.status.as_ref() {
+                                ui.label(egui::RichText::new(status).weak());
+                            }
+                        });
+                    });
+            });
 
-        // Get screen size from monitor info if available
-        #[cfg(target_os = "windows")]
-        {
-            let primary_monitor = get_primary_monitor_size();
-            viewer.screen_size = primary_monitor;
-            viewer.last_known_monitor_size = primary_monitor;
+        if export {
+            self.start_stereo_export();
         }
-
-        if let Some(path) = path {
-            viewer.load_image(&path);
+        if close {
+            self.stereo_export = None;
         }
     }
 
-    fn poll_pending_media_directory_scan(&mut self, ctx: &egui::Context) {
-        let Some(rx) = self.pending_media_directory_scan.as_ref() else {
+    /// Composes the full-resolution pair on a worker thread and writes it next to the image.
+    fn start_stereo_export(&mut self) {
+        let background = self.export_background();
+        let Some(img) = self.image.as_ref() else {
             return;
         };
-
-        let result = match rx.try_recv() {
-            Ok(result) => result,
-            Err(crossbeam_channel::TryRecvError::Empty) => return,
-            Err(crossbeam_channel::TryRecvError::Disconnected) => {
-                self.pending_media_directory_scan = None;
-                self.pending_media_directory_target = None;
-                self.pending_media_directory_scan_kind = None;
-                self.pending_media_directory_started_at = None;
-                return;
-            }
-        };
-
-        self.pending_media_directory_scan = None;
-        let scan_kind = self
-            .pending_media_directory_scan_kind
-            .take()
-            .unwrap_or(PendingMediaDirectoryScanKind::InitialLoad);
-        let Some(target_path) = self.pending_media_directory_target.take() else {
-            self.pending_media_directory_started_at = None;
+        let Some(dialog) = self.stereo_export.as_mut() else {
             return;
         };
-
-        if let Some(started_at) = self.pending_media_directory_started_at.take() {
-            self.perf_metrics
-                .record_duration("media_index_async_scan_ms", started_at.elapsed());
+        if dialog.export_rx.is_some() || img.path != dialog.source_path {
+            return;
         }
+        let frame = img.current_frame_data();
+        let (pixels, width, height) = (frame.pixels.clone(), frame.width, frame.height);
+        let settings = dialog.settings;
+        let target = stereo_export::export_path(&img.path, settings.output);
 
-        let scanned_directory = result.directory.clone();
-        let mut files = self
-            .media_directory_index
-            .apply_directory_scan_result(result);
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        crate::async_runtime::spawn_blocking_or_thread("stereo-export", move || {
+            let result = stereo_export::export_stereo_png(
+                settings, &pixels, width, height, background, &target,
+            )
+            .map(|()| target);
+            let _ = tx.send(result);
+        });
+        dialog.export_rx = Some(rx);
+        dialog.status = Some("Exporting…".to_string());
+    }
 
-        match scan_kind {
-            PendingMediaDirectoryScanKind::InitialLoad => {
-                if files.is_empty() {
-                    files.push(target_path.clone());
-                }
+    /// Opens the scan cleanup dialog for the current image.
+    fn open_scan_cleanup(&mut self) {
+        if self.manga_mode || !matches!(self.current_media_type, Some(MediaType::Image)) {
+            return;
+        }
+        let Some(img) = self.image.as_ref() else {
+            return;
+        };
+        let frame = img.current_frame_data();
+        let (width, height, pixels) = downscale_rgba_if_needed(
+            frame.width,
+            frame.height,
+            &frame.pixels,
+            scan_cleanup::PREVIEW_MAX_SIDE,
+            FilterType::Triangle,
+        );
+        self.scan_cleanup = Some(scan_cleanup::ScanCleanupDialog::new(
+            img.path.clone(),
+            pixels.into_owned(),
+            width,
+            height,
+        ));
+    }
 
-                let current_path = self.image_list.get(self.current_index).cloned();
-                if current_path.as_ref() != Some(&target_path) {
-                    return;
-                }
+    fn draw_scan_cleanup_window(&mut self, ctx: &egui::Context) {
+        let has_transparency = self.scan_cleanup.is_some() && self.current_image_has_transparency();
+        let Some(dialog) = self.scan_cleanup.as_mut() else {
+            return;
+        };
 
-                self.set_image_list(files);
-                let resolved_index = self
-                    .image_list
-                    .iter()
-                    .position(|candidate| candidate == &target_path)
-                    .unwrap_or(0);
-                self.set_current_index_clamped(resolved_index);
-                if !self.defer_directory_work_for_fast_startup() {
-                    self.schedule_solo_probe_window(&target_path, self.current_media_type);
-                }
-                ctx.request_repaint();
-            }
-            PendingMediaDirectoryScanKind::ExternalRefresh => {
-                let current_path_before = self.current_media_path();
-                let current_index_before = self.current_index;
-                let current_directory = current_path_before
-                    .as_ref()
-                    .and_then(|path| path.parent().map(Path::to_path_buf))
-                    .or_else(|| {
-                        self.image_list
-                            .first()
-                            .and_then(|path| path.parent().map(Path::to_path_buf))
+        if let Some(rx) = dialog.export_rx.as_ref() {
+            match rx.try_recv() {
+                Ok(result) => {
+                    dialog.status = Some(match result {
+                        Ok(target) => format!(
+                            "Exported {}",
+                            target.file_name().unwrap_or_default().to_string_lossy()
+                        ),
+                        Err(err) => err,
                     });
-
-                if current_directory.as_deref() != Some(scanned_directory.as_path()) {
-                    return;
-                }
-
-                if self.try_append_new_entries_in_strip_mode(&files) {
-                    self.clear_stale_marked_files();
-                    self.clear_stale_prepared_clipboard_paths();
-                    self.modal_thumbnail_cache.retain(|path, _| path.exists());
-                    ctx.request_repaint();
-                    return;
+                    dialog.export_rx = None;
                 }
-
-                if self.manga_mode && self.is_true_masonry_mode() {
-                    self.persist_current_masonry_folder_metadata_snapshot();
+                Err(crossbeam_channel::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(Duration::from_millis(50));
                 }
-
-                self.set_image_list(files);
-                self.clear_stale_marked_files();
-                self.clear_stale_prepared_clipboard_paths();
-                self.modal_thumbnail_cache.retain(|path, _| path.exists());
-
-                if self.image_list.is_empty() {
-                    self.clear_current_media_after_all_files_removed();
-                    ctx.request_repaint();
-                    return;
+                Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                    dialog.status = Some("Export failed".to_string());
+                    dialog.export_rx = None;
                 }
+            }
+        }
 
-                let previous_was_folder_entry = current_path_before
-                    .as_ref()
-                    .is_some_and(|path| self.is_folder_navigation_entry_path(path.as_path()));
-                let same_path_index = current_path_before.as_ref().and_then(|path| {
-                    self.image_list
-                        .iter()
-                        .position(|candidate| candidate == path)
-                });
-                let first_media_index = self
-                    .image_list
-                    .iter()
-                    .position(|path| !self.is_folder_navigation_entry_path(path.as_path()));
+        let mut close = ctx.input(|input| input.key_pressed(egui::Key::Escape));
+        let mut export = None;
+        let screen_rect = ctx.screen_rect();
 
-                let resolved_index = if previous_was_folder_entry {
-                    first_media_index.or(same_path_index).unwrap_or_else(|| {
-                        current_index_before.min(self.image_list.len().saturating_sub(1))
-                    })
-                } else {
-                    same_path_index.or(first_media_index).unwrap_or_else(|| {
-                        current_index_before.min(self.image_list.len().saturating_sub(1))
-                    })
-                };
-                self.set_current_index_clamped(resolved_index);
+        egui::Area::new(egui::Id::new("scan_cleanup_backdrop"))
+            .fixed_pos(screen_rect.min)
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, screen_rect.size());
+                ui.painter().rect_filled(
+                    rect,
+                    0.0,
+                    egui::Color32::from_rgba_unmultiplied(4, 8, 13, 214),
+                );
+            });
 
-                if let Some(path) = self.current_media_path() {
-                    self.pending_window_title = Some(self.compute_window_title_for_path(&path));
-                }
+        let modal_width = (screen_rect.width() - 60.0).clamp(320.0, 560.0);
+        let preview_height = (screen_rect.height() - 320.0).clamp(160.0, 420.0);
+        egui::Area::new(egui::Id::new("scan_cleanup_window"))
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.set_width(modal_width);
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgba_unmultiplied(16, 23, 31, 252))
+                    .stroke(egui::Stroke::new(
+                        1.0,
+                        egui::Color32::from_rgba_unmultiplied(166, 207, 255, 62),
+                    ))
+                    .rounding(18.0)
+                    .inner_margin(egui::Margin::same(18.0))
+                    .show(ui, |ui| {
+                        ui.label(
+                            egui::RichText::new("Scan Cleanup")
+                                .size(20.0)
+                                .strong()
+                                .color(egui::Color32::WHITE),
+                        );
+                        ui.label(
+                            egui::RichText::new(
+                                "Straightens and cleans up a photographed document page.",
+                            )
+                            .color(egui::Color32::from_rgb(150, 170, 192)),
+                        );
+                        ui.add_space(10.0);
 
-                if self.manga_mode {
-                    self.manga_clear_cache();
-                    self.ensure_manga_loader();
-                    if Self::layout_mode_is_grid(self.manga_layout_mode) {
-                        self.restore_masonry_folder_metadata_snapshot();
-                        self.mark_manga_dimension_cache_current_if_complete();
-                    }
-                    self.manga_update_preload_queue();
-                }
+                        egui::Grid::new("scan_cleanup_settings")
+                            .num_columns(2)
+                            .spacing([24.0, 6.0])
+                            .show(ui, |ui| {
+                                ui.label("Deskew");
+                                ui.checkbox(
+                                    &mut dialog.settings.deskew,
+                                    format!("Level the text ({:+.2}°)", -dialog.skew_degrees),
+                                );
+                                ui.end_row();
 
-                ctx.request_repaint();
-            }
-        }
-    }
+                                ui.label("Paper");
+                                ui.checkbox(&mut dialog.settings.white_balance, "White balance");
+                                ui.end_row();
 
-    fn clear_pending_media_load(&mut self) {
-        self.pending_media_load = None;
-        self.retained_media_placeholder_visible = false;
-        self.defer_media_view_reset = false;
-    }
+                                ui.label("Contrast");
+                                ui.add(
+                                    egui::Slider::new(&mut dialog.settings.contrast, 0.0..=1.0)
+                                        .show_value(false),
+                                );
+                                ui.end_row();
 
-    fn clear_pending_manga_video_load(&mut self) {
-        self.pending_manga_video_load = None;
-    }
+                                ui.label("Black & white");
+                                ui.checkbox(&mut dialog.settings.binarize, "Threshold the page");
+                                ui.end_row();
+                            });
 
-    fn manga_video_load_pending_for_index(&self, index: usize) -> bool {
-        self.pending_manga_video_load
-            .as_ref()
-            .is_some_and(|pending| {
-                pending.index == index
-                    && self
-                        .image_list
-                        .get(index)
-                        .is_some_and(|current_path| current_path == &pending.path)
-            })
-    }
+                        ui.add_space(10.0);
+                        match dialog.preview_texture(ui.ctx()) {
+                            Some(texture) => {
+                                let size = texture.size_vec2();
+                                let scale =
+                                    (ui.available_width() / size.x).min(preview_height / size.y);
+                                ui.vertical_centered(|ui| {
+                                    ui.image((texture.id(), size * scale.min(1.0)));
+                                });
+                            }
+                            None => {
+                                ui.label(
+                                    egui::RichText::new("Nothing left after cropping.").weak(),
+                                );
+                            }
+                        }
 
-    fn start_async_manga_focused_video_load(
-        &mut self,
-        index: usize,
-        path: PathBuf,
-        muted: bool,
-        initial_volume: f64,
-        autoplay: bool,
-        seamless_lod_refresh: bool,
-    ) {
-        if !gstreamer_runtime_available() {
-            self.clear_pending_manga_video_load();
-            self.remove_manga_video_player(index);
-            self.remove_manga_video_texture(index);
-            self.manga_video_preview_resume_secs.remove(&index);
-            if self.manga_focused_video_index == Some(index) {
-                self.manga_focused_video_index = None;
-            }
-            self.video_playback_unavailable_reason =
-                Some(Self::gstreamer_missing_video_error_text().to_string());
+                        ui.add_space(14.0);
+                        if has_transparency {
+                            ui.horizontal(|ui| {
+                                self.export_alpha_unsaved |= png_export::alpha_option_ui(
+                                    ui,
+                                    &mut self.config.export_alpha,
+                                    &mut self.config.export_background_rgb,
+                                );
+                            });
+                        }
+                        ui.horizontal(|ui| {
+                            let enabled = dialog.export_rx.is_none() && dialog.preview.is_some();
+                            if ui
+                                .add_enabled(enabled, egui::Button::new("Export PNG"))
+                                .on_hover_text("Save the result next to the image")
+                                .clicked()
+                            {
+                                export = Some(scan_cleanup::ScanExportFormat::Png {
+                                    background: self.config.export_flatten_background(),
+                                });
+                            }
+                            if ui
+                                .add_enabled(enabled, egui::Button::new("Export PDF"))
+                                .on_hover_text("Save a one-page PDF next to the image")
+                                .clicked()
+                            {
+                                export = Some(scan_cleanup::ScanExportFormat::Pdf);
+                            }
+                            close |= ui.button("Close").clicked();
+                            if let Some(status) = dialog.status.as_ref() {
+                                ui.label(egui::RichText::new(status).weak());
+                            }
+                        });
+                    });
+            });
+
+        if let Some(format) = export {
+            self.start_scan_cleanup_export(format);
+        }
+        if close {
+            self.scan_cleanup = None;
+        }
+    }
+
+    /// Cleans the full-resolution frame on a worker thread and writes it next to the image.
+    fn start_scan_cleanup_export(&mut self, format: scan_cleanup::ScanExportFormat) {
+        self.save_export_alpha_choice();
+        let Some(img) = self.image.as_ref() else {
+            return;
+        };
+        let Some(dialog) = self.scan_cleanup.as_mut() else {
+            return;
+        };
+        if dialog.export_rx.is_some() || img.path != dialog.source_path {
             return;
         }
+        let frame = img.current_frame_data();
+        let (pixels, width, height) = (frame.pixels.clone(), frame.width, frame.height);
+        let (settings, skew_degrees) = (dialog.settings, dialog.skew_degrees);
+        let target = scan_cleanup::export_path(&img.path, format);
+
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        crate::async_runtime::spawn_blocking_or_thread("scan-cleanup-export", move || {
+            let result = scan_cleanup::export_cleaned(
+                settings,
+                skew_degrees,
+                &pixels,
+                width,
+                height,
+                format,
+                &target,
+            )
+            .map(|()| target);
+            let _ = tx.send(result);
+        });
+        dialog.export_rx = Some(rx);
+        dialog.status = Some("Exporting…".to_string());
+    }
 
-        let request_id = self.next_manga_video_load_request_id;
-        self.next_manga_video_load_request_id = self
-            .next_manga_video_load_request_id
-            .saturating_add(1)
-            .max(1);
-        let output_bounds = if self.is_masonry_mode() {
-            self.manga_video_output_bounds_for_index(index)
+    /// Opens the PDF export dialog for the marked images, or for every image in the folder
+    /// list when nothing is marked.
+    fn open_pdf_export(&mut self) {
+        let is_image = |path: &PathBuf| matches!(get_media_type(path), Some(MediaType::Image));
+        let marked: Vec<PathBuf> = self
+            .collect_marked_paths_in_current_order()
+            .into_iter()
+            .filter(is_image)
+            .collect();
+        let from_marked = !marked.is_empty();
+        let paths = if from_marked {
+            marked
         } else {
-            // Long-strip focused playback stays at source quality.
-            None
+            self.image_list
+                .iter()
+                .filter(|path| is_image(path))
+                .cloned()
+                .collect()
         };
+        self.pdf_export = Some(pdf_export::PdfExportDialog::new(
+            paths,
+            from_marked,
+            self.config.pdf_page_size,
+            self.config.pdf_margin_mm,
+        ));
+    }
 
-        self.pending_manga_video_load = Some(PendingMangaFocusedVideoLoad {
-            request_id,
-            index,
-            path: path.clone(),
-            started_at: Instant::now(),
-        });
-
-        let saved_position = self.manga_video_preview_resume_by_path.get(&path).copied();
+    fn draw_pdf_export_window(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = self.pdf_export.as_mut() else {
+            return;
+        };
+        let job = dialog.job.and_then(|id| self.jobs.job(id));
+        let running = self.jobs.is_active(dialog.job);
+        let mut close = ctx.input(|input| input.key_pressed(egui::Key::Escape));
+        let mut export = false;
+        let screen_rect = ctx.screen_rect();
 
-        let (
-            prefer_hardware_decode,
-            disable_hardware_decode,
-            enable_cuda_decode,
-            enable_d3d12_decode,
-        ) = self.effective_video_decoder_preferences();
-        self.manga_video_load_coordinator
-            .submit(MangaFocusedVideoLoadRequest {
-                request_id,
-                index,
-                path,
-                muted,
-                initial_volume,
-                prefer_hardware_decode,
-                disable_hardware_decode,
-                enable_cuda_decode,
-                enable_d3d12_decode,
-                output_bounds,
-                autoplay,
-                seamless_lod_refresh,
-                resume_position_secs: saved_position,
+        egui::Area::new(egui::Id::new("pdf_export_backdrop"))
+            .fixed_pos(screen_rect.min)
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, screen_rect.size());
+                ui.painter().rect_filled(
+                    rect,
+                    0.0,
+                    egui::Color32::from_rgba_unmultiplied(4, 8, 13, 214),
+                );
             });
-    }
 
-    fn poll_pending_manga_video_load(&mut self, ctx: &egui::Context) {
-        let mut applied_any = false;
-        let mut pending_dimension_updates = Vec::new();
+        let modal_width = (screen_rect.width() - 60.0).clamp(320.0, 460.0);
+        egui::Area::new(egui::Id::new("pdf_export_window"))
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.set_width(modal_width);
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgba_unmultiplied(16, 23, 31, 252))
+                    .stroke(egui::Stroke::new(
+                        1.0,
+                        egui::Color32::from_rgba_unmultiplied(166, 207, 255, 62),
+                    ))
+                    .rounding(18.0)
+                    .inner_margin(egui::Margin::same(18.0))
+                    .show(ui, |ui| {
+                        ui.label(
+                            egui::RichText::new("Export to PDF")
+                                .size(20.0)
+                                .strong()
+                                .color(egui::Color32::WHITE),
+                        );
+                        let source = if dialog.from_marked {
+                            "marked"
+                        } else {
+                            "in the folder list"
+                        };
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{} image{} {source}, one per page.",
+                                dialog.paths.len(),
+                                if dialog.paths.len() == 1 { "" } else { "s" },
+                            ))
+                            .color(egui::Color32::from_rgb(150, 170, 192)),
+                        );
+                        ui.add_space(10.0);
 
-        loop {
-            let result = match self.manga_video_load_coordinator.try_recv() {
-                Ok(result) => result,
-                Err(crossbeam_channel::TryRecvError::Empty) => break,
-                Err(crossbeam_channel::TryRecvError::Disconnected) => {
-                    self.clear_pending_manga_video_load();
-                    break;
-                }
-            };
+                        ui.add_enabled_ui(!running, |ui| {
+                            egui::Grid::new("pdf_export_settings")
+                                .num_columns(2)
+                                .spacing([24.0, 6.0])
+                                .show(ui, |ui| {
+                                    ui.label("Page size");
+                                    ui.horizontal(|ui| {
+                                        for size in PdfPageSize::ALL {
+                                            ui.radio_value(
+                                                &mut dialog.page_size,
+                                                size,
+                                                size.label(),
+                                            );
+                                        }
+                                    });
+                                    ui.end_row();
 
-            let (result_request_id, result_index, result_path, worker_elapsed) = (
-                result.request_id,
-                result.index,
-                &result.path,
-                result.worker_elapsed,
-            );
+                                    ui.label("Margin");
+                                    ui.add(
+                                        egui::Slider::new(&mut dialog.margin_mm, 0.0..=50.0)
+                                            .suffix(" mm"),
+                                    );
+                                    ui.end_row();
+                                });
+                        });
 
-            let Some(pending) = self.pending_manga_video_load.as_ref() else {
-                continue;
-            };
+                        if running {
+                            ui.add_space(10.0);
+                            let total = dialog.paths.len().max(1);
+                            let done = job.and_then(|job| job.progress).unwrap_or(0.0);
+                            let pages = (done * total as f32).round() as usize;
+                            ui.add(
+                                egui::ProgressBar::new(done)
+                                    .text(format!("Page {pages} of {total}")),
+                            );
+                        }
 
-            if result_request_id != pending.request_id
-                || result_index != pending.index
-                || result_path != &pending.path
-            {
-                self.perf_metrics
-                    .increment_counter("manga_video_async_stale", 1);
-                continue;
+                        ui.add_space(14.0);
+                        ui.horizontal(|ui| {
+                            export = ui
+                                .add_enabled(
+                                    !running && !dialog.paths.is_empty(),
+                                    egui::Button::new("Export PDF"),
+                                )
+                                .on_hover_text("Save the PDF next to the first image")
+                                .clicked();
+                            close |= ui
+                                .button("Close")
+                                .on_hover_text("A running export continues in the jobs panel")
+                                .clicked();
+                            if let Some(job) = job {
+                                ui.label(egui::RichText::new(job.status_text()).weak());
+                            }
+                        });
+                    });
+            });
+
+        if export {
+            self.start_pdf_export();
+        }
+        if close {
+            self.pdf_export = None;
+        }
+    }
+
+    /// Builds the PDF in a background job, reporting each finished page.
+    fn start_pdf_export(&mut self) {
+        let filter = self.config.downscale_filter.to_image_filter();
+        let Some(dialog) = self.pdf_export.as_mut() else {
+            return;
+        };
+        let Some(first) = dialog.paths.first() else {
+            return;
+        };
+        if self.jobs.is_active(dialog.job) {
+            return;
+        }
+        if (dialog.page_size, dialog.margin_mm)
+            != (self.config.pdf_page_size, self.config.pdf_margin_mm)
+        {
+            self.config.pdf_page_size = dialog.page_size;
+            self.config.pdf_margin_mm = dialog.margin_mm;
+            self.config.save();
+        }
+        let target = pdf_export::export_path(first);
+        let paths = dialog.paths.clone();
+        let (page_size, margin_mm) = (dialog.page_size, dialog.margin_mm);
+
+        let title = format!("PDF export ({} pages)", paths.len());
+        dialog.job = Some(self.jobs.spawn(title, move |context| {
+            pdf_export::export_images_pdf(
+                &paths,
+                page_size,
+                margin_mm,
+                filter,
+                &target,
+                context.cancel_flag(),
+                |done| context.set_progress(done as f32 / paths.len() as f32),
+            )
+            .map(|()| {
+                format!(
+                    "Exported {}",
+                    target.file_name().unwrap_or_default().to_string_lossy()
+                )
+            })
+        }));
+    }
+
+    /// Opens the animation conversion dialog for the current animated GIF or video.
+    fn open_animation_export(&mut self) {
+        if self.manga_mode {
+            return;
+        }
+        let (quality, fps) = (
+            self.config.animation_export_quality,
+            self.config.animation_export_fps,
+        );
+        let dialog = match self.current_media_type {
+            Some(MediaType::Video) => {
+                let Some(player) = self.video_player.as_ref() else {
+                    return;
+                };
+                let (Some(path), Some(duration)) = (self.current_media_path(), player.duration())
+                else {
+                    return;
+                };
+                let position = player.position().unwrap_or_default();
+                animation_export::AnimationExportDialog::for_video(
+                    path, duration, position, quality, fps,
+                )
             }
+            Some(MediaType::Image) => {
+                let Some(img) = self.image.as_ref().filter(|img| {
+                    img.is_animated()
+                        && img
+                            .path
+                            .extension()
+                            .is_some_and(|ext| ext.eq_ignore_ascii_case("gif"))
+                }) else {
+                    self.show_toast(i18n::tr("toast.not_animated_gif"));
+                    return;
+                };
+                animation_export::AnimationExportDialog::for_gif(img.path.clone(), quality, fps)
+            }
+            None => return,
+        };
+        self.animation_export = Some(dialog);
+    }
 
-            let Some(pending) = self.pending_manga_video_load.take() else {
-                continue;
-            };
+    fn draw_animation_export_window(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = self.animation_export.as_mut() else {
+            return;
+        };
+        let job = dialog.job.and_then(|id| self.jobs.job(id));
+        let running = self.jobs.is_active(dialog.job);
+        let mut close = ctx.input(|input| input.key_pressed(egui::Key::Escape));
+        let mut export = false;
+        let screen_rect = ctx.screen_rect();
 
-            let total_elapsed = pending.started_at.elapsed();
-            self.perf_metrics
-                .record_duration("manga_video_async_ms", total_elapsed);
-            self.perf_metrics
-                .record_duration("manga_video_async_worker_ms", worker_elapsed);
-            self.perf_metrics.record_duration(
-                "manga_video_async_queue_ms",
-                total_elapsed.saturating_sub(worker_elapsed),
-            );
+        egui::Area::new(egui::Id::new("animation_export_backdrop"))
+            .fixed_pos(screen_rect.min)
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, screen_rect.size());
+                ui.painter().rect_filled(
+                    rect,
+                    0.0,
+                    egui::Color32::from_rgba_unmultiplied(4, 8, 13, 214),
+                );
+            });
 
-            let still_targeted = self.manga_mode
-                && self.manga_focused_video_index == Some(result_index)
-                && self
-                    .image_list
-                    .get(result_index)
-                    .is_some_and(|current_path| current_path == result_path);
+        let modal_width = (screen_rect.width() - 60.0).clamp(320.0, 460.0);
+        egui::Area::new(egui::Id::new("animation_export_window"))
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.set_width(modal_width);
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgba_unmultiplied(16, 23, 31, 252))
+                    .stroke(egui::Stroke::new(
+                        1.0,
+                        egui::Color32::from_rgba_unmultiplied(166, 207, 255, 62),
+                    ))
+                    .rounding(18.0)
+                    .inner_margin(egui::Margin::same(18.0))
+                    .show(ui, |ui| {
+                        ui.label(
+                            egui::RichText::new("Convert animation")
+                                .size(20.0)
+                                .strong()
+                                .color(egui::Color32::WHITE),
+                        );
+                        ui.label(
+                            egui::RichText::new(
+                                dialog
+                                    .path
+                                    .file_name()
+                                    .unwrap_or_default()
+                                    .to_string_lossy(),
+                            )
+                            .color(egui::Color32::from_rgb(150, 170, 192)),
+                        );
+                        ui.add_space(10.0);
 
-            if !still_targeted {
-                self.perf_metrics
-                    .increment_counter("manga_video_async_stale", 1);
-                continue;
-            }
+                        ui.add_enabled_ui(!running, |ui| {
+                            egui::Grid::new("animation_export_settings")
+                                .num_columns(2)
+                                .spacing([24.0, 6.0])
+                                .show(ui, |ui| {
+                                    if let Some(duration) = dialog.video_duration {
+                                        let length = duration.as_secs_f32();
+                                        ui.label("Format");
+                                        ui.horizontal(|ui| {
+                                            for format in animation_export::AnimationFormat::ALL {
+                                                ui.radio_value(
+                                                    &mut dialog.settings.format,
+                                                    format,
+                                                    format.label(),
+                                                );
+                                            }
+                                        });
+                                        ui.end_row();
 
-            match result {
-                MangaFocusedVideoLoadResult {
-                    index,
-                    path,
-                    autoplay,
-                    seamless_lod_refresh,
-                    result: Ok(mut player),
-                    ..
-                } => {
-                    if self.manga_video_players.contains_key(&index)
-                        && !self.manga_video_player_matches(index)
-                    {
-                        self.remove_manga_video_player(index);
-                        self.remove_manga_video_texture(index);
-                    }
+                                        ui.label("Start");
+                                        ui.add(
+                                            egui::Slider::new(&mut dialog.start_secs, 0.0..=length)
+                                                .suffix(" s"),
+                                        );
+                                        ui.end_row();
 
-                    let mut synchronized_state = false;
-                    if seamless_lod_refresh && self.manga_video_player_matches(index) {
-                        if let Some(current_player) = self.manga_video_players.get_mut(&index) {
-                            let current_position = current_player.displayed_position();
-                            let current_was_playing = current_player.is_playing();
-                            let current_muted = current_player.is_muted();
-                            let current_volume = current_player.volume();
+                                        ui.label("End");
+                                        ui.add(
+                                            egui::Slider::new(&mut dialog.end_secs, 0.0..=length)
+                                                .suffix(" s"),
+                                        );
+                                        ui.end_row();
+                                    } else {
+                                        ui.label("Format");
+                                        ui.label(animation_export::AnimationFormat::WebP.label());
+                                        ui.end_row();
+                                    }
 
-                            if let Some(position) = current_position {
-                                let _ = player.seek_to_time_with_mode(
-                                    position.as_secs_f64(),
-                                    VideoSeekMode::Accurate,
+                                    ui.label("Quality");
+                                    ui.add(egui::Slider::new(
+                                        &mut dialog.settings.quality,
+                                        1..=100,
+                                    ));
+                                    ui.end_row();
+
+                                    ui.label("Frame rate");
+                                    ui.add(
+                                        egui::Slider::new(&mut dialog.settings.fps, 1..=60)
+                                            .suffix(" fps"),
+                                    )
+                                    .on_hover_text("Frames closer together than this are dropped");
+                                    ui.end_row();
+                                });
+                        });
+                        if dialog.video_duration.is_some() {
+                            let max_end =
+                                dialog.start_secs + animation_export::MAX_CLIP_LENGTH.as_secs_f32();
+                            if dialog.end_secs <= dialog.start_secs {
+                                ui.label(
+                                    egui::RichText::new("The end must come after the start.")
+                                        .weak(),
+                                );
+                            } else if dialog.end_secs > max_end {
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "Only the first {} seconds are converted.",
+                                        animation_export::MAX_CLIP_LENGTH.as_secs()
+                                    ))
+                                    .weak(),
                                 );
                             }
+                        }
 
-                            if current_was_playing {
-                                if !player.is_playing() {
-                                    let _ = player.play();
+                        if running {
+                            ui.add_space(10.0);
+                            ui.add(
+                                egui::ProgressBar::new(
+                                    job.and_then(|job| job.progress).unwrap_or(0.0),
+                                )
+                                .show_percentage(),
+                            );
+                        }
+
+                        let valid_range =
+                            dialog.video_duration.is_none() || dialog.end_secs > dialog.start_secs;
+                        ui.add_space(14.0);
+                        ui.horizontal(|ui| {
+                            export = ui
+                                .add_enabled(!running && valid_range, egui::Button::new("Convert"))
+                                .on_hover_text("Save the result next to the source file")
+                                .clicked();
+                            if running && ui.button("Cancel").clicked() {
+                                if let Some(id) = dialog.job {
+                                    self.jobs.cancel(id);
                                 }
-                            } else if player.is_playing() {
-                                let _ = player.pause();
                             }
+                            close |= ui
+                                .button("Close")
+                                .on_hover_text("A running job continues in the jobs panel")
+                                .clicked();
+                            if let Some(job) = job {
+                                ui.label(egui::RichText::new(job.status_text()).weak());
+                            }
+                        });
+                    });
+            });
 
-                            player.set_muted(current_muted);
-                            player.set_volume(current_volume);
-                            synchronized_state = true;
-                        }
-                    }
+        if export {
+            self.start_animation_export();
+        }
+        if close {
+            self.animation_export = None;
+        }
+    }
 
-                    if !synchronized_state {
-                        Self::apply_video_audio_overrides(
-                            &mut player,
-                            self.manga_video_user_muted,
-                            self.manga_video_user_volume,
+    /// Converts in a background job, reporting the fraction done.
+    fn start_animation_export(&mut self) {
+        let Some(dialog) = self.animation_export.as_mut() else {
+            return;
+        };
+        if self.jobs.is_active(dialog.job) {
+            return;
+        }
+        let settings = dialog.settings;
+        if (settings.quality, settings.fps)
+            != (
+                self.config.animation_export_quality,
+                self.config.animation_export_fps,
+            )
+        {
+            self.config.animation_export_quality = settings.quality;
+            self.config.animation_export_fps = settings.fps;
+            self.config.save();
+        }
+        let source = dialog.source();
+        let target = animation_export::export_path(&dialog.path, settings.format);
+        let title = format!(
+            "Convert {} to {}",
+            dialog
+                .path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy(),
+            settings.format.label()
+        );
+        dialog.job = Some(self.jobs.spawn(title, move |context| {
+            animation_export::export_animation(
+                &source,
+                &settings,
+                &target,
+                context.cancel_flag(),
+                |done| context.set_progress(done),
+            )
+            .map(|()| {
+                format!(
+                    "Saved {}",
+                    target.file_name().unwrap_or_default().to_string_lossy()
+                )
+            })
+        }));
+    }
+
+    /// Sets the trim in (`start`) or out mark at the playback position of the current video,
+    /// or clears it when it is already there.
+    fn toggle_video_trim_mark(&mut self, start: bool) {
+        if self.current_media_type != Some(MediaType::Video) {
+            return;
+        }
+        let (Some(path), Some(position)) = (
+            self.current_media_path(),
+            self.video_player
+                .as_ref()
+                .and_then(|player| player.position()),
+        ) else {
+            return;
+        };
+        let marks = self
+            .video_trim_marks
+            .get_or_insert_with(|| video_trim::TrimMarks::new(path.clone()));
+        if marks.path != path {
+            *marks = video_trim::TrimMarks::new(path);
+        }
+        if start {
+            marks.toggle_start(position);
+        } else {
+            marks.toggle_end(position);
+        }
+    }
+
+    /// Opens the trim dialog for the marked range of the current video.
+    fn open_video_trim(&mut self) {
+        if self.manga_mode || self.current_media_type != Some(MediaType::Video) {
+            return;
+        }
+        let (Some(path), Some(duration)) = (
+            self.current_media_path(),
+            self.video_player
+                .as_ref()
+                .and_then(|player| player.duration()),
+        ) else {
+            return;
+        };
+        let marks = self
+            .video_trim_marks
+            .clone()
+            .filter(|marks| marks.path == path)
+            .unwrap_or_else(|| video_trim::TrimMarks::new(path));
+        self.video_trim = Some(video_trim::TrimDialog::new(
+            &marks,
+            duration,
+            self.config.video_trim_mode,
+        ));
+    }
+
+    fn draw_video_trim_window(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = self.video_trim.as_mut() else {
+            return;
+        };
+        let job = dialog.job.and_then(|id| self.jobs.job(id));
+        let running = self.jobs.is_active(dialog.job);
+        let mut close = ctx.input(|input| input.key_pressed(egui::Key::Escape));
+        let mut export = false;
+        let screen_rect = ctx.screen_rect();
+
+        egui::Area::new(egui::Id::new("video_trim_backdrop"))
+            .fixed_pos(screen_rect.min)
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, screen_rect.size());
+                ui.painter().rect_filled(
+                    rect,
+                    0.0,
+                    egui::Color32::from_rgba_unmultiplied(4, 8, 13, 214),
+                );
+            });
+
+        let modal_width = (screen_rect.width() - 60.0).clamp(320.0, 460.0);
+        egui::Area::new(egui::Id::new("video_trim_window"))
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.set_width(modal_width);
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgba_unmultiplied(16, 23, 31, 252))
+                    .stroke(egui::Stroke::new(
+                        1.0,
+                        egui::Color32::from_rgba_unmultiplied(166, 207, 255, 62),
+                    ))
+                    .rounding(18.0)
+                    .inner_margin(egui::Margin::same(18.0))
+                    .show(ui, |ui| {
+                        ui.label(
+                            egui::RichText::new("Trim video")
+                                .size(20.0)
+                                .strong()
+                                .color(egui::Color32::WHITE),
+                        );
+                        ui.label(
+                            egui::RichText::new(
+                                dialog
+                                    .path
+                                    .file_name()
+                                    .unwrap_or_default()
+                                    .to_string_lossy(),
+                            )
+                            .color(egui::Color32::from_rgb(150, 170, 192)),
                         );
+                        ui.add_space(10.0);
 
-                        if autoplay && !player.is_playing() {
-                            if let Err(err) = player.play() {
-                                self.manga_video_failed.insert(index);
-                                self.video_playback_unavailable_reason = Some(err);
-                                self.manga_focused_video_index = None;
-                                continue;
-                            }
-                        }
-                    }
+                        let length = dialog.duration.as_secs_f32();
+                        ui.add_enabled_ui(!running, |ui| {
+                            egui::Grid::new("video_trim_settings")
+                                .num_columns(2)
+                                .spacing([24.0, 6.0])
+                                .show(ui, |ui| {
+                                    ui.label("Output");
+                                    ui.vertical(|ui| {
+                                        ui.radio_value(
+                                            &mut dialog.mode,
+                                            TrimMode::Copy,
+                                            "Copy streams",
+                                        )
+                                        .on_hover_text("Fast and lossless; cuts land on keyframes");
+                                        ui.radio_value(
+                                            &mut dialog.mode,
+                                            TrimMode::Mp4,
+                                            "Re-encode to MP4",
+                                        )
+                                        .on_hover_text("H.264 and AAC, cut exactly at the marks");
+                                    });
+                                    ui.end_row();
 
-                    // Re-check resume position at apply-time to cover races where
-                    // fullscreen/preview position was recorded after this async load started.
-                    let resume_position = self.manga_resume_position_for_index(index);
-                    Self::seek_video_player_to_resume_position(&mut player, resume_position);
-                    if let Some(position) = player.displayed_position() {
-                        self.manga_record_video_preview_resume_secs(index, position);
-                    }
+                                    ui.label("Start");
+                                    ui.add(
+                                        egui::Slider::new(&mut dialog.start_secs, 0.0..=length)
+                                            .suffix(" s"),
+                                    );
+                                    ui.end_row();
 
-                    let dims = player.dimensions();
-                    if dims.0 > 0 && dims.1 > 0 {
-                        if !self.masonry_authoritative_dimension_lock_active() {
-                            if let Some(ref mut loader) = self.manga_loader {
-                                if loader.update_video_dimensions(index, dims.0, dims.1) {
-                                    pending_dimension_updates.push(index);
-                                }
-                            }
+                                    ui.label("End");
+                                    ui.add(
+                                        egui::Slider::new(&mut dialog.end_secs, 0.0..=length)
+                                            .suffix(" s"),
+                                    );
+                                    ui.end_row();
+                                });
+                        });
+                        let valid_range = dialog.has_valid_range();
+                        if valid_range {
+                            let (start, end) = dialog.range();
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "Clip length {}",
+                                    video_player::format_duration(end - start)
+                                ))
+                                .weak(),
+                            );
+                        } else {
+                            ui.label(
+                                egui::RichText::new("The end must come after the start.").weak(),
+                            );
                         }
-                    }
 
-                    if !self.is_masonry_mode() {
-                        if let Some(frame) = player.get_frame() {
-                            let displayed_position = frame.pts;
-                            let target_side = self.manga_target_texture_side_for_dynamic_media(
-                                index,
-                                MangaMediaType::Video,
+                        if running {
+                            ui.add_space(10.0);
+                            ui.add(
+                                egui::ProgressBar::new(
+                                    job.and_then(|job| job.progress).unwrap_or(0.0),
+                                )
+                                .show_percentage(),
                             );
-                            let no_downscale =
-                                frame.width <= target_side && frame.height <= target_side;
-                            let (w, h, color_image) = if no_downscale {
-                                let size = [frame.width as usize, frame.height as usize];
-                                match try_color_image_from_opaque_rgba_bytes(size, frame.pixels) {
-                                    Ok(color_image) => (frame.width, frame.height, color_image),
-                                    Err(pixels) => (
-                                        frame.width,
-                                        frame.height,
-                                        egui::ColorImage::from_rgba_unmultiplied(size, &pixels),
-                                    ),
+                        }
+
+                        ui.add_space(14.0);
+                        ui.horizontal(|ui| {
+                            export = ui
+                                .add_enabled(!running && valid_range, egui::Button::new("Trim"))
+                                .on_hover_text("Save the clip next to the source file")
+                                .clicked();
+                            if running && ui.button("Cancel").clicked() {
+                                if let Some(id) = dialog.job {
+                                    self.jobs.cancel(id);
                                 }
-                            } else {
-                                let (w, h, pixels) = downscale_rgba_if_needed(
-                                    frame.width,
-                                    frame.height,
-                                    &frame.pixels,
-                                    target_side,
-                                    self.config.downscale_filter.to_image_filter(),
-                                );
-                                (
-                                    w,
-                                    h,
-                                    egui::ColorImage::from_rgba_unmultiplied(
-                                        [w as usize, h as usize],
-                                        pixels.as_ref(),
-                                    ),
-                                )
-                            };
-                            let texture_options =
-                                self.config.texture_filter_video.to_egui_options();
+                            }
+                            close |= ui
+                                .button("Close")
+                                .on_hover_text("A running job continues in the jobs panel")
+                                .clicked();
+                            if let Some(job) = job {
+                                ui.label(egui::RichText::new(job.status_text()).weak());
+                            }
+                        });
+                    });
+            });
+
+        if export {
+            self.start_video_trim();
+        }
+        if close {
+            self.video_trim = None;
+        }
+    }
+
+    /// Runs ffmpeg in a background job, reporting the fraction done.
+    fn start_video_trim(&mut self) {
+        let Some(dialog) = self.video_trim.as_mut() else {
+            return;
+        };
+        if self.jobs.is_active(dialog.job) {
+            return;
+        }
+        if dialog.mode != self.config.video_trim_mode {
+            self.config.video_trim_mode = dialog.mode;
+            self.config.save();
+        }
+        let ffmpeg = self
+            .config
+            .ffmpeg_path
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("ffmpeg"));
+        let job = dialog.job(ffmpeg);
+        let title = format!(
+            "Trim {}",
+            dialog
+                .path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+        );
+        dialog.job = Some(self.jobs.spawn(title, move |context| {
+            video_trim::trim_video(&job, context.cancel_flag(), |done| {
+                context.set_progress(done)
+            })
+            .map(|()| {
+                format!(
+                    "Saved {}",
+                    job.target.file_name().unwrap_or_default().to_string_lossy()
+                )
+            })
+        }));
+    }
+
+    /// Opens the batch rename dialog for every file in the folder list.
+    fn open_batch_rename(&mut self) {
+        let paths: Vec<PathBuf> = self
+            .image_list
+            .iter()
+            .filter(|path| !self.is_folder_navigation_entry_path(path))
+            .cloned()
+            .collect();
+        if paths.is_empty() {
+            return;
+        }
+        self.file_action_menu = None;
+        let mut dialog = batch_rename::BatchRenameDialog::new(paths);
+        dialog.ensure_dates(&mut self.jobs);
+        self.batch_rename = Some(dialog);
+    }
+
+    fn draw_batch_rename_window(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = self.batch_rename.as_mut() else {
+            return;
+        };
+        dialog.ensure_dates(&mut self.jobs);
+        if dialog.poll() {
+            ctx.request_repaint_after(Duration::from_millis(50));
+        }
+
+        let mut close = ctx.input(|input| input.key_pressed(egui::Key::Escape));
+        let mut rename = false;
+        let mut undo = false;
+        let can_undo = self.last_batch_rename.is_some();
+        let plan = dialog.plan();
+        let screen_rect = ctx.screen_rect();
+
+        egui::Area::new(egui::Id::new("batch_rename_backdrop"))
+            .fixed_pos(screen_rect.min)
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, screen_rect.size());
+                ui.painter().rect_filled(
+                    rect,
+                    0.0,
+                    egui::Color32::from_rgba_unmultiplied(4, 8, 13, 214),
+                );
+            });
+
+        let modal_width = (screen_rect.width() - 60.0).clamp(320.0, 560.0);
+        egui::Area::new(egui::Id::new("batch_rename_window"))
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.set_width(modal_width);
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgba_unmultiplied(16, 23, 31, 252))
+                    .stroke(egui::Stroke::new(
+                        1.0,
+                        egui::Color32::from_rgba_unmultiplied(166, 207, 255, 62),
+                    ))
+                    .rounding(18.0)
+                    .inner_margin(egui::Margin::same(18.0))
+                    .show(ui, |ui| {
+                        ui.label(
+                            egui::RichText::new("Batch Rename")
+                                .size(20.0)
+                                .strong()
+                                .color(egui::Color32::WHITE),
+                        );
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{} file{} in the folder list. Tokens: {{n}} counter, {{name}} original name, {{date}} and {{time}} taken.",
+                                dialog.paths.len(),
+                                if dialog.paths.len() == 1 { "" } else { "s" },
+                            ))
+                            .color(egui::Color32::from_rgb(150, 170, 192)),
+                        );
+                        ui.add_space(10.0);
 
-                            if let Some((texture, stored_w, stored_h)) =
-                                self.manga_video_textures.get_mut(&index)
-                            {
-                                texture.set(color_image, texture_options);
-                                *stored_w = w;
-                                *stored_h = h;
-                            } else {
-                                let texture = ctx.load_texture(
-                                    format!("manga_video_{}", index),
-                                    color_image,
-                                    texture_options,
+                        egui::Grid::new("batch_rename_settings")
+                            .num_columns(2)
+                            .spacing([24.0, 6.0])
+                            .show(ui, |ui| {
+                                ui.label("Pattern");
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut dialog.template)
+                                        .desired_width(f32::INFINITY),
                                 );
-                                self.manga_video_textures.insert(index, (texture, w, h));
+                                ui.end_row();
+
+                                ui.label("Counter");
+                                ui.horizontal(|ui| {
+                                    ui.add(
+                                        egui::DragValue::new(&mut dialog.start)
+                                            .range(0..=999_999)
+                                            .prefix("start "),
+                                    );
+                                    ui.add(
+                                        egui::DragValue::new(&mut dialog.padding)
+                                            .range(0..=8)
+                                            .prefix("digits "),
+                                    );
+                                });
+                                ui.end_row();
+                            });
+
+                        ui.add_space(10.0);
+                        match &plan {
+                            Ok(None) => {
+                                ui.horizontal(|ui| {
+                                    ui.spinner();
+                                    ui.label("Reading dates…");
+                                });
                             }
-                            if let Some(path) = self.image_list.get(index).cloned() {
-                                self.manga_video_texture_paths.insert(index, path);
+                            Ok(Some(renames)) if renames.is_empty() => {
+                                ui.label(
+                                    egui::RichText::new("Every file already has its new name.")
+                                        .weak(),
+                                );
                             }
-                            if let Some(position) = displayed_position {
-                                self.manga_record_video_preview_resume_secs(index, position);
+                            Ok(Some(renames)) => {
+                                egui::ScrollArea::vertical()
+                                    .max_height(260.0)
+                                    .auto_shrink([false, true])
+                                    .show(ui, |ui| {
+                                        for (original, renamed) in renames {
+                                            let name = |path: &PathBuf| {
+                                                path.file_name()
+                                                    .unwrap_or_default()
+                                                    .to_string_lossy()
+                                                    .into_owned()
+                                            };
+                                            ui.label(format!(
+                                                "{}  →  {}",
+                                                name(original),
+                                                name(renamed)
+                                            ));
+                                        }
+                                    });
+                            }
+                            Err(err) => {
+                                ui.colored_label(egui::Color32::from_rgb(255, 148, 148), err);
                             }
                         }
-                    }
 
-                    self.manga_video_player_paths.insert(index, path);
-                    self.manga_video_players.insert(index, player);
-                    self.error_message = None;
-                    self.manga_evict_distant_video_players(index, None);
-                    applied_any = true;
-                }
-                MangaFocusedVideoLoadResult {
-                    index,
-                    path,
-                    result: Err(err),
-                    ..
-                } => {
-                    self.manga_video_failed.insert(index);
-                    self.video_playback_unavailable_reason =
-                        Some(format!("Failed to load video: {}", err));
-                    eprintln!(
-                        "Failed to create video player for manga index {} ({}): {}",
-                        index,
-                        path.display(),
-                        err
-                    );
+                        ui.add_space(14.0);
+                        ui.horizontal(|ui| {
+                            let ready = matches!(&plan, Ok(Some(renames)) if !renames.is_empty());
+                            rename = ui
+                                .add_enabled(ready, egui::Button::new("Rename"))
+                                .clicked();
+                            undo = ui
+                                .add_enabled(can_undo, egui::Button::new("Undo Last Batch"))
+                                .clicked();
+                            close |= ui.button("Close").clicked();
+                            if let Some(status) = dialog.status.as_ref() {
+                                ui.label(egui::RichText::new(status).weak());
+                            }
+                        });
+                    });
+            });
 
-                    if self.manga_focused_video_index == Some(index)
-                        && !self.manga_video_players.contains_key(&index)
-                    {
-                        self.manga_focused_video_index = None;
-                    }
-                }
+        if rename {
+            if let Ok(Some(renames)) = plan {
+                self.apply_batch_rename(renames, false);
             }
+        } else if undo {
+            if let Some(renames) = self.last_batch_rename.clone() {
+                let reverted = renames
+                    .into_iter()
+                    .rev()
+                    .map(|(original, renamed)| (renamed, original))
+                    .collect();
+                self.apply_batch_rename(reverted, true);
+            }
+        }
+        if close {
+            self.batch_rename = None;
         }
+    }
 
-        if self.is_masonry_mode()
-            && !self.masonry_authoritative_dimension_lock_active()
-            && !pending_dimension_updates.is_empty()
+    /// Renames a batch (or reverts the last one) and carries sidecars, ratings and
+    /// annotation layers over to the new names.
+    fn apply_batch_rename(&mut self, renames: Vec<(PathBuf, PathBuf)>, undo: bool) {
+        let originals: Vec<PathBuf> = renames
+            .iter()
+            .map(|(original, _)| original.clone())
+            .collect();
+        let result = match renames
+            .iter()
+            .find(|(_, renamed)| renamed.exists() && !originals.contains(renamed))
         {
-            self.masonry_queue_dimension_updates(pending_dimension_updates);
-            if !self.masonry_navigation_active_for_heavy_work() {
-                let force_flush = !self.masonry_metadata_preload_active;
-                self.masonry_flush_pending_dimension_updates(force_flush);
+            Some((_, taken)) => Err(format!(
+                "{} already exists",
+                taken.file_name().unwrap_or_default().to_string_lossy()
+            )),
+            None => {
+                self.release_video_resources_for_paths(&originals);
+                self.apply_path_renames(&renames)
             }
-        }
+        };
 
-        if applied_any {
-            ctx.request_repaint();
+        let status = match result {
+            Ok(()) => {
+                batch_rename::move_companion_files(&renames);
+                for (original, renamed) in &renames {
+                    self.annotations.rename_path(original, renamed);
+                }
+                if let Some(directory) = renames.first().and_then(|(path, _)| path.parent()) {
+                    self.ratings.forget_directory(directory);
+                }
+                if let Some(dialog) = self.batch_rename.as_mut() {
+                    for path in &mut dialog.paths {
+                        if let Some((_, renamed)) =
+                            renames.iter().find(|(original, _)| original == path)
+                        {
+                            *path = renamed.clone();
+                        }
+                    }
+                }
+                let count = renames.len();
+                self.last_batch_rename = (!undo).then_some(renames);
+                format!(
+                    "{} {count} file{}",
+                    if undo { "Restored" } else { "Renamed" },
+                    if count == 1 { "" } else { "s" }
+                )
+            }
+            Err(err) => {
+                tracing::warn!("Batch rename failed: {err}");
+                err
+            }
+        };
+        if let Some(dialog) = self.batch_rename.as_mut() {
+            dialog.status = Some(status);
         }
     }
 
-    fn start_async_image_load(
-        &mut self,
-        path: PathBuf,
-        max_texture_side: u32,
-        downscale_filter: FilterType,
-        gif_filter: FilterType,
-    ) {
-        let request_id = self.next_media_load_request_id;
-        self.next_media_load_request_id = self.next_media_load_request_id.saturating_add(1).max(1);
-
-        self.pending_media_load = Some(PendingMediaLoad {
-            request_id,
-            path: path.clone(),
-            kind: PendingMediaLoadKind::Image,
-            max_texture_side: Some(max_texture_side),
-            started_at: Instant::now(),
-        });
+    /// Picks the pair to compare: the two marked images, or the marked image (A) against
+    /// the current one (B).
+    fn compare_pair(&self) -> Result<(PathBuf, PathBuf), String> {
+        let is_image = |path: &PathBuf| matches!(get_media_type(path), Some(MediaType::Image));
+        let marked: Vec<&PathBuf> = self
+            .image_list
+            .iter()
+            .filter(|path| self.is_path_marked(path) && is_image(path))
+            .collect();
+        let current = if self.manga_mode {
+            None
+        } else {
+            self.image_list
+                .get(self.current_index)
+                .filter(|path| is_image(path))
+        };
+        match (current, marked.as_slice()) {
+            (_, [a, b]) => Ok(((*a).clone(), (*b).clone())),
+            (Some(current), [marked]) if *marked != current => {
+                Ok(((*marked).clone(), current.clone()))
+            }
+            _ => Err(
+                "Mark one other image (space or ctrl+click) to compare it with the current image, or mark exactly two images."
+                    .to_string(),
+            ),
+        }
+    }
 
-        self.media_load_coordinator.submit(MediaLoadRequest::Image {
-            request_id,
-            path,
-            max_texture_side,
-            downscale_filter,
-            gif_filter,
-        });
+    fn open_compare_view(&mut self) {
+        match self.compare_pair() {
+            Ok((a, b)) => self.open_compare_view_for(a, b),
+            Err(err) => {
+                self.compare_view = Some(compare::CompareView::new(
+                    PathBuf::new(),
+                    PathBuf::new(),
+                    compare::CompareState::Failed(err),
+                ));
+            }
+        }
     }
 
-    fn live_video_output_bounds_for_solo(&self) -> Option<(u32, u32)> {
-        let viewport = self.solo_viewport_size_for_lod();
-        let max_side = self.max_texture_side.max(1);
-        let width = (viewport.x.ceil() as u32).clamp(1, max_side);
-        let height = (viewport.y.ceil() as u32).clamp(1, max_side);
-        Some((width, height))
+    /// Decodes `a` and `b` on a worker thread; the view shows a spinner until they arrive.
+    fn open_compare_view_for(&mut self, a: PathBuf, b: PathBuf) {
+        let state = self.load_compare_pair(a.clone(), b.clone());
+        self.compare_view = Some(compare::CompareView::new(a, b, state));
     }
 
-    fn async_video_output_bounds_for_solo(&self) -> Option<(u32, u32)> {
+    fn load_compare_pair(&self, a: PathBuf, b: PathBuf) -> compare::CompareState {
         let max_side = self.max_texture_side.max(1);
-        let monitor = get_primary_monitor_size();
-        if monitor.x > 0.0 && monitor.y > 0.0 {
-            let width = (monitor.x.ceil() as u32).clamp(1, max_side);
-            let height = (monitor.y.ceil() as u32).clamp(1, max_side);
-            Some((width, height))
-        } else {
-            self.live_video_output_bounds_for_solo()
-        }
+        let filter = self.config.downscale_filter.to_image_filter();
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        crate::async_runtime::spawn_blocking_or_thread("compare-load", move || {
+            let _ = tx.send(compare::load_pair(&a, &b, max_side, filter));
+        });
+        compare::CompareState::Loading(rx)
     }
 
-    fn start_async_video_load(&mut self, path: PathBuf) {
-        if !gstreamer_runtime_available() {
-            self.suppress_video_controls_for_next_video_load = false;
-            self.suppress_video_controls_for_request_id = None;
-            self.pending_media_load = None;
-            self.drop_retained_media_placeholder();
-            self.set_video_playback_unavailable_for_path(
-                &path,
-                Self::gstreamer_missing_video_error_text().to_string(),
-            );
+    /// Full-window compare view with shared zoom/pan. Escape closes it.
+    fn draw_compare_view(&mut self, ctx: &egui::Context) {
+        let Some(view) = self.compare_view.as_mut() else {
             return;
+        };
+        if view.poll(ctx) {
+            ctx.request_repaint_after(Duration::from_millis(50));
         }
 
-        let request_id = self.next_media_load_request_id;
-        self.next_media_load_request_id = self.next_media_load_request_id.saturating_add(1).max(1);
-
-        if self.suppress_video_controls_for_next_video_load {
-            self.suppress_video_controls_for_request_id = Some(request_id);
-        } else {
-            self.suppress_video_controls_for_request_id = None;
-        }
-        self.suppress_video_controls_for_next_video_load = false;
-
-        let muted = if self.config.video_muted_remember {
-            self.config.state_muted
-        } else {
-            self.config.video_muted_by_default
-        };
-        let initial_volume = if self.config.video_volume_remember {
-            self.config.state_volume
-        } else {
-            self.config.video_default_volume
+        let mut close = ctx.input(|input| input.key_pressed(egui::Key::Escape));
+        let screen_rect = ctx.screen_rect();
+        let file_name = |path: &Path| {
+            path.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned()
         };
-        let (
-            prefer_hardware_decode,
-            disable_hardware_decode,
-            enable_cuda_decode,
-            enable_d3d12_decode,
-        ) = self.effective_video_decoder_preferences();
-        let output_bounds = self.async_video_output_bounds_for_solo();
 
-        self.pending_media_load = Some(PendingMediaLoad {
-            request_id,
-            path: path.clone(),
-            kind: PendingMediaLoadKind::Video,
-            max_texture_side: output_bounds.map(|(width, height)| width.max(height)),
-            started_at: Instant::now(),
-        });
+        let ready = matches!(view.state, compare::CompareState::Ready(_));
+        let mut swap = false;
+        egui::Area::new(egui::Id::new("compare_view"))
+            .fixed_pos(screen_rect.min)
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, screen_rect.size());
+                ui.set_min_size(rect.size());
+                ui.painter().rect_filled(
+                    rect,
+                    0.0,
+                    egui::Color32::from_rgba_unmultiplied(4, 8, 13, 246),
+                );
+                let content = rect.with_min_y(rect.min.y + 52.0).shrink(12.0);
+
+                let toolbar_rect = rect.with_max_y(rect.min.y + 52.0);
+                ui.allocate_new_ui(
+                    egui::UiBuilder::new()
+                        .max_rect(toolbar_rect.shrink2(egui::vec2(0.0, 8.0)))
+                        .layout(egui::Layout::top_down(egui::Align::Center)),
+                    |ui| {
+                        egui::Frame::popup(ui.style()).show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new("Compare").strong());
+                                ui.separator();
+                                ui.add_enabled_ui(ready, |ui| {
+                                    for layout in compare::CompareLayout::ALL {
+                                        ui.selectable_value(
+                                            &mut view.layout,
+                                            layout,
+                                            layout.label(),
+                                        );
+                                    }
+                                    ui.separator();
+                                    swap = ui
+                                        .button("Swap")
+                                        .on_hover_text("Swap images A and B")
+                                        .clicked();
+                                    if ui
+                                        .button("Reset view")
+                                        .on_hover_text("Fit both images (double-click)")
+                                        .clicked()
+                                    {
+                                        view.reset_view();
+                                    }
+                                });
+                                ui.separator();
+                                close |=
+                                    ui.small_button("✕").on_hover_text("Close (Esc)").clicked();
+                            });
+                        });
+                    },
+                );
 
-        let saved_position = self.manga_video_preview_resume_by_path.get(&path).copied();
+                let textures = match &view.state {
+                    compare::CompareState::Ready(textures) => textures.clone(),
+                    compare::CompareState::Loading(_) => {
+                        ui.put(content, egui::Spinner::new().size(32.0));
+                        return;
+                    }
+                    compare::CompareState::Failed(err) => {
+                        ui.put(
+                            content,
+                            egui::Label::new(
+                                egui::RichText::new(err.as_str())
+                                    .color(egui::Color32::from_rgb(255, 190, 135))
+                                    .size(16.0),
+                            ),
+                        );
+                        return;
+                    }
+                };
 
-        // FIX: Destroy the 1st-frame thumbnail so the UI is forced to use our seamless masonry frame!
-        if saved_position.is_some() || self.pending_mode_switch_placeholder.is_some() {
-            self.pending_video_thumbnail_placeholder = None;
-        }
+                let panes = match view.layout {
+                    compare::CompareLayout::SideBySide => {
+                        let half = (content.width() - 8.0) / 2.0;
+                        vec![
+                            content.with_max_x(content.min.x + half),
+                            content.with_min_x(content.max.x - half),
+                        ]
+                    }
+                    compare::CompareLayout::Wipe | compare::CompareLayout::Difference => {
+                        vec![content]
+                    }
+                };
 
-        self.media_load_coordinator.submit(MediaLoadRequest::Video {
-            request_id,
-            path,
-            muted,
-            initial_volume,
-            prefer_hardware_decode,
-            disable_hardware_decode,
-            enable_cuda_decode,
-            enable_d3d12_decode,
-            output_bounds,
-            resume_position_secs: saved_position,
-        });
-    }
+                // Zoom and pan apply to every pane at once.
+                let response = ui.interact(
+                    content,
+                    egui::Id::new("compare_view_content"),
+                    egui::Sense::click_and_drag(),
+                );
+                if response.double_clicked() {
+                    view.reset_view();
+                }
+                if let Some(pointer) = response.hover_pos() {
+                    let (scroll, zoom_delta) =
+                        ui.input(|input| (input.smooth_scroll_delta.y, input.zoom_delta()));
+                    let factor = zoom_delta * (scroll * 0.0015).exp();
+                    if (factor - 1.0).abs() > f32::EPSILON {
+                        let pane = panes
+                            .iter()
+                            .copied()
+                            .find(|pane| pane.contains(pointer))
+                            .unwrap_or(content);
+                        view.zoom_at(pane, pointer, factor);
+                    }
+                }
 
-    fn poll_pending_media_load(&mut self, ctx: &egui::Context) {
-        let mut applied_any = false;
+                let size_a = textures.a.size_vec2();
+                let size_b = textures.b.size_vec2();
+                let full_uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+                let caption =
+                    |ui: &egui::Ui, pos: egui::Pos2, align: egui::Align2, text: String| {
+                        ui.painter().text(
+                            pos,
+                            align,
+                            text,
+                            egui::FontId::proportional(13.0),
+                            egui::Color32::from_rgb(200, 215, 232),
+                        );
+                    };
+
+                match view.layout {
+                    compare::CompareLayout::SideBySide => {
+                        for (pane, texture, size, path) in [
+                            (panes[0], &textures.a, size_a, &view.a),
+                            (panes[1], &textures.b, size_b, &view.b),
+                        ] {
+                            let painter = ui.painter_at(pane);
+                            painter.image(
+                                texture.id(),
+                                view.image_rect(pane, size),
+                                full_uv,
+                                egui::Color32::WHITE,
+                            );
+                            caption(
+                                ui,
+                                pane.center_bottom(),
+                                egui::Align2::CENTER_BOTTOM,
+                                file_name(path),
+                            );
+                        }
+                    }
+                    compare::CompareLayout::Wipe => {
+                        // B is resized to A's dimensions, so both share A's rect.
+                        let image_rect = view.image_rect(content, size_a);
+                        let divider_x = image_rect.min.x + image_rect.width() * view.wipe;
+                        let painter = ui.painter_at(content);
+                        painter.image(textures.b.id(), image_rect, full_uv, egui::Color32::WHITE);
+                        painter
+                            .with_clip_rect(content.intersect(image_rect.with_max_x(divider_x)))
+                            .image(textures.a.id(), image_rect, full_uv, egui::Color32::WHITE);
+
+                        let divider_top = image_rect.min.y.max(content.min.y);
+                        let divider_bottom = image_rect.max.y.min(content.max.y);
+                        let handle = egui::Rect::from_x_y_ranges(
+                            divider_x - 8.0..=divider_x + 8.0,
+                            divider_top..=divider_bottom,
+                        );
+                        let handle_response = ui
+                            .interact(
+                                handle,
+                                egui::Id::new("compare_wipe_divider"),
+                                egui::Sense::drag(),
+                            )
+                            .on_hover_cursor(egui::CursorIcon::ResizeHorizontal);
+                        if handle_response.dragged() {
+                            if let Some(pointer) = handle_response.interact_pointer_pos() {
+                                view.wipe = ((pointer.x - image_rect.min.x) / image_rect.width())
+                                    .clamp(0.0, 1.0);
+                            }
+                        }
+                        painter.line_segment(
+                            [
+                                egui::pos2(divider_x, divider_top),
+                                egui::pos2(divider_x, divider_bottom),
+                            ],
+                            egui::Stroke::new(2.0, egui::Color32::WHITE),
+                        );
+                        painter.circle(
+                            egui::pos2(divider_x, (divider_top + divider_bottom) / 2.0),
+                            7.0,
+                            egui::Color32::from_rgb(16, 23, 31),
+                            egui::Stroke::new(2.0, egui::Color32::WHITE),
+                        );
+                        caption(
+                            ui,
+                            content.left_bottom(),
+                            egui::Align2::LEFT_BOTTOM,
+                            format!("A: {}", file_name(&view.a)),
+                        );
+                        caption(
+                            ui,
+                            content.right_bottom(),
+                            egui::Align2::RIGHT_BOTTOM,
+                            format!("B: {}", file_name(&view.b)),
+                        );
+                    }
+                    compare::CompareLayout::Difference => {
+                        ui.painter_at(content).image(
+                            textures.difference.id(),
+                            view.image_rect(content, size_a),
+                            full_uv,
+                            egui::Color32::WHITE,
+                        );
+                        caption(
+                            ui,
+                            content.center_bottom(),
+                            egui::Align2::CENTER_BOTTOM,
+                            format!(
+                                "{} vs {}: {:.2}% of pixels differ",
+                                file_name(&view.a),
+                                file_name(&view.b),
+                                textures.differing_fraction * 100.0
+                            ),
+                        );
+                    }
+                }
 
-        loop {
-            let result = match self.media_load_coordinator.try_recv() {
-                Ok(result) => result,
-                Err(crossbeam_channel::TryRecvError::Empty) => break,
-                Err(crossbeam_channel::TryRecvError::Disconnected) => {
-                    self.drop_retained_media_placeholder();
-                    self.clear_pending_media_load();
-                    break;
+                if response.dragged() {
+                    view.offset += response.drag_delta();
                 }
-            };
+            });
 
-            let (result_request_id, result_path, worker_elapsed) = match &result {
-                MediaLoadResult::Image {
-                    request_id,
-                    path,
-                    worker_elapsed,
-                    ..
-                } => (*request_id, path, *worker_elapsed),
-                MediaLoadResult::Video {
-                    request_id,
-                    path,
-                    worker_elapsed,
-                    ..
-                } => (*request_id, path, *worker_elapsed),
-            };
+        if close {
+            self.compare_view = None;
+        } else if swap {
+            let (b, a) = (view.a.clone(), view.b.clone());
+            let (layout, wipe, zoom, offset) = (view.layout, view.wipe, view.zoom, view.offset);
+            self.open_compare_view_for(a, b);
+            if let Some(view) = self.compare_view.as_mut() {
+                view.layout = layout;
+                view.wipe = wipe;
+                view.zoom = zoom;
+                view.offset = offset;
+            }
+        }
+    }
 
-            let Some(pending) = self.pending_media_load.as_ref() else {
-                continue;
-            };
+    /// Opens the monitor wall on the folder of the current item. Not over the manga reader.
+    fn open_monitor_wall(&mut self) {
+        if self.manga_mode {
+            return;
+        }
+        let Some(anchor) = self
+            .current_media_path()
+            .or_else(|| self.image_list.first().cloned())
+        else {
+            return;
+        };
+        self.monitor_wall = Some(monitor_wall::MonitorWall::new(
+            monitor_wall::wall_directory(&anchor),
+            self.config.monitor_wall_count,
+        ));
+    }
 
-            if result_request_id != pending.request_id || result_path != &pending.path {
-                self.perf_metrics
-                    .increment_counter("load_media_async_stale", 1);
-                continue;
-            }
+    /// Full-window grid of the newest images in the watched folder. Folder changes are
+    /// picked up through the shared directory index; clicking a tile opens that image.
+    fn draw_monitor_wall(&mut self, ctx: &egui::Context) {
+        let Some(wall) = self.monitor_wall.as_mut() else {
+            return;
+        };
+        let filter = self.config.downscale_filter.to_image_filter();
+        let now = Instant::now();
+        if let Some(rx) = wall.scan_rx.as_ref() {
+            match rx.try_recv() {
+                Ok(result) => {
+                    wall.scan_rx = None;
+                    let files = self
+                        .media_directory_index
+                        .apply_directory_scan_result(result);
+                    wall.set_files(files, filter);
+                }
+                Err(crossbeam_channel::TryRecvError::Empty) => {}
+                Err(crossbeam_channel::TryRecvError::Disconnected) => wall.scan_rx = None,
+            }
+        } else if wall.check_due(now) {
+            wall.last_check = Some(now);
+            let probe = wall.probe_path();
+            match self.media_directory_index.try_cached_media_for_path(&probe) {
+                Some(files) => wall.set_files(files, filter),
+                None => {
+                    wall.scan_rx = self
+                        .media_directory_index
+                        .request_media_scan_for_path(&probe);
+                }
+            }
+        }
+        let busy = wall.poll(ctx) || wall.scan_rx.is_some();
+        ctx.request_repaint_after(if busy {
+            Duration::from_millis(50)
+        } else {
+            monitor_wall::POLL_INTERVAL
+        });
 
-            let Some(pending) = self.pending_media_load.take() else {
-                continue;
-            };
+        let mut close = ctx.input(|input| input.key_pressed(egui::Key::Escape));
+        let mut open: Option<PathBuf> = None;
+        let screen_rect = ctx.screen_rect();
+        egui::Area::new(egui::Id::new("monitor_wall"))
+            .fixed_pos(screen_rect.min)
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, screen_rect.size());
+                ui.set_min_size(rect.size());
+                ui.painter().rect_filled(
+                    rect,
+                    0.0,
+                    egui::Color32::from_rgba_unmultiplied(4, 8, 13, 246),
+                );
+                let content = rect.with_min_y(rect.min.y + 52.0).shrink(12.0);
+
+                let toolbar_rect = rect.with_max_y(rect.min.y + 52.0);
+                ui.allocate_new_ui(
+                    egui::UiBuilder::new()
+                        .max_rect(toolbar_rect.shrink2(egui::vec2(0.0, 8.0)))
+                        .layout(egui::Layout::top_down(egui::Align::Center)),
+                    |ui| {
+                        egui::Frame::popup(ui.style()).show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new("Monitor wall").strong());
+                                ui.separator();
+                                ui.label(format!(
+                                    "{}: newest {} of {}",
+                                    wall.folder_name(),
+                                    wall.tiles.len(),
+                                    wall.count
+                                ));
+                                if busy {
+                                    ui.spinner();
+                                }
+                                ui.separator();
+                                if ui
+                                    .button("Refresh")
+                                    .on_hover_text("Rescan the folder now")
+                                    .clicked()
+                                {
+                                    wall.invalidate();
+                                }
+                                ui.separator();
+                                close |=
+                                    ui.small_button("✕").on_hover_text("Close (Esc)").clicked();
+                            });
+                        });
+                    },
+                );
 
-            let total_elapsed = pending.started_at.elapsed();
-            self.perf_metrics
-                .record_duration("load_media_async_ms", total_elapsed);
-            self.perf_metrics
-                .record_duration("load_media_async_worker_ms", worker_elapsed);
-            self.perf_metrics.record_duration(
-                "load_media_async_queue_ms",
-                total_elapsed.saturating_sub(worker_elapsed),
-            );
+                if wall.tiles.is_empty() {
+                    if busy || wall.files.is_none() {
+                        ui.put(content, egui::Spinner::new().size(32.0));
+                    } else {
+                        ui.put(
+                            content,
+                            egui::Label::new(
+                                egui::RichText::new("Waiting for images in this folder…")
+                                    .color(egui::Color32::from_rgb(200, 215, 232))
+                                    .size(16.0),
+                            ),
+                        );
+                    }
+                    return;
+                }
 
-            match result {
-                MediaLoadResult::Image { path, result, .. } => match result {
-                    Ok(loaded) => {
-                        self.consume_deferred_media_view_reset();
-                        self.retained_media_placeholder_visible = false;
-                        let (display_w, display_h) = loaded.image.display_dimensions();
-                        if display_w > 0 && display_h > 0 {
-                            store_cached_dimensions(
-                                &path,
-                                CachedMediaKind::Image,
-                                display_w,
-                                display_h,
+                let (columns, rows) = monitor_wall::grid_dimensions(
+                    wall.tiles.len(),
+                    content.width() / content.height().max(1.0),
+                );
+                let cell_size = egui::vec2(
+                    content.width() / columns as f32,
+                    content.height() / rows as f32,
+                );
+                let full_uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+                for (index, tile) in wall.tiles.iter().enumerate() {
+                    let cell = egui::Rect::from_min_size(
+                        content.min
+                            + egui::vec2(
+                                (index % columns) as f32 * cell_size.x,
+                                (index / columns) as f32 * cell_size.y,
+                            ),
+                        cell_size,
+                    )
+                    .shrink(4.0);
+                    let response = ui
+                        .interact(
+                            cell,
+                            egui::Id::new(("monitor_wall_tile", index)),
+                            egui::Sense::click(),
+                        )
+                        .on_hover_cursor(egui::CursorIcon::PointingHand);
+                    let painter = ui.painter_at(cell);
+                    painter.rect_filled(
+                        cell,
+                        6.0,
+                        egui::Color32::from_rgba_unmultiplied(255, 255, 255, 8),
+                    );
+                    let image_area = cell.with_max_y(cell.max.y - 20.0).shrink(4.0);
+                    match (&tile.texture, &tile.error) {
+                        (Some(texture), _) => {
+                            let size = texture.size_vec2();
+                            let scale = (image_area.width() / size.x)
+                                .min(image_area.height() / size.y)
+                                .min(1.0);
+                            painter.image(
+                                texture.id(),
+                                egui::Rect::from_center_size(image_area.center(), size * scale),
+                                full_uv,
+                                egui::Color32::WHITE,
                             );
                         }
-
-                        self.cache_loaded_image_first_frame(
-                            &path,
-                            loaded.max_texture_side,
-                            &loaded.image,
-                            loaded.is_animated_webp,
-                        );
-                        self.clear_current_image_texture_upload();
-                        self.image = Some(loaded.image);
-                        self.image_changed = true;
-                        self.pending_media_layout = false;
-                        self.error_message = None;
-                        self.clear_video_playback_unavailable_state();
-                        if !self.defer_directory_work_for_fast_startup() {
-                            self.schedule_solo_probe_window(&path, Some(MediaType::Image));
+                        (None, Some(_)) => {
+                            painter.text(
+                                image_area.center(),
+                                egui::Align2::CENTER_CENTER,
+                                "Cannot read image",
+                                egui::FontId::proportional(13.0),
+                                egui::Color32::from_rgb(255, 190, 135),
+                            );
                         }
-
-                        if loaded.is_animated_webp {
-                            if let Some(rx) = LoadedImage::start_streaming_webp(
-                                &path,
-                                Some(loaded.max_texture_side),
-                                loaded.gif_filter,
-                            ) {
-                                self.anim_stream_rx = Some(rx);
-                                self.anim_stream_path = Some(path);
-                                self.anim_stream_done = false;
-                                self.anim_seekbar_total_frames = Some(
-                                    self.image
-                                        .as_ref()
-                                        .map(|image| image.frame_count())
-                                        .unwrap_or(1),
-                                );
-                            }
+                        (None, None) => {
+                            ui.put(
+                                egui::Rect::from_center_size(
+                                    image_area.center(),
+                                    egui::vec2(24.0, 24.0),
+                                ),
+                                egui::Spinner::new().size(20.0),
+                            );
                         }
                     }
-                    Err(err) => {
-                        self.drop_retained_media_placeholder();
-                        self.error_message = Some(err);
+                    painter.text(
+                        cell.center_bottom() - egui::vec2(0.0, 4.0),
+                        egui::Align2::CENTER_BOTTOM,
+                        tile.path.file_name().unwrap_or_default().to_string_lossy(),
+                        egui::FontId::proportional(12.0),
+                        egui::Color32::from_rgb(200, 215, 232),
+                    );
+                    let stroke = if monitor_wall::MonitorWall::is_new(tile, now) {
+                        Some(egui::Stroke::new(
+                            2.0,
+                            egui::Color32::from_rgb(94, 214, 255),
+                        ))
+                    } else if response.hovered() {
+                        Some(egui::Stroke::new(
+                            1.0,
+                            egui::Color32::from_rgba_unmultiplied(166, 207, 255, 120),
+                        ))
+                    } else {
+                        None
+                    };
+                    if let Some(stroke) = stroke {
+                        ui.painter().rect_stroke(cell, 6.0, stroke);
                     }
-                },
-                MediaLoadResult::Video { path, result, .. } => {
-                    let suppress_controls_reveal =
-                        self.suppress_video_controls_for_request_id == Some(result_request_id);
-                    if suppress_controls_reveal {
-                        self.suppress_video_controls_for_request_id = None;
+                    if response.clicked() {
+                        open = Some(tile.path.clone());
                     }
+                }
+            });
 
-                    match result {
-                        Ok(mut player) => {
-                            let resume_position_secs = self
-                                .manga_video_preview_resume_by_path
-                                .get(&path)
-                                .copied()
-                                .or_else(|| {
-                                    self.image_list
-                                        .iter()
-                                        .position(|candidate| candidate == &path)
-                                        .and_then(|idx| {
-                                            self.manga_video_preview_resume_secs.get(&idx).copied()
-                                        })
-                                })
-                                .filter(|secs| secs.is_finite() && *secs >= 0.0);
-                            let resume_position = resume_position_secs.map(Duration::from_secs_f64);
-                            Self::seek_video_player_to_resume_position(
-                                &mut player,
-                                resume_position,
-                            );
+        if close || open.is_some() {
+            self.monitor_wall = None;
+        }
+        if let Some(path) = open {
+            self.load_image(&path);
+        }
+    }
 
-                            let dims = player.dimensions();
-                            if dims.0 > 0 && dims.1 > 0 {
-                                store_cached_dimensions(
-                                    &path,
-                                    CachedMediaKind::Video,
-                                    dims.0,
-                                    dims.1,
-                                );
-                            }
+    fn straighten_mode_active(&self) -> bool {
+        self.straighten.active
+            && !self.manga_mode
+            && matches!(self.current_media_type, Some(MediaType::Image))
+    }
+
+    fn toggle_straighten_mode(&mut self) {
+        if self.straighten.active {
+            self.straighten.active = false;
+            self.straighten.line_start = None;
+            self.straighten.line_end = None;
+        } else if !self.manga_mode && matches!(self.current_media_type, Some(MediaType::Image)) {
+            self.straighten.active = true;
+            self.straighten.status = None;
+            self.annotations.active = false;
+            self.annotations.cancel_shape();
+            self.perspective.active = false;
+            self.eyedropper_active = false;
+            self.is_panning = false;
+            self.last_mouse_pos = None;
+        }
+    }
 
-                            self.video_player = Some(player);
-                            self.current_video_path = Some(path.clone());
-                            self.error_message = None;
-                            self.clear_video_playback_unavailable_state();
-                            if !suppress_controls_reveal {
-                                self.show_video_controls = true;
-                                self.touch_bottom_overlays();
-                            }
+    /// While `free_rotate` is held, dragging with the primary button turns the image by the
+    /// angle the pointer sweeps around the view center.
+    fn apply_free_rotate(&mut self, ctx: &egui::Context) {
+        let allowed = !self.manga_mode
+            && self.current_media_type.is_some()
+            && !self.any_modal_dialog_open()
+            && self.file_action_menu.is_none()
+            && !self.zoom_input_focused
+            && self.window_allows_keyboard_shortcuts(ctx);
+        let (held, down, pointer_pos) = ctx.input(|input| {
+            let modifiers = input.modifiers;
+            (
+                allowed
+                    && self.action_binding_down(
+                        Action::FreeRotate,
+                        input,
+                        modifiers.ctrl,
+                        modifiers.shift,
+                        modifiers.alt,
+                    ),
+                input.pointer.button_down(egui::PointerButton::Primary),
+                input.pointer.latest_pos(),
+            )
+        });
+        self.free_rotate_held = held;
+        if !held || !down {
+            self.free_rotate_last_pos = None;
+            return;
+        }
+        let Some(pos) = pointer_pos else {
+            return;
+        };
+        let pivot = ctx.screen_rect().center() + self.offset;
+        if let Some(last) = self.free_rotate_last_pos.replace(pos) {
+            let delta = straighten::swept_degrees(pivot, last, pos);
+            if delta != 0.0 {
+                self.update_precise_rotation(delta);
+                ctx.request_repaint();
+            }
+        }
+        ctx.set_cursor_icon(egui::CursorIcon::Grabbing);
+    }
+
+    /// Screen-aligned grid to line a horizon or an upright edge up against.
+    fn draw_level_grid(painter: &egui::Painter, rect: egui::Rect) {
+        let stroke = egui::Stroke::new(1.0, egui::Color32::from_white_alpha(70));
+        for segment in straighten::level_grid_lines(rect, straighten::LEVEL_GRID_SPACING) {
+            painter.line_segment(segment, stroke);
+        }
+    }
+
+    /// Darkens everything outside the crop that the current rotation leaves, and turns a
+    /// primary-button drag into a level line. Releasing the line rotates the view.
+    fn draw_straighten_layer(
+        &mut self,
+        ctx: &egui::Context,
+        painter: &egui::Painter,
+        center: egui::Pos2,
+        display_size: egui::Vec2,
+        angle_radians: f32,
+    ) {
+        let toolbar_rect = self.draw_straighten_toolbar(ctx);
+
+        let crop = egui::Rect::from_center_size(
+            center,
+            straighten::inscribed_crop_size(display_size.x, display_size.y, angle_radians),
+        );
+        let bounds = egui::Rect::from_center_size(
+            center,
+            rotated_bounding_size(display_size, angle_radians),
+        )
+        .union(crop);
+        let shade = egui::Color32::from_black_alpha(150);
+        for rect in [
+            bounds.with_max_y(crop.min.y),
+            bounds.with_min_y(crop.max.y),
+            egui::Rect::from_x_y_ranges(bounds.min.x..=crop.min.x, crop.y_range()),
+            egui::Rect::from_x_y_ranges(crop.max.x..=bounds.max.x, crop.y_range()),
+        ] {
+            painter.rect_filled(rect, 0.0, shade);
+        }
+        painter.rect_stroke(
+            crop,
+            0.0,
+            egui::Stroke::new(1.0, egui::Color32::from_white_alpha(200)),
+        );
+        Self::draw_level_grid(painter, crop);
+
+        let (pressed, down, pointer_pos) = ctx.input(|input| {
+            (
+                input.pointer.button_pressed(egui::PointerButton::Primary),
+                input.pointer.button_down(egui::PointerButton::Primary),
+                input.pointer.interact_pos(),
+            )
+        });
+        let over_ui = pointer_pos.map_or(true, |pos| toolbar_rect.contains(pos))
+            || self.title_bar_ui_blocking()
+            || self.any_modal_dialog_open()
+            || self.file_action_menu.is_some();
+        if pressed && !over_ui {
+            self.straighten.line_start = pointer_pos;
+            self.straighten.line_end = pointer_pos;
+        }
+        if let Some(start) = self.straighten.line_start {
+            if let Some(pos) = pointer_pos {
+                self.straighten.line_end = Some(pos);
+            }
+            let end = self.straighten.line_end.unwrap_or(start);
+            if down {
+                painter.line_segment(
+                    [start, end],
+                    egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 214, 90)),
+                );
+                ctx.request_repaint();
+            } else {
+                self.straighten.line_start = None;
+                self.straighten.line_end = None;
+                if let Some(correction) = straighten::correction_degrees(start, end) {
+                    self.update_precise_rotation(correction);
+                }
+            }
+        }
+        if !over_ui {
+            ctx.set_cursor_icon(egui::CursorIcon::Crosshair);
+        }
+    }
 
-                            if self.defer_media_view_reset {
-                                self.pending_media_layout = false;
-                            } else {
-                                self.retained_media_placeholder_visible = false;
-                                self.image_changed = true;
-                                self.pending_media_layout = true;
-                            }
+    /// Hint, current angle, reset/export and close. Returns the toolbar rect so presses on it
+    /// don't start a line.
+    fn draw_straighten_toolbar(&mut self, ctx: &egui::Context) -> egui::Rect {
+        self.poll_straighten_export(ctx);
+        let angle = Self::normalize_precise_rotation_degrees(self.precise_rotation_target_degrees);
+        let can_export =
+            angle.abs() >= 0.01 && self.straighten.export_rx.is_none() && self.image.is_some();
+        let has_transparency = self.current_image_has_transparency();
+        let mut reset_clicked = false;
+        let mut export_clicked = false;
+        let mut close_clicked = false;
+        let mut slider_delta = 0.0;
 
-                            if !self.defer_directory_work_for_fast_startup() {
-                                self.schedule_solo_probe_window(&path, Some(MediaType::Video));
-                            }
+        let response = egui::Area::new(egui::Id::new("straighten_toolbar"))
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -16.0))
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Straighten").strong());
+                        ui.label(
+                            egui::RichText::new("Drag along a line that should be level").weak(),
+                        );
+                        ui.separator();
+                        let mut slider_angle = angle.clamp(-45.0, 45.0);
+                        if ui
+                            .add(
+                                egui::Slider::new(&mut slider_angle, -45.0..=45.0)
+                                    .suffix("°")
+                                    .fixed_decimals(2),
+                            )
+                            .on_hover_text("Fine rotation; hold r and drag for larger turns")
+                            .changed()
+                        {
+                            slider_delta = slider_angle - angle;
                         }
-                        Err(err) => {
-                            if self.retained_media_placeholder_visible {
-                                self.drop_retained_media_placeholder();
-                            }
-                            self.error_message = None;
-                            self.set_video_playback_unavailable_for_path(
-                                &path,
-                                format!("Failed to load video: {}", err),
+                        reset_clicked = ui
+                            .add_enabled(angle.abs() >= 0.01, egui::Button::new("Reset"))
+                            .clicked();
+                        if has_transparency {
+                            self.export_alpha_unsaved |= png_export::alpha_option_ui(
+                                ui,
+                                &mut self.config.export_alpha,
+                                &mut self.config.export_background_rgb,
                             );
-                            if !suppress_controls_reveal {
-                                self.show_video_controls = true;
-                                self.touch_bottom_overlays();
-                            }
                         }
-                    }
-                }
-            }
+                        export_clicked = ui
+                            .add_enabled(can_export, egui::Button::new("Export PNG"))
+                            .on_hover_text(
+                                "Save the rotated and cropped image next to the original",
+                            )
+                            .clicked();
+                        if let Some(status) = self.straighten.status.as_ref() {
+                            ui.label(egui::RichText::new(status).weak());
+                        }
+                        ui.separator();
+                        close_clicked = ui.small_button("✕").clicked();
+                    });
+                });
+            });
 
-            applied_any = true;
+        if slider_delta != 0.0 {
+            self.update_precise_rotation(slider_delta);
         }
+        if reset_clicked {
+            self.update_precise_rotation(-angle);
+        }
+        if export_clicked {
+            self.start_straighten_export();
+        }
+        if close_clicked {
+            self.toggle_straighten_mode();
+        }
+        response.response.rect
+    }
 
-        if applied_any {
-            ctx.request_repaint();
+    /// Rotates and crops a copy of the current frame on a worker thread and writes it as PNG.
+    fn start_straighten_export(&mut self) {
+        if self.straighten.export_rx.is_some() {
+            return;
         }
+        let Some(img) = self.image.as_ref() else {
+            return;
+        };
+        let frame = img.current_frame_data();
+        let (pixels, width, height) = (frame.pixels.clone(), frame.width, frame.height);
+        let angle = Self::normalize_precise_rotation_degrees(self.precise_rotation_target_degrees);
+        let flips = (self.flip_horizontal, self.flip_vertical);
+        let target = straighten::export_path(&img.path);
+        let background = self.export_background();
+
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        crate::async_runtime::spawn_blocking_or_thread("straighten-export", move || {
+            let result = straighten::export_straightened_png(
+                &pixels, width, height, angle, flips, background, &target,
+            )
+            .map(|()| target);
+            let _ = tx.send(result);
+        });
+        self.straighten.export_rx = Some(rx);
+        self.straighten.status = Some("Exporting…".to_string());
     }
 
-    fn load_image_retaining_visible_media(&mut self, path: &PathBuf) {
-        self.load_media_internal(path, true);
+    fn poll_straighten_export(&mut self, ctx: &egui::Context) {
+        let Some(rx) = self.straighten.export_rx.as_ref() else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(result) => {
+                self.straighten.status = Some(match result {
+                    Ok(target) => format!(
+                        "Exported {}",
+                        target.file_name().unwrap_or_default().to_string_lossy()
+                    ),
+                    Err(err) => err,
+                });
+                self.straighten.export_rx = None;
+            }
+            Err(crossbeam_channel::TryRecvError::Empty) => {
+                ctx.request_repaint_after(Duration::from_millis(50));
+            }
+            Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                self.straighten.status = Some("Export failed".to_string());
+                self.straighten.export_rx = None;
+            }
+        }
     }
 
-    /// Load an image from path
-    fn load_image(&mut self, path: &PathBuf) {
-        self.load_media_internal(path, false);
+    fn can_open_index_in_new_window(&self, index: usize) -> bool {
+        self.image_list
+            .get(index)
+            .is_some_and(|path| matches!(get_media_type(path), Some(MediaType::Image)))
     }
 
-    /// Load any media (image or video) from path
-    fn load_media(&mut self, path: &PathBuf) {
-        self.load_media_internal(path, false);
+    /// Opens the image at `index` in an additional window that can browse the same folder.
+    fn open_index_in_new_window(&mut self, index: usize) {
+        if !self.can_open_index_in_new_window(index) {
+            return;
+        }
+        let target = self.image_list[index].clone();
+        let paths: Vec<PathBuf> = self
+            .image_list
+            .iter()
+            .filter(|path| matches!(get_media_type(path), Some(MediaType::Image)))
+            .cloned()
+            .collect();
+        let start = paths.iter().position(|path| *path == target).unwrap_or(0);
+        let settings = viewer_window::ViewerWindowSettings {
+            max_texture_side: self.max_texture_side.max(1),
+            downscale_filter: self.config.downscale_filter.to_image_filter(),
+            gif_filter: self.config.gif_resize_filter.to_image_filter(),
+            background: self.background_color32(),
+        };
+        self.viewer_window_serial += 1;
+        self.viewer_windows.push(viewer_window::ViewerWindow::new(
+            self.viewer_window_serial,
+            paths,
+            start,
+            settings,
+            load_viewer_window_frame,
+        ));
     }
 
-    fn load_media_internal(&mut self, path: &PathBuf, retain_visible_media_until_ready: bool) {
-        let load_media_start = Instant::now();
-        if !retain_visible_media_until_ready {
-            self.set_solo_preload_momentum(SoloPreloadMomentum::Neutral);
+    fn show_viewer_windows(&mut self, ctx: &egui::Context) {
+        if self.viewer_windows.is_empty() {
+            return;
         }
+        let icon = self
+            .viewer_window_icon
+            .get_or_insert_with(|| Arc::new(build_app_icon()))
+            .clone();
+        let synced = self.zoom_sync;
+        self.viewer_windows.retain_mut(|window| {
+            window.set_synced(synced);
+            window.show(ctx, &icon)
+        });
+        let toggle_requested = self
+            .viewer_windows
+            .iter_mut()
+            .fold(false, |requested, window| {
+                window.take_sync_toggle_request() | requested
+            });
+        if toggle_requested {
+            self.run_action(Action::ToggleZoomSync);
+        }
+        self.sync_zoom_across_windows();
+    }
 
-        if !self.manga_mode
-            && self.manga_layout_mode == MangaLayoutMode::Masonry
-            && self.has_resident_masonry_runtime_cache()
+    fn toggle_zoom_sync(&mut self) {
+        self.zoom_sync = !self.zoom_sync;
+        // Starts from the main view when it takes part, else from the next window moved.
+        self.zoom_sync_last_view = None;
+    }
+
+    /// The main window's view for zoom sync. Only solo images in fullscreen take part: the
+    /// floating window resizes itself around the image instead of zooming within it.
+    fn main_synced_view(&self) -> Option<viewer_window::SyncedView> {
+        if !self.is_fullscreen
+            || self.manga_mode
+            || !matches!(self.current_media_type, Some(MediaType::Image))
         {
-            self.pause_masonry_metadata_preload();
-        } else {
-            self.reset_masonry_metadata_preload();
+            return None;
         }
-        self.clear_pending_media_load();
-        self.pending_video_thumbnail_placeholder = None;
-        self.clear_video_playback_unavailable_state();
-
-        self.current_file_size_label = None;
-        self.current_file_size_label_path = None;
-        self.pending_file_size_probe = None;
-        self.pending_file_size_probe_path = None;
+        let (width, height) = self.media_display_dimensions()?;
+        let displayed = egui::vec2(width as f32, height as f32) * self.zoom;
+        viewer_window::SyncedView::from_offset(displayed, self.offset)
+    }
 
-        // Update the native window title (taskbar title) using Unicode-safe conversion.
-        self.pending_window_title = Some(self.compute_window_title_for_path(path));
+    fn apply_main_synced_view(&mut self, view: viewer_window::SyncedView) {
+        if self.main_synced_view().is_none() {
+            return;
+        }
+        let Some((width, height)) = self.media_display_dimensions() else {
+            return;
+        };
+        let zoom = self.clamp_zoom(view.width / width as f32);
+        self.zoom = zoom;
+        self.zoom_target = zoom;
+        self.zoom_velocity = 0.0;
+        self.offset = view.offset_for(egui::vec2(width as f32, height as f32) * zoom);
+        self.remember_current_fullscreen_view_state();
+        self.maybe_refresh_current_solo_image_lod();
+    }
 
-        // Determine media type up-front so we can decide whether to keep a placeholder frame.
-        let is_folder_entry = self.is_folder_navigation_entry_path(path.as_path());
-        let media_type = if is_folder_entry {
-            Some(MediaType::Image)
-        } else {
-            get_media_type(path)
+    /// Copies the view the user just changed to every other synced window.
+    fn sync_zoom_across_windows(&mut self) {
+        if !self.zoom_sync {
+            return;
+        }
+        let mut changed = None;
+        for (index, window) in self.viewer_windows.iter_mut().enumerate() {
+            if let Some(view) = window.take_view_change() {
+                changed = Some((Some(index), view));
+            }
+        }
+        if changed.is_none() {
+            changed = self
+                .main_synced_view()
+                .filter(|view| {
+                    !self
+                        .zoom_sync_last_view
+                        .is_some_and(|last| last.approx_eq(view))
+                })
+                .map(|view| (None, view));
+        }
+        let Some((source, view)) = changed else {
+            return;
         };
-        self.current_media_type = media_type;
-        self.current_video_path =
-            matches!(media_type, Some(MediaType::Video)).then(|| path.clone());
+        for (index, window) in self.viewer_windows.iter_mut().enumerate() {
+            if Some(index) != source {
+                window.apply_synced_view(view);
+            }
+        }
+        if source.is_some() {
+            self.apply_main_synced_view(view);
+        }
+        self.zoom_sync_last_view = Some(self.main_synced_view().unwrap_or(view));
+    }
+
+    /// Rule-of-thirds, grid and crosshair guides over the painted image, plus the pixel grid
+    /// from 800% zoom.
+    fn draw_guides(&self, painter: &egui::Painter, view: AnnotationView) {
+        let size = view.image_size;
+        if self.config.pixel_grid && view.zoom >= guides::PIXEL_GRID_MIN_ZOOM {
+            let clip = painter.clip_rect();
+            let corners = [
+                clip.left_top(),
+                clip.right_top(),
+                clip.left_bottom(),
+                clip.right_bottom(),
+            ]
+            .map(|corner| view.to_image_unclamped(corner));
+            let stroke = egui::Stroke::new(
+                1.0,
+                egui::Color32::from_rgba_unmultiplied(128, 128, 128, 110),
+            );
+            for [a, b] in guides::pixel_grid_lines(size, egui::Rect::from_points(&corners)) {
+                painter.line_segment([view.to_screen(a), view.to_screen(b)], stroke);
+            }
+        }
 
-        let mut used_mode_switch_placeholder = false;
-        let transition_placeholder = self
-            .pending_mode_switch_placeholder
-            .take()
-            .filter(|placeholder| {
-                let matches_target = Some(placeholder.media_type) == media_type;
-                if matches_target {
-                    used_mode_switch_placeholder = true;
-                }
-                matches_target
-            })
-            .or_else(|| {
-                if retain_visible_media_until_ready
-                    && Self::retain_visible_media_placeholder_for_swap(
-                        self.is_fullscreen,
-                        media_type,
-                    )
-                {
-                    self.capture_current_media_placeholder(media_type)
-                } else {
-                    None
+        let mut lines = Vec::new();
+        if self.config.overlay_thirds {
+            lines.extend(guides::thirds_lines(size));
+        }
+        if self.config.overlay_grid {
+            lines.extend(guides::grid_lines(size, self.config.grid_divisions));
+        }
+        if self.config.overlay_crosshair {
+            lines.extend(guides::crosshair_lines(size, 24.0 / view.zoom.max(0.0001)));
+        }
+        // A dark halo under a light line keeps guides visible on any image.
+        let halo = egui::Stroke::new(3.0, egui::Color32::from_black_alpha(90));
+        let stroke = egui::Stroke::new(1.0, egui::Color32::from_white_alpha(190));
+        for [a, b] in lines {
+            let segment = [view.to_screen(a), view.to_screen(b)];
+            painter.line_segment(segment, halo);
+            painter.line_segment(segment, stroke);
+        }
+    }
+
+    /// Letterbox mask and safe-area frames over the video, on screen only. With a mask, the
+    /// safe areas are measured inside the kept picture.
+    fn draw_video_frame_guides(&self, painter: &egui::Painter, view: AnnotationView) {
+        let frame = egui::Rect::from_min_size(egui::Pos2::ZERO, view.image_size);
+        let quad = |rect: egui::Rect| {
+            [
+                rect.left_top(),
+                rect.right_top(),
+                rect.right_bottom(),
+                rect.left_bottom(),
+            ]
+            .map(|corner| view.to_screen(corner))
+            .to_vec()
+        };
+        let area = match self.config.video_aspect_mask {
+            Some(ratio) => {
+                let area = video_guides::masked_area(frame, ratio);
+                for bar in video_guides::mask_bars(frame, area) {
+                    painter.add(egui::Shape::convex_polygon(
+                        quad(bar),
+                        egui::Color32::from_black_alpha(215),
+                        egui::Stroke::NONE,
+                    ));
                 }
+                painter.add(egui::Shape::closed_line(
+                    quad(area),
+                    egui::Stroke::new(1.0, egui::Color32::from_white_alpha(110)),
+                ));
+                area
+            }
+            None => frame,
+        };
+        if self.config.video_safe_areas {
+            for (share, color) in [
+                (
+                    video_guides::ACTION_SAFE,
+                    egui::Color32::from_white_alpha(170),
+                ),
+                (
+                    video_guides::TITLE_SAFE,
+                    egui::Color32::from_rgba_unmultiplied(255, 214, 90, 170),
+                ),
+            ] {
+                painter.add(egui::Shape::closed_line(
+                    quad(video_guides::safe_area(area, share)),
+                    egui::Stroke::new(1.0, color),
+                ));
+            }
+        }
+    }
+
+    fn eyedropper_mode_active(&self) -> bool {
+        self.eyedropper_active
+            && !self.manga_mode
+            && matches!(self.current_media_type, Some(MediaType::Image))
+    }
+
+    fn toggle_eyedropper_mode(&mut self) {
+        if self.eyedropper_active {
+            self.eyedropper_active = false;
+        } else if !self.manga_mode && matches!(self.current_media_type, Some(MediaType::Image)) {
+            self.eyedropper_active = true;
+            self.annotations.active = false;
+            self.annotations.cancel_shape();
+            self.straighten.active = false;
+            self.perspective.active = false;
+            self.is_panning = false;
+            self.last_mouse_pos = None;
+        }
+    }
+
+    /// Magnified loupe and color readout next to the pointer. Values come from the decoded
+    /// frame rather than the texture; a primary click copies the hex value.
+    fn draw_eyedropper_layer(&mut self, ctx: &egui::Context, view: AnnotationView) {
+        let (pointer_pos, clicked) = ctx.input(|input| {
+            (
+                input.pointer.hover_pos(),
+                input.pointer.button_clicked(egui::PointerButton::Primary),
+            )
+        });
+        let Some(pos) = pointer_pos else {
+            return;
+        };
+        if self.title_bar_ui_blocking()
+            || self.any_modal_dialog_open()
+            || self.file_action_menu.is_some()
+            || self
+                .adjustments_panel_rect
+                .is_some_and(|rect| rect.contains(pos))
+        {
+            return;
+        }
+        let Some(img) = self.image.as_ref() else {
+            return;
+        };
+        let frame = img.current_frame_data();
+        let image_pos = view.to_image_unclamped(pos);
+        let Some(picked) = eyedropper::pick(frame, view.image_size, image_pos) else {
+            return;
+        };
+        let cells = eyedropper::loupe(frame, view.image_size, image_pos);
+        ctx.set_cursor_icon(egui::CursorIcon::Crosshair);
+
+        const CELL: f32 = 11.0;
+        const GAP: f32 = 24.0;
+        let side = (eyedropper::LOUPE_RADIUS * 2 + 1) as usize;
+        let loupe_size = side as f32 * CELL;
+        let label_height = 40.0;
+        let screen = ctx.screen_rect();
+        let mut origin = pos + egui::vec2(GAP, GAP);
+        if origin.x + loupe_size > screen.max.x {
+            origin.x = pos.x - GAP - loupe_size;
+        }
+        if origin.y + loupe_size + label_height > screen.max.y {
+            origin.y = pos.y - GAP - loupe_size - label_height;
+        }
+
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Tooltip,
+            egui::Id::new("eyedropper_loupe"),
+        ));
+        let loupe_rect = egui::Rect::from_min_size(origin, egui::vec2(loupe_size, loupe_size));
+        let panel = egui::Rect::from_min_max(
+            loupe_rect.min,
+            loupe_rect.max + egui::vec2(0.0, label_height),
+        )
+        .expand(4.0);
+        painter.rect_filled(
+            panel,
+            8.0,
+            egui::Color32::from_rgba_unmultiplied(12, 16, 22, 236),
+        );
+        for (index, cell) in cells.iter().enumerate() {
+            let rect = egui::Rect::from_min_size(
+                origin + egui::vec2((index % side) as f32, (index / side) as f32) * CELL,
+                egui::vec2(CELL, CELL),
+            );
+            let color = cell.map_or(egui::Color32::from_gray(24), |[r, g, b, a]| {
+                egui::Color32::from_rgba_unmultiplied(r, g, b, a)
             });
-        let keep_current_view_until_swap =
-            retain_visible_media_until_ready && transition_placeholder.is_some();
+            painter.rect_filled(rect, 0.0, color);
+        }
+        let center = egui::Rect::from_min_size(
+            origin
+                + egui::vec2(
+                    eyedropper::LOUPE_RADIUS as f32,
+                    eyedropper::LOUPE_RADIUS as f32,
+                ) * CELL,
+            egui::vec2(CELL, CELL),
+        );
+        painter.rect_stroke(center, 0.0, egui::Stroke::new(2.0, egui::Color32::BLACK));
+        painter.rect_stroke(center, 0.0, egui::Stroke::new(1.0, egui::Color32::WHITE));
+
+        let swatch = egui::Rect::from_min_size(
+            egui::pos2(loupe_rect.min.x, loupe_rect.max.y + 6.0),
+            egui::vec2(28.0, 28.0),
+        );
+        painter.rect_filled(swatch, 4.0, picked.color32());
+        painter.rect_stroke(
+            swatch,
+            4.0,
+            egui::Stroke::new(1.0, egui::Color32::from_gray(140)),
+        );
+        let text_pos = egui::pos2(swatch.max.x + 6.0, swatch.min.y);
+        painter.text(
+            text_pos,
+            egui::Align2::LEFT_TOP,
+            picked.hex(),
+            egui::FontId::monospace(13.0),
+            egui::Color32::WHITE,
+        );
+        painter.text(
+            text_pos + egui::vec2(0.0, 15.0),
+            egui::Align2::LEFT_TOP,
+            format!("{}  ({}, {})", picked.rgb_text(), picked.x, picked.y),
+            egui::FontId::proportional(11.0),
+            egui::Color32::from_rgb(170, 186, 204),
+        );
+
+        if clicked {
+            let hex = picked.hex();
+            ctx.copy_text(hex.clone());
+            self.show_action_osd(
+                "clipboard",
+                i18n::tr_args("osd.color_copied", &[("color", &hex)]),
+            );
+        }
+    }
+
+    /// Whether the `magnifier` key is held over a still image in the solo view.
+    fn magnifier_held(&self, ctx: &egui::Context) -> bool {
+        if self.manga_mode
+            || !matches!(self.current_media_type, Some(MediaType::Image))
+            || self.any_modal_dialog_open()
+            || self.file_action_menu.is_some()
+            || self.zoom_input_focused
+            || !self.window_allows_keyboard_shortcuts(ctx)
+        {
+            return false;
+        }
+        ctx.input(|input| {
+            let modifiers = input.modifiers;
+            self.action_binding_down(
+                Action::Magnifier,
+                input,
+                modifiers.ctrl,
+                modifiers.shift,
+                modifiers.alt,
+            )
+        })
+    }
+
+    /// Whether the `show_original` key is held while adjustments or annotations change how
+    /// the current image is drawn. The texture stays the same; only the overlays are skipped.
+    fn show_original_held(&self, ctx: &egui::Context) -> bool {
+        if self.manga_mode
+            || !matches!(self.current_media_type, Some(MediaType::Image))
+            || (self.image_adjustments.is_identity()
+                && self.lut.is_none()
+                && !self.annotation_mode_active())
+            || self.any_modal_dialog_open()
+            || self.file_action_menu.is_some()
+            || self.zoom_input_focused
+            || !self.window_allows_keyboard_shortcuts(ctx)
+        {
+            return false;
+        }
+        ctx.input(|input| {
+            let modifiers = input.modifiers;
+            self.action_binding_down(
+                Action::ShowOriginal,
+                input,
+                modifiers.ctrl,
+                modifiers.shift,
+                modifiers.alt,
+            )
+        })
+    }
+
+    /// Label at the top of the view while the original image is shown.
+    fn draw_show_original_badge(&self, painter: &egui::Painter) {
+        let text = i18n::tr("view.original");
+        let font = egui::FontId::proportional(13.0);
+        let galley = painter.layout_no_wrap(text.to_string(), font, egui::Color32::WHITE);
+        let clip = painter.clip_rect();
+        let rect = egui::Rect::from_center_size(
+            egui::pos2(clip.center().x, clip.top() + 28.0),
+            galley.size() + egui::vec2(20.0, 8.0),
+        );
+        painter.rect_filled(rect, 10.0, egui::Color32::from_black_alpha(170));
+        painter.galley(
+            rect.center() - galley.size() * 0.5,
+            galley,
+            egui::Color32::WHITE,
+        );
+    }
+
+    /// Round loupe centered on the pointer, `magnifier_zoom` times the current zoom. It is
+    /// sampled from the decoded frame, so it stays sharp when the view is downscaled.
+    fn draw_magnifier(&mut self, ctx: &egui::Context, view: AnnotationView) {
+        let Some(pointer) = ctx.input(|input| input.pointer.hover_pos()) else {
+            return;
+        };
+        let Some(img) = self.image.as_ref() else {
+            return;
+        };
+        let loupe = magnifier::render(
+            img.current_frame_data(),
+            view,
+            pointer,
+            self.config.magnifier_zoom,
+            ctx.pixels_per_point(),
+        );
+        let background = self.background_color32();
+        let texture = match self.magnifier_texture.as_mut() {
+            Some(texture) => {
+                texture.set(loupe, egui::TextureOptions::NEAREST);
+                texture
+            }
+            None => self.magnifier_texture.insert(ctx.load_texture(
+                "magnifier",
+                loupe,
+                egui::TextureOptions::NEAREST,
+            )),
+        };
 
-        // Clear previous media state.
-        // When a placeholder was captured above we immediately restore it after clearing
-        // the current decode state so the visible frame stays on screen during navigation.
-        // MEMORY OPTIMIZATION: Explicitly drop textures to release GPU memory immediately.
-        // Setting to None allows Rust to drop the TextureHandle, which signals egui to
-        // free the underlying GPU texture on the next frame.
-        self.stop_fullscreen_video_playback();
-        if let Some(texture) = self.video_texture.take() {
-            drop(texture);
-        }
-        self.video_texture_source_path = None;
-        self.video_texture_dims = None;
-        if let Some(texture) = self.texture.take() {
-            drop(texture);
+        let radius = magnifier::LOUPE_DIAMETER * 0.5;
+        let rect = egui::Rect::from_center_size(pointer, egui::Vec2::splat(radius * 2.0));
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Tooltip,
+            egui::Id::new("magnifier"),
+        ));
+        painter.circle_filled(pointer, radius, background);
+        painter.image(
+            texture.id(),
+            rect,
+            egui::Rect::from_min_max(egui::Pos2::ZERO, egui::pos2(1.0, 1.0)),
+            egui::Color32::WHITE,
+        );
+        painter.circle_stroke(
+            pointer,
+            radius,
+            egui::Stroke::new(2.0, egui::Color32::from_gray(220)),
+        );
+        painter.circle_stroke(
+            pointer,
+            radius + 1.5,
+            egui::Stroke::new(1.0, egui::Color32::from_black_alpha(160)),
+        );
+        ctx.set_cursor_icon(egui::CursorIcon::Crosshair);
+    }
+
+    fn perspective_mode_active(&self) -> bool {
+        self.perspective.active
+            && !self.manga_mode
+            && matches!(self.current_media_type, Some(MediaType::Image))
+    }
+
+    fn toggle_perspective_mode(&mut self) {
+        if self.perspective.active {
+            self.perspective.active = false;
+            self.perspective.dragging = None;
+        } else if !self.manga_mode && matches!(self.current_media_type, Some(MediaType::Image)) {
+            self.perspective.active = true;
+            self.perspective.status = None;
+            self.annotations.active = false;
+            self.annotations.cancel_shape();
+            self.straighten.active = false;
+            self.eyedropper_active = false;
+            self.is_panning = false;
+            self.last_mouse_pos = None;
         }
-        self.image_texture_dims = None;
-        self.image = None;
-        self.retained_media_placeholder_visible = transition_placeholder.is_some();
+    }
 
-        if let Some(placeholder) = transition_placeholder {
-            match placeholder.media_type {
-                MediaType::Image => {
-                    self.texture = Some(placeholder.texture);
-                    self.image_texture_dims = Some(placeholder.dims);
-                }
-                MediaType::Video => {
-                    self.video_texture = Some(placeholder.texture);
-                    self.video_texture_source_path = self
-                        .current_video_path
-                        .clone()
-                        .or_else(|| self.current_media_path());
-                    self.video_texture_dims = Some(placeholder.dims);
-                }
+    /// Corner handles over the image, or the rectified preview when that is switched on.
+    /// Handles live in normalized image coordinates so they follow zoom, pan and rotation.
+    fn draw_perspective_layer(
+        &mut self,
+        ctx: &egui::Context,
+        painter: &egui::Painter,
+        view: AnnotationView,
+        texture_id: egui::TextureId,
+    ) {
+        let path = self.image.as_ref().map(|img| img.path.clone());
+        if self.perspective.path != path {
+            self.perspective.path = path;
+            self.perspective.quad = perspective::FULL_IMAGE;
+            self.perspective.dragging = None;
+        }
+        let toolbar_rect = self.draw_perspective_toolbar(ctx);
+
+        if self.perspective.preview {
+            let area = painter.clip_rect();
+            painter.rect_filled(area, 0.0, self.background_color32());
+            let out_size = perspective::output_size(self.perspective.quad, view.image_size);
+            let scale = ((area.width() - 48.0) / out_size.x)
+                .min((area.height() - 48.0) / out_size.y)
+                .max(0.01);
+            let rect = egui::Rect::from_center_size(area.center(), out_size * scale);
+            if let Some(mesh) = perspective::preview_mesh(texture_id, self.perspective.quad, rect) {
+                painter.add(mesh);
             }
+            return;
         }
 
-        // Cancel any in-flight background animation stream.
-        self.reset_fullscreen_anim_stream_state();
-
-        // Reset GIF playback state for new media
-        self.gif_paused = false;
-        self.gif_seeking = false;
-        self.gif_seek_preview_frame = None;
-
-        if keep_current_view_until_swap {
-            self.freeze_current_media_view();
-            self.defer_media_view_reset = true;
-        } else {
-            self.reset_media_view_for_swap();
-            self.defer_media_view_reset = false;
+        let to_screen = |p: egui::Pos2| {
+            view.to_screen(egui::pos2(p.x * view.image_size.x, p.y * view.image_size.y))
+        };
+        let corners = self.perspective.quad.map(to_screen);
+        let accent = egui::Color32::from_rgb(90, 200, 255);
+        painter.add(egui::Shape::closed_line(
+            corners.to_vec(),
+            egui::Stroke::new(2.0, accent),
+        ));
 
-            if used_mode_switch_placeholder {
-                self.image_changed = true;
+        let (pressed, down, pointer_pos) = ctx.input(|input| {
+            (
+                input.pointer.button_pressed(egui::PointerButton::Primary),
+                input.pointer.button_down(egui::PointerButton::Primary),
+                input.pointer.interact_pos(),
+            )
+        });
+        let over_ui = pointer_pos.map_or(true, |pos| toolbar_rect.contains(pos))
+            || self.title_bar_ui_blocking()
+            || self.any_modal_dialog_open()
+            || self.file_action_menu.is_some();
+        let hovered_handle = pointer_pos.filter(|_| !over_ui).and_then(|pos| {
+            corners
+                .iter()
+                .position(|corner| corner.distance(pos) <= 14.0)
+        });
+        if pressed {
+            self.perspective.dragging = hovered_handle;
+        }
+        if let Some(index) = self.perspective.dragging {
+            if !down {
+                self.perspective.dragging = None;
+            } else if let Some(pos) = pointer_pos {
+                let p = view.to_image(pos);
+                self.perspective.quad[index] =
+                    egui::pos2(p.x / view.image_size.x, p.y / view.image_size.y);
+                ctx.request_repaint();
             }
+            ctx.set_cursor_icon(egui::CursorIcon::Grabbing);
+        } else if hovered_handle.is_some() {
+            ctx.set_cursor_icon(egui::CursorIcon::Grab);
         }
-        self.error_message = None;
 
-        let defer_directory_work_for_fast_startup = self.defer_directory_work_for_fast_startup();
-        if !defer_directory_work_for_fast_startup {
-            self.start_async_file_size_probe(path.clone());
+        for (index, corner) in corners.into_iter().enumerate() {
+            let active = self.perspective.dragging == Some(index) || hovered_handle == Some(index);
+            painter.circle(
+                corner,
+                if active { 8.0 } else { 6.5 },
+                egui::Color32::from_rgb(16, 23, 31),
+                egui::Stroke::new(2.0, accent),
+            );
         }
+    }
 
-        // Reuse cached directory listing when the parent folder is unchanged.
-        let index_stats_before = self.media_directory_index.stats();
-        let index_lookup_start = Instant::now();
+    /// Hint, preview toggle, reset/export and close. Returns the toolbar rect so presses on it
+    /// don't grab a handle.
+    fn draw_perspective_toolbar(&mut self, ctx: &egui::Context) -> egui::Rect {
+        self.poll_perspective_export(ctx);
+        let changed = self.perspective.quad != perspective::FULL_IMAGE;
+        let can_export = changed && self.perspective.export_rx.is_none() && self.image.is_some();
+        let mut reset_clicked = false;
+        let mut export_clicked = false;
+        let mut close_clicked = false;
 
-        self.pending_media_directory_scan = None;
-        self.pending_media_directory_target = None;
-        self.pending_media_directory_scan_kind = None;
-        self.pending_media_directory_started_at = None;
+        let response = egui::Area::new(egui::Id::new("perspective_toolbar"))
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -16.0))
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Perspective").strong());
+                        ui.label(
+                            egui::RichText::new("Drag the corners onto the edges of the page")
+                                .weak(),
+                        );
+                        ui.separator();
+                        ui.checkbox(&mut self.perspective.preview, "Preview");
+                        reset_clicked = ui
+                            .add_enabled(changed, egui::Button::new("Reset"))
+                            .clicked();
+                        self.export_alpha_unsaved |= png_export::alpha_option_ui(
+                            ui,
+                            &mut self.config.export_alpha,
+                            &mut self.config.export_background_rgb,
+                        );
+                        export_clicked = ui
+                            .add_enabled(can_export, egui::Button::new("Export PNG"))
+                            .on_hover_text("Save the rectified image next to the original")
+                            .clicked();
+                        if let Some(status) = self.perspective.status.as_ref() {
+                            ui.label(egui::RichText::new(status).weak());
+                        }
+                        ui.separator();
+                        close_clicked = ui.small_button("✕").clicked();
+                    });
+                });
+            });
 
-        if defer_directory_work_for_fast_startup {
-            self.set_image_list(vec![path.clone()]);
-        } else {
-            if let Some(files) = self.media_directory_index.try_cached_media_for_path(path) {
-                self.set_image_list(files);
-            } else {
-                // Keep current media navigable immediately while the full directory scan runs in background.
-                self.set_image_list(vec![path.clone()]);
-                let _ = self
-                    .begin_media_directory_scan(path, PendingMediaDirectoryScanKind::InitialLoad);
-            }
+        if reset_clicked {
+            self.perspective.quad = perspective::FULL_IMAGE;
         }
-
-        if self.image_list.is_empty() {
-            self.set_image_list(vec![path.clone()]);
+        if export_clicked {
+            self.start_perspective_export();
+        }
+        if close_clicked {
+            self.toggle_perspective_mode();
         }
+        response.response.rect
+    }
 
-        self.perf_metrics
-            .record_duration("media_index_lookup_ms", index_lookup_start.elapsed());
-        let index_stats_after = self.media_directory_index.stats();
-        if index_stats_after.hits > index_stats_before.hits {
-            self.perf_metrics.increment_counter(
-                "media_index_hits",
-                index_stats_after.hits - index_stats_before.hits,
-            );
+    /// Warps a copy of the current frame on a worker thread and writes it as PNG.
+    fn start_perspective_export(&mut self) {
+        if self.perspective.export_rx.is_some() {
+            return;
         }
-        if index_stats_after.misses > index_stats_before.misses {
-            self.perf_metrics.increment_counter(
-                "media_index_misses",
-                index_stats_after.misses - index_stats_before.misses,
-            );
+        let Some(img) = self.image.as_ref() else {
+            return;
+        };
+        let frame = img.current_frame_data();
+        let (pixels, width, height) = (frame.pixels.clone(), frame.width, frame.height);
+        let quad = self.perspective.quad;
+        let out_size = perspective::output_size(quad, egui::vec2(width as f32, height as f32));
+        let target = perspective::export_path(&img.path);
+        let background = self.export_background();
+
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        crate::async_runtime::spawn_blocking_or_thread("perspective-export", move || {
+            let result = perspective::export_corrected_png(
+                &pixels, width, height, quad, out_size, background, &target,
+            )
+            .map(|()| target);
+            let _ = tx.send(result);
+        });
+        self.perspective.export_rx = Some(rx);
+        self.perspective.status = Some("Exporting…".to_string());
+    }
+
+    fn poll_perspective_export(&mut self, ctx: &egui::Context) {
+        let Some(rx) = self.perspective.export_rx.as_ref() else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(result) => {
+                self.perspective.status = Some(match result {
+                    Ok(target) => format!(
+                        "Exported {}",
+                        target.file_name().unwrap_or_default().to_string_lossy()
+                    ),
+                    Err(err) => err,
+                });
+                self.perspective.export_rx = None;
+            }
+            Err(crossbeam_channel::TryRecvError::Empty) => {
+                ctx.request_repaint_after(Duration::from_millis(50));
+            }
+            Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                self.perspective.status = Some("Export failed".to_string());
+                self.perspective.export_rx = None;
+            }
         }
-        self.set_current_index_clamped(
-            self.image_list
-                .iter()
-                .position(|candidate| candidate == path)
-                .unwrap_or(0),
-        );
+    }
 
-        match media_type {
-            Some(MediaType::Video) => {
-                if !gstreamer_runtime_available() {
-                    self.gstreamer_initialized = false;
-                    self.drop_retained_media_placeholder();
-                    self.set_video_playback_unavailable_for_path(
-                        path,
-                        Self::gstreamer_missing_video_error_text().to_string(),
-                    );
-                    self.show_video_controls = false;
-                    self.image_changed = true;
-                    self.pending_media_layout = false;
-                    self.perf_metrics
-                        .record_duration("load_media_prepare_ms", load_media_start.elapsed());
-                    return;
-                }
+    /// Load next image
+    fn next_image(&mut self) {
+        if self.image_list.is_empty() {
+            return;
+        }
 
-                // Mark GStreamer as initialized (it will be lazily initialized on first use)
-                self.gstreamer_initialized = true;
+        // In manga mode, scroll to next image instead of loading
+        if self.manga_mode && self.is_fullscreen {
+            let next_index = if self.current_index + 1 >= self.image_list.len() {
+                0
+            } else {
+                self.current_index + 1
+            };
+            self.set_current_index_clamped(next_index);
+            let scroll_to = self.manga_get_scroll_offset_for_index(next_index);
+            self.manga_scroll_target = scroll_to;
+            self.manga_update_preload_queue();
+            return;
+        }
 
-                self.start_async_video_load(path.clone());
-            }
-            Some(MediaType::Image) => {
-                if is_folder_entry {
-                    self.consume_deferred_media_view_reset();
-                    self.drop_retained_media_placeholder();
-                    self.image = Some(Self::build_folder_placeholder_image(
-                        path.clone(),
-                        Self::is_up_navigation_entry_path(path.as_path()),
-                    ));
-                    self.texture = None;
-                    self.image_texture_dims = Some((512, 512));
-                    self.show_video_controls = false;
-                    self.error_message = None;
-                    self.image_changed = true;
-                    self.pending_media_layout = false;
-                    self.perf_metrics
-                        .record_duration("load_media_prepare_ms", load_media_start.elapsed());
-                    return;
-                }
+        let Some(next_index) = self.rating_filtered_neighbor(true) else {
+            return;
+        };
 
-                // Load as image with configured filters.
-                // For animated WebP we only decode the FIRST frame here so the
-                // window appears instantly, then start streaming remaining frames
-                // in the background so the animation begins playing progressively.
-                let downscale_filter = self.config.downscale_filter.to_image_filter();
-                let gif_filter = self.config.gif_resize_filter.to_image_filter();
-                let target_lod_side =
-                    self.solo_target_texture_side_for_path(path, MediaType::Image, true);
-                let max_tex =
-                    Self::solo_image_load_texture_side(target_lod_side, self.max_texture_side);
+        // Save current view state before navigating (fullscreen only)
+        self.save_current_fullscreen_view_state();
+        self.set_solo_preload_momentum(SoloPreloadMomentum::Forward);
 
-                if self.try_load_image_from_decoded_cache(path, max_tex, gif_filter) {
-                    if !defer_directory_work_for_fast_startup {
-                        self.schedule_solo_probe_window(path, media_type);
-                    }
-                    self.perf_metrics
-                        .record_duration("load_media_prepare_ms", load_media_start.elapsed());
-                    return;
-                }
+        self.set_current_index_clamped(next_index);
+        let path = self.image_list[self.current_index].clone();
+        self.begin_image_transition(true, &path);
+        self.load_image_retaining_visible_media(&path);
+    }
 
-                if self.try_load_image_from_thumbnail_cache(path, max_tex) {
-                    if !defer_directory_work_for_fast_startup {
-                        self.schedule_solo_probe_window(path, media_type);
-                    }
-                    self.perf_metrics
-                        .record_duration("load_media_prepare_ms", load_media_start.elapsed());
-                    return;
-                }
+    /// Keeps the image on screen for a `transition_effect` into `target`. Only image-to-image
+    /// navigation animates.
+    fn begin_image_transition(&mut self, forward: bool, target: &Path) {
+        self.image_transition = None;
+        if self.config.transition_effect == TransitionEffect::Off
+            || self.manga_mode
+            || self.video_texture.is_some()
+            || get_media_type(target) != Some(MediaType::Image)
+            || self.is_folder_navigation_entry_path(target)
+        {
+            return;
+        }
+        let (Some(texture), Some(rect)) = (self.texture.clone(), self.last_image_rect) else {
+            return;
+        };
+        self.image_transition = Some(ImageTransition {
+            texture,
+            rect,
+            forward,
+            target: target.to_path_buf(),
+            requested_at: Instant::now(),
+            started_at: None,
+        });
+    }
 
-                if !self.is_fullscreen {
-                    self.pending_media_layout = true;
-                }
-                self.start_async_image_load(path.clone(), max_tex, downscale_filter, gif_filter);
+    /// The outgoing image and how to draw it this frame, while a transition runs. Starts the
+    /// transition once the incoming image replaces the outgoing texture.
+    fn image_transition_frame(
+        &mut self,
+        ctx: &egui::Context,
+        view_width: f32,
+    ) -> Option<(egui::TextureHandle, TransitionFrame)> {
+        let transition = self.image_transition.as_mut()?;
+        let shown_texture = self
+            .texture
+            .as_ref()
+            .filter(|_| self.video_texture.is_none())
+            .map(egui::TextureHandle::id);
+        let on_target = self.image_list.get(self.current_index) == Some(&transition.target);
+        let now = Instant::now();
+        let started_at = match transition.started_at {
+            Some(started_at) if on_target && shown_texture.is_some() => started_at,
+            Some(_) => {
+                self.image_transition = None;
+                return None;
+            }
+            None if on_target
+                && !self.retained_media_placeholder_visible
+                && shown_texture.is_some_and(|id| id != transition.texture.id()) =>
+            {
+                *transition.started_at.insert(now)
             }
             None => {
-                self.drop_retained_media_placeholder();
-                self.error_message = Some(format!("Unsupported file format: {:?}", path));
+                if !on_target
+                    || now.duration_since(transition.requested_at) > IMAGE_TRANSITION_MAX_WAIT
+                {
+                    self.image_transition = None;
+                } else {
+                    ctx.request_repaint_after(Duration::from_millis(16));
+                }
+                return None;
             }
-        }
+        };
 
-        if media_type.is_some()
-            && !is_folder_entry
-            && !defer_directory_work_for_fast_startup
-            && self.pending_media_load.is_none()
-        {
-            self.schedule_solo_probe_window(path, media_type);
+        let duration = Duration::from_millis(u64::from(self.config.transition_duration_ms));
+        let elapsed = now.duration_since(started_at);
+        if elapsed >= duration {
+            self.image_transition = None;
+            return None;
         }
-
-        self.perf_metrics
-            .record_duration("load_media_prepare_ms", load_media_start.elapsed());
+        ctx.request_repaint();
+        let progress = self
+            .config
+            .transition_easing
+            .apply(elapsed.as_secs_f32() / duration.as_secs_f32());
+        let frame = self.config.transition_effect.frame(
+            progress,
+            transition.rect,
+            transition.forward,
+            view_width,
+        );
+        Some((transition.texture.clone(), frame))
     }
 
-    /// Save the current view state for the current image (fullscreen only).
-    /// This allows restoring zoom, pan, and rotation when returning to this image.
-    fn save_current_fullscreen_view_state(&mut self) {
-        if !self.is_fullscreen || !self.current_fullscreen_view_has_memory {
-            return;
+    /// Next or previous index in folder order, skipping items the rating filter hides.
+    /// `None` when the filter hides everything else.
+    fn rating_filtered_neighbor(&mut self, forward: bool) -> Option<usize> {
+        let len = self.image_list.len();
+        let step_from_current = |step: usize| {
+            if forward {
+                (self.current_index + step) % len
+            } else {
+                (self.current_index + len - step % len) % len
+            }
+        };
+        let filter = self.config.rating_filter;
+        if filter == RatingFilter::All {
+            return Some(step_from_current(1));
+        }
+        for step in 1..len {
+            let candidate = step_from_current(step);
+            let path = self.image_list[candidate].clone();
+            if !self.is_folder_navigation_entry_path(path.as_path())
+                && self.ratings.get(&path).passes(filter)
+            {
+                return Some(candidate);
+            }
         }
+        None
+    }
 
-        let Some(path) = self.image_list.get(self.current_index).cloned() else {
+    /// Applies `update` to the current item's rating and saves it to the XMP sidecar.
+    fn update_current_rating(&mut self, update: impl FnOnce(&mut ratings::ImageRating)) {
+        let Some(path) = self.current_media_path() else {
             return;
         };
+        if self.is_folder_navigation_entry_path(path.as_path()) {
+            return;
+        }
+        let mut rating = self.ratings.get(&path);
+        update(&mut rating);
+        if let Err(err) = self.ratings.set(&path, rating) {
+            tracing::warn!("failed to save rating: {}", err);
+        }
+    }
 
-        let state = FullscreenViewState {
-            zoom: self.zoom,
-            zoom_target: self.zoom_target,
-            offset: self.offset,
-            precise_rotation_degrees: self.precise_rotation_degrees,
-            precise_rotation_target_degrees: self.precise_rotation_target_degrees,
-            rotation_steps: self.current_rotation_steps,
-            flip_horizontal: self.flip_horizontal,
-            flip_vertical: self.flip_vertical,
+    fn adjacent_video_index(&self, forward: bool) -> Option<usize> {
+        let len = self.image_list.len();
+        if len <= 1 {
+            return None;
+        }
+
+        for step in 1..len {
+            let candidate = if forward {
+                (self.current_index + step) % len
+            } else {
+                (self.current_index + len - (step % len)) % len
+            };
+
+            if self
+                .image_list
+                .get(candidate)
+                .is_some_and(|path| Self::is_video_navigation_candidate_path(path.as_path()))
+            {
+                return Some(candidate);
+            }
+        }
+
+        None
+    }
+
+    fn navigate_video_file(&mut self, forward: bool) {
+        let Some(target_index) = self.adjacent_video_index(forward) else {
+            return;
         };
 
-        self.fullscreen_view_states.insert(path, state);
+        self.navigate_video_file_to_index(target_index);
     }
 
-    fn remember_current_fullscreen_view_state(&mut self) {
-        if !self.is_fullscreen || self.manga_mode {
+    fn navigate_video_file_to_index(&mut self, target_index: usize) {
+        if !self
+            .image_list
+            .get(target_index)
+            .is_some_and(|path| Self::is_video_navigation_candidate_path(path.as_path()))
+        {
+            return;
+        }
+
+        if self.manga_mode && self.is_fullscreen {
+            self.set_current_index_clamped(target_index);
+            let scroll_to = self.manga_get_scroll_offset_for_index(target_index);
+            self.manga_scroll_target = scroll_to;
+            self.manga_update_preload_queue();
             return;
         }
 
-        self.current_fullscreen_view_has_memory = true;
         self.save_current_fullscreen_view_state();
+        self.set_current_index_clamped(target_index);
+        let path = self.image_list[self.current_index].clone();
+        self.load_image_retaining_visible_media(&path);
     }
 
-    fn clear_current_fullscreen_view_memory(&mut self) {
-        self.current_fullscreen_view_has_memory = false;
+    /// Load previous image
+    fn prev_image(&mut self) {
+        if self.image_list.is_empty() {
+            return;
+        }
 
-        let Some(path) = self.image_list.get(self.current_index).cloned() else {
+        // In manga mode, scroll to previous image instead of loading
+        if self.manga_mode && self.is_fullscreen {
+            let prev_index = if self.current_index == 0 {
+                self.image_list.len() - 1
+            } else {
+                self.current_index - 1
+            };
+            self.set_current_index_clamped(prev_index);
+            let scroll_to = self.manga_get_scroll_offset_for_index(prev_index);
+            self.manga_scroll_target = scroll_to;
+            self.manga_update_preload_queue();
+            return;
+        }
+
+        let Some(prev_index) = self.rating_filtered_neighbor(false) else {
             return;
         };
 
-        self.fullscreen_view_states.remove(&path);
+        // Save current view state before navigating (fullscreen only)
+        self.save_current_fullscreen_view_state();
+        self.set_solo_preload_momentum(SoloPreloadMomentum::Backward);
+
+        self.set_current_index_clamped(prev_index);
+        let path = self.image_list[self.current_index].clone();
+        self.begin_image_transition(false, &path);
+        self.load_image_retaining_visible_media(&path);
     }
 
-    /// Restore the saved view state for a given image path (fullscreen only).
-    /// Returns true if state was restored, false if no saved state exists.
-    fn restore_fullscreen_view_state(&mut self, path: &PathBuf) -> bool {
-        if !self.is_fullscreen {
-            return false;
+    /// Load first image
+    fn first_image(&mut self) {
+        if self.image_list.is_empty() {
+            return;
         }
 
-        if let Some(state) = self.fullscreen_view_states.get(path).cloned() {
-            self.zoom = state.zoom;
-            self.zoom_target = state.zoom_target;
-            self.offset = state.offset;
-            self.zoom_velocity = 0.0;
-            self.current_rotation_steps = state.rotation_steps;
-            self.precise_rotation_degrees = state.precise_rotation_degrees;
-            self.precise_rotation_target_degrees = state.precise_rotation_target_degrees;
-            self.precise_rotation_velocity = 0.0;
-            self.flip_horizontal = state.flip_horizontal;
-            self.flip_vertical = state.flip_vertical;
+        // In manga mode, jump to start of strip
+        if self.manga_mode && self.is_fullscreen {
+            self.manga_go_to_start();
+            return;
+        }
 
-            // Apply saved rotations if image was reloaded
-            if let Some(ref mut img) = self.image {
-                for _ in 0..state.rotation_steps {
-                    img.rotate_clockwise();
-                }
-                if state.rotation_steps > 0 {
-                    self.texture = None; // Force texture rebuild
-                }
-            }
+        if self.current_index == 0 {
+            return;
+        }
 
-            self.current_fullscreen_view_has_memory = true;
+        // Save current view state before navigating (fullscreen only)
+        self.save_current_fullscreen_view_state();
 
-            true
-        } else {
-            false
-        }
+        self.set_current_index_clamped(0);
+        let path = self.image_list[self.current_index].clone();
+        self.load_image_retaining_visible_media(&path);
     }
 
-    /// Update the discrete 90° rotation count for the current image.
-    /// When fullscreen is active, also sync it into the per-image fullscreen state cache.
-    fn update_fullscreen_rotation(&mut self, clockwise: bool) {
-        if clockwise {
-            self.current_rotation_steps = (self.current_rotation_steps + 1) % 4;
-        } else {
-            self.current_rotation_steps = (self.current_rotation_steps + 3) % 4;
+    /// Load last image
+    fn last_image(&mut self) {
+        if self.image_list.is_empty() {
+            return;
         }
 
-        if !self.is_fullscreen {
+        // In manga mode, jump to end of strip
+        if self.manga_mode && self.is_fullscreen {
+            self.manga_go_to_end();
             return;
         }
 
-        self.remember_current_fullscreen_view_state();
+        let last_index = self.image_list.len() - 1;
+        if self.current_index == last_index {
+            return;
+        }
+
+        // Save current view state before navigating (fullscreen only)
+        self.save_current_fullscreen_view_state();
+
+        self.set_current_index_clamped(last_index);
+        let path = self.image_list[self.current_index].clone();
+        self.load_image_retaining_visible_media(&path);
     }
 
-    fn normalize_precise_rotation_degrees(degrees: f32) -> f32 {
-        (degrees + 180.0).rem_euclid(360.0) - 180.0
+    fn valid_layout_bounds(size: egui::Vec2) -> Option<egui::Vec2> {
+        (size.x.is_finite() && size.y.is_finite() && size.x > 0.0 && size.y > 0.0).then_some(size)
     }
 
-    fn current_precise_rotation_angle_degrees(&self) -> f32 {
-        if !self.manga_mode && self.current_media_type.is_some() {
-            Self::normalize_precise_rotation_degrees(self.precise_rotation_degrees)
-        } else {
-            0.0
+    fn floating_monitor_bounds_for_layout(
+        viewport_monitor: Option<egui::Vec2>,
+        current_viewport: egui::Vec2,
+        last_known_monitor: egui::Vec2,
+    ) -> egui::Vec2 {
+        viewport_monitor
+            .and_then(Self::valid_layout_bounds)
+            .or_else(|| Self::valid_layout_bounds(last_known_monitor))
+            .or_else(|| Self::valid_layout_bounds(current_viewport))
+            .unwrap_or(egui::vec2(1.0, 1.0))
+    }
+
+    fn refresh_last_known_monitor_size(&mut self, ctx: &egui::Context) {
+        if let Some(monitor) = ctx
+            .input(|i| i.raw.viewport().monitor_size)
+            .and_then(Self::valid_layout_bounds)
+        {
+            self.last_known_monitor_size = monitor;
         }
     }
 
-    fn reset_precise_rotation(&mut self) {
-        self.precise_rotation_degrees = 0.0;
-        self.precise_rotation_target_degrees = 0.0;
-        self.precise_rotation_velocity = 0.0;
+    fn monitor_size_points(&self, ctx: &egui::Context) -> egui::Vec2 {
+        Self::floating_monitor_bounds_for_layout(
+            ctx.input(|i| i.raw.viewport().monitor_size),
+            self.screen_size,
+            self.last_known_monitor_size,
+        )
     }
 
-    fn reset_discrete_rotation(&mut self, ctx: &egui::Context) {
-        let steps = self.current_rotation_steps % 4;
-        if steps == 0 {
-            self.current_rotation_steps = 0;
+    /// Places a floating window of `inner_size` on its monitor: where it was last left on that
+    /// monitor, otherwise centered. Until the window is first shown the saved monitor wins,
+    /// so the viewer reopens where it was closed.
+    fn center_window_on_monitor(&mut self, ctx: &egui::Context, inner_size: egui::Vec2) {
+        let saved = self.config.state_floating_placement;
+        let target = saved
+            .filter(|_| !self.startup_window_shown)
+            .and_then(|placement| placement.find_monitor(&monitors::enumerate()))
+            .or_else(monitors::window_monitor);
+        if let Some(target) = target {
+            let center = saved
+                .filter(|placement| placement.monitor == target.rect)
+                .map_or(egui::vec2(0.5, 0.5), |placement| placement.center);
+            let position = target.position_at_points(
+                center,
+                inner_size,
+                ctx.pixels_per_point(),
+                ctx.zoom_factor(),
+            );
+            self.send_outer_position(ctx, position);
             return;
         }
 
-        if let Some(ref mut img) = self.image {
-            match steps {
-                1 => img.rotate_counter_clockwise(),
-                2 => {
-                    img.rotate_clockwise();
-                    img.rotate_clockwise();
-                }
-                3 => img.rotate_clockwise(),
-                _ => {}
-            }
+        let monitor = self.monitor_size_points(ctx);
+        let x = (monitor.x - inner_size.x) * 0.5;
+        let y = (monitor.y - inner_size.y) * 0.5;
+        self.send_outer_position(ctx, egui::pos2(x.max(0.0), y));
+    }
 
-            self.texture_frame = usize::MAX;
-            let _ = self.update_texture(ctx);
+    fn floating_layout_size_for_media_bounds(
+        media_w: f32,
+        media_h: f32,
+        monitor: egui::Vec2,
+        mode: FitMode,
+    ) -> Option<(f32, egui::Vec2)> {
+        if media_w <= 0.0 || media_h <= 0.0 || monitor.x <= 0.0 || monitor.y <= 0.0 {
+            return None;
         }
 
-        self.current_rotation_steps = 0;
+        // Floating windows never upscale; the window crops whatever the mode lets overflow.
+        let zoom = mode
+            .zoom_for(monitor, egui::vec2(media_w, media_h))
+            .min(1.0);
+        let size = egui::Vec2::new(
+            (media_w * zoom).min(monitor.x).max(200.0),
+            (media_h * zoom).min(monitor.y).max(150.0),
+        );
+        Some((zoom, size))
     }
 
-    fn reset_current_view_rotation(&mut self, ctx: &egui::Context) {
-        self.reset_discrete_rotation(ctx);
-        self.reset_precise_rotation();
+    fn floating_layout_size_for_media(
+        &self,
+        media_w: f32,
+        media_h: f32,
+        monitor: egui::Vec2,
+    ) -> Option<(f32, egui::Vec2)> {
+        Self::floating_layout_size_for_media_bounds(
+            media_w,
+            media_h,
+            monitor,
+            self.config.floating_fit_mode,
+        )
     }
 
-    fn update_precise_rotation(&mut self, delta_degrees: f32) {
-        if self.manga_mode || self.current_media_type.is_none() {
-            return;
+    fn prepare_single_instance_media_handoff(&mut self, ctx: &egui::Context) {
+        if self.manga_mode {
+            self.stop_manga_wheel_scroll();
+            self.stop_manga_autoscroll();
+            self.manga_mode = false;
+            set_metadata_cache_enabled(false);
+            self.manga_clear_cache();
+            self.manga_loader = None;
         }
 
-        self.precise_rotation_target_degrees = Self::normalize_precise_rotation_degrees(
-            self.precise_rotation_target_degrees + delta_degrees,
-        );
+        self.clear_strip_return_context();
+        self.titlebar_pending_restore_layout = None;
+        self.titlebar_previous_mode = None;
+        self.toggle_fullscreen = false;
+        self.toggle_fullscreen_force_borderless = false;
+        self.toggle_fullscreen_from_titlebar = false;
+        self.pending_fullscreen_layout = false;
+        self.pending_maximized_layout = false;
+        self.pending_window_resize = None;
+        self.image_changed = false;
+        self.pending_media_layout = false;
+        self.saved_fullscreen_entry_index = None;
+        self.fullscreen_view_states.clear();
+        self.current_fullscreen_view_has_memory = false;
+        self.strip_open_force_fit_path = None;
+        self.is_fullscreen = false;
+        self.fullscreen_transition = 0.0;
+        self.fullscreen_transition_target = 0.0;
+        self.force_floating_layout_once = true;
 
-        if self.is_fullscreen {
-            self.remember_current_fullscreen_view_state();
-        }
+        self.request_native_maximize = if self.current_window_is_maximized(ctx) {
+            Some(false)
+        } else {
+            None
+        };
     }
 
-    fn toggle_media_flip(&mut self, horizontal: bool, vertical: bool) {
-        if self.manga_mode || self.current_media_type.is_none() {
-            return;
-        }
+    fn current_window_is_maximized(&self, ctx: &egui::Context) -> bool {
+        let viewport_maximized = ctx.input(|i| i.raw.viewport().maximized);
 
-        if horizontal {
-            self.flip_horizontal = !self.flip_horizontal;
-        }
-        if vertical {
-            self.flip_vertical = !self.flip_vertical;
+        #[cfg(target_os = "windows")]
+        {
+            viewport_maximized
+                .or_else(crate::windows_env::active_window_is_maximized)
+                .unwrap_or(false)
         }
 
-        if self.is_fullscreen {
-            self.remember_current_fullscreen_view_state();
+        #[cfg(not(target_os = "windows"))]
+        {
+            viewport_maximized.unwrap_or(false)
         }
     }
 
-    /// Load next image
-    fn next_image(&mut self) {
-        if self.image_list.is_empty() {
-            return;
+    fn use_native_fullscreen_window_transition(&self) -> bool {
+        #[cfg(target_os = "windows")]
+        {
+            self.config.fullscreen_native_window_transition
         }
 
-        // In manga mode, scroll to next image instead of loading
-        if self.manga_mode && self.is_fullscreen {
-            let next_index = if self.current_index + 1 >= self.image_list.len() {
-                0
-            } else {
-                self.current_index + 1
-            };
-            self.set_current_index_clamped(next_index);
-            let scroll_to = self.manga_get_scroll_offset_for_index(next_index);
-            self.manga_scroll_target = scroll_to;
-            self.manga_update_preload_queue();
-            return;
+        #[cfg(not(target_os = "windows"))]
+        {
+            false
         }
+    }
 
-        // Save current view state before navigating (fullscreen only)
-        self.save_current_fullscreen_view_state();
-        self.set_solo_preload_momentum(SoloPreloadMomentum::Forward);
+    fn fullscreen_layout_ready(&self, ctx: &egui::Context) -> bool {
+        let monitor = self.monitor_size_points(ctx);
+        let viewport = ctx.screen_rect().size();
 
-        self.set_current_index_clamped(if self.current_index + 1 >= self.image_list.len() {
-            0
-        } else {
-            self.current_index + 1
-        });
-        let path = self.image_list[self.current_index].clone();
-        self.load_image_retaining_visible_media(&path);
+        (viewport.x - monitor.x).abs() <= 2.0 && (viewport.y - monitor.y).abs() <= 2.0
     }
 
-    fn adjacent_video_index(&self, forward: bool) -> Option<usize> {
-        let len = self.image_list.len();
-        if len <= 1 {
-            return None;
+    fn strip_return_mode_for_fullscreen_toggle(&self) -> Option<MangaLayoutMode> {
+        if self.strip_return_button_only {
+            None
+        } else {
+            self.strip_return_mode
         }
+    }
 
-        for step in 1..len {
-            let candidate = if forward {
-                (self.current_index + step) % len
-            } else {
-                (self.current_index + len - (step % len)) % len
-            };
+    fn sync_current_index_to_visible_media(&mut self) {
+        let visible_path = if matches!(self.current_media_type, Some(MediaType::Video)) {
+            self.current_video_path
+                .as_ref()
+                .or(self.pending_file_size_probe_path.as_ref())
+                .or(self.current_file_size_label_path.as_ref())
+        } else {
+            self.image
+                .as_ref()
+                .map(|img| &img.path)
+                .or(self.pending_file_size_probe_path.as_ref())
+                .or(self.current_file_size_label_path.as_ref())
+        };
 
-            if self
-                .image_list
-                .get(candidate)
-                .is_some_and(|path| Self::is_video_navigation_candidate_path(path.as_path()))
-            {
-                return Some(candidate);
-            }
+        let Some(visible_path) = visible_path else {
+            return;
+        };
+
+        if let Some(index) = self
+            .image_list
+            .iter()
+            .position(|candidate| candidate == visible_path)
+        {
+            self.set_current_index_clamped(index);
         }
+    }
 
-        None
+    fn current_titlebar_return_mode(&self) -> TitlebarToggleReturnMode {
+        if self.manga_mode {
+            TitlebarToggleReturnMode::Manga(self.manga_layout_mode)
+        } else if let Some(layout_mode) = self.strip_return_mode_for_fullscreen_toggle() {
+            TitlebarToggleReturnMode::Manga(layout_mode)
+        } else {
+            TitlebarToggleReturnMode::Fullscreen
+        }
     }
 
-    fn navigate_video_file(&mut self, forward: bool) {
-        let Some(target_index) = self.adjacent_video_index(forward) else {
-            return;
+    fn request_titlebar_fullscreen_reentry(
+        &mut self,
+        return_mode: Option<TitlebarToggleReturnMode>,
+    ) {
+        self.sync_current_index_to_visible_media();
+        self.toggle_fullscreen_from_titlebar = true;
+        if self.config.maximize_to_borderless_fullscreen {
+            self.toggle_fullscreen_force_borderless = true;
+        }
+        self.titlebar_pending_restore_layout = match return_mode {
+            Some(TitlebarToggleReturnMode::Manga(layout_mode)) => Some(layout_mode),
+            _ => None,
         };
-
-        self.navigate_video_file_to_index(target_index);
+        self.titlebar_previous_mode = None;
+        self.toggle_fullscreen = true;
     }
 
-    fn navigate_video_file_to_index(&mut self, target_index: usize) {
-        if !self
-            .image_list
-            .get(target_index)
-            .is_some_and(|path| Self::is_video_navigation_candidate_path(path.as_path()))
+    fn request_goto_file_fullscreen_toggle(&mut self) {
+        self.stop_manga_autoscroll();
+
+        if self.is_fullscreen
+            && !self.manga_mode
+            && self.strip_return_mode_for_fullscreen_toggle().is_some()
         {
+            self.return_to_strip_mode_from_fullscreen_toggle();
             return;
         }
 
-        if self.manga_mode && self.is_fullscreen {
-            self.set_current_index_clamped(target_index);
-            let scroll_to = self.manga_get_scroll_offset_for_index(target_index);
-            self.manga_scroll_target = scroll_to;
-            self.manga_update_preload_queue();
-            return;
+        if !self.is_fullscreen {
+            if let Some(previous_mode) = self.titlebar_previous_mode.take() {
+                self.request_titlebar_fullscreen_reentry(Some(previous_mode));
+                return;
+            }
+
+            if self.config.maximize_to_borderless_fullscreen {
+                self.toggle_fullscreen_force_borderless = true;
+            }
         }
 
-        self.save_current_fullscreen_view_state();
-        self.set_current_index_clamped(target_index);
-        let path = self.image_list[self.current_index].clone();
-        self.load_image_retaining_visible_media(&path);
+        self.toggle_fullscreen = true;
     }
 
-    /// Load previous image
-    fn prev_image(&mut self) {
-        if self.image_list.is_empty() {
+    /// Covers `monitor` with the borderless fullscreen window. Without monitor information
+    /// the window covers the viewport's monitor from the desktop origin.
+    fn place_fullscreen_window(&mut self, ctx: &egui::Context, monitor: Option<monitors::Monitor>) {
+        let Some(monitor) = monitor else {
+            let size = self.monitor_size_points(ctx);
+            ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(egui::Pos2::ZERO));
+            ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(size));
             return;
-        }
+        };
+        let (position, size) = monitor.placement_points(ctx.pixels_per_point(), ctx.zoom_factor());
+        ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(position));
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(size));
+        self.fullscreen_monitor_move = Some(FullscreenMonitorMove {
+            monitor,
+            frames_left: 30,
+            remaximize: false,
+        });
+    }
 
-        // In manga mode, scroll to previous image instead of loading
-        if self.manga_mode && self.is_fullscreen {
-            let prev_index = if self.current_index == 0 {
-                self.image_list.len() - 1
-            } else {
-                self.current_index - 1
-            };
-            self.set_current_index_clamped(prev_index);
-            let scroll_to = self.manga_get_scroll_offset_for_index(prev_index);
-            self.manga_scroll_target = scroll_to;
-            self.manga_update_preload_queue();
+    fn move_window_to_next_monitor(&mut self, ctx: &egui::Context) {
+        let all = monitors::enumerate();
+        let Some(target) =
+            monitors::window_monitor().and_then(|current| monitors::next_monitor(&all, &current))
+        else {
+            return;
+        };
+        let pixels_per_point = ctx.pixels_per_point();
+        let zoom_factor = ctx.zoom_factor();
+
+        if !self.is_fullscreen {
+            let size = ctx
+                .input(|i| i.viewport().outer_rect)
+                .map(|rect| rect.size())
+                .unwrap_or(self.screen_size);
+            let position = target.centered_position_points(size, pixels_per_point, zoom_factor);
+            ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(position));
             return;
         }
 
-        // Save current view state before navigating (fullscreen only)
-        self.save_current_fullscreen_view_state();
-        self.set_solo_preload_momentum(SoloPreloadMomentum::Backward);
-
-        self.set_current_index_clamped(if self.current_index == 0 {
-            self.image_list.len() - 1
+        self.pending_fullscreen_layout = true;
+        if self.current_window_is_maximized(ctx) {
+            // A maximized window cannot be moved; restore it, move it and maximize it again
+            // once it is on the target monitor.
+            #[cfg(target_os = "windows")]
+            {
+                let _ = crate::windows_env::set_active_window_maximized(false);
+            }
+            #[cfg(not(target_os = "windows"))]
+            {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Maximized(false));
+            }
+            let size = self.screen_size.min(target.rect.size() / target.scale) * 0.8;
+            let position = target.centered_position_points(size, pixels_per_point, zoom_factor);
+            ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(position));
+            self.fullscreen_monitor_move = Some(FullscreenMonitorMove {
+                monitor: target,
+                frames_left: 1,
+                remaximize: true,
+            });
         } else {
-            self.current_index - 1
-        });
-        let path = self.image_list[self.current_index].clone();
-        self.load_image_retaining_visible_media(&path);
+            self.place_fullscreen_window(ctx, Some(target));
+        }
+        ctx.request_repaint();
     }
 
-    /// Load first image
-    fn first_image(&mut self) {
-        if self.image_list.is_empty() {
+    /// Saves the monitor and spot of the floating window, for the next start and for leaving
+    /// fullscreen.
+    fn remember_floating_placement(&mut self, ctx: &egui::Context) {
+        if self.is_fullscreen || !self.startup_window_shown || self.current_window_is_maximized(ctx)
+        {
             return;
         }
-
-        // In manga mode, jump to start of strip
-        if self.manga_mode && self.is_fullscreen {
-            self.manga_go_to_start();
+        let (Some(monitor), Some(outer)) = (
+            monitors::window_monitor(),
+            ctx.input(|i| i.raw.viewport().outer_rect),
+        ) else {
             return;
+        };
+        let placement = SavedPlacement::capture(&monitor, outer, ctx.pixels_per_point());
+        if self.config.state_floating_placement != Some(placement) {
+            self.config.state_floating_placement = Some(placement);
+            self.pending_idle_config_sync = true;
         }
+    }
 
-        if self.current_index == 0 {
+    /// Monitor work area of the window in points, or the whole monitor where it is unknown.
+    fn floating_work_area(&self, ctx: &egui::Context) -> egui::Rect {
+        monitors::window_monitor()
+            .map(|monitor| monitor.work_area_points(ctx.pixels_per_point(), ctx.zoom_factor()))
+            .unwrap_or_else(|| {
+                egui::Rect::from_min_size(egui::Pos2::ZERO, self.monitor_size_points(ctx))
+            })
+    }
+
+    /// Title bar and borders of the native window: outer size minus inner size.
+    fn window_decoration_size(ctx: &egui::Context) -> egui::Vec2 {
+        ctx.input(|i| {
+            let viewport = i.raw.viewport();
+            match (viewport.inner_rect, viewport.outer_rect) {
+                (Some(inner), Some(outer)) => (outer.size() - inner.size()).max(egui::Vec2::ZERO),
+                _ => egui::Vec2::ZERO,
+            }
+        })
+    }
+
+    /// Fits the media into `bounds` with `floating_fit_mode`, never above 100%.
+    fn fit_floating_zoom_to(&mut self, bounds: egui::Vec2) {
+        let Some((media_w, media_h)) = self.media_display_dimensions() else {
+            return;
+        };
+        if media_w == 0 || media_h == 0 {
             return;
         }
+        let zoom = self
+            .config
+            .floating_fit_mode
+            .zoom_for(bounds, egui::vec2(media_w as f32, media_h as f32))
+            .min(1.0);
+        let zoom = self.clamp_zoom(zoom);
+        self.zoom = zoom;
+        self.zoom_target = zoom;
+        self.zoom_velocity = 0.0;
+        self.offset = egui::Vec2::ZERO;
+    }
 
-        // Save current view state before navigating (fullscreen only)
-        self.save_current_fullscreen_view_state();
-
-        self.set_current_index_clamped(0);
-        let path = self.image_list[self.current_index].clone();
-        self.load_image_retaining_visible_media(&path);
+    /// Moves the floating window to `outer` (in points) and pins that size, so the media is
+    /// fitted inside instead of the window following the zoom.
+    fn place_pinned_floating_window(&mut self, ctx: &egui::Context, outer: egui::Rect) {
+        let decoration = Self::window_decoration_size(ctx);
+        let inner = (outer.size() - decoration).max(egui::vec2(200.0, 150.0));
+        self.send_outer_position(ctx, outer.min);
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(inner));
+        self.floating_size_pinned = true;
+        self.floating_zoom_inside_window_locked = false;
+        self.fit_floating_zoom_to(inner);
     }
 
-    /// Load last image
-    fn last_image(&mut self) {
-        if self.image_list.is_empty() {
+    fn snap_floating_window(&mut self, ctx: &egui::Context, direction: SnapDirection) {
+        if self.is_fullscreen || self.current_window_is_maximized(ctx) {
             return;
         }
+        let region = self.window_snap_region.unwrap_or_default().step(direction);
+        self.window_snap_region = Some(region);
+        let outer = region.rect(self.floating_work_area(ctx));
+        self.place_pinned_floating_window(ctx, outer);
+    }
 
-        // In manga mode, jump to end of strip
-        if self.manga_mode && self.is_fullscreen {
-            self.manga_go_to_end();
+    /// Pixel presets resize the window around its center and fit the media inside;
+    /// percentage presets set the zoom and let the floating autosize size the window.
+    fn apply_window_size_preset(&mut self, ctx: &egui::Context, index: usize) {
+        if self.is_fullscreen || self.current_window_is_maximized(ctx) {
             return;
         }
-
-        let last_index = self.image_list.len() - 1;
-        if self.current_index == last_index {
+        let Some(preset) = self.config.window_size_presets.get(index).copied() else {
             return;
+        };
+        self.window_snap_region = None;
+        match preset {
+            SizePreset::MediaPercent(percent) => {
+                self.floating_size_pinned = false;
+                self.floating_zoom_inside_window_locked = false;
+                let zoom = self.clamp_zoom(percent / 100.0);
+                self.zoom = zoom;
+                self.zoom_target = zoom;
+                self.zoom_velocity = 0.0;
+                self.offset = egui::Vec2::ZERO;
+            }
+            SizePreset::Pixels(width, height) => {
+                let inner = egui::vec2(width as f32, height as f32) / ctx.pixels_per_point();
+                let outer_size = inner + Self::window_decoration_size(ctx);
+                let center = ctx
+                    .input(|i| i.raw.viewport().outer_rect)
+                    .map_or_else(|| ctx.screen_rect().center(), |rect| rect.center());
+                self.place_pinned_floating_window(
+                    ctx,
+                    egui::Rect::from_min_size((center - outer_size * 0.5).round(), outer_size),
+                );
+            }
         }
+        self.show_action_osd(
+            "window_size",
+            i18n::tr_args("osd.window_size", &[("size", &preset.label())]),
+        );
+    }
 
-        // Save current view state before navigating (fullscreen only)
-        self.save_current_fullscreen_view_state();
+    /// Switches the window level. With `always_on_top_compact`, a floating window also moves
+    /// into the bottom-right corner of its monitor at a fraction of the monitor width, and
+    /// goes back to its previous place and zoom when toggled off.
+    fn toggle_always_on_top_mode(&mut self, ctx: &egui::Context) {
+        self.always_on_top = !self.always_on_top;
+        let level = if self.always_on_top {
+            egui::WindowLevel::AlwaysOnTop
+        } else {
+            egui::WindowLevel::Normal
+        };
+        ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(level));
 
-        self.set_current_index_clamped(last_index);
-        let path = self.image_list[self.current_index].clone();
-        self.load_image_retaining_visible_media(&path);
+        if !self.always_on_top {
+            if let Some((rect, zoom)) = self.always_on_top_compact_restore.take() {
+                self.zoom = zoom;
+                self.zoom_target = zoom;
+                self.zoom_velocity = 0.0;
+                self.offset = egui::Vec2::ZERO;
+                ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(rect.size()));
+                ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(rect.min));
+            }
+            return;
+        }
+        if !self.config.always_on_top_compact || self.is_fullscreen {
+            return;
+        }
+        let Some(outer) = ctx.input(|i| i.viewport().outer_rect) else {
+            return;
+        };
+        let Some((media_w, media_h)) = self.media_display_dimensions() else {
+            return;
+        };
+        let (origin, area) = match monitors::window_monitor() {
+            Some(monitor) => monitor.placement_points(ctx.pixels_per_point(), ctx.zoom_factor()),
+            None => (egui::Pos2::ZERO, self.monitor_size_points(ctx)),
+        };
+        let (media_w, media_h) = (media_w.max(1) as f32, media_h.max(1) as f32);
+        let width = area.x * self.config.always_on_top_compact_size / 100.0;
+        let zoom = (width / media_w).min(area.y * 0.5 / media_h);
+        let size = egui::vec2(media_w * zoom, media_h * zoom).max(egui::vec2(200.0, 150.0));
+        let margin = egui::vec2(24.0, 24.0);
+
+        self.always_on_top_compact_restore = Some((outer, self.zoom));
+        self.zoom = zoom;
+        self.zoom_target = zoom;
+        self.zoom_velocity = 0.0;
+        self.offset = egui::Vec2::ZERO;
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(size));
+        ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(
+            origin + area - size - margin,
+        ));
     }
 
-    fn valid_layout_bounds(size: egui::Vec2) -> Option<egui::Vec2> {
-        (size.x.is_finite() && size.y.is_finite() && size.x > 0.0 && size.y > 0.0).then_some(size)
+    /// Small "on top" marker in the top-left corner, below the title bar when it is shown.
+    fn draw_always_on_top_badge(&self, ctx: &egui::Context) {
+        if !self.always_on_top {
+            return;
+        }
+        let screen_rect = ctx.screen_rect();
+        let top = if self.show_controls && self.always_on_top_compact_restore.is_none() {
+            self.overlay_theme.title_bar_height + 8.0
+        } else {
+            8.0
+        };
+        egui::Area::new(egui::Id::new("always_on_top_badge"))
+            .fixed_pos(screen_rect.min + egui::vec2(8.0, top))
+            .order(egui::Order::Foreground)
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgba_unmultiplied(16, 23, 31, 200))
+                    .stroke(egui::Stroke::new(
+                        1.0,
+                        egui::Color32::from_rgba_unmultiplied(127, 188, 255, 94),
+                    ))
+                    .rounding(8.0)
+                    .inner_margin(egui::Margin::symmetric(7.0, 3.0))
+                    .show(ui, |ui| {
+                        ui.label(
+                            egui::RichText::new("📌 On top")
+                                .size(11.5)
+                                .color(egui::Color32::from_rgb(208, 228, 252)),
+                        );
+                    });
+            });
     }
 
-    fn floating_monitor_bounds_for_layout(
-        viewport_monitor: Option<egui::Vec2>,
-        current_viewport: egui::Vec2,
-        last_known_monitor: egui::Vec2,
-    ) -> egui::Vec2 {
-        viewport_monitor
-            .and_then(Self::valid_layout_bounds)
-            .or_else(|| Self::valid_layout_bounds(last_known_monitor))
-            .or_else(|| Self::valid_layout_bounds(current_viewport))
-            .unwrap_or(egui::vec2(1.0, 1.0))
+    const TRAY_OPEN_FILE: u32 = 1;
+    const TRAY_SLIDESHOW: u32 = 2;
+    const TRAY_EXIT: u32 = 3;
+    /// Recent file `i` has menu id `TRAY_RECENT_FILE + i`.
+    const TRAY_RECENT_FILE: u32 = 100;
+
+    fn tray_menu(&self) -> Vec<tray::TrayMenuItem> {
+        let recent = self
+            .config
+            .state_recent_files
+            .iter()
+            .enumerate()
+            .map(|(index, path)| {
+                let label = path.file_name().map_or_else(
+                    || path.display().to_string(),
+                    |name| name.to_string_lossy().into_owned(),
+                );
+                tray::TrayMenuItem::item(Self::TRAY_RECENT_FILE + index as u32, label)
+            })
+            .collect();
+        vec![
+            tray::TrayMenuItem::item(Self::TRAY_OPEN_FILE, "Open File..."),
+            tray::TrayMenuItem::Submenu {
+                label: "Recent Files".to_string(),
+                items: recent,
+            },
+            tray::TrayMenuItem::checked(
+                Self::TRAY_SLIDESHOW,
+                "Slideshow",
+                self.slideshow.is_some(),
+            ),
+            tray::TrayMenuItem::Separator,
+            tray::TrayMenuItem::item(Self::TRAY_EXIT, "Exit"),
+        ]
     }
 
-    fn refresh_last_known_monitor_size(&mut self, ctx: &egui::Context) {
-        if let Some(monitor) = ctx
-            .input(|i| i.raw.viewport().monitor_size)
-            .and_then(Self::valid_layout_bounds)
-        {
-            self.last_known_monitor_size = monitor;
+    fn refresh_tray_menu(&self) {
+        if let Some(tray) = self.tray.as_ref() {
+            tray.set_menu(self.tray_menu());
         }
     }
 
-    fn monitor_size_points(&self, ctx: &egui::Context) -> egui::Vec2 {
-        Self::floating_monitor_bounds_for_layout(
-            ctx.input(|i| i.raw.viewport().monitor_size),
-            self.screen_size,
-            self.last_known_monitor_size,
-        )
+    /// Puts `path` at the top of the recent files, saved with the next idle config write.
+    /// Returns whether the list changed.
+    fn remember_recent_file(&mut self, path: &Path) -> bool {
+        let changed = self.config.push_recent_file(path);
+        if changed {
+            self.pending_idle_config_sync = true;
+            self.refresh_tray_menu();
+            self.refresh_jump_list();
+        }
+        changed
     }
 
-    fn center_window_on_monitor(&mut self, ctx: &egui::Context, inner_size: egui::Vec2) {
-        let monitor = self.monitor_size_points(ctx);
-        let x = (monitor.x - inner_size.x) * 0.5;
-        let y = (monitor.y - inner_size.y) * 0.5;
-        self.send_outer_position(ctx, egui::pos2(x.max(0.0), y));
+    /// Rebuilds the taskbar jump list from the recent files on a worker thread; the shell
+    /// calls can take a moment.
+    fn refresh_jump_list(&self) {
+        #[cfg(target_os = "windows")]
+        {
+            let recent = self.config.state_recent_files.clone();
+            let _ = std::thread::Builder::new()
+                .name("jump-list".to_string())
+                .spawn(move || {
+                    if let Err(err) = windows_env::update_jump_list(&recent) {
+                        tracing::debug!("Jump list update failed: {err}");
+                    }
+                });
+        }
     }
 
-    fn floating_layout_size_for_media_bounds(
-        media_w: f32,
-        media_h: f32,
-        monitor: egui::Vec2,
-    ) -> Option<(f32, egui::Vec2)> {
-        if media_w <= 0.0 || media_h <= 0.0 || monitor.x <= 0.0 || monitor.y <= 0.0 {
-            return None;
+    /// Handles tray clicks and a finished open dialog. Runs before the minimized early
+    /// return, since the tray is how a window hidden to it comes back.
+    fn poll_tray(&mut self, ctx: &egui::Context) {
+        if let Some(rx) = self.pending_open_dialog.as_ref() {
+            match rx.try_recv() {
+                Ok(picked) => {
+                    self.pending_open_dialog = None;
+                    if let Some(path) = picked {
+                        self.open_file_from_tray(ctx, &path);
+                    }
+                }
+                Err(crossbeam_channel::TryRecvError::Empty) => {}
+                Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                    self.pending_open_dialog = None;
+                }
+            }
         }
 
-        let zoom = if media_w > monitor.x || media_h > monitor.y {
-            (monitor.x / media_w).min(monitor.y / media_h).min(1.0)
-        } else {
-            1.0
+        let Some(events) = self
+            .tray
+            .as_ref()
+            .map(|tray| tray.events().try_iter().collect::<Vec<_>>())
+        else {
+            return;
         };
-
-        let size = egui::Vec2::new((media_w * zoom).max(200.0), (media_h * zoom).max(150.0));
-        Some((zoom, size))
+        for event in events {
+            match event {
+                tray::TrayEvent::Activate => self.show_from_tray(ctx),
+                tray::TrayEvent::Menu(Self::TRAY_OPEN_FILE) => self.start_open_file_dialog(ctx),
+                tray::TrayEvent::Menu(Self::TRAY_SLIDESHOW) => self.toggle_slideshow(),
+                tray::TrayEvent::Menu(Self::TRAY_EXIT) => {
+                    self.show_from_tray(ctx);
+                    self.request_app_exit();
+                }
+                tray::TrayEvent::Menu(id) => {
+                    let recent = id
+                        .checked_sub(Self::TRAY_RECENT_FILE)
+                        .and_then(|index| self.config.state_recent_files.get(index as usize))
+                        .cloned();
+                    if let Some(path) = recent {
+                        self.open_file_from_tray(ctx, &path);
+                    }
+                }
+            }
+        }
     }
 
-    fn floating_layout_size_for_media(
-        &self,
-        media_w: f32,
-        media_h: f32,
-        monitor: egui::Vec2,
-    ) -> Option<(f32, egui::Vec2)> {
-        Self::floating_layout_size_for_media_bounds(media_w, media_h, monitor)
+    /// Shows the window again after it was hidden to the tray, and brings it to the front.
+    fn show_from_tray(&mut self, ctx: &egui::Context) {
+        if std::mem::take(&mut self.hidden_in_tray) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        ctx.request_repaint();
     }
 
-    fn prepare_single_instance_media_handoff(&mut self, ctx: &egui::Context) {
-        if self.manga_mode {
-            self.stop_manga_wheel_scroll();
-            self.stop_manga_autoscroll();
-            self.manga_mode = false;
-            set_metadata_cache_enabled(false);
-            self.manga_clear_cache();
-            self.manga_loader = None;
+    fn open_file_from_tray(&mut self, ctx: &egui::Context, path: &Path) {
+        if !path.exists() {
+            self.show_toast(i18n::tr_args(
+                "toast.file_missing",
+                &[("path", &path.display())],
+            ));
+            return;
         }
-
-        self.clear_strip_return_context();
-        self.titlebar_pending_restore_layout = None;
-        self.titlebar_previous_mode = None;
-        self.toggle_fullscreen = false;
-        self.toggle_fullscreen_force_borderless = false;
-        self.toggle_fullscreen_from_titlebar = false;
-        self.pending_fullscreen_layout = false;
-        self.pending_maximized_layout = false;
-        self.pending_window_resize = None;
-        self.image_changed = false;
-        self.pending_media_layout = false;
-        self.saved_fullscreen_entry_index = None;
-        self.fullscreen_view_states.clear();
-        self.current_fullscreen_view_has_memory = false;
-        self.strip_open_force_fit_path = None;
-        self.is_fullscreen = false;
-        self.fullscreen_transition = 0.0;
-        self.fullscreen_transition_target = 0.0;
-        self.force_floating_layout_once = true;
-
-        self.request_native_maximize = if self.current_window_is_maximized(ctx) {
-            Some(false)
-        } else {
-            None
-        };
+        self.open_file_in_window(ctx, path);
     }
 
-    fn current_window_is_maximized(&self, ctx: &egui::Context) -> bool {
-        let viewport_maximized = ctx.input(|i| i.raw.viewport().maximized);
+    /// Brings the window up and opens `path` through the usual background load, which
+    /// reports a missing or unreachable file itself.
+    fn open_file_in_window(&mut self, ctx: &egui::Context, path: &Path) {
+        self.show_from_tray(ctx);
+        self.prepare_single_instance_media_handoff(ctx);
+        self.load_media(&path.to_path_buf());
+        self.remember_recent_file(path);
+    }
 
-        #[cfg(target_os = "windows")]
-        {
-            viewport_maximized
-                .or_else(crate::windows_env::active_window_is_maximized)
-                .unwrap_or(false)
+    /// Runs the commands that arrived on the control socket and answers each client.
+    fn poll_remote_control(&mut self, ctx: &egui::Context) {
+        let Some(requests) = self
+            .remote_control
+            .as_ref()
+            .map(|server| server.requests().try_iter().collect::<Vec<_>>())
+        else {
+            return;
+        };
+        for request in requests {
+            let result = self.run_remote_command(ctx, &request.command);
+            request.answer(result);
+            ctx.request_repaint();
         }
+    }
 
-        #[cfg(not(target_os = "windows"))]
-        {
-            viewport_maximized.unwrap_or(false)
-        }
+    fn run_remote_command(
+        &mut self,
+        ctx: &egui::Context,
+        command: &remote_control::ControlCommand,
+    ) -> Result<String, String> {
+        use remote_control::ControlCommand;
+        match command {
+            // No existence check here: on an offline share it would stall the frame.
+            ControlCommand::Open(path) => self.open_file_in_window(ctx, path),
+            ControlCommand::Next => self.run_action(Action::NextImage),
+            ControlCommand::Previous => self.run_action(Action::PreviousImage),
+            ControlCommand::Seek(seconds) => {
+                let player = self
+                    .video_player
+                    .as_mut()
+                    .filter(|_| self.current_media_type == Some(MediaType::Video))
+                    .ok_or_else(|| "No video is open".to_string())?;
+                player.seek_to_time_with_mode(*seconds, VideoSeekMode::Accurate)?;
+            }
+            ControlCommand::GetState => return Ok(self.remote_control_state()),
+            ControlCommand::Action(name) => {
+                let action =
+                    Action::from_str(name).ok_or_else(|| format!("Unknown action `{name}`"))?;
+                self.run_action(action);
+            }
+        }
+        Ok(remote_control::reply_ok(&[]))
+    }
+
+    fn remote_control_state(&self) -> String {
+        use remote_control::Value;
+        let path = self
+            .current_media_path()
+            .map(|path| path.display().to_string());
+        let media = match self.current_media_type {
+            Some(MediaType::Image) => Value::Str("image"),
+            Some(MediaType::Video) => Value::Str("video"),
+            None => Value::Null,
+        };
+        let mode = if !self.manga_mode {
+            "solo"
+        } else if self.is_masonry_mode() {
+            "masonry"
+        } else {
+            "long_strip"
+        };
+        let player = self
+            .video_player
+            .as_ref()
+            .filter(|_| self.current_media_type == Some(MediaType::Video));
+        let seconds = |time: Option<Duration>| {
+            time.map_or(Value::Null, |time| Value::Number(time.as_secs_f64()))
+        };
+        remote_control::reply_ok(&[
+            ("path", path.as_deref().map_or(Value::Null, Value::Str)),
+            ("index", Value::Number(self.current_index as f64)),
+            ("count", Value::Number(self.image_list.len() as f64)),
+            ("media", media),
+            ("mode", Value::Str(mode)),
+            ("fullscreen", Value::Bool(self.is_fullscreen)),
+            ("zoom", Value::Number(f64::from(self.zoom))),
+            (
+                "position",
+                seconds(player.and_then(|player| player.position())),
+            ),
+            (
+                "duration",
+                seconds(player.and_then(|player| player.duration())),
+            ),
+            (
+                "playing",
+                Value::Bool(player.is_some_and(|player| player.is_playing())),
+            ),
+        ])
     }
 
-    fn use_native_fullscreen_window_transition(&self) -> bool {
+    /// Opens the system file dialog on a worker thread, starting in the current folder.
+    fn start_open_file_dialog(&mut self, ctx: &egui::Context) {
+        if self.pending_open_dialog.is_some() {
+            return;
+        }
         #[cfg(target_os = "windows")]
         {
-            self.config.fullscreen_native_window_transition
+            let folder = self
+                .image_list
+                .get(self.current_index)
+                .and_then(|path| path.parent())
+                .map(Path::to_path_buf);
+            let (tx, rx) = crossbeam_channel::bounded(1);
+            let ctx = ctx.clone();
+            let spawned = std::thread::Builder::new()
+                .name("open-file-dialog".to_string())
+                .spawn(move || {
+                    let mut extensions: Vec<&str> = plugins::registry().extensions().collect();
+                    extensions.sort_unstable();
+                    let _ = tx.send(windows_env::pick_file_to_open(
+                        folder.as_deref(),
+                        &extensions,
+                    ));
+                    ctx.request_repaint();
+                });
+            if spawned.is_ok() {
+                self.pending_open_dialog = Some(rx);
+            }
         }
-
         #[cfg(not(target_os = "windows"))]
-        {
-            false
-        }
+        let _ = ctx;
     }
 
-    fn fullscreen_layout_ready(&self, ctx: &egui::Context) -> bool {
-        let monitor = self.monitor_size_points(ctx);
-        let viewport = ctx.screen_rect().size();
-
-        (viewport.x - monitor.x).abs() <= 2.0 && (viewport.y - monitor.y).abs() <= 2.0
+    /// Mirrors video playback on the taskbar button and runs its thumbnail buttons.
+    fn update_taskbar(&mut self) {
+        let video = self.current_media_type == Some(MediaType::Video) && !self.manga_mode;
+        if self.taskbar.is_none() && video {
+            let repaint_target = self.repaint_target.clone();
+            self.taskbar =
+                taskbar::TaskbarControls::attach(move || repaint_target.request_repaint());
+        }
+        let Some(taskbar) = self.taskbar.as_mut() else {
+            return;
+        };
+        let progress = match self.video_player.as_ref() {
+            Some(player) if video && player.is_playing() => {
+                taskbar::TaskbarProgress::Playing(player.position_fraction())
+            }
+            Some(player) if video => taskbar::TaskbarProgress::Paused(player.position_fraction()),
+            _ => taskbar::TaskbarProgress::Hidden,
+        };
+        taskbar.show(progress);
+        let clicks: Vec<_> = taskbar.events().try_iter().collect();
+        for click in clicks {
+            self.run_action(match click {
+                taskbar::TaskbarButton::Previous => Action::PreviousImage,
+                taskbar::TaskbarButton::PlayPause => Action::VideoPlayPause,
+                taskbar::TaskbarButton::Next => Action::NextImage,
+            });
+        }
     }
 
-    fn strip_return_mode_for_fullscreen_toggle(&self) -> Option<MangaLayoutMode> {
-        if self.strip_return_button_only {
-            None
-        } else {
-            self.strip_return_mode
+    /// Keeps the shell's media session on the current video and runs media key presses.
+    fn update_media_controls(&mut self) {
+        let video = self.current_media_type == Some(MediaType::Video) && !self.manga_mode;
+        if self.media_controls.is_none() && video {
+            let repaint_target = self.repaint_target.clone();
+            self.media_controls =
+                media_controls::MediaControls::attach(move || repaint_target.request_repaint());
+        }
+        let Some(controls) = self.media_controls.as_mut() else {
+            return;
+        };
+        let now_playing = match (
+            self.video_player.as_ref(),
+            self.image_list.get(self.current_index),
+        ) {
+            (Some(player), Some(path)) if video => Some(media_controls::NowPlaying::new(
+                path.clone(),
+                player.tag_title(),
+                player.tag_artist(),
+                player.is_playing(),
+            )),
+            _ => None,
+        };
+        controls.update(now_playing);
+        let commands: Vec<_> = controls.events().try_iter().collect();
+        for command in commands {
+            let playing = self
+                .video_player
+                .as_ref()
+                .is_some_and(|player| player.is_playing());
+            match command {
+                media_controls::MediaCommand::Play if !playing => {
+                    self.run_action(Action::VideoPlayPause)
+                }
+                media_controls::MediaCommand::Pause if playing => {
+                    self.run_action(Action::VideoPlayPause)
+                }
+                media_controls::MediaCommand::Play | media_controls::MediaCommand::Pause => {}
+                media_controls::MediaCommand::Next => self.run_action(Action::NextImage),
+                media_controls::MediaCommand::Previous => self.run_action(Action::PreviousImage),
+            }
         }
     }
 
-    fn sync_current_index_to_visible_media(&mut self) {
-        let visible_path = if matches!(self.current_media_type, Some(MediaType::Video)) {
-            self.current_video_path
-                .as_ref()
-                .or(self.pending_file_size_probe_path.as_ref())
-                .or(self.current_file_size_label_path.as_ref())
-        } else {
-            self.image
-                .as_ref()
-                .map(|img| &img.path)
-                .or(self.pending_file_size_probe_path.as_ref())
-                .or(self.current_file_size_label_path.as_ref())
+    /// Applies the measured gain to the current video once its analysis is done.
+    fn apply_loudness_normalization(&mut self, ctx: &egui::Context) {
+        if !self.config.loudness_normalization {
+            return;
+        }
+        let Some(player) = self.video_player.as_mut() else {
+            return;
         };
-
-        let Some(visible_path) = visible_path else {
+        if player.normalization_gain().is_some() {
+            return;
+        }
+        let Some(path) = self.current_video_path.as_ref() else {
             return;
         };
-
-        if let Some(index) = self
-            .image_list
-            .iter()
-            .position(|candidate| candidate == visible_path)
+        let egui_ctx = ctx.clone();
+        if let Some(loudness) =
+            loudness::lookup_or_analyze(path, move || egui_ctx.request_repaint())
         {
-            self.set_current_index_clamped(index);
+            let gain = loudness.map_or(1.0, |loudness| {
+                loudness.gain_for_target(self.config.loudness_target_lufs)
+            });
+            player.set_normalization_gain(gain);
         }
     }
 
-    fn current_titlebar_return_mode(&self) -> TitlebarToggleReturnMode {
-        if self.manga_mode {
-            TitlebarToggleReturnMode::Manga(self.manga_layout_mode)
-        } else if let Some(layout_mode) = self.strip_return_mode_for_fullscreen_toggle() {
-            TitlebarToggleReturnMode::Manga(layout_mode)
+    fn toggle_slideshow(&mut self) {
+        if self.slideshow.take().is_some() {
+            self.show_toast(i18n::tr("toast.slideshow_stopped"));
         } else {
-            TitlebarToggleReturnMode::Fullscreen
+            self.slideshow = Some((self.current_index, Instant::now()));
+            self.show_toast(i18n::tr_args(
+                "toast.slideshow_started",
+                &[("seconds", &self.config.slideshow_interval_seconds)],
+            ));
         }
+        self.refresh_tray_menu();
     }
 
-    fn request_titlebar_fullscreen_reentry(
-        &mut self,
-        return_mode: Option<TitlebarToggleReturnMode>,
-    ) {
-        self.sync_current_index_to_visible_media();
-        self.toggle_fullscreen_from_titlebar = true;
-        if self.config.maximize_to_borderless_fullscreen {
-            self.toggle_fullscreen_force_borderless = true;
+    /// Goes to the next file once the current one has been shown for the slideshow interval.
+    /// Videos play to their end instead, or for the interval when they loop.
+    fn advance_slideshow(&mut self, ctx: &egui::Context) {
+        let Some((index, shown_at)) = self.slideshow else {
+            return;
+        };
+        let interval = Duration::from_secs_f32(self.config.slideshow_interval_seconds);
+        if index != self.current_index {
+            // Moved by hand: the new file gets a full interval.
+            self.slideshow = Some((self.current_index, Instant::now()));
+            ctx.request_repaint_after(interval);
+            return;
         }
-        self.titlebar_pending_restore_layout = match return_mode {
-            Some(TitlebarToggleReturnMode::Manga(layout_mode)) => Some(layout_mode),
-            _ => None,
+        let elapsed = shown_at.elapsed();
+        let due = if self.current_media_type == Some(MediaType::Video) {
+            let looping = self.config.video_loop;
+            self.video_player
+                .as_mut()
+                .map_or(elapsed >= interval, |player| {
+                    player.is_eos() || player.has_ended() || (looping && elapsed >= interval)
+                })
+        } else {
+            elapsed >= interval
         };
-        self.titlebar_previous_mode = None;
-        self.toggle_fullscreen = true;
+        if due {
+            self.next_image();
+            self.slideshow = Some((self.current_index, Instant::now()));
+            ctx.request_repaint_after(interval);
+        } else {
+            ctx.request_repaint_after(
+                interval
+                    .saturating_sub(elapsed)
+                    .max(Duration::from_millis(100)),
+            );
+        }
     }
 
-    fn request_goto_file_fullscreen_toggle(&mut self) {
-        self.stop_manga_autoscroll();
-
-        if self.is_fullscreen
-            && !self.manga_mode
-            && self.strip_return_mode_for_fullscreen_toggle().is_some()
-        {
-            self.return_to_strip_mode_from_fullscreen_toggle();
+    /// Re-sends the fullscreen placement after a monitor change until the window matches it.
+    /// The first attempt is converted with the old monitor's DPI for the size; once the
+    /// window has moved, [`egui::Context::pixels_per_point`] reports the new one.
+    fn settle_fullscreen_monitor_move(&mut self, ctx: &egui::Context) {
+        let Some(mut pending) = self.fullscreen_monitor_move.take() else {
             return;
-        }
-
+        };
         if !self.is_fullscreen {
-            if let Some(previous_mode) = self.titlebar_previous_mode.take() {
-                self.request_titlebar_fullscreen_reentry(Some(previous_mode));
-                return;
-            }
-
-            if self.config.maximize_to_borderless_fullscreen {
-                self.toggle_fullscreen_force_borderless = true;
-            }
+            return;
         }
-
-        self.toggle_fullscreen = true;
+        if pending.remaximize {
+            self.request_native_maximize = Some(true);
+            return;
+        }
+        let (position, size) = pending
+            .monitor
+            .placement_points(ctx.pixels_per_point(), ctx.zoom_factor());
+        let placed = ctx.input(|i| i.viewport().outer_rect).is_some_and(|rect| {
+            (rect.min - position).length() < 1.0 && (rect.size() - size).length() < 1.0
+        });
+        if placed || pending.frames_left == 0 {
+            return;
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(position));
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(size));
+        pending.frames_left -= 1;
+        self.fullscreen_monitor_move = Some(pending);
+        ctx.request_repaint_after(Duration::from_millis(16));
     }
 
     fn request_shortcut_fullscreen_toggle(&mut self) {
@@ -15348,6 +23108,14 @@ impl ImageViewer {
     fn apply_floating_layout_for_current_image(&mut self, ctx: &egui::Context) {
         self.offset = egui::Vec2::ZERO;
 
+        if self.floating_size_pinned {
+            let bounds = ctx
+                .input(|i| i.raw.viewport().inner_rect)
+                .map_or_else(|| ctx.screen_rect().size(), |rect| rect.size());
+            self.fit_floating_zoom_to(bounds);
+            return;
+        }
+
         let Some((media_w_u, media_h_u)) = self.media_display_dimensions() else {
             return;
         };
@@ -15421,10 +23189,8 @@ impl ImageViewer {
                         monitor.y.max(viewport_bounds.y),
                     )
                 };
-                let z = self.fit_zoom_for_target_bounds(
-                    target_bounds,
-                    egui::vec2(img_w as f32, img_h as f32),
-                );
+                let z =
+                    self.fullscreen_fit_zoom(target_bounds, egui::vec2(img_w as f32, img_h as f32));
                 self.zoom = z;
                 self.zoom_target = z;
                 if force_fit {
@@ -15549,6 +23315,73 @@ impl ImageViewer {
         egui::Color32::from_rgb(r, g, b)
     }
 
+    /// Whether the current image has transparent pixels; scanned once per image.
+    fn current_image_has_transparency(&mut self) -> bool {
+        let Some(img) = self.image.as_ref() else {
+            return false;
+        };
+        match &self.current_image_transparency {
+            Some((path, has_transparency)) if *path == img.path => *has_transparency,
+            _ => {
+                let has_transparency = img.current_frame_data().has_transparency();
+                self.current_image_transparency = Some((img.path.clone(), has_transparency));
+                has_transparency
+            }
+        }
+    }
+
+    /// Saves a transparency choice changed from an export toolbar or dialog.
+    fn save_export_alpha_choice(&mut self) {
+        if std::mem::take(&mut self.export_alpha_unsaved) {
+            self.config.save();
+        }
+    }
+
+    /// Color PNG exports are flattened onto, or `None` to keep alpha.
+    fn export_background(&mut self) -> Option<[u8; 3]> {
+        self.save_export_alpha_choice();
+        self.config.export_flatten_background()
+    }
+
+    /// The checkerboard tile to paint under the current image, if the checkerboard is enabled
+    /// and the image has transparent pixels.
+    fn checkerboard_texture_for_current_image(
+        &mut self,
+        ctx: &egui::Context,
+    ) -> Option<egui::TextureId> {
+        if !self.config.checkerboard_background
+            || self.manga_mode
+            || self.video_texture.is_some()
+            || self.retained_media_placeholder_visible
+        {
+            return None;
+        }
+
+        if !self.current_image_has_transparency() {
+            return None;
+        }
+
+        let colors = [
+            self.config.checkerboard_light_rgb,
+            self.config.checkerboard_dark_rgb,
+        ];
+        match &self.checkerboard_texture {
+            Some((texture, built_from)) if *built_from == colors => Some(texture.id()),
+            _ => {
+                let [light, dark] = colors.map(|[r, g, b]| egui::Color32::from_rgb(r, g, b));
+                let tile = egui::ColorImage {
+                    size: [2, 2],
+                    pixels: vec![light, dark, dark, light],
+                };
+                let texture =
+                    ctx.load_texture("checkerboard", tile, egui::TextureOptions::NEAREST_REPEAT);
+                let id = texture.id();
+                self.checkerboard_texture = Some((texture, colors));
+                Some(id)
+            }
+        }
+    }
+
     fn background_clear_color(&self) -> [f32; 4] {
         let [r, g, b] = self.config.background_rgb;
         [r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, 1.0]
@@ -15558,20 +23391,33 @@ impl ImageViewer {
     fn zoom_at(&mut self, center: egui::Pos2, factor: f32, available_rect: egui::Rect) {
         let old_zoom = self.zoom;
         self.zoom = self.clamp_zoom(self.zoom * factor);
+        self.apply_zoom_anchor(center, old_zoom, available_rect);
+        self.maybe_refresh_current_solo_image_lod();
+    }
 
-        // In fullscreen we allow panning and cursor-follow zoom.
-        // In floating mode we keep the image centered and let the window autosize instead.
+    /// Re-anchor the pan offset after a zoom change from `old_zoom`, keeping either the
+    /// pointer or the view center fixed as `zoom_anchor` decides.
+    fn apply_zoom_anchor(
+        &mut self,
+        pointer: egui::Pos2,
+        old_zoom: f32,
+        available_rect: egui::Rect,
+    ) {
+        let panned = self.offset.length() > 0.1;
+        let follow_cursor =
+            self.config
+                .zoom_anchor
+                .follows_cursor(self.is_fullscreen, old_zoom, self.zoom, panned);
+        let anchor_offset = if follow_cursor {
+            pointer - available_rect.center()
+        } else {
+            egui::Vec2::ZERO
+        };
+        let zoom_ratio = self.zoom / old_zoom;
+        self.offset = self.offset * zoom_ratio - anchor_offset * (zoom_ratio - 1.0);
         if self.is_fullscreen {
-            let rect_center = available_rect.center();
-            let cursor_offset = center - rect_center;
-
-            let zoom_ratio = self.zoom / old_zoom;
-            self.offset = self.offset * zoom_ratio - cursor_offset * (zoom_ratio - 1.0);
             self.remember_current_fullscreen_view_state();
-        } else {
-            self.offset = egui::Vec2::ZERO;
         }
-        self.maybe_refresh_current_solo_image_lod();
     }
 
     // ============ MANGA READING MODE METHODS ============
@@ -16980,6 +24826,8 @@ impl ImageViewer {
             && self.manga_layout_offsets.len() == len.saturating_add(1)
             && self.manga_total_height_cache_len == len
             && (self.manga_total_height_cache_screen_y - screen_y).abs() <= 1e-6
+            && self.manga_page_width_zoom.is_none()
+            && self.manga_total_height_cache_page_width == 0.0
             && zoom.is_finite()
             && cached_zoom.is_finite()
             && (zoom - cached_zoom).abs() > 1e-6;
@@ -17811,7 +25659,8 @@ impl ImageViewer {
                     | (MediaType::Video, MangaMediaType::Video)
             );
 
-            if compatible && w > 0 && h > 0 {
+            // A segmented page's handle only covers its top segment.
+            if compatible && w > 0 && h > 0 && !self.manga_texture_cache.is_segmented(index) {
                 let dims = self.manga_item_source_dimensions(index).unwrap_or((w, h));
                 self.pending_mode_switch_placeholder = Some(ModeSwitchPlaceholder {
                     texture,
@@ -17968,6 +25817,7 @@ impl ImageViewer {
     fn clear_manga_runtime_cache(&mut self, preserve_dimensions: bool) {
         // Clear the texture cache
         self.manga_texture_cache.clear();
+        self.manga_panel_breaks.clear();
         self.masonry_runtime_cache_signature = 0;
         self.clear_masonry_authoritative_dimension_lock();
         self.strip_entry_placeholder_index = None;
@@ -18689,6 +26539,7 @@ impl ImageViewer {
             let mut focused_position_to_record = None;
 
             if let Some(player) = self.manga_video_players.get_mut(&focused_idx) {
+                player.set_deinterlace(self.config.video_deinterlace);
                 // Update duration cache
                 player.update_duration();
 
@@ -18706,7 +26557,7 @@ impl ImageViewer {
                     // Update layout dimensions from source/original video size (not decoded frame size).
                     // Decoded frames may be output-bounded/downscaled, which would otherwise shrink
                     // strip layout dimensions and cause cascading reflow/squash at low zoom.
-                    let (source_w, source_h) = player.dimensions();
+                    let (source_w, source_h) = player.display_dimensions();
                     let (layout_w, layout_h) = if source_w > 0 && source_h > 0 {
                         (source_w, source_h)
                     } else {
@@ -18761,7 +26612,7 @@ impl ImageViewer {
                     if let Some((texture, stored_w, stored_h)) =
                         self.manga_video_textures.get_mut(&focused_idx)
                     {
-                        texture.set(color_image, texture_options);
+                        upload_video_frame(texture, color_image, texture_options);
                         *stored_w = w;
                         *stored_h = h;
                     } else {
@@ -19607,13 +27458,33 @@ impl ImageViewer {
             })
     }
 
+    /// Page width in pixels while long-strip page-width zoom is active.
+    fn manga_page_width_zoom_px(&self) -> Option<f32> {
+        if self.is_masonry_mode() {
+            return None;
+        }
+
+        self.manga_page_width_zoom
+            .map(|fraction| (self.screen_size.x * fraction).max(1.0))
+    }
+
     /// Get the display height of an image at a given index (scaled to fit screen height)
     fn manga_get_image_display_height(&self, index: usize) -> f32 {
+        if let Some(size) = self.manga_page_fit_override_size(index) {
+            return size.y;
+        }
+
+        if let Some(page_width) = self.manga_page_width_zoom_px() {
+            return match self.manga_get_page_layout_dimensions(index) {
+                Some((img_w, img_h)) if img_w > 0.0 && img_h > 0.0 => page_width * img_h / img_w,
+                // Same 2:3 manga estimate as the width fallback below.
+                _ => page_width / 0.67,
+            };
+        }
+
         // Prefer metadata dimensions for layout stability; when they are temporarily
         // unavailable, fall back to cached texture dimensions so visible pages never stretch.
-        let img_h = self
-            .manga_get_image_source_dimensions(index)
-            .map(|(_, h)| h);
+        let img_h = self.manga_get_page_layout_dimensions(index).map(|(_, h)| h);
 
         if let Some(img_h) = img_h {
             if img_h > 0.0 {
@@ -19633,7 +27504,15 @@ impl ImageViewer {
 
     /// Get the display width of an image at a given index (scaled to fit screen height)
     fn manga_get_image_display_width(&self, index: usize) -> f32 {
-        let dims = self.manga_get_image_source_dimensions(index);
+        if let Some(size) = self.manga_page_fit_override_size(index) {
+            return size.x;
+        }
+
+        if let Some(page_width) = self.manga_page_width_zoom_px() {
+            return page_width;
+        }
+
+        let dims = self.manga_get_page_layout_dimensions(index);
 
         if let Some((img_w, img_h)) = dims {
             if img_h > 0.0 {
@@ -19768,12 +27647,16 @@ impl ImageViewer {
             self.manga_compute_upload_batch_limit(pending_loads, decoded_backlog_total);
         self.manga_upload_batch_limit = upload_batch_limit;
 
-        let (new_decoded_images, mut layout_dim_updates) = {
+        let segment_tall_pages = self.config.manga_segment_tall_pages && !self.is_masonry_mode();
+        let (mut new_decoded_images, mut layout_dim_updates) = {
             let Some(loader) = self.manga_loader.as_mut() else {
                 return false;
             };
 
             // Poll for decoded images from the background threads
+            loader.set_panel_detection(self.config.manga_panel_snap);
+            loader.set_tall_page_segmentation(segment_tall_pages);
+
             let mailbox_headroom = Self::MANGA_DECODED_MAILBOX_MAX_ITEMS
                 .saturating_sub(self.manga_decoded_mailbox.len());
             let (decoded_images, decoded_dim_updates) = if mailbox_headroom == 0 {
@@ -19827,6 +27710,14 @@ impl ImageViewer {
 
         let mailbox_received = !new_decoded_images.is_empty();
         if mailbox_received {
+            for decoded in new_decoded_images.iter_mut() {
+                if !decoded.panel_breaks.is_empty() {
+                    self.manga_panel_breaks.insert(
+                        decoded.path.clone(),
+                        std::mem::take(&mut decoded.panel_breaks),
+                    );
+                }
+            }
             self.manga_decoded_mailbox.extend(new_decoded_images);
         }
 
@@ -19920,12 +27811,6 @@ impl ImageViewer {
                 continue;
             }
 
-            // Create the texture
-            let color_image = egui::ColorImage::from_rgba_unmultiplied(
-                [decoded.width as usize, decoded.height as usize],
-                &decoded.pixels,
-            );
-
             // Static images + video thumbnails can use mipmaps for faster minification
             // in manga strip and masonry layouts.
             let texture_options = self.manga_texture_options_for_cached_upload(
@@ -19935,24 +27820,62 @@ impl ImageViewer {
                 decoded.height,
             );
 
+            let max_side = self.max_texture_side.max(1);
             let upload_texture_started = Instant::now();
-            let texture = ctx.load_texture(
-                format!("manga_{}", decoded.index),
-                color_image,
-                texture_options,
-            );
-            self.perf_metrics
-                .record_duration("manga_upload_texture_ms", upload_texture_started.elapsed());
+            let evicted = if decoded.height > max_side && decoded.width <= max_side {
+                // Tall page decoded at full resolution: upload stacked segments that each
+                // fit the texture limit.
+                let row_bytes = decoded.width as usize * 4;
+                let segments = decoded
+                    .pixels
+                    .chunks(row_bytes * max_side as usize)
+                    .enumerate()
+                    .map(|(segment, rows)| {
+                        let color_image = egui::ColorImage::from_rgba_unmultiplied(
+                            [decoded.width as usize, rows.len() / row_bytes],
+                            rows,
+                        );
+                        ctx.load_texture(
+                            format!("manga_{}_{}", decoded.index, segment),
+                            color_image,
+                            texture_options,
+                        )
+                    })
+                    .collect();
+                self.perf_metrics
+                    .record_duration("manga_upload_texture_ms", upload_texture_started.elapsed());
 
-            // Insert into cache with media type (this may evict old entries)
-            let evicted = self.manga_texture_cache.insert_with_type(
-                decoded.index,
-                decoded.path.clone(),
-                texture,
-                decoded.width,
-                decoded.height,
-                decoded.media_type,
-            );
+                self.manga_texture_cache.insert_segmented(
+                    decoded.index,
+                    decoded.path.clone(),
+                    segments,
+                    max_side,
+                    decoded.width,
+                    decoded.height,
+                )
+            } else {
+                let color_image = egui::ColorImage::from_rgba_unmultiplied(
+                    [decoded.width as usize, decoded.height as usize],
+                    &decoded.pixels,
+                );
+                let texture = ctx.load_texture(
+                    format!("manga_{}", decoded.index),
+                    color_image,
+                    texture_options,
+                );
+                self.perf_metrics
+                    .record_duration("manga_upload_texture_ms", upload_texture_started.elapsed());
+
+                // Insert into cache with media type (this may evict old entries)
+                self.manga_texture_cache.insert_with_type(
+                    decoded.index,
+                    decoded.path.clone(),
+                    texture,
+                    decoded.width,
+                    decoded.height,
+                    decoded.media_type,
+                )
+            };
             uploaded_textures = uploaded_textures.saturating_add(1);
 
             if let Some(started_at) = self.manga_ttv_pending.remove(&decoded.index) {
@@ -20003,11 +27926,13 @@ impl ImageViewer {
         // every call and make wheel scrolling feel laggy.
         let zoom = (self.zoom * 10_000.0).round() / 10_000.0;
         let screen_y = self.screen_size.y.round();
+        let page_width = self.manga_page_width_zoom_px().map_or(0.0, f32::round);
         let len = self.image_list.len();
 
         let needs_recompute = !self.manga_total_height_cache_valid
             || (self.manga_total_height_cache_zoom - zoom).abs() > 1e-6
             || (self.manga_total_height_cache_screen_y - screen_y).abs() > 1e-6
+            || (self.manga_total_height_cache_page_width - page_width).abs() > 1e-6
             || self.manga_total_height_cache_len != len;
 
         if needs_recompute {
@@ -20024,6 +27949,7 @@ impl ImageViewer {
             self.manga_total_height_cache = total;
             self.manga_total_height_cache_zoom = zoom;
             self.manga_total_height_cache_screen_y = screen_y;
+            self.manga_total_height_cache_page_width = page_width;
             self.manga_total_height_cache_len = len;
             self.manga_total_height_cache_valid = true;
         }
@@ -20210,6 +28136,10 @@ impl ImageViewer {
 
     /// Scroll up by one page (screen height) in manga mode
     fn manga_page_up(&mut self) {
+        if self.manga_panel_snap_step(false) {
+            return;
+        }
+
         // Keep PageUp behavior to exactly one previous file while avoiding
         // instantaneous strip snaps that can show a transient black frame.
         self.manga_page_up_smooth_continuous();
@@ -20283,17 +28213,94 @@ impl ImageViewer {
                     force_triangle_filters,
                 );
             }
-        }
+        }
+
+        // Still run the standard queue update (throttled) for eviction bookkeeping.
+        self.manga_update_preload_queue();
+    }
+
+    /// Scroll down by one page (screen height) in manga mode
+    fn manga_page_down(&mut self) {
+        if self.manga_panel_snap_step(true) {
+            return;
+        }
+
+        // Keep PageDown behavior to exactly one next file while avoiding
+        // instantaneous strip snaps that can show a transient black frame.
+        self.manga_page_down_smooth_continuous();
+    }
+
+    /// Panel snap (`manga_panel_snap`): inside a page taller than the viewport, scroll so the
+    /// next/previous detected panel gutter sits at the viewport top. Without a gutter in
+    /// reach the view moves by most of a screen, so no content is skipped.
+    ///
+    /// Returns false once the page edge is on screen; the caller then changes files.
+    fn manga_panel_snap_step(&mut self, forward: bool) -> bool {
+        if !self.config.manga_panel_snap
+            || !self.manga_mode
+            || self.is_masonry_mode()
+            || self.image_list.is_empty()
+        {
+            return false;
+        }
+
+        // Base on the scroll destination so repeated presses keep stepping mid-animation.
+        let viewport_h = self.screen_size.y.max(1.0);
+        let viewport_top = self.manga_scroll_target.max(0.0);
+        let index = self.manga_index_at_y(viewport_top);
+        let page_start = self.manga_page_start_y(index);
+        let page_height = self.manga_page_height_cached(index);
+        let page_end = page_start + page_height;
+        if page_height <= viewport_h + 1.0 {
+            return false;
+        }
+
+        // Ignore gutters right at the viewport top so a press always makes progress.
+        let min_step = viewport_h * 0.15;
+        let breaks: Vec<f32> = if self.manga_page_override(index).quarter_turns == 0 {
+            self.image_list
+                .get(index)
+                .and_then(|path| self.manga_panel_breaks.get(path))
+                .map(|fractions| {
+                    fractions
+                        .iter()
+                        .map(|fraction| page_start + fraction * page_height)
+                        .collect()
+                })
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        let target = if forward {
+            if viewport_top + viewport_h >= page_end - 1.0 {
+                return false;
+            }
+            breaks
+                .iter()
+                .copied()
+                .find(|&y| y > viewport_top + min_step && y <= viewport_top + viewport_h)
+                .unwrap_or(viewport_top + viewport_h * 0.9)
+                .min(page_end - viewport_h)
+        } else {
+            if viewport_top <= page_start + 1.0 {
+                return false;
+            }
+            breaks
+                .iter()
+                .rev()
+                .copied()
+                .find(|&y| y < viewport_top - min_step && y >= viewport_top - viewport_h)
+                .unwrap_or(viewport_top - viewport_h * 0.9)
+                .max(page_start)
+        };
 
-        // Still run the standard queue update (throttled) for eviction bookkeeping.
+        self.stop_manga_wheel_scroll();
+        let max_scroll = (self.manga_total_height() - viewport_h).max(0.0);
+        self.manga_scroll_target = target.clamp(0.0, max_scroll);
+        self.manga_scroll_velocity = 0.0;
         self.manga_update_preload_queue();
-    }
-
-    /// Scroll down by one page (screen height) in manga mode
-    fn manga_page_down(&mut self) {
-        // Keep PageDown behavior to exactly one next file while avoiding
-        // instantaneous strip snaps that can show a transient black frame.
-        self.manga_page_down_smooth_continuous();
+        true
     }
 
     /// PageDown-style navigation, but with smooth inertial motion (no instant snap).
@@ -20707,7 +28714,7 @@ impl ImageViewer {
 
         // If video controls are visible, lift the manga button above them.
         let video_controls_offset = if self.show_video_controls {
-            56.0 + 8.0
+            self.overlay_theme.video_bar_height + 8.0
         } else {
             0.0
         };
@@ -20848,7 +28855,7 @@ impl ImageViewer {
 
         // If video controls are visible, lift the zoom HUD above them.
         let video_controls_offset = if self.show_video_controls {
-            56.0 + 8.0
+            self.overlay_theme.video_bar_height + 8.0
         } else {
             0.0
         };
@@ -20882,7 +28889,15 @@ impl ImageViewer {
             };
             let new_zoom = self.clamp_zoom(self.zoom * factor);
 
-            if (new_zoom - old_zoom).abs() > 0.0001 {
+            if let Some(fraction) = self
+                .manga_page_width_zoom
+                .filter(|_| self.manga_mode && self.manga_page_width_zoom_px().is_some())
+            {
+                let center_pos = egui::pos2(self.screen_size.x * 0.5, self.screen_size.y * 0.5);
+                if self.apply_strip_page_width_at_screen_pos(fraction * factor, center_pos) {
+                    self.manga_finish_direct_zoom_change();
+                }
+            } else if (new_zoom - old_zoom).abs() > 0.0001 {
                 let zoom_ratio = new_zoom / old_zoom;
 
                 if self.manga_mode {
@@ -21052,8 +29067,17 @@ impl ImageViewer {
                     (Self::MANGA_HUD_PANEL_HEIGHT - Self::MANGA_HUD_PANEL_INNER_HEIGHT) * 0.5,
                 ));
                 ui.allocate_new_ui(egui::UiBuilder::new().max_rect(inner_rect), |ui| {
-                    let display_zoom = self.zoom;
-                    let max_zoom = self.max_zoom_factor();
+                    // Page-width zoom reuses the HUD: the slider and label show the
+                    // strip column width (percent of the window) instead of image zoom.
+                    let page_width_zoom = self
+                        .manga_page_width_zoom
+                        .filter(|_| self.manga_mode && self.manga_page_width_zoom_px().is_some());
+                    let display_zoom = page_width_zoom.unwrap_or(self.zoom);
+                    let (min_zoom, max_zoom) = if page_width_zoom.is_some() {
+                        self.manga_page_width_zoom_bounds()
+                    } else {
+                        (0.1, self.max_zoom_factor())
+                    };
 
                     ui.spacing_mut().item_spacing.x = 4.0;
                     ui.spacing_mut().slider_width = 100.0;
@@ -21094,12 +29118,24 @@ impl ImageViewer {
                         }
 
                         let mut slider_value = display_zoom;
-                        let slider = egui::Slider::new(&mut slider_value, 0.1..=max_zoom)
+                        let slider = egui::Slider::new(&mut slider_value, min_zoom..=max_zoom)
                             .show_value(false)
                             .clamping(egui::SliderClamping::Always);
                         let slider_resp = ui.add_sized([100.0, 24.0], slider);
 
-                        if slider_resp.changed() && slider_resp.dragged() {
+                        if slider_resp.changed()
+                            && slider_resp.dragged()
+                            && page_width_zoom.is_some()
+                        {
+                            let center_pos =
+                                egui::pos2(self.screen_size.x * 0.5, self.screen_size.y * 0.5);
+                            if self.apply_strip_page_width_at_screen_pos(slider_value, center_pos) {
+                                self.manga_finish_direct_zoom_change();
+                            }
+
+                            self.manga_zoom_plus_held = false;
+                            self.manga_zoom_minus_held = false;
+                        } else if slider_resp.changed() && slider_resp.dragged() {
                             let old_zoom = self.zoom.max(0.0001);
                             let new_zoom = self.clamp_zoom(slider_value);
 
@@ -21192,6 +29228,14 @@ impl ImageViewer {
 
     /// Apply a single zoom step for manga mode (used for initial click)
     fn apply_manga_zoom_step(&mut self, zoom_in: bool) {
+        if self.manga_page_width_zoom_px().is_some() {
+            let center_pos = egui::pos2(self.screen_size.x * 0.5, self.screen_size.y * 0.5);
+            if self.step_manga_page_width_zoom(zoom_in, center_pos) {
+                self.manga_finish_direct_zoom_change();
+            }
+            return;
+        }
+
         let step = self.config.zoom_step;
         let old_zoom = self.zoom.max(0.0001);
         let new_zoom = if zoom_in {
@@ -21281,6 +29325,301 @@ impl ImageViewer {
         true
     }
 
+    fn manga_page_width_zoom_bounds(&self) -> (f32, f32) {
+        let min = self.config.manga_page_width_min_percent / 100.0;
+        let max = self.config.manga_page_width_max_percent / 100.0;
+        (min, max.max(min))
+    }
+
+    fn clamp_manga_page_width_zoom(&self, fraction: f32) -> f32 {
+        let (min, max) = self.manga_page_width_zoom_bounds();
+        fraction.clamp(min, max)
+    }
+
+    /// Toggle long-strip page-width zoom, keeping the page at the viewport center in place.
+    fn toggle_manga_page_width_zoom(&mut self) {
+        if !self.manga_mode || !self.is_fullscreen || self.is_masonry_mode() {
+            return;
+        }
+
+        self.stop_manga_wheel_scroll();
+        let anchor = self.manga_capture_anchor_at_screen_y(self.screen_size.y * 0.5);
+
+        self.manga_page_width_zoom = match self.manga_page_width_zoom {
+            Some(_) => None,
+            None => Some(self.clamp_manga_page_width_zoom(1.0)),
+        };
+        self.offset.x = 0.0;
+        self.manga_shift_wheel_pan_velocity_x = 0.0;
+        self.invalidate_manga_layout_cache();
+
+        if let Some(a) = anchor {
+            self.manga_apply_anchor_at_screen_y(a);
+        }
+
+        self.queue_manga_page_width_zoom_store();
+        self.manga_finish_direct_zoom_change();
+        self.manga_update_preload_queue();
+    }
+
+    /// Step the page-width zoom by `manga_page_width_step_percent` around a screen anchor.
+    fn step_manga_page_width_zoom(&mut self, widen: bool, anchor_screen: egui::Pos2) -> bool {
+        let Some(fraction) = self.manga_page_width_zoom else {
+            return false;
+        };
+
+        let step = self.config.manga_page_width_step_percent / 100.0;
+        let target = if widen {
+            fraction + step
+        } else {
+            fraction - step
+        };
+        self.apply_strip_page_width_at_screen_pos(target, anchor_screen)
+    }
+
+    /// Reflow the long strip for a new page width (fraction of the viewport width).
+    /// The page under `anchor_screen` keeps the same content point under the anchor.
+    fn apply_strip_page_width_at_screen_pos(
+        &mut self,
+        new_fraction: f32,
+        anchor_screen: egui::Pos2,
+    ) -> bool {
+        if !self.manga_mode || self.is_masonry_mode() {
+            return false;
+        }
+
+        let Some(old_fraction) = self.manga_page_width_zoom else {
+            return false;
+        };
+        let new_fraction = self.clamp_manga_page_width_zoom(new_fraction);
+        if (new_fraction - old_fraction).abs() <= 0.0001 {
+            return false;
+        }
+
+        let anchor_screen_y = anchor_screen.y.clamp(0.0, self.screen_size.y.max(1.0));
+        let anchor = self.manga_capture_anchor_at_screen_y(anchor_screen_y);
+
+        let old_width = (self.screen_size.x * old_fraction).max(0.0001);
+        let old_left = (self.screen_size.x - old_width) * 0.5 + self.offset.x;
+        let fraction_x = ((anchor_screen.x - old_left) / old_width).clamp(0.0, 1.0);
+
+        self.manga_page_width_zoom = Some(new_fraction);
+        self.invalidate_manga_layout_cache();
+
+        let new_width = (self.screen_size.x * new_fraction).max(0.0001);
+        let centered_left = (self.screen_size.x - new_width) * 0.5;
+        self.offset.x = anchor_screen.x - fraction_x * new_width - centered_left;
+
+        if let Some(a) = anchor {
+            self.manga_apply_anchor_at_screen_y(a);
+        }
+
+        self.queue_manga_page_width_zoom_store();
+        true
+    }
+
+    fn queue_manga_page_width_zoom_store(&mut self) {
+        let Some(directory) = self
+            .current_media_path()
+            .and_then(|path| path.parent().map(Path::to_path_buf))
+        else {
+            return;
+        };
+
+        self.pending_manga_page_width_zoom_store = Some((directory, self.manga_page_width_zoom));
+    }
+
+    fn flush_pending_manga_folder_view_state_store(&mut self) {
+        if let Some((directory, page_width)) = self.pending_manga_page_width_zoom_store.take() {
+            store_manga_page_width_zoom(directory.as_path(), page_width);
+        }
+        if std::mem::take(&mut self.pending_manga_page_overrides_store) {
+            if let Some(directory) = self.manga_page_overrides_directory.as_ref() {
+                store_manga_page_overrides(directory.as_path(), &self.manga_page_overrides);
+            }
+        }
+    }
+
+    /// Load the page-width zoom and per-page overrides remembered for the folder of the
+    /// current item.
+    fn restore_manga_folder_view_state(&mut self) {
+        self.flush_pending_manga_folder_view_state_store();
+
+        let directory = self
+            .current_media_path()
+            .and_then(|path| path.parent().map(Path::to_path_buf));
+        self.manga_page_width_zoom = directory
+            .as_ref()
+            .and_then(|directory| lookup_manga_page_width_zoom(directory.as_path()))
+            .map(|fraction| self.clamp_manga_page_width_zoom(fraction));
+
+        if directory != self.manga_page_overrides_directory {
+            let had_overrides = !self.manga_page_overrides.is_empty();
+            self.manga_page_overrides = directory
+                .as_ref()
+                .map(|directory| lookup_manga_page_overrides(directory.as_path()))
+                .unwrap_or_default();
+            self.manga_page_overrides_directory = directory;
+            if had_overrides || !self.manga_page_overrides.is_empty() {
+                self.invalidate_manga_layout_cache();
+            }
+        }
+    }
+
+    /// Per-page rotation/fit override for a long-strip page (always default in Masonry).
+    fn manga_page_override(&self, index: usize) -> MangaPageOverride {
+        if self.manga_page_overrides.is_empty() || self.is_masonry_mode() {
+            return MangaPageOverride::default();
+        }
+
+        self.image_list
+            .get(index)
+            .and_then(|path| path.file_name())
+            .and_then(|name| name.to_str())
+            .and_then(|name| self.manga_page_overrides.get(name))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Rotate or re-fit the page at the viewport center, keeping it in place on screen.
+    fn update_centered_manga_page_override(&mut self, update: impl FnOnce(&mut MangaPageOverride)) {
+        if !self.manga_mode || !self.is_fullscreen || self.is_masonry_mode() {
+            return;
+        }
+
+        let Some(anchor) = self.manga_capture_anchor_at_screen_y(self.screen_size.y * 0.5) else {
+            return;
+        };
+        let Some(file_name) = self
+            .image_list
+            .get(anchor.0)
+            .and_then(|path| path.file_name())
+            .and_then(|name| name.to_str())
+            .map(str::to_string)
+        else {
+            return;
+        };
+
+        self.stop_manga_wheel_scroll();
+        let page_override = self
+            .manga_page_overrides
+            .entry(file_name.clone())
+            .or_default();
+        update(page_override);
+        if page_override.is_default() {
+            self.manga_page_overrides.remove(&file_name);
+        }
+        self.invalidate_manga_layout_cache();
+        self.manga_apply_anchor_at_screen_y(anchor);
+
+        if self.manga_page_overrides_directory.is_some() {
+            self.pending_manga_page_overrides_store = true;
+        }
+        self.manga_finish_direct_zoom_change();
+        self.manga_update_preload_queue();
+    }
+
+    /// Source dimensions as laid out, i.e. swapped for pages rotated by a quarter turn.
+    fn manga_get_page_layout_dimensions(&self, index: usize) -> Option<(f32, f32)> {
+        let dims = self.manga_get_image_source_dimensions(index)?;
+        if self.manga_page_override(index).quarter_turns % 2 == 1 {
+            Some((dims.1, dims.0))
+        } else {
+            Some(dims)
+        }
+    }
+
+    /// Display size for pages with a fit override; `None` follows the strip layout.
+    fn manga_page_fit_override_size(&self, index: usize) -> Option<egui::Vec2> {
+        let fit = self.manga_page_override(index).fit;
+        if fit == MangaPageFit::Auto {
+            return None;
+        }
+
+        // Same 2:3 manga estimate as the layout fallbacks.
+        let (img_w, img_h) = self
+            .manga_get_page_layout_dimensions(index)
+            .filter(|(w, h)| *w > 0.0 && *h > 0.0)
+            .unwrap_or((0.67, 1.0));
+        let page_width_px = self.manga_page_width_zoom_px();
+
+        Some(match fit {
+            MangaPageFit::Width => {
+                let width = page_width_px.unwrap_or(self.screen_size.x * self.zoom);
+                egui::vec2(width, width * img_h / img_w)
+            }
+            _ => {
+                let height = if page_width_px.is_some() {
+                    self.screen_size.y
+                } else {
+                    self.screen_size.y * self.zoom
+                };
+                egui::vec2(height * img_w / img_h, height)
+            }
+        })
+    }
+
+    /// Paint a strip page texture into its layout rect, applying the page's rotation override.
+    ///
+    /// `segments` are the page's textures top to bottom (one for most pages); each full
+    /// segment covers `segment_fraction` of the page height.
+    fn paint_manga_page_texture(
+        &self,
+        painter: &egui::Painter,
+        index: usize,
+        segments: &[egui::TextureId],
+        segment_fraction: f32,
+        rect: egui::Rect,
+        tint: egui::Color32,
+    ) {
+        let quarter_turns = self.manga_page_override(index).quarter_turns % 4;
+        let full_uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+        if quarter_turns == 0 && segments.len() == 1 {
+            painter.image(segments[0], rect, full_uv, tint);
+            return;
+        }
+
+        // The layout rect already has rotated proportions; the unrotated quad is its transpose.
+        let size = if quarter_turns % 2 == 1 {
+            egui::vec2(rect.height(), rect.width())
+        } else {
+            rect.size()
+        };
+        let angle = f32::from(quarter_turns) * std::f32::consts::FRAC_PI_2;
+        let segment_fraction = segment_fraction.max(f32::EPSILON);
+
+        for (segment, &texture_id) in segments.iter().enumerate() {
+            let top = segment as f32 * segment_fraction;
+            let bottom = (top + segment_fraction).min(1.0);
+            if top >= bottom {
+                break;
+            }
+
+            // Segment center relative to the unrotated quad's center, rotated with the page.
+            let offset = egui::vec2(0.0, ((top + bottom) * 0.5 - 0.5) * size.y);
+            let segment_size = egui::vec2(size.x, (bottom - top) * size.y);
+            if quarter_turns == 0 {
+                painter.image(
+                    texture_id,
+                    egui::Rect::from_center_size(rect.center() + offset, segment_size),
+                    full_uv,
+                    tint,
+                );
+            } else {
+                paint_rotated_texture(
+                    painter,
+                    texture_id,
+                    rotate_quad_point(rect.center(), offset, angle),
+                    segment_size,
+                    angle,
+                    false,
+                    false,
+                    tint,
+                );
+            }
+        }
+    }
+
     /// Apply pointer-anchored zoom for masonry mode using screen-space cursor position.
     /// Keeps the content point under the cursor stable while zooming.
     fn apply_masonry_zoom_at_screen_pos(
@@ -21781,10 +30120,20 @@ impl ImageViewer {
                     .get_texture_info_for_path(idx, path)
             }) {
                 let draw_rect = gallery_fit_rect(image_rect, tex_w, tex_h);
-                ui.painter().image(
-                    texture_id,
+                let (segments, segment_fraction) = self
+                    .image_list
+                    .get(idx)
+                    .and_then(|path| {
+                        self.manga_texture_cache
+                            .get_texture_segments_for_path(idx, path)
+                    })
+                    .unwrap_or_else(|| (vec![texture_id], 1.0));
+                self.paint_manga_page_texture(
+                    ui.painter(),
+                    idx,
+                    &segments,
+                    segment_fraction,
                     draw_rect,
-                    egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
                     egui::Color32::WHITE,
                 );
 
@@ -21889,18 +30238,43 @@ impl ImageViewer {
                     navigation_active_for_visible_retry,
                 );
             } else {
-                // Image not loaded yet - draw a placeholder
-                ui.painter()
-                    .rect_filled(image_rect, 0.0, egui::Color32::from_gray(30));
+                // Image not loaded yet - prefer the blurred micro-thumbnail over a flat placeholder
+                let blur_preview = if self.config.manga_blur_placeholders && !is_animated_image {
+                    self.manga_loader.as_mut().and_then(|loader| {
+                        loader.request_micro_thumbnail(&self.image_list, idx);
+                        let texture_id = loader.micro_thumbnail_texture(ui.ctx(), idx)?;
+                        let draw_rect = loader
+                            .get_dimensions(idx)
+                            .map(|(w, h)| gallery_fit_rect(image_rect, w, h))
+                            .unwrap_or(image_rect);
+                        Some((texture_id, draw_rect))
+                    })
+                } else {
+                    None
+                };
 
-                // Draw a subtle loading spinner or indicator
-                ui.painter().text(
-                    image_rect.center(),
-                    egui::Align2::CENTER_CENTER,
-                    "⏳",
-                    egui::FontId::proportional(24.0),
-                    egui::Color32::from_gray(80),
-                );
+                if let Some((texture_id, draw_rect)) = blur_preview {
+                    self.paint_manga_page_texture(
+                        ui.painter(),
+                        idx,
+                        &[texture_id],
+                        1.0,
+                        draw_rect,
+                        egui::Color32::from_gray(220),
+                    );
+                } else {
+                    ui.painter()
+                        .rect_filled(image_rect, 0.0, egui::Color32::from_gray(30));
+
+                    // Draw a subtle loading spinner or indicator
+                    ui.painter().text(
+                        image_rect.center(),
+                        egui::Align2::CENTER_CENTER,
+                        "⏳",
+                        egui::FontId::proportional(24.0),
+                        egui::Color32::from_gray(80),
+                    );
+                }
 
                 if is_animated_image {
                     let icon_bg_rect =
@@ -21951,7 +30325,7 @@ impl ImageViewer {
             return false;
         }
 
-        let freeze_for_help = self.shortcuts_help_modal_open;
+        let freeze_for_help = self.input_blocking_overlay_open();
 
         self.manga_prune_ttv_pending();
 
@@ -21973,7 +30347,7 @@ impl ImageViewer {
         let mut masonry_scrollbar_moved_this_frame = false;
         let mut masonry_autoscroll_moved_this_frame = false;
         let mut masonry_scroll_animation_moved_this_frame = false;
-        let masonry_preload_input_blocked = self.shortcuts_help_modal_open
+        let masonry_preload_input_blocked = self.input_blocking_overlay_open()
             || (self.is_masonry_mode() && self.masonry_metadata_preload_active);
         let mode_scroll_up_action = if self.is_masonry_mode() {
             Action::MasonryScrollUp
@@ -22030,7 +30404,11 @@ impl ImageViewer {
             && ctx.input(|i| i.pointer.button_pressed(egui::PointerButton::Primary));
         let primary_released = !masonry_preload_input_blocked
             && ctx.input(|i| i.pointer.button_released(egui::PointerButton::Primary));
+        // A touch double tap toggles fullscreen (when enabled) rather than resetting the view.
+        let touch_double_tap =
+            self.touch_frame(ctx).double_tap && self.config.touch_double_tap_fullscreen;
         let primary_double_clicked = !masonry_preload_input_blocked
+            && !touch_double_tap
             && ctx.input(|i| {
                 i.pointer
                     .button_double_clicked(egui::PointerButton::Primary)
@@ -22038,7 +30416,7 @@ impl ImageViewer {
         let pointer_delta = if masonry_preload_input_blocked {
             egui::Vec2::ZERO
         } else {
-            ctx.input(|i| i.pointer.delta())
+            self.single_pointer_drag_delta(ctx)
         };
         let secondary_clicked = !masonry_preload_input_blocked
             && ctx.input(|i| i.pointer.button_clicked(egui::PointerButton::Secondary));
@@ -22143,7 +30521,7 @@ impl ImageViewer {
             });
         }
 
-        let controls_bar_height = 56.0;
+        let controls_bar_height = self.overlay_theme.video_bar_height;
         let over_controls =
             pointer_pos.map_or(false, |p| p.y > screen_height - controls_bar_height);
 
@@ -22534,6 +30912,26 @@ impl ImageViewer {
                         self.config.manga_wheel_max_velocity,
                     );
                 animation_active = true;
+            } else if wants_ctrl_zoom && self.manga_page_width_zoom_px().is_some() {
+                if self.manga_wheel_scroll_active {
+                    self.stop_manga_wheel_scroll();
+                }
+
+                // Page-width zoom: Ctrl+wheel widens/narrows the strip column instead of
+                // scaling the fit-to-height layout.
+                let anchor = pointer_pos
+                    .map(|p| {
+                        egui::pos2(
+                            (p.x - screen_rect.min.x).clamp(0.0, screen_width),
+                            (p.y - screen_rect.min.y).clamp(0.0, screen_height),
+                        )
+                    })
+                    .unwrap_or(egui::pos2(screen_width * 0.5, screen_height * 0.5));
+                if self.step_manga_page_width_zoom(wheel_steps_ctrl_effective > 0.0, anchor) {
+                    self.manga_finish_direct_zoom_change();
+                    self.manga_update_preload_queue();
+                    animation_active = true;
+                }
             } else if wants_ctrl_zoom {
                 if self.manga_wheel_scroll_active {
                     self.stop_manga_wheel_scroll();
@@ -23308,13 +31706,8 @@ impl ImageViewer {
     fn media_display_dimensions(&self) -> Option<(u32, u32)> {
         if let Some(ref img) = self.image {
             Some(img.display_dimensions())
-        } else if let Some(ref player) = self.video_player {
-            let dims = player.dimensions();
-            if dims.0 > 0 && dims.1 > 0 {
-                Some(dims)
-            } else {
-                self.video_texture_dims
-            }
+        } else if self.video_player.is_some() {
+            self.video_view_dimensions()
         } else if matches!(self.current_media_type, Some(MediaType::Image)) {
             Self::pending_image_display_dimensions(
                 self.retained_media_placeholder_visible,
@@ -23328,6 +31721,45 @@ impl ImageViewer {
         }
     }
 
+    /// Size a playing video is zoomed, panned, fitted and drawn at: its source dimensions,
+    /// whatever size the working texture is decoded to.
+    fn video_view_dimensions(&self) -> Option<(u32, u32)> {
+        self.video_player
+            .as_ref()
+            .map(VideoPlayer::display_dimensions)
+            .filter(|dims| dims.0 > 0 && dims.1 > 0)
+            .or(self.video_texture_dims)
+    }
+
+    /// Size the solo view draws its texture at. Outside of a retained placeholder this is the
+    /// size [`Self::media_display_dimensions`] gives the zoom, pan and fit code, so images and
+    /// videos are laid out and drawn through the same numbers.
+    fn solo_draw_dimensions(&self) -> Option<(u32, u32)> {
+        if self.video_texture.is_some() {
+            // A retained placeholder keeps its own size so the temporary frame does not
+            // jump or stretch before the next video shows.
+            if self.retained_media_placeholder_visible {
+                return self.video_texture_dims.or_else(|| {
+                    self.video_player
+                        .as_ref()
+                        .map(VideoPlayer::display_dimensions)
+                        .filter(|dims| dims.0 > 0 && dims.1 > 0)
+                });
+            }
+            return self.video_view_dimensions();
+        }
+        self.texture.as_ref()?;
+        if self.retained_media_placeholder_visible {
+            self.image_texture_dims
+        } else if let Some(img) = self.image.as_ref() {
+            let frame = img.current_frame_data();
+            Self::solo_texture_dims_match_frame(self.image_texture_dims, frame)
+                .then(|| img.display_dimensions())
+        } else {
+            self.image_texture_dims
+        }
+    }
+
     fn current_image_cached_dimensions(&self) -> Option<(u32, u32)> {
         if !matches!(self.current_media_type, Some(MediaType::Image)) {
             return None;
@@ -23630,29 +32062,52 @@ impl ImageViewer {
         }
     }
 
-    fn right_click_black_bar_action(
+    /// Index of the click zone under `pos`. The black bars beside a solo image belong to the
+    /// outermost zones, however narrow those are.
+    fn click_zone_index_at(&self, pos: egui::Pos2, screen_rect: egui::Rect) -> Option<usize> {
+        let zones = &self.config.click_zones;
+        if !self.manga_mode {
+            if let Some(image_rect) = self.current_media_rect(screen_rect) {
+                const MIN_BAR_WIDTH: f32 = 0.5;
+                let left_gap = image_rect.min.x - screen_rect.min.x;
+                let right_gap = screen_rect.max.x - image_rect.max.x;
+
+                if left_gap > MIN_BAR_WIDTH && pos.x < image_rect.min.x {
+                    return Some(0);
+                }
+                if right_gap > MIN_BAR_WIDTH && pos.x > image_rect.max.x {
+                    return Some(zones.len().saturating_sub(1));
+                }
+            }
+        }
+        let fraction = (pos.x - screen_rect.min.x) / screen_rect.width().max(1.0);
+        ClickZone::index_at(zones, fraction)
+    }
+
+    /// Action of the click zone under `pos`, when `click_zone_button` is `button`. Right-click
+    /// zones stay tied to `select_area` keeping its right-click binding, which has always
+    /// switched edge navigation on and off.
+    fn click_zone_action(
         &self,
+        button: ClickZoneButton,
         pos: egui::Pos2,
         screen_rect: egui::Rect,
-    ) -> Option<Action> {
-        let image_rect = self.current_media_rect(screen_rect)?;
-
-        const MIN_BAR_WIDTH: f32 = 0.5;
-        let left_gap = image_rect.min.x - screen_rect.min.x;
-        let right_gap = screen_rect.max.x - image_rect.max.x;
-
-        if left_gap > MIN_BAR_WIDTH && pos.x < image_rect.min.x {
-            return Some(Action::PreviousImage);
+    ) -> Option<Action> {
+        if button == ClickZoneButton::None || self.config.click_zone_button != button {
+            return None;
         }
-        if right_gap > MIN_BAR_WIDTH && pos.x > image_rect.max.x {
-            return Some(Action::NextImage);
+        if button == ClickZoneButton::Right
+            && !self.action_uses_binding(Action::SelectArea, InputBinding::MouseRight)
+        {
+            return None;
         }
-
-        None
+        let index = self.click_zone_index_at(pos, screen_rect)?;
+        self.config.click_zones.get(index)?.action
     }
 
     fn request_floating_autosize(&mut self, ctx: &egui::Context) {
         if self.is_fullscreen
+            || self.floating_size_pinned
             || self.current_window_is_maximized(ctx)
             || self.is_resizing
             || self.pending_window_resize.is_some()
@@ -23839,7 +32294,7 @@ impl ImageViewer {
         let current_video_path = self.image_list.get(self.current_index).cloned();
         let current_video_target_side = current_video_path
             .as_ref()
-            .map(|path| self.solo_target_texture_side_for_path(path, MediaType::Video, false))
+            .map(|path| self.solo_video_target_texture_side(path))
             .unwrap_or(self.max_texture_side.max(1));
         let solo_video_upload_filter = self.config.downscale_filter.to_image_filter();
         let solo_video_texture_filter = self.config.texture_filter_video;
@@ -23947,10 +32402,22 @@ impl ImageViewer {
                         .to_egui_options_with_mipmap(enable_mipmap)
                 };
 
-                if let Some(texture) = self.texture.as_mut() {
-                    texture.set(color_image, texture_options);
-                } else {
-                    self.texture = Some(ctx.load_texture("image", color_image, texture_options));
+                // A transition still shows the outgoing image from this texture, so the incoming
+                // image gets a texture of its own.
+                let texture_in_transition =
+                    self.image_transition.as_ref().is_some_and(|transition| {
+                        self.texture
+                            .as_ref()
+                            .is_some_and(|texture| texture.id() == transition.texture.id())
+                    });
+                match self.texture.as_mut() {
+                    Some(texture) if !texture_in_transition => {
+                        texture.set(color_image, texture_options);
+                    }
+                    _ => {
+                        self.texture =
+                            Some(ctx.load_texture("image", color_image, texture_options));
+                    }
                 }
                 self.image_texture_dims = Some((w, h));
                 self.texture_frame = img.current_frame_index();
@@ -23973,20 +32440,42 @@ impl ImageViewer {
         }
 
         // Handle video frame updates
+        // The frame drawn now reaches the screen on the next display refresh.
+        let refresh_interval = Duration::from_secs_f32(
+            ctx.input(|input| input.predicted_dt)
+                .clamp(1.0 / 480.0, 0.1),
+        );
+        let mut playlist_video_ended = false;
+        let mut advance_after_video_end = false;
         if let Some(ref mut player) = self.video_player {
+            player.set_frame_pacing(self.config.video_frame_pacing);
+            player.set_deinterlace(self.config.video_deinterlace);
             // Update duration cache
             player.update_duration();
 
-            // Check for video end and handle looping
+            // Check for video end and handle looping; a playlist moves on instead.
             if player.is_eos() {
-                if self.config.video_loop {
+                let repeat_item = match self.playlist {
+                    Some(_) => self.config.playlist_repeat == PlaylistRepeat::One,
+                    None => self.config.video_loop,
+                };
+                if repeat_item {
                     let _ = player.restart();
                     needs_repaint = true;
+                } else {
+                    playlist_video_ended = self.playlist.is_some();
+                    advance_after_video_end =
+                        !playlist_video_ended && self.config.advance_on_video_end;
                 }
             }
+            if player.take_orientation_changed() {
+                self.image_rotated = true;
+            }
 
             // Get new frame if available
-            if let Some(frame) = player.get_frame() {
+            if let Some(frame) =
+                player.get_paced_frame(Instant::now() + refresh_interval, refresh_interval)
+            {
                 activate_deferred_video_swap = self.defer_media_view_reset;
                 solo_displayed_video_position = frame.pts;
 
@@ -24026,7 +32515,7 @@ impl ImageViewer {
 
                 // Reuse the same GPU texture across frames to avoid per-frame allocations.
                 if let Some(texture) = self.video_texture.as_mut() {
-                    texture.set(color_image, texture_options);
+                    upload_video_frame(texture, color_image, texture_options);
                 } else {
                     self.video_texture =
                         Some(ctx.load_texture("video", color_image, texture_options));
@@ -24040,13 +32529,33 @@ impl ImageViewer {
 
             // Only request repaint for active video playback or when seeking
             if player.is_playing() {
-                // Do not use a 7ms timer here: 7ms mathematically caps the UI below 144 Hz.
-                // Immediate repaint lets the platform frame pacing hit high-refresh monitors.
-                ctx.request_repaint();
+                if self.config.low_power_mode {
+                    ctx.request_repaint_after(Duration::from_millis(Self::LOW_POWER_FRAME_MS));
+                } else if self.config.video_frame_pacing && self.config.video_repaint_at_deadline {
+                    // Wake one refresh before the next frame is due so it makes that refresh;
+                    // with nothing queued yet, poll at refresh rate until the sink delivers.
+                    let wake_in = player
+                        .next_frame_due()
+                        .map(|due| {
+                            due.saturating_duration_since(Instant::now())
+                                .saturating_sub(refresh_interval)
+                        })
+                        .unwrap_or(refresh_interval);
+                    ctx.request_repaint_after(wake_in);
+                } else {
+                    // Do not use a 7ms timer here: 7ms mathematically caps the UI below 144 Hz.
+                    // Immediate repaint lets the platform frame pacing hit high-refresh monitors.
+                    ctx.request_repaint();
+                }
             } else if self.is_seeking {
                 needs_repaint = true;
             }
         }
+        if playlist_video_ended {
+            self.advance_playlist_after_video_end();
+        } else if advance_after_video_end && !self.manga_mode {
+            self.next_image();
+        }
 
         if let Some(position) = solo_displayed_video_position {
             if matches!(self.current_media_type, Some(MediaType::Video)) {
@@ -24075,13 +32584,300 @@ impl ImageViewer {
             self.pending_media_layout = true;
         }
 
-        // If we are waiting on video dimensions (e.g. right after switching videos),
-        // we need a repaint to get the first decoded frame ASAP.
-        if self.pending_media_layout {
-            needs_repaint = true;
+        needs_repaint
+    }
+
+    /// Feeds the right-button drag into the gesture tracker and returns the bound action once
+    /// a gesture is released. Right-drags are left alone when a pan action is bound to them.
+    fn update_mouse_gesture(&mut self, ctx: &egui::Context) -> Option<Action> {
+        let right_drag_pans = [Action::Pan, Action::MangaPan, Action::MasonryPan]
+            .into_iter()
+            .any(|action| self.action_uses_binding(action, InputBinding::MouseRight));
+        if !self.config.mouse_gestures_enabled || right_drag_pans || self.manga_autoscroll_active {
+            self.mouse_gesture.cancel();
+            return None;
         }
 
-        needs_repaint
+        let (pressed, down, released, pointer_pos, screen_rect) = ctx.input(|input| {
+            (
+                input.pointer.button_pressed(egui::PointerButton::Secondary),
+                input.pointer.button_down(egui::PointerButton::Secondary),
+                input
+                    .pointer
+                    .button_released(egui::PointerButton::Secondary),
+                input.pointer.hover_pos(),
+                input.screen_rect,
+            )
+        });
+
+        if pressed {
+            match pointer_pos {
+                Some(pos) if !self.pointer_over_shortcut_blocking_ui(Some(pos), screen_rect) => {
+                    self.mouse_gesture.begin(pos)
+                }
+                _ => self.mouse_gesture.cancel(),
+            }
+        }
+        if !self.mouse_gesture.is_tracking() {
+            return None;
+        }
+
+        if let Some(pos) = pointer_pos {
+            self.mouse_gesture.move_to(pos);
+        }
+        if released {
+            let gesture = self.mouse_gesture.finish()?;
+            return self.config.mouse_gestures.get(&gesture).copied();
+        }
+        if !down {
+            // The release happened while input was routed elsewhere (modal, menu, ...).
+            self.mouse_gesture.cancel();
+        }
+        None
+    }
+
+    /// This pass's touch gestures (see `touch_input`).
+    fn touch_frame(&mut self, ctx: &egui::Context) -> TouchFrame {
+        let pass_nr = ctx.cumulative_pass_nr();
+        ctx.input(|input| self.touch_tracker.update(input, pass_nr))
+    }
+
+    /// Pointer delta for drag-panning, or zero while a multi-finger touch gesture owns the drag
+    /// (egui mirrors the first finger as the pointer).
+    fn single_pointer_drag_delta(&mut self, ctx: &egui::Context) -> egui::Vec2 {
+        let touch = self.touch_frame(ctx);
+        if touch.multi_touch && (self.config.touch_pinch_zoom || self.config.touch_two_finger_pan) {
+            egui::Vec2::ZERO
+        } else {
+            ctx.input(|i| i.pointer.delta())
+        }
+    }
+
+    /// Auto-pans a zoomed-in image while the pointer rests near a window edge, faster the
+    /// closer it is. Panning stops once the image edge meets the window edge.
+    fn apply_edge_pan(&mut self, ctx: &egui::Context) {
+        if !self.config.edge_pan
+            || self.manga_mode
+            || self.is_panning
+            || self.annotation_mode_active()
+            || self.straighten.active
+            || self.perspective.active
+            || self.eyedropper_active
+            || self.mouse_over_window_buttons
+            || self.title_bar_ui_blocking()
+            || self.any_modal_dialog_open()
+            || self.file_action_menu.is_some()
+            || !self.window_allows_keyboard_shortcuts(ctx)
+        {
+            return;
+        }
+        let (pointer_pos, button_down, dt) = ctx.input(|input| {
+            (
+                input
+                    .pointer
+                    .latest_pos()
+                    .filter(|_| input.pointer.has_pointer()),
+                input.pointer.any_down(),
+                input.stable_dt.min(0.1),
+            )
+        });
+        let Some(pointer_pos) = pointer_pos else {
+            return;
+        };
+        if button_down || ctx.is_pointer_over_area() {
+            return;
+        }
+
+        let screen_rect = ctx.screen_rect();
+        let margin = self.config.edge_pan_margin;
+        let proximity = |distance: f32| (1.0 - distance / margin).clamp(0.0, 1.0);
+        // Positive near the left/top edge, which reveals more of that side.
+        let direction = egui::vec2(
+            proximity(pointer_pos.x - screen_rect.min.x)
+                - proximity(screen_rect.max.x - pointer_pos.x),
+            proximity(pointer_pos.y - screen_rect.min.y)
+                - proximity(screen_rect.max.y - pointer_pos.y),
+        );
+        if self.pan_within_overflow(
+            direction * self.config.edge_pan_speed * dt,
+            screen_rect.size(),
+        ) {
+            ctx.request_repaint();
+        }
+    }
+
+    /// Moves the pan offset by `delta` until an image edge meets the matching edge of `view`.
+    /// Axes along which the image fits the view stay put. Returns whether anything moved.
+    fn pan_within_overflow(&mut self, delta: egui::Vec2, view: egui::Vec2) -> bool {
+        let Some((width, height)) = self.media_display_dimensions() else {
+            return false;
+        };
+        // How far the offset may move each way before an image edge meets the window edge.
+        let overflow = (egui::vec2(width as f32, height as f32) * self.zoom - view) * 0.5;
+        let mut changed = false;
+        for axis in 0..2 {
+            if overflow[axis] <= 0.0 {
+                continue;
+            }
+            let step = delta[axis];
+            let offset = self.offset[axis];
+            let panned = if step > 0.0 {
+                (offset + step).min(overflow[axis]).max(offset)
+            } else if step < 0.0 {
+                (offset + step).max(-overflow[axis]).min(offset)
+            } else {
+                offset
+            };
+            if panned != offset {
+                self.offset[axis] = panned;
+                changed = true;
+            }
+        }
+        if changed && self.is_fullscreen {
+            self.remember_current_fullscreen_view_state();
+        }
+        changed
+    }
+
+    /// Offset direction of a `pan_*` action: panning left reveals the left side, so the
+    /// image moves right.
+    fn keyboard_pan_direction(action: Action) -> egui::Vec2 {
+        match action {
+            Action::PanLeft => egui::vec2(1.0, 0.0),
+            Action::PanRight => egui::vec2(-1.0, 0.0),
+            Action::PanUp => egui::vec2(0.0, 1.0),
+            Action::PanDown => egui::vec2(0.0, -1.0),
+            _ => egui::Vec2::ZERO,
+        }
+    }
+
+    /// Keeps panning while `pan_*` keys are held. The press itself moved one
+    /// `keyboard_pan_step` through `run_action`; after a short delay the image glides on,
+    /// speeding up to `keyboard_pan_acceleration` times the starting speed.
+    fn apply_keyboard_pan(&mut self, ctx: &egui::Context) {
+        let blocked = self.manga_mode
+            || self.any_modal_dialog_open()
+            || self.file_action_menu.is_some()
+            || self.zoom_input_focused
+            || !self.window_allows_keyboard_shortcuts(ctx);
+        let (direction, dt) = ctx.input(|input| {
+            let ctrl = input.modifiers.ctrl;
+            let shift = input.modifiers.shift;
+            let alt = input.modifiers.alt;
+            let direction = [
+                Action::PanLeft,
+                Action::PanRight,
+                Action::PanUp,
+                Action::PanDown,
+            ]
+            .into_iter()
+            .filter(|action| self.action_binding_down(*action, input, ctrl, shift, alt))
+            .map(Self::keyboard_pan_direction)
+            .fold(egui::Vec2::ZERO, |sum, direction| sum + direction);
+            (direction, input.stable_dt.min(0.1))
+        });
+        if blocked || direction == egui::Vec2::ZERO {
+            self.keyboard_pan_held_since = None;
+            return;
+        }
+
+        let held_since = *self
+            .keyboard_pan_held_since
+            .get_or_insert_with(Instant::now);
+        ctx.request_repaint();
+        let Some(gliding) = held_since
+            .elapsed()
+            .checked_sub(Self::KEYBOARD_PAN_REPEAT_DELAY)
+        else {
+            return;
+        };
+        let ramp = (gliding.as_secs_f32() / Self::KEYBOARD_PAN_RAMP.as_secs_f32()).min(1.0);
+        let acceleration = 1.0 + (self.config.keyboard_pan_acceleration - 1.0) * ramp;
+        let speed = self.config.keyboard_pan_step * Self::KEYBOARD_PAN_STEPS_PER_SECOND;
+        self.pan_within_overflow(
+            direction * speed * acceleration * dt,
+            ctx.screen_rect().size(),
+        );
+    }
+
+    /// Applies touchscreen / precision-touchpad gestures: pinch zoom, two-finger pan, swipe
+    /// navigation and double-tap fullscreen, each behind its own config toggle.
+    fn handle_touch_gestures(&mut self, ctx: &egui::Context) {
+        let touch = self.touch_frame(ctx);
+        if !self.window_allows_keyboard_shortcuts(ctx)
+            || self.any_modal_dialog_open()
+            || self.file_action_menu.is_some()
+        {
+            return;
+        }
+        let screen_rect = ctx.screen_rect();
+        let mut changed = false;
+
+        if let Some((factor, center)) = touch.pinch.filter(|_| self.config.touch_pinch_zoom) {
+            if self.manga_mode && self.is_fullscreen {
+                let new_zoom = self.clamp_zoom(self.zoom * factor);
+                let anchor = (center - screen_rect.min).to_pos2();
+                let applied = if self.is_masonry_mode() {
+                    self.apply_masonry_zoom_at_screen_pos(new_zoom, anchor)
+                } else if self.manga_page_width_zoom_px().is_none() {
+                    self.apply_strip_zoom_at_screen_pos(new_zoom, anchor)
+                } else {
+                    false
+                };
+                if applied {
+                    self.manga_finish_direct_zoom_change();
+                    changed = true;
+                }
+            } else if !self.manga_mode {
+                self.zoom_at(center, factor, screen_rect);
+                self.zoom_target = self.zoom;
+                self.zoom_velocity = 0.0;
+                changed = true;
+            }
+        }
+
+        if self.config.touch_two_finger_pan && self.is_fullscreen && touch.pan != egui::Vec2::ZERO {
+            if self.manga_mode {
+                // Same 1:1 mapping as drag-panning the strip.
+                self.stop_manga_wheel_scroll();
+                let max_scroll = (self.manga_total_height() - self.screen_size.y).max(0.0);
+                self.manga_scroll_offset =
+                    (self.manga_scroll_offset - touch.pan.y).clamp(0.0, max_scroll);
+                self.manga_scroll_target = self.manga_scroll_offset;
+                self.offset.x += touch.pan.x;
+                self.manga_update_current_index();
+                self.manga_update_preload_queue();
+            } else {
+                self.offset += touch.pan;
+                self.remember_current_fullscreen_view_state();
+            }
+            changed = true;
+        }
+
+        if let Some(direction) = touch.swipe.filter(|_| self.config.touch_swipe_navigation) {
+            // Swipes only navigate while nothing is left to pan; otherwise the drag was a pan.
+            let fits_screen = self.media_display_dimensions().is_some_and(|(w, h)| {
+                w as f32 * self.zoom <= screen_rect.width() + 1.0
+                    && h as f32 * self.zoom <= screen_rect.height() + 1.0
+            });
+            if !self.manga_mode && self.is_fullscreen && fits_screen {
+                self.offset = egui::Vec2::ZERO;
+                match direction {
+                    SwipeDirection::Left => self.next_image(),
+                    SwipeDirection::Right => self.prev_image(),
+                }
+                changed = true;
+            }
+        }
+
+        if touch.double_tap && self.config.touch_double_tap_fullscreen {
+            self.request_shortcut_fullscreen_toggle();
+            changed = true;
+        }
+
+        if changed {
+            ctx.request_repaint();
+        }
     }
 
     /// Handle keyboard and mouse input
@@ -24095,7 +32891,29 @@ impl ImageViewer {
             return;
         }
 
-        if self.any_modal_dialog_open() || self.file_action_menu.is_some() {
+        if self.any_modal_dialog_open()
+            || self.file_action_menu.is_some()
+            || self.zoom_input_focused
+            || self
+                .quick_filter
+                .as_ref()
+                .is_some_and(|filter| filter.editing)
+        {
+            return;
+        }
+
+        // Escape clears an active filter before it can exit the viewer.
+        if self.quick_filter.is_some()
+            && ctx.input_mut(|input| input.consume_key(egui::Modifiers::NONE, egui::Key::Escape))
+        {
+            self.clear_quick_filter();
+            return;
+        }
+
+        // Enter, Space and the arrows press or leave the focused control; Escape drops focus.
+        let focus_held = self.keyboard_focus_active || accessibility::control_has_focus(ctx);
+        self.keyboard_focus_active = accessibility::control_has_focus(ctx);
+        if focus_held && accessibility::focus_key_pressed(ctx) {
             return;
         }
 
@@ -24107,7 +32925,8 @@ impl ImageViewer {
             return;
         }
 
-        let screen_width = ctx.screen_rect().width();
+        let gesture_action = self.update_mouse_gesture(ctx);
+
         let (mark_file_key, _) = self.active_mark_shortcuts();
         let mark_file_pressed = ctx.input(|input| {
             !input.modifiers.ctrl
@@ -24123,7 +32942,7 @@ impl ImageViewer {
         }
 
         // Collect actions to run (we can't mutate self inside ctx.input closure)
-        let mut actions_to_run: Vec<Action> = Vec::new();
+        let mut actions_to_run: Vec<Action> = gesture_action.into_iter().collect();
         let mut strip_item_open_from_strip = false;
         let mut strip_item_open_pointer_pos: Option<egui::Pos2> = None;
         let mut right_click_toggle_fullscreen = false;
@@ -24192,8 +33011,9 @@ impl ImageViewer {
             }
 
             // Check discrete bindings that are not handled by dedicated pointer or hold logic.
-            for (action, bindings) in &self.config.action_bindings {
-                let action = *action;
+            let context = self.binding_context();
+            for action in Action::ALL {
+                let bindings = self.config.bindings_in(context, action);
 
                 let handled_elsewhere = matches!(
                     action,
@@ -24227,38 +33047,17 @@ impl ImageViewer {
                     continue;
                 }
 
-                let action_active = match action {
-                    Action::ToggleFullscreen
-                    | Action::GotoFile
-                    | Action::Exit
-                    | Action::ResetZoom
-                    | Action::Minimize
-                    | Action::Close => true,
-                    Action::NextImage
-                    | Action::PreviousImage
-                    | Action::RotateClockwise
-                    | Action::RotateCounterClockwise
-                    | Action::FlipVertically
-                    | Action::FlipHorizontally
-                    | Action::ZoomIn
-                    | Action::ZoomOut
-                    | Action::VideoPlayPause
-                    | Action::VideoMute => !self.manga_mode,
-                    Action::PreciseRotationClockwise | Action::PreciseRotationCounterClockwise => {
-                        !self.manga_mode
-                    }
-                    Action::MangaNextImage
-                    | Action::MangaPreviousImage
-                    | Action::MangaZoomIn
-                    | Action::MangaZoomOut => manga_fullscreen && !masonry_fullscreen,
-                    Action::MasonryZoomIn | Action::MasonryZoomOut => masonry_fullscreen,
-                    _ => false,
-                };
+                let action_active = shortcut_active(
+                    action,
+                    self.manga_mode,
+                    manga_fullscreen,
+                    masonry_fullscreen,
+                );
                 if !action_active {
                     continue;
                 }
 
-                for binding in bindings {
+                for binding in bindings.iter() {
                     match binding {
                         InputBinding::Key(key) => {
                             if mark_file_pressed && Some(*key) == mark_file_key {
@@ -24283,6 +33082,11 @@ impl ImageViewer {
                                 actions_to_run.push(action);
                             }
                         }
+                        InputBinding::KeyWithCtrlShift(key) => {
+                            if ctrl && shift && !alt && input.key_pressed(*key) {
+                                actions_to_run.push(action);
+                            }
+                        }
                         InputBinding::MouseMiddle => {
                             if middle_pressed {
                                 actions_to_run.push(action);
@@ -24335,27 +33139,13 @@ impl ImageViewer {
                 }
 
                 if let Some(pos) = pointer_pos {
-                    let select_area_uses_right_click =
-                        self.action_uses_binding(Action::SelectArea, InputBinding::MouseRight);
                     let goto_file_uses_right_click =
                         self.action_uses_binding(Action::GotoFile, InputBinding::MouseRight);
 
-                    if select_area_uses_right_click && !self.manga_mode {
-                        if let Some(action) =
-                            self.right_click_black_bar_action(pos, input.screen_rect)
-                        {
-                            actions_to_run.push(action);
-                            right_click_navigated = true;
-                            return;
-                        }
-                    }
-
-                    let side_zone = screen_width / 9.0;
-                    if select_area_uses_right_click && pos.x < side_zone {
-                        actions_to_run.push(Action::PreviousImage);
-                        right_click_navigated = true;
-                    } else if select_area_uses_right_click && pos.x > screen_width - side_zone {
-                        actions_to_run.push(Action::NextImage);
+                    if let Some(action) =
+                        self.click_zone_action(ClickZoneButton::Right, pos, input.screen_rect)
+                    {
+                        actions_to_run.push(action);
                         right_click_navigated = true;
                     } else if !self.manga_mode
                         && goto_file_uses_right_click
@@ -24369,6 +33159,34 @@ impl ImageViewer {
                     }
                 }
             }
+
+            let zone_button = match self.config.click_zone_button {
+                ClickZoneButton::Left => Some(egui::PointerButton::Primary),
+                ClickZoneButton::Middle => Some(egui::PointerButton::Middle),
+                ClickZoneButton::Right | ClickZoneButton::None => None,
+            };
+            if let (Some(button), Some(pos)) = (zone_button, pointer_pos) {
+                let tool_active = self.annotation_mode_active()
+                    || self.straighten.active
+                    || self.perspective.active
+                    || self.eyedropper_active
+                    || self.free_rotate_held;
+                if input.pointer.button_clicked(button)
+                    && !pointer_over_shortcut_ui
+                    && !tool_active
+                    && !ctrl
+                    && !shift
+                    && !alt
+                {
+                    if let Some(action) = self.click_zone_action(
+                        self.config.click_zone_button,
+                        pos,
+                        input.screen_rect,
+                    ) {
+                        actions_to_run.push(action);
+                    }
+                }
+            }
         });
 
         if let Some(menu_pos) = ctrl_secondary_single_file_menu_pos {
@@ -24421,12 +33239,15 @@ impl ImageViewer {
                     return false;
                 }
 
-                if let Some(pos) = pointer_pos {
-                    let side_zone = screen_width / 9.0;
-                    pos.x >= side_zone && pos.x <= screen_width - side_zone
-                } else {
-                    false
-                }
+                // Zones with an action keep the right click to themselves, even while
+                // `select_area` has lost its right-click binding.
+                pointer_pos.is_some_and(|pos| {
+                    self.config.click_zone_button != ClickZoneButton::Right
+                        || self
+                            .click_zone_index_at(pos, input.screen_rect)
+                            .and_then(|index| self.config.click_zones.get(index))
+                            .map_or(true, |zone| zone.action.is_none())
+                })
             })
         };
 
@@ -24446,9 +33267,9 @@ impl ImageViewer {
 
         // Backward-compatible fallback: treat Enter as fullscreen toggle when unbound.
         let enter_pressed = ctx.input(|i| i.key_pressed(egui::Key::Enter));
-        let enter_bound = self.config.action_uses_binding(
+        let enter_bound = self.action_uses_binding(
             Action::ToggleFullscreen,
-            &InputBinding::Key(egui::Key::Enter),
+            InputBinding::Key(egui::Key::Enter),
         );
         if enter_pressed && !enter_bound {
             self.request_shortcut_fullscreen_toggle();
@@ -24689,17 +33510,17 @@ impl ImageViewer {
             let end = ctx.input(|i| i.key_pressed(egui::Key::End));
 
             let page_up_bound = self
-                .config
-                .action_uses_binding(Action::PreviousImage, &InputBinding::Key(egui::Key::PageUp));
-            let page_down_bound = self
-                .config
-                .action_uses_binding(Action::NextImage, &InputBinding::Key(egui::Key::PageDown));
-            let home_bound = self
-                .config
-                .any_action_uses_binding(&InputBinding::Key(egui::Key::Home));
-            let end_bound = self
-                .config
-                .any_action_uses_binding(&InputBinding::Key(egui::Key::End));
+                .action_uses_binding(Action::PreviousImage, InputBinding::Key(egui::Key::PageUp));
+            let page_down_bound =
+                self.action_uses_binding(Action::NextImage, InputBinding::Key(egui::Key::PageDown));
+            let home_bound = self.config.any_action_uses_binding(
+                self.binding_context(),
+                &InputBinding::Key(egui::Key::Home),
+            );
+            let end_bound = self.config.any_action_uses_binding(
+                self.binding_context(),
+                &InputBinding::Key(egui::Key::End),
+            );
 
             if page_up && !page_up_bound {
                 if self.video_navigation_mode_active() {
@@ -24731,6 +33552,32 @@ impl ImageViewer {
     }
 
     /// Draw the control bar
+    /// Title-bar box showing the zoom level; typing a percentage and pressing Enter zooms
+    /// to it. Returns whether the pointer is over it.
+    fn draw_zoom_input(&mut self, ui: &mut egui::Ui) -> bool {
+        let id = egui::Id::new("title_bar_zoom_input");
+        let editing = ui.memory(|mem| mem.has_focus(id));
+        if !editing {
+            self.zoom_input_text = format!("{:.0}%", self.zoom * 100.0);
+        }
+        let resp = ui
+            .add(
+                egui::TextEdit::singleline(&mut self.zoom_input_text)
+                    .id(id)
+                    .desired_width(44.0)
+                    .text_color(egui::Color32::GRAY)
+                    .frame(editing),
+            )
+            .on_hover_text("Zoom: type a percentage and press Enter");
+        self.zoom_input_focused = resp.has_focus();
+        if resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            if let Some(ZoomPreset::Zoom(zoom)) = ZoomPreset::from_str(&self.zoom_input_text) {
+                self.set_zoom_level(zoom);
+            }
+        }
+        resp.contains_pointer()
+    }
+
     fn draw_controls(&mut self, ctx: &egui::Context) {
         let screen_rect = ctx.screen_rect();
         let title_bar_menu_was_active = self.title_bar_menu_active;
@@ -24738,6 +33585,7 @@ impl ImageViewer {
         // Default to false each frame; updated below when the bar is visible.
         self.mouse_over_window_buttons = false;
         self.mouse_over_title_text = false;
+        self.zoom_input_focused = false;
         self.title_bar_menu_active = false;
 
         // Keep title-text drag-selection state sticky until the primary button is released.
@@ -24789,7 +33637,8 @@ impl ImageViewer {
         let mut breadcrumb_popup_active = false;
 
         // Draw control bar
-        let bar_height = Self::TITLE_BAR_HEIGHT;
+        let theme = self.overlay_theme;
+        let bar_height = theme.title_bar_height;
         let bar_rect = egui::Rect::from_min_size(
             screen_rect.min,
             egui::Vec2::new(screen_rect.width(), bar_height),
@@ -24800,11 +33649,7 @@ impl ImageViewer {
             .order(egui::Order::Foreground)
             .show(ctx, |ui| {
                 let painter = ui.painter();
-                painter.rect_filled(
-                    bar_rect,
-                    0.0,
-                    egui::Color32::from_rgba_unmultiplied(40, 40, 40, 220),
-                );
+                painter.rect_filled(bar_rect, 0.0, theme.title_bar_fill);
 
                 ui.allocate_new_ui(egui::UiBuilder::new().max_rect(bar_rect), |ui| {
                     ui.set_min_height(bar_height);
@@ -24855,18 +33700,19 @@ impl ImageViewer {
                                 let toggle_size = egui::vec2(24.0, 24.0);
                                 let (toggle_rect, toggle_resp_base) =
                                     ui.allocate_exact_size(toggle_size, egui::Sense::click());
-                                let toggle_resp =
-                                    toggle_resp_base.on_hover_text(if self.show_breadcrumb_bar {
-                                        "Hide breadcrumb address bar"
-                                    } else {
-                                        "Show breadcrumb address bar"
-                                    });
+                                let toggle_label = if self.show_breadcrumb_bar {
+                                    "Hide breadcrumb address bar"
+                                } else {
+                                    "Show breadcrumb address bar"
+                                };
+                                accessibility::describe_button(&toggle_resp_base, toggle_label);
+                                let toggle_resp = toggle_resp_base.on_hover_text(toggle_label);
 
                                 if ui.is_rect_visible(toggle_rect) {
                                     let bg = if toggle_resp.is_pointer_button_down_on() {
-                                        egui::Color32::from_rgba_unmultiplied(255, 255, 255, 40)
+                                        theme.icon.gamma_multiply(40.0 / 255.0)
                                     } else if toggle_resp.hovered() {
-                                        egui::Color32::from_rgba_unmultiplied(255, 255, 255, 22)
+                                        theme.icon.gamma_multiply(22.0 / 255.0)
                                     } else {
                                         egui::Color32::TRANSPARENT
                                     };
@@ -24874,9 +33720,9 @@ impl ImageViewer {
 
                                     let icon_rect = toggle_rect.shrink2(egui::vec2(4.0, 4.0));
                                     let icon_color = if self.show_breadcrumb_bar {
-                                        TITLEBAR_CONTROL_ICON_COLOR
+                                        theme.icon
                                     } else {
-                                        TITLEBAR_CONTROL_ICON_COLOR.gamma_multiply(170.0 / 255.0)
+                                        theme.icon.gamma_multiply(170.0 / 255.0)
                                     };
                                     Self::paint_breadcrumb_toggle_folder_icon(
                                         ui, icon_rect, icon_color,
@@ -24925,9 +33771,10 @@ impl ImageViewer {
                                     }
                                 }
 
+                                let title_text = emoji::display_text(&title_text).into_owned();
                                 let resp = ui.add(
                                     egui::Label::new(
-                                        egui::RichText::new(title_text).color(egui::Color32::WHITE),
+                                        egui::RichText::new(title_text).color(theme.text),
                                     )
                                     .selectable(true)
                                     .truncate(),
@@ -24945,7 +33792,7 @@ impl ImageViewer {
                                 if !show_details {
                                     let resp = ui.add(
                                         egui::Label::new(
-                                            egui::RichText::new("...").color(egui::Color32::GRAY),
+                                            egui::RichText::new("...").color(theme.muted_text),
                                         )
                                         .selectable(true),
                                     );
@@ -24956,8 +33803,78 @@ impl ImageViewer {
                                     if let Some((w, h)) = self.media_display_dimensions() {
                                         let resp = ui.add(
                                             egui::Label::new(
-                                                egui::RichText::new(format!("{}x{}", w, h))
-                                                    .color(egui::Color32::GRAY),
+                                                egui::RichText::new(format!("{}x{}", w, h))
+                                                    .color(theme.muted_text),
+                                            )
+                                            .selectable(true),
+                                        );
+                                        over_title_text |= resp.contains_pointer();
+                                        started_title_text_drag |=
+                                            resp.drag_started() || resp.dragged();
+                                    }
+
+                                    if self
+                                        .current_file_size_label_path
+                                        .as_ref()
+                                        .is_some_and(|label_path| label_path == &path)
+                                    {
+                                        if let Some(file_size_label) =
+                                            self.current_file_size_label.as_ref()
+                                        {
+                                            let resp = ui.add(
+                                                egui::Label::new(
+                                                    egui::RichText::new(file_size_label)
+                                                        .color(theme.muted_text),
+                                                )
+                                                .selectable(true),
+                                            );
+                                            over_title_text |= resp.contains_pointer();
+                                            started_title_text_drag |=
+                                                resp.drag_started() || resp.dragged();
+                                        }
+                                    }
+
+                                    if self.manga_mode {
+                                        let resp = ui.add(
+                                            egui::Label::new(
+                                                egui::RichText::new(format!(
+                                                    "{:.0}%",
+                                                    self.zoom * 100.0
+                                                ))
+                                                .color(theme.muted_text),
+                                            )
+                                            .selectable(true),
+                                        );
+                                        over_title_text |= resp.contains_pointer();
+                                        started_title_text_drag |=
+                                            resp.drag_started() || resp.dragged();
+                                    } else {
+                                        over_title_text |= self.draw_zoom_input(ui);
+                                    }
+
+                                    if !self.manga_mode {
+                                        let resp = ui
+                                            .add(
+                                                egui::Label::new(
+                                                    egui::RichText::new(
+                                                        self.active_fit_mode().label(),
+                                                    )
+                                                    .color(theme.muted_text),
+                                                )
+                                                .selectable(true),
+                                            )
+                                            .on_hover_text("Fit mode (cycle_fit_mode)");
+                                        over_title_text |= resp.contains_pointer();
+                                        started_title_text_drag |=
+                                            resp.drag_started() || resp.dragged();
+                                    }
+
+                                    let rating_label = self.ratings.get(&path).label();
+                                    if !rating_label.is_empty() {
+                                        let resp = ui.add(
+                                            egui::Label::new(
+                                                egui::RichText::new(rating_label)
+                                                    .color(egui::Color32::from_rgb(255, 200, 70)),
                                             )
                                             .selectable(true),
                                         );
@@ -24965,42 +33882,26 @@ impl ImageViewer {
                                         started_title_text_drag |=
                                             resp.drag_started() || resp.dragged();
                                     }
-
-                                    if self
-                                        .current_file_size_label_path
-                                        .as_ref()
-                                        .is_some_and(|label_path| label_path == &path)
-                                    {
-                                        if let Some(file_size_label) =
-                                            self.current_file_size_label.as_ref()
-                                        {
-                                            let resp = ui.add(
+                                    if self.config.rating_filter != RatingFilter::All {
+                                        let resp = ui
+                                            .add(
                                                 egui::Label::new(
-                                                    egui::RichText::new(file_size_label)
-                                                        .color(egui::Color32::GRAY),
+                                                    egui::RichText::new(format!(
+                                                        "Showing: {}",
+                                                        self.config.rating_filter.label()
+                                                    ))
+                                                    .color(theme.muted_text),
                                                 )
                                                 .selectable(true),
+                                            )
+                                            .on_hover_text(
+                                                "Next/previous skip other items (cycle_rating_filter)",
                                             );
-                                            over_title_text |= resp.contains_pointer();
-                                            started_title_text_drag |=
-                                                resp.drag_started() || resp.dragged();
-                                        }
+                                        over_title_text |= resp.contains_pointer();
+                                        started_title_text_drag |=
+                                            resp.drag_started() || resp.dragged();
                                     }
 
-                                    let resp = ui.add(
-                                        egui::Label::new(
-                                            egui::RichText::new(format!(
-                                                "{:.0}%",
-                                                self.zoom * 100.0
-                                            ))
-                                            .color(egui::Color32::GRAY),
-                                        )
-                                        .selectable(true),
-                                    );
-                                    over_title_text |= resp.contains_pointer();
-                                    started_title_text_drag |=
-                                        resp.drag_started() || resp.dragged();
-
                                     if self.video_player.is_some() {
                                         let resp = ui.add(
                                             egui::Label::new(
@@ -25022,7 +33923,7 @@ impl ImageViewer {
                                                     self.current_index + 1,
                                                     self.image_list.len()
                                                 ))
-                                                .color(egui::Color32::GRAY),
+                                                .color(theme.muted_text),
                                             )
                                             .selectable(true),
                                         );
@@ -25030,6 +33931,28 @@ impl ImageViewer {
                                         started_title_text_drag |=
                                             resp.drag_started() || resp.dragged();
                                     }
+
+                                    if let Some((count, received, total)) =
+                                        self.url_prefetch.progress()
+                                    {
+                                        let amount = match total {
+                                            Some(total) if total > 0 => format!(
+                                                "{:.0}%",
+                                                received as f64 * 100.0 / total as f64
+                                            ),
+                                            _ => format!(
+                                                "{:.1} MB",
+                                                received as f64 / (1024.0 * 1024.0)
+                                            ),
+                                        };
+                                        ui.label(
+                                            egui::RichText::new(i18n::tr_args(
+                                                "status.prefetching",
+                                                &[("count", &count), ("amount", &amount)],
+                                            ))
+                                            .color(theme.muted_text),
+                                        );
+                                    }
                                 }
                             }
 
@@ -25055,24 +33978,35 @@ impl ImageViewer {
                             fn window_icon_button(
                                 ui: &mut egui::Ui,
                                 kind: WindowButton,
+                                icon_color: egui::Color32,
                             ) -> egui::Response {
                                 // Match the control bar height so the hit area reaches the very top (y=0).
                                 let size = egui::Vec2::new(32.0, 32.0);
                                 let (rect, response) =
                                     ui.allocate_exact_size(size, egui::Sense::click());
+                                accessibility::describe_button(
+                                    &response,
+                                    match kind {
+                                        WindowButton::Menu => "Menu",
+                                        WindowButton::Minimize => "Minimize",
+                                        WindowButton::Maximize => "Maximize",
+                                        WindowButton::Restore => "Restore",
+                                        WindowButton::Close => "Close",
+                                    },
+                                );
 
                                 if ui.is_rect_visible(rect) {
                                     let bg = if response.is_pointer_button_down_on() {
-                                        egui::Color32::from_rgba_unmultiplied(255, 255, 255, 40)
+                                        icon_color.gamma_multiply(40.0 / 255.0)
                                     } else if response.hovered() {
-                                        egui::Color32::from_rgba_unmultiplied(255, 255, 255, 20)
+                                        icon_color.gamma_multiply(20.0 / 255.0)
                                     } else {
                                         egui::Color32::TRANSPARENT
                                     };
                                     ui.painter().rect_filled(rect, 4.0, bg);
 
                                     let stroke =
-                                        egui::Stroke::new(1.6, TITLEBAR_CONTROL_ICON_COLOR);
+                                        egui::Stroke::new(1.6, icon_color);
                                     // Keep icons visually consistent while using a taller hit-rect.
                                     let pad_x = 10.0;
                                     let pad_y = 11.0;
@@ -25133,7 +34067,7 @@ impl ImageViewer {
                             }
 
                             // Close button
-                            if window_icon_button(ui, WindowButton::Close).clicked() {
+                            if window_icon_button(ui, WindowButton::Close, theme.icon).clicked() {
                                 self.request_app_exit();
                             }
 
@@ -25146,7 +34080,7 @@ impl ImageViewer {
                             } else {
                                 WindowButton::Maximize
                             };
-                            if window_icon_button(ui, button).clicked() {
+                            if window_icon_button(ui, button, theme.icon).clicked() {
                                 if self.is_fullscreen {
                                     self.titlebar_previous_mode =
                                         Some(self.current_titlebar_return_mode());
@@ -25173,13 +34107,13 @@ impl ImageViewer {
                             }
 
                             // Minimize button
-                            if window_icon_button(ui, WindowButton::Minimize).clicked() {
+                            if window_icon_button(ui, WindowButton::Minimize, theme.icon).clicked() {
                                 self.request_minimize = true;
                             }
 
                             // Menu button (left of minimize): opens a compact pop-menu.
                             let menu_button_response =
-                                window_icon_button(ui, WindowButton::Menu).on_hover_text("Menu");
+                                window_icon_button(ui, WindowButton::Menu, theme.icon).on_hover_text("Menu");
                             let app_menu_popup_id = ui.make_persistent_id("title_bar_fab_menu");
                             let mut title_bar_menu_active = menu_button_response.hovered();
 
@@ -25253,6 +34187,26 @@ impl ImageViewer {
 
                                     ui.separator();
 
+                                    if self
+                                        .menu_action_row(ui, "Settings", MenuActionIcon::Config)
+                                        .clicked()
+                                    {
+                                        self.open_settings_window();
+                                        close_popup = true;
+                                    }
+
+                                    if self
+                                        .menu_action_row(
+                                            ui,
+                                            "Usage Statistics",
+                                            MenuActionIcon::Stats,
+                                        )
+                                        .clicked()
+                                    {
+                                        self.open_usage_stats_window();
+                                        close_popup = true;
+                                    }
+
                                     if self
                                         .menu_action_row(
                                             ui,
@@ -25764,7 +34718,8 @@ impl ImageViewer {
         }
 
         let screen_rect = ctx.screen_rect();
-        let bar_height = 56.0; // Increased height for bottom padding
+        let theme = self.overlay_theme;
+        let bar_height = theme.video_bar_height;
         let bottom_padding = 8.0; // Gap at the bottom so buttons don't look cramped
 
         // Draw control bar
@@ -25780,11 +34735,7 @@ impl ImageViewer {
                 let painter = ui.painter();
 
                 // Semi-transparent background
-                painter.rect_filled(
-                    bar_rect,
-                    0.0,
-                    egui::Color32::from_rgba_unmultiplied(20, 20, 20, 230),
-                );
+                painter.rect_filled(bar_rect, 0.0, theme.video_bar_fill);
 
                 // Check if mouse is over this bar
                 self.mouse_over_video_controls =
@@ -25813,6 +34764,7 @@ impl ImageViewer {
     fn draw_video_playback_unavailable_controls_inner(&mut self, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
             let play_btn = ui.add(egui::Button::new("▶").min_size(egui::vec2(32.0, 24.0)));
+            accessibility::describe_button(&play_btn, "Play");
             if play_btn.clicked() {
                 self.queue_video_playback_unavailable_popup();
             }
@@ -25833,6 +34785,63 @@ impl ImageViewer {
         }
     }
 
+    /// Draws the thumbnail and timestamp of the seek bar position at `fraction` above the bar.
+    fn draw_seek_hover_preview(
+        ctx: &egui::Context,
+        theme: &theme::OverlayTheme,
+        thumbnails: Option<&mut SeekThumbnails>,
+        bar_inner: egui::Rect,
+        fraction: f32,
+        duration: Duration,
+    ) {
+        let time = duration.mul_f32(fraction);
+        let texture = thumbnails.and_then(|thumbnails| thumbnails.thumbnail_at(ctx, time));
+        let image_size = texture.map_or(egui::Vec2::ZERO, |texture| texture.size_vec2());
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Tooltip,
+            egui::Id::new("seek_hover_preview"),
+        ));
+        let galley = painter.layout_no_wrap(
+            format_duration(time),
+            egui::FontId::proportional(13.0),
+            theme.text,
+        );
+
+        let padding = 4.0;
+        let rows = if texture.is_some() { 3.0 } else { 2.0 };
+        let size = egui::vec2(
+            image_size.x.max(galley.size().x) + padding * 2.0,
+            image_size.y + galley.size().y + padding * rows,
+        );
+        let screen = ctx.screen_rect();
+        let x = (bar_inner.min.x + bar_inner.width() * fraction - size.x / 2.0)
+            .clamp(screen.min.x, (screen.max.x - size.x).max(screen.min.x));
+        let rect = egui::Rect::from_min_size(egui::pos2(x, bar_inner.min.y - 12.0 - size.y), size);
+        painter.rect(
+            rect,
+            theme.corner_radius,
+            theme.panel_fill,
+            egui::Stroke::new(1.0, theme.panel_stroke),
+        );
+        if let Some(texture) = texture {
+            let image_rect = egui::Rect::from_min_size(
+                egui::pos2(rect.center().x - image_size.x / 2.0, rect.min.y + padding),
+                image_size,
+            );
+            painter.image(
+                texture.id(),
+                image_rect,
+                egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                egui::Color32::WHITE,
+            );
+        }
+        let text_pos = egui::pos2(
+            rect.center().x - galley.size().x / 2.0,
+            rect.max.y - padding - galley.size().y,
+        );
+        painter.galley(text_pos, galley, theme.text);
+    }
+
     fn active_seek_pointer_fraction(ctx: &egui::Context, bar_inner: egui::Rect) -> Option<f32> {
         ctx.input(|i| {
             i.pointer
@@ -25903,11 +34912,13 @@ impl ImageViewer {
 
     /// Draw video seekbar and controls (called from draw_video_controls)
     fn draw_video_seekbar_inner(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let theme = self.overlay_theme;
         let video_seek_policy = self.config.video_seek_policy;
         let commit_seek_mode = self.commit_video_seek_mode();
         let mut play_toggle_requested = false;
         let mut file_navigation_requested: Option<VideoFileNavigation> = None;
         let mut audio_track_requested: Option<i32> = None;
+        let mut frame_guides_changed = false;
         let mut subtitle_selection_requested: Option<VideoSubtitleSelection> = None;
         let mut resume_error: Option<String> = None;
 
@@ -25949,8 +34960,38 @@ impl ImageViewer {
             );
 
             // Background bar
-            ui.painter()
-                .rect_filled(bar_inner, 3.0, egui::Color32::from_gray(60));
+            ui.painter().rect_filled(bar_inner, 3.0, theme.track);
+
+            // Trim range between the in and out marks
+            if let (Some(marks), Some(duration)) = (
+                self.video_trim_marks
+                    .as_ref()
+                    .filter(|marks| Some(&marks.path) == current_video_path.as_ref()),
+                duration.filter(|duration| !duration.is_zero()),
+            ) {
+                if marks.start.is_some() || marks.end.is_some() {
+                    let (start, end) = marks.range(duration);
+                    let x = |time: Duration| {
+                        bar_inner.min.x
+                            + bar_inner.width()
+                                * (time.as_secs_f32() / duration.as_secs_f32()).clamp(0.0, 1.0)
+                    };
+                    let range_rect = egui::Rect::from_x_y_ranges(
+                        x(start)..=x(end),
+                        bar_inner.y_range().expand(2.0),
+                    );
+                    let trim_color = egui::Color32::from_rgb(255, 196, 72);
+                    ui.painter()
+                        .rect_filled(range_rect, 2.0, trim_color.gamma_multiply(0.45));
+                    for mark in [marks.start, marks.end].into_iter().flatten() {
+                        ui.painter().vline(
+                            x(mark),
+                            bar_inner.y_range().expand(5.0),
+                            egui::Stroke::new(2.0, trim_color),
+                        );
+                    }
+                }
+            }
 
             // Progress bar (freeze display while dragging to avoid flicker)
             let display_fraction = if self.is_seeking {
@@ -25959,13 +35000,17 @@ impl ImageViewer {
                 position_fraction
             };
             let progress_width = bar_inner.width() * display_fraction;
+            accessibility::describe_slider(
+                &seek_response,
+                "Seek",
+                f64::from(display_fraction * 100.0),
+            );
             if progress_width > 0.0 {
                 let progress_rect = egui::Rect::from_min_size(
                     bar_inner.min,
                     egui::Vec2::new(progress_width, seek_bar_height),
                 );
-                ui.painter()
-                    .rect_filled(progress_rect, 3.0, egui::Color32::from_rgb(66, 133, 244));
+                ui.painter().rect_filled(progress_rect, 3.0, theme.accent);
             }
 
             // Seek handle
@@ -25977,7 +35022,34 @@ impl ImageViewer {
                 6.0
             };
             ui.painter()
-                .circle_filled(handle_center, handle_radius, egui::Color32::WHITE);
+                .circle_filled(handle_center, handle_radius, theme.handle);
+
+            // Thumbnail and time of the hovered position
+            let hover_fraction = if self.is_seeking {
+                self.seek_preview_fraction
+            } else {
+                seek_response
+                    .hover_pos()
+                    .map(|pos| ((pos.x - bar_inner.min.x) / bar_inner.width()).clamp(0.0, 1.0))
+            };
+            if let (Some(fraction), Some(duration)) = (hover_fraction, duration) {
+                let thumbnails = match current_video_path.as_deref() {
+                    Some(path) if self.config.video_seek_thumbnails => {
+                        if !self
+                            .seek_thumbnails
+                            .as_ref()
+                            .is_some_and(|thumbnails| thumbnails.is_for(path, duration))
+                        {
+                            self.seek_thumbnails = Some(SeekThumbnails::new(path, duration));
+                        }
+                        self.seek_thumbnails.as_mut()
+                    }
+                    _ => None,
+                };
+                Self::draw_seek_hover_preview(
+                    ctx, &theme, thumbnails, bar_inner, fraction, duration,
+                );
+            }
 
             // Handle seeking
             let primary_down = ctx.input(|i| i.pointer.button_down(egui::PointerButton::Primary));
@@ -26113,7 +35185,7 @@ impl ImageViewer {
                     .unwrap_or_else(|| "0:00".to_string());
                 ui.label(
                     egui::RichText::new(format!("{} / {}", pos_str, dur_str))
-                        .color(egui::Color32::WHITE)
+                        .color(theme.text)
                         .size(12.0),
                 );
 
@@ -26167,6 +35239,11 @@ impl ImageViewer {
                         egui::Vec2::new(vol_slider_width, 20.0),
                         egui::Sense::click_and_drag(),
                     );
+                    accessibility::describe_slider(
+                        &vol_response,
+                        "Volume",
+                        f64::from(volume_visual * 100.0),
+                    );
 
                     let vol_bar = egui::Rect::from_min_size(
                         egui::pos2(
@@ -26177,8 +35254,7 @@ impl ImageViewer {
                     );
 
                     // Volume background
-                    ui.painter()
-                        .rect_filled(vol_bar, 2.0, egui::Color32::from_gray(60));
+                    ui.painter().rect_filled(vol_bar, 2.0, theme.track);
 
                     // Volume level
                     let vol_width = vol_bar.width() * volume_visual;
@@ -26187,15 +35263,14 @@ impl ImageViewer {
                             vol_bar.min,
                             egui::Vec2::new(vol_width, vol_slider_height),
                         );
-                        ui.painter()
-                            .rect_filled(vol_progress, 2.0, egui::Color32::WHITE);
+                        ui.painter().rect_filled(vol_progress, 2.0, theme.handle);
                     }
 
                     // Volume handle
                     let vol_handle_x = vol_bar.min.x + vol_width;
                     let vol_handle_center = egui::pos2(vol_handle_x, vol_bar.center().y);
                     ui.painter()
-                        .circle_filled(vol_handle_center, 5.0, egui::Color32::WHITE);
+                        .circle_filled(vol_handle_center, 5.0, theme.handle);
 
                     // Handle volume changes
                     if vol_response.dragged() || vol_response.clicked() {
@@ -26319,10 +35394,40 @@ impl ImageViewer {
                     ) {
                         audio_track_requested = Some(selected_track);
                     }
+
+                    let guides_popup_id = Self::solo_video_frame_guides_popup_id();
+                    let guides_popup_open = ui.memory(|mem| mem.is_popup_open(guides_popup_id));
+                    let guides_label = self
+                        .config
+                        .video_aspect_mask
+                        .map(video_guides::aspect_label);
+                    let guides_btn = Self::video_control_icon_button(
+                        ui,
+                        VideoControlIcon::FrameGuides,
+                        "Safe areas and aspect mask",
+                        guides_label.as_deref(),
+                        guides_popup_open
+                            || self.config.video_safe_areas
+                            || self.config.video_aspect_mask.is_some(),
+                    );
+                    if guides_btn.clicked() {
+                        ui.memory_mut(|mem| mem.toggle_popup(guides_popup_id));
+                    }
+                    frame_guides_changed = Self::draw_frame_guides_popup(
+                        ui,
+                        guides_popup_id,
+                        &guides_btn,
+                        &mut self.config.video_safe_areas,
+                        &mut self.config.video_aspect_mask,
+                    );
                 });
             });
         });
 
+        if frame_guides_changed {
+            self.config.save();
+        }
+
         if let Some(err) = resume_error {
             self.set_video_playback_unavailable_runtime(err);
         }
@@ -26360,6 +35465,7 @@ impl ImageViewer {
         frame_count: usize,
         total_duration_ms: u64,
     ) {
+        let theme = self.overlay_theme;
         let default_custom_fps = self.sync_custom_fps_with_current_media_default(
             media_path,
             frame_count,
@@ -26380,6 +35486,11 @@ impl ImageViewer {
         let mut custom_fps = self.webp_custom_fps.clamp(1, max_custom_fps);
         let (slider_rect, slider_response) =
             ui.allocate_exact_size(egui::vec2(80.0, 22.0), egui::Sense::click_and_drag());
+        accessibility::describe_slider(
+            &slider_response,
+            "Animation speed (FPS)",
+            custom_fps.into(),
+        );
 
         let slider_double_clicked = slider_response.double_clicked()
             || (slider_response.hovered()
@@ -26450,12 +35561,11 @@ impl ImageViewer {
                 5.5
             };
 
+            ui.painter().rect_filled(track_rect, 2.0, theme.track);
             ui.painter()
-                .rect_filled(track_rect, 2.0, egui::Color32::from_gray(70));
+                .rect_filled(active_rect, 2.0, theme.animation_accent);
             ui.painter()
-                .rect_filled(active_rect, 2.0, egui::Color32::from_rgb(76, 175, 80));
-            ui.painter()
-                .circle_filled(handle_center, handle_radius, egui::Color32::WHITE);
+                .circle_filled(handle_center, handle_radius, theme.handle);
         }
 
         let fps_list_button = ui.add_sized(
@@ -26526,8 +35636,37 @@ impl ImageViewer {
         ui.spacing_mut().item_spacing.x = old_item_spacing_x;
     }
 
+    /// Seek-bar fraction of `frame`. Once every frame is known the bar is laid out by time,
+    /// so long holds take their share of it; while frames still stream in, by frame index.
+    fn animated_seekbar_fraction(
+        timestamps: Option<&[u64]>,
+        total_duration_ms: u32,
+        frame: usize,
+        display_frame_count: usize,
+    ) -> f32 {
+        match timestamps {
+            Some(timestamps) if total_duration_ms > 0 => timestamps
+                .get(frame)
+                .map_or(1.0, |start| *start as f32 / total_duration_ms as f32),
+            _ => frame as f32 / display_frame_count.saturating_sub(1).max(1) as f32,
+        }
+    }
+
+    /// Moves `img` to `fraction` of the seek bar, laid out as in
+    /// [`Self::animated_seekbar_fraction`], and returns the frame it landed on.
+    fn seek_animated_image(img: &mut LoadedImage, time_based: bool, fraction: f32) -> usize {
+        let total_duration_ms = img.total_duration_ms();
+        if time_based && total_duration_ms > 0 {
+            img.seek_to_time_ms((f64::from(fraction) * f64::from(total_duration_ms)).round() as u64);
+        } else {
+            img.set_frame((img.frame_count().saturating_sub(1) as f32 * fraction).round() as usize);
+        }
+        img.current_frame_index()
+    }
+
     /// Draw GIF seekbar and controls for non-manga mode
     fn draw_gif_seekbar_inner(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let theme = self.overlay_theme;
         let Some(ref img) = self.image else {
             return;
         };
@@ -26544,15 +35683,15 @@ impl ImageViewer {
             display_frame_count = base.max(current_frame + 1).max(1);
             self.anim_seekbar_total_frames = Some(display_frame_count);
         }
-        let position_fraction = if !self.anim_stream_done {
-            if display_frame_count > 1 {
-                current_frame as f32 / (display_frame_count - 1) as f32
-            } else {
-                0.0
-            }
-        } else {
-            img.position_fraction() as f32
-        };
+        let time_based = self.anim_stream_done;
+        let timestamps = time_based.then(|| img.frame_timestamps_ms());
+        let position_ms = img.position_ms();
+        let position_fraction = Self::animated_seekbar_fraction(
+            timestamps.as_deref(),
+            total_duration_ms,
+            current_frame,
+            display_frame_count,
+        );
         let animated_label = Self::animated_image_label_for_path(
             self.image_list.get(self.current_index).or(Some(&img.path)),
         );
@@ -26578,25 +35717,36 @@ impl ImageViewer {
             );
 
             // Background bar
-            ui.painter()
-                .rect_filled(bar_inner, 3.0, egui::Color32::from_gray(60));
+            ui.painter().rect_filled(bar_inner, 3.0, theme.track);
 
             // Progress bar
             let display_fraction = if self.gif_seeking {
                 self.gif_seek_preview_frame
-                    .map(|f| f as f32 / (display_frame_count - 1).max(1) as f32)
+                    .map(|frame| {
+                        Self::animated_seekbar_fraction(
+                            timestamps.as_deref(),
+                            total_duration_ms,
+                            frame,
+                            display_frame_count,
+                        )
+                    })
                     .unwrap_or(position_fraction)
             } else {
                 position_fraction
             };
             let progress_width = bar_inner.width() * display_fraction;
+            accessibility::describe_slider(
+                &seek_response,
+                "Seek",
+                f64::from(display_fraction * 100.0),
+            );
             if progress_width > 0.0 {
                 let progress_rect = egui::Rect::from_min_size(
                     bar_inner.min,
                     egui::Vec2::new(progress_width, seek_bar_height),
                 );
                 ui.painter()
-                    .rect_filled(progress_rect, 3.0, egui::Color32::from_rgb(76, 175, 80));
+                    .rect_filled(progress_rect, 3.0, theme.animation_accent);
                 // Green for GIF
             }
 
@@ -26609,7 +35759,7 @@ impl ImageViewer {
                 6.0
             };
             ui.painter()
-                .circle_filled(handle_center, handle_radius, egui::Color32::WHITE);
+                .circle_filled(handle_center, handle_radius, theme.handle);
 
             // Handle seeking
             let primary_down = ctx.input(|i| i.pointer.button_down(egui::PointerButton::Primary));
@@ -26627,12 +35777,11 @@ impl ImageViewer {
                 {
                     let seek_fraction =
                         ((pos.x - bar_inner.min.x) / bar_inner.width()).clamp(0.0, 1.0);
-                    let target_frame = ((frame_count - 1) as f32 * seek_fraction).round() as usize;
-                    self.gif_seek_preview_frame = Some(target_frame);
-
                     // Update the actual frame
                     if let Some(ref mut img) = self.image {
-                        img.set_frame(target_frame);
+                        let target_frame =
+                            Self::seek_animated_image(img, time_based, seek_fraction);
+                        self.gif_seek_preview_frame = Some(target_frame);
                         self.texture = None; // Force texture rebuild
                     }
                 }
@@ -26643,9 +35792,8 @@ impl ImageViewer {
                 if let Some(pos) = seek_response.interact_pointer_pos() {
                     let seek_fraction =
                         ((pos.x - bar_inner.min.x) / bar_inner.width()).clamp(0.0, 1.0);
-                    let target_frame = ((frame_count - 1) as f32 * seek_fraction).round() as usize;
                     if let Some(ref mut img) = self.image {
-                        img.set_frame(target_frame);
+                        Self::seek_animated_image(img, time_based, seek_fraction);
                         self.texture = None;
                     }
                     ctx.request_repaint();
@@ -26666,6 +35814,10 @@ impl ImageViewer {
                     egui::Button::new(if self.gif_paused { "▶" } else { "⏸" })
                         .min_size(egui::vec2(32.0, 24.0)),
                 );
+                accessibility::describe_button(
+                    &play_btn,
+                    if self.gif_paused { "Play" } else { "Pause" },
+                );
 
                 if play_btn.clicked() {
                     self.gif_paused = !self.gif_paused;
@@ -26699,7 +35851,7 @@ impl ImageViewer {
 
                 // Frame display
                 let duration_secs = total_duration_ms as f64 / 1000.0;
-                let current_time = (position_fraction as f64 * duration_secs).max(0.0);
+                let current_time = position_ms as f64 / 1000.0;
                 ui.label(
                     egui::RichText::new(format!(
                         "Frame {}/{} ({:.1}s / {:.1}s)",
@@ -26708,7 +35860,7 @@ impl ImageViewer {
                         current_time,
                         duration_secs
                     ))
-                    .color(egui::Color32::WHITE)
+                    .color(theme.text)
                     .size(12.0),
                 );
 
@@ -26716,7 +35868,7 @@ impl ImageViewer {
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     ui.label(
                         egui::RichText::new(animated_label)
-                            .color(egui::Color32::from_rgb(76, 175, 80))
+                            .color(theme.animation_accent)
                             .size(14.0),
                     );
                     if show_fps_controls {
@@ -26780,7 +35932,8 @@ impl ImageViewer {
         }
 
         let screen_rect = ctx.screen_rect();
-        let bar_height = 56.0;
+        let theme = self.overlay_theme;
+        let bar_height = theme.video_bar_height;
         let bottom_padding = 8.0;
 
         let bar_rect = egui::Rect::from_min_size(
@@ -26795,11 +35948,7 @@ impl ImageViewer {
                 let painter = ui.painter();
 
                 // Semi-transparent background
-                painter.rect_filled(
-                    bar_rect,
-                    0.0,
-                    egui::Color32::from_rgba_unmultiplied(20, 20, 20, 230),
-                );
+                painter.rect_filled(bar_rect, 0.0, theme.video_bar_fill);
 
                 self.mouse_over_video_controls =
                     self.mouse_over_video_controls || ui.rect_contains_pointer(bar_rect);
@@ -26829,6 +35978,7 @@ impl ImageViewer {
         ctx: &egui::Context,
         video_idx: usize,
     ) {
+        let theme = self.overlay_theme;
         let video_seek_policy = self.config.video_seek_policy;
         let commit_seek_mode = self.commit_video_seek_mode();
         let mut play_toggle_requested = false;
@@ -26878,8 +36028,7 @@ impl ImageViewer {
             );
 
             // Background bar
-            ui.painter()
-                .rect_filled(bar_inner, 3.0, egui::Color32::from_gray(60));
+            ui.painter().rect_filled(bar_inner, 3.0, theme.track);
 
             // Progress bar
             let display_fraction = if self.manga_video_seeking {
@@ -26889,13 +36038,17 @@ impl ImageViewer {
                 position_fraction
             };
             let progress_width = bar_inner.width() * display_fraction;
+            accessibility::describe_slider(
+                &seek_response,
+                "Seek",
+                f64::from(display_fraction * 100.0),
+            );
             if progress_width > 0.0 {
                 let progress_rect = egui::Rect::from_min_size(
                     bar_inner.min,
                     egui::Vec2::new(progress_width, seek_bar_height),
                 );
-                ui.painter()
-                    .rect_filled(progress_rect, 3.0, egui::Color32::from_rgb(66, 133, 244));
+                ui.painter().rect_filled(progress_rect, 3.0, theme.accent);
             }
 
             // Seek handle
@@ -26907,7 +36060,7 @@ impl ImageViewer {
                 6.0
             };
             ui.painter()
-                .circle_filled(handle_center, handle_radius, egui::Color32::WHITE);
+                .circle_filled(handle_center, handle_radius, theme.handle);
 
             // Handle seeking
             let primary_down = ctx.input(|i| i.pointer.button_down(egui::PointerButton::Primary));
@@ -27050,7 +36203,7 @@ impl ImageViewer {
                     .unwrap_or_else(|| "0:00".to_string());
                 ui.label(
                     egui::RichText::new(format!("{} / {}", pos_str, dur_str))
-                        .color(egui::Color32::WHITE)
+                        .color(theme.text)
                         .size(12.0),
                 );
 
@@ -27090,6 +36243,11 @@ impl ImageViewer {
                         egui::Vec2::new(vol_slider_width, 20.0),
                         egui::Sense::click_and_drag(),
                     );
+                    accessibility::describe_slider(
+                        &vol_response,
+                        "Volume",
+                        f64::from(volume * 100.0),
+                    );
 
                     let vol_bar = egui::Rect::from_min_size(
                         egui::pos2(
@@ -27099,8 +36257,7 @@ impl ImageViewer {
                         egui::Vec2::new(vol_slider_width, vol_slider_height),
                     );
 
-                    ui.painter()
-                        .rect_filled(vol_bar, 2.0, egui::Color32::from_gray(60));
+                    ui.painter().rect_filled(vol_bar, 2.0, theme.track);
 
                     let vol_width = vol_bar.width() * volume;
                     if vol_width > 0.0 {
@@ -27108,14 +36265,13 @@ impl ImageViewer {
                             vol_bar.min,
                             egui::Vec2::new(vol_width, vol_slider_height),
                         );
-                        ui.painter()
-                            .rect_filled(vol_progress, 2.0, egui::Color32::WHITE);
+                        ui.painter().rect_filled(vol_progress, 2.0, theme.handle);
                     }
 
                     let vol_handle_x = vol_bar.min.x + vol_width;
                     let vol_handle_center = egui::pos2(vol_handle_x, vol_bar.center().y);
                     ui.painter()
-                        .circle_filled(vol_handle_center, 5.0, egui::Color32::WHITE);
+                        .circle_filled(vol_handle_center, 5.0, theme.handle);
 
                     if vol_response.dragged() || vol_response.clicked() {
                         self.manga_video_volume_dragging = true;
@@ -27237,6 +36393,7 @@ impl ImageViewer {
 
     /// Draw seekbar for animated GIFs in manga mode
     fn draw_manga_gif_seekbar(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, gif_idx: usize) {
+        let theme = self.overlay_theme;
         let Some(img) = self.manga_animated_images.get(&gif_idx) else {
             return;
         };
@@ -27264,15 +36421,15 @@ impl ImageViewer {
             self.manga_anim_seekbar_total_frames
                 .insert(gif_idx, display_frame_count);
         }
-        let position_fraction = if is_streaming {
-            if display_frame_count > 1 {
-                current_frame as f32 / (display_frame_count - 1) as f32
-            } else {
-                0.0
-            }
-        } else {
-            img.position_fraction() as f32
-        };
+        let time_based = !is_streaming;
+        let timestamps = time_based.then(|| img.frame_timestamps_ms());
+        let position_ms = img.position_ms();
+        let position_fraction = Self::animated_seekbar_fraction(
+            timestamps.as_deref(),
+            total_duration_ms,
+            current_frame,
+            display_frame_count,
+        );
         let animated_label = Self::animated_image_label_for_path(self.image_list.get(gif_idx));
         let show_fps_controls = self
             .image_list
@@ -27300,25 +36457,36 @@ impl ImageViewer {
             );
 
             // Background bar
-            ui.painter()
-                .rect_filled(bar_inner, 3.0, egui::Color32::from_gray(60));
+            ui.painter().rect_filled(bar_inner, 3.0, theme.track);
 
             // Progress bar
             let display_fraction = if self.gif_seeking {
                 self.gif_seek_preview_frame
-                    .map(|f| f as f32 / (display_frame_count - 1).max(1) as f32)
+                    .map(|frame| {
+                        Self::animated_seekbar_fraction(
+                            timestamps.as_deref(),
+                            total_duration_ms,
+                            frame,
+                            display_frame_count,
+                        )
+                    })
                     .unwrap_or(position_fraction)
             } else {
                 position_fraction
             };
             let progress_width = bar_inner.width() * display_fraction;
+            accessibility::describe_slider(
+                &seek_response,
+                "Seek",
+                f64::from(display_fraction * 100.0),
+            );
             if progress_width > 0.0 {
                 let progress_rect = egui::Rect::from_min_size(
                     bar_inner.min,
                     egui::Vec2::new(progress_width, seek_bar_height),
                 );
                 ui.painter()
-                    .rect_filled(progress_rect, 3.0, egui::Color32::from_rgb(76, 175, 80));
+                    .rect_filled(progress_rect, 3.0, theme.animation_accent);
                 // Green for GIF
             }
 
@@ -27331,7 +36499,7 @@ impl ImageViewer {
                 6.0
             };
             ui.painter()
-                .circle_filled(handle_center, handle_radius, egui::Color32::WHITE);
+                .circle_filled(handle_center, handle_radius, theme.handle);
 
             // Handle seeking
             let primary_down = ctx.input(|i| i.pointer.button_down(egui::PointerButton::Primary));
@@ -27349,12 +36517,11 @@ impl ImageViewer {
                 {
                     let seek_fraction =
                         ((pos.x - bar_inner.min.x) / bar_inner.width()).clamp(0.0, 1.0);
-                    let target_frame = ((frame_count - 1) as f32 * seek_fraction).round() as usize;
-                    self.gif_seek_preview_frame = Some(target_frame);
-
                     // Update the actual frame
                     if let Some(img) = self.manga_animated_images.get_mut(&gif_idx) {
-                        img.set_frame(target_frame);
+                        let target_frame =
+                            Self::seek_animated_image(img, time_based, seek_fraction);
+                        self.gif_seek_preview_frame = Some(target_frame);
                     }
                     // Force texture update
                     self.manga_texture_cache.remove(gif_idx);
@@ -27366,9 +36533,8 @@ impl ImageViewer {
                 if let Some(pos) = seek_response.interact_pointer_pos() {
                     let seek_fraction =
                         ((pos.x - bar_inner.min.x) / bar_inner.width()).clamp(0.0, 1.0);
-                    let target_frame = ((frame_count - 1) as f32 * seek_fraction).round() as usize;
                     if let Some(img) = self.manga_animated_images.get_mut(&gif_idx) {
-                        img.set_frame(target_frame);
+                        Self::seek_animated_image(img, time_based, seek_fraction);
                     }
                     self.manga_texture_cache.remove(gif_idx);
                     ctx.request_repaint();
@@ -27389,6 +36555,10 @@ impl ImageViewer {
                     egui::Button::new(if self.gif_paused { "▶" } else { "⏸" })
                         .min_size(egui::vec2(32.0, 24.0)),
                 );
+                accessibility::describe_button(
+                    &play_btn,
+                    if self.gif_paused { "Play" } else { "Pause" },
+                );
 
                 if play_btn.clicked() {
                     self.gif_paused = !self.gif_paused;
@@ -27422,7 +36592,7 @@ impl ImageViewer {
 
                 // Frame display
                 let duration_secs = total_duration_ms as f64 / 1000.0;
-                let current_time = (position_fraction as f64 * duration_secs).max(0.0);
+                let current_time = position_ms as f64 / 1000.0;
                 ui.label(
                     egui::RichText::new(format!(
                         "Frame {}/{} ({:.1}s / {:.1}s)",
@@ -27431,7 +36601,7 @@ impl ImageViewer {
                         current_time,
                         duration_secs
                     ))
-                    .color(egui::Color32::WHITE)
+                    .color(theme.text)
                     .size(12.0),
                 );
 
@@ -27439,7 +36609,7 @@ impl ImageViewer {
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     ui.label(
                         egui::RichText::new(animated_label)
-                            .color(egui::Color32::from_rgb(76, 175, 80))
+                            .color(theme.animation_accent)
                             .size(14.0),
                     );
                     if show_fps_controls {
@@ -27543,6 +36713,8 @@ impl ImageViewer {
                 self.resize_start_inner_size = Some(inner_size);
                 self.resize_start_cursor_screen = Some(current_cursor_screen);
                 self.resize_last_size = None;
+                self.window_snap_region = None;
+                self.floating_size_pinned = false;
                 return;
             }
         };
@@ -27741,7 +36913,7 @@ impl ImageViewer {
 
         let mut pointer_pos = ctx.input(|i| i.pointer.hover_pos());
 
-        if self.shortcuts_help_modal_open {
+        if self.input_blocking_overlay_open() {
             self.is_panning = false;
             self.is_resizing = false;
             self.resize_direction = ResizeDirection::None;
@@ -27862,19 +37034,10 @@ impl ImageViewer {
                             self.zoom_target = self.zoom;
                             self.zoom_velocity = 0.0;
                         } else {
-                            // In floating mode, follow cursor when zoomed past 100%
                             let old_zoom = self.zoom;
                             self.zoom_target = self.clamp_zoom(self.zoom_target * factor);
                             self.zoom = self.clamp_zoom(self.zoom * factor);
-
-                            let has_offset = self.offset.length() > 0.1;
-                            if old_zoom > 1.0 || self.zoom > 1.0 || has_offset {
-                                let rect_center = screen_rect.center();
-                                let cursor_offset = pos - rect_center;
-                                let zoom_ratio = self.zoom / old_zoom;
-                                self.offset =
-                                    self.offset * zoom_ratio - cursor_offset * (zoom_ratio - 1.0);
-                            }
+                            self.apply_zoom_anchor(pos, old_zoom, screen_rect);
                             self.zoom_velocity = 0.0;
                             self.maybe_refresh_current_solo_image_lod();
                         }
@@ -27911,15 +37074,7 @@ impl ImageViewer {
                                 let old_zoom = self.zoom;
                                 self.zoom_target = self.clamp_zoom(self.zoom_target * factor);
                                 self.zoom = self.clamp_zoom(self.zoom * factor);
-
-                                let has_offset = self.offset.length() > 0.1;
-                                if old_zoom > 1.0 || self.zoom > 1.0 || has_offset {
-                                    let rect_center = screen_rect.center();
-                                    let cursor_offset = pos - rect_center;
-                                    let zoom_ratio = self.zoom / old_zoom;
-                                    self.offset = self.offset * zoom_ratio
-                                        - cursor_offset * (zoom_ratio - 1.0);
-                                }
+                                self.apply_zoom_anchor(pos, old_zoom, screen_rect);
                                 self.zoom_velocity = 0.0;
                                 self.maybe_refresh_current_solo_image_lod();
                             }
@@ -27976,7 +37131,7 @@ impl ImageViewer {
             // Important: give resize edges priority over the overlay so bottom/bottom-corner resizing works.
             let over_video_controls =
                 self.show_video_controls && hover_resize_direction == ResizeDirection::None && {
-                    let bar_height = 56.0;
+                    let bar_height = self.overlay_theme.video_bar_height;
                     pointer_pos.map_or(false, |pos| pos.y > screen_rect.height() - bar_height)
                 };
             let pointer_over_shortcut_ui =
@@ -28083,9 +37238,10 @@ impl ImageViewer {
                 self.resize_start_cursor_screen = None;
                 self.resize_last_size = None;
             } else if !self.is_resizing {
-                // Handle panning/window dragging (only if not resizing, not seeking, and not over video controls)
-                if pan_down_action
-                    && hover_resize_direction == ResizeDirection::None
+                // Handle panning/window dragging (only if not resizing, not seeking, and not over
+                // video controls). These only guard the start of a drag: a pan that is under way
+                // keeps going when the pointer crosses the video controls as they fade in.
+                let pan_may_start = hover_resize_direction == ResizeDirection::None
                     && !over_video_controls
                     && !self.is_seeking
                     && !self.is_volume_dragging
@@ -28097,8 +37253,8 @@ impl ImageViewer {
                     && !pointer_over_shortcut_ui
                     && !self.manga_autoscroll_active
                     && !primary_consumed_for_autoscroll
-                    && !(over_title_bar && self.mouse_over_title_text)
-                {
+                    && !(over_title_bar && self.mouse_over_title_text);
+                if pan_down_action && (pan_may_start || self.is_panning) {
                     self.manga_shift_wheel_pan_velocity_x = 0.0;
                     if let Some(pos) = pointer_pos {
                         // Check if drag started from the top controls region for window dragging.
@@ -28113,7 +37269,7 @@ impl ImageViewer {
                                 if self.is_fullscreen {
                                     self.reset_floating_window_drag_anchor();
                                     // In fullscreen, pan the image
-                                    let delta = ctx.input(|i| i.pointer.delta());
+                                    let delta = self.single_pointer_drag_delta(ctx);
                                     self.offset += delta;
                                     self.remember_current_fullscreen_view_state();
                                 } else if in_title_bar {
@@ -28122,7 +37278,7 @@ impl ImageViewer {
                                 } else if floating_image_exceeds_window {
                                     self.reset_floating_window_drag_anchor();
                                     // In floating mode when zoomed past 100%, pan image inside window
-                                    let delta = ctx.input(|i| i.pointer.delta());
+                                    let delta = self.single_pointer_drag_delta(ctx);
                                     self.offset += delta;
                                 } else {
                                     // In floating mode at/below 100%, bypass native StartDrag snap behavior.
@@ -28142,6 +37298,9 @@ impl ImageViewer {
                         self.is_panning = false;
                     }
                     self.last_mouse_pos = None;
+                    if self.floating_drag_start_outer_pos.is_some() {
+                        self.remember_floating_placement(ctx);
+                    }
                     self.reset_floating_window_drag_anchor();
 
                     // Set cursor based on hover state
@@ -28217,11 +37376,15 @@ impl ImageViewer {
             // Called after resize handling to avoid fighting with resize on first click frame.
             self.request_floating_autosize(ctx);
 
-            // Handle double-click to fit media to screen (fullscreen) or reset zoom (floating)
+            // Handle double-click to fit media to screen (fullscreen) or reset zoom (floating).
+            // A touch double tap toggles fullscreen instead when that gesture is enabled.
+            let touch_double_tap =
+                self.touch_frame(ctx).double_tap && self.config.touch_double_tap_fullscreen;
             if ctx.input(|i| {
                 i.pointer
                     .button_double_clicked(egui::PointerButton::Primary)
-            }) && !title_ui_blocking
+            }) && !touch_double_tap
+                && !title_ui_blocking
                 && !pointer_over_shortcut_ui
             {
                 self.reset_current_view_rotation(ctx);
@@ -28235,12 +37398,10 @@ impl ImageViewer {
                     let img_h = img_h_u as f32;
 
                     if self.is_fullscreen {
-                        // Fit to whichever fullscreen axis is exhausted first.
+                        // Apply the fullscreen fit mode.
                         if img_w > 0.0 && img_h > 0.0 {
-                            let fit_zoom = self.fit_zoom_for_target_bounds(
-                                screen_rect.size(),
-                                egui::vec2(img_w, img_h),
-                            );
+                            let fit_zoom = self
+                                .fullscreen_fit_zoom(screen_rect.size(), egui::vec2(img_w, img_h));
                             self.zoom = fit_zoom;
                             self.zoom_target = self.zoom;
                         }
@@ -28265,58 +37426,25 @@ impl ImageViewer {
         egui::CentralPanel::default()
             .frame(egui::Frame::none().fill(self.background_color32()))
             .show(ctx, |ui| {
-                // Determine which texture to use and get dimensions
-                let (active_texture, display_dims) = if let Some(ref texture) = self.video_texture {
-                    // Video mode (or video placeholder while the next video is loading)
-                    // Once the real player is active, size/center against the source dimensions
-                    // so high-resolution videos that are decoded to a smaller working texture
-                    // still fill their intended floating window without apparent black bars.
-                    // While we are still showing a retained placeholder, stick to the placeholder
-                    // texture dimensions so the temporary frame does not jump/stretch.
-                    let dims = if self.retained_media_placeholder_visible {
-                        self.video_texture_dims.or_else(|| {
-                            self.video_player.as_ref().and_then(|p| {
-                                let dims = p.dimensions();
-                                (dims.0 > 0 && dims.1 > 0).then_some(dims)
-                            })
-                        })
-                    } else {
-                        self.video_player
-                            .as_ref()
-                            .and_then(|p| {
-                                let dims = p.dimensions();
-                                (dims.0 > 0 && dims.1 > 0).then_some(dims)
-                            })
-                            .or(self.video_texture_dims)
-                    };
-                    (Some(texture), dims)
-                } else if let Some(ref texture) = self.texture {
-                    // Image mode
-                    let dims = if self.retained_media_placeholder_visible {
-                        self.image_texture_dims
-                    } else if let Some(img) = self.image.as_ref() {
-                        let frame = img.current_frame_data();
-                        if Self::solo_texture_dims_match_frame(self.image_texture_dims, frame) {
-                            Some(img.display_dimensions())
-                        } else {
-                            None
-                        }
-                    } else {
-                        self.image_texture_dims
-                    };
-                    (Some(texture), dims)
-                } else {
-                    (None, None)
-                };
+                let checkerboard_texture = self.checkerboard_texture_for_current_image(ctx);
+                let transition = self.image_transition_frame(ctx, ui.max_rect().width());
+                let rotation_turn = self.rotation_turn_frame(ctx);
+                self.last_image_rect = None;
+
+                // Video frame (or the placeholder of the next video) first, then the image.
+                let display_dims = self.solo_draw_dimensions();
+                let active_texture = self.video_texture.as_ref().or(self.texture.as_ref());
 
                 if let (Some(texture), Some((img_w, img_h))) = (active_texture, display_dims) {
                     let available = ui.available_rect_before_wrap();
-                    let precise_rotation_degrees = self.current_precise_rotation_angle_degrees();
+                    let precise_rotation_degrees = self.current_precise_rotation_angle_degrees()
+                        + rotation_turn.as_ref().map_or(0.0, |turn| turn.degrees);
                     let flip_horizontal = !self.manga_mode && self.flip_horizontal;
                     let flip_vertical = !self.manga_mode && self.flip_vertical;
 
                     let base_display_size =
-                        egui::Vec2::new(img_w as f32 * self.zoom, img_h as f32 * self.zoom);
+                        egui::Vec2::new(img_w as f32 * self.zoom, img_h as f32 * self.zoom)
+                            * rotation_turn.as_ref().map_or(1.0, |turn| turn.scale);
                     let display_size = if precise_rotation_degrees.abs() < 0.01 {
                         base_display_size
                     } else {
@@ -28326,6 +37454,15 @@ impl ImageViewer {
                         )
                     };
 
+                    if !self.manga_mode && self.config.pan_min_visible > 0.0 {
+                        self.offset = clamped_pan_offset(
+                            self.offset,
+                            display_size,
+                            available.size(),
+                            self.config.pan_min_visible,
+                        );
+                    }
+
                     // During resize, use the commanded size to compute center to avoid jitter
                     // from frame timing mismatches when window position changes.
                     let center = if self.is_resizing {
@@ -28354,30 +37491,189 @@ impl ImageViewer {
                     } else {
                         available.center() + self.offset
                     };
+                    let center = center
+                        + transition
+                            .as_ref()
+                            .map_or(egui::Vec2::ZERO, |(_, frame)| frame.incoming_offset);
+                    // A turning view swings around its pivot along with the image.
+                    let center = match rotation_turn.as_ref() {
+                        Some(RotationTurnFrame {
+                            degrees,
+                            pivot: Some(pivot),
+                            ..
+                        }) => rotate_quad_point(*pivot, center - *pivot, degrees.to_radians()),
+                        _ => center,
+                    };
                     let image_rect = egui::Rect::from_center_size(center, display_size);
 
-                    let final_rect = image_rect;
+                    let final_rect = image_rect;
+
+                    if let Some(checkerboard) = checkerboard_texture {
+                        let (corners, _) = rotated_texture_quad(
+                            center,
+                            base_display_size,
+                            precise_rotation_degrees.to_radians(),
+                            false,
+                            false,
+                        );
+                        paint_checkerboard_quad(
+                            ui.painter(),
+                            checkerboard,
+                            corners,
+                            base_display_size,
+                            self.config.checkerboard_size as f32 / ctx.pixels_per_point(),
+                        );
+                    }
+
+                    let show_original = self.show_original_held(ctx);
+                    if !self.manga_mode
+                        && !show_original
+                        && (!self.image_adjustments.is_identity() || self.lut.is_some())
+                        && self.adjustment_renderer.is_available()
+                    {
+                        let (corners, uvs) = rotated_texture_quad(
+                            center,
+                            base_display_size,
+                            precise_rotation_degrees.to_radians(),
+                            flip_horizontal,
+                            flip_vertical,
+                        );
+                        ui.painter().add(self.adjustment_renderer.paint_callback(
+                            ui.clip_rect(),
+                            texture.id(),
+                            corners,
+                            uvs,
+                            self.image_adjustments,
+                            self.lut.clone(),
+                        ));
+                    } else if precise_rotation_degrees.abs() < 0.01
+                        && !flip_horizontal
+                        && !flip_vertical
+                    {
+                        ui.painter().image(
+                            texture.id(),
+                            final_rect,
+                            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                            egui::Color32::WHITE,
+                        );
+                    } else {
+                        paint_rotated_texture(
+                            ui.painter(),
+                            texture.id(),
+                            center,
+                            base_display_size,
+                            precise_rotation_degrees.to_radians(),
+                            flip_horizontal,
+                            flip_vertical,
+                            egui::Color32::WHITE,
+                        );
+                    }
+
+                    if let Some((outgoing, frame)) = transition.as_ref() {
+                        ui.painter().image(
+                            outgoing.id(),
+                            frame.outgoing_rect,
+                            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                            egui::Color32::from_white_alpha(
+                                (frame.outgoing_alpha * 255.0).round() as u8
+                            ),
+                        );
+                    }
+                    if self.video_texture.is_none() {
+                        self.last_image_rect = Some(final_rect);
+                    }
+
+                    let texture_id = texture.id();
+                    let view = AnnotationView {
+                        center,
+                        zoom: self.zoom,
+                        image_size: egui::vec2(img_w as f32, img_h as f32),
+                        angle_radians: precise_rotation_degrees.to_radians(),
+                        flip_horizontal,
+                        flip_vertical,
+                    };
+                    self.draw_guides(ui.painter(), view);
+                    if !self.manga_mode && matches!(self.current_media_type, Some(MediaType::Video))
+                    {
+                        self.draw_video_frame_guides(ui.painter(), view);
+                    }
+
+                    if show_original {
+                        self.draw_show_original_badge(ui.painter());
+                    } else if self.annotation_mode_active() {
+                        let view = AnnotationView {
+                            center,
+                            zoom: self.zoom,
+                            image_size: egui::vec2(img_w as f32, img_h as f32),
+                            angle_radians: precise_rotation_degrees.to_radians(),
+                            flip_horizontal,
+                            flip_vertical,
+                        };
+                        let painter = ui.painter().clone();
+                        self.draw_annotation_layer(ctx, &painter, view);
+                    }
+
+                    if self.perspective_mode_active() {
+                        let view = AnnotationView {
+                            center,
+                            zoom: self.zoom,
+                            image_size: egui::vec2(img_w as f32, img_h as f32),
+                            angle_radians: precise_rotation_degrees.to_radians(),
+                            flip_horizontal,
+                            flip_vertical,
+                        };
+                        let painter = ui.painter().clone();
+                        self.draw_perspective_layer(ctx, &painter, view, texture_id);
+                    }
+
+                    if self.free_rotate_held && !self.straighten_mode_active() {
+                        Self::draw_level_grid(ui.painter(), ui.painter().clip_rect());
+                    }
 
-                    if precise_rotation_degrees.abs() < 0.01 && !flip_horizontal && !flip_vertical {
-                        ui.painter().image(
-                            texture.id(),
-                            final_rect,
-                            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
-                            egui::Color32::WHITE,
-                        );
-                    } else {
-                        paint_rotated_texture(
-                            ui.painter(),
-                            texture.id(),
+                    if self.straighten_mode_active() {
+                        let painter = ui.painter().clone();
+                        self.draw_straighten_layer(
+                            ctx,
+                            &painter,
                             center,
                             base_display_size,
                             precise_rotation_degrees.to_radians(),
+                        );
+                    }
+
+                    if self.eyedropper_mode_active() {
+                        let view = AnnotationView {
+                            center,
+                            zoom: self.zoom,
+                            image_size: egui::vec2(img_w as f32, img_h as f32),
+                            angle_radians: precise_rotation_degrees.to_radians(),
                             flip_horizontal,
                             flip_vertical,
-                            egui::Color32::WHITE,
-                        );
+                        };
+                        self.draw_eyedropper_layer(ctx, view);
+                    }
+
+                    if self.magnifier_held(ctx) {
+                        let view = AnnotationView {
+                            center,
+                            zoom: self.zoom,
+                            image_size: egui::vec2(img_w as f32, img_h as f32),
+                            angle_radians: precise_rotation_degrees.to_radians(),
+                            flip_horizontal,
+                            flip_vertical,
+                        };
+                        self.draw_magnifier(ctx, view);
+                    } else {
+                        self.magnifier_texture = None;
                     }
 
+                    self.adjustments_panel_rect = if self.adjustments_panel_open && !self.manga_mode
+                    {
+                        Some(self.draw_adjustments_panel(ctx))
+                    } else {
+                        None
+                    };
+
                     let folder_entry_path = self
                         .image_list
                         .get(self.current_index)
@@ -28395,8 +37691,12 @@ impl ImageViewer {
                         ctx.request_repaint();
                     }
 
-                    if self.pending_media_load.is_some() && !self.retained_media_placeholder_visible
-                    {
+                    // The previous media stays on screen while the next one decodes; mark slow
+                    // decodes so a large file does not look like an ignored key press.
+                    if self.pending_media_load.as_ref().is_some_and(|pending| {
+                        !self.retained_media_placeholder_visible
+                            || pending.started_at.elapsed() >= Self::SLOW_MEDIA_LOAD_SPINNER_DELAY
+                    }) {
                         let time = ui.input(|i| i.time);
                         paint_loading_spinner(ui.painter(), final_rect, time);
                         ctx.request_repaint_after(Duration::from_millis(16));
@@ -28492,6 +37792,10 @@ impl Drop for ImageViewer {
             self.pending_idle_config_sync = false;
             self.config.sync_disk_file_with_template();
         }
+        self.flush_pending_manga_folder_view_state_store();
+        usage_stats::flush();
+        self.remember_video_position();
+        video_resume::flush();
     }
 }
 
@@ -28505,15 +37809,26 @@ impl eframe::App for ImageViewer {
             return;
         }
 
+        self.refresh_overlay_theme(ctx);
+        if ctx.zoom_factor() != self.config.ui_scale {
+            ctx.set_zoom_factor(self.config.ui_scale);
+        }
+        self.keep_overlays_for_keyboard_focus(ctx);
+
         // ============ SINGLE INSTANCE: CHECK FOR INCOMING FILES ============
         // Check if another instance sent us a file path to open
         #[cfg(target_os = "windows")]
         if let Some(ref receiver) = self.file_receiver {
-            if let Some(path) = receiver.try_recv() {
+            if let Some(request) = receiver.try_recv() {
                 self.prepare_single_instance_media_handoff(ctx);
+                self.show_from_tray(ctx);
 
                 // Load the new file
-                self.load_media(&path);
+                self.load_media(&request.path);
+                self.remember_recent_file(&request.path);
+                if request.slideshow && self.slideshow.is_none() {
+                    self.toggle_slideshow();
+                }
 
                 // Bring window to foreground
                 ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
@@ -28523,7 +37838,15 @@ impl eframe::App for ImageViewer {
             }
         }
 
+        self.poll_tray(ctx);
+        self.poll_remote_control(ctx);
+        self.update_taskbar();
+        self.update_media_controls();
+        self.apply_loudness_normalization(ctx);
         self.poll_pending_media_directory_scan(ctx);
+        self.poll_download(ctx);
+        self.update_url_prefetch(ctx);
+        self.poll_jobs(ctx);
         self.poll_pending_solo_probe(ctx);
         self.preload_cached_solo_image_textures_for_current_neighbors(ctx);
         self.poll_pending_media_load(ctx);
@@ -28543,7 +37866,8 @@ impl eframe::App for ImageViewer {
         self.screen_size = ctx.screen_rect().size();
 
         // PERFORMANCE: Check if window is minimized to reduce resource usage
-        let is_minimized = ctx.input(|i| i.raw.viewport().minimized.unwrap_or(false));
+        let is_minimized =
+            ctx.input(|i| i.raw.viewport().minimized.unwrap_or(false)) || self.hidden_in_tray;
 
         // When minimized, skip most processing to save CPU/GPU
         if is_minimized {
@@ -28559,6 +37883,8 @@ impl eframe::App for ImageViewer {
 
         self.handle_masonry_preload_focus_loss(ctx);
         self.update_pointer_activity_tracking(ctx);
+        self.advance_slideshow(ctx);
+        self.watch_external_edit();
 
         // Update FPS stats for the debug overlay (and for general diagnostics).
         // Use the previous frame's activity classification so low-rate overlay polls
@@ -28566,8 +37892,8 @@ impl eframe::App for ImageViewer {
         let fps_frame_dt_hint_s = ctx.input(|i| Some(i.stable_dt));
         self.update_fps_stats(self.fps_last_frame_was_active, fps_frame_dt_hint_s);
 
-        // Lazily install large CJK fonts only when we actually have a filename that needs them.
-        self.ensure_windows_cjk_fonts_if_needed(ctx);
+        // Lazily install large script fonts only when a filename actually needs them.
+        self.ensure_fallback_fonts(ctx);
 
         // Apply requested startup window mode (exactly once).
         if !self.startup_window_mode_applied {
@@ -28577,16 +37903,29 @@ impl eframe::App for ImageViewer {
             }
         }
 
-        // Handle file drops (disabled while the help modal is open).
-        if !self.shortcuts_help_modal_open {
-            ctx.input(|i| {
-                if !i.raw.dropped_files.is_empty() {
-                    if let Some(path) = i.raw.dropped_files[0].path.clone() {
-                        // Layout will be applied via `image_changed`.
-                        self.load_image(&path);
-                    }
-                }
+        // Handle file drops (disabled while the help modal or settings window is open).
+        if !self.input_blocking_overlay_open() {
+            let dropped: Vec<PathBuf> = ctx.input(|i| {
+                i.raw
+                    .dropped_files
+                    .iter()
+                    .filter_map(|file| file.path.clone())
+                    .collect()
             });
+            let is_lut = |path: &PathBuf| {
+                path.extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("cube"))
+            };
+            if dropped.len() == 1 && is_lut(&dropped[0]) {
+                self.load_lut(&dropped[0]);
+            } else if dropped.len() > 1 {
+                // Several files at once become a playlist queue.
+                self.start_playlist("Dropped files".to_string(), dropped);
+            } else if let Some(path) = dropped.into_iter().next() {
+                // Layout will be applied via `image_changed`.
+                self.load_image(&path);
+                self.remember_recent_file(&path);
+            }
         }
 
         // Window title might have changed due to file drops.
@@ -28600,7 +37939,7 @@ impl eframe::App for ImageViewer {
         // Run this before input so the input handler can properly suppress actions over the video bar.
         let _ = self.update_bottom_overlays_visibility(ctx);
 
-        if self.shortcuts_help_modal_open {
+        if self.input_blocking_overlay_open() {
             self.stop_manga_wheel_scroll();
             self.stop_manga_autoscroll();
             self.manga_scroll_target = self.manga_scroll_offset;
@@ -28613,14 +37952,20 @@ impl eframe::App for ImageViewer {
             self.last_mouse_pos = None;
         }
 
-        // Handle input (disabled while the help modal is open).
-        if !self.shortcuts_help_modal_open {
+        // Handle input (disabled while the help modal or settings window is open).
+        if !self.input_blocking_overlay_open() {
             self.handle_input(ctx);
+            self.handle_touch_gestures(ctx);
+            self.apply_edge_pan(ctx);
+            self.apply_keyboard_pan(ctx);
+            self.apply_free_rotate(ctx);
+        } else {
+            self.touch_tracker.reset();
         }
 
         let viewport_close_requested = ctx.input(|input| input.viewport().close_requested());
         if viewport_close_requested {
-            if self.pending_exit_confirmation || self.has_marked_files() {
+            if self.pending_exit_confirmation || !self.exit_confirmation_reasons().is_empty() {
                 ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
                 if !self.pending_exit_confirmation {
                     self.request_app_exit();
@@ -28655,7 +38000,11 @@ impl eframe::App for ImageViewer {
                 // Fullscreen entry logic will apply the appropriate layout.
                 self.image_changed = false;
             } else {
-                if self.force_floating_layout_once && window_is_maximized {
+                if let Some(state) = self.take_external_edit_reload_view() {
+                    // An external edit was reloaded: keep the window and the view as they were.
+                    self.apply_view_state(&state);
+                    self.pending_media_layout = false;
+                } else if self.force_floating_layout_once && window_is_maximized {
                     self.pending_media_layout = true;
                 } else if self.force_floating_layout_once {
                     self.apply_floating_layout_for_current_image(ctx);
@@ -28675,7 +38024,18 @@ impl eframe::App for ImageViewer {
 
         // Apply layout changes after image rotation (resize window to match new dimensions)
         if self.image_rotated {
-            if self.is_fullscreen {
+            let zoom_before = self.zoom;
+            let pending_turn = self
+                .rotation_turn
+                .as_ref()
+                .filter(|turn| turn.started_at.is_none())
+                .map(|turn| turn.clockwise);
+            let view_turned = pending_turn.is_some_and(|clockwise| {
+                self.turn_view_with_rotation(ctx, clockwise, window_is_maximized)
+            });
+            if view_turned {
+                // Zoom and pan already follow the rotation.
+            } else if self.is_fullscreen {
                 // Fullscreen: fit vertically to screen, centered.
                 // Don't call apply_fullscreen_layout_for_current_image here because it would
                 // try to restore the saved state (which has old rotation). Instead, just
@@ -28683,14 +38043,8 @@ impl eframe::App for ImageViewer {
                 self.offset = egui::Vec2::ZERO;
                 if let Some((img_w, img_h)) = self.media_display_dimensions() {
                     if img_w > 0 && img_h > 0 {
-                        let viewport_bounds = ctx.screen_rect().size().max(egui::vec2(1.0, 1.0));
-                        let monitor = self.monitor_size_points(ctx);
-                        let target_bounds = egui::vec2(
-                            monitor.x.max(viewport_bounds.x),
-                            monitor.y.max(viewport_bounds.y),
-                        );
-                        let z = self.fit_zoom_for_target_bounds(
-                            target_bounds,
+                        let z = self.fullscreen_fit_zoom(
+                            self.fullscreen_target_bounds(ctx),
                             egui::vec2(img_w as f32, img_h as f32),
                         );
                         self.zoom = z;
@@ -28703,6 +38057,12 @@ impl eframe::App for ImageViewer {
                 // Floating: resize window to match new image dimensions (swapped after rotation)
                 self.apply_floating_layout_for_current_image(ctx);
             }
+            if let Some(turn) = self.rotation_turn.as_mut() {
+                if pending_turn.is_some() {
+                    turn.zoom_ratio = zoom_before / self.zoom.max(f32::EPSILON);
+                    turn.started_at = Some(Instant::now());
+                }
+            }
             self.image_rotated = false;
         }
 
@@ -28711,7 +38071,8 @@ impl eframe::App for ImageViewer {
         if self.pending_media_layout {
             if self.media_display_dimensions().is_some() {
                 if self.force_floating_layout_once && window_is_maximized {
-                    ctx.request_repaint_after(Duration::from_millis(16));
+                    // Wait for the window to leave the maximized state; the repaint
+                    // scheduler keeps polling while the layout is pending.
                 } else if self.force_floating_layout_once {
                     self.apply_floating_layout_for_current_image(ctx);
                     self.force_floating_layout_once = false;
@@ -28731,6 +38092,7 @@ impl eframe::App for ImageViewer {
 
         // Process viewport commands
         if self.should_exit {
+            self.remember_floating_placement(ctx);
             ctx.send_viewport_cmd(egui::ViewportCommand::Close);
             return;
         }
@@ -28738,6 +38100,9 @@ impl eframe::App for ImageViewer {
         if self.toggle_fullscreen {
             self.stop_manga_autoscroll();
             let entering_fullscreen = !self.is_fullscreen;
+            if entering_fullscreen {
+                self.remember_floating_placement(ctx);
+            }
             let toggled_from_titlebar = self.toggle_fullscreen_from_titlebar;
             let window_was_maximized = self.current_window_is_maximized(ctx);
             let use_native_transition = self.use_native_fullscreen_window_transition()
@@ -28772,6 +38137,7 @@ impl eframe::App for ImageViewer {
 
                 if entering_fullscreen {
                     self.saved_fullscreen_entry_index = Some(self.current_index);
+                    self.always_on_top_compact_restore = None;
                     self.pending_window_resize = None;
                     self.pending_maximized_layout = false;
 
@@ -28799,11 +38165,7 @@ impl eframe::App for ImageViewer {
 
                         // Use borderless "pseudo-fullscreen" instead of OS fullscreen.
                         // This avoids a brief desktop flash on Windows caused by toggling window styles/swapchain.
-                        let monitor = self.monitor_size_points(ctx);
-                        ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(
-                            egui::Pos2::ZERO,
-                        ));
-                        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(monitor));
+                        self.place_fullscreen_window(ctx, monitors::window_monitor());
                     }
                 } else {
                     // Exiting fullscreen - use delayed resize to prevent flash
@@ -28885,12 +38247,28 @@ impl eframe::App for ImageViewer {
             self.toggle_fullscreen = false;
             self.toggle_fullscreen_force_borderless = false;
             self.toggle_fullscreen_from_titlebar = false;
+            self.window_snap_region = None;
+            self.floating_size_pinned = false;
 
             if let Some(path) = self.current_media_path() {
                 self.pending_window_title = Some(self.compute_window_title_for_path(&path));
             }
         }
 
+        if std::mem::take(&mut self.move_to_next_monitor) {
+            self.move_window_to_next_monitor(ctx);
+        }
+        if let Some(direction) = self.pending_window_snap.take() {
+            self.snap_floating_window(ctx, direction);
+        }
+        if let Some(index) = self.pending_size_preset.take() {
+            self.apply_window_size_preset(ctx, index);
+        }
+        if std::mem::take(&mut self.toggle_always_on_top) {
+            self.toggle_always_on_top_mode(ctx);
+        }
+        self.settle_fullscreen_monitor_move(ctx);
+
         let fullscreen_animation_active = false;
 
         // Process pending window resize (delayed to prevent flash on fullscreen exit)
@@ -28945,7 +38323,13 @@ impl eframe::App for ImageViewer {
         }
 
         if self.request_minimize {
-            ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+            if self.config.minimize_to_tray && self.tray.is_some() {
+                // Hidden instead of minimized; the tray icon shows it again.
+                ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+                self.hidden_in_tray = true;
+            } else {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+            }
             self.request_minimize = false;
         }
 
@@ -28955,14 +38339,23 @@ impl eframe::App for ImageViewer {
         let skip_drawing =
             !self.startup_window_shown && matches!(self.current_media_type, Some(MediaType::Video));
 
+        // A view copy draws one frame with the media alone and captures it.
+        self.receive_view_capture(ctx);
+        let capturing_view = self.view_capture == Some(ViewCapture::Pending);
+
         // Draw controls overlay (top bar for title/buttons) BEFORE the main view.
         // This ensures title-bar hover/selection state is available to suppress gestures
         // (drag/pan/double-click) in the same frame.
-        if !skip_drawing && !self.shortcuts_help_modal_open {
+        if !skip_drawing
+            && !capturing_view
+            && !self.input_blocking_overlay_open()
+            && self.always_on_top_compact_restore.is_none()
+        {
             self.draw_controls(ctx);
         } else {
             self.mouse_over_window_buttons = false;
             self.mouse_over_title_text = false;
+            self.zoom_input_focused = false;
             self.title_bar_menu_active = false;
             self.title_text_dragging = false;
         }
@@ -28975,27 +38368,51 @@ impl eframe::App for ImageViewer {
         };
 
         // Draw video controls overlay (bottom bar for video playback controls)
-        if !skip_drawing && !self.shortcuts_help_modal_open {
+        if !skip_drawing && !capturing_view && !self.input_blocking_overlay_open() {
             self.draw_video_controls(ctx);
             // Also draw manga mode video controls if in manga mode
             self.draw_manga_video_controls(ctx);
         }
 
         // Draw manga mode toggle button and zoom HUD (bottom-right in fullscreen)
-        if !skip_drawing && !self.shortcuts_help_modal_open {
+        if !skip_drawing && !capturing_view && !self.input_blocking_overlay_open() {
             self.draw_manga_zoom_bar(ctx);
             self.draw_manga_toggle_button(ctx);
         }
 
         // Draw FPS overlay (top-right) when enabled.
-        if !skip_drawing {
+        if !skip_drawing && !capturing_view {
             self.draw_fps_overlay(ctx);
+            self.draw_media_directory_scan_spinner(ctx);
+            self.draw_download_overlay(ctx);
+            self.draw_always_on_top_badge(ctx);
             self.draw_file_action_context_menu(ctx);
             self.draw_delete_confirmation_modal(ctx);
             self.draw_rename_modal(ctx);
             self.draw_exit_confirmation_modal(ctx);
             self.draw_shortcuts_help_modal(ctx);
-        }
+            self.draw_settings_window(ctx);
+            self.draw_usage_stats_window(ctx);
+            self.draw_stereo_export_window(ctx);
+            self.draw_scan_cleanup_window(ctx);
+            self.draw_pdf_export_window(ctx);
+            self.draw_animation_export_window(ctx);
+            self.draw_video_trim_window(ctx);
+            self.draw_jobs_panel(ctx);
+            self.draw_batch_rename_window(ctx);
+            self.draw_compare_view(ctx);
+            self.draw_monitor_wall(ctx);
+            self.draw_command_palette(ctx);
+            self.draw_quick_filter_bar(ctx);
+            self.playlist_panel_rect = self.draw_playlist_panel(ctx);
+            self.video_resume_offer_rect = self.draw_video_resume_offer(ctx);
+            self.osd.show(ctx, &self.overlay_theme);
+            accessibility::draw_focus_ring(ctx);
+        }
+        if capturing_view {
+            self.request_view_capture(ctx);
+        }
+        self.show_viewer_windows(ctx);
 
         let (hide_idle_cursor, cursor_idle_repaint_after) = if skip_drawing {
             (false, None)
@@ -29090,6 +38507,14 @@ impl eframe::App for ImageViewer {
                 }
             });
 
+        // A seek or volume drag ends when its bar sees the release. If the bar was not drawn
+        // that frame (the media changed mid-drag), clear the flag here instead of repainting
+        // forever.
+        if (self.is_seeking || self.is_volume_dragging) && !ctx.input(|i| i.pointer.any_down()) {
+            self.is_seeking = false;
+            self.is_volume_dragging = false;
+        }
+
         let any_animation_active = fullscreen_animation_active
             || pending_resize_active
             || texture_animation_active
@@ -29131,45 +38556,97 @@ impl eframe::App for ImageViewer {
         }
 
         self.run_idle_config_sync_if_needed();
+        if self.is_idle {
+            self.flush_pending_manga_folder_view_state_store();
+        }
+
+        if self.pending_media_layout {
+            self.pending_media_layout_since
+                .get_or_insert_with(Instant::now);
+        } else {
+            self.pending_media_layout_since = None;
+        }
+        if video_playing || any_animation_active || media_load_pending {
+            self.video_paused_since = None;
+        }
 
         // Smart repaint scheduling for CPU efficiency:
         // - Active animations: immediate repaint
         // - Visible manga placeholders / queued uploads: poll near 60fps
         // - Off-screen/background manga decode only: poll slowly
-        // - Waiting for video dims: poll at 60fps
+        // - Waiting for video dims: poll at 60fps, then slowly if they do not arrive
         // - Idle with video playing: poll near high-refresh cadence
+        // - Paused video: release its decoder after a while
         // - Time-based auto-hide UI: repaint once at its deadline
         // - Fully idle: push repaint far into the future (event loop will still wake on input)
+        // `low_power_mode` caps the frame pacing at 60fps and triples the background polls.
+        let low_power = self.config.low_power_mode;
+        let frame_pacing = |millis: u64| {
+            Duration::from_millis(if low_power {
+                millis.max(Self::LOW_POWER_FRAME_MS)
+            } else {
+                millis
+            })
+        };
+        let background_poll =
+            |millis: u64| Duration::from_millis(if low_power { millis * 3 } else { millis });
         if any_animation_active {
             if self.masonry_navigation_active_for_heavy_work() {
                 // Pace active masonry redraws near high-refresh cadence instead of spinning
                 // the UI thread as fast as possible under load.
-                ctx.request_repaint_after(Duration::from_millis(7));
+                ctx.request_repaint_after(frame_pacing(7));
+            } else if low_power {
+                ctx.request_repaint_after(frame_pacing(0));
             } else {
                 ctx.request_repaint();
             }
         } else if media_load_pending || manga_video_load_pending {
             ctx.request_repaint_after(Duration::from_millis(16));
-        } else if self.pending_media_layout {
-            ctx.request_repaint_after(Duration::from_millis(16));
+        } else if let Some(since) = self.pending_media_layout_since {
+            // Dimensions normally arrive within a few frames. Keep checking for a layout
+            // that stays pending (e.g. waiting on the window to leave maximized) without
+            // drawing 60 frames a second indefinitely.
+            if since.elapsed() < Self::PENDING_LAYOUT_FAST_POLL {
+                ctx.request_repaint_after(Duration::from_millis(16));
+            } else {
+                ctx.request_repaint_after(background_poll(Self::PENDING_LAYOUT_SLOW_POLL_MS));
+            }
         } else if manga_needs_fast_background_poll {
-            ctx.request_repaint_after(Duration::from_millis(16));
+            ctx.request_repaint_after(background_poll(16));
         } else if manga_background_work_pending {
-            ctx.request_repaint_after(Duration::from_millis(66));
+            ctx.request_repaint_after(background_poll(66));
         } else if manga_video_playing {
             if self.manga_mode && !self.is_masonry_mode() {
                 // Long-strip focused video playback should run near high-refresh cadence.
                 // 16ms caps it around 60 FPS and makes 120/144 FPS sources feel stuttery.
-                ctx.request_repaint_after(Duration::from_millis(7));
+                ctx.request_repaint_after(frame_pacing(7));
             } else {
                 ctx.request_repaint_after(Duration::from_millis(16));
             }
         } else if video_playing {
             // Solo video playback should match the same high-refresh pacing used by
             // long-strip focused video mode when hardware permits.
-            ctx.request_repaint_after(Duration::from_millis(7));
-        } else if self.video_player.is_some() {
-            if let Some(delay) = cursor_idle_repaint_after {
+            ctx.request_repaint_after(frame_pacing(7));
+        } else if let Some(player) = self.video_player.as_mut() {
+            let mut next_repaint = cursor_idle_repaint_after;
+            if !player.is_suspended() {
+                let suspend_after = if low_power {
+                    Self::LOW_POWER_VIDEO_SUSPEND_AFTER
+                } else {
+                    Self::VIDEO_SUSPEND_AFTER
+                };
+                let paused_for = self
+                    .video_paused_since
+                    .get_or_insert_with(Instant::now)
+                    .elapsed();
+                if paused_for >= suspend_after {
+                    player.suspend();
+                } else {
+                    let remaining = suspend_after - paused_for;
+                    next_repaint = Some(next_repaint.map_or(remaining, |d| d.min(remaining)));
+                }
+            }
+            if let Some(delay) = next_repaint {
                 ctx.request_repaint_after(delay);
             }
         } else {
@@ -29310,22 +38787,17 @@ fn get_global_cursor_pos() -> Option<egui::Pos2> {
     None
 }
 
-fn init_runtime_diagnostics() {
+fn init_runtime_diagnostics(config: &Config, cli: &CliArgs) {
     static INIT: OnceLock<()> = OnceLock::new();
 
     INIT.get_or_init(|| {
-        let filter = std::env::var("RIV_LOG")
-            .or_else(|_| std::env::var("RUST_LOG"))
-            .unwrap_or_else(|_| "warn".to_string());
-
-        let env_filter = tracing_subscriber::EnvFilter::try_new(filter)
-            .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("warn"));
-
-        let _ = tracing_subscriber::fmt()
-            .with_env_filter(env_filter)
-            .with_target(false)
-            .compact()
-            .try_init();
+        logging::init(logging::LoggingOptions {
+            level: config.log_level,
+            verbose: cli.verbose,
+            log_to_file: config.log_to_file,
+            max_file_size_bytes: config.log_file_max_size_mb.saturating_mul(1024 * 1024),
+            max_files: config.log_file_max_files,
+        });
 
         if std::env::var_os("RIV_PUFFIN").is_some() {
             puffin::set_scopes_on(true);
@@ -29333,70 +38805,178 @@ fn init_runtime_diagnostics() {
     });
 }
 
-fn install_panic_report_hook() {
-    static INIT: OnceLock<()> = OnceLock::new();
-
-    INIT.get_or_init(|| {
-        let previous_hook = std::panic::take_hook();
-        std::panic::set_hook(Box::new(move |panic_info| {
-            let payload = if let Some(msg) = panic_info.payload().downcast_ref::<&str>() {
-                (*msg).to_string()
-            } else if let Some(msg) = panic_info.payload().downcast_ref::<String>() {
-                msg.clone()
-            } else {
-                "<non-string panic payload>".to_string()
-            };
-
-            let location = panic_info
-                .location()
-                .map(|loc| format!("{}:{}:{}", loc.file(), loc.line(), loc.column()))
-                .unwrap_or_else(|| "<unknown location>".to_string());
-
-            let current_thread = std::thread::current();
-            let thread_name = current_thread.name().unwrap_or("unnamed");
-            let backtrace = std::backtrace::Backtrace::force_capture();
-            let timestamp = format!("{:?}", std::time::SystemTime::now());
-
-            let panic_report = format!(
-                "[{timestamp}] thread='{thread_name}'\nlocation: {location}\npayload: {payload}\n\nbacktrace:\n{backtrace}\n"
-            );
+/// Command-line arguments: an optional media path plus diagnostic flags.
+#[derive(Debug, Default)]
+struct CliArgs {
+    file_path: Option<PathBuf>,
+    /// `--verbose` / `-v`: force debug-level logging for this run.
+    verbose: bool,
+    /// `--diagnose`: write a support bundle zip instead of opening a window. The positional
+    /// path, if any, is the output `.zip` or folder.
+    diagnose: bool,
+    /// `--wallpaper`: rotate the desktop wallpaper through the positional folder (or the
+    /// folder of the positional image) from the tray, without a window.
+    wallpaper: bool,
+    /// `--open-folder`: ask for a folder and open its first file (jump list task).
+    open_folder: bool,
+    /// `--slideshow`: start a slideshow on the opened file (jump list task).
+    slideshow: bool,
+    /// `--bench-decode`: time the JPEG decoders on the positional folder and exit.
+    bench_decode: bool,
+    /// `--portable`: keep settings and caches next to the executable, as a `portable.txt`
+    /// marker there does.
+    portable: bool,
+    /// `--thumbnail`: write a PNG thumbnail of the positional file to the second positional
+    /// path and exit.
+    thumbnail: bool,
+    /// `--size N`: longest side of the `--thumbnail` output.
+    thumbnail_size: Option<u32>,
+    /// Why an argument was rejected, e.g. `--size` without a positive number.
+    usage_error: Option<String>,
+    /// `--probe`: print the positional file's metadata as JSON and exit.
+    probe: bool,
+    /// Second positional argument, the `--thumbnail` output.
+    output_path: Option<PathBuf>,
+}
 
-            let log_dir = std::env::temp_dir().join("rust-image-viewer");
-            if std::fs::create_dir_all(&log_dir).is_ok() {
-                let _ = std::fs::write(log_dir.join("panic.log"), panic_report);
+impl CliArgs {
+    fn parse<I: IntoIterator<Item = String>>(args: I) -> Self {
+        let mut cli = Self::default();
+
+        let mut args = args.into_iter().peekable();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--verbose" | "-v" => cli.verbose = true,
+                "--diagnose" => cli.diagnose = true,
+                "--wallpaper" => cli.wallpaper = true,
+                "--open-folder" => cli.open_folder = true,
+                "--slideshow" => cli.slideshow = true,
+                "--bench-decode" => cli.bench_decode = true,
+                "--portable" => cli.portable = true,
+                "--thumbnail" => cli.thumbnail = true,
+                "--probe" => cli.probe = true,
+                "--size" => {
+                    // A following flag is left alone so it still counts.
+                    let value = args.next_if(|value| !value.starts_with("--"));
+                    match value.as_deref().map(str::parse::<u32>) {
+                        Some(Ok(size)) if size > 0 => cli.thumbnail_size = Some(size),
+                        _ => {
+                            cli.usage_error = Some(format!(
+                                "--size needs a positive number, got `{}`",
+                                value.as_deref().unwrap_or("")
+                            ));
+                        }
+                    }
+                }
+                _ if cli.file_path.is_none() => {
+                    cli.file_path = Some(path_access::without_extended_prefix(PathBuf::from(arg)));
+                }
+                _ if cli.output_path.is_none() => {
+                    cli.output_path = Some(PathBuf::from(arg));
+                }
+                _ => {}
             }
+        }
 
-            previous_hook(panic_info);
-        }));
-    });
+        cli
+    }
 }
 
 fn main() -> eframe::Result<()> {
-    init_runtime_diagnostics();
-    install_panic_report_hook();
+    crash_report::install_panic_hook();
     let _ = async_runtime::init_runtime();
 
     #[cfg(target_os = "windows")]
     windows_env::refresh_process_path_from_registry();
 
     // Parse command line arguments
-    let args: Vec<String> = std::env::args().collect();
-    let image_path = if args.len() > 1 {
-        Some(PathBuf::from(&args[1]))
-    } else {
-        None
-    };
+    #[cfg_attr(not(target_os = "windows"), allow(unused_mut))]
+    let mut cli = CliArgs::parse(std::env::args().skip(1));
+    storage::set_portable(cli.portable);
+
+    if cli.diagnose {
+        let config = Config::load();
+        storage::init(config.cache_dir.as_deref(), config.temp_dir.as_deref());
+        plugins::init(&config.plugin_filters);
+        diagnostics::run(&config, cli.file_path.as_deref());
+        return Ok(());
+    }
+
+    if cli.bench_decode {
+        decode_bench::run(cli.file_path.as_deref());
+        return Ok(());
+    }
+
+    if cli.thumbnail || cli.probe {
+        #[cfg(target_os = "windows")]
+        windows_env::attach_parent_console();
+        if let Some(err) = &cli.usage_error {
+            eprintln!("{err}");
+            std::process::exit(2);
+        }
+        let command = match (cli.file_path.clone(), cli.output_path.clone()) {
+            (Some(input), _) if cli.probe => headless::HeadlessCommand::Probe(input),
+            (Some(input), Some(output)) => headless::HeadlessCommand::Thumbnail {
+                input,
+                output,
+                size: cli
+                    .thumbnail_size
+                    .unwrap_or(headless::DEFAULT_THUMBNAIL_SIZE),
+            },
+            _ => {
+                eprintln!("Usage: --thumbnail <in> <out> [--size N] | --probe <in>");
+                std::process::exit(2);
+            }
+        };
+        let config = Config::load();
+        storage::init(config.cache_dir.as_deref(), config.temp_dir.as_deref());
+        plugins::init(&config.plugin_filters);
+        std::process::exit(headless::run(&config, command));
+    }
+
+    if cli.wallpaper {
+        let config = Config::load();
+        storage::init(config.cache_dir.as_deref(), config.temp_dir.as_deref());
+        init_runtime_diagnostics(&config, &cli);
+        plugins::init(&config.plugin_filters);
+        let start = cli
+            .file_path
+            .clone()
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_default();
+        if let Err(err) = wallpaper::run(&config, &start) {
+            tracing::error!("Wallpaper slideshow failed: {err}");
+            #[cfg(target_os = "windows")]
+            windows_env::show_error_message_box("Wallpaper slideshow", &err);
+        }
+        return Ok(());
+    }
+
+    #[cfg(target_os = "windows")]
+    if cli.open_folder {
+        cli.file_path = windows_env::pick_folder_to_open().and_then(|folder| {
+            get_media_in_directory_bounded(&folder)
+                .unwrap_or_default()
+                .into_iter()
+                .find(|path| !path.is_dir() && get_media_type(path).is_some())
+        });
+    }
 
     // NO FILE = NO WINDOW. Exit immediately if no file is provided.
-    let Some(file_path) = image_path else {
+    let Some(file_path) = cli.file_path.clone() else {
         // No file provided, exit without creating any window
         return Ok(());
     };
 
-    tracing::info!(target: "startup", file = %file_path.display(), "launch request received");
-
-    // Load config early to check single_instance setting
+    // Load config early to check single_instance setting (and logging preferences)
     let config = Config::load();
+    storage::init(config.cache_dir.as_deref(), config.temp_dir.as_deref());
+    init_runtime_diagnostics(&config, &cli);
+    crash_report::set_enabled(config.crash_reports);
+    usage_stats::set_enabled(config.usage_stats);
+    i18n::set_language(config.language);
+
+    tracing::info!(target: "startup", file = %file_path.display(), "launch request received");
     configure_metadata_cache_size_limit(config.metadata_cache_max_size_mb);
     set_metadata_cache_enabled(false);
 
@@ -29405,8 +38985,12 @@ fn main() -> eframe::Result<()> {
     #[cfg(target_os = "windows")]
     let (file_receiver, _lock) = {
         let (receiver, callback) = FileReceiver::new();
-        match single_instance::try_acquire_lock(config.single_instance, Some(&file_path), callback)
-        {
+        match single_instance::try_acquire_lock(
+            config.single_instance,
+            Some(&file_path),
+            cli.slideshow,
+            callback,
+        ) {
             SingleInstanceResult::Primary(lock) => {
                 tracing::debug!(target: "single_instance", "acquired primary instance lock");
                 // We are the primary instance - proceed with window creation
@@ -29429,10 +39013,39 @@ fn main() -> eframe::Result<()> {
     #[cfg(not(target_os = "windows"))]
     let file_receiver: Option<FileReceiver> = None;
 
+    // Plugin formats must be known before the media type is decided.
+    plugins::init(&config.plugin_filters);
+
     // Determine media type and calculate initial window size BEFORE creating the window.
     // This prevents the flash of a default-sized window.
-    let media_type = get_media_type(&file_path);
-    let screen_size = get_primary_monitor_size();
+    let media_type = match file_path.to_str().and_then(remote::parse_url) {
+        Some(url) if remote::is_video_url(&url) => Some(MediaType::Video),
+        Some(_) => Some(MediaType::Image),
+        None => get_media_type(&file_path),
+    };
+    // Reopen on the monitor the floating window was last left on while it is connected;
+    // otherwise center on the primary monitor.
+    let connected_monitors = monitors::enumerate();
+    let startup_placement = config.state_floating_placement.and_then(|placement| {
+        placement
+            .find_monitor(&connected_monitors)
+            .map(|monitor| (monitor, placement.center))
+    });
+    let screen_size = startup_placement.map_or_else(get_primary_monitor_size, |(monitor, _)| {
+        monitor.rect.size() / monitor.scale
+    });
+    // Before the window exists, positions are in the primary monitor's points.
+    let primary_scale = connected_monitors
+        .iter()
+        .find(|monitor| monitor.primary)
+        .map_or(1.0, |monitor| monitor.scale);
+    let startup_position = |size: egui::Vec2| match startup_placement {
+        Some((monitor, center)) => monitor.position_at_points(center, size, primary_scale, 1.0),
+        None => egui::Pos2::new(
+            ((screen_size.x - size.x) * 0.5).max(0.0),
+            ((screen_size.y - size.y) * 0.5).max(0.0),
+        ),
+    };
 
     // For images, we can get dimensions immediately from the file header.
     // For videos, we start hidden and show once GStreamer decodes the first frame.
@@ -29453,23 +39066,13 @@ fn main() -> eframe::Result<()> {
 
             let size =
                 egui::Vec2::new((img_w * fit_zoom).max(200.0), (img_h * fit_zoom).max(150.0));
-
-            // Calculate centered position for images
-            let pos = egui::Pos2::new(
-                ((screen_size.x - size.x) * 0.5).max(0.0),
-                ((screen_size.y - size.y) * 0.5).max(0.0),
-            );
-            (size, pos, true) // Images: show window immediately with correct size
+            (size, startup_position(size), true) // Images: show window immediately with correct size
         }
         Some(MediaType::Video) => {
             if !gstreamer_runtime_available() {
                 // Missing runtime: show immediately with placeholder text in floating mode.
                 let size = egui::Vec2::new(800.0, 600.0);
-                let pos = egui::Pos2::new(
-                    ((screen_size.x - size.x) * 0.5).max(0.0),
-                    ((screen_size.y - size.y) * 0.5).max(0.0),
-                );
-                (size, pos, true)
+                (size, startup_position(size), true)
             } else {
                 // Videos: position window OFF-SCREEN initially
                 // This completely hides the window until the first frame is ready.
@@ -29482,11 +39085,7 @@ fn main() -> eframe::Result<()> {
         None => {
             // Unknown file type, show error window
             let size = egui::Vec2::new(400.0, 200.0);
-            let pos = egui::Pos2::new(
-                ((screen_size.x - size.x) * 0.5).max(0.0),
-                ((screen_size.y - size.y) * 0.5).max(0.0),
-            );
-            (size, pos, true)
+            (size, startup_position(size), true)
         }
     };
 
@@ -29516,6 +39115,7 @@ fn main() -> eframe::Result<()> {
     // - Smart repaint scheduling (no repainting when idle)
     //
     // Note: We don't set fullscreen in the viewport to avoid triggering NVIDIA GSYNC
+    let start_slideshow = cli.slideshow;
     let options = eframe::NativeOptions {
         // Keep the renderer lightweight at idle. This viewer renders 2D UI + a single image/video
         // texture; MSAA and a depth buffer are not required for perceptible quality.
@@ -29542,30 +39142,159 @@ fn main() -> eframe::Result<()> {
         ..Default::default()
     };
 
-    eframe::run_native(
+    let mut recovery = gpu_recovery::Recovery::default();
+    let mut result = eframe::run_native(
         "Image & Video Viewer",
-        options,
-        Box::new(move |cc| {
+        options.clone(),
+        Box::new(|cc| {
             egui_extras::install_image_loaders(&cc.egui_ctx);
-            #[cfg(target_os = "windows")]
-            {
-                Ok(Box::new(ImageViewer::new(
-                    cc,
-                    Some(file_path),
-                    start_visible,
-                    file_receiver,
-                )))
+            if let Some(gl) = cc.gl.as_ref() {
+                diagnostics::log_gl_context_info(gl);
             }
+            #[cfg(target_os = "windows")]
+            let mut viewer = ImageViewer::new(cc, Some(file_path), start_visible, file_receiver);
             #[cfg(not(target_os = "windows"))]
-            {
-                Ok(Box::new(ImageViewer::new(
-                    cc,
-                    Some(file_path),
-                    start_visible,
-                )))
+            let mut viewer = ImageViewer::new(cc, Some(file_path), start_visible);
+            if start_slideshow {
+                viewer.toggle_slideshow();
             }
+            Ok(Box::new(recovery.session(viewer)))
         }),
-    )
+    );
+
+    // After a GPU device loss the viewer comes back here with its state intact; a new event
+    // loop run gives it a new window, context and painter where the old window was.
+    while let Some(mut viewer) = recovery.take_lost_app(&result) {
+        let mut options = options.clone();
+        options.viewport = options.viewport.with_visible(true);
+        if let Some((inner, outer)) = recovery.last_window() {
+            options.viewport = options
+                .viewport
+                .with_inner_size(inner.size())
+                .with_position(outer.min);
+        }
+        result = eframe::run_native(
+            "Image & Video Viewer",
+            options,
+            Box::new(|cc| {
+                egui_extras::install_image_loaders(&cc.egui_ctx);
+                viewer.attach_renderer(cc);
+                Ok(Box::new(recovery.session(viewer)))
+            }),
+        );
+    }
+    result
+}
+
+/// Whether the shortcut dispatch runs `action` in the current view. Pointer, hold and strip
+/// navigation actions are handled by their own code and are never active here.
+fn shortcut_active(
+    action: Action,
+    manga_mode: bool,
+    manga_fullscreen: bool,
+    masonry_fullscreen: bool,
+) -> bool {
+    match action {
+        Action::ToggleFullscreen
+        | Action::GotoFile
+        | Action::Exit
+        | Action::ExitFullscreenOrQuit
+        | Action::OpenSettings
+        | Action::CommandPalette
+        | Action::FilterFolder
+        | Action::MoveToNextMonitor
+        | Action::SnapWindowLeft
+        | Action::SnapWindowRight
+        | Action::SnapWindowUp
+        | Action::SnapWindowDown
+        | Action::WindowSizePreset1
+        | Action::WindowSizePreset2
+        | Action::WindowSizePreset3
+        | Action::ToggleAlwaysOnTop
+        | Action::ToggleSlideshow
+        | Action::TogglePlaylistPanel
+        | Action::TogglePlaylistShuffle
+        | Action::CyclePlaylistRepeat
+        | Action::ResetZoom
+        | Action::Minimize
+        | Action::NewWindow
+        | Action::ToggleZoomSync
+        | Action::UiScaleUp
+        | Action::UiScaleDown
+        | Action::ResetUiScale
+        | Action::CompareImages
+        | Action::CopyViewToClipboard
+        | Action::ExportPdf
+        | Action::BatchRename
+        | Action::ClearRating
+        | Action::Rate1
+        | Action::Rate2
+        | Action::Rate3
+        | Action::Rate4
+        | Action::Rate5
+        | Action::TogglePick
+        | Action::ToggleReject
+        | Action::CycleRatingFilter
+        | Action::SortToFolder1
+        | Action::SortToFolder2
+        | Action::SortToFolder3
+        | Action::SortToFolder4
+        | Action::Close => true,
+        Action::NextImage
+        | Action::PreviousImage
+        | Action::RotateClockwise
+        | Action::RotateCounterClockwise
+        | Action::FlipVertically
+        | Action::FlipHorizontally
+        | Action::SaveOrientedCopy
+        | Action::ToggleAnnotations
+        | Action::ToggleAdjustments
+        | Action::OpenInEditor
+        | Action::ToggleCheckerboard
+        | Action::ToggleThirds
+        | Action::ToggleGrid
+        | Action::ToggleCrosshair
+        | Action::Straighten
+        | Action::Perspective
+        | Action::ToggleEyedropper
+        | Action::StereoExport
+        | Action::ScanCleanup
+        | Action::ConvertAnimation
+        | Action::CycleFitMode
+        | Action::FitWhole
+        | Action::FitWidth
+        | Action::FitHeight
+        | Action::FitFill
+        | Action::FitActualSize
+        | Action::CycleZoomPresets
+        | Action::Zoom25
+        | Action::Zoom50
+        | Action::Zoom100
+        | Action::Zoom200
+        | Action::Zoom400
+        | Action::PanLeft
+        | Action::PanRight
+        | Action::PanUp
+        | Action::PanDown
+        | Action::ZoomIn
+        | Action::ZoomOut
+        | Action::VideoPlayPause
+        | Action::VideoMute
+        | Action::VideoTrimIn
+        | Action::VideoTrimOut
+        | Action::VideoTrim
+        | Action::MonitorWall => !manga_mode,
+        Action::PreciseRotationClockwise | Action::PreciseRotationCounterClockwise => !manga_mode,
+        Action::MangaNextImage
+        | Action::MangaPreviousImage
+        | Action::MangaZoomIn
+        | Action::MangaZoomOut
+        | Action::MangaTogglePageWidth
+        | Action::MangaRotatePage
+        | Action::MangaCyclePageFit => manga_fullscreen && !masonry_fullscreen,
+        Action::MasonryZoomIn | Action::MasonryZoomOut => masonry_fullscreen,
+        _ => false,
+    }
 }
 
 fn build_app_icon() -> egui::IconData {
@@ -29645,7 +39374,61 @@ fn build_fallback_icon() -> egui::IconData {
 
 #[cfg(test)]
 mod tests {
-    use super::{ImageFrame, ImageViewer, MediaType, SoloPreloadMomentum};
+    use super::{
+        clamped_pan_offset, crop_screenshot, shortcut_active, Action, CliArgs, ClickZone, FitMode,
+        ImageFrame, ImageViewer, MediaType, SoloPreloadMomentum, ZoomPreset,
+    };
+    use std::path::PathBuf;
+
+    #[test]
+    fn default_shortcuts_do_not_share_an_input_within_a_view() {
+        use crate::config::{BindingContext, Config, InputBinding};
+
+        let config = Config::default();
+        // (bindings, manga_mode, manga_fullscreen, masonry_fullscreen)
+        let views = [
+            (BindingContext::Image, false, false, false),
+            (BindingContext::Video, false, false, false),
+            (BindingContext::Manga, true, false, false),
+            (BindingContext::Manga, true, true, false),
+            (BindingContext::Manga, true, true, true),
+        ];
+        for (context, manga_mode, manga_fullscreen, masonry_fullscreen) in views {
+            let mut seen: Vec<(InputBinding, Action)> = Vec::new();
+            for action in Action::ALL {
+                if !shortcut_active(action, manga_mode, manga_fullscreen, masonry_fullscreen) {
+                    continue;
+                }
+                for binding in config.bindings_in(context, action).iter() {
+                    if let Some((_, other)) = seen.iter().find(|(seen, _)| seen == binding) {
+                        panic!("{binding:?} runs both {other:?} and {action:?} in {context:?}");
+                    }
+                    seen.push((binding.clone(), action));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn view_capture_crops_to_the_media_in_pixels() {
+        let pixels = (0..16u8)
+            .map(|i| egui::Color32::from_rgb(i, 0, 0))
+            .collect();
+        let image = egui::ColorImage {
+            size: [4, 4],
+            pixels,
+        };
+        // At 2 pixels per point, (0.5, 0.5)-(1.5, 2.5) covers columns 1..3 of rows 1..4.
+        let rect = egui::Rect::from_min_max(egui::pos2(0.5, 0.5), egui::pos2(1.5, 2.5));
+        let (width, height, rgba) = crop_screenshot(&image, rect, 2.0).unwrap();
+        assert_eq!((width, height), (2, 3));
+        let reds: Vec<u8> = rgba.chunks_exact(4).map(|pixel| pixel[0]).collect();
+        assert_eq!(reds, vec![5, 6, 9, 10, 13, 14]);
+        assert!(rgba.chunks_exact(4).all(|pixel| pixel[3] == 255));
+
+        let outside = egui::Rect::from_min_max(egui::pos2(5.0, 5.0), egui::pos2(9.0, 9.0));
+        assert!(crop_screenshot(&image, outside, 1.0).is_none());
+    }
 
     #[test]
     fn solo_probe_offsets_interleave_without_momentum() {
@@ -29782,14 +39565,114 @@ mod tests {
         let monitor = egui::vec2(1920.0, 1080.0);
         let bounds = ImageViewer::floating_monitor_bounds_for_layout(None, old_window, monitor);
 
-        let (_, size) =
-            ImageViewer::floating_layout_size_for_media_bounds(4000.0, 6000.0, bounds).unwrap();
+        let (_, size) = ImageViewer::floating_layout_size_for_media_bounds(
+            4000.0,
+            6000.0,
+            bounds,
+            FitMode::Fit,
+        )
+        .unwrap();
 
         assert_eq!(bounds, monitor);
         assert!((size.x - 720.0).abs() <= f32::EPSILON);
         assert!((size.y - 1080.0).abs() <= f32::EPSILON);
     }
 
+    #[test]
+    fn floating_fit_modes_cap_at_actual_size_and_the_monitor() {
+        let monitor = egui::vec2(1920.0, 1080.0);
+        let layout = |mode| {
+            ImageViewer::floating_layout_size_for_media_bounds(4000.0, 6000.0, monitor, mode)
+                .unwrap()
+        };
+
+        let (zoom, size) = layout(FitMode::FitWidth);
+        assert!((zoom - 0.48).abs() <= 1e-6);
+        assert_eq!(size, egui::vec2(1920.0, 1080.0));
+
+        let (zoom, size) = layout(FitMode::ActualSize);
+        assert_eq!(zoom, 1.0);
+        assert_eq!(size, monitor);
+
+        let (zoom, _) = ImageViewer::floating_layout_size_for_media_bounds(
+            400.0,
+            300.0,
+            monitor,
+            FitMode::Fill,
+        )
+        .unwrap();
+        assert_eq!(zoom, 1.0);
+    }
+
+    #[test]
+    fn zoom_presets_cycle_from_fit_through_the_percentages() {
+        let presets = ZoomPreset::parse_list("fit, 100%, 200, bogus").unwrap();
+        assert_eq!(
+            presets,
+            [
+                ZoomPreset::Fit,
+                ZoomPreset::Zoom(1.0),
+                ZoomPreset::Zoom(2.0)
+            ]
+        );
+        assert_eq!(
+            ZoomPreset::next_in(&presets, 0.37),
+            Some(ZoomPreset::Zoom(1.0))
+        );
+        assert_eq!(
+            ZoomPreset::next_in(&presets, 1.0),
+            Some(ZoomPreset::Zoom(2.0))
+        );
+        assert_eq!(ZoomPreset::next_in(&presets, 2.0), Some(ZoomPreset::Fit));
+
+        let percentages = ZoomPreset::parse_list("50, 100").unwrap();
+        assert_eq!(
+            ZoomPreset::next_in(&percentages, 0.8),
+            Some(ZoomPreset::Zoom(0.5))
+        );
+        assert!(ZoomPreset::parse_list("zero, -5").is_none());
+    }
+
+    #[test]
+    fn click_zones_split_the_window_by_weight() {
+        let zones = ClickZone::parse_list("previous_image, none:2, next_image:1").unwrap();
+        assert_eq!(zones.len(), 3);
+        assert_eq!(zones[0].action, Some(Action::PreviousImage));
+        assert_eq!(zones[1].action, None);
+        assert_eq!(zones[1].to_config_string(), "none:2");
+        assert_eq!(ClickZone::index_at(&zones, 0.2), Some(0));
+        assert_eq!(ClickZone::index_at(&zones, 0.5), Some(1));
+        assert_eq!(ClickZone::index_at(&zones, 0.8), Some(2));
+        assert_eq!(ClickZone::index_at(&zones, 1.0), Some(2));
+
+        assert!(ClickZone::parse_list("next_image, bogus_action").is_none());
+        assert!(ClickZone::parse_list("next_image:0").is_none());
+    }
+
+    #[test]
+    fn pan_clamp_keeps_the_requested_share_on_screen() {
+        let view = egui::vec2(1000.0, 800.0);
+        let far = egui::vec2(5000.0, -5000.0);
+
+        // A 2000x400 image with half of it required: 1000 px wide (the whole view) and 200 px
+        // tall may remain visible.
+        let image = egui::vec2(2000.0, 400.0);
+        assert_eq!(
+            clamped_pan_offset(far, image, view, 0.5),
+            egui::vec2(500.0, -400.0)
+        );
+
+        // At 1.0 the larger axis covers the view and the smaller one stays inside it.
+        assert_eq!(
+            clamped_pan_offset(far, image, view, 1.0),
+            egui::vec2(500.0, -200.0)
+        );
+
+        // Offsets already within the limits are left alone.
+        let near = egui::vec2(30.0, -20.0);
+        assert_eq!(clamped_pan_offset(near, image, view, 1.0), near);
+    }
+
     #[test]
     fn floating_image_navigation_does_not_retain_previous_texture_placeholder() {
         assert!(!ImageViewer::retain_visible_media_placeholder_for_swap(
@@ -29813,4 +39696,58 @@ mod tests {
 
         assert!(viewer.should_short_circuit_frame_for_exit());
     }
+
+    #[test]
+    fn cli_args_take_first_path_and_verbose_flag_in_any_order() {
+        let cli = CliArgs::parse(["--verbose", "a.png", "b.png"].map(String::from));
+        assert!(cli.verbose);
+        assert_eq!(cli.file_path, Some(PathBuf::from("a.png")));
+
+        let cli = CliArgs::parse(["a.png"].map(String::from));
+        assert!(!cli.verbose);
+        assert!(!cli.diagnose);
+        assert_eq!(cli.file_path, Some(PathBuf::from("a.png")));
+
+        let cli = CliArgs::parse(["--diagnose", "bundle.zip"].map(String::from));
+        assert!(cli.diagnose);
+        assert_eq!(cli.file_path, Some(PathBuf::from("bundle.zip")));
+
+        let cli = CliArgs::parse(["Wallpapers", "--wallpaper"].map(String::from));
+        assert!(cli.wallpaper);
+        assert_eq!(cli.file_path, Some(PathBuf::from("Wallpapers")));
+
+        let cli = CliArgs::parse(["--slideshow", "a.png"].map(String::from));
+        assert!(cli.slideshow);
+        assert!(!cli.open_folder);
+        assert_eq!(cli.file_path, Some(PathBuf::from("a.png")));
+
+        let cli = CliArgs::parse(["--portable", "a.png"].map(String::from));
+        assert!(cli.portable);
+        assert_eq!(cli.file_path, Some(PathBuf::from("a.png")));
+
+        let cli = CliArgs::parse([r"\\?\UNC\server\share\a.png"].map(String::from));
+        assert_eq!(cli.file_path, Some(PathBuf::from(r"\\server\share\a.png")));
+    }
+
+    #[test]
+    fn cli_size_takes_only_a_positive_number() {
+        let cli =
+            CliArgs::parse(["--thumbnail", "a.png", "b.png", "--size", "64"].map(String::from));
+        assert_eq!(cli.thumbnail_size, Some(64));
+        assert_eq!(cli.usage_error, None);
+
+        let cli = CliArgs::parse(
+            ["--thumbnail", "a.png", "b.png", "--size", "--probe"].map(String::from),
+        );
+        assert!(cli.probe);
+        assert_eq!(cli.thumbnail_size, None);
+        assert!(cli.usage_error.is_some());
+
+        for size in ["abc", "0", "-5"] {
+            let cli = CliArgs::parse(["--size", size, "a.png"].map(String::from));
+            assert_eq!(cli.thumbnail_size, None);
+            assert!(cli.usage_error.is_some(), "{size}");
+            assert_eq!(cli.file_path, Some(PathBuf::from("a.png")));
+        }
+    }
 }
@@ -35,12 +35,13 @@ use crate::image_loader::{
     get_media_type, is_supported_image, is_supported_video, probe_image_dimensions, LoadedImage,
     MediaType,
 };
-use crate::image_resize::downscale_rgba_if_needed;
+use crate::image_resize::{box_blur_rgba_in_place, downscale_rgba_if_needed};
 use crate::metadata_cache::{
     lookup_cached_dimensions, lookup_cached_dimensions_batch, lookup_cached_static_thumbnail,
     lookup_cached_video_thumbnail, store_cached_dimensions, store_cached_static_thumbnail,
     store_cached_video_thumbnail, CachedImageThumbnail, CachedMediaKind, CachedVideoThumbnail,
 };
+use crate::panel_detection::detect_panel_breaks;
 use crate::video_player::gstreamer_runtime_available;
 use crate::video_thumbnail::{
     extract_video_first_frame_without_gstreamer, probe_video_dimensions_with_gstreamer,
@@ -98,6 +99,19 @@ pub const LOD_SIDE_BUCKETS: &[u32] = &[
     96, 128, 192, 256, 384, 512, 768, 1024, 1536, 2048, 3072, 4096,
 ];
 
+/// Pixel budget for Long Strip pages decoded taller than the GPU texture limit (uploaded as
+/// stacked segments). 48 MP covers an 800x40000 webtoon page at full resolution.
+const MAX_SEGMENTED_PAGE_PIXELS: f64 = 48.0 * 1024.0 * 1024.0;
+
+/// Longest side of the blur-up previews generated by the micro-thumbnail worker.
+pub const MICRO_THUMBNAIL_SIDE: u32 = 32;
+/// Previews are only asked for visible placeholders; a short queue keeps them current.
+const MICRO_REQUEST_QUEUE_CAPACITY: usize = 16;
+/// Box-blur radius (in micro-thumbnail pixels) baked into the preview before upload.
+const MICRO_THUMBNAIL_BLUR_RADIUS: u32 = 2;
+/// Micro-thumbnails are ~4 KiB each; keep a few thousand so scrolling back stays smooth.
+const MAX_CACHED_MICRO_THUMBNAILS: usize = 4096;
+
 /// Media type for manga items (extended to include videos/animations)
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum MangaMediaType {
@@ -130,6 +144,8 @@ pub struct DecodedImage {
     pub decode_time: Duration,
     /// Time spent in the final resize/downscale step after decode.
     pub resize_time: Duration,
+    /// Panel gutters as fractions of the height (static images, when panel detection is on).
+    pub panel_breaks: Vec<f32>,
 }
 
 /// Request sent to the loader thread pool.
@@ -146,6 +162,27 @@ pub struct LoadRequest {
     pub gif_filter: FilterType,
     pub priority: i32, // Lower = higher priority
     pub queued_at: Instant,
+    /// Run webtoon panel detection on the decoded pixels.
+    pub detect_panels: bool,
+    /// Decode pages taller than `max_texture_side` at full width for segmented upload.
+    pub segment_tall_pages: bool,
+}
+
+/// Turns one request into pixels. The loader uses [`MangaLoader::load_single_image`];
+/// tests substitute fake decoders.
+type DecodeFn = Arc<dyn Fn(&LoadRequest) -> Option<DecodedImage> + Send + Sync>;
+
+/// Worker side of the load channels, run on the coordinator thread.
+struct Coordinator {
+    request_rx: Receiver<LoadRequest>,
+    urgent_request_rx: Receiver<LoadRequest>,
+    result_tx: Sender<DecodedImage>,
+    loading_indices: Arc<RwLock<HashMap<usize, usize>>>,
+    loaded_levels: Arc<RwLock<HashMap<usize, u32>>>,
+    retry_state: Arc<RwLock<HashMap<usize, RetryState>>>,
+    shutdown: Arc<AtomicBool>,
+    generation: Arc<AtomicUsize>,
+    decode: DecodeFn,
 }
 
 #[derive(Clone)]
@@ -159,6 +196,34 @@ struct DimResult {
     items: Vec<(usize, u32, u32, MangaMediaType)>,
 }
 
+struct MicroRequest {
+    generation: usize,
+    index: usize,
+    path: PathBuf,
+}
+
+struct MicroResult {
+    generation: usize,
+    index: usize,
+    /// `None` marks items whose preview could not be produced, so they are not re-requested.
+    thumbnail: Option<MicroThumbnail>,
+}
+
+/// Tiny, already blurred RGBA preview of a page.
+#[derive(Clone)]
+pub struct MicroThumbnail {
+    pub pixels: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Micro-thumbnail cache slot: pixels are uploaded lazily the first time the preview is drawn.
+enum MicroThumbnailEntry {
+    Unavailable,
+    Pixels(MicroThumbnail),
+    Texture(egui::TextureHandle),
+}
+
 #[derive(Clone, Copy, Debug)]
 struct RetryState {
     attempts: u8,
@@ -194,6 +259,14 @@ pub struct MangaLoader {
     dim_result_rx: Receiver<DimResult>,
     /// Indices currently queued for async dimension probing (main thread only).
     dim_pending: HashSet<usize>,
+    /// Blur-up preview request channel (main thread -> micro-thumbnail worker).
+    micro_request_tx: Sender<MicroRequest>,
+    /// Blur-up preview result channel (micro-thumbnail worker -> main thread).
+    micro_result_rx: Receiver<MicroResult>,
+    /// Blur-up previews shown while full textures decode.
+    micro_thumbnails: LruCache<usize, MicroThumbnailEntry>,
+    /// Indices whose micro-thumbnail is queued on the micro-thumbnail worker.
+    micro_pending: HashSet<usize>,
     /// Flag to signal shutdown to worker threads
     shutdown: Arc<AtomicBool>,
     /// Current scroll direction: positive = scrolling down, negative = scrolling up
@@ -210,6 +283,10 @@ pub struct MangaLoader {
     visible_page_count: usize,
     /// Long-strip-only viewport coverage equivalent (e.g. 1.5 visible pages).
     strip_visible_item_equivalent: Option<f32>,
+    /// Attach panel breaks to decoded static images (`manga_panel_snap`).
+    detect_panels: bool,
+    /// Allow decodes taller than the texture limit (`manga_segment_tall_pages`, Long Strip).
+    segment_tall_pages: bool,
 }
 
 /// Statistics for monitoring loader performance.
@@ -222,17 +299,31 @@ pub struct LoaderStats {
 impl MangaLoader {
     /// Create a new manga loader with background thread pool.
     pub fn new() -> Self {
+        let decode: DecodeFn = Arc::new(Self::load_single_image);
+        let (loader, coordinator) = Self::with_decoder(decode, MAX_PENDING_UPLOADS);
+        crate::async_runtime::spawn_blocking_or_thread("manga-loader-coordinator", move || {
+            coordinator.run();
+        });
+        loader
+    }
+
+    /// Builds the loader and the not yet started coordinator that decodes with `decode`.
+    /// At most `max_pending_uploads` decoded images wait for the main thread at a time.
+    fn with_decoder(decode: DecodeFn, max_pending_uploads: usize) -> (Self, Coordinator) {
         // Create bounded channels to prevent unbounded memory growth
         let (request_tx, request_rx) = crossbeam_channel::bounded::<LoadRequest>(256);
         let (urgent_request_tx, urgent_request_rx) =
             crossbeam_channel::bounded::<LoadRequest>(URGENT_REQUEST_QUEUE_CAPACITY);
         let (result_tx, result_rx) =
-            crossbeam_channel::bounded::<DecodedImage>(MAX_PENDING_UPLOADS);
+            crossbeam_channel::bounded::<DecodedImage>(max_pending_uploads);
 
         let (dim_request_tx, dim_request_rx) = crossbeam_channel::bounded::<DimRequest>(64);
         let (dim_background_request_tx, dim_background_request_rx) =
             crossbeam_channel::bounded::<DimRequest>(64);
         let (dim_result_tx, dim_result_rx) = crossbeam_channel::bounded::<DimResult>(64);
+        let (micro_request_tx, micro_request_rx) =
+            crossbeam_channel::bounded::<MicroRequest>(MICRO_REQUEST_QUEUE_CAPACITY);
+        let (micro_result_tx, micro_result_rx) = crossbeam_channel::bounded::<MicroResult>(64);
 
         let loading_indices = Arc::new(RwLock::new(HashMap::new()));
         let loaded_levels = Arc::new(RwLock::new(HashMap::new()));
@@ -240,25 +331,18 @@ impl MangaLoader {
         let shutdown = Arc::new(AtomicBool::new(false));
         let generation = Arc::new(AtomicUsize::new(0));
 
-        // Spawn a coordinator thread that processes requests using Rayon
-        let loading_clone = Arc::clone(&loading_indices);
-        let loaded_clone = Arc::clone(&loaded_levels);
-        let retry_clone = Arc::clone(&retry_state);
-        let shutdown_clone = Arc::clone(&shutdown);
-        let generation_clone = Arc::clone(&generation);
-
-        crate::async_runtime::spawn_blocking_or_thread("manga-loader-coordinator", move || {
-            Self::coordinator_loop(
-                request_rx,
-                urgent_request_rx,
-                result_tx,
-                loading_clone,
-                loaded_clone,
-                retry_clone,
-                shutdown_clone,
-                generation_clone,
-            );
-        });
+        // The coordinator processes requests using Rayon once `new` starts its thread.
+        let coordinator = Coordinator {
+            request_rx,
+            urgent_request_rx,
+            result_tx,
+            loading_indices: Arc::clone(&loading_indices),
+            loaded_levels: Arc::clone(&loaded_levels),
+            retry_state: Arc::clone(&retry_state),
+            shutdown: Arc::clone(&shutdown),
+            generation: Arc::clone(&generation),
+            decode,
+        };
 
         // Spawn a lightweight dimension probe worker.
         // This keeps header-based size probes off the UI thread.
@@ -360,7 +444,44 @@ impl MangaLoader {
             }
         });
 
-        Self {
+        // Blur-up previews get their own thread, one file at a time and off the Rayon pool,
+        // so they never hold up dimension probes or page decodes.
+        let shutdown_clone = Arc::clone(&shutdown);
+        let generation_clone = Arc::clone(&generation);
+        let loaded_levels_clone = Arc::clone(&loaded_levels);
+        crate::async_runtime::spawn_blocking_or_thread("manga-micro-thumbnail-worker", move || {
+            while !shutdown_clone.load(Ordering::Acquire) {
+                let req = match micro_request_rx.recv_timeout(Duration::from_millis(500)) {
+                    Ok(req) => req,
+                    Err(crossbeam_channel::RecvTimeoutError::Timeout) => continue,
+                    Err(crossbeam_channel::RecvTimeoutError::Disconnected) => break,
+                };
+
+                if req.generation != generation_clone.load(Ordering::Acquire) {
+                    continue;
+                }
+                // The page itself arrived first; its preview is not needed now, but may be
+                // after an eviction, so nothing is cached for it. The page's own result
+                // clears the pending request.
+                if loaded_levels_clone.read().contains_key(&req.index) {
+                    continue;
+                }
+                let thumbnail = Self::decode_micro_thumbnail(&req.path);
+
+                if micro_result_tx
+                    .send(MicroResult {
+                        generation: req.generation,
+                        index: req.index,
+                        thumbnail,
+                    })
+                    .is_err()
+                {
+                    return;
+                }
+            }
+        });
+
+        let loader = Self {
             request_tx,
             urgent_request_tx,
             result_rx,
@@ -372,6 +493,12 @@ impl MangaLoader {
             dim_background_request_tx,
             dim_result_rx,
             dim_pending: HashSet::new(),
+            micro_request_tx,
+            micro_result_rx,
+            micro_thumbnails: LruCache::new(
+                NonZeroUsize::new(MAX_CACHED_MICRO_THUMBNAILS).expect("cache capacity is non-zero"),
+            ),
+            micro_pending: HashSet::new(),
             shutdown,
             scroll_direction: 1,
             last_visible_index: 0,
@@ -380,7 +507,10 @@ impl MangaLoader {
             stats: LoaderStats::default(),
             visible_page_count: 1,
             strip_visible_item_equivalent: None,
-        }
+            detect_panels: false,
+            segment_tall_pages: false,
+        };
+        (loader, coordinator)
     }
 
     /// Number of indices currently being decoded on background workers.
@@ -619,9 +749,133 @@ impl MangaLoader {
             }
         }
 
+        for _ in 0..max_messages {
+            let Ok(res) = self.micro_result_rx.try_recv() else {
+                break;
+            };
+            if res.generation != self.current_generation {
+                continue;
+            }
+            self.micro_pending.remove(&res.index);
+            let entry = match res.thumbnail {
+                Some(thumbnail) => MicroThumbnailEntry::Pixels(thumbnail),
+                None => MicroThumbnailEntry::Unavailable,
+            };
+            self.micro_thumbnails.put(res.index, entry);
+        }
+
         updated
     }
 
+    /// Queue a blur-up preview for a visible placeholder on the micro-thumbnail worker.
+    ///
+    /// No-op when the preview is cached, already queued, or the item is not a static image.
+    pub fn request_micro_thumbnail(&mut self, image_list: &[PathBuf], index: usize) {
+        if self.micro_pending.contains(&index) || self.micro_thumbnails.contains(&index) {
+            return;
+        }
+
+        let Some(path) = image_list.get(index) else {
+            return;
+        };
+        if !Self::micro_thumbnail_supported(path) {
+            return;
+        }
+
+        match self.micro_request_tx.try_send(MicroRequest {
+            generation: self.current_generation,
+            index,
+            path: path.clone(),
+        }) {
+            Ok(()) => {
+                self.micro_pending.insert(index);
+            }
+            Err(TrySendError::Full(_)) | Err(TrySendError::Disconnected(_)) => {
+                // Backpressure: the placeholder asks again next frame.
+            }
+        }
+    }
+
+    /// Texture for the blur-up preview of `index`, uploading it on first use.
+    pub fn micro_thumbnail_texture(
+        &mut self,
+        ctx: &egui::Context,
+        index: usize,
+    ) -> Option<egui::TextureId> {
+        let entry = self.micro_thumbnails.get_mut(&index)?;
+
+        if let MicroThumbnailEntry::Pixels(thumbnail) = entry {
+            let image = egui::ColorImage::from_rgba_unmultiplied(
+                [thumbnail.width as usize, thumbnail.height as usize],
+                &thumbnail.pixels,
+            );
+            let texture = ctx.load_texture(
+                format!("manga_micro_{index}"),
+                image,
+                egui::TextureOptions::LINEAR,
+            );
+            *entry = MicroThumbnailEntry::Texture(texture);
+        }
+
+        match entry {
+            MicroThumbnailEntry::Texture(texture) => Some(texture.id()),
+            MicroThumbnailEntry::Pixels(_) | MicroThumbnailEntry::Unavailable => None,
+        }
+    }
+
+    fn micro_thumbnail_supported(path: &Path) -> bool {
+        // GIF decode walks every frame; not worth it for a 32px preview.
+        is_supported_image(path)
+            && !path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("gif"))
+    }
+
+    fn decode_micro_thumbnail(path: &Path) -> Option<MicroThumbnail> {
+        if !Self::micro_thumbnail_supported(path) {
+            return None;
+        }
+
+        // Camera JPEGs carry a small thumbnail in their EXIF block; reading it is far cheaper
+        // than decoding the page, which the page loader does anyway.
+        let embedded = Self::probe_image_dimensions_cached(path)
+            .and_then(|(w, h)| LoadedImage::embedded_jpeg_thumbnail(path, w, h));
+        let decoded;
+        let frame = match embedded.as_ref() {
+            Some(frame) => frame,
+            None => {
+                decoded = LoadedImage::load_first_frame_only(
+                    path,
+                    Some(MICRO_THUMBNAIL_SIDE),
+                    FilterType::Triangle,
+                    FilterType::Triangle,
+                )
+                .ok()?;
+                decoded.current_frame_data()
+            }
+        };
+        let (width, height, pixels) = downscale_rgba_if_needed(
+            frame.width,
+            frame.height,
+            &frame.pixels,
+            MICRO_THUMBNAIL_SIDE,
+            FilterType::Triangle,
+        );
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        let mut pixels = pixels.into_owned();
+        box_blur_rgba_in_place(width, height, &mut pixels, MICRO_THUMBNAIL_BLUR_RADIUS);
+
+        Some(MicroThumbnail {
+            pixels,
+            width,
+            height,
+        })
+    }
+
     /// Seed conservative fallback dimensions for uncached items in `[start, end)`.
     ///
     /// This is a recovery path for rare probe stalls: it keeps masonry warm-up moving
@@ -681,233 +935,6 @@ impl MangaLoader {
         seeded
     }
 
-    /// Coordinator loop that processes requests in parallel using Rayon.
-    fn coordinator_loop(
-        request_rx: Receiver<LoadRequest>,
-        urgent_request_rx: Receiver<LoadRequest>,
-        result_tx: Sender<DecodedImage>,
-        loading_indices: Arc<RwLock<HashMap<usize, usize>>>,
-        loaded_levels: Arc<RwLock<HashMap<usize, u32>>>,
-        retry_state: Arc<RwLock<HashMap<usize, RetryState>>>,
-        shutdown: Arc<AtomicBool>,
-        generation: Arc<AtomicUsize>,
-    ) {
-        // Collect requests in batches for parallel processing
-        let mut batch: Vec<LoadRequest> = Vec::with_capacity(16);
-
-        loop {
-            if shutdown.load(Ordering::Relaxed) {
-                break;
-            }
-
-            // Collect available requests (non-blocking after first)
-            batch.clear();
-
-            // Prefer urgent visible retries so sharpening upgrades do not wait behind preload work.
-            crossbeam_channel::select! {
-                recv(urgent_request_rx) -> req => {
-                    if let Ok(req) = req {
-                        batch.push(req);
-                    } else {
-                        continue;
-                    }
-                }
-                recv(request_rx) -> req => {
-                    if let Ok(req) = req {
-                        batch.push(req);
-                    } else {
-                        continue;
-                    }
-                }
-                default(std::time::Duration::from_millis(500)) => continue,
-            }
-
-            while batch.len() < 32 {
-                match urgent_request_rx.try_recv() {
-                    Ok(req) => batch.push(req),
-                    Err(crossbeam_channel::TryRecvError::Empty) => break,
-                    Err(crossbeam_channel::TryRecvError::Disconnected) => break,
-                }
-            }
-
-            // Drain any additional pending requests (non-blocking)
-            while batch.len() < 32 {
-                match request_rx.try_recv() {
-                    Ok(req) => batch.push(req),
-                    Err(crossbeam_channel::TryRecvError::Empty) => break,
-                    Err(crossbeam_channel::TryRecvError::Disconnected) => break,
-                }
-            }
-
-            if batch.is_empty() {
-                continue;
-            }
-
-            // Sort by priority (lower = higher priority = process first)
-            batch.sort_by_key(|r| r.priority);
-
-            // Get current generation for filtering stale requests
-            let current_gen = generation.load(Ordering::Acquire);
-
-            // If the generation changes while we're decoding (e.g., a fast scrollbar jump),
-            // we prefer to drop the whole batch's outputs rather than clog the result channel
-            // with stale work.
-            let generation_changed = || generation.load(Ordering::Acquire) != current_gen;
-
-            enum DecodeOutcome {
-                Skipped,
-                Failed,
-                Decoded(DecodedImage),
-            }
-
-            let process_one = |req: &LoadRequest| -> (usize, usize, DecodeOutcome) {
-                let req_generation = req.generation;
-
-                // Skip if already loaded or if we've been shut down
-                if shutdown.load(Ordering::Relaxed) {
-                    return (req.index, req_generation, DecodeOutcome::Skipped);
-                }
-
-                // Skip stale requests (e.g., after fast scrollbar jumps / cancel).
-                if req_generation != current_gen {
-                    return (req.index, req_generation, DecodeOutcome::Skipped);
-                }
-
-                // Check if already in loaded set
-                {
-                    let loaded = loaded_levels.read();
-                    if loaded
-                        .get(&req.index)
-                        .is_some_and(|side| *side >= req.target_texture_side)
-                    {
-                        return (req.index, req_generation, DecodeOutcome::Skipped);
-                    }
-                }
-
-                // Load the image
-                let queue_wait = req.queued_at.elapsed();
-                let decode_started = Instant::now();
-                let decoded = Self::load_single_image(req);
-                let decode_time = decode_started.elapsed();
-
-                let outcome = match decoded {
-                    Some(mut decoded) => {
-                        decoded.queue_wait = queue_wait;
-                        decoded.decode_time = decode_time;
-                        DecodeOutcome::Decoded(decoded)
-                    }
-                    None => DecodeOutcome::Failed,
-                };
-
-                (req.index, req_generation, outcome)
-            };
-
-            let publish_one = |idx: usize, req_generation: usize, outcome: DecodeOutcome| {
-                // Request has finished one way or another; allow it to be re-requested if needed.
-                Self::clear_loading_if_generation(&loading_indices, idx, req_generation);
-
-                if generation_changed() {
-                    return true;
-                }
-
-                match outcome {
-                    DecodeOutcome::Skipped => {}
-                    DecodeOutcome::Failed => {
-                        Self::register_decode_failure(&loaded_levels, &retry_state, idx);
-                    }
-                    DecodeOutcome::Decoded(decoded) => {
-                        // A decoded payload with empty pixels/dimensions is still useful for metadata
-                        // (e.g., video dimension fallback), but does not satisfy texture preloading.
-                        let has_usable_pixels =
-                            !decoded.pixels.is_empty() && decoded.width > 0 && decoded.height > 0;
-
-                        let loaded_side = decoded.width.max(decoded.height);
-
-                        match result_tx.try_send(decoded) {
-                            Ok(_) => {
-                                if has_usable_pixels {
-                                    let mut loaded = loaded_levels.write();
-                                    loaded
-                                        .entry(idx)
-                                        .and_modify(|side| *side = (*side).max(loaded_side))
-                                        .or_insert(loaded_side);
-                                    retry_state.write().remove(&idx);
-                                } else {
-                                    Self::register_decode_failure(
-                                        &loaded_levels,
-                                        &retry_state,
-                                        idx,
-                                    );
-                                }
-                            }
-                            Err(TrySendError::Full(_decoded)) => {
-                                // Channel full: drop decoded result.
-                                // We intentionally do NOT mark as loaded so the main thread can re-request.
-                                loaded_levels.write().remove(&idx);
-                            }
-                            Err(TrySendError::Disconnected(_decoded)) => {
-                                return false; // Main thread gone, exit
-                            }
-                        }
-                    }
-                }
-
-                true
-            };
-
-            // IMPORTANT: for "urgent" requests (negative priority), decode the single highest
-            // priority request first (serially) so it is not competing with neighbor prefetch.
-            // This is the key to making far jumps feel instant.
-            let urgent_head = batch.first().map_or(false, |r| r.priority < 0);
-            let mut start_index = 0usize;
-
-            if urgent_head {
-                if let Some(first) = batch.first() {
-                    let (idx, req_generation, outcome) = process_one(first);
-                    if !publish_one(idx, req_generation, outcome) {
-                        return;
-                    }
-                    start_index = 1;
-                }
-            }
-
-            if start_index >= batch.len() {
-                continue;
-            }
-
-            let parallel_len = batch.len() - start_index;
-            let (outcome_tx, outcome_rx) = crossbeam_channel::unbounded();
-            let mut disconnected = false;
-
-            rayon::scope(|scope| {
-                for req in batch[start_index..].iter() {
-                    let outcome_tx = outcome_tx.clone();
-                    scope.spawn(move |_| {
-                        let outcome = process_one(req);
-                        let _ = outcome_tx.send(outcome);
-                    });
-                }
-
-                drop(outcome_tx);
-
-                for _ in 0..parallel_len {
-                    let Ok((idx, req_generation, outcome)) = outcome_rx.recv() else {
-                        break;
-                    };
-
-                    if !publish_one(idx, req_generation, outcome) {
-                        disconnected = true;
-                        break;
-                    }
-                }
-            });
-
-            if disconnected {
-                return;
-            }
-        }
-    }
-
     fn clear_loading_if_generation(
         loading_indices: &Arc<RwLock<HashMap<usize, usize>>>,
         index: usize,
@@ -1046,6 +1073,7 @@ impl MangaLoader {
                             queue_wait: Duration::ZERO,
                             decode_time: Duration::ZERO,
                             resize_time,
+                            panel_breaks: Vec::new(),
                         })
                     }
                     None => {
@@ -1067,6 +1095,7 @@ impl MangaLoader {
                             queue_wait: Duration::ZERO,
                             decode_time: Duration::ZERO,
                             resize_time: Duration::ZERO,
+                            panel_breaks: Vec::new(),
                         })
                     }
                 }
@@ -1084,7 +1113,15 @@ impl MangaLoader {
                     .and_then(|ext| ext.to_str())
                     .map(|ext| matches!(ext.to_ascii_lowercase().as_str(), "gif" | "webp"))
                     .unwrap_or(false);
-                if !may_be_animated_by_ext {
+                let segmented_side = if req.segment_tall_pages && !may_be_animated_by_ext {
+                    Self::segmented_page_decode_side(req, original_width, original_height)
+                } else {
+                    None
+                };
+                let decode_side = segmented_side.unwrap_or(effective_texture_side);
+
+                // Segmented decodes are far too large for the thumbnail cache.
+                if !may_be_animated_by_ext && segmented_side.is_none() {
                     if let Some(cached) =
                         lookup_cached_static_thumbnail(&req.path, effective_texture_side)
                     {
@@ -1101,6 +1138,7 @@ impl MangaLoader {
                             queue_wait: Duration::ZERO,
                             decode_time: Duration::ZERO,
                             resize_time: Duration::ZERO,
+                            panel_breaks: Vec::new(),
                         });
                     }
                 }
@@ -1117,7 +1155,7 @@ impl MangaLoader {
                 let mut img = if is_animated_webp {
                     LoadedImage::load_first_frame_only(
                         &req.path,
-                        Some(effective_texture_side),
+                        Some(decode_side),
                         downscale_filter,
                         gif_filter,
                     )
@@ -1125,7 +1163,7 @@ impl MangaLoader {
                 } else {
                     LoadedImage::load_with_max_texture_side(
                         &req.path,
-                        Some(effective_texture_side),
+                        Some(decode_side),
                         downscale_filter,
                         gif_filter,
                     )
@@ -1150,13 +1188,13 @@ impl MangaLoader {
                     frame.width,
                     frame.height,
                     &frame.pixels,
-                    effective_texture_side,
+                    decode_side,
                     downscale_filter,
                 );
                 let resize_time = resize_started.elapsed();
                 let pixels = pixels.into_owned();
 
-                if manga_media_type == MangaMediaType::StaticImage {
+                if manga_media_type == MangaMediaType::StaticImage && segmented_side.is_none() {
                     store_cached_static_thumbnail(
                         &req.path,
                         effective_texture_side,
@@ -1183,11 +1221,29 @@ impl MangaLoader {
                     queue_wait: Duration::ZERO,
                     decode_time: Duration::ZERO,
                     resize_time,
+                    panel_breaks: Vec::new(),
                 })
             }
         }
     }
 
+    /// Longest side to decode a tall Long Strip page at once its requested LOD has reached the
+    /// texture limit. Only the width (which must still fit one texture) and the pixel budget
+    /// bound it; `None` when the page fits a single texture anyway.
+    fn segmented_page_decode_side(req: &LoadRequest, width: u32, height: u32) -> Option<u32> {
+        let max_side = req.max_texture_side.max(1);
+        if height <= max_side || height <= width || req.target_texture_side < max_side {
+            return None;
+        }
+
+        let scale = (MAX_SEGMENTED_PAGE_PIXELS / (f64::from(width) * f64::from(height)))
+            .sqrt()
+            .min(f64::from(max_side) / f64::from(width.max(1)))
+            .min(1.0);
+        let side = (f64::from(height) * scale).floor() as u32;
+        (side > max_side).then_some(side)
+    }
+
     fn quantize_target_texture_side(target_texture_side: u32, max_texture_side: u32) -> u32 {
         let max_side = max_texture_side.max(1);
         let target = target_texture_side.max(1).min(max_side);
@@ -1565,6 +1621,18 @@ impl MangaLoader {
         (ahead, behind)
     }
 
+    /// Enable panel detection for subsequent decode requests.
+    pub fn set_panel_detection(&mut self, enabled: bool) {
+        self.detect_panels = enabled;
+    }
+
+    /// Enable full-resolution decodes of pages taller than the texture limit.
+    ///
+    /// The UI must then split such decodes into several textures before upload.
+    pub fn set_tall_page_segmentation(&mut self, enabled: bool) {
+        self.segment_tall_pages = enabled;
+    }
+
     /// Update the visible item count for adaptive preloading.
     /// Call this after calculating how many items are visible on screen.
     /// Long strip can optionally pass fractional viewport coverage to avoid oversized windows.
@@ -1726,6 +1794,8 @@ impl MangaLoader {
                     gif_filter: request_gif_filter,
                     priority,
                     queued_at: Instant::now(),
+                    detect_panels: self.detect_panels,
+                    segment_tall_pages: self.segment_tall_pages,
                 });
             }
         }
@@ -1771,6 +1841,7 @@ impl MangaLoader {
         for _ in 0..max_items {
             match self.result_rx.try_recv() {
                 Ok(decoded) => {
+                    self.micro_pending.remove(&decoded.index);
                     // Cache dimensions and media type for stable layout
                     let new_dims = (
                         decoded.original_width,
@@ -1831,6 +1902,8 @@ impl MangaLoader {
 
         // Clear existing cache
         self.dimension_cache.clear();
+        self.micro_thumbnails.clear();
+        self.micro_pending.clear();
 
         if image_list.is_empty() {
             return 0;
@@ -1935,6 +2008,12 @@ impl MangaLoader {
         // Clear any queued dimension probes
         self.dim_pending.clear();
 
+        // Previews are keyed by index like the dimension cache and share its lifetime.
+        if !preserve_dimensions {
+            self.micro_thumbnails.clear();
+        }
+        self.micro_pending.clear();
+
         // Drain dimension result channel
         while self.dim_result_rx.try_recv().is_ok() {}
 
@@ -1956,6 +2035,15 @@ impl MangaLoader {
         self.clear_with_dimension_policy(true);
     }
 
+    /// Forget every upload, e.g. after the GPU context was lost; pages load again as they
+    /// are shown.
+    pub fn forget_uploaded_textures(&mut self) {
+        self.loaded_levels.write().clear();
+        self.retry_state.write().clear();
+        self.micro_thumbnails.clear();
+        self.micro_pending.clear();
+    }
+
     /// Mark an index as needing reload (called when cache is evicted).
     pub fn mark_unloaded(&mut self, index: usize) {
         self.loaded_levels.write().remove(&index);
@@ -2048,6 +2136,8 @@ impl MangaLoader {
             gif_filter: request_gif_filter,
             priority: -200_000,
             queued_at: Instant::now(),
+            detect_panels: self.detect_panels,
+            segment_tall_pages: self.segment_tall_pages,
         };
 
         let send_result = match self.urgent_request_tx.try_send(req.clone()) {
@@ -2083,6 +2173,9 @@ impl MangaLoader {
         // Clear loading indices (they'll be re-requested around the new position)
         self.loading_indices.write().clear();
 
+        // Queued previews are dropped by the generation bump; let placeholders re-request them.
+        self.micro_pending.clear();
+
         // Drain result channel to clear stale decoded images.
         // Any drained decoded index may have been marked as loaded by workers,
         // so clear loaded bookkeeping for those entries.
@@ -2153,6 +2246,251 @@ impl MangaLoader {
     }
 }
 
+impl Coordinator {
+    /// Processes requests in parallel using Rayon until shutdown or the main thread is gone.
+    fn run(self) {
+        // Collect requests in batches for parallel processing
+        let mut batch: Vec<LoadRequest> = Vec::with_capacity(16);
+
+        loop {
+            if self.shutdown.load(Ordering::Relaxed) {
+                break;
+            }
+
+            // Collect available requests (non-blocking after first)
+            batch.clear();
+
+            // Prefer urgent visible retries so sharpening upgrades do not wait behind preload work.
+            crossbeam_channel::select! {
+                recv(self.urgent_request_rx) -> req => {
+                    if let Ok(req) = req {
+                        batch.push(req);
+                    } else {
+                        continue;
+                    }
+                }
+                recv(self.request_rx) -> req => {
+                    if let Ok(req) = req {
+                        batch.push(req);
+                    } else {
+                        continue;
+                    }
+                }
+                default(std::time::Duration::from_millis(500)) => continue,
+            }
+
+            self.fill_batch(&mut batch);
+            if !self.process_batch(&mut batch) {
+                return;
+            }
+        }
+    }
+
+    /// Drains queued requests into `batch` without blocking, urgent ones first, up to 32.
+    fn fill_batch(&self, batch: &mut Vec<LoadRequest>) {
+        while batch.len() < 32 {
+            match self.urgent_request_rx.try_recv() {
+                Ok(req) => batch.push(req),
+                Err(crossbeam_channel::TryRecvError::Empty) => break,
+                Err(crossbeam_channel::TryRecvError::Disconnected) => break,
+            }
+        }
+
+        // Drain any additional pending requests (non-blocking)
+        while batch.len() < 32 {
+            match self.request_rx.try_recv() {
+                Ok(req) => batch.push(req),
+                Err(crossbeam_channel::TryRecvError::Empty) => break,
+                Err(crossbeam_channel::TryRecvError::Disconnected) => break,
+            }
+        }
+    }
+
+    /// Decodes one batch and publishes the results. Returns `false` once the main thread
+    /// has dropped the result channel.
+    fn process_batch(&self, batch: &mut [LoadRequest]) -> bool {
+        if batch.is_empty() {
+            return true;
+        }
+
+        let Coordinator {
+            result_tx,
+            loading_indices,
+            loaded_levels,
+            retry_state,
+            shutdown,
+            generation,
+            decode,
+            ..
+        } = self;
+
+        // Sort by priority (lower = higher priority = process first)
+        batch.sort_by_key(|r| r.priority);
+
+        // Get current generation for filtering stale requests
+        let current_gen = generation.load(Ordering::Acquire);
+
+        // If the generation changes while we're decoding (e.g., a fast scrollbar jump),
+        // we prefer to drop the whole batch's outputs rather than clog the result channel
+        // with stale work.
+        let generation_changed = || generation.load(Ordering::Acquire) != current_gen;
+
+        enum DecodeOutcome {
+            Skipped,
+            Failed,
+            Decoded(DecodedImage),
+        }
+
+        let process_one = |req: &LoadRequest| -> (usize, usize, DecodeOutcome) {
+            let req_generation = req.generation;
+
+            // Skip if already loaded or if we've been shut down
+            if shutdown.load(Ordering::Relaxed) {
+                return (req.index, req_generation, DecodeOutcome::Skipped);
+            }
+
+            // Skip stale requests (e.g., after fast scrollbar jumps / cancel).
+            if req_generation != current_gen {
+                return (req.index, req_generation, DecodeOutcome::Skipped);
+            }
+
+            // Check if already in loaded set
+            {
+                let loaded = loaded_levels.read();
+                if loaded
+                    .get(&req.index)
+                    .is_some_and(|side| *side >= req.target_texture_side)
+                {
+                    return (req.index, req_generation, DecodeOutcome::Skipped);
+                }
+            }
+
+            // Load the image
+            let queue_wait = req.queued_at.elapsed();
+            let decode_started = Instant::now();
+            let decoded = decode(req);
+            let decode_time = decode_started.elapsed();
+
+            let outcome = match decoded {
+                Some(mut decoded) => {
+                    decoded.queue_wait = queue_wait;
+                    decoded.decode_time = decode_time;
+                    if req.detect_panels && decoded.media_type == MangaMediaType::StaticImage {
+                        decoded.panel_breaks =
+                            detect_panel_breaks(decoded.width, decoded.height, &decoded.pixels);
+                    }
+                    DecodeOutcome::Decoded(decoded)
+                }
+                None => DecodeOutcome::Failed,
+            };
+
+            (req.index, req_generation, outcome)
+        };
+
+        let publish_one = |idx: usize, req_generation: usize, outcome: DecodeOutcome| {
+            // Request has finished one way or another; allow it to be re-requested if needed.
+            MangaLoader::clear_loading_if_generation(loading_indices, idx, req_generation);
+
+            if generation_changed() {
+                return true;
+            }
+
+            match outcome {
+                DecodeOutcome::Skipped => {}
+                DecodeOutcome::Failed => {
+                    MangaLoader::register_decode_failure(loaded_levels, retry_state, idx);
+                }
+                DecodeOutcome::Decoded(decoded) => {
+                    // A decoded payload with empty pixels/dimensions is still useful for metadata
+                    // (e.g., video dimension fallback), but does not satisfy texture preloading.
+                    let has_usable_pixels =
+                        !decoded.pixels.is_empty() && decoded.width > 0 && decoded.height > 0;
+
+                    let loaded_side = decoded.width.max(decoded.height);
+
+                    match result_tx.try_send(decoded) {
+                        Ok(_) => {
+                            if has_usable_pixels {
+                                let mut loaded = loaded_levels.write();
+                                loaded
+                                    .entry(idx)
+                                    .and_modify(|side| *side = (*side).max(loaded_side))
+                                    .or_insert(loaded_side);
+                                retry_state.write().remove(&idx);
+                            } else {
+                                MangaLoader::register_decode_failure(
+                                    loaded_levels,
+                                    retry_state,
+                                    idx,
+                                );
+                            }
+                        }
+                        Err(TrySendError::Full(_decoded)) => {
+                            // Channel full: drop decoded result.
+                            // We intentionally do NOT mark as loaded so the main thread can re-request.
+                            loaded_levels.write().remove(&idx);
+                        }
+                        Err(TrySendError::Disconnected(_decoded)) => {
+                            return false; // Main thread gone, exit
+                        }
+                    }
+                }
+            }
+
+            true
+        };
+
+        // IMPORTANT: for "urgent" requests (negative priority), decode the single highest
+        // priority request first (serially) so it is not competing with neighbor prefetch.
+        // This is the key to making far jumps feel instant.
+        let urgent_head = batch.first().map_or(false, |r| r.priority < 0);
+        let mut start_index = 0usize;
+
+        if urgent_head {
+            if let Some(first) = batch.first() {
+                let (idx, req_generation, outcome) = process_one(first);
+                if !publish_one(idx, req_generation, outcome) {
+                    return false;
+                }
+                start_index = 1;
+            }
+        }
+
+        if start_index >= batch.len() {
+            return true;
+        }
+
+        let parallel_len = batch.len() - start_index;
+        let (outcome_tx, outcome_rx) = crossbeam_channel::unbounded();
+        let mut disconnected = false;
+
+        rayon::scope(|scope| {
+            for req in batch[start_index..].iter() {
+                let outcome_tx = outcome_tx.clone();
+                scope.spawn(move |_| {
+                    let outcome = process_one(req);
+                    let _ = outcome_tx.send(outcome);
+                });
+            }
+
+            drop(outcome_tx);
+
+            for _ in 0..parallel_len {
+                let Ok((idx, req_generation, outcome)) = outcome_rx.recv() else {
+                    break;
+                };
+
+                if !publish_one(idx, req_generation, outcome) {
+                    disconnected = true;
+                    break;
+                }
+            }
+        });
+
+        !disconnected
+    }
+}
+
 impl Default for MangaLoader {
     fn default() -> Self {
         Self::new()
@@ -2182,12 +2520,28 @@ pub struct MangaTextureCache {
 #[derive(Clone)]
 struct MangaTextureEntry {
     path: PathBuf,
+    /// The whole page, or its top segment for pages taller than the texture limit.
     texture: egui::TextureHandle,
+    /// Remaining segments of a tall page, top to bottom.
+    lower_segments: Vec<egui::TextureHandle>,
+    /// Rows covered by each segment except possibly the last (equals `height` when unsplit).
+    segment_rows: u32,
     width: u32,
     height: u32,
     media_type: MangaMediaType,
 }
 
+impl MangaTextureEntry {
+    fn segments(&self) -> (Vec<egui::TextureId>, f32) {
+        let ids = std::iter::once(&self.texture)
+            .chain(&self.lower_segments)
+            .map(egui::TextureHandle::id)
+            .collect();
+        let fraction = self.segment_rows as f32 / self.height.max(1) as f32;
+        (ids, fraction.min(1.0))
+    }
+}
+
 impl MangaTextureCache {
     pub fn new(max_entries: usize) -> Self {
         let capacity = NonZeroUsize::new(max_entries.max(1)).expect("cache capacity is non-zero");
@@ -2322,6 +2676,30 @@ impl MangaTextureCache {
         })
     }
 
+    /// All textures of an entry top to bottom, plus the share of the page height each full
+    /// segment covers (`1.0` for entries uploaded as a single texture).
+    pub fn get_texture_segments_for_path(
+        &mut self,
+        index: usize,
+        path: &Path,
+    ) -> Option<(Vec<egui::TextureId>, f32)> {
+        if let Some(entry) = self.pinned_entries.get(&index) {
+            return (entry.path.as_path() == path).then(|| entry.segments());
+        }
+
+        self.unpinned_entries
+            .get(&index)
+            .and_then(|entry| (entry.path.as_path() == path).then(|| entry.segments()))
+    }
+
+    /// Whether the entry was uploaded as more than one texture.
+    pub fn is_segmented(&self, index: usize) -> bool {
+        self.pinned_entries
+            .get(&index)
+            .or_else(|| self.unpinned_entries.peek(&index))
+            .is_some_and(|entry| !entry.lower_segments.is_empty())
+    }
+
     pub fn contains_for_path(&self, index: usize, path: &Path) -> bool {
         self.pinned_entries
             .get(&index)
@@ -2343,14 +2721,51 @@ impl MangaTextureCache {
         height: u32,
         media_type: MangaMediaType,
     ) -> Vec<usize> {
-        let entry = MangaTextureEntry {
-            path,
-            texture,
-            width,
-            height,
-            media_type,
-        };
+        self.insert_entry(
+            index,
+            MangaTextureEntry {
+                path,
+                texture,
+                lower_segments: Vec::new(),
+                segment_rows: height,
+                width,
+                height,
+                media_type,
+            },
+        )
+    }
+
+    /// Insert a static page uploaded as stacked segments of `segment_rows` rows each.
+    /// Returns evicted indices if cache was full.
+    pub fn insert_segmented(
+        &mut self,
+        index: usize,
+        path: PathBuf,
+        mut segments: Vec<egui::TextureHandle>,
+        segment_rows: u32,
+        width: u32,
+        height: u32,
+    ) -> Vec<usize> {
+        if segments.is_empty() {
+            return Vec::new();
+        }
+        let texture = segments.remove(0);
 
+        self.insert_entry(
+            index,
+            MangaTextureEntry {
+                path,
+                texture,
+                lower_segments: segments,
+                segment_rows,
+                width,
+                height,
+                media_type: MangaMediaType::StaticImage,
+            },
+        )
+    }
+
+    fn insert_entry(&mut self, index: usize, entry: MangaTextureEntry) -> Vec<usize> {
         if self.pinned_indices.contains(&index) {
             self.unpinned_entries.pop(&index);
             self.pinned_entries.insert(index, entry);
@@ -2377,6 +2792,8 @@ impl MangaTextureCache {
                 return false;
             }
             entry.texture = texture;
+            entry.lower_segments.clear();
+            entry.segment_rows = height;
             entry.width = width;
             entry.height = height;
             return true;
@@ -2387,6 +2804,8 @@ impl MangaTextureCache {
                 return false;
             }
             entry.texture = texture;
+            entry.lower_segments.clear();
+            entry.segment_rows = height;
             entry.width = width;
             entry.height = height;
             return true;
@@ -2428,3 +2847,188 @@ impl Default for MangaTextureCache {
         Self::new(DEFAULT_CACHED_TEXTURES)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Coordinator, DecodeFn, DecodedImage, LoadRequest, MangaLoader, MangaMediaType};
+    use image::imageops::FilterType;
+    use parking_lot::Mutex;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// Requested and decoded texture side; a fake page counts as loaded at this LOD.
+    const SIDE: u32 = 256;
+
+    fn pages(count: usize) -> Vec<PathBuf> {
+        (0..count)
+            .map(|index| PathBuf::from(format!("page_{index:03}.png")))
+            .collect()
+    }
+
+    fn fake_page(req: &LoadRequest) -> DecodedImage {
+        DecodedImage {
+            index: req.index,
+            path: req.path.clone(),
+            pixels: vec![255; (16 * SIDE * 4) as usize],
+            width: 16,
+            height: SIDE,
+            original_width: 16,
+            original_height: SIDE,
+            media_type: MangaMediaType::StaticImage,
+            requested_side: req.target_texture_side,
+            queue_wait: Duration::ZERO,
+            decode_time: Duration::ZERO,
+            resize_time: Duration::ZERO,
+            panel_breaks: Vec::new(),
+        }
+    }
+
+    /// Loader whose decoder logs every index it is asked for and fails those in `failing`.
+    fn recording_loader(
+        max_pending_uploads: usize,
+        failing: &[usize],
+    ) -> (MangaLoader, Coordinator, Arc<Mutex<Vec<usize>>>) {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let decode_log = Arc::clone(&log);
+        let failing = failing.to_vec();
+        let decode: DecodeFn = Arc::new(move |req: &LoadRequest| {
+            decode_log.lock().push(req.index);
+            (!failing.contains(&req.index)).then(|| fake_page(req))
+        });
+        let (loader, coordinator) = MangaLoader::with_decoder(decode, max_pending_uploads);
+        (loader, coordinator, log)
+    }
+
+    fn request_around(loader: &mut MangaLoader, pages: &[PathBuf], visible_index: usize) {
+        loader.update_preload_queue(
+            pages,
+            visible_index,
+            1000.0,
+            4096,
+            SIDE,
+            FilterType::Triangle,
+            FilterType::Triangle,
+            false,
+        );
+    }
+
+    /// Runs every queued batch on the test thread instead of the coordinator thread.
+    fn run_queued(coordinator: &Coordinator) {
+        loop {
+            let mut batch = Vec::new();
+            coordinator.fill_batch(&mut batch);
+            if batch.is_empty() {
+                return;
+            }
+            assert!(coordinator.process_batch(&mut batch));
+        }
+    }
+
+    fn sorted(mut indices: Vec<usize>) -> Vec<usize> {
+        indices.sort_unstable();
+        indices
+    }
+
+    fn polled_indices(loader: &mut MangaLoader) -> Vec<usize> {
+        let (decoded, _) = loader.poll_decoded_images_with_limit(usize::MAX);
+        sorted(decoded.iter().map(|image| image.index).collect())
+    }
+
+    fn loaded_indices(loader: &MangaLoader) -> Vec<usize> {
+        sorted(loader.loaded_levels.read().keys().copied().collect())
+    }
+
+    #[test]
+    fn cancelled_generations_are_not_decoded_or_delivered() {
+        let pages = pages(200);
+        let (mut loader, coordinator, log) = recording_loader(128, &[]);
+
+        // Default windows at the top: the visible page and 12 ahead.
+        request_around(&mut loader, &pages, 0);
+        assert_eq!(loader.pending_load_count(), 13);
+        loader.cancel_pending_loads();
+        assert_eq!(loader.pending_load_count(), 0);
+        run_queued(&coordinator);
+        assert!(log.lock().is_empty());
+        assert!(polled_indices(&mut loader).is_empty());
+
+        // Decoded but not yet polled results are drained by a cancel and requested again.
+        request_around(&mut loader, &pages, 0);
+        run_queued(&coordinator);
+        loader.cancel_pending_loads();
+        assert!(polled_indices(&mut loader).is_empty());
+        assert!(loaded_indices(&loader).is_empty());
+
+        log.lock().clear();
+        request_around(&mut loader, &pages, 0);
+        run_queued(&coordinator);
+        assert_eq!(log.lock().len(), 13);
+        assert_eq!(polled_indices(&mut loader), (0..=12).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn full_result_channel_does_not_mark_pages_loaded() {
+        let pages = pages(200);
+        let (mut loader, coordinator, log) = recording_loader(4, &[]);
+
+        request_around(&mut loader, &pages, 0);
+        run_queued(&coordinator);
+        assert_eq!(log.lock().len(), 13);
+        assert_eq!(loader.pending_load_count(), 0);
+
+        let delivered = polled_indices(&mut loader);
+        assert_eq!(delivered.len(), 4);
+        assert_eq!(loaded_indices(&loader), delivered);
+
+        // Only the dropped pages are decoded again.
+        log.lock().clear();
+        request_around(&mut loader, &pages, 0);
+        run_queued(&coordinator);
+        let redecoded = sorted(log.lock().clone());
+        assert_eq!(redecoded.len(), 9);
+        assert!(redecoded.iter().all(|index| !delivered.contains(index)));
+        assert_eq!(loaded_indices(&loader).len(), 8);
+    }
+
+    #[test]
+    fn large_jump_decodes_the_target_page_first() {
+        let pages = pages(200);
+        let (mut loader, coordinator, log) = recording_loader(128, &[]);
+
+        // Both windows are still queued when the jump lands.
+        request_around(&mut loader, &pages, 0);
+        request_around(&mut loader, &pages, 100);
+        run_queued(&coordinator);
+
+        let order = log.lock().clone();
+        assert_eq!(order.first(), Some(&100));
+        assert_eq!(sorted(order), (94..=112).collect::<Vec<_>>());
+        assert_eq!(polled_indices(&mut loader), (94..=112).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn mark_unloaded_requests_evicted_and_failed_pages_again() {
+        let pages = pages(200);
+        let (mut loader, coordinator, log) = recording_loader(128, &[5]);
+
+        request_around(&mut loader, &pages, 0);
+        run_queued(&coordinator);
+        assert_eq!(polled_indices(&mut loader).len(), 12);
+        assert!(!loaded_indices(&loader).contains(&5));
+
+        // Loaded pages are skipped and the failed one waits for its retry backoff.
+        log.lock().clear();
+        request_around(&mut loader, &pages, 0);
+        run_queued(&coordinator);
+        assert!(log.lock().is_empty());
+
+        // An evicted texture and a cleared failure are both eligible again right away.
+        loader.mark_unloaded(3);
+        loader.mark_unloaded(5);
+        request_around(&mut loader, &pages, 0);
+        run_queued(&coordinator);
+        assert_eq!(sorted(log.lock().clone()), [3, 5]);
+        assert_eq!(polled_indices(&mut loader), [3]);
+    }
+}
@@ -0,0 +1,136 @@
+//! System media transport controls: the keyboard media keys and the Windows media flyout.
+//!
+//! While a video is open the viewer registers its window with the shell's media session,
+//! so play/pause, next and previous keys reach it even when another window has focus, and
+//! the volume flyout shows the video's title and thumbnail. Title and artist come from the
+//! file's tags as the player reports them, falling back to the file name; the thumbnail is
+//! the one the shell has for the file. Only Windows has an implementation; elsewhere
+//! [`MediaControls::attach`] returns `None`.
+
+use std::path::PathBuf;
+
+use crossbeam_channel::Receiver;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub enum MediaCommand {
+    Play,
+    Pause,
+    Next,
+    Previous,
+}
+
+/// What the flyout shows.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub struct NowPlaying {
+    pub path: PathBuf,
+    pub title: String,
+    pub artist: Option<String>,
+    pub playing: bool,
+}
+
+impl NowPlaying {
+    /// `title` from the tags when there is one, otherwise the file name without extension.
+    pub fn new(path: PathBuf, title: Option<&str>, artist: Option<&str>, playing: bool) -> Self {
+        let title = title
+            .map(str::trim)
+            .filter(|title| !title.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| {
+                path.file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            });
+        let artist = artist
+            .map(str::trim)
+            .filter(|artist| !artist.is_empty())
+            .map(str::to_string);
+        Self {
+            path,
+            title,
+            artist,
+            playing,
+        }
+    }
+}
+
+/// The window's media session; disabled again when dropped.
+pub struct MediaControls {
+    events: Receiver<MediaCommand>,
+    /// Last state handed to the shell.
+    shown: Option<NowPlaying>,
+    #[cfg(target_os = "windows")]
+    native: crate::windows_env::MediaControlsHandle,
+}
+
+impl MediaControls {
+    /// Registers the viewer window when it is the active window. `wake` runs after every
+    /// command is queued.
+    pub fn attach(wake: impl Fn() + Send + Sync + 'static) -> Option<Self> {
+        let (event_tx, events) = crossbeam_channel::unbounded::<MediaCommand>();
+
+        #[cfg(target_os = "windows")]
+        {
+            let window = crate::windows_env::active_viewer_window()?;
+            let native = crate::windows_env::MediaControlsHandle::attach(
+                window,
+                Box::new(move |command| {
+                    let _ = event_tx.send(command);
+                    wake();
+                }),
+            )?;
+            Some(Self {
+                events,
+                shown: None,
+                native,
+            })
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        {
+            let _ = (event_tx, events, wake);
+            None
+        }
+    }
+
+    /// Shows `now_playing`, or clears the session for `None`. Only changes reach the shell.
+    pub fn update(&mut self, now_playing: Option<NowPlaying>) {
+        if self.shown == now_playing {
+            return;
+        }
+        #[cfg(target_os = "windows")]
+        {
+            let media_changed = match (&self.shown, &now_playing) {
+                (Some(shown), Some(now)) => {
+                    shown.path != now.path || shown.title != now.title || shown.artist != now.artist
+                }
+                _ => true,
+            };
+            self.native.update(now_playing.as_ref(), media_changed);
+        }
+        self.shown = now_playing;
+    }
+
+    pub fn events(&self) -> &Receiver<MediaCommand> {
+        &self.events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NowPlaying;
+    use std::path::PathBuf;
+
+    #[test]
+    fn title_falls_back_to_the_file_name() {
+        let path = PathBuf::from("clips/Holiday 2024.mkv");
+        let tagged = NowPlaying::new(path.clone(), Some(" Beach day "), Some(""), true);
+        assert_eq!(tagged.title, "Beach day");
+        assert_eq!(tagged.artist, None);
+
+        let untagged = NowPlaying::new(path, Some("  "), Some("Sam"), false);
+        assert_eq!(untagged.title, "Holiday 2024");
+        assert_eq!(untagged.artist.as_deref(), Some("Sam"));
+    }
+}
@@ -11,7 +11,7 @@ use parking_lot::Mutex;
 use redb::backends::FileBackend;
 use redb::{Database, DatabaseError, ReadableTable, StorageBackend, TableDefinition, TableHandle};
 
-use crate::app_dirs;
+use crate::storage;
 
 const METADATA_TABLE: TableDefinition<&str, &str> = TableDefinition::new("media_dimensions");
 
@@ -662,21 +662,7 @@ pub fn metadata_cache_stats() -> MetadataCacheStats {
 }
 
 fn default_cache_path() -> Option<PathBuf> {
-    #[cfg(target_os = "windows")]
-    {
-        if let Some(base_dir) = app_dirs::app_local_data_dir() {
-            if std::fs::create_dir_all(&base_dir).is_ok() {
-                return Some(base_dir.join("metadata_cache.redb"));
-            }
-        }
-    }
-
-    let base_dir = std::env::temp_dir().join(app_dirs::APP_DIR_NAME);
-    if std::fs::create_dir_all(&base_dir).is_ok() {
-        return Some(base_dir.join("metadata_cache.redb"));
-    }
-
-    None
+    storage::data_file("metadata_cache.redb")
 }
 
 fn metadata_cache_max_size_bytes() -> u64 {
@@ -0,0 +1,320 @@
+//! Monitor wall: the newest images of one folder tiled in a grid, updating live.
+//!
+//! Meant for folders that something else keeps writing to (render output, screenshots).
+//! Change detection goes through the shared [`crate::media_index::MediaDirectoryIndex`], so
+//! the wall only rescans when the folder's modification time moves. Each rescan ranks the
+//! images by file modification time on a worker and decodes thumbnails only for tiles that
+//! are new or were rewritten; unchanged tiles keep their textures.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use image::imageops::FilterType;
+
+use crate::image_loader::{is_supported_image, LoadedImage};
+
+/// How often the folder's modification time is checked.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Long-side cap for tile thumbnails.
+pub const THUMBNAIL_SIDE: u32 = 512;
+
+/// How long a freshly arrived tile stays highlighted.
+pub const NEW_TILE_HIGHLIGHT: Duration = Duration::from_secs(3);
+
+/// `(path, modified)` pairs, newest first, at most `count` of them. Ties sort by path so
+/// the order does not flicker between scans.
+pub fn newest_first(
+    mut entries: Vec<(PathBuf, SystemTime)>,
+    count: usize,
+) -> Vec<(PathBuf, SystemTime)> {
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    entries.truncate(count);
+    entries
+}
+
+/// Columns and rows for `count` tiles in an area of `aspect` (width / height), picking the
+/// column count that gives the largest square-ish cells.
+pub fn grid_dimensions(count: usize, aspect: f32) -> (usize, usize) {
+    if count == 0 {
+        return (1, 1);
+    }
+    let aspect = aspect.max(0.01);
+    (1..=count)
+        .map(|columns| (columns, count.div_ceil(columns)))
+        .max_by(|&(ca, ra), &(cb, rb)| {
+            let cell =
+                |columns: usize, rows: usize| (aspect / columns as f32).min(1.0 / rows as f32);
+            cell(ca, ra)
+                .partial_cmp(&cell(cb, rb))
+                .unwrap_or(std::cmp::Ordering::Equal)
+                // Prefer fewer columns when both fit equally well.
+                .then(cb.cmp(&ca))
+        })
+        .unwrap_or((1, count))
+}
+
+pub enum MonitorWallEvent {
+    /// The current newest-first selection.
+    Ranked(Vec<(PathBuf, SystemTime)>),
+    Thumbnail {
+        path: PathBuf,
+        modified: SystemTime,
+        result: Result<(Vec<u8>, u32, u32), String>,
+    },
+}
+
+/// Ranks `files` and decodes thumbnails for the winners not in `known`, sending events as
+/// it goes. Runs on a worker thread.
+pub fn refresh(
+    files: Vec<PathBuf>,
+    count: usize,
+    known: HashSet<(PathBuf, SystemTime)>,
+    filter: FilterType,
+    tx: crossbeam_channel::Sender<MonitorWallEvent>,
+) {
+    let entries = files
+        .into_iter()
+        .filter(|path| is_supported_image(path))
+        .filter_map(|path| {
+            let modified = std::fs::metadata(&path).ok()?.modified().ok()?;
+            Some((path, modified))
+        })
+        .collect();
+    let newest = newest_first(entries, count);
+    if tx.send(MonitorWallEvent::Ranked(newest.clone())).is_err() {
+        return;
+    }
+    for (path, modified) in newest {
+        if known.contains(&(path.clone(), modified)) {
+            continue;
+        }
+        let result =
+            LoadedImage::load_with_max_texture_side(&path, Some(THUMBNAIL_SIDE), filter, filter)
+                .map(|image| {
+                    let frame = image.current_frame_data();
+                    (frame.pixels.clone(), frame.width, frame.height)
+                });
+        let event = MonitorWallEvent::Thumbnail {
+            path,
+            modified,
+            result,
+        };
+        if tx.send(event).is_err() {
+            return;
+        }
+    }
+}
+
+pub struct MonitorWallTile {
+    pub path: PathBuf,
+    pub modified: SystemTime,
+    pub texture: Option<egui::TextureHandle>,
+    pub error: Option<String>,
+    /// When the tile joined the wall, for the arrival highlight. `None` for the first scan.
+    pub arrived_at: Option<Instant>,
+}
+
+/// Monitor wall state while it is open.
+pub struct MonitorWall {
+    pub directory: PathBuf,
+    pub count: usize,
+    pub tiles: Vec<MonitorWallTile>,
+    /// Folder listing the last refresh was started from.
+    pub files: Option<Vec<PathBuf>>,
+    pub last_check: Option<Instant>,
+    pub scan_rx: Option<crossbeam_channel::Receiver<crate::media_index::DirectoryScanResult>>,
+    pub refresh_rx: Option<crossbeam_channel::Receiver<MonitorWallEvent>>,
+    /// Whether a ranking arrived yet; tiles of the first one get no arrival highlight.
+    ranked_once: bool,
+}
+
+impl MonitorWall {
+    pub fn new(directory: PathBuf, count: usize) -> Self {
+        Self {
+            directory,
+            count: count.max(1),
+            tiles: Vec::new(),
+            files: None,
+            last_check: None,
+            scan_rx: None,
+            refresh_rx: None,
+            ranked_once: false,
+        }
+    }
+
+    /// A path inside the folder, for the directory index APIs that key on a file's parent.
+    pub fn probe_path(&self) -> PathBuf {
+        self.directory.join(".monitor-wall")
+    }
+
+    pub fn folder_name(&self) -> String {
+        self.directory
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.directory.display().to_string())
+    }
+
+    /// Whether the next folder check is due.
+    pub fn check_due(&self, now: Instant) -> bool {
+        self.scan_rx.is_none()
+            && self
+                .last_check
+                .map_or(true, |last| now.duration_since(last) >= POLL_INTERVAL)
+    }
+
+    /// Starts a refresh for a new folder listing; unchanged listings are ignored.
+    pub fn set_files(&mut self, files: Vec<PathBuf>, filter: FilterType) {
+        if self.files.as_ref() == Some(&files) {
+            return;
+        }
+        self.files = Some(files.clone());
+        let known = self
+            .tiles
+            .iter()
+            .filter(|tile| tile.texture.is_some())
+            .map(|tile| (tile.path.clone(), tile.modified))
+            .collect();
+        let count = self.count;
+        let (tx, rx) = crossbeam_channel::unbounded();
+        crate::async_runtime::spawn_blocking_or_thread("monitor-wall-refresh", move || {
+            refresh(files, count, known, filter, tx);
+        });
+        self.refresh_rx = Some(rx);
+    }
+
+    /// Forces a rescan on the next check even if the folder listing did not change, e.g.
+    /// when a file was rewritten in place.
+    pub fn invalidate(&mut self) {
+        self.files = None;
+        self.last_check = None;
+    }
+
+    /// Drops every tile texture, e.g. after the GPU context was lost, and rescans so the
+    /// thumbnails are decoded again.
+    pub fn forget_textures(&mut self) {
+        for tile in &mut self.tiles {
+            tile.texture = None;
+        }
+        self.invalidate();
+    }
+
+    fn apply_ranking(&mut self, ranked: Vec<(PathBuf, SystemTime)>) {
+        let arrived_at = self.ranked_once.then(Instant::now);
+        self.ranked_once = true;
+        let mut previous = std::mem::take(&mut self.tiles);
+        self.tiles = ranked
+            .into_iter()
+            .map(|(path, modified)| {
+                match previous
+                    .iter()
+                    .position(|tile| tile.path == path && tile.modified == modified)
+                {
+                    Some(index) => previous.swap_remove(index),
+                    None => MonitorWallTile {
+                        path,
+                        modified,
+                        texture: None,
+                        error: None,
+                        arrived_at,
+                    },
+                }
+            })
+            .collect();
+    }
+
+    /// Applies worker results. Returns `true` while a refresh is still running.
+    pub fn poll(&mut self, ctx: &egui::Context) -> bool {
+        let Some(rx) = self.refresh_rx.take() else {
+            return false;
+        };
+        loop {
+            match rx.try_recv() {
+                Ok(MonitorWallEvent::Ranked(ranked)) => self.apply_ranking(ranked),
+                Ok(MonitorWallEvent::Thumbnail {
+                    path,
+                    modified,
+                    result,
+                }) => {
+                    let Some(tile) = self
+                        .tiles
+                        .iter_mut()
+                        .find(|tile| tile.path == path && tile.modified == modified)
+                    else {
+                        continue;
+                    };
+                    match result {
+                        Ok((pixels, width, height)) => {
+                            tile.texture = Some(ctx.load_texture(
+                                format!("monitor_wall_{}", path.display()),
+                                egui::ColorImage::from_rgba_unmultiplied(
+                                    [width as usize, height as usize],
+                                    &pixels,
+                                ),
+                                egui::TextureOptions::LINEAR,
+                            ));
+                            tile.error = None;
+                        }
+                        Err(err) => tile.error = Some(err),
+                    }
+                }
+                Err(crossbeam_channel::TryRecvError::Empty) => {
+                    self.refresh_rx = Some(rx);
+                    return true;
+                }
+                Err(crossbeam_channel::TryRecvError::Disconnected) => return false,
+            }
+        }
+    }
+
+    /// Whether `tile` should still show the arrival highlight.
+    pub fn is_new(tile: &MonitorWallTile, now: Instant) -> bool {
+        tile.arrived_at
+            .is_some_and(|arrived| now.duration_since(arrived) < NEW_TILE_HIGHLIGHT)
+    }
+}
+
+/// Directory a wall opened from `path` watches: the folder itself, or the file's folder.
+pub fn wall_directory(path: &Path) -> PathBuf {
+    if path.is_dir() {
+        path.to_path_buf()
+    } else {
+        path.parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{grid_dimensions, newest_first};
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn newest_first_keeps_the_most_recent_files() {
+        let at = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+        let entries = vec![
+            (PathBuf::from("old.png"), at(10)),
+            (PathBuf::from("b.png"), at(30)),
+            (PathBuf::from("newest.png"), at(40)),
+            (PathBuf::from("a.png"), at(30)),
+        ];
+        let names: Vec<PathBuf> = newest_first(entries, 3)
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        assert_eq!(names, ["newest.png", "a.png", "b.png"].map(PathBuf::from));
+    }
+
+    #[test]
+    fn grid_follows_the_area_shape() {
+        assert_eq!(grid_dimensions(12, 16.0 / 9.0), (4, 3));
+        assert_eq!(grid_dimensions(12, 9.0 / 16.0), (3, 4));
+        assert_eq!(grid_dimensions(1, 2.0), (1, 1));
+        assert_eq!(grid_dimensions(0, 1.0), (1, 1));
+        let (columns, rows) = grid_dimensions(7, 1.0);
+        assert!(columns * rows >= 7);
+    }
+}
@@ -0,0 +1,247 @@
+//! Display geometry for placing the borderless fullscreen window.
+//!
+//! Monitor bounds come from the OS in physical pixels, while viewport commands take egui
+//! points. With mixed DPI the two disagree per monitor: a position is converted with the
+//! scale of the monitor the window is on *now*, a size with the scale of the monitor it
+//! ends up on. [`Monitor::placement_points`] keeps those apart.
+//!
+//! [`SavedPlacement`] remembers where the floating window was left, for the next start and
+//! for leaving fullscreen.
+
+/// One display, in physical desktop pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Monitor {
+    pub rect: egui::Rect,
+    /// `rect` minus the taskbar and docked toolbars.
+    pub work: egui::Rect,
+    /// DPI scale of this monitor (1.0 at 96 DPI).
+    pub scale: f32,
+    pub primary: bool,
+}
+
+impl Monitor {
+    /// Outer position and inner size, in points, that cover this monitor. `pixels_per_point`
+    /// is the window's current value; `zoom_factor` is egui's UI zoom on top of the DPI scale.
+    pub fn placement_points(
+        &self,
+        pixels_per_point: f32,
+        zoom_factor: f32,
+    ) -> (egui::Pos2, egui::Vec2) {
+        let position = (self.rect.min.to_vec2() / pixels_per_point.max(0.01)).to_pos2();
+        let size = self.rect.size() / (self.scale * zoom_factor).max(0.01);
+        (position, size)
+    }
+
+    /// The work area in points, for a window on this monitor: the position is converted
+    /// with `pixels_per_point` like [`Monitor::placement_points`], the size with this
+    /// monitor's scale.
+    pub fn work_area_points(&self, pixels_per_point: f32, zoom_factor: f32) -> egui::Rect {
+        let position = (self.work.min.to_vec2() / pixels_per_point.max(0.01)).to_pos2();
+        let size = self.work.size() / (self.scale * zoom_factor).max(0.01);
+        egui::Rect::from_min_size(position, size)
+    }
+
+    /// Outer position, in points, that centers a window of `size` points on this monitor.
+    /// The window keeps its size in points when it changes monitor, so its pixel size there
+    /// follows this monitor's scale.
+    pub fn centered_position_points(
+        &self,
+        size: egui::Vec2,
+        pixels_per_point: f32,
+        zoom_factor: f32,
+    ) -> egui::Pos2 {
+        let size_px = size * self.scale * zoom_factor;
+        let min_px = self.rect.center() - size_px * 0.5;
+        let min_px = min_px.max(self.rect.min);
+        (min_px.to_vec2() / pixels_per_point.max(0.01)).to_pos2()
+    }
+
+    /// Outer position, in points, that puts the center of a window of `size` points at
+    /// `center` (fractions of the work area), kept inside the work area.
+    pub fn position_at_points(
+        &self,
+        center: egui::Vec2,
+        size: egui::Vec2,
+        pixels_per_point: f32,
+        zoom_factor: f32,
+    ) -> egui::Pos2 {
+        let size_px = size * self.scale * zoom_factor;
+        let center_px = self.work.min + self.work.size() * center;
+        let last_min = (self.work.max - size_px).max(self.work.min);
+        let min_px = (center_px - size_px * 0.5).clamp(self.work.min, last_min);
+        (min_px.to_vec2() / pixels_per_point.max(0.01)).to_pos2()
+    }
+}
+
+/// Where the floating window was last left: its monitor, in physical desktop pixels, and the
+/// window center as a fraction of that monitor's work area. Fractions keep the spot when the
+/// DPI or the window size changes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SavedPlacement {
+    pub monitor: egui::Rect,
+    pub center: egui::Vec2,
+}
+
+impl SavedPlacement {
+    /// The placement of a window with outer rect `window`, in points, on `monitor`.
+    pub fn capture(monitor: &Monitor, window: egui::Rect, pixels_per_point: f32) -> Self {
+        let center_px = window.center().to_vec2() * pixels_per_point;
+        let work = monitor.work;
+        let center = (center_px - work.min.to_vec2()) / work.size().max(egui::vec2(1.0, 1.0));
+        Self {
+            monitor: monitor.rect,
+            center: center.clamp(egui::Vec2::ZERO, egui::vec2(1.0, 1.0)),
+        }
+    }
+
+    /// Reads `left,top,right,bottom,x,y` as written by [`SavedPlacement::to_ini_string`].
+    pub fn parse(value: &str) -> Option<Self> {
+        let numbers = value
+            .split(',')
+            .map(|number| number.trim().parse::<f32>().ok().filter(|n| n.is_finite()))
+            .collect::<Option<Vec<_>>>()?;
+        let [left, top, right, bottom, x, y] = numbers[..] else {
+            return None;
+        };
+        let monitor = egui::Rect::from_min_max(egui::pos2(left, top), egui::pos2(right, bottom));
+        monitor.is_positive().then(|| Self {
+            monitor,
+            center: egui::vec2(x, y).clamp(egui::Vec2::ZERO, egui::vec2(1.0, 1.0)),
+        })
+    }
+
+    pub fn to_ini_string(self) -> String {
+        let rect = self.monitor;
+        format!(
+            "{},{},{},{},{:.4},{:.4}",
+            rect.min.x, rect.min.y, rect.max.x, rect.max.y, self.center.x, self.center.y
+        )
+    }
+
+    /// The monitor this was saved on, while it is still connected with the same bounds.
+    pub fn find_monitor(&self, monitors: &[Monitor]) -> Option<Monitor> {
+        monitors
+            .iter()
+            .find(|monitor| monitor.rect == self.monitor)
+            .copied()
+    }
+}
+
+/// Connected monitors, left to right then top to bottom. Empty where enumeration is not
+/// supported; callers then fall back to the viewport's own monitor size.
+pub fn enumerate() -> Vec<Monitor> {
+    #[cfg(target_os = "windows")]
+    let mut monitors = crate::windows_env::monitors();
+    #[cfg(not(target_os = "windows"))]
+    let mut monitors: Vec<Monitor> = Vec::new();
+    monitors.sort_by(|a, b| {
+        (a.rect.min.x, a.rect.min.y)
+            .partial_cmp(&(b.rect.min.x, b.rect.min.y))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    monitors
+}
+
+/// The monitor the active viewer window is on.
+pub fn window_monitor() -> Option<Monitor> {
+    #[cfg(target_os = "windows")]
+    {
+        crate::windows_env::active_window_monitor()
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        None
+    }
+}
+
+/// The monitor after `current` in [`enumerate`] order, wrapping around. `None` with fewer
+/// than two monitors.
+pub fn next_monitor(monitors: &[Monitor], current: &Monitor) -> Option<Monitor> {
+    if monitors.len() < 2 {
+        return None;
+    }
+    let index = monitors
+        .iter()
+        .position(|monitor| monitor.rect == current.rect)
+        .unwrap_or(monitors.len() - 1);
+    monitors.get((index + 1) % monitors.len()).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{next_monitor, Monitor, SavedPlacement};
+
+    fn monitor(x: f32, width: f32, height: f32, scale: f32) -> Monitor {
+        let rect = egui::Rect::from_min_size(egui::pos2(x, 0.0), egui::vec2(width, height));
+        Monitor {
+            rect,
+            work: rect,
+            scale,
+            primary: x == 0.0,
+        }
+    }
+
+    #[test]
+    fn placement_converts_position_and_size_with_different_scales() {
+        // A 4K monitor at 150% to the right of a 1080p monitor at 100%.
+        let target = monitor(1920.0, 3840.0, 2160.0, 1.5);
+
+        // Seen from a window still on the 100% monitor.
+        let (position, size) = target.placement_points(1.0, 1.0);
+        assert_eq!(position, egui::pos2(1920.0, 0.0));
+        assert_eq!(size, egui::vec2(2560.0, 1440.0));
+
+        // Once the window sits on the 150% monitor, positions use its scale as well.
+        let (position, size) = target.placement_points(1.5, 1.0);
+        assert_eq!(position, egui::pos2(1280.0, 0.0));
+        assert_eq!(size, egui::vec2(2560.0, 1440.0));
+
+        // An 800x600 point window is 1200x900 pixels there.
+        let centered = target.centered_position_points(egui::vec2(800.0, 600.0), 1.0, 1.0);
+        assert_eq!(centered, egui::pos2(1920.0 + 1320.0, 630.0));
+
+        let work = target.work_area_points(1.5, 1.0);
+        assert_eq!(work.min, egui::pos2(1280.0, 0.0));
+        assert_eq!(work.size(), egui::vec2(2560.0, 1440.0));
+    }
+
+    #[test]
+    fn saved_placement_round_trips_and_stays_on_screen() {
+        let monitors = [
+            monitor(0.0, 1920.0, 1080.0, 1.0),
+            monitor(1920.0, 3840.0, 2160.0, 1.5),
+        ];
+        // A 400x300 point window in the right quarter of the 150% monitor, seen from there.
+        let window = egui::Rect::from_min_size(egui::pos2(3000.0, 570.0), egui::vec2(400.0, 300.0));
+        let placement = SavedPlacement::capture(&monitors[1], window, 1.5);
+        assert_eq!(placement.center, egui::vec2(0.75, 0.5));
+
+        let restored = SavedPlacement::parse(&placement.to_ini_string()).unwrap();
+        assert_eq!(restored, placement);
+        let target = restored.find_monitor(&monitors).unwrap();
+        let position =
+            target.position_at_points(restored.center, egui::vec2(400.0, 300.0), 1.5, 1.0);
+        assert_eq!(position, window.min);
+
+        // A window larger than the space right of the center is pulled back inside.
+        let position =
+            target.position_at_points(egui::vec2(1.0, 1.0), egui::vec2(800.0, 600.0), 1.5, 1.0);
+        assert_eq!(position, egui::pos2(1280.0 + 1760.0, 1440.0 - 600.0));
+
+        // A disconnected monitor is not found; the caller falls back to centering.
+        assert_eq!(restored.find_monitor(&monitors[..1]), None);
+        assert_eq!(SavedPlacement::parse("1,2,3"), None);
+        assert_eq!(SavedPlacement::parse("0,0,0,0,0.5,0.5"), None);
+    }
+
+    #[test]
+    fn next_monitor_wraps_around() {
+        let monitors = [
+            monitor(0.0, 1920.0, 1080.0, 1.0),
+            monitor(1920.0, 3840.0, 2160.0, 1.5),
+        ];
+        assert_eq!(next_monitor(&monitors, &monitors[0]), Some(monitors[1]));
+        assert_eq!(next_monitor(&monitors, &monitors[1]), Some(monitors[0]));
+        assert_eq!(next_monitor(&monitors[..1], &monitors[0]), None);
+    }
+}
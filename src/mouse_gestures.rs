@@ -0,0 +1,181 @@
+//! Hold-right-drag mouse gestures.
+//!
+//! A gesture is one straight stroke or an L-shape (two perpendicular strokes). Each has a
+//! fixed `gesture_*` key in the `[Gestures]` section of config.ini that names the action
+//! it runs.
+
+/// Pointer travel needed before a stroke direction is recognized.
+const STROKE_MIN_PX: f32 = 40.0;
+/// Strokes beyond this turn the drag into an unrecognized gesture.
+const MAX_STROKES: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GestureDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl GestureDirection {
+    fn is_horizontal(self) -> bool {
+        matches!(self, GestureDirection::Left | GestureDirection::Right)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MouseGesture {
+    pub first: GestureDirection,
+    pub second: Option<GestureDirection>,
+}
+
+/// Every gesture that has a config.ini key, in template order.
+const GESTURE_KEYS: [(MouseGesture, &str); 12] = {
+    use GestureDirection::*;
+    const fn single(first: GestureDirection) -> MouseGesture {
+        MouseGesture {
+            first,
+            second: None,
+        }
+    }
+    const fn l_shape(first: GestureDirection, second: GestureDirection) -> MouseGesture {
+        MouseGesture {
+            first,
+            second: Some(second),
+        }
+    }
+    [
+        (single(Left), "gesture_left"),
+        (single(Right), "gesture_right"),
+        (single(Up), "gesture_up"),
+        (single(Down), "gesture_down"),
+        (l_shape(Down, Right), "gesture_down_right"),
+        (l_shape(Down, Left), "gesture_down_left"),
+        (l_shape(Up, Right), "gesture_up_right"),
+        (l_shape(Up, Left), "gesture_up_left"),
+        (l_shape(Right, Down), "gesture_right_down"),
+        (l_shape(Right, Up), "gesture_right_up"),
+        (l_shape(Left, Down), "gesture_left_down"),
+        (l_shape(Left, Up), "gesture_left_up"),
+    ]
+};
+
+impl MouseGesture {
+    pub fn from_ini_key(key: &str) -> Option<Self> {
+        GESTURE_KEYS
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(key))
+            .map(|(gesture, _)| *gesture)
+    }
+
+    pub fn all() -> impl Iterator<Item = (MouseGesture, &'static str)> {
+        GESTURE_KEYS.iter().copied()
+    }
+}
+
+/// State machine for one right-button drag.
+#[derive(Debug, Default)]
+pub struct GestureTracker {
+    /// Where the current stroke started; `None` while no gesture is being tracked.
+    anchor: Option<egui::Pos2>,
+    strokes: Vec<GestureDirection>,
+}
+
+impl GestureTracker {
+    pub fn begin(&mut self, pos: egui::Pos2) {
+        self.anchor = Some(pos);
+        self.strokes.clear();
+    }
+
+    pub fn is_tracking(&self) -> bool {
+        self.anchor.is_some()
+    }
+
+    pub fn move_to(&mut self, pos: egui::Pos2) {
+        let Some(anchor) = self.anchor else {
+            return;
+        };
+        let delta = pos - anchor;
+        if delta.x.abs().max(delta.y.abs()) < STROKE_MIN_PX {
+            return;
+        }
+
+        let direction = if delta.x.abs() >= delta.y.abs() {
+            if delta.x > 0.0 {
+                GestureDirection::Right
+            } else {
+                GestureDirection::Left
+            }
+        } else if delta.y > 0.0 {
+            GestureDirection::Down
+        } else {
+            GestureDirection::Up
+        };
+
+        // Continuing in the same direction only extends the current stroke.
+        if self.strokes.last() != Some(&direction) && self.strokes.len() <= MAX_STROKES {
+            self.strokes.push(direction);
+        }
+        self.anchor = Some(pos);
+    }
+
+    /// Ends tracking and returns the recognized gesture, if the strokes form one.
+    pub fn finish(&mut self) -> Option<MouseGesture> {
+        self.anchor = None;
+        let strokes = std::mem::take(&mut self.strokes);
+        match strokes.as_slice() {
+            [first] => Some(MouseGesture {
+                first: *first,
+                second: None,
+            }),
+            [first, second] if first.is_horizontal() != second.is_horizontal() => {
+                Some(MouseGesture {
+                    first: *first,
+                    second: Some(*second),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        self.anchor = None;
+        self.strokes.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drag(points: &[(f32, f32)]) -> Option<MouseGesture> {
+        let mut tracker = GestureTracker::default();
+        tracker.begin(egui::pos2(points[0].0, points[0].1));
+        for &(x, y) in &points[1..] {
+            tracker.move_to(egui::pos2(x, y));
+        }
+        tracker.finish()
+    }
+
+    #[test]
+    fn recognizes_straight_and_l_shaped_drags() {
+        assert_eq!(
+            drag(&[(0.0, 0.0), (50.0, 5.0), (120.0, -4.0)]),
+            MouseGesture::from_ini_key("gesture_right")
+        );
+        assert_eq!(
+            drag(&[(0.0, 0.0), (3.0, 60.0), (70.0, 62.0)]),
+            MouseGesture::from_ini_key("gesture_down_right")
+        );
+    }
+
+    #[test]
+    fn short_reversed_and_long_drags_are_not_gestures() {
+        assert_eq!(drag(&[(0.0, 0.0), (20.0, 10.0)]), None);
+        assert_eq!(drag(&[(0.0, 0.0), (60.0, 0.0), (0.0, 0.0)]), None);
+        assert_eq!(
+            drag(&[(0.0, 0.0), (0.0, 60.0), (60.0, 60.0), (60.0, 0.0)]),
+            None
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! On-screen display: brief notices that confirm what just happened, such as "Zoom 150%",
+//! "Muted" or "Copied 3 files".
+//!
+//! Notices stack at the bottom of the window, newest at the bottom. Each fades in, stays for
+//! its duration and fades out; at most [`MAX_VISIBLE`] are kept. A notice pushed with a key
+//! replaces the one with the same key in place, so holding a zoom key updates one notice
+//! instead of queuing a column of them.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::theme::OverlayTheme;
+
+/// Notices on screen at once; pushing another drops the oldest.
+const MAX_VISIBLE: usize = 4;
+const FADE_IN: Duration = Duration::from_millis(120);
+const FADE_OUT: Duration = Duration::from_millis(300);
+/// Default time on screen for action feedback.
+pub const ACTION_DURATION: Duration = Duration::from_millis(1500);
+/// Default time on screen for messages worth reading.
+pub const MESSAGE_DURATION: Duration = Duration::from_secs(4);
+
+struct Toast {
+    key: Option<&'static str>,
+    text: String,
+    shown_at: Instant,
+    duration: Duration,
+}
+
+#[derive(Default)]
+pub struct Osd {
+    toasts: VecDeque<Toast>,
+}
+
+impl Osd {
+    /// Queues `text` to stay fully visible for `duration`.
+    pub fn push(&mut self, text: impl Into<String>, duration: Duration) {
+        self.insert(None, text.into(), duration, Instant::now());
+    }
+
+    /// Shows `text` in place of the notice with the same `key`, or as a new one.
+    pub fn replace(&mut self, key: &'static str, text: impl Into<String>, duration: Duration) {
+        self.insert(Some(key), text.into(), duration, Instant::now());
+    }
+
+    fn insert(
+        &mut self,
+        key: Option<&'static str>,
+        text: String,
+        duration: Duration,
+        now: Instant,
+    ) {
+        self.toasts
+            .retain(|toast| now.duration_since(toast.shown_at) < toast.lifetime());
+        if let Some(existing) =
+            key.and_then(|key| self.toasts.iter_mut().find(|toast| toast.key == Some(key)))
+        {
+            // Restart the timer without fading in again.
+            existing.shown_at = now.checked_sub(FADE_IN).unwrap_or(now);
+            existing.text = text;
+            existing.duration = duration;
+            return;
+        }
+        if self.toasts.len() >= MAX_VISIBLE {
+            self.toasts.pop_front();
+        }
+        self.toasts.push_back(Toast {
+            key,
+            text,
+            shown_at: now,
+            duration,
+        });
+    }
+
+    /// Draws the visible notices and drops the expired ones.
+    pub fn show(&mut self, ctx: &egui::Context, theme: &OverlayTheme) {
+        let now = Instant::now();
+        self.toasts
+            .retain(|toast| now.duration_since(toast.shown_at) < toast.lifetime());
+        if self.toasts.is_empty() {
+            return;
+        }
+
+        let mut fading = false;
+        let mut next_change = Duration::MAX;
+        egui::Area::new(egui::Id::new("osd"))
+            .order(egui::Order::Tooltip)
+            .interactable(false)
+            .anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -48.0))
+            .show(ctx, |ui| {
+                ui.spacing_mut().item_spacing.y = 6.0;
+                ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
+                    for toast in &self.toasts {
+                        let elapsed = now.duration_since(toast.shown_at);
+                        let opacity = opacity(elapsed, toast.duration);
+                        if opacity < 1.0 {
+                            fading = true;
+                        } else {
+                            next_change = next_change.min(FADE_IN + toast.duration - elapsed);
+                        }
+                        draw_toast(ui, theme, &toast.text, opacity);
+                    }
+                });
+            });
+        if fading {
+            ctx.request_repaint();
+        } else {
+            ctx.request_repaint_after(next_change);
+        }
+    }
+}
+
+impl Toast {
+    fn lifetime(&self) -> Duration {
+        FADE_IN + self.duration + FADE_OUT
+    }
+}
+
+fn draw_toast(ui: &mut egui::Ui, theme: &OverlayTheme, text: &str, opacity: f32) {
+    egui::Frame::none()
+        .fill(theme.panel_fill.gamma_multiply(opacity))
+        .stroke(egui::Stroke::new(
+            1.0,
+            theme.panel_stroke.gamma_multiply(opacity),
+        ))
+        .rounding(theme.corner_radius)
+        .inner_margin(egui::Margin::symmetric(14.0, 9.0))
+        .show(ui, |ui| {
+            ui.label(egui::RichText::new(text).color(theme.text.gamma_multiply(opacity)));
+        });
+}
+
+/// Opacity of a notice `elapsed` after it appeared, shown for `duration` between its fades.
+fn opacity(elapsed: Duration, duration: Duration) -> f32 {
+    if elapsed < FADE_IN {
+        return elapsed.as_secs_f32() / FADE_IN.as_secs_f32();
+    }
+    let fade_out_at = FADE_IN + duration;
+    if elapsed <= fade_out_at {
+        return 1.0;
+    }
+    let fading = (elapsed - fade_out_at).as_secs_f32() / FADE_OUT.as_secs_f32();
+    (1.0 - fading).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{opacity, Osd, FADE_IN, FADE_OUT};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn notices_fade_in_hold_and_fade_out() {
+        let duration = Duration::from_secs(1);
+        assert_eq!(opacity(Duration::ZERO, duration), 0.0);
+        assert!((opacity(FADE_IN / 2, duration) - 0.5).abs() < 1e-3);
+        assert_eq!(opacity(FADE_IN + duration, duration), 1.0);
+        assert!((opacity(FADE_IN + duration + FADE_OUT / 2, duration) - 0.5).abs() < 1e-3);
+        assert_eq!(opacity(FADE_IN + duration + FADE_OUT, duration), 0.0);
+    }
+
+    #[test]
+    fn keyed_notices_replace_each_other_and_the_queue_is_capped() {
+        let now = Instant::now();
+        let mut osd = Osd::default();
+        let second = Duration::from_secs(1);
+        osd.insert(Some("zoom"), "Zoom 110%".into(), second, now);
+        osd.insert(None, "Muted".into(), second, now);
+        osd.insert(Some("zoom"), "Zoom 121%".into(), second, now);
+        let texts: Vec<&str> = osd.toasts.iter().map(|toast| toast.text.as_str()).collect();
+        assert_eq!(texts, ["Zoom 121%", "Muted"]);
+
+        for index in 0..5 {
+            osd.insert(None, format!("Notice {index}"), second, now);
+        }
+        assert_eq!(osd.toasts.len(), 4);
+        assert_eq!(osd.toasts.back().unwrap().text, "Notice 4");
+    }
+}
@@ -0,0 +1,104 @@
+//! Experimental webtoon panel detection.
+//!
+//! Finds horizontal gutters (bands of flat rows) in a decoded page so Long Strip
+//! PageUp/PageDown can stop at panel boundaries instead of moving a fixed distance.
+
+/// Rows whose sampled luma spread stays within this are treated as gutter.
+const FLAT_ROW_MAX_LUMA_SPREAD: u8 = 24;
+/// Upper bound on pixels sampled per row; wider pages are strided.
+const MAX_SAMPLES_PER_ROW: usize = 192;
+
+/// Centers of horizontal gutters as fractions of the page height, top to bottom.
+///
+/// Gutters touching the top or bottom edge are page margins rather than panel breaks and
+/// are left out.
+pub fn detect_panel_breaks(width: u32, height: u32, rgba: &[u8]) -> Vec<f32> {
+    let (width, height) = (width as usize, height as usize);
+    let row_len = width.saturating_mul(4);
+    if width == 0 || height < 3 || rgba.len() < row_len.saturating_mul(height) {
+        return Vec::new();
+    }
+
+    // A gutter must be a noticeable band, not a single ruled line inside a panel.
+    let min_gap_rows = (width / 40).max(4);
+    let step = width.div_ceil(MAX_SAMPLES_PER_ROW).max(1);
+
+    let mut breaks = Vec::new();
+    let mut run_start: Option<usize> = None;
+    for y in 0..=height {
+        let flat = y < height && row_is_flat(&rgba[y * row_len..(y + 1) * row_len], step);
+        match (flat, run_start) {
+            (true, None) => run_start = Some(y),
+            (false, Some(start)) => {
+                run_start = None;
+                let touches_edge = start == 0 || y == height;
+                if !touches_edge && y - start >= min_gap_rows {
+                    breaks.push((start + y) as f32 * 0.5 / height as f32);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    breaks
+}
+
+fn row_is_flat(row: &[u8], step: usize) -> bool {
+    let mut min = u8::MAX;
+    let mut max = u8::MIN;
+    for pixel in row.chunks_exact(4).step_by(step) {
+        let luma = ((u16::from(pixel[0]) * 77
+            + u16::from(pixel[1]) * 150
+            + u16::from(pixel[2]) * 29)
+            >> 8) as u8;
+        min = min.min(luma);
+        max = max.max(luma);
+        if max - min > FLAT_ROW_MAX_LUMA_SPREAD {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::detect_panel_breaks;
+
+    /// `rows` describes the page top to bottom: `true` = white gutter row, `false` = a
+    /// row of alternating black/white pixels (panel art).
+    fn page(width: usize, rows: &[bool]) -> Vec<u8> {
+        rows.iter()
+            .flat_map(|&gutter| {
+                (0..width).flat_map(move |x| {
+                    let v = if gutter || x % 2 == 0 { 255 } else { 0 };
+                    [v, v, v, 255]
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn finds_gutter_between_panels_and_ignores_page_margins() {
+        let mut rows = vec![true; 10];
+        rows.extend([false; 40]);
+        rows.extend([true; 20]);
+        rows.extend([false; 20]);
+        rows.extend([true; 10]);
+        let pixels = page(16, &rows);
+
+        let breaks = detect_panel_breaks(16, rows.len() as u32, &pixels);
+
+        assert_eq!(breaks, vec![0.6]);
+    }
+
+    #[test]
+    fn thin_lines_inside_a_panel_are_not_breaks() {
+        let mut rows = vec![false; 30];
+        rows.extend([true; 2]);
+        rows.extend([false; 30]);
+        let pixels = page(16, &rows);
+
+        assert!(detect_panel_breaks(16, rows.len() as u32, &pixels).is_empty());
+    }
+}
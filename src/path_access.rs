@@ -0,0 +1,188 @@
+//! Access to long paths and network shares.
+//!
+//! - Paths at or past `MAX_PATH` are given their `\\?\` form (`\\?\UNC\server\share\...` for
+//!   shares) before they go to Windows APIs that do not add it themselves. Paths that arrive
+//!   in that form are turned back into the plain form, so they match folder listings and
+//!   read normally in the UI.
+//! - Files on network shares are not memory-mapped: mapped pages are fetched in small round
+//!   trips, and a dropped connection faults the process. They are read through
+//!   [`NETWORK_READ_AHEAD`]-sized reads instead.
+//! - Listing a folder on a share runs on a worker and is given up after [`LISTING_TIMEOUT`],
+//!   so an unreachable server stalls the listing rather than the window.
+
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Read size for files on network shares.
+pub const NETWORK_READ_AHEAD: usize = 1024 * 1024;
+/// Network files up to this size are read into memory whole; larger ones are streamed
+/// through the read-ahead buffer.
+pub const NETWORK_READ_WHOLE_MAX_BYTES: u64 = 256 * 1024 * 1024;
+/// A folder listing on a share that takes longer than this is given up.
+pub const LISTING_TIMEOUT: Duration = Duration::from_secs(20);
+/// A background folder listing shows a spinner once it has run this long.
+pub const LISTING_SPINNER_DELAY: Duration = Duration::from_millis(400);
+/// Paths this long need the `\\?\` form. Directories stop 12 characters short of
+/// `MAX_PATH` (260) to leave room for an 8.3 file name.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+const LONG_PATH_LENGTH: usize = 260 - 12;
+
+/// `path` in the `\\?\` form when it is too long for plain Win32 calls; unchanged otherwise
+/// and on other platforms.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn extended_length(path: &Path) -> Cow<'_, Path> {
+    if !cfg!(target_os = "windows") {
+        return Cow::Borrowed(path);
+    }
+    match path.to_str().and_then(extended_length_form) {
+        Some(extended) => Cow::Owned(PathBuf::from(extended)),
+        None => Cow::Borrowed(path),
+    }
+}
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn extended_length_form(path: &str) -> Option<String> {
+    if path.len() < LONG_PATH_LENGTH || path.starts_with(r"\\?\") {
+        return None;
+    }
+    let path = path.replace('/', "\\");
+    // The `\\?\` form skips normalization, so `.` and `..` components would be taken
+    // literally.
+    if path
+        .split('\\')
+        .any(|component| component == "." || component == "..")
+    {
+        return None;
+    }
+    if let Some(share) = path.strip_prefix(r"\\") {
+        return (!share.starts_with(['.', '?'])).then(|| format!(r"\\?\UNC\{share}"));
+    }
+    is_drive_path(&path).then(|| format!(r"\\?\{path}"))
+}
+
+/// `path` without a `\\?\` or `\\?\UNC\` prefix, for paths handed over by the shell or the
+/// command line in that form.
+pub fn without_extended_prefix(path: PathBuf) -> PathBuf {
+    match path.to_str().and_then(plain_form) {
+        Some(plain) => PathBuf::from(plain),
+        None => path,
+    }
+}
+
+fn plain_form(path: &str) -> Option<String> {
+    if let Some(share) = path.strip_prefix(r"\\?\UNC\") {
+        return Some(format!(r"\\{share}"));
+    }
+    path.strip_prefix(r"\\?\")
+        .filter(|rest| is_drive_path(rest))
+        .map(str::to_string)
+}
+
+/// `C:\...` or `C:/...`.
+fn is_drive_path(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && matches!(bytes[2], b'\\' | b'/')
+}
+
+/// Whether `path` is on a network share: a UNC path, or a drive letter mapped to a share.
+pub fn is_network_path(path: &Path) -> bool {
+    let Some(path) = path.to_str() else {
+        return false;
+    };
+    if let Some(rest) = path.strip_prefix(r"\\?\") {
+        return rest.starts_with(r"UNC\") || is_remote_drive(rest);
+    }
+    let unc = path.starts_with(r"\\") || path.starts_with("//");
+    (unc && !path[2..].starts_with(['.', '?'])) || is_remote_drive(path)
+}
+
+#[cfg(target_os = "windows")]
+fn is_remote_drive(path: &str) -> bool {
+    use winapi::um::fileapi::GetDriveTypeW;
+    use winapi::um::winbase::DRIVE_REMOTE;
+
+    if !is_drive_path(path) {
+        return false;
+    }
+    let root: Vec<u16> = format!("{}\\", &path[..2])
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    unsafe { GetDriveTypeW(root.as_ptr()) == DRIVE_REMOTE }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn is_remote_drive(_path: &str) -> bool {
+    false
+}
+
+/// Runs `list` on a worker and waits up to [`LISTING_TIMEOUT`] for it. `None` when it has not
+/// finished by then; the worker is left to finish on its own.
+pub fn list_with_timeout<T, F>(thread_name: &str, list: F) -> Option<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (tx, rx) = crossbeam_channel::bounded(1);
+    crate::async_runtime::spawn_blocking_or_thread(thread_name, move || {
+        let _ = tx.send(list());
+    });
+    rx.recv_timeout(LISTING_TIMEOUT).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::{extended_length_form, is_network_path, without_extended_prefix};
+
+    #[test]
+    fn long_paths_get_the_extended_form() {
+        let long_name = "n".repeat(250);
+        assert_eq!(extended_length_form(r"C:\short.png"), None);
+        assert_eq!(
+            extended_length_form(&format!("C:/photos/{long_name}.png")),
+            Some(format!(r"\\?\C:\photos\{long_name}.png"))
+        );
+        assert_eq!(
+            extended_length_form(&format!(r"\\server\share\{long_name}.png")),
+            Some(format!(r"\\?\UNC\server\share\{long_name}.png"))
+        );
+        assert_eq!(
+            extended_length_form(&format!(r"C:\photos\..\{long_name}.png")),
+            None
+        );
+        assert_eq!(
+            extended_length_form(&format!(r"\\?\C:\{long_name}.png")),
+            None
+        );
+    }
+
+    #[test]
+    fn extended_prefixes_are_removed() {
+        assert_eq!(
+            without_extended_prefix(PathBuf::from(r"\\?\C:\a.png")),
+            PathBuf::from(r"C:\a.png")
+        );
+        assert_eq!(
+            without_extended_prefix(PathBuf::from(r"\\?\UNC\server\share\a.png")),
+            PathBuf::from(r"\\server\share\a.png")
+        );
+        assert_eq!(
+            without_extended_prefix(PathBuf::from(r"\\?\Volume{1234}\a.png")),
+            PathBuf::from(r"\\?\Volume{1234}\a.png")
+        );
+    }
+
+    #[test]
+    fn unc_paths_are_network_paths() {
+        assert!(is_network_path(Path::new(r"\\server\share\a.png")));
+        assert!(is_network_path(Path::new(r"\\?\UNC\server\share\a.png")));
+        assert!(!is_network_path(Path::new(r"\\.\pipe\name")));
+        assert!(!is_network_path(Path::new("/home/user/a.png")));
+    }
+}
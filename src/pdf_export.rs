@@ -0,0 +1,160 @@
+//! Batch PDF export: several images into one PDF, one image per page.
+//!
+//! Images are decoded one at a time in a background job (first frame of animations) and the
+//! job reports each finished page, so the jobs panel can show progress for long batches.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use image::imageops::FilterType;
+
+use crate::config::PdfPageSize;
+use crate::image_loader::LoadedImage;
+use crate::pdf_writer::{image_sized_page, PdfWriter, POINTS_PER_INCH};
+
+/// Long-side cap for embedded images; keeps batches of camera photos to a sane file size.
+pub const MAX_IMAGE_SIDE: u32 = 4096;
+
+/// Resolution used to size pages for [`PdfPageSize::Image`].
+pub const IMAGE_PAGE_DPI: f32 = 150.0;
+
+const MM_PER_INCH: f32 = 25.4;
+
+fn mm_to_points(mm: f32) -> f32 {
+    mm / MM_PER_INCH * POINTS_PER_INCH
+}
+
+/// Page size and image placement (in points from the top-left corner) for one image.
+/// Paper sizes turn landscape for landscape images; the image is fitted and centered
+/// inside the margins.
+pub fn page_layout(
+    page_size: PdfPageSize,
+    margin_mm: f32,
+    width: u32,
+    height: u32,
+) -> (egui::Vec2, egui::Rect) {
+    let margin = mm_to_points(margin_mm.max(0.0));
+    let paper_mm = match page_size {
+        PdfPageSize::A4 => egui::vec2(210.0, 297.0),
+        PdfPageSize::Letter => egui::vec2(215.9, 279.4),
+        PdfPageSize::Image => {
+            let (size, rect) = image_sized_page(width, height, IMAGE_PAGE_DPI);
+            let margins = egui::vec2(margin, margin);
+            return (size + margins * 2.0, rect.translate(margins));
+        }
+    };
+    let mut page = paper_mm * (POINTS_PER_INCH / MM_PER_INCH);
+    if width > height {
+        page = egui::vec2(page.y, page.x);
+    }
+    let margin = margin.min(page.min_elem() * 0.25);
+    let area = egui::Rect::from_min_max(
+        egui::pos2(margin, margin),
+        (page - egui::vec2(margin, margin)).to_pos2(),
+    );
+    let image = egui::vec2(width.max(1) as f32, height.max(1) as f32);
+    let scale = (area.width() / image.x).min(area.height() / image.y);
+    (
+        page,
+        egui::Rect::from_center_size(area.center(), image * scale),
+    )
+}
+
+/// `<dir>/<dir name>.pages.pdf` next to `first`, never overwriting.
+pub fn export_path(first: &Path) -> PathBuf {
+    let dir = first.parent().unwrap_or_else(|| Path::new("."));
+    let name = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "images".to_string());
+    crate::image_loader::sibling_export_path(&dir.join(format!("{name}.pdf")), "pages", "pdf")
+}
+
+/// Writes `paths` into one PDF at `target`, calling `progress` with the number of pages
+/// done after each page. Stops between pages once `cancelled` is set.
+pub fn export_images_pdf(
+    paths: &[PathBuf],
+    page_size: PdfPageSize,
+    margin_mm: f32,
+    filter: FilterType,
+    target: &Path,
+    cancelled: &AtomicBool,
+    progress: impl Fn(usize),
+) -> Result<(), String> {
+    if paths.is_empty() {
+        return Err("No images to export".to_string());
+    }
+    let mut writer = PdfWriter::new();
+    for (index, path) in paths.iter().enumerate() {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err("Cancelled".to_string());
+        }
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let image =
+            LoadedImage::load_with_max_texture_side(path, Some(MAX_IMAGE_SIDE), filter, filter)
+                .map_err(|err| format!("Failed to read {name}: {err}"))?;
+        let frame = image.current_frame_data();
+        let (page, image_rect) = page_layout(page_size, margin_mm, frame.width, frame.height);
+        writer.add_image_page(page, image_rect, &frame.pixels, frame.width, frame.height)?;
+        progress(index + 1);
+    }
+    writer.save(target)
+}
+
+/// State of the PDF export dialog while it is open.
+pub struct PdfExportDialog {
+    pub paths: Vec<PathBuf>,
+    /// Whether `paths` are the marked files rather than the whole folder list.
+    pub from_marked: bool,
+    pub page_size: PdfPageSize,
+    pub margin_mm: f32,
+    /// The last export started from this dialog.
+    pub job: Option<crate::jobs::JobId>,
+}
+
+impl PdfExportDialog {
+    pub fn new(
+        paths: Vec<PathBuf>,
+        from_marked: bool,
+        page_size: PdfPageSize,
+        margin_mm: f32,
+    ) -> Self {
+        Self {
+            paths,
+            from_marked,
+            page_size,
+            margin_mm,
+            job: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{export_path, page_layout};
+    use crate::config::PdfPageSize;
+    use std::path::Path;
+
+    #[test]
+    fn paper_pages_follow_image_orientation_and_fit_inside_margins() {
+        let (page, rect) = page_layout(PdfPageSize::A4, 10.0, 4000, 3000);
+        assert!(page.x > page.y);
+        assert!((page.y - 595.3).abs() < 0.5);
+        let margin = 10.0 / 25.4 * 72.0;
+        assert!((rect.min.y - margin).abs() < 0.01);
+        assert!(rect.min.x >= margin && rect.max.x <= page.x - margin + 0.01);
+        assert!((rect.width() / rect.height() - 4.0 / 3.0).abs() < 1e-3);
+
+        let (page, rect) = page_layout(PdfPageSize::Image, 0.0, 300, 150);
+        assert_eq!(page, egui::vec2(144.0, 72.0));
+        assert_eq!(rect.min, egui::Pos2::ZERO);
+    }
+
+    #[test]
+    fn exports_are_named_after_the_folder() {
+        assert_eq!(
+            export_path(Path::new("/nonexistent/scans/page1.jpg")),
+            Path::new("/nonexistent/scans/scans.pages.pdf")
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Minimal PDF writer for image pages.
+//!
+//! Every page holds one raster image, stored losslessly as a Flate-compressed RGB (or gray)
+//! XObject. That is all the scan and batch exports need, so there is no font, text or vector
+//! support. Alpha is flattened onto white, like paper.
+
+use std::io::Write;
+use std::path::Path;
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+
+/// PDF user-space units per inch.
+pub const POINTS_PER_INCH: f32 = 72.0;
+
+pub struct PdfWriter {
+    buffer: Vec<u8>,
+    /// Byte offset of each object, indexed by object number - 1. Objects 1 (catalog) and
+    /// 2 (page tree) are written last, once the page list is known.
+    offsets: Vec<usize>,
+    pages: Vec<usize>,
+}
+
+impl Default for PdfWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PdfWriter {
+    pub fn new() -> Self {
+        let mut buffer = Vec::new();
+        // The binary comment marks the file as 8-bit for transfer tools.
+        buffer.extend_from_slice(b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n");
+        Self {
+            buffer,
+            offsets: vec![0, 0],
+            pages: Vec::new(),
+        }
+    }
+
+    fn reserve_object(&mut self) -> usize {
+        self.offsets.push(0);
+        self.offsets.len()
+    }
+
+    fn begin_object(&mut self, id: usize) {
+        self.offsets[id - 1] = self.buffer.len();
+        self.buffer
+            .extend_from_slice(format!("{id} 0 obj\n").as_bytes());
+    }
+
+    fn write_stream(&mut self, id: usize, dictionary: &str, data: &[u8]) {
+        self.begin_object(id);
+        self.buffer.extend_from_slice(
+            format!("<< {dictionary} /Length {} >>\nstream\n", data.len()).as_bytes(),
+        );
+        self.buffer.extend_from_slice(data);
+        self.buffer.extend_from_slice(b"\nendstream\nendobj\n");
+    }
+
+    /// Adds a `page_size` page (in points) showing the straight RGBA8 image stretched over
+    /// `image_rect`, given in points from the top-left corner of the page.
+    pub fn add_image_page(
+        &mut self,
+        page_size: egui::Vec2,
+        image_rect: egui::Rect,
+        pixels: &[u8],
+        width: u32,
+        height: u32,
+    ) -> Result<(), String> {
+        if width == 0 || height == 0 || pixels.len() < width as usize * height as usize * 4 {
+            return Err("Frame buffer does not match its dimensions".to_string());
+        }
+        let pixels = &pixels[..width as usize * height as usize * 4];
+        let gray = pixels
+            .chunks_exact(4)
+            .all(|px| px[0] == px[1] && px[1] == px[2]);
+        let flatten = |value: u8, alpha: u8| -> u8 {
+            let alpha = alpha as u32;
+            ((value as u32 * alpha + 255 * (255 - alpha) + 127) / 255) as u8
+        };
+        let samples: Vec<u8> = if gray {
+            pixels
+                .chunks_exact(4)
+                .map(|px| flatten(px[0], px[3]))
+                .collect()
+        } else {
+            pixels
+                .chunks_exact(4)
+                .flat_map(|px| [px[0], px[1], px[2]].map(|value| flatten(value, px[3])))
+                .collect()
+        };
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&samples)
+            .and_then(|()| encoder.flush())
+            .map_err(|err| format!("Failed to compress page image: {err}"))?;
+        let compressed = encoder
+            .finish()
+            .map_err(|err| format!("Failed to compress page image: {err}"))?;
+
+        let image_id = self.reserve_object();
+        let color_space = if gray { "/DeviceGray" } else { "/DeviceRGB" };
+        self.write_stream(
+            image_id,
+            &format!(
+                "/Type /XObject /Subtype /Image /Width {width} /Height {height} \
+                 /ColorSpace {color_space} /BitsPerComponent 8 /Filter /FlateDecode"
+            ),
+            &compressed,
+        );
+
+        // PDF space has its origin at the bottom-left corner.
+        let content = format!(
+            "q {:.3} 0 0 {:.3} {:.3} {:.3} cm /Im0 Do Q",
+            image_rect.width(),
+            image_rect.height(),
+            image_rect.min.x,
+            page_size.y - image_rect.max.y,
+        );
+        let content_id = self.reserve_object();
+        self.write_stream(content_id, "", content.as_bytes());
+
+        let page_id = self.reserve_object();
+        self.begin_object(page_id);
+        self.buffer.extend_from_slice(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.3} {:.3}] \
+                 /Resources << /XObject << /Im0 {image_id} 0 R >> >> /Contents {content_id} 0 R >>\nendobj\n",
+                page_size.x, page_size.y
+            )
+            .as_bytes(),
+        );
+        self.pages.push(page_id);
+        Ok(())
+    }
+
+    /// Writes the page tree, catalog and cross-reference table.
+    pub fn finish(mut self) -> Vec<u8> {
+        let kids: Vec<String> = self.pages.iter().map(|id| format!("{id} 0 R")).collect();
+        self.begin_object(2);
+        self.buffer.extend_from_slice(
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>\nendobj\n",
+                kids.join(" "),
+                self.pages.len()
+            )
+            .as_bytes(),
+        );
+        self.begin_object(1);
+        self.buffer
+            .extend_from_slice(b"<< /Type /Catalog /Pages 2 0 R >>\nendobj\n");
+
+        let xref_offset = self.buffer.len();
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", self.offsets.len() + 1);
+        for offset in &self.offsets {
+            xref.push_str(&format!("{offset:010} 00000 n \n"));
+        }
+        xref.push_str(&format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+            self.offsets.len() + 1
+        ));
+        self.buffer.extend_from_slice(xref.as_bytes());
+        self.buffer
+    }
+
+    pub fn save(self, target: &Path) -> Result<(), String> {
+        std::fs::write(target, self.finish())
+            .map_err(|err| format!("Failed to write {}: {err}", target.display()))
+    }
+}
+
+/// Page the size of the image at `dpi`, with the image filling it.
+pub fn image_sized_page(width: u32, height: u32, dpi: f32) -> (egui::Vec2, egui::Rect) {
+    let size = egui::vec2(width as f32, height as f32) * (POINTS_PER_INCH / dpi.max(1.0));
+    (size, egui::Rect::from_min_size(egui::Pos2::ZERO, size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{image_sized_page, PdfWriter};
+
+    fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack
+            .windows(needle.len())
+            .position(|window| window == needle)
+    }
+
+    #[test]
+    fn xref_offsets_point_at_their_objects() {
+        let mut writer = PdfWriter::new();
+        let gray = [128, 128, 128, 255].repeat(4);
+        let color = [255, 0, 0, 255].repeat(4);
+        let (size, rect) = image_sized_page(2, 2, 72.0);
+        writer.add_image_page(size, rect, &gray, 2, 2).unwrap();
+        writer.add_image_page(size, rect, &color, 2, 2).unwrap();
+        let pdf = writer.finish();
+
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        assert!(find(&pdf, b"/Count 2").is_some());
+        assert!(find(&pdf, b"/DeviceGray").is_some());
+        assert!(find(&pdf, b"/DeviceRGB").is_some());
+
+        let xref = find(&pdf, b"xref\n").unwrap();
+        let table = String::from_utf8_lossy(&pdf[xref..]);
+        let offsets: Vec<usize> = table
+            .lines()
+            .skip(3)
+            .take_while(|line| line.ends_with(" n "))
+            .map(|line| line[..10].parse().unwrap())
+            .collect();
+        assert_eq!(offsets.len(), 8);
+        for (index, offset) in offsets.into_iter().enumerate() {
+            let header = format!("{} 0 obj", index + 1);
+            assert!(pdf[offset..].starts_with(header.as_bytes()));
+        }
+    }
+
+    #[test]
+    fn image_sized_page_uses_the_resolution() {
+        let (size, rect) = image_sized_page(300, 150, 150.0);
+        assert_eq!(size, egui::vec2(144.0, 72.0));
+        assert_eq!(rect.max, egui::pos2(144.0, 72.0));
+        assert!(PdfWriter::new()
+            .add_image_page(size, rect, &[0; 8], 300, 150)
+            .is_err());
+    }
+}
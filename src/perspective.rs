@@ -0,0 +1,248 @@
+//! Perspective (keystone) correction: four corner handles mark a quadrilateral in the image
+//! (a document or whiteboard shot at an angle) and the quad is warped back to a rectangle.
+//!
+//! The preview is an egui mesh whose vertex UVs follow the homography, so the GPU does the
+//! warp; a fine grid keeps the piecewise-linear interpolation indistinguishable from the
+//! exact projective mapping. Export runs the exact mapping on the CPU.
+
+use std::path::{Path, PathBuf};
+
+use crate::png_export::blend_bilinear;
+
+/// Grid cells per side of the preview mesh.
+const PREVIEW_GRID: usize = 24;
+
+/// Largest exported side, to keep runaway handles from allocating huge images.
+const MAX_OUTPUT_SIDE: f32 = 16384.0;
+
+/// Corners in normalized image coordinates (`0..=1`), in order top-left, top-right,
+/// bottom-right, bottom-left.
+pub type Quad = [egui::Pos2; 4];
+
+pub const FULL_IMAGE: Quad = [
+    egui::pos2(0.0, 0.0),
+    egui::pos2(1.0, 0.0),
+    egui::pos2(1.0, 1.0),
+    egui::pos2(0.0, 1.0),
+];
+
+/// Projective map from the unit square onto a quad (Heckbert's square-to-quad form).
+#[derive(Debug, Clone, Copy)]
+pub struct Homography {
+    a: f32,
+    b: f32,
+    c: f32,
+    d: f32,
+    e: f32,
+    f: f32,
+    g: f32,
+    h: f32,
+}
+
+impl Homography {
+    /// Maps `(0,0)`, `(1,0)`, `(1,1)`, `(0,1)` onto `quad`. `None` for degenerate quads.
+    pub fn square_to_quad(quad: Quad) -> Option<Self> {
+        let [p0, p1, p2, p3] = quad;
+        let sx = p0.x - p1.x + p2.x - p3.x;
+        let sy = p0.y - p1.y + p2.y - p3.y;
+        let (dx1, dx2) = (p1.x - p2.x, p3.x - p2.x);
+        let (dy1, dy2) = (p1.y - p2.y, p3.y - p2.y);
+        let det = dx1 * dy2 - dx2 * dy1;
+        if det.abs() < 1e-9 {
+            return None;
+        }
+        let g = (sx * dy2 - dx2 * sy) / det;
+        let h = (dx1 * sy - sx * dy1) / det;
+        Some(Self {
+            a: p1.x - p0.x + g * p1.x,
+            b: p3.x - p0.x + h * p3.x,
+            c: p0.x,
+            d: p1.y - p0.y + g * p1.y,
+            e: p3.y - p0.y + h * p3.y,
+            f: p0.y,
+            g,
+            h,
+        })
+    }
+
+    pub fn map(&self, u: f32, v: f32) -> egui::Pos2 {
+        let w = self.g * u + self.h * v + 1.0;
+        egui::pos2(
+            (self.a * u + self.b * v + self.c) / w,
+            (self.d * u + self.e * v + self.f) / w,
+        )
+    }
+}
+
+/// Output size in pixels for `quad` on an image of `image_size`: the average lengths of
+/// opposite edges.
+pub fn output_size(quad: Quad, image_size: egui::Vec2) -> egui::Vec2 {
+    let [p0, p1, p2, p3] = quad.map(|p| egui::pos2(p.x * image_size.x, p.y * image_size.y));
+    let width = (p0.distance(p1) + p3.distance(p2)) * 0.5;
+    let height = (p0.distance(p3) + p1.distance(p2)) * 0.5;
+    egui::vec2(
+        width.round().clamp(1.0, MAX_OUTPUT_SIDE),
+        height.round().clamp(1.0, MAX_OUTPUT_SIDE),
+    )
+}
+
+/// Warped preview of `texture_id` filling `rect`.
+pub fn preview_mesh(
+    texture_id: egui::TextureId,
+    quad: Quad,
+    rect: egui::Rect,
+) -> Option<egui::Mesh> {
+    let homography = Homography::square_to_quad(quad)?;
+    let mut mesh = egui::Mesh::with_texture(texture_id);
+    let steps = PREVIEW_GRID as f32;
+    for row in 0..=PREVIEW_GRID {
+        for col in 0..=PREVIEW_GRID {
+            let (u, v) = (col as f32 / steps, row as f32 / steps);
+            mesh.vertices.push(egui::epaint::Vertex {
+                pos: rect.min + egui::vec2(u * rect.width(), v * rect.height()),
+                uv: homography.map(u, v),
+                color: egui::Color32::WHITE,
+            });
+        }
+    }
+    let stride = PREVIEW_GRID as u32 + 1;
+    for row in 0..PREVIEW_GRID as u32 {
+        for col in 0..PREVIEW_GRID as u32 {
+            let top_left = row * stride + col;
+            let bottom_left = top_left + stride;
+            mesh.add_triangle(top_left, top_left + 1, bottom_left);
+            mesh.add_triangle(top_left + 1, bottom_left + 1, bottom_left);
+        }
+    }
+    Some(mesh)
+}
+
+/// Warps straight RGBA8 pixels so `quad` becomes an upright rectangle of `out_size`.
+/// Bilinear sampling; samples outside the image are transparent.
+pub fn warp_rgba(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    quad: Quad,
+    out_size: (u32, u32),
+) -> Option<Vec<u8>> {
+    let homography = Homography::square_to_quad(quad)?;
+    if width == 0 || height == 0 || pixels.len() < width as usize * height as usize * 4 {
+        return None;
+    }
+    let (out_width, out_height) = out_size;
+    let texel = |x: i64, y: i64| -> [f32; 4] {
+        if x < 0 || y < 0 || x >= width as i64 || y >= height as i64 {
+            return [0.0; 4];
+        }
+        let start = (y as usize * width as usize + x as usize) * 4;
+        std::array::from_fn(|channel| pixels[start + channel] as f32)
+    };
+
+    let mut out = Vec::with_capacity(out_width as usize * out_height as usize * 4);
+    for y in 0..out_height {
+        for x in 0..out_width {
+            let uv = homography.map(
+                (x as f32 + 0.5) / out_width as f32,
+                (y as f32 + 0.5) / out_height as f32,
+            );
+            let (sx, sy) = (uv.x * width as f32 - 0.5, uv.y * height as f32 - 0.5);
+            let (x0, y0) = (sx.floor(), sy.floor());
+            let (fx, fy) = (sx - x0, sy - y0);
+            let (x0, y0) = (x0 as i64, y0 as i64);
+            out.extend_from_slice(&blend_bilinear(
+                [
+                    texel(x0, y0),
+                    texel(x0 + 1, y0),
+                    texel(x0, y0 + 1),
+                    texel(x0 + 1, y0 + 1),
+                ],
+                fx,
+                fy,
+            ));
+        }
+    }
+    Some(out)
+}
+
+/// `scan.jpg` -> `scan.corrected.png`, or `scan.corrected-2.png` and so on if taken.
+pub fn export_path(image_path: &Path) -> PathBuf {
+    crate::image_loader::sibling_export_path(image_path, "corrected", "png")
+}
+
+/// Warps a frame to `out_size` (see [`output_size`]) and writes it as PNG.
+pub fn export_corrected_png(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    quad: Quad,
+    out_size: egui::Vec2,
+    background: Option<[u8; 3]>,
+    target: &Path,
+) -> Result<(), String> {
+    let (out_width, out_height) = (out_size.x as u32, out_size.y as u32);
+    let warped = warp_rgba(pixels, width, height, quad, (out_width, out_height))
+        .ok_or_else(|| "The corners do not form a usable quadrilateral".to_string())?;
+    crate::png_export::write_png(warped, out_width, out_height, background, target)
+}
+
+/// Perspective mode state: the quad, the handle being dragged and export progress.
+pub struct PerspectiveTool {
+    pub active: bool,
+    /// Image the quad belongs to; switching images resets it.
+    pub path: Option<PathBuf>,
+    pub quad: Quad,
+    pub dragging: Option<usize>,
+    pub preview: bool,
+    pub status: Option<String>,
+    pub export_rx: Option<crossbeam_channel::Receiver<Result<PathBuf, String>>>,
+}
+
+impl Default for PerspectiveTool {
+    fn default() -> Self {
+        Self {
+            active: false,
+            path: None,
+            quad: FULL_IMAGE,
+            dragging: None,
+            preview: false,
+            status: None,
+            export_rx: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{output_size, warp_rgba, Homography, FULL_IMAGE};
+
+    #[test]
+    fn homography_maps_square_corners_onto_the_quad() {
+        let quad = [
+            egui::pos2(0.1, 0.2),
+            egui::pos2(0.9, 0.05),
+            egui::pos2(0.8, 0.95),
+            egui::pos2(0.2, 0.7),
+        ];
+        let homography = Homography::square_to_quad(quad).unwrap();
+        for ((u, v), expected) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+            .into_iter()
+            .zip(quad)
+        {
+            assert!((homography.map(u, v) - expected).length() < 1e-5);
+        }
+        let collapsed = [egui::pos2(0.5, 0.5); 4];
+        assert!(Homography::square_to_quad(collapsed).is_none());
+    }
+
+    #[test]
+    fn full_image_quad_is_an_identity_warp() {
+        assert_eq!(
+            output_size(FULL_IMAGE, egui::vec2(3.0, 2.0)),
+            egui::vec2(3.0, 2.0)
+        );
+        let pixels: Vec<u8> = (0..3 * 2 * 4).map(|value| value as u8 * 10).collect();
+        let warped = warp_rgba(&pixels, 3, 2, FULL_IMAGE, (3, 2)).unwrap();
+        assert_eq!(warped, pixels);
+    }
+}
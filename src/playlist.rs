@@ -0,0 +1,250 @@
+//! Playlists: a queue of media files played in order, across folders.
+//!
+//! A queue comes from an `.m3u` / `.m3u8` file, a `.txt` file with one entry per line, or
+//! from several files dropped on the window at once. Entries may be http(s) URLs: videos
+//! stream from them, and images are downloaded ahead of time (see [`crate::remote`]) and
+//! replaced in the queue by their downloaded copies.
+//!
+//! While one is active, next/previous move through the queue instead of the folder and a
+//! video that ends moves on to the next item as `playlist_repeat` says, instead of looping.
+//! `playlist_shuffle` plays the queue in a random order.
+
+use std::path::{Path, PathBuf};
+
+use crate::config::PlaylistRepeat;
+
+/// Whether `path` is a playlist file rather than media.
+pub fn is_playlist_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            ["m3u", "m3u8", "txt"]
+                .iter()
+                .any(|playlist_ext| ext.eq_ignore_ascii_case(playlist_ext))
+        })
+}
+
+/// The entries of the playlist at `path`, with relative entries resolved against its folder.
+pub fn read_m3u(path: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let bytes = std::fs::read(path)?;
+    let base = path.parent().unwrap_or(Path::new(""));
+    Ok(parse_m3u(&String::from_utf8_lossy(&bytes), base))
+}
+
+/// Paths listed in M3U `text`. `#EXTINF` and other directives are skipped, and so are URLs
+/// other than `file://` and http(s) ones; http(s) URLs are kept as they are.
+fn parse_m3u(text: &str, base: &Path) -> Vec<PathBuf> {
+    text.lines()
+        .map(|line| line.trim_start_matches('\u{feff}').trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|entry| {
+            if let Some(uri) = entry.strip_prefix("file://") {
+                return Some(PathBuf::from(file_uri_path(uri)));
+            }
+            if let Some(url) = crate::remote::parse_url(entry) {
+                return Some(PathBuf::from(url));
+            }
+            if entry.contains("://") {
+                return None;
+            }
+            Some(base.join(entry))
+        })
+        .collect()
+}
+
+/// The path part of a `file://` URI: percent escapes decoded, and the slash before a drive
+/// letter (`/C:/...`) dropped.
+fn file_uri_path(uri: &str) -> String {
+    let uri = uri.strip_prefix("localhost").unwrap_or(uri);
+    let mut bytes = Vec::with_capacity(uri.len());
+    let mut rest = uri.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = (byte == b'%')
+            .then(|| tail.get(..2))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(decoded) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            None => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    let path = String::from_utf8_lossy(&bytes).into_owned();
+    let drive = path.as_bytes();
+    if drive.len() >= 3 && drive[0] == b'/' && drive[1].is_ascii_alphabetic() && drive[2] == b':' {
+        path[1..].to_string()
+    } else {
+        path
+    }
+}
+
+/// Index to continue with after the item at `index` of a `len`-item queue played to its end;
+/// `None` stops on the last item.
+pub fn next_after_end(index: usize, len: usize, repeat: PlaylistRepeat) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    match repeat {
+        PlaylistRepeat::One => Some(index.min(len - 1)),
+        PlaylistRepeat::All => Some((index + 1) % len),
+        PlaylistRepeat::Off => (index + 1 < len).then_some(index + 1),
+    }
+}
+
+/// The active queue.
+pub struct Playlist {
+    /// Playlist file name, or what the queue was built from.
+    pub name: String,
+    /// Items in the order they were listed.
+    listed: Vec<PathBuf>,
+    /// Items in play order: `listed`, or a shuffle of it.
+    order: Vec<PathBuf>,
+    shuffled: bool,
+    /// The queue panel is shown.
+    pub panel_open: bool,
+    /// Item the queue panel last scrolled to; it follows the current item when that changes.
+    pub scrolled_to: Option<usize>,
+}
+
+impl Playlist {
+    /// A queue of `items`; repeated entries are kept once, where they first appear.
+    pub fn new(name: String, items: Vec<PathBuf>) -> Self {
+        let mut listed: Vec<PathBuf> = Vec::with_capacity(items.len());
+        for item in items {
+            if !listed.contains(&item) {
+                listed.push(item);
+            }
+        }
+        Self {
+            name,
+            order: listed.clone(),
+            listed,
+            shuffled: false,
+            panel_open: false,
+            scrolled_to: None,
+        }
+    }
+
+    /// Items in play order.
+    pub fn items(&self) -> &[PathBuf] {
+        &self.order
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        self.order.iter().any(|item| item == path)
+    }
+
+    /// Shuffles the queue or restores the listed order. When shuffling, `current` moves to
+    /// the front so everything after it is still to come.
+    pub fn set_shuffled(&mut self, shuffled: bool, current: Option<&Path>) {
+        if shuffled == self.shuffled {
+            return;
+        }
+        self.shuffled = shuffled;
+        self.order = self.listed.clone();
+        if !shuffled {
+            return;
+        }
+        shuffle(&mut self.order, time_seed());
+        if let Some(position) =
+            current.and_then(|current| self.order.iter().position(|item| item == current))
+        {
+            let current = self.order.remove(position);
+            self.order.insert(0, current);
+        }
+    }
+
+    /// Drops items whose files are gone; URLs stay. Returns whether anything was removed.
+    pub fn retain_existing(&mut self) -> bool {
+        let before = self.order.len();
+        let keep = |item: &PathBuf| item.exists() || crate::remote::is_url(item);
+        self.listed.retain(keep);
+        self.order.retain(keep);
+        self.order.len() != before
+    }
+
+    /// Puts `replacement` in place of `item`, such as a downloaded copy of a URL. Returns
+    /// whether `item` was queued.
+    pub fn replace(&mut self, item: &Path, replacement: &Path) -> bool {
+        let mut replaced = false;
+        for queued in self.listed.iter_mut().chain(self.order.iter_mut()) {
+            if queued == item {
+                *queued = replacement.to_path_buf();
+                replaced = true;
+            }
+        }
+        replaced
+    }
+}
+
+fn time_seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0x9E37_79B9_7F4A_7C15, |elapsed| elapsed.as_nanos() as u64)
+}
+
+/// Fisher-Yates shuffle driven by xorshift64*.
+fn shuffle<T>(items: &mut [T], seed: u64) {
+    let mut state = seed | 1;
+    for i in (1..items.len()).rev() {
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        let random = state.wrapping_mul(0x2545_F491_4F6C_DD1D);
+        items.swap(i, (random % (i as u64 + 1)) as usize);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::{next_after_end, parse_m3u, shuffle, Playlist};
+    use crate::config::PlaylistRepeat;
+
+    #[test]
+    fn parses_extended_m3u() {
+        let text = "\u{feff}#EXTM3U\n#EXTINF:12,Clip\nclips/a.mp4\r\n\n/abs/b.png\n\
+                    http://example.com/c.mp4\nfile:///C:/My%20Videos/d.webm\nftp://example.com/e.png\n";
+        assert_eq!(
+            parse_m3u(text, Path::new("/lists")),
+            vec![
+                PathBuf::from("/lists/clips/a.mp4"),
+                PathBuf::from("/abs/b.png"),
+                PathBuf::from("http://example.com/c.mp4"),
+                PathBuf::from("C:/My Videos/d.webm"),
+            ]
+        );
+    }
+
+    #[test]
+    fn repeat_modes_pick_the_next_item() {
+        assert_eq!(next_after_end(0, 3, PlaylistRepeat::Off), Some(1));
+        assert_eq!(next_after_end(2, 3, PlaylistRepeat::Off), None);
+        assert_eq!(next_after_end(2, 3, PlaylistRepeat::All), Some(0));
+        assert_eq!(next_after_end(1, 3, PlaylistRepeat::One), Some(1));
+        assert_eq!(next_after_end(0, 0, PlaylistRepeat::All), None);
+    }
+
+    #[test]
+    fn shuffle_keeps_every_item_and_the_current_one_first() {
+        let mut items: Vec<u32> = (0..50).collect();
+        shuffle(&mut items, 7);
+        assert_ne!(items, (0..50).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+
+        let paths: Vec<PathBuf> = (0..10).map(|i| PathBuf::from(format!("{i}.jpg"))).collect();
+        let mut playlist = Playlist::new("test".to_string(), paths.clone());
+        playlist.set_shuffled(true, Some(Path::new("4.jpg")));
+        assert_eq!(playlist.items()[0], Path::new("4.jpg"));
+        playlist.set_shuffled(false, None);
+        assert_eq!(playlist.items(), paths.as_slice());
+    }
+}
@@ -0,0 +1,380 @@
+//! The format registry: which decoder opens each file extension, built in or added by a
+//! plugin, plus the plugin filters. Plugins are native libraries in the `plugins` folder next
+//! to `config.ini`, loaded once at startup.
+//!
+//! A plugin is a `.dll` (`.so`, `.dylib`) exporting a small C ABI:
+//!
+//! - `uint32_t riv_plugin_abi_version(void)` returning [`ABI_VERSION`] (required).
+//! - `const char *riv_plugin_name(void)` (optional, the file name is used otherwise).
+//! - A decoder: `const char *riv_decoder_extensions(void)` with comma-separated extensions
+//!   such as `"qoi, pcx"`, `int32_t riv_decode(const uint8_t *path, size_t path_len,
+//!   RivImage *out)` filling `out` with straight RGBA8 pixels and returning 0 on success, and
+//!   `void riv_free_image(RivImage *image)` releasing them.
+//! - A filter: `const char *riv_filter_name(void)` and `int32_t riv_filter_apply(uint8_t
+//!   *pixels, uint32_t width, uint32_t height)` editing RGBA8 pixels in place, 0 on success.
+//!
+//! `path` is UTF-8 and not NUL-terminated. Decoders and filters run on worker threads, several
+//! at a time, so they must be thread-safe. Built-in formats are registered before any plugin and
+//! keep the built-in decoders; filters run on still images in the order `plugin_filters` lists
+//! them.
+
+use std::collections::HashMap;
+use std::ffi::{c_char, CStr};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use libloading::Library;
+
+use crate::image_loader::{
+    decode_builtin_image, MediaType, SUPPORTED_IMAGE_EXTENSIONS, SUPPORTED_VIDEO_EXTENSIONS,
+};
+use crate::storage;
+
+/// Plugins built for another ABI version are skipped.
+pub const ABI_VERSION: u32 = 1;
+
+/// Image handed over by `riv_decode`; the pixels stay owned by the plugin until
+/// `riv_free_image`.
+#[repr(C)]
+pub struct RivImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: *mut u8,
+}
+
+type AbiVersionFn = unsafe extern "C" fn() -> u32;
+type NameFn = unsafe extern "C" fn() -> *const c_char;
+type DecodeFn = unsafe extern "C" fn(*const u8, usize, *mut RivImage) -> i32;
+type FreeImageFn = unsafe extern "C" fn(*mut RivImage);
+type FilterFn = unsafe extern "C" fn(*mut u8, u32, u32) -> i32;
+
+/// Width, height and RGBA8 pixels.
+type Decoded = Result<(u32, u32, Vec<u8>), String>;
+type DecodeImpl = Box<dyn Fn(&Path) -> Decoded + Send + Sync>;
+type FilterImpl = Box<dyn Fn(u32, u32, &mut [u8]) -> Result<(), String> + Send + Sync>;
+
+/// How files with one extension are opened.
+enum Decoder {
+    /// Still image, decoded to RGBA8 in process.
+    Image(DecodeImpl),
+    /// Video, played through GStreamer.
+    Video,
+}
+
+struct Format {
+    decoder: Decoder,
+    /// Plugin that registered the format; `None` for built-in formats.
+    plugin: Option<String>,
+}
+
+/// Every format the viewer opens, and the enabled plugin filters.
+#[derive(Default)]
+pub struct FormatRegistry {
+    /// Lower-case extension to the format registered for it first.
+    formats: HashMap<String, Format>,
+    /// Filters named in `plugin_filters`, in that order.
+    filters: Vec<(String, FilterImpl)>,
+    /// Loaded plugin names, for logs and diagnostics.
+    plugins: Vec<String>,
+    /// Keeps the plugin code mapped; function pointers above point into it.
+    _libraries: Vec<Library>,
+}
+
+static REGISTRY: OnceLock<FormatRegistry> = OnceLock::new();
+
+/// Folder plugins are loaded from.
+pub fn plugins_dir() -> PathBuf {
+    storage::config_dir().join("plugins")
+}
+
+/// Loads the plugins folder. Call once at startup, before any media is decoded; later calls
+/// keep the first registry.
+pub fn init(enabled_filters: &[String]) {
+    let registry = FormatRegistry::load_dir(&plugins_dir(), enabled_filters);
+    let _ = REGISTRY.set(registry);
+}
+
+/// The registry; only the built-in formats until [`init`] runs.
+pub fn registry() -> &'static FormatRegistry {
+    REGISTRY.get_or_init(FormatRegistry::with_builtins)
+}
+
+/// Reads the exported function `name` (NUL-terminated) as `T`.
+///
+/// # Safety
+/// `T` must be the function type the ABI documents for `name`.
+unsafe fn export<T: Copy>(library: &Library, name: &[u8]) -> Option<T> {
+    library.get::<T>(name).ok().map(|symbol| *symbol)
+}
+
+fn plugin_string(function: Option<NameFn>) -> Option<String> {
+    // SAFETY: the ABI asks for a NUL-terminated string that outlives the library.
+    let pointer = unsafe { function?() };
+    if pointer.is_null() {
+        return None;
+    }
+    Some(
+        unsafe { CStr::from_ptr(pointer) }
+            .to_string_lossy()
+            .into_owned(),
+    )
+}
+
+/// Comma-separated extensions, lower-cased, without dots or blanks.
+fn parse_extensions(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|extension| {
+            extension
+                .trim()
+                .trim_start_matches('.')
+                .to_ascii_lowercase()
+        })
+        .filter(|extension| !extension.is_empty())
+        .collect()
+}
+
+impl FormatRegistry {
+    fn with_builtins() -> Self {
+        let mut registry = Self::default();
+        for extension in SUPPORTED_IMAGE_EXTENSIONS {
+            registry.register(
+                extension,
+                Decoder::Image(Box::new(decode_builtin_image)),
+                None,
+            );
+        }
+        for extension in SUPPORTED_VIDEO_EXTENSIONS {
+            registry.register(extension, Decoder::Video, None);
+        }
+        registry
+    }
+
+    /// Adds `extension` unless a format is already registered for it.
+    fn register(&mut self, extension: &str, decoder: Decoder, plugin: Option<&str>) {
+        self.formats
+            .entry(extension.to_ascii_lowercase())
+            .or_insert_with(|| Format {
+                decoder,
+                plugin: plugin.map(str::to_string),
+            });
+    }
+
+    fn load_dir(dir: &Path, enabled_filters: &[String]) -> Self {
+        let mut registry = Self::with_builtins();
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return registry;
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case(std::env::consts::DLL_EXTENSION))
+            })
+            .collect();
+        paths.sort();
+
+        let mut filters = Vec::new();
+        for path in paths {
+            match registry.load_library(&path, &mut filters) {
+                Ok(name) => tracing::info!(target: "plugins", "Loaded plugin {name}"),
+                Err(err) => tracing::warn!(
+                    target: "plugins",
+                    "Skipped plugin {}: {err}",
+                    path.display()
+                ),
+            }
+        }
+        for name in enabled_filters {
+            match filters.iter().position(|(filter, _)| filter == name) {
+                Some(index) => registry.filters.push(filters.swap_remove(index)),
+                None => tracing::warn!(target: "plugins", "No plugin provides filter {name}"),
+            }
+        }
+        registry
+    }
+
+    /// Registers what the library at `path` exports and returns its name.
+    fn load_library(
+        &mut self,
+        path: &Path,
+        filters: &mut Vec<(String, FilterImpl)>,
+    ) -> Result<String, String> {
+        // SAFETY: loading runs the library's initializers; plugins in the user's own config
+        // folder are trusted like the viewer itself.
+        let library = unsafe { Library::new(path) }.map_err(|err| err.to_string())?;
+        let abi_version: AbiVersionFn = unsafe { export(&library, b"riv_plugin_abi_version\0") }
+            .ok_or_else(|| "riv_plugin_abi_version is missing".to_string())?;
+        let version = unsafe { abi_version() };
+        if version != ABI_VERSION {
+            return Err(format!(
+                "built for plugin ABI {version}, this viewer uses {ABI_VERSION}"
+            ));
+        }
+        let name =
+            plugin_string(unsafe { export(&library, b"riv_plugin_name\0") }).unwrap_or_else(|| {
+                path.file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            });
+
+        let decoder: (Option<DecodeFn>, Option<FreeImageFn>, _) = unsafe {
+            (
+                export(&library, b"riv_decode\0"),
+                export(&library, b"riv_free_image\0"),
+                plugin_string(export(&library, b"riv_decoder_extensions\0")),
+            )
+        };
+        if let (Some(decode), Some(free_image), Some(extensions)) = decoder {
+            for extension in parse_extensions(&extensions) {
+                let decoder =
+                    Decoder::Image(Box::new(move |path| decode_with(decode, free_image, path)));
+                self.register(&extension, decoder, Some(&name));
+            }
+        }
+
+        let filter: (Option<FilterFn>, _) = unsafe {
+            (
+                export(&library, b"riv_filter_apply\0"),
+                plugin_string(export(&library, b"riv_filter_name\0")),
+            )
+        };
+        if let (Some(apply), Some(filter_name)) = filter {
+            let filter: FilterImpl = Box::new(move |width, height, pixels: &mut [u8]| {
+                // SAFETY: `pixels` holds `width * height` RGBA8 pixels, as the ABI promises.
+                match unsafe { apply(pixels.as_mut_ptr(), width, height) } {
+                    0 => Ok(()),
+                    code => Err(format!("filter failed with code {code}")),
+                }
+            });
+            filters.push((filter_name, filter));
+        }
+
+        self.plugins.push(name.clone());
+        self._libraries.push(library);
+        Ok(name)
+    }
+
+    pub fn plugin_names(&self) -> &[String] {
+        &self.plugins
+    }
+
+    /// Every registered extension, in no particular order.
+    #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+    pub fn extensions(&self) -> impl Iterator<Item = &str> {
+        self.formats.keys().map(String::as_str)
+    }
+
+    /// Extensions plugins added, in no particular order.
+    pub fn plugin_extensions(&self) -> impl Iterator<Item = &str> {
+        self.formats
+            .iter()
+            .filter(|(_, format)| format.plugin.is_some())
+            .map(|(extension, _)| extension.as_str())
+    }
+
+    fn format_for(&self, path: &Path) -> Option<&Format> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        self.formats.get(&extension)
+    }
+
+    /// Whether `path` opens as an image or a video; `None` for unknown extensions.
+    pub fn media_type(&self, path: &Path) -> Option<MediaType> {
+        self.format_for(path).map(|format| match format.decoder {
+            Decoder::Image(_) => MediaType::Image,
+            Decoder::Video => MediaType::Video,
+        })
+    }
+
+    /// Decodes `path` with the image decoder registered for its extension, as RGBA8. `None`
+    /// when no image decoder handles it.
+    pub fn decode(&self, path: &Path) -> Option<Decoded> {
+        match &self.format_for(path)?.decoder {
+            Decoder::Image(decode) => Some(decode(path)),
+            Decoder::Video => None,
+        }
+    }
+
+    /// Runs the enabled filters over RGBA8 pixels. A failing filter is logged and skipped.
+    pub fn apply_filters(&self, width: u32, height: u32, pixels: &mut [u8]) {
+        for (name, filter) in &self.filters {
+            if let Err(err) = filter(width, height, pixels) {
+                tracing::warn!(target: "plugins", "Plugin filter {name}: {err}");
+            }
+        }
+    }
+}
+
+fn decode_with(decode: DecodeFn, free_image: FreeImageFn, path: &Path) -> Decoded {
+    let path = path.to_string_lossy();
+    let mut image = RivImage {
+        width: 0,
+        height: 0,
+        pixels: std::ptr::null_mut(),
+    };
+    // SAFETY: the plugin fills `image` and owns its pixels until `free_image`.
+    let code = unsafe { decode(path.as_ptr(), path.len(), &mut image) };
+    if code != 0 {
+        if !image.pixels.is_null() {
+            unsafe { free_image(&mut image) };
+        }
+        return Err(format!("Plugin decoder failed with code {code}"));
+    }
+    if image.pixels.is_null() || image.width == 0 || image.height == 0 {
+        return Err("Plugin decoder returned an empty image".to_string());
+    }
+    let len = image.width as usize * image.height as usize * 4;
+    // SAFETY: a successful decode hands over `width * height` RGBA8 pixels.
+    let pixels = unsafe { std::slice::from_raw_parts(image.pixels, len) }.to_vec();
+    let (width, height) = (image.width, image.height);
+    unsafe { free_image(&mut image) };
+    Ok((width, height, pixels))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_extensions, Decoder, FormatRegistry};
+    use crate::image_loader::MediaType;
+    use std::path::Path;
+
+    #[test]
+    fn decoders_match_extensions_case_insensitively() {
+        assert_eq!(parse_extensions(" QOI, .pcx,, "), ["qoi", "pcx"]);
+
+        let mut registry = FormatRegistry::with_builtins();
+        registry.register(
+            "QOI",
+            Decoder::Image(Box::new(|_| Ok((1, 1, vec![1, 2, 3, 255])))),
+            Some("qoi"),
+        );
+        registry.register(
+            "png",
+            Decoder::Image(Box::new(|_| Err("shadowed".to_string()))),
+            Some("png"),
+        );
+        registry.filters.push((
+            "invert".to_string(),
+            Box::new(|_, _, pixels: &mut [u8]| {
+                pixels[..3]
+                    .iter_mut()
+                    .for_each(|value| *value = 255 - *value);
+                Ok(())
+            }),
+        ));
+        assert_eq!(
+            registry.media_type(Path::new("a/picture.QOI")),
+            Some(MediaType::Image)
+        );
+        assert_eq!(
+            registry.media_type(Path::new("clip.MKV")),
+            Some(MediaType::Video)
+        );
+        assert_eq!(registry.media_type(Path::new("notes.txt")), None);
+        assert!(registry.decode(Path::new("clip.mkv")).is_none());
+        // Built-in formats were registered first and keep their decoders.
+        assert_eq!(registry.plugin_extensions().collect::<Vec<_>>(), ["qoi"]);
+
+        let (width, height, mut pixels) = registry.decode(Path::new("x.qoi")).unwrap().unwrap();
+        registry.apply_filters(width, height, &mut pixels);
+        assert_eq!(pixels, [254, 253, 252, 255]);
+    }
+}
@@ -0,0 +1,127 @@
+//! PNG output shared by the export tools, and the alpha handling around it.
+//!
+//! Exports work on straight (non-premultiplied) RGBA8, never on the premultiplied texture
+//! data egui draws from. Resampling weights neighbours by their alpha, so fully transparent
+//! pixels (usually stored as black) cannot bleed dark fringes into the edges next to them.
+//! Images with transparency are written with their alpha, or flattened onto
+//! `export_background_rgb` when `export_alpha = flatten`.
+
+use std::path::Path;
+
+use crate::config::ExportAlpha;
+
+/// Writes straight RGBA8 `pixels` as PNG. With a `background`, the image is flattened onto
+/// it and written as opaque RGB.
+pub fn write_png(
+    pixels: Vec<u8>,
+    width: u32,
+    height: u32,
+    background: Option<[u8; 3]>,
+    target: &Path,
+) -> Result<(), String> {
+    let mismatch = || "Frame buffer does not match its dimensions".to_string();
+    let result = match background {
+        Some(background) => {
+            image::RgbImage::from_raw(width, height, flatten_rgba(&pixels, background))
+                .ok_or_else(mismatch)?
+                .save_with_format(target, image::ImageFormat::Png)
+        }
+        None => image::RgbaImage::from_raw(width, height, pixels)
+            .ok_or_else(mismatch)?
+            .save_with_format(target, image::ImageFormat::Png),
+    };
+    result.map_err(|err| format!("Failed to write {}: {err}", target.display()))
+}
+
+/// Straight RGBA8 `pixels` composited over an opaque `background`, as RGB8.
+pub fn flatten_rgba(pixels: &[u8], background: [u8; 3]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(pixels.len() / 4 * 3);
+    for pixel in pixels.chunks_exact(4) {
+        let alpha = pixel[3] as u32;
+        for (value, background) in pixel[..3].iter().zip(background) {
+            out.push(
+                ((*value as u32 * alpha + background as u32 * (255 - alpha) + 127) / 255) as u8,
+            );
+        }
+    }
+    out
+}
+
+/// Bilinear blend of four straight RGBA texels (top left, top right, bottom left, bottom
+/// right) at the fractions `fx`, `fy` between them. Colors are weighted by their alpha, so a
+/// transparent neighbour lowers the coverage without darkening the color.
+pub fn blend_bilinear(texels: [[f32; 4]; 4], fx: f32, fy: f32) -> [u8; 4] {
+    let weights = [
+        (1.0 - fx) * (1.0 - fy),
+        fx * (1.0 - fy),
+        (1.0 - fx) * fy,
+        fx * fy,
+    ];
+    let mut color = [0.0f32; 3];
+    let mut alpha = 0.0f32;
+    for (texel, weight) in texels.iter().zip(weights) {
+        let coverage = texel[3] * weight;
+        for (sum, value) in color.iter_mut().zip(texel) {
+            *sum += value * coverage;
+        }
+        alpha += coverage;
+    }
+    if alpha <= 0.0 {
+        return [0; 4];
+    }
+    let [r, g, b] = color.map(|sum| (sum / alpha).round().clamp(0.0, 255.0) as u8);
+    [r, g, b, alpha.round().clamp(0.0, 255.0) as u8]
+}
+
+/// Keep alpha / flatten choice and the flatten color, shown next to the PNG export buttons.
+/// Returns whether either changed.
+pub fn alpha_option_ui(
+    ui: &mut egui::Ui,
+    alpha: &mut ExportAlpha,
+    background: &mut [u8; 3],
+) -> bool {
+    let mut changed = ui
+        .selectable_value(alpha, ExportAlpha::Keep, "Keep alpha")
+        .on_hover_text("Write the transparent areas as they are")
+        .changed();
+    changed |= ui
+        .selectable_value(alpha, ExportAlpha::Flatten, "Flatten")
+        .on_hover_text("Fill the transparent areas with a solid color")
+        .changed();
+    if *alpha == ExportAlpha::Flatten {
+        changed |= egui::color_picker::color_edit_button_srgb(ui, background)
+            .on_hover_text("Flatten color")
+            .changed();
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{blend_bilinear, flatten_rgba};
+
+    #[test]
+    fn flattening_blends_by_alpha() {
+        let pixels = [255, 0, 0, 255, 255, 0, 0, 0, 0, 0, 255, 128];
+        assert_eq!(
+            flatten_rgba(&pixels, [255, 255, 255]),
+            vec![255, 0, 0, 255, 255, 255, 127, 127, 255]
+        );
+    }
+
+    #[test]
+    fn transparent_neighbours_do_not_darken_edges() {
+        let red = [255.0, 0.0, 0.0, 255.0];
+        let clear = [0.0, 0.0, 0.0, 0.0];
+        assert_eq!(
+            blend_bilinear([red, clear, red, clear], 0.5, 0.5),
+            [255, 0, 0, 128]
+        );
+        assert_eq!(blend_bilinear([clear; 4], 0.3, 0.7), [0; 4]);
+        let gray = [100.0, 100.0, 100.0, 255.0];
+        assert_eq!(
+            blend_bilinear([gray, red, gray, red], 0.25, 0.0),
+            [139, 75, 75, 255]
+        );
+    }
+}
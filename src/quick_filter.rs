@@ -0,0 +1,240 @@
+//! Quick filter: narrows the current folder to the files matching a typed query.
+//!
+//! The query is a list of space-separated terms that must all match:
+//!
+//! - a bare word matches a substring of the file name, ignoring case;
+//! - `.png` or `ext:png` matches the extension, `ext:jpg,png` either of them;
+//! - `type:image`, `type:video` or `type:animated` matches the media type (comma lists work);
+//! - `rating:3` matches three stars or more, `rating:pick` and `rating:reject` the flags.
+//!
+//! The viewer keeps the unfiltered folder listing while a filter is active so clearing it
+//! restores the folder, and re-applies the query when a rescan delivers a new listing.
+
+use std::path::{Path, PathBuf};
+
+use crate::image_loader::{get_media_type, is_supported_video, LoadedImage};
+use crate::ratings::{Flag, ImageRating};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaKind {
+    Image,
+    Video,
+    /// GIFs and animated WebPs.
+    Animated,
+}
+
+impl MediaKind {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "image" | "images" | "img" | "still" => Some(Self::Image),
+            "video" | "videos" | "vid" => Some(Self::Video),
+            "animated" | "animation" | "anim" | "gif" => Some(Self::Animated),
+            _ => None,
+        }
+    }
+}
+
+/// The kind of `path` for `type:` terms. Every GIF counts as animated; WebPs are checked
+/// for the animation flag in their header.
+pub fn media_kind(path: &Path) -> MediaKind {
+    if is_supported_video(path) {
+        return MediaKind::Video;
+    }
+    let is_gif = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("gif"));
+    if is_gif || LoadedImage::is_animated_webp(path) {
+        MediaKind::Animated
+    } else {
+        MediaKind::Image
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Term {
+    /// Lowercase file name substring.
+    Name(String),
+    /// Lowercase extensions without the dot, any of which matches.
+    Extension(Vec<String>),
+    Kind(Vec<MediaKind>),
+    MinStars(u8),
+    Flag(Flag),
+}
+
+/// A parsed query. Terms that cannot be parsed (`type:foo`) match nothing, so a typo shows
+/// an empty result rather than silently widening it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query {
+    terms: Vec<Term>,
+    invalid: bool,
+}
+
+impl Query {
+    pub fn parse(text: &str) -> Self {
+        let mut query = Self::default();
+        for word in text.split_whitespace() {
+            let word = word.to_lowercase();
+            let term = if let Some(extensions) = word.strip_prefix("ext:") {
+                Some(Term::Extension(
+                    extensions
+                        .split(',')
+                        .map(|ext| ext.trim_start_matches('.').to_string())
+                        .filter(|ext| !ext.is_empty())
+                        .collect(),
+                ))
+            } else if let Some(kinds) = word.strip_prefix("type:") {
+                kinds
+                    .split(',')
+                    .filter(|kind| !kind.is_empty())
+                    .map(MediaKind::parse)
+                    .collect::<Option<Vec<_>>>()
+                    .map(Term::Kind)
+            } else if let Some(rating) = word.strip_prefix("rating:") {
+                match rating {
+                    "pick" | "picked" => Some(Term::Flag(Flag::Pick)),
+                    "reject" | "rejected" => Some(Term::Flag(Flag::Reject)),
+                    stars => stars
+                        .parse::<u8>()
+                        .ok()
+                        .filter(|stars| *stars <= 5)
+                        .map(Term::MinStars),
+                }
+            } else if word.len() > 1 && word.starts_with('.') {
+                Some(Term::Extension(vec![word[1..].to_string()]))
+            } else {
+                Some(Term::Name(word))
+            };
+            match term {
+                Some(term) => query.terms.push(term),
+                None => query.invalid = true,
+            }
+        }
+        query
+    }
+
+    /// Whether the query matches everything, i.e. has no terms at all.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && !self.invalid
+    }
+
+    /// Whether `path` passes every term. `rating` is only asked for when a `rating:` term
+    /// needs it, and the media type is only probed for `type:` terms.
+    pub fn matches(&self, path: &Path, mut rating: impl FnMut(&Path) -> ImageRating) -> bool {
+        if self.invalid {
+            return false;
+        }
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let mut kind = None;
+        self.terms.iter().all(|term| match term {
+            Term::Name(part) => name.contains(part.as_str()),
+            Term::Extension(extensions) => extensions.contains(&extension),
+            Term::Kind(kinds) => kinds.contains(kind.get_or_insert_with(|| media_kind(path))),
+            Term::MinStars(stars) => rating(path).stars >= *stars,
+            Term::Flag(flag) => rating(path).flag == *flag,
+        })
+    }
+}
+
+/// The active filter: the typed text, the folder it applies to and the unfiltered listing.
+pub struct QuickFilter {
+    pub text: String,
+    /// Folder whose listing is filtered; loading media from another folder drops the filter.
+    pub folder: Option<PathBuf>,
+    /// The folder listing without the filter.
+    pub source: Vec<PathBuf>,
+    /// Media files in `source`, not counting folder navigation entries.
+    pub media_count: usize,
+    /// Media files in `source` that match, updated whenever the filter is applied.
+    pub match_count: usize,
+    /// The text field has keyboard focus, so typing edits the query instead of running
+    /// shortcuts.
+    pub editing: bool,
+    /// Drops the text typed this frame: the key that opened the filter is also a character.
+    pub swallow_opening_key: bool,
+}
+
+impl QuickFilter {
+    pub fn new(folder: Option<PathBuf>, source: Vec<PathBuf>) -> Self {
+        let media_count = source
+            .iter()
+            .filter(|path| get_media_type(path).is_some())
+            .count();
+        Self {
+            text: String::new(),
+            folder,
+            source,
+            media_count,
+            match_count: media_count,
+            editing: true,
+            swallow_opening_key: true,
+        }
+    }
+
+    /// Whether `files` is a listing of the filtered folder.
+    pub fn covers(&self, files: &[PathBuf]) -> bool {
+        self.folder
+            .as_deref()
+            .is_some_and(|folder| files.iter().any(|path| path.parent() == Some(folder)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::Query;
+    use crate::ratings::{Flag, ImageRating};
+
+    fn unrated(_: &Path) -> ImageRating {
+        ImageRating::default()
+    }
+
+    #[test]
+    fn terms_match_name_extension_and_type() {
+        let query = Query::parse("Beach .JPG");
+        assert!(query.matches(Path::new("/photos/beach-01.jpg"), unrated));
+        assert!(!query.matches(Path::new("/photos/beach-01.png"), unrated));
+        assert!(!query.matches(Path::new("/photos/city.jpg"), unrated));
+
+        let query = Query::parse("ext:png,jpg");
+        assert!(query.matches(Path::new("a.png"), unrated));
+        assert!(query.matches(Path::new("a.jpg"), unrated));
+        assert!(!query.matches(Path::new("a.gif"), unrated));
+
+        let query = Query::parse("type:video,animated");
+        assert!(query.matches(Path::new("clip.mp4"), unrated));
+        assert!(query.matches(Path::new("loop.gif"), unrated));
+        assert!(!query.matches(Path::new("photo.jpg"), unrated));
+
+        assert!(Query::parse("   ").is_empty());
+        assert!(!Query::parse("type:sound").matches(Path::new("clip.mp4"), unrated));
+    }
+
+    #[test]
+    fn rating_terms_ask_for_the_rating() {
+        let rated = |_: &Path| ImageRating {
+            stars: 4,
+            flag: Flag::Pick,
+        };
+        assert!(Query::parse("rating:3").matches(Path::new("a.jpg"), rated));
+        assert!(!Query::parse("rating:5").matches(Path::new("a.jpg"), rated));
+        assert!(Query::parse("rating:pick").matches(Path::new("a.jpg"), rated));
+        assert!(!Query::parse("rating:reject").matches(Path::new("a.jpg"), rated));
+        assert!(!Query::parse("rating:3").matches(Path::new("a.jpg"), unrated));
+
+        let mut asked = false;
+        Query::parse("a").matches(Path::new("a.jpg"), |_| {
+            asked = true;
+            ImageRating::default()
+        });
+        assert!(!asked);
+    }
+}
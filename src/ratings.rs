@@ -0,0 +1,310 @@
+//! Star ratings and pick/reject flags for culling, stored in XMP sidecars.
+//!
+//! Sidecars follow the Adobe convention: `photo.jpg` -> `photo.xmp`, so ratings show up in
+//! Lightroom, Bridge, darktable and digiKam. The rating is `xmp:Rating` (`-1` marks a reject)
+//! and a pick is `xmpDM:good`. Existing sidecars are edited in place so metadata written by
+//! other tools survives; a sidecar is only created once an image gets a rating or a flag.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::config::RatingFilter;
+
+const XMP_NAMESPACE: &str = "http://ns.adobe.com/xap/1.0/";
+const XMP_DM_NAMESPACE: &str = "http://ns.adobe.com/xmp/1.0/DynamicMedia/";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Flag {
+    #[default]
+    None,
+    Pick,
+    Reject,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ImageRating {
+    /// `0` (unrated) to `5`.
+    pub stars: u8,
+    pub flag: Flag,
+}
+
+impl ImageRating {
+    pub fn passes(&self, filter: RatingFilter) -> bool {
+        match filter {
+            RatingFilter::All => true,
+            RatingFilter::Rated => self.stars > 0,
+            RatingFilter::Picked => self.flag == Flag::Pick,
+            RatingFilter::Rejected => self.flag == Flag::Reject,
+            RatingFilter::Unrejected => self.flag != Flag::Reject,
+        }
+    }
+
+    /// `★★★☆☆`, followed by the flag if there is one. Empty when unrated and unflagged.
+    pub fn label(&self) -> String {
+        let mut label = String::new();
+        if self.stars > 0 {
+            label.extend((0..5).map(|star| if star < self.stars { '★' } else { '☆' }));
+        }
+        let flag = match self.flag {
+            Flag::None => return label,
+            Flag::Pick => "⚑ Pick",
+            Flag::Reject => "✖ Reject",
+        };
+        if !label.is_empty() {
+            label.push(' ');
+        }
+        label.push_str(flag);
+        label
+    }
+}
+
+/// `photo.jpg` -> `photo.xmp`.
+pub fn sidecar_path(image_path: &Path) -> PathBuf {
+    image_path.with_extension("xmp")
+}
+
+/// Value of `property` (`prefix:Name`) written either as an attribute or as a simple element.
+fn property_value<'a>(xmp: &'a str, property: &str) -> Option<&'a str> {
+    let attribute = format!("{property}=");
+    if let Some(start) = xmp.find(&attribute) {
+        let rest = &xmp[start + attribute.len()..];
+        let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let rest = &rest[1..];
+        return rest.find(quote).map(|end| rest[..end].trim());
+    }
+    let open = format!("<{property}>");
+    let start = xmp.find(&open)? + open.len();
+    let end = xmp[start..].find(&format!("</{property}>"))?;
+    Some(xmp[start..start + end].trim())
+}
+
+/// Reads the rating and flag from sidecar text. Unknown or missing values read as unrated.
+pub fn parse_xmp(xmp: &str) -> ImageRating {
+    let rating = property_value(xmp, "xmp:Rating")
+        .and_then(|value| value.parse::<f32>().ok())
+        .unwrap_or(0.0);
+    let good =
+        property_value(xmp, "xmpDM:good").is_some_and(|value| value.eq_ignore_ascii_case("true"));
+    let flag = if rating < 0.0 {
+        Flag::Reject
+    } else if good {
+        Flag::Pick
+    } else {
+        Flag::None
+    };
+    ImageRating {
+        stars: rating.clamp(0.0, 5.0).round() as u8,
+        flag,
+    }
+}
+
+/// Sets `property` in place, or adds it (and its namespace) to the first `rdf:Description`.
+fn set_property(
+    xmp: &mut String,
+    property: &str,
+    namespace: &str,
+    value: &str,
+) -> Result<(), String> {
+    let attribute = format!("{property}=");
+    if let Some(start) = xmp.find(&attribute) {
+        let value_start = start + attribute.len() + 1;
+        let quote = xmp[value_start - 1..].chars().next().unwrap_or('"');
+        let end = xmp[value_start..]
+            .find(quote)
+            .ok_or_else(|| format!("Malformed {property} in the sidecar"))?;
+        xmp.replace_range(value_start..value_start + end, value);
+        return Ok(());
+    }
+    let open = format!("<{property}>");
+    if let Some(start) = xmp.find(&open) {
+        let value_start = start + open.len();
+        let end = xmp[value_start..]
+            .find(&format!("</{property}>"))
+            .ok_or_else(|| format!("Malformed {property} in the sidecar"))?;
+        xmp.replace_range(value_start..value_start + end, value);
+        return Ok(());
+    }
+    let description = xmp
+        .find("<rdf:Description")
+        .ok_or_else(|| "The sidecar has no rdf:Description to add the rating to".to_string())?
+        + "<rdf:Description".len();
+    let prefix = property.split(':').next().unwrap_or(property);
+    let mut insert = String::new();
+    if !xmp.contains(&format!("xmlns:{prefix}=")) {
+        insert.push_str(&format!("\n    xmlns:{prefix}=\"{namespace}\""));
+    }
+    insert.push_str(&format!("\n    {property}=\"{value}\""));
+    xmp.insert_str(description, &insert);
+    Ok(())
+}
+
+/// Sidecar text carrying `rating`: `existing` edited in place, or a new minimal packet.
+pub fn update_xmp(existing: Option<&str>, rating: ImageRating) -> Result<String, String> {
+    let stars = if rating.flag == Flag::Reject {
+        "-1".to_string()
+    } else {
+        rating.stars.min(5).to_string()
+    };
+    let good = if rating.flag == Flag::Pick {
+        "True"
+    } else {
+        "False"
+    };
+    let mut xmp = existing.map(str::to_string).unwrap_or_else(|| {
+        concat!(
+            "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n",
+            "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n",
+            " <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n",
+            "  <rdf:Description rdf:about=\"\"/>\n",
+            " </rdf:RDF>\n",
+            "</x:xmpmeta>\n",
+            "<?xpacket end=\"w\"?>\n",
+        )
+        .to_string()
+    });
+    set_property(&mut xmp, "xmp:Rating", XMP_NAMESPACE, &stars)?;
+    set_property(&mut xmp, "xmpDM:good", XMP_DM_NAMESPACE, good)?;
+    Ok(xmp)
+}
+
+/// Ratings of the folders visited this session, keyed by sidecar path.
+#[derive(Default)]
+pub struct RatingStore {
+    ratings: HashMap<PathBuf, ImageRating>,
+    loaded_directories: HashSet<PathBuf>,
+}
+
+impl RatingStore {
+    /// Reads every sidecar in `directory` once; later lookups in it are map hits.
+    fn load_directory(&mut self, directory: &Path) {
+        if !self.loaded_directories.insert(directory.to_path_buf()) {
+            return;
+        }
+        let Ok(entries) = std::fs::read_dir(directory) else {
+            return;
+        };
+        for path in entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+        {
+            let is_sidecar = path
+                .extension()
+                .is_some_and(|extension| extension.eq_ignore_ascii_case("xmp"));
+            if !is_sidecar {
+                continue;
+            }
+            if let Ok(text) = std::fs::read_to_string(&path) {
+                self.ratings.insert(path, parse_xmp(&text));
+            }
+        }
+    }
+
+    /// Drops what is known about `directory`, so the next lookup rereads its sidecars.
+    pub fn forget_directory(&mut self, directory: &Path) {
+        if self.loaded_directories.remove(directory) {
+            self.ratings
+                .retain(|sidecar, _| sidecar.parent() != Some(directory));
+        }
+    }
+
+    pub fn get(&mut self, image_path: &Path) -> ImageRating {
+        if let Some(directory) = image_path.parent() {
+            self.load_directory(directory);
+        }
+        self.ratings
+            .get(&sidecar_path(image_path))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Writes `rating` to the image's sidecar. Unrated, unflagged images without a sidecar
+    /// are left alone rather than getting an empty one.
+    pub fn set(&mut self, image_path: &Path, mut rating: ImageRating) -> Result<(), String> {
+        if rating.flag == Flag::Reject {
+            // A reject is stored as rating -1, so it replaces the stars.
+            rating.stars = 0;
+        }
+        let sidecar = sidecar_path(image_path);
+        let existing = std::fs::read_to_string(&sidecar).ok();
+        if existing.is_none() && rating == ImageRating::default() {
+            self.ratings.remove(&sidecar);
+            return Ok(());
+        }
+        let xmp = update_xmp(existing.as_deref(), rating)?;
+        std::fs::write(&sidecar, xmp)
+            .map_err(|err| format!("Failed to write {}: {err}", sidecar.display()))?;
+        self.ratings.insert(sidecar, rating);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_xmp, update_xmp, Flag, ImageRating};
+    use crate::config::RatingFilter;
+
+    #[test]
+    fn new_sidecars_round_trip_ratings_and_flags() {
+        for rating in [
+            ImageRating {
+                stars: 4,
+                flag: Flag::Pick,
+            },
+            ImageRating {
+                stars: 0,
+                flag: Flag::Reject,
+            },
+            ImageRating {
+                stars: 2,
+                flag: Flag::None,
+            },
+        ] {
+            let xmp = update_xmp(None, rating).unwrap();
+            assert_eq!(parse_xmp(&xmp), rating);
+        }
+        let picked = ImageRating {
+            stars: 3,
+            flag: Flag::Pick,
+        };
+        assert!(picked.passes(RatingFilter::Rated) && picked.passes(RatingFilter::Picked));
+        assert!(!picked.passes(RatingFilter::Rejected));
+        assert_eq!(picked.label(), "★★★☆☆ ⚑ Pick");
+    }
+
+    #[test]
+    fn existing_sidecars_keep_other_metadata() {
+        let lightroom = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+ <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+  <rdf:Description rdf:about=""
+    xmlns:xmp="http://ns.adobe.com/xap/1.0/"
+    xmlns:crs="http://ns.adobe.com/camera-raw-settings/1.0/"
+    xmp:Rating="1"
+    crs:Exposure2012="+0.35">
+   <dc:subject><rdf:Bag><rdf:li>beach</rdf:li></rdf:Bag></dc:subject>
+  </rdf:Description>
+ </rdf:RDF>
+</x:xmpmeta>"#;
+        assert_eq!(parse_xmp(lightroom).stars, 1);
+        let rating = ImageRating {
+            stars: 5,
+            flag: Flag::Pick,
+        };
+        let updated = update_xmp(Some(lightroom), rating).unwrap();
+        assert_eq!(parse_xmp(&updated), rating);
+        assert!(updated.contains(r#"crs:Exposure2012="+0.35""#));
+        assert!(updated.contains("<rdf:li>beach</rdf:li>"));
+        assert!(updated.contains("xmlns:xmpDM="));
+        assert_eq!(updated.matches("xmlns:xmp=").count(), 1);
+
+        let element_form = "<rdf:Description><xmp:Rating>3</xmp:Rating></rdf:Description>";
+        let updated = update_xmp(
+            Some(element_form),
+            ImageRating {
+                stars: 0,
+                flag: Flag::Reject,
+            },
+        )
+        .unwrap();
+        assert!(updated.contains("<xmp:Rating>-1</xmp:Rating>"));
+    }
+}
@@ -0,0 +1,440 @@
+//! Opening `http://` and `https://` URLs.
+//!
+//! A URL comes from the command line (or another instance forwarding it) or from pasting it
+//! into the window. Videos are not downloaded: the URL goes straight to the playback
+//! pipeline, which streams it. Anything else is downloaded to memory on a worker through
+//! GStreamer's HTTP source, with progress and a cancel button, then saved under `downloads`
+//! in the temp folder and opened like a local file.
+//!
+//! A playlist of URLs has the next few images downloaded ahead by a [`Prefetcher`]. Every
+//! URL is saved to a folder named after its hash, so an image downloaded once (ahead or
+//! not) opens from disk until the folder is cleared out after [`DOWNLOAD_RETENTION`].
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime};
+
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+
+use crate::image_loader::{is_supported_image, is_supported_video};
+
+/// Downloads larger than this are stopped.
+const MAX_DOWNLOAD_BYTES: u64 = 512 * 1024 * 1024;
+/// How long the worker waits for data before checking for cancellation again.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Downloaded files older than this are removed when the next download is saved.
+const DOWNLOAD_RETENTION: Duration = Duration::from_secs(7 * 86_400);
+const DOWNLOADS_DIR_NAME: &str = "downloads";
+
+/// `text` as an http(s) URL, if it is one.
+pub fn parse_url(text: &str) -> Option<String> {
+    let text = text.trim();
+    let scheme_end = text.find("://")?;
+    let scheme = &text[..scheme_end];
+    let has_host = text.len() > scheme_end + 3;
+    ((scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https"))
+        && has_host
+        && !text.contains(char::is_whitespace))
+    .then(|| text.to_string())
+}
+
+/// Whether `path` holds a URL rather than a file path.
+pub fn is_url(path: &Path) -> bool {
+    path.to_str().and_then(parse_url).is_some()
+}
+
+/// The last path segment of `url`, without query or fragment, percent-decoded.
+fn url_file_name(url: &str) -> String {
+    let without_query = url.split(['?', '#']).next().unwrap_or(url);
+    let after_host = without_query
+        .split_once("://")
+        .map_or(without_query, |(_, rest)| rest);
+    let name = after_host
+        .split_once('/')
+        .and_then(|(_, path)| path.rsplit('/').next())
+        .map(percent_decode)
+        .unwrap_or_default();
+    let name: String = name
+        .chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let name = name.trim_matches(['.', ' ']);
+    if name.is_empty() {
+        "download".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+fn percent_decode(text: &str) -> String {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let decoded = (byte == b'%')
+            .then(|| tail.get(..2))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match decoded {
+            Some(decoded) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            None => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Whether `url` names a video, which is streamed instead of downloaded.
+pub fn is_video_url(url: &str) -> bool {
+    is_supported_video(Path::new(&url_file_name(url)))
+}
+
+/// URI for GStreamer: the URL itself, or a `file://` URI of a local path.
+pub fn media_uri(path: &Path) -> Result<String, String> {
+    if let Some(url) = path.to_str().and_then(parse_url) {
+        return Ok(url);
+    }
+    gst::glib::filename_to_uri(path, None)
+        .map(|uri| uri.to_string())
+        .map_err(|e| format!("Failed to build file URI for {:?}: {}", path, e))
+}
+
+fn downloads_dir() -> PathBuf {
+    crate::storage::temp_dir().join(DOWNLOADS_DIR_NAME)
+}
+
+/// Whether `path` is a downloaded copy, which has no folder to browse.
+pub fn is_download(path: &Path) -> bool {
+    path.starts_with(downloads_dir())
+}
+
+/// Folder the download of `url` is saved in.
+fn download_dir(url: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+    downloads_dir().join(format!("{:016x}", hasher.finish()))
+}
+
+/// The saved copy of `url`, when it was downloaded before and is still there.
+pub fn cached_download(url: &str) -> Option<PathBuf> {
+    std::fs::read_dir(download_dir(url))
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| path.is_file())
+}
+
+/// A download in progress.
+pub struct Download {
+    pub url: String,
+    /// File name shown while downloading.
+    pub name: String,
+    received: Arc<AtomicU64>,
+    /// Expected size; 0 while unknown.
+    total: Arc<AtomicU64>,
+    cancelled: Arc<AtomicBool>,
+    result: crossbeam_channel::Receiver<Result<PathBuf, String>>,
+}
+
+impl Download {
+    pub fn start(url: String) -> Self {
+        let received = Arc::new(AtomicU64::new(0));
+        let total = Arc::new(AtomicU64::new(0));
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let (tx, result) = crossbeam_channel::bounded(1);
+        let worker = Fetch {
+            url: url.clone(),
+            received: Arc::clone(&received),
+            total: Arc::clone(&total),
+            cancelled: Arc::clone(&cancelled),
+        };
+        crate::async_runtime::spawn_blocking_or_thread("url-download", move || {
+            let saved = worker
+                .run()
+                .and_then(|bytes| save_download(&worker.url, &bytes));
+            let _ = tx.send(saved);
+        });
+        Self {
+            name: url_file_name(&url),
+            url,
+            received,
+            total,
+            cancelled,
+            result,
+        }
+    }
+
+    /// Bytes received so far and the expected size, when the server sent one.
+    pub fn progress(&self) -> (u64, Option<u64>) {
+        let total = self.total.load(Ordering::Relaxed);
+        (
+            self.received.load(Ordering::Relaxed),
+            (total > 0).then_some(total),
+        )
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// The saved file or the error, once the download has ended.
+    pub fn try_finish(&self) -> Option<Result<PathBuf, String>> {
+        match self.result.try_recv() {
+            Ok(result) => Some(result),
+            Err(crossbeam_channel::TryRecvError::Empty) => None,
+            Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                Some(Err("The download stopped unexpectedly".to_string()))
+            }
+        }
+    }
+}
+
+impl Drop for Download {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+struct Fetch {
+    url: String,
+    received: Arc<AtomicU64>,
+    total: Arc<AtomicU64>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Fetch {
+    fn run(&self) -> Result<Vec<u8>, String> {
+        static GST_INIT: OnceLock<Result<(), String>> = OnceLock::new();
+        GST_INIT
+            .get_or_init(|| gst::init().map_err(|err| err.to_string()))
+            .clone()?;
+
+        let source = gst::Element::make_from_uri(gst::URIType::Src, &self.url, None)
+            .map_err(|_| "No GStreamer HTTP source is installed (souphttpsrc)".to_string())?;
+        let sink = gst_app::AppSink::builder().sync(false).build();
+        let pipeline = gst::Pipeline::new();
+        pipeline
+            .add_many([&source, sink.upcast_ref()])
+            .and_then(|()| source.link(&sink))
+            .map_err(|err| err.to_string())?;
+        let result = self.pull(&pipeline, &sink);
+        let _ = pipeline.set_state(gst::State::Null);
+        result
+    }
+
+    fn pull(&self, pipeline: &gst::Pipeline, sink: &gst_app::AppSink) -> Result<Vec<u8>, String> {
+        pipeline
+            .set_state(gst::State::Playing)
+            .map_err(|_| format!("Cannot open {}", self.url))?;
+        let bus = pipeline.bus().ok_or("The download pipeline has no bus")?;
+        let mut bytes = Vec::new();
+        loop {
+            if self.cancelled.load(Ordering::Relaxed) {
+                return Err("Cancelled".to_string());
+            }
+            if let Some(message) = bus.pop_filtered(&[gst::MessageType::Error]) {
+                if let gst::MessageView::Error(err) = message.view() {
+                    return Err(err.error().to_string());
+                }
+            }
+            let Some(sample) = sink.try_pull_sample(gst::ClockTime::from_nseconds(
+                POLL_INTERVAL.as_nanos() as u64,
+            )) else {
+                if sink.is_eos() {
+                    return Ok(bytes);
+                }
+                continue;
+            };
+            if self.total.load(Ordering::Relaxed) == 0 {
+                if let Some(total) = pipeline.query_duration::<gst::format::Bytes>() {
+                    self.total.store(*total, Ordering::Relaxed);
+                }
+            }
+            let Some(buffer) = sample.buffer() else {
+                continue;
+            };
+            let map = buffer.map_readable().map_err(|err| err.to_string())?;
+            bytes.extend_from_slice(map.as_slice());
+            self.received.store(bytes.len() as u64, Ordering::Relaxed);
+            if bytes.len() as u64 > MAX_DOWNLOAD_BYTES {
+                return Err(format!(
+                    "The file is larger than {} MiB",
+                    MAX_DOWNLOAD_BYTES / (1024 * 1024)
+                ));
+            }
+        }
+    }
+}
+
+/// Writes downloaded `bytes` to `downloads/<url hash>/<name>` and returns the path. The name
+/// gets the extension of the detected format when the URL does not carry a usable one.
+fn save_download(url: &str, bytes: &[u8]) -> Result<PathBuf, String> {
+    let mut name = url_file_name(url);
+    if !is_supported_image(Path::new(&name)) {
+        let format =
+            image::guess_format(bytes).map_err(|_| format!("{url} is not a supported image"))?;
+        let extension = format.extensions_str().first().copied().unwrap_or("img");
+        name = format!("{name}.{extension}");
+    }
+    remove_old_downloads(&downloads_dir());
+    let dir = download_dir(url);
+    std::fs::create_dir_all(&dir).map_err(|err| err.to_string())?;
+    let path = dir.join(name);
+    std::fs::write(&path, bytes)
+        .map_err(|err| format!("Failed to save {}: {err}", path.display()))?;
+    Ok(path)
+}
+
+/// Downloads of the images coming up in a playlist, running next to the one being shown.
+#[derive(Default)]
+pub struct Prefetcher {
+    downloads: Vec<Download>,
+    /// Wanted URLs found already downloaded, returned by the next [`Prefetcher::poll`].
+    cached: Vec<(String, PathBuf)>,
+    /// URLs that failed this session; they are not tried again in the background.
+    failed: HashSet<String>,
+}
+
+impl Prefetcher {
+    /// Downloads `urls` that are not on disk yet and cancels downloads of other URLs.
+    pub fn update(&mut self, urls: &[String]) {
+        self.downloads
+            .retain(|download| urls.contains(&download.url));
+        for url in urls {
+            if self.failed.contains(url)
+                || self.downloads.iter().any(|download| &download.url == url)
+                || self.cached.iter().any(|(cached, _)| cached == url)
+            {
+                continue;
+            }
+            match cached_download(url) {
+                Some(path) => self.cached.push((url.clone(), path)),
+                None => self.downloads.push(Download::start(url.clone())),
+            }
+        }
+    }
+
+    /// Hands over the running download of `url`, to show it in the foreground.
+    pub fn take(&mut self, url: &str) -> Option<Download> {
+        let index = self
+            .downloads
+            .iter()
+            .position(|download| download.url == url)?;
+        Some(self.downloads.remove(index))
+    }
+
+    /// Takes back a foreground download that is no longer shown. It keeps running while
+    /// [`Prefetcher::update`] still asks for its URL.
+    pub fn adopt(&mut self, download: Download) {
+        if !download.is_cancelled() {
+            self.downloads.push(download);
+        }
+    }
+
+    /// Downloads that ended since the last call, and URLs found on disk, by URL.
+    pub fn poll(&mut self) -> Vec<(String, Result<PathBuf, String>)> {
+        let mut finished: Vec<_> = self
+            .cached
+            .drain(..)
+            .map(|(url, path)| (url, Ok(path)))
+            .collect();
+        self.downloads
+            .retain(|download| match download.try_finish() {
+                Some(result) => {
+                    finished.push((download.url.clone(), result));
+                    false
+                }
+                None => true,
+            });
+        for (url, result) in &finished {
+            if result.is_err() {
+                self.failed.insert(url.clone());
+            }
+        }
+        finished
+    }
+
+    /// Running downloads with their received bytes and total expected size, the total being
+    /// known only when every server sent one.
+    pub fn progress(&self) -> Option<(usize, u64, Option<u64>)> {
+        if self.downloads.is_empty() {
+            return None;
+        }
+        let mut received = 0;
+        let mut total = Some(0);
+        for download in &self.downloads {
+            let (bytes, size) = download.progress();
+            received += bytes;
+            total = total.zip(size).map(|(sum, size)| sum + size);
+        }
+        Some((self.downloads.len(), received, total))
+    }
+}
+
+fn remove_old_downloads(downloads: &Path) {
+    let Ok(entries) = std::fs::read_dir(downloads) else {
+        return;
+    };
+    let now = SystemTime::now();
+    for entry in entries.flatten() {
+        let old = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_some_and(|age| age > DOWNLOAD_RETENTION);
+        if old {
+            let _ = std::fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_video_url, parse_url, url_file_name};
+
+    #[test]
+    fn recognizes_http_urls() {
+        assert_eq!(
+            parse_url("  https://example.com/a.png \n"),
+            Some("https://example.com/a.png".to_string())
+        );
+        assert!(parse_url("HTTP://example.com").is_some());
+        assert_eq!(parse_url("ftp://example.com/a.png"), None);
+        assert_eq!(parse_url("https://"), None);
+        assert_eq!(parse_url("C:\\photos\\a.png"), None);
+        assert_eq!(parse_url("see https://example.com"), None);
+    }
+
+    #[test]
+    fn file_names_come_from_the_url_path() {
+        assert_eq!(
+            url_file_name("https://example.com/photos/My%20Cat.jpg?size=large#top"),
+            "My Cat.jpg"
+        );
+        assert_eq!(url_file_name("https://example.com/"), "download");
+        assert_eq!(url_file_name("https://example.com"), "download");
+        assert_eq!(url_file_name("https://example.com/a%3Fb.png"), "a_b.png");
+        assert!(is_video_url("https://example.com/clip.MP4?token=1"));
+        assert!(!is_video_url("https://example.com/image.png"));
+    }
+}
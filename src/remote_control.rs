@@ -0,0 +1,275 @@
+//! Local control interface, so scripts, Stream Deck buttons, AutoHotkey and test harnesses can
+//! drive the viewer.
+//!
+//! With `remote_control = true` the viewer listens on a local socket named
+//! [`SOCKET_NAME`] (the named pipe `\\.\pipe\RustImageViewer_Control.sock` on Windows).
+//! Clients write one command per line and read one JSON line back:
+//!
+//! - `open <path>`, `next`, `prev`, `seek <seconds>` and `action <name>` (any `config.ini`
+//!   action name) answer `{"ok":true}`.
+//! - `get-state` answers with the current file, its position in the folder, the zoom, the
+//!   view mode and, for videos, the playback position.
+//! - Failures, and commands left out of `remote_control_allow`, answer
+//!   `{"ok":false,"error":"..."}`.
+//!
+//! Each connection is served on its own thread; commands run on the UI thread in order.
+
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crossbeam_channel::{Receiver, Sender};
+use interprocess::local_socket::{prelude::*, GenericNamespaced, ListenerOptions, Stream};
+
+pub const SOCKET_NAME: &str = "RustImageViewer_Control.sock";
+
+/// Command names accepted by `remote_control_allow`.
+pub const COMMAND_NAMES: [&str; 6] = ["open", "next", "prev", "seek", "get-state", "action"];
+
+/// How long a client waits for the UI thread, which may be busy or minimized.
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControlCommand {
+    Open(PathBuf),
+    Next,
+    Previous,
+    /// Seek the current video to this many seconds.
+    Seek(f64),
+    GetState,
+    /// Run the `config.ini` action with this name.
+    Action(String),
+}
+
+impl ControlCommand {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let line = line.trim();
+        let (verb, argument) = line
+            .split_once(char::is_whitespace)
+            .map_or((line, ""), |(verb, argument)| (verb, argument.trim()));
+        let missing = || format!("`{verb}` needs an argument");
+        match verb.to_ascii_lowercase().as_str() {
+            "open" if argument.is_empty() => Err(missing()),
+            "open" => Ok(Self::Open(PathBuf::from(argument.trim_matches('"')))),
+            "next" => Ok(Self::Next),
+            "prev" | "previous" => Ok(Self::Previous),
+            "seek" => argument
+                .parse::<f64>()
+                .ok()
+                .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+                .map(Self::Seek)
+                .ok_or_else(|| format!("`seek` needs seconds, got `{argument}`")),
+            "get-state" | "get_state" | "state" => Ok(Self::GetState),
+            "action" if argument.is_empty() => Err(missing()),
+            "action" => Ok(Self::Action(argument.to_string())),
+            "" => Err("Empty command".to_string()),
+            _ => Err(format!("Unknown command `{verb}`")),
+        }
+    }
+
+    /// The name `remote_control_allow` lists this command under.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Open(_) => "open",
+            Self::Next => "next",
+            Self::Previous => "prev",
+            Self::Seek(_) => "seek",
+            Self::GetState => "get-state",
+            Self::Action(_) => "action",
+        }
+    }
+}
+
+/// A value in a JSON reply.
+pub enum Value<'a> {
+    Str(&'a str),
+    Number(f64),
+    Bool(bool),
+    Null,
+}
+
+fn push_json_string(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if u32::from(c) < 0x20 => out.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// `{"ok":true}` followed by `fields`.
+pub fn reply_ok(fields: &[(&str, Value)]) -> String {
+    let mut out = String::from("{\"ok\":true");
+    for (key, value) in fields {
+        out.push(',');
+        push_json_string(&mut out, key);
+        out.push(':');
+        match value {
+            Value::Str(text) => push_json_string(&mut out, text),
+            Value::Number(number) if number.is_finite() => out.push_str(&number.to_string()),
+            Value::Bool(flag) => out.push_str(if *flag { "true" } else { "false" }),
+            Value::Number(_) | Value::Null => out.push_str("null"),
+        }
+    }
+    out.push('}');
+    out
+}
+
+pub fn reply_error(message: &str) -> String {
+    let mut out = String::from("{\"ok\":false,\"error\":");
+    push_json_string(&mut out, message);
+    out.push('}');
+    out
+}
+
+/// A command waiting for the UI thread.
+pub struct ControlRequest {
+    pub command: ControlCommand,
+    reply: Sender<String>,
+}
+
+impl ControlRequest {
+    /// Answers the client with a reply built by [`reply_ok`] or the error.
+    pub fn answer(self, result: Result<String, String>) {
+        let reply = result.unwrap_or_else(|err| reply_error(&err));
+        let _ = self.reply.send(reply);
+    }
+}
+
+/// The listening socket; commands arrive on [`ControlServer::requests`].
+pub struct ControlServer {
+    requests: Receiver<ControlRequest>,
+}
+
+impl ControlServer {
+    /// Starts listening. `None` when the socket cannot be created, e.g. because another
+    /// viewer window already owns it.
+    pub fn start(allowed: Vec<String>, wake: impl Fn() + Send + Sync + 'static) -> Option<Self> {
+        let name = SOCKET_NAME.to_ns_name::<GenericNamespaced>().ok()?;
+        let listener = match ListenerOptions::new().name(name).create_sync() {
+            Ok(listener) => listener,
+            Err(err) => {
+                tracing::warn!(target: "remote_control", "Cannot listen on {SOCKET_NAME}: {err}");
+                return None;
+            }
+        };
+        let (sender, requests) = crossbeam_channel::unbounded();
+        let allowed = Arc::new(allowed);
+        let wake: Arc<dyn Fn() + Send + Sync> = Arc::new(wake);
+        thread::Builder::new()
+            .name("remote-control".to_string())
+            .spawn(move || {
+                for conn in listener.incoming().filter_map(Result::ok) {
+                    let sender = sender.clone();
+                    let allowed = Arc::clone(&allowed);
+                    let wake = Arc::clone(&wake);
+                    let _ = thread::Builder::new()
+                        .name("remote-control-client".to_string())
+                        .spawn(move || serve_client(conn, &sender, &allowed, wake.as_ref()));
+                }
+            })
+            .ok()?;
+        tracing::info!(target: "remote_control", "Listening on {SOCKET_NAME}");
+        Some(Self { requests })
+    }
+
+    pub fn requests(&self) -> &Receiver<ControlRequest> {
+        &self.requests
+    }
+}
+
+fn serve_client(
+    conn: Stream,
+    sender: &Sender<ControlRequest>,
+    allowed: &[String],
+    wake: &(dyn Fn() + Send + Sync),
+) {
+    let mut reader = BufReader::new(&conn);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) | Err(_) => return,
+            Ok(_) => {}
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        let reply = match ControlCommand::parse(&line) {
+            Ok(command) if !allowed.iter().any(|name| name == command.name()) => reply_error(
+                &format!("`{}` is not in remote_control_allow", command.name()),
+            ),
+            Ok(command) => {
+                let (reply, answer) = crossbeam_channel::bounded(1);
+                if sender.send(ControlRequest { command, reply }).is_err() {
+                    return;
+                }
+                wake();
+                answer
+                    .recv_timeout(REPLY_TIMEOUT)
+                    .unwrap_or_else(|_| reply_error("The viewer did not answer in time"))
+            }
+            Err(err) => reply_error(&err),
+        };
+        let mut writer = &conn;
+        if writer
+            .write_all(format!("{reply}\n").as_bytes())
+            .and_then(|()| writer.flush())
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{reply_error, reply_ok, ControlCommand, Value};
+    use std::path::PathBuf;
+
+    #[test]
+    fn commands_parse_with_their_arguments() {
+        assert_eq!(
+            ControlCommand::parse("open \"C:\\Pictures\\a b.png\"\r\n"),
+            Ok(ControlCommand::Open(PathBuf::from("C:\\Pictures\\a b.png")))
+        );
+        assert_eq!(ControlCommand::parse("PREV"), Ok(ControlCommand::Previous));
+        assert_eq!(
+            ControlCommand::parse("seek 12.5"),
+            Ok(ControlCommand::Seek(12.5))
+        );
+        assert!(ControlCommand::parse("seek -1").is_err());
+        assert!(ControlCommand::parse("open").is_err());
+        assert_eq!(
+            ControlCommand::parse("action toggle_fullscreen").map(|command| command.name()),
+            Ok("action")
+        );
+        assert!(ControlCommand::parse("launch").is_err());
+    }
+
+    #[test]
+    fn replies_are_single_line_json() {
+        assert_eq!(
+            reply_ok(&[
+                ("path", Value::Str("C:\\a \"b\".png")),
+                ("zoom", Value::Number(1.5)),
+                ("playing", Value::Bool(false)),
+                ("duration", Value::Null),
+            ]),
+            r#"{"ok":true,"path":"C:\\a \"b\".png","zoom":1.5,"playing":false,"duration":null}"#
+        );
+        assert_eq!(
+            reply_error("bad\nline"),
+            r#"{"ok":false,"error":"bad\nline"}"#
+        );
+    }
+}
@@ -0,0 +1,375 @@
+//! Scan cleanup for photographed documents: deskew, paper white balance, contrast stretch and
+//! optional black-and-white thresholding, exported as PNG or a one-page PDF.
+//!
+//! The skew is measured once on the downscaled preview (the angle does not depend on the
+//! resolution) and reused for the full-resolution export. Rotation goes through
+//! [`crate::straighten::straighten_rgba`], so the result is cropped like straighten mode.
+
+use std::path::{Path, PathBuf};
+
+/// Longest side of the preview rendered in the dialog.
+pub const PREVIEW_MAX_SIDE: u32 = 900;
+
+/// Largest skew searched for, in degrees either way.
+const MAX_SKEW_DEGREES: f32 = 8.0;
+
+/// Skew search resolution in degrees.
+const SKEW_STEP_DEGREES: f32 = 0.25;
+
+/// Skews smaller than this are left alone; rotating would only soften the text.
+const MIN_SKEW_DEGREES: f32 = 0.1;
+
+/// PDF pages are sized as if the photo had been scanned at this resolution.
+pub const PDF_DPI: f32 = 200.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScanCleanupSettings {
+    pub deskew: bool,
+    pub white_balance: bool,
+    /// Levels stretch strength, `0` (off) to `1` (clip 2% at both ends of the histogram).
+    pub contrast: f32,
+    pub binarize: bool,
+}
+
+impl Default for ScanCleanupSettings {
+    fn default() -> Self {
+        Self {
+            deskew: true,
+            white_balance: true,
+            contrast: 0.7,
+            binarize: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanExportFormat {
+    /// Flattened onto `background` when given.
+    Png {
+        background: Option<[u8; 3]>,
+    },
+    Pdf,
+}
+
+impl ScanExportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ScanExportFormat::Png { .. } => "png",
+            ScanExportFormat::Pdf => "pdf",
+        }
+    }
+}
+
+fn luma(px: &[u8]) -> u8 {
+    (0.2126 * px[0] as f32 + 0.7152 * px[1] as f32 + 0.0722 * px[2] as f32)
+        .round()
+        .clamp(0.0, 255.0) as u8
+}
+
+fn luma_histogram(pixels: &[u8]) -> [u32; 256] {
+    let mut histogram = [0u32; 256];
+    for px in pixels.chunks_exact(4) {
+        histogram[luma(px) as usize] += 1;
+    }
+    histogram
+}
+
+/// Luma value below which `fraction` of the pixels fall.
+fn percentile(histogram: &[u32; 256], fraction: f32) -> u8 {
+    let total: u32 = histogram.iter().sum();
+    let target = (total as f32 * fraction).ceil() as u32;
+    let mut seen = 0;
+    for (value, count) in histogram.iter().enumerate() {
+        seen += count;
+        if seen >= target.max(1) {
+            return value as u8;
+        }
+    }
+    255
+}
+
+/// Otsu's threshold: the luma split that maximizes the between-class variance.
+pub fn otsu_threshold(histogram: &[u32; 256]) -> u8 {
+    let total: f64 = histogram.iter().map(|&count| count as f64).sum();
+    let weighted_total: f64 = histogram
+        .iter()
+        .enumerate()
+        .map(|(value, &count)| value as f64 * count as f64)
+        .sum();
+    let (mut background, mut weighted_background) = (0.0, 0.0);
+    let (mut best, mut best_variance) = (127u8, -1.0);
+    for (value, &count) in histogram.iter().enumerate() {
+        background += count as f64;
+        weighted_background += value as f64 * count as f64;
+        let foreground = total - background;
+        if background == 0.0 || foreground == 0.0 {
+            continue;
+        }
+        let mean_background = weighted_background / background;
+        let mean_foreground = (weighted_total - weighted_background) / foreground;
+        let variance = background * foreground * (mean_background - mean_foreground).powi(2);
+        if variance > best_variance {
+            best_variance = variance;
+            best = value as u8;
+        }
+    }
+    best
+}
+
+/// Angle in degrees (clockwise on screen) of the text lines in a page, found by projecting
+/// the dark pixels along candidate angles and keeping the one with the sharpest row profile.
+pub fn estimate_skew_degrees(pixels: &[u8], width: u32, height: u32) -> f32 {
+    if width < 8 || height < 8 || pixels.len() < width as usize * height as usize * 4 {
+        return 0.0;
+    }
+    let threshold = otsu_threshold(&luma_histogram(pixels));
+    let dark: Vec<(f32, f32)> = pixels
+        .chunks_exact(4)
+        .enumerate()
+        .filter(|(_, px)| luma(px) < threshold)
+        .map(|(index, _)| {
+            (
+                (index % width as usize) as f32,
+                (index / width as usize) as f32,
+            )
+        })
+        .collect();
+    if dark.is_empty() {
+        return 0.0;
+    }
+
+    let steps = (MAX_SKEW_DEGREES / SKEW_STEP_DEGREES).round() as i32;
+    let reach = width as f32 * (MAX_SKEW_DEGREES.to_radians()).tan();
+    let bins = height as usize + 2 * reach.ceil() as usize + 1;
+    let mut profile = vec![0u32; bins];
+    let (mut best_angle, mut best_score) = (0.0, 0u64);
+    for step in -steps..=steps {
+        let angle = step as f32 * SKEW_STEP_DEGREES;
+        let slope = angle.to_radians().tan();
+        profile.iter_mut().for_each(|bin| *bin = 0);
+        for &(x, y) in &dark {
+            let row = (y - x * slope + reach).round();
+            profile[row.clamp(0.0, (bins - 1) as f32) as usize] += 1;
+        }
+        let score: u64 = profile
+            .iter()
+            .map(|&count| count as u64 * count as u64)
+            .sum();
+        // Prefer the smaller correction on ties, so blank pages stay put.
+        if score > best_score || (score == best_score && angle.abs() < f32::abs(best_angle)) {
+            best_score = score;
+            best_angle = angle;
+        }
+    }
+    best_angle
+}
+
+/// Applies the tonal steps (white balance, contrast, thresholding) in place.
+fn apply_tone(settings: ScanCleanupSettings, pixels: &mut [u8]) {
+    if settings.white_balance {
+        // Paper is the brightest tenth of the page; scale each channel so it becomes white.
+        let histogram = luma_histogram(pixels);
+        let paper_luma = percentile(&histogram, 0.9);
+        let (mut sum, mut count) = ([0.0f64; 3], 0u32);
+        for px in pixels.chunks_exact(4) {
+            if luma(px) >= paper_luma {
+                for (total, &value) in sum.iter_mut().zip(px) {
+                    *total += value as f64;
+                }
+                count += 1;
+            }
+        }
+        if count > 0 {
+            let gains = sum.map(|channel| {
+                let paper = (channel / count as f64) as f32;
+                (255.0 / paper.max(1.0)).clamp(1.0, 4.0)
+            });
+            for px in pixels.chunks_exact_mut(4) {
+                for (value, gain) in px.iter_mut().zip(gains) {
+                    *value = (*value as f32 * gain).round().min(255.0) as u8;
+                }
+            }
+        }
+    }
+
+    let strength = settings.contrast.clamp(0.0, 1.0);
+    if strength > 0.0 {
+        let histogram = luma_histogram(pixels);
+        let black = percentile(&histogram, 0.02) as f32 * strength;
+        let white = 255.0 - (255.0 - percentile(&histogram, 0.98) as f32) * strength;
+        if white - black > 8.0 {
+            let scale = 255.0 / (white - black);
+            for px in pixels.chunks_exact_mut(4) {
+                for value in &mut px[..3] {
+                    *value = ((*value as f32 - black) * scale).round().clamp(0.0, 255.0) as u8;
+                }
+            }
+        }
+    }
+
+    if settings.binarize {
+        let threshold = otsu_threshold(&luma_histogram(pixels));
+        for px in pixels.chunks_exact_mut(4) {
+            let value = if luma(px) <= threshold { 0 } else { 255 };
+            px[..3].fill(value);
+        }
+    }
+}
+
+/// Runs the cleanup on straight RGBA8 pixels. `skew_degrees` comes from
+/// [`estimate_skew_degrees`] and is only used when deskewing is enabled.
+pub fn clean_rgba(
+    settings: ScanCleanupSettings,
+    skew_degrees: f32,
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+) -> Option<(Vec<u8>, u32, u32)> {
+    if width == 0 || height == 0 || pixels.len() < width as usize * height as usize * 4 {
+        return None;
+    }
+    let (mut out, width, height) = if settings.deskew && skew_degrees.abs() >= MIN_SKEW_DEGREES {
+        crate::straighten::straighten_rgba(pixels, width, height, -skew_degrees, false, false)?
+    } else {
+        (
+            pixels[..width as usize * height as usize * 4].to_vec(),
+            width,
+            height,
+        )
+    };
+    apply_tone(settings, &mut out);
+    Some((out, width, height))
+}
+
+/// `page.jpg` -> `page.clean.png` / `page.clean.pdf`, never overwriting.
+pub fn export_path(image_path: &Path, format: ScanExportFormat) -> PathBuf {
+    crate::image_loader::sibling_export_path(image_path, "clean", format.extension())
+}
+
+pub fn export_cleaned(
+    settings: ScanCleanupSettings,
+    skew_degrees: f32,
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    format: ScanExportFormat,
+    target: &Path,
+) -> Result<(), String> {
+    let (pixels, width, height) = clean_rgba(settings, skew_degrees, pixels, width, height)
+        .ok_or_else(|| "Nothing left after cropping".to_string())?;
+    match format {
+        ScanExportFormat::Png { background } => {
+            crate::png_export::write_png(pixels, width, height, background, target)
+        }
+        ScanExportFormat::Pdf => {
+            let mut writer = crate::pdf_writer::PdfWriter::new();
+            let (page_size, image_rect) =
+                crate::pdf_writer::image_sized_page(width, height, PDF_DPI);
+            writer.add_image_page(page_size, image_rect, &pixels, width, height)?;
+            writer.save(target)
+        }
+    }
+}
+
+/// State of the scan cleanup dialog while it is open.
+pub struct ScanCleanupDialog {
+    pub source_path: PathBuf,
+    /// Downscaled copy of the page used for the live preview.
+    pub preview_source: (Vec<u8>, u32, u32),
+    pub skew_degrees: f32,
+    pub settings: ScanCleanupSettings,
+    /// Preview texture and the settings it was rendered with.
+    pub preview: Option<(ScanCleanupSettings, egui::TextureHandle)>,
+    pub status: Option<String>,
+    pub export_rx: Option<crossbeam_channel::Receiver<Result<PathBuf, String>>>,
+}
+
+impl ScanCleanupDialog {
+    pub fn new(source_path: PathBuf, pixels: Vec<u8>, width: u32, height: u32) -> Self {
+        let skew_degrees = estimate_skew_degrees(&pixels, width, height);
+        Self {
+            source_path,
+            preview_source: (pixels, width, height),
+            skew_degrees,
+            settings: ScanCleanupSettings::default(),
+            preview: None,
+            status: None,
+            export_rx: None,
+        }
+    }
+
+    /// Re-renders the preview texture if the settings changed since the last frame.
+    pub fn preview_texture(&mut self, ctx: &egui::Context) -> Option<&egui::TextureHandle> {
+        let stale = !matches!(&self.preview, Some((settings, _)) if *settings == self.settings);
+        if stale {
+            let (pixels, width, height) = &self.preview_source;
+            self.preview = clean_rgba(self.settings, self.skew_degrees, pixels, *width, *height)
+                .map(|(pixels, width, height)| {
+                    let image = egui::ColorImage::from_rgba_unmultiplied(
+                        [width as usize, height as usize],
+                        &pixels,
+                    );
+                    let texture = ctx.load_texture(
+                        "scan_cleanup_preview",
+                        image,
+                        egui::TextureOptions::LINEAR,
+                    );
+                    (self.settings, texture)
+                });
+        }
+        self.preview.as_ref().map(|(_, texture)| texture)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clean_rgba, estimate_skew_degrees, ScanCleanupSettings};
+
+    /// White page with dark horizontal lines, tilted by `slope` (rise per pixel).
+    fn lined_page(width: u32, height: u32, slope: f32) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+        for y in 0..height {
+            for x in 0..width {
+                let row = y as f32 - x as f32 * slope;
+                let ink = row.rem_euclid(12.0) < 2.0;
+                let value = if ink { 20 } else { 235 };
+                pixels.extend_from_slice(&[value, value, value, 255]);
+            }
+        }
+        pixels
+    }
+
+    #[test]
+    fn skew_estimate_finds_tilted_lines() {
+        let level = lined_page(160, 120, 0.0);
+        assert_eq!(estimate_skew_degrees(&level, 160, 120), 0.0);
+
+        let slope = 3f32.to_radians().tan();
+        let tilted = lined_page(160, 120, slope);
+        let skew = estimate_skew_degrees(&tilted, 160, 120);
+        assert!((skew - 3.0).abs() <= 0.5, "skew {skew}");
+    }
+
+    #[test]
+    fn cleanup_whitens_paper_and_binarizes() {
+        // Yellowish paper with one dark pixel.
+        let mut pixels = [220, 200, 150, 255].repeat(9);
+        pixels[..4].copy_from_slice(&[40, 35, 30, 255]);
+        let settings = ScanCleanupSettings {
+            deskew: false,
+            contrast: 0.0,
+            ..ScanCleanupSettings::default()
+        };
+        let (out, width, height) = clean_rgba(settings, 0.0, &pixels, 3, 3).unwrap();
+        assert_eq!((width, height), (3, 3));
+        assert_eq!(&out[4..8], &[255, 255, 255, 255]);
+
+        let binarized = ScanCleanupSettings {
+            binarize: true,
+            ..settings
+        };
+        let (out, _, _) = clean_rgba(binarized, 0.0, &pixels, 3, 3).unwrap();
+        assert_eq!(&out[..4], &[0, 0, 0, 255]);
+        assert_eq!(&out[4..8], &[255, 255, 255, 255]);
+    }
+}
@@ -0,0 +1,283 @@
+//! Thumbnails of the frame under the pointer while hovering the video seek bar.
+//!
+//! The timeline of a video is cut into at most [`MAX_SLOTS`] slots. Hovering a slot asks a
+//! worker for its thumbnail: the worker keeps a small paused GStreamer pipeline that scales
+//! frames down to [`THUMBNAIL_WIDTH`], seeks it to the keyframe nearest the slot and takes
+//! the prerolled frame. Finished slots are kept for the video, so the index fills in as the
+//! pointer moves and sweeping back over the bar shows them right away.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use crossbeam_channel::{Receiver, Sender};
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use gstreamer_video as gst_video;
+use parking_lot::Mutex;
+
+/// Slots per video; long videos get proportionally coarser slots.
+const MAX_SLOTS: u32 = 120;
+/// Shortest slot, so short clips do not decode a thumbnail per frame.
+const MIN_SLOT: Duration = Duration::from_secs(1);
+/// Width of the thumbnails in pixels; the height follows the display aspect ratio.
+pub const THUMBNAIL_WIDTH: u32 = 160;
+/// A seek that has not prerolled by then is given up, for example on a broken file.
+const SEEK_TIMEOUT: Duration = Duration::from_secs(2);
+/// The worker closes its pipeline after the seek bar has not been hovered for this long.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Length of one slot for a video of `duration`.
+fn slot_length(duration: Duration) -> Duration {
+    (duration / MAX_SLOTS).max(MIN_SLOT)
+}
+
+/// The slot `time` falls into.
+fn slot_at(duration: Duration, time: Duration) -> u32 {
+    let slot = time.min(duration).as_nanos() / slot_length(duration).as_nanos();
+    (slot as u32).min(MAX_SLOTS - 1)
+}
+
+/// The time the thumbnail of `slot` is taken at: the middle of the slot, within the video.
+fn slot_time(duration: Duration, slot: u32) -> Duration {
+    let length = slot_length(duration);
+    (length * slot + length / 2).min(duration)
+}
+
+struct Thumbnail {
+    pixels: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+/// Lazily built thumbnail index of one video.
+pub struct SeekThumbnails {
+    path: PathBuf,
+    duration: Duration,
+    /// Decoded slots; `None` marks a slot whose thumbnail could not be taken.
+    slots: Arc<Mutex<HashMap<u32, Option<Thumbnail>>>>,
+    requests: Option<Sender<u32>>,
+    /// Cleared by the worker when it exits, so the next request starts another one.
+    worker_running: Arc<AtomicBool>,
+    /// Last slot sent to the worker, to avoid queueing the same slot every frame.
+    requested: Option<u32>,
+    /// Texture of the slot shown last; it stays up until the hovered slot is decoded.
+    texture: Option<(u32, egui::TextureHandle)>,
+}
+
+impl SeekThumbnails {
+    pub fn new(path: &Path, duration: Duration) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            duration,
+            slots: Arc::new(Mutex::new(HashMap::new())),
+            requests: None,
+            worker_running: Arc::new(AtomicBool::new(false)),
+            requested: None,
+            texture: None,
+        }
+    }
+
+    /// Whether this index belongs to `path` at `duration`.
+    pub fn is_for(&self, path: &Path, duration: Duration) -> bool {
+        self.path == path && self.duration == duration
+    }
+
+    /// Thumbnail for the frame at `time`. Asks the worker for the slot when it is not
+    /// decoded yet and returns the previous thumbnail meanwhile; `ctx` is repainted once the
+    /// slot is ready.
+    pub fn thumbnail_at(
+        &mut self,
+        ctx: &egui::Context,
+        time: Duration,
+    ) -> Option<&egui::TextureHandle> {
+        let slot = slot_at(self.duration, time);
+        if self.texture.as_ref().map(|(shown, _)| *shown) != Some(slot) {
+            let decoded = self.slots.lock().get(&slot).map(|thumbnail| {
+                thumbnail.as_ref().map(|thumbnail| {
+                    egui::ColorImage::from_rgba_unmultiplied(
+                        [thumbnail.width as usize, thumbnail.height as usize],
+                        &thumbnail.pixels,
+                    )
+                })
+            });
+            match decoded {
+                Some(Some(image)) => match self.texture.as_mut() {
+                    Some((shown, texture)) => {
+                        texture.set(image, egui::TextureOptions::LINEAR);
+                        *shown = slot;
+                    }
+                    None => {
+                        let texture =
+                            ctx.load_texture("seek_thumbnail", image, egui::TextureOptions::LINEAR);
+                        self.texture = Some((slot, texture));
+                    }
+                },
+                Some(None) => {}
+                None => self.request(ctx, slot),
+            }
+        }
+        self.texture.as_ref().map(|(_, texture)| texture)
+    }
+
+    fn request(&mut self, ctx: &egui::Context, slot: u32) {
+        if self.requested == Some(slot) && self.worker_running.load(Ordering::Acquire) {
+            return;
+        }
+        if !self.worker_running.load(Ordering::Acquire) {
+            self.requests = None;
+        }
+        let requests = self.requests.get_or_insert_with(|| {
+            let (sender, receiver) = crossbeam_channel::unbounded();
+            self.worker_running.store(true, Ordering::Release);
+            let worker = Worker {
+                path: self.path.clone(),
+                duration: self.duration,
+                slots: Arc::clone(&self.slots),
+                running: Arc::clone(&self.worker_running),
+                ctx: ctx.clone(),
+            };
+            crate::async_runtime::spawn_blocking_or_thread("seek-thumbnails", move || {
+                worker.run(&receiver);
+            });
+            sender
+        });
+        if requests.send(slot).is_ok() {
+            self.requested = Some(slot);
+        }
+    }
+}
+
+struct Worker {
+    path: PathBuf,
+    duration: Duration,
+    slots: Arc<Mutex<HashMap<u32, Option<Thumbnail>>>>,
+    running: Arc<AtomicBool>,
+    ctx: egui::Context,
+}
+
+impl Worker {
+    fn run(self, requests: &Receiver<u32>) {
+        let pipeline = ThumbnailPipeline::open(&self.path);
+        if pipeline.is_none() {
+            tracing::debug!("No seek thumbnails for {}", self.path.display());
+        }
+        while let Ok(mut slot) = requests.recv_timeout(IDLE_TIMEOUT) {
+            // Only the newest request matters: the pointer has moved past the others.
+            while let Ok(newer) = requests.try_recv() {
+                slot = newer;
+            }
+            if self.slots.lock().contains_key(&slot) {
+                continue;
+            }
+            let thumbnail = pipeline
+                .as_ref()
+                .and_then(|pipeline| pipeline.frame_at(slot_time(self.duration, slot)));
+            self.slots.lock().insert(slot, thumbnail);
+            self.ctx.request_repaint();
+        }
+        if let Some(pipeline) = pipeline {
+            pipeline.close();
+        }
+        self.running.store(false, Ordering::Release);
+    }
+}
+
+/// Paused decode pipeline that scales frames down to thumbnails.
+struct ThumbnailPipeline {
+    pipeline: gst::Pipeline,
+    sink: gst_app::AppSink,
+}
+
+impl ThumbnailPipeline {
+    fn open(path: &Path) -> Option<Self> {
+        static GST_INIT: OnceLock<Result<(), ()>> = OnceLock::new();
+        if GST_INIT
+            .get_or_init(|| gst::init().map_err(|_| ()))
+            .is_err()
+        {
+            return None;
+        }
+
+        let uri = crate::remote::media_uri(path).ok()?;
+        let description = format!(
+            "uridecodebin uri=\"{}\" ! videoconvert ! videoscale ! \
+             video/x-raw,format=RGBA,width={THUMBNAIL_WIDTH},pixel-aspect-ratio=1/1 ! \
+             appsink name=sink max-buffers=1 drop=true sync=false",
+            uri.replace('"', "\\\"")
+        );
+        let pipeline = gst::parse::launch(&description)
+            .ok()?
+            .downcast::<gst::Pipeline>()
+            .ok()?;
+        let sink = pipeline
+            .by_name("sink")?
+            .dynamic_cast::<gst_app::AppSink>()
+            .ok()?;
+        let pipeline = Self { pipeline, sink };
+        if pipeline.pipeline.set_state(gst::State::Paused).is_err() {
+            pipeline.close();
+            return None;
+        }
+        Some(pipeline)
+    }
+
+    /// The keyframe nearest `time`, scaled down.
+    fn frame_at(&self, time: Duration) -> Option<Thumbnail> {
+        let timeout = gst::ClockTime::from_nseconds(SEEK_TIMEOUT.as_nanos() as u64);
+        // Seeking before the first preroll fails, so wait for it.
+        let (result, _, _) = self.pipeline.state(timeout);
+        result.ok()?;
+        self.pipeline
+            .seek_simple(
+                gst::SeekFlags::FLUSH | gst::SeekFlags::KEY_UNIT | gst::SeekFlags::SNAP_NEAREST,
+                gst::ClockTime::from_nseconds(time.as_nanos() as u64),
+            )
+            .ok()?;
+        let sample = self.sink.try_pull_preroll(timeout)?;
+        let info = gst_video::VideoInfo::from_caps(sample.caps()?).ok()?;
+        let buffer = sample.buffer()?.map_readable().ok()?;
+        let (width, height) = (info.width(), info.height());
+        let stride = usize::try_from(*info.stride().first()?).ok()?;
+        let row = width as usize * 4;
+        let mut pixels = Vec::with_capacity(row * height as usize);
+        for y in 0..height as usize {
+            pixels.extend_from_slice(buffer.get(y * stride..y * stride + row)?);
+        }
+        Some(Thumbnail {
+            pixels,
+            width,
+            height,
+        })
+    }
+
+    fn close(self) {
+        let _ = self.pipeline.set_state(gst::State::Null);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{slot_at, slot_time, MAX_SLOTS};
+
+    #[test]
+    fn slots_cover_the_timeline() {
+        // A two-hour video gets a slot per minute.
+        let long = Duration::from_secs(2 * 60 * 60);
+        assert_eq!(slot_at(long, Duration::from_secs(59)), 0);
+        assert_eq!(slot_at(long, Duration::from_secs(61)), 1);
+        assert_eq!(slot_at(long, long), MAX_SLOTS - 1);
+        assert_eq!(slot_time(long, 1), Duration::from_secs(90));
+
+        // Short clips stop at one slot per second.
+        let short = Duration::from_millis(4500);
+        assert_eq!(slot_at(short, Duration::from_millis(2500)), 2);
+        assert_eq!(slot_at(short, Duration::from_secs(10)), 4);
+        assert_eq!(slot_time(short, 4), short);
+    }
+}
@@ -0,0 +1,738 @@
+//! Settings window: edit config.ini from inside the viewer.
+//!
+//! The window is generated from the config template (see `Config::editable_ini_sections`), so
+//! new keys show up here without touching this file. Widgets are picked from the section, key
+//! and current value. Edits are returned as [`IniOverride`]s and re-parsed by `Config`, which
+//! keeps validation and clamping in one place; the caller applies the new config live and
+//! writes it back to config.ini.
+
+use std::collections::HashMap;
+
+use crate::config::{
+    binding_to_string, ini_key_requires_restart, ini_value_choices, parse_input_binding, Action,
+    Config, IniEntry, IniOverride, IniSection, InputBinding,
+};
+
+const SIDEBAR_WIDTH: f32 = 190.0;
+const ACCENT_COLOR: egui::Color32 = egui::Color32::from_rgb(120, 190, 255);
+const MUTED_TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(150, 170, 192);
+
+/// How a press-to-bind capture is written into its entry.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CaptureMode {
+    /// Add the binding to a comma-separated binding list.
+    Append,
+    /// Replace the value with the binding (single-binding keys).
+    Replace,
+    /// Replace the value with a bare key; modifiers and mouse input are ignored.
+    KeyOnly,
+}
+
+struct BindingCapture {
+    section: usize,
+    entry: usize,
+    mode: CaptureMode,
+}
+
+/// What happened in the settings window this frame.
+#[derive(Default)]
+pub struct SettingsWindowResponse {
+    /// Values the user changed, in config.ini syntax.
+    pub changes: Vec<IniOverride>,
+    pub close: bool,
+    pub open_config_file: bool,
+}
+
+pub struct SettingsWindow {
+    sections: Vec<IniSection>,
+    selected_section: usize,
+    filter: String,
+    capture: Option<BindingCapture>,
+    /// Free-form values being typed, committed on Enter or focus loss.
+    text_drafts: HashMap<(usize, usize), String>,
+    status: Option<String>,
+    /// Last frame's window rect; mouse captures only count clicks outside it.
+    modal_rect: Option<egui::Rect>,
+    skip_outside_click_once: bool,
+}
+
+impl SettingsWindow {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            sections: config.editable_ini_sections(),
+            selected_section: 0,
+            filter: String::new(),
+            capture: None,
+            text_drafts: HashMap::new(),
+            status: None,
+            modal_rect: None,
+            // The click that opened the window must not close it again.
+            skip_outside_click_once: true,
+        }
+    }
+
+    /// Reload values after the config changed (clamped values, linked alias keys).
+    pub fn refresh(&mut self, config: &Config) {
+        self.sections = config.editable_ini_sections();
+        self.selected_section = self
+            .selected_section
+            .min(self.sections.len().saturating_sub(1));
+        self.text_drafts.clear();
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, config: &Config) -> SettingsWindowResponse {
+        let mut response = SettingsWindowResponse::default();
+        let was_capturing = self.capture.is_some();
+
+        if let Some(capture) = self.capture.as_ref() {
+            let keys_only = capture.mode == CaptureMode::KeyOnly;
+            if let Some(binding) = captured_binding(ctx, self.modal_rect, keys_only) {
+                self.finish_capture(binding, config, &mut response.changes);
+            }
+        } else if ctx.input(|input| input.key_pressed(egui::Key::Escape)) {
+            response.close = true;
+        }
+
+        let screen_rect = ctx.screen_rect();
+        egui::Area::new(egui::Id::new("settings_window_backdrop"))
+            .fixed_pos(screen_rect.min)
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, screen_rect.size());
+                ui.painter().rect_filled(
+                    rect,
+                    0.0,
+                    egui::Color32::from_rgba_unmultiplied(4, 8, 13, 214),
+                );
+            });
+
+        let modal_size = egui::vec2(
+            (screen_rect.width() - 60.0).clamp(560.0, 980.0),
+            (screen_rect.height() - 44.0).clamp(440.0, 780.0),
+        );
+        let modal_pos = screen_rect.center() - modal_size * 0.5;
+        let body_height = (modal_size.y - 150.0).max(220.0);
+
+        let modal_response = egui::Area::new(egui::Id::new("settings_window"))
+            .fixed_pos(modal_pos)
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.set_min_size(modal_size);
+                ui.set_max_width(modal_size.x);
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgba_unmultiplied(16, 23, 31, 252))
+                    .stroke(egui::Stroke::new(
+                        1.0,
+                        egui::Color32::from_rgba_unmultiplied(166, 207, 255, 62),
+                    ))
+                    .rounding(18.0)
+                    .inner_margin(egui::Margin::same(18.0))
+                    .show(ui, |ui| {
+                        self.draw_header(ui, &mut response);
+                        ui.add_space(10.0);
+                        ui.separator();
+                        ui.add_space(8.0);
+
+                        ui.horizontal_top(|ui| {
+                            self.draw_sidebar(ui, body_height);
+                            ui.separator();
+                            egui::ScrollArea::vertical()
+                                .id_salt("settings_window_entries")
+                                .max_height(body_height)
+                                .auto_shrink([false, false])
+                                .show(ui, |ui| {
+                                    self.draw_entries(ui, &mut response.changes);
+                                });
+                        });
+                    });
+            });
+
+        let modal_rect = modal_response.response.rect;
+        self.modal_rect = Some(modal_rect);
+
+        if self.skip_outside_click_once {
+            self.skip_outside_click_once = false;
+        } else if !was_capturing && !ctx.memory(|memory| memory.any_popup_open()) {
+            // Color pickers and combo boxes open popups outside the window; clicks there
+            // must not close it. Presses rather than clicks, so releasing a mouse button that
+            // was just captured as a binding does not close the window either.
+            let clicked_outside_modal = ctx.input(|input| {
+                let clicked = input.pointer.button_pressed(egui::PointerButton::Primary)
+                    || input.pointer.button_pressed(egui::PointerButton::Secondary);
+                let pointer_pos = input
+                    .pointer
+                    .interact_pos()
+                    .or_else(|| input.pointer.hover_pos());
+
+                clicked && pointer_pos.is_some_and(|pos| !modal_rect.contains(pos))
+            });
+            if clicked_outside_modal {
+                response.close = true;
+            }
+        }
+
+        response
+    }
+
+    fn draw_header(&mut self, ui: &mut egui::Ui, response: &mut SettingsWindowResponse) {
+        ui.horizontal(|ui| {
+            ui.vertical(|ui| {
+                ui.label(
+                    egui::RichText::new("Settings")
+                        .color(egui::Color32::WHITE)
+                        .strong()
+                        .size(22.0),
+                );
+                ui.add_space(2.0);
+                ui.label(
+                    egui::RichText::new(
+                        "Changes apply immediately and are saved to config.ini. Keys marked \"restart\" take effect on the next launch.",
+                    )
+                    .color(egui::Color32::from_rgb(170, 190, 212))
+                    .size(12.5),
+                );
+                ui.add_space(4.0);
+                let status = self.status.clone().unwrap_or_else(|| {
+                    format!("Config source: {}", Config::config_path().display())
+                });
+                ui.label(
+                    egui::RichText::new(status)
+                        .monospace()
+                        .color(egui::Color32::from_rgb(128, 165, 198))
+                        .size(11.0),
+                );
+            });
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if header_button(ui, "Close").clicked() {
+                    response.close = true;
+                }
+                if header_button(ui, "Open config.ini").clicked() {
+                    response.open_config_file = true;
+                }
+            });
+        });
+    }
+
+    fn draw_sidebar(&mut self, ui: &mut egui::Ui, body_height: f32) {
+        ui.allocate_ui_with_layout(
+            egui::vec2(SIDEBAR_WIDTH, body_height),
+            egui::Layout::top_down(egui::Align::Min),
+            |ui| {
+                ui.set_width(SIDEBAR_WIDTH);
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.filter)
+                        .hint_text("Search settings")
+                        .desired_width(f32::INFINITY),
+                );
+                ui.add_space(8.0);
+
+                let searching = !self.filter.trim().is_empty();
+                for (index, section) in self.sections.iter().enumerate() {
+                    let selected = !searching && index == self.selected_section;
+                    let label = egui::RichText::new(&section.name).size(14.0);
+                    if ui.selectable_label(selected, label).clicked() {
+                        self.selected_section = index;
+                        self.filter.clear();
+                    }
+                }
+            },
+        );
+    }
+
+    fn draw_entries(&mut self, ui: &mut egui::Ui, changes: &mut Vec<IniOverride>) {
+        let filter = self.filter.trim().to_ascii_lowercase();
+        let visible: Vec<(usize, usize)> = if filter.is_empty() {
+            self.sections
+                .get(self.selected_section)
+                .map(|section| 0..section.entries.len())
+                .into_iter()
+                .flatten()
+                .map(|entry| (self.selected_section, entry))
+                .collect()
+        } else {
+            self.sections
+                .iter()
+                .enumerate()
+                .flat_map(|(section_index, section)| {
+                    section
+                        .entries
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, entry)| {
+                            entry.key.contains(&filter)
+                                || entry.comment.to_ascii_lowercase().contains(&filter)
+                        })
+                        .map(move |(entry_index, _)| (section_index, entry_index))
+                })
+                .collect()
+        };
+
+        if visible.is_empty() {
+            ui.label(egui::RichText::new("No settings match.").color(MUTED_TEXT_COLOR));
+            return;
+        }
+
+        let mut last_section = None;
+        for (section_index, entry_index) in visible {
+            if !filter.is_empty() && last_section != Some(section_index) {
+                ui.add_space(6.0);
+                ui.label(
+                    egui::RichText::new(&self.sections[section_index].name)
+                        .color(ACCENT_COLOR)
+                        .strong()
+                        .size(15.0),
+                );
+                last_section = Some(section_index);
+            }
+            self.draw_entry(ui, section_index, entry_index, changes);
+        }
+    }
+
+    fn draw_entry(
+        &mut self,
+        ui: &mut egui::Ui,
+        section_index: usize,
+        entry_index: usize,
+        changes: &mut Vec<IniOverride>,
+    ) {
+        let section_name = self.sections[section_index].name.clone();
+        let entry = self.sections[section_index].entries[entry_index].clone();
+
+        ui.add_space(6.0);
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new(&entry.key)
+                    .monospace()
+                    .color(egui::Color32::WHITE)
+                    .strong(),
+            );
+            if ini_key_requires_restart(&entry.key) {
+                ui.label(
+                    egui::RichText::new("restart")
+                        .size(11.0)
+                        .color(egui::Color32::from_rgb(240, 190, 110)),
+                );
+            }
+        });
+
+        let new_value = self.entry_editor(ui, section_index, entry_index, &section_name, &entry);
+
+        if !entry.comment.is_empty() {
+            ui.label(
+                egui::RichText::new(&entry.comment)
+                    .size(11.5)
+                    .color(MUTED_TEXT_COLOR),
+            );
+        }
+        ui.add_space(6.0);
+        ui.separator();
+
+        let Some(value) = new_value else {
+            return;
+        };
+        if value == entry.value {
+            return;
+        }
+
+        self.sections[section_index].entries[entry_index].value = value.clone();
+        self.status = None;
+        changes.push(IniOverride {
+            section: section_name,
+            key: entry.key,
+            value,
+        });
+    }
+
+    /// Draw the widget for one entry; returns the new value when it was edited.
+    fn entry_editor(
+        &mut self,
+        ui: &mut egui::Ui,
+        section_index: usize,
+        entry_index: usize,
+        section: &str,
+        entry: &IniEntry,
+    ) -> Option<String> {
+        let key = entry.key.as_str();
+        let value = entry.value.as_str();
+
+        if let Some(choices) = ini_value_choices(section, key) {
+            return choice_editor(ui, (section, key), value, choices);
+        }
+
+        if section.eq_ignore_ascii_case("shortcuts") {
+            if key.ends_with("mark_file") {
+                return self.single_binding_editor(
+                    ui,
+                    section_index,
+                    entry_index,
+                    value,
+                    CaptureMode::KeyOnly,
+                );
+            }
+            return self.binding_list_editor(ui, section_index, entry_index, value);
+        }
+
+        if section.eq_ignore_ascii_case("video") && key.starts_with("priority_") {
+            return self.single_binding_editor(
+                ui,
+                section_index,
+                entry_index,
+                value,
+                CaptureMode::Replace,
+            );
+        }
+
+        if let Some(mut enabled) = parse_bool_value(value) {
+            return ui
+                .checkbox(&mut enabled, "")
+                .changed()
+                .then(|| if enabled { "true" } else { "false" }.to_string());
+        }
+
+        if key.ends_with("_rgb") {
+            if let Some(mut rgb) = parse_rgb_value(value) {
+                return egui::color_picker::color_edit_button_srgb(ui, &mut rgb)
+                    .changed()
+                    .then(|| format!("{}, {}, {}", rgb[0], rgb[1], rgb[2]));
+            }
+        }
+
+        if key == "zoom_step" {
+            if let Ok(mut step) = value.parse::<f64>() {
+                return ui
+                    .add(egui::Slider::new(&mut step, 1.01..=2.0).fixed_decimals(2))
+                    .changed()
+                    .then(|| format_float_value(step));
+            }
+        }
+
+        if let Ok(mut number) = value.parse::<i64>() {
+            return ui
+                .add(egui::DragValue::new(&mut number).speed(1.0))
+                .changed()
+                .then(|| number.to_string());
+        }
+
+        if let Ok(mut number) = value.parse::<f64>() {
+            return ui
+                .add(
+                    egui::DragValue::new(&mut number)
+                        .speed(0.01)
+                        .max_decimals(3),
+                )
+                .changed()
+                .then(|| format_float_value(number));
+        }
+
+        self.text_editor(ui, section_index, entry_index, value)
+    }
+
+    fn text_editor(
+        &mut self,
+        ui: &mut egui::Ui,
+        section_index: usize,
+        entry_index: usize,
+        value: &str,
+    ) -> Option<String> {
+        let draft = self
+            .text_drafts
+            .entry((section_index, entry_index))
+            .or_insert_with(|| value.to_string());
+        let edit = ui.add(egui::TextEdit::singleline(draft).desired_width(360.0));
+
+        if edit.lost_focus() {
+            return self.text_drafts.remove(&(section_index, entry_index));
+        }
+        None
+    }
+
+    fn binding_list_editor(
+        &mut self,
+        ui: &mut egui::Ui,
+        section_index: usize,
+        entry_index: usize,
+        value: &str,
+    ) -> Option<String> {
+        let bindings: Vec<&str> = value
+            .split(',')
+            .map(str::trim)
+            .filter(|binding| !binding.is_empty())
+            .collect();
+        let mut new_value = None;
+
+        ui.horizontal_wrapped(|ui| {
+            for (index, binding) in bindings.iter().enumerate() {
+                let chip = ui
+                    .add(
+                        egui::Button::new(egui::RichText::new(format!("{binding}  ×")).monospace())
+                            .rounding(6.0),
+                    )
+                    .on_hover_text("Remove this binding");
+                if chip.clicked() {
+                    let remaining: Vec<&str> = bindings
+                        .iter()
+                        .enumerate()
+                        .filter(|(other, _)| *other != index)
+                        .map(|(_, binding)| *binding)
+                        .collect();
+                    new_value = Some(remaining.join(", "));
+                }
+            }
+            self.capture_button(ui, section_index, entry_index, CaptureMode::Append, "+ Add");
+        });
+
+        new_value
+    }
+
+    fn single_binding_editor(
+        &mut self,
+        ui: &mut egui::Ui,
+        section_index: usize,
+        entry_index: usize,
+        value: &str,
+        mode: CaptureMode,
+    ) -> Option<String> {
+        let mut new_value = None;
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(value).monospace());
+            self.capture_button(ui, section_index, entry_index, mode, "Change");
+            if !value.eq_ignore_ascii_case("none") && ui.button("None").clicked() {
+                new_value = Some("none".to_string());
+            }
+        });
+
+        new_value
+    }
+
+    fn capture_button(
+        &mut self,
+        ui: &mut egui::Ui,
+        section_index: usize,
+        entry_index: usize,
+        mode: CaptureMode,
+        label: &str,
+    ) {
+        let capturing = self.capture.as_ref().is_some_and(|capture| {
+            capture.section == section_index && capture.entry == entry_index
+        });
+
+        if capturing {
+            let hint = if mode == CaptureMode::KeyOnly {
+                "Press a key… (click to cancel)"
+            } else {
+                "Press a key, scroll, or click outside… (click to cancel)"
+            };
+            if ui
+                .button(egui::RichText::new(hint).color(ACCENT_COLOR))
+                .clicked()
+            {
+                self.capture = None;
+            }
+            return;
+        }
+
+        let button = ui.button(label);
+        if button.clicked() {
+            // Keep Space/Enter from re-clicking the button instead of being captured.
+            button.surrender_focus();
+            self.capture = Some(BindingCapture {
+                section: section_index,
+                entry: entry_index,
+                mode,
+            });
+            self.status = None;
+        }
+    }
+
+    fn finish_capture(
+        &mut self,
+        binding: InputBinding,
+        config: &Config,
+        changes: &mut Vec<IniOverride>,
+    ) {
+        let Some(capture) = self.capture.take() else {
+            return;
+        };
+        let text = binding_to_string(&binding);
+        if parse_input_binding(&text).is_none() {
+            self.status = Some(format!("\"{text}\" cannot be used in config.ini."));
+            return;
+        }
+
+        let Some(section) = self.sections.get_mut(capture.section) else {
+            return;
+        };
+        let Some(entry) = section.entries.get_mut(capture.entry) else {
+            return;
+        };
+
+        let value = match capture.mode {
+            CaptureMode::Append => {
+                if entry
+                    .value
+                    .split(',')
+                    .any(|existing| existing.trim().eq_ignore_ascii_case(&text))
+                {
+                    self.status = Some(format!("{} is already bound to {text}.", entry.key));
+                    return;
+                }
+                if entry.value.trim().is_empty() {
+                    text.clone()
+                } else {
+                    format!("{}, {text}", entry.value)
+                }
+            }
+            CaptureMode::Replace | CaptureMode::KeyOnly => text.clone(),
+        };
+
+        entry.value = value.clone();
+        changes.push(IniOverride {
+            section: section.name.clone(),
+            key: entry.key.clone(),
+            value,
+        });
+
+        let own_action = Action::from_str(&entry.key);
+        let mut conflicts: Vec<String> = config
+            .action_bindings
+            .iter()
+            .filter(|(action, bindings)| {
+                Some(**action) != own_action && bindings.contains(&binding)
+            })
+            .map(|(action, _)| format!("{action:?}"))
+            .collect();
+        conflicts.sort();
+        self.status = if conflicts.is_empty() || capture.mode == CaptureMode::KeyOnly {
+            None
+        } else {
+            Some(format!("{text} is also bound to: {}", conflicts.join(", ")))
+        };
+    }
+}
+
+fn header_button(ui: &mut egui::Ui, label: &str) -> egui::Response {
+    ui.add(
+        egui::Button::new(egui::RichText::new(label).color(egui::Color32::WHITE))
+            .min_size(egui::vec2(88.0, 30.0))
+            .fill(egui::Color32::from_rgba_unmultiplied(255, 255, 255, 24))
+            .stroke(egui::Stroke::new(
+                1.0,
+                egui::Color32::from_rgba_unmultiplied(255, 255, 255, 56),
+            ))
+            .rounding(7.0),
+    )
+}
+
+fn choice_editor(
+    ui: &mut egui::Ui,
+    id_salt: (&str, &str),
+    value: &str,
+    choices: &[&str],
+) -> Option<String> {
+    let mut selected = value.to_ascii_lowercase();
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(selected.clone())
+        .show_ui(ui, |ui| {
+            for choice in choices {
+                ui.selectable_value(&mut selected, (*choice).to_string(), *choice);
+            }
+        });
+
+    (selected != value.to_ascii_lowercase()).then_some(selected)
+}
+
+/// The first key, mouse button or wheel step this frame, as a binding.
+fn captured_binding(
+    ctx: &egui::Context,
+    modal_rect: Option<egui::Rect>,
+    keys_only: bool,
+) -> Option<InputBinding> {
+    ctx.input(|input| {
+        for event in &input.events {
+            match event {
+                egui::Event::Key {
+                    key,
+                    pressed: true,
+                    repeat: false,
+                    modifiers,
+                    ..
+                } => {
+                    let binding = if keys_only {
+                        InputBinding::Key(*key)
+                    } else if modifiers.ctrl && modifiers.shift {
+                        InputBinding::KeyWithCtrlShift(*key)
+                    } else if modifiers.ctrl {
+                        InputBinding::KeyWithCtrl(*key)
+                    } else if modifiers.shift {
+                        InputBinding::KeyWithShift(*key)
+                    } else if modifiers.alt {
+                        InputBinding::KeyWithAlt(*key)
+                    } else {
+                        InputBinding::Key(*key)
+                    };
+                    return Some(binding);
+                }
+                // Clicks inside the window belong to its own widgets (e.g. cancel).
+                egui::Event::PointerButton {
+                    pos,
+                    button,
+                    pressed: true,
+                    ..
+                } if !keys_only && !modal_rect.is_some_and(|rect| rect.contains(*pos)) => {
+                    return Some(match button {
+                        egui::PointerButton::Primary => InputBinding::MouseLeft,
+                        egui::PointerButton::Secondary => InputBinding::MouseRight,
+                        egui::PointerButton::Middle => InputBinding::MouseMiddle,
+                        egui::PointerButton::Extra1 => InputBinding::Mouse4,
+                        egui::PointerButton::Extra2 => InputBinding::Mouse5,
+                    });
+                }
+                // Shift+wheel may arrive as horizontal scrolling; use the dominant axis.
+                egui::Event::MouseWheel {
+                    delta, modifiers, ..
+                } if !keys_only && *delta != egui::Vec2::ZERO => {
+                    let up = if delta.y.abs() >= delta.x.abs() {
+                        delta.y > 0.0
+                    } else {
+                        delta.x > 0.0
+                    };
+                    return Some(match (modifiers.ctrl, modifiers.shift, up) {
+                        (true, _, true) => InputBinding::CtrlScrollUp,
+                        (true, _, false) => InputBinding::CtrlScrollDown,
+                        (false, true, true) => InputBinding::ShiftScrollUp,
+                        (false, true, false) => InputBinding::ShiftScrollDown,
+                        (false, false, true) => InputBinding::ScrollUp,
+                        (false, false, false) => InputBinding::ScrollDown,
+                    });
+                }
+                _ => {}
+            }
+        }
+        None
+    })
+}
+
+fn parse_bool_value(value: &str) -> Option<bool> {
+    match value {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_rgb_value(value: &str) -> Option<[u8; 3]> {
+    let mut channels = value.split(',').map(|part| part.trim().parse::<u8>());
+    let rgb = [
+        channels.next()?.ok()?,
+        channels.next()?.ok()?,
+        channels.next()?.ok()?,
+    ];
+    channels.next().is_none().then_some(rgb)
+}
+
+/// Round away float noise from drag widgets so config.ini stays readable.
+fn format_float_value(value: f64) -> String {
+    format!("{}", (value * 1000.0).round() / 1000.0)
+}
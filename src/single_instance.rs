@@ -31,6 +31,13 @@ const MUTEX_NAME: &str = "Global\\RustImageViewer_SingleInstance_A7F3B2C1";
 const IPC_SOCKET_NAME: &str = "RustImageViewer_SingleInstance_A7F3B2C1.sock";
 const IPC_WAKE_MESSAGE: &str = "WAKE";
 
+/// A file forwarded by a secondary instance.
+pub struct OpenRequest {
+    pub path: PathBuf,
+    /// The secondary was started with `--slideshow`.
+    pub slideshow: bool,
+}
+
 /// Result of attempting to acquire the single-instance lock.
 pub enum SingleInstanceResult {
     /// This is the first (primary) instance.
@@ -102,8 +109,9 @@ fn send_message_to_primary(message: &str) -> bool {
     stream.flush().is_ok()
 }
 
-fn send_file_path_to_primary(path: &PathBuf) -> bool {
-    let payload = format!("OPEN:{}", path.to_string_lossy());
+fn send_file_path_to_primary(path: &PathBuf, slideshow: bool) -> bool {
+    let prefix = if slideshow { "SLIDESHOW" } else { "OPEN" };
+    let payload = format!("{prefix}:{}", path.to_string_lossy());
     let sent = send_message_to_primary(&payload);
     if sent {
         tracing::debug!(target: "single_instance", path = %path.display(), "forwarded file path to primary");
@@ -119,11 +127,13 @@ fn wake_pipe_listener() {
 ///
 /// If `enabled` is false, returns `Disabled` immediately.
 /// If this is the first instance, returns `Primary` with the lock guard.
-/// If another instance exists, sends `file_path` to it and returns `Secondary`.
+/// If another instance exists, sends `file_path` to it and returns `Secondary`; with
+/// `slideshow` the primary also starts a slideshow on it.
 pub fn try_acquire_lock(
     enabled: bool,
     file_path: Option<&PathBuf>,
-    on_file_received: impl Fn(PathBuf) + Send + 'static,
+    slideshow: bool,
+    on_file_received: impl Fn(OpenRequest) + Send + 'static,
 ) -> SingleInstanceResult {
     if !enabled {
         return SingleInstanceResult::Disabled;
@@ -147,7 +157,7 @@ pub fn try_acquire_lock(
         }
 
         if let Some(path) = file_path {
-            let _ = send_file_path_to_primary(path);
+            let _ = send_file_path_to_primary(path, slideshow);
         }
 
         return SingleInstanceResult::Secondary;
@@ -172,7 +182,7 @@ pub fn try_acquire_lock(
 /// Listen for local-socket messages and call callback when a file path is received.
 fn run_socket_listener(
     shutdown_flag: Arc<AtomicBool>,
-    on_file_received: impl Fn(PathBuf) + Send + 'static,
+    on_file_received: impl Fn(OpenRequest) + Send + 'static,
 ) {
     let name = match IPC_SOCKET_NAME.to_ns_name::<GenericNamespaced>() {
         Ok(name) => name,
@@ -205,11 +215,16 @@ fn run_socket_listener(
             if shutdown_flag.load(Ordering::SeqCst) {
                 break;
             }
-        } else if let Some(path_str) = message.strip_prefix("OPEN:") {
+        } else if let Some((kind, path_str)) = message.split_once(':') {
+            let slideshow = match kind {
+                "OPEN" => false,
+                "SLIDESHOW" => true,
+                _ => continue,
+            };
             let path = PathBuf::from(path_str.trim());
             if path.exists() {
                 tracing::debug!(target: "single_instance", path = %path.display(), "received open request from secondary instance");
-                on_file_received(path);
+                on_file_received(OpenRequest { path, slideshow });
             }
         }
     }
@@ -219,19 +234,19 @@ fn run_socket_listener(
 type WakeCallback = Arc<dyn Fn() + Send + Sync>;
 
 pub struct FileReceiver {
-    receiver: crossbeam_channel::Receiver<PathBuf>,
+    receiver: crossbeam_channel::Receiver<OpenRequest>,
     wake_callback: Arc<Mutex<Option<WakeCallback>>>,
 }
 
 impl FileReceiver {
     /// Create a new file receiver and return the sender callback.
-    pub fn new() -> (Self, impl Fn(PathBuf) + Send + 'static) {
+    pub fn new() -> (Self, impl Fn(OpenRequest) + Send + 'static) {
         let (sender, receiver) = crossbeam_channel::unbounded();
         let wake_callback: Arc<Mutex<Option<WakeCallback>>> = Arc::new(Mutex::new(None));
         let wake_for_callback = Arc::clone(&wake_callback);
 
-        let callback = move |path: PathBuf| {
-            if sender.send(path).is_ok() {
+        let callback = move |request: OpenRequest| {
+            if sender.send(request).is_ok() {
                 let wake = wake_for_callback
                     .lock()
                     .ok()
@@ -250,8 +265,8 @@ impl FileReceiver {
         )
     }
 
-    /// Try to receive a file path without blocking.
-    pub fn try_recv(&self) -> Option<PathBuf> {
+    /// Try to receive a forwarded file without blocking.
+    pub fn try_recv(&self) -> Option<OpenRequest> {
         self.receiver.try_recv().ok()
     }
 
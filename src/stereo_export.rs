@@ -0,0 +1,282 @@
+//! Stereo pair export.
+//!
+//! The source is a parallel side-by-side image: the left half is the left eye, the right
+//! half the right eye. It can be written out as a red/cyan anaglyph or rearranged for
+//! cross-eye free viewing (right eye on the left). A horizontal parallax shift moves the
+//! convergence plane before export; the non-overlapping columns are cropped away.
+
+use std::path::{Path, PathBuf};
+
+/// Longest side of the preview rendered in the export dialog.
+pub const PREVIEW_MAX_SIDE: u32 = 640;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StereoOutput {
+    Anaglyph,
+    CrossEye,
+}
+
+impl StereoOutput {
+    pub const ALL: [StereoOutput; 2] = [StereoOutput::Anaglyph, StereoOutput::CrossEye];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StereoOutput::Anaglyph => "Anaglyph (red/cyan)",
+            StereoOutput::CrossEye => "Cross-eye side-by-side",
+        }
+    }
+
+    fn file_tag(self) -> &'static str {
+        match self {
+            StereoOutput::Anaglyph => "anaglyph",
+            StereoOutput::CrossEye => "crosseye",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StereoExportSettings {
+    pub output: StereoOutput,
+    /// Horizontal shift of the right eye against the left, in source pixels. Positive
+    /// values push the scene back, negative values pull it towards the viewer.
+    pub parallax: i32,
+    /// Treat the left half as the right eye (for pairs that are already cross-eye).
+    pub swap_eyes: bool,
+}
+
+impl Default for StereoExportSettings {
+    fn default() -> Self {
+        Self {
+            output: StereoOutput::Anaglyph,
+            parallax: 0,
+            swap_eyes: false,
+        }
+    }
+}
+
+/// Straight RGBA8 pixels with their dimensions.
+#[derive(Debug, Clone)]
+pub struct RgbaBuffer {
+    pub pixels: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Largest parallax offered for a pair `width` pixels wide: a tenth of one eye's width.
+pub fn max_parallax(width: u32) -> i32 {
+    ((width / 2) / 10).max(1) as i32
+}
+
+/// Composes the stereo output from a side-by-side pair. Returns `None` if the image is
+/// too narrow to hold two eyes or the parallax crops everything away.
+pub fn compose(
+    settings: StereoExportSettings,
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+) -> Option<RgbaBuffer> {
+    let eye_width = width / 2;
+    let shift = settings.parallax.unsigned_abs();
+    if eye_width == 0 || height == 0 || shift >= eye_width {
+        return None;
+    }
+    if pixels.len() < width as usize * height as usize * 4 {
+        return None;
+    }
+
+    let (mut left_x, mut right_x) = (0, eye_width);
+    if settings.swap_eyes {
+        std::mem::swap(&mut left_x, &mut right_x);
+    }
+    // Crop the columns that only one eye sees after the shift.
+    if settings.parallax > 0 {
+        right_x += shift;
+    } else {
+        left_x += shift;
+    }
+    let out_eye_width = eye_width - shift;
+
+    let row_bytes = width as usize * 4;
+    let eye_row_bytes = out_eye_width as usize * 4;
+    let eye_row = |y: u32, x: u32| {
+        let start = y as usize * row_bytes + x as usize * 4;
+        &pixels[start..start + eye_row_bytes]
+    };
+
+    let (out_width, pixels) = match settings.output {
+        StereoOutput::Anaglyph => {
+            let mut out = Vec::with_capacity(eye_row_bytes * height as usize);
+            for y in 0..height {
+                for (left, right) in eye_row(y, left_x)
+                    .chunks_exact(4)
+                    .zip(eye_row(y, right_x).chunks_exact(4))
+                {
+                    out.extend_from_slice(&[left[0], right[1], right[2], left[3].max(right[3])]);
+                }
+            }
+            (out_eye_width, out)
+        }
+        StereoOutput::CrossEye => {
+            let mut out = Vec::with_capacity(eye_row_bytes * 2 * height as usize);
+            for y in 0..height {
+                out.extend_from_slice(eye_row(y, right_x));
+                out.extend_from_slice(eye_row(y, left_x));
+            }
+            (out_eye_width * 2, out)
+        }
+    };
+
+    Some(RgbaBuffer {
+        pixels,
+        width: out_width,
+        height,
+    })
+}
+
+/// `pair.jpg` -> `pair.anaglyph.png` or `pair.crosseye.png`, never overwriting.
+pub fn export_path(image_path: &Path, output: StereoOutput) -> PathBuf {
+    crate::image_loader::sibling_export_path(image_path, output.file_tag(), "png")
+}
+
+pub fn export_stereo_png(
+    settings: StereoExportSettings,
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    background: Option<[u8; 3]>,
+    target: &Path,
+) -> Result<(), String> {
+    let composed = compose(settings, pixels, width, height)
+        .ok_or_else(|| "Image is too narrow for this parallax".to_string())?;
+    crate::png_export::write_png(
+        composed.pixels,
+        composed.width,
+        composed.height,
+        background,
+        target,
+    )
+}
+
+/// State of the export dialog while it is open.
+pub struct StereoExportDialog {
+    pub source_path: PathBuf,
+    pub source_width: u32,
+    /// Downscaled copy of the pair used for the live preview.
+    pub preview_source: RgbaBuffer,
+    pub settings: StereoExportSettings,
+    /// Preview texture and the settings it was rendered with.
+    pub preview: Option<(StereoExportSettings, egui::TextureHandle)>,
+    pub status: Option<String>,
+    pub export_rx: Option<crossbeam_channel::Receiver<Result<PathBuf, String>>>,
+}
+
+impl StereoExportDialog {
+    pub fn new(source_path: PathBuf, source_width: u32, preview_source: RgbaBuffer) -> Self {
+        Self {
+            source_path,
+            source_width,
+            preview_source,
+            settings: StereoExportSettings::default(),
+            preview: None,
+            status: None,
+            export_rx: None,
+        }
+    }
+
+    /// Re-renders the preview texture if the settings changed since the last frame.
+    pub fn preview_texture(&mut self, ctx: &egui::Context) -> Option<&egui::TextureHandle> {
+        let stale = !matches!(&self.preview, Some((settings, _)) if *settings == self.settings);
+        if stale {
+            // Parallax is chosen in source pixels; scale it down to the preview.
+            let scale = self.preview_source.width as f32 / self.source_width.max(1) as f32;
+            let preview_settings = StereoExportSettings {
+                parallax: (self.settings.parallax as f32 * scale).round() as i32,
+                ..self.settings
+            };
+            self.preview = compose(
+                preview_settings,
+                &self.preview_source.pixels,
+                self.preview_source.width,
+                self.preview_source.height,
+            )
+            .map(|composed| {
+                let image = egui::ColorImage::from_rgba_unmultiplied(
+                    [composed.width as usize, composed.height as usize],
+                    &composed.pixels,
+                );
+                let texture =
+                    ctx.load_texture("stereo_export_preview", image, egui::TextureOptions::LINEAR);
+                (self.settings, texture)
+            });
+        }
+        self.preview.as_ref().map(|(_, texture)| texture)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compose, StereoExportSettings, StereoOutput};
+
+    /// 4x1 pair: left eye pixels are red-ish, right eye pixels green/blue-ish.
+    fn pair() -> Vec<u8> {
+        vec![
+            10, 0, 0, 255, 20, 0, 0, 255, // left eye
+            0, 30, 40, 255, 0, 50, 60, 255, // right eye
+        ]
+    }
+
+    #[test]
+    fn anaglyph_takes_red_from_left_and_cyan_from_right() {
+        let settings = StereoExportSettings::default();
+        let out = compose(settings, &pair(), 4, 1).unwrap();
+        assert_eq!((out.width, out.height), (2, 1));
+        assert_eq!(out.pixels, vec![10, 30, 40, 255, 20, 50, 60, 255]);
+
+        // Positive parallax shifts the right eye by one column and crops one column.
+        let shifted = compose(
+            StereoExportSettings {
+                parallax: 1,
+                ..settings
+            },
+            &pair(),
+            4,
+            1,
+        )
+        .unwrap();
+        assert_eq!(shifted.pixels, vec![10, 50, 60, 255]);
+        assert!(compose(
+            StereoExportSettings {
+                parallax: 2,
+                ..settings
+            },
+            &pair(),
+            4,
+            1
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn cross_eye_puts_right_eye_first_unless_swapped() {
+        let settings = StereoExportSettings {
+            output: StereoOutput::CrossEye,
+            ..StereoExportSettings::default()
+        };
+        let out = compose(settings, &pair(), 4, 1).unwrap();
+        assert_eq!(out.width, 4);
+        assert_eq!(&out.pixels[..8], &pair()[8..]);
+        assert_eq!(&out.pixels[8..], &pair()[..8]);
+
+        let swapped = compose(
+            StereoExportSettings {
+                swap_eyes: true,
+                ..settings
+            },
+            &pair(),
+            4,
+            1,
+        )
+        .unwrap();
+        assert_eq!(swapped.pixels, pair());
+    }
+}
@@ -0,0 +1,258 @@
+//! Where the viewer keeps its files.
+//!
+//! In the standard (installed) mode:
+//! - `config.ini` lives in the per-user roaming folder (`%APPDATA%\rust-image-viewer`,
+//!   `~/.config/rust-image-viewer`).
+//! - Caches and stores (the redb files, the GStreamer registry, logs, crash reports and the
+//!   wallpaper copies) live in the per-user local folder (`%LOCALAPPDATA%\rust-image-viewer`,
+//!   `~/.local/share/rust-image-viewer`), or in `cache_dir` when config.ini sets one.
+//! - Scratch files go to a folder in the system temp folder, or to `temp_dir`.
+//!
+//! Nothing is written next to the executable, which may sit in a read-only Program Files
+//! folder. A cache file missing from its folder is moved in from where older versions or an
+//! earlier `cache_dir` left it (see [`data_file`]).
+//!
+//! In portable mode, chosen by a [`PORTABLE_MARKER`] file next to the executable or by
+//! `--portable`, everything stays next to the executable instead, for running from a USB
+//! stick: `config.ini` beside it, caches in `cache` and scratch files in `temp`. Relative
+//! `cache_dir` / `temp_dir` values are taken from the executable folder, and nothing is
+//! moved in from the per-user folders of the computer.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::app_dirs;
+
+/// File next to the executable that turns on portable mode.
+pub const PORTABLE_MARKER: &str = "portable.txt";
+const PORTABLE_CACHE_DIR_NAME: &str = "cache";
+const PORTABLE_TEMP_DIR_NAME: &str = "temp";
+
+static PORTABLE: OnceLock<bool> = OnceLock::new();
+
+/// Chooses portable mode when `requested` (`--portable`) or when [`PORTABLE_MARKER`] sits
+/// next to the executable. Call before the config is loaded.
+pub fn set_portable(requested: bool) {
+    let portable = requested || portable_marker_present();
+    if PORTABLE.set(portable).is_err() {
+        tracing::debug!("storage mode was chosen before the command line was read");
+    }
+}
+
+/// Whether settings and caches are kept next to the executable.
+pub fn is_portable() -> bool {
+    *PORTABLE.get_or_init(portable_marker_present)
+}
+
+fn portable_marker_present() -> bool {
+    exe_dir().is_some_and(|dir| dir.join(PORTABLE_MARKER).is_file())
+}
+
+/// The executable folder in portable mode.
+fn portable_root() -> Option<PathBuf> {
+    if is_portable() {
+        exe_dir()
+    } else {
+        None
+    }
+}
+
+struct Locations {
+    /// `cache_dir`, or the local app data folder.
+    data: Option<PathBuf>,
+    /// `temp_dir`, or a folder in the system temp folder.
+    temp: PathBuf,
+}
+
+impl Locations {
+    fn resolve(cache_dir: Option<&Path>, temp_dir: Option<&Path>) -> Self {
+        let Some(root) = portable_root() else {
+            return Self {
+                data: cache_dir
+                    .map(Path::to_path_buf)
+                    .or_else(app_dirs::app_local_data_dir),
+                temp: temp_dir
+                    .map(Path::to_path_buf)
+                    .unwrap_or_else(default_temp_dir),
+            };
+        };
+        Self {
+            data: Some(root.join(cache_dir.unwrap_or(Path::new(PORTABLE_CACHE_DIR_NAME)))),
+            temp: root.join(temp_dir.unwrap_or(Path::new(PORTABLE_TEMP_DIR_NAME))),
+        }
+    }
+}
+
+static LOCATIONS: OnceLock<Locations> = OnceLock::new();
+
+fn locations() -> &'static Locations {
+    LOCATIONS.get_or_init(|| Locations::resolve(None, None))
+}
+
+/// Applies the `cache_dir` / `temp_dir` settings. Call once, right after the config is
+/// loaded and before anything opens a cache; later calls are ignored, so changing them takes
+/// a restart.
+pub fn init(cache_dir: Option<&Path>, temp_dir: Option<&Path>) {
+    if LOCATIONS
+        .set(Locations::resolve(cache_dir, temp_dir))
+        .is_err()
+    {
+        tracing::debug!("storage locations were resolved before the config was loaded");
+    }
+}
+
+fn default_temp_dir() -> PathBuf {
+    std::env::temp_dir().join(app_dirs::APP_DIR_NAME)
+}
+
+fn exe_dir() -> Option<PathBuf> {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+}
+
+/// Folder of `config.ini`, created if needed.
+pub fn config_dir() -> PathBuf {
+    if let Some(root) = portable_root() {
+        return root;
+    }
+    let config_dir = if cfg!(target_os = "windows") {
+        app_dirs::app_config_dir().unwrap_or_else(|| {
+            exe_dir()
+                .map(|dir| dir.join(app_dirs::APP_DIR_NAME))
+                .unwrap_or_else(|| PathBuf::from(".").join(app_dirs::APP_DIR_NAME))
+        })
+    } else {
+        // On Unix-like systems, use ~/.config
+        let base_dir = std::env::var("XDG_CONFIG_HOME")
+            .ok()
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var("HOME")
+                    .ok()
+                    .map(|h| PathBuf::from(h).join(".config"))
+            })
+            .unwrap_or_else(|| PathBuf::from("."));
+        base_dir.join(app_dirs::APP_DIR_NAME)
+    };
+    let _ = std::fs::create_dir_all(&config_dir);
+    config_dir
+}
+
+/// Folder for scratch files, created if needed.
+pub fn temp_dir() -> PathBuf {
+    let temp = &locations().temp;
+    if std::fs::create_dir_all(temp).is_ok() {
+        return temp.clone();
+    }
+    let fallback = default_temp_dir();
+    let _ = std::fs::create_dir_all(&fallback);
+    fallback
+}
+
+/// Folder for caches and stores, created if needed. Falls back to [`temp_dir`] when the
+/// configured or per-user folder cannot be created.
+pub fn data_dir() -> Option<PathBuf> {
+    if let Some(data) = locations().data.as_ref() {
+        if std::fs::create_dir_all(data).is_ok() {
+            return Some(data.clone());
+        }
+        tracing::warn!("cannot use {} for caches", data.display());
+    }
+    let temp = temp_dir();
+    temp.is_dir().then_some(temp)
+}
+
+/// `name` in [`data_dir`]. When the file is not there yet, a copy left by an older version
+/// (in the temp folder or next to the executable) or in the default folder before
+/// `cache_dir` was set is moved in first.
+pub fn data_file(name: &str) -> Option<PathBuf> {
+    let dir = data_dir()?;
+    let target = dir.join(name);
+    if !target.exists() {
+        let previous = previous_data_dirs(&dir)
+            .into_iter()
+            .map(|previous_dir| previous_dir.join(name))
+            .find(|previous| previous.is_file());
+        if let Some(previous) = previous {
+            match move_file(&previous, &target) {
+                Ok(()) => tracing::info!("moved {} to {}", previous.display(), target.display()),
+                Err(err) => tracing::warn!("failed to move {}: {err}", previous.display()),
+            }
+        }
+    }
+    Some(target)
+}
+
+/// Folders a data file may have been left in, other than `current`. None in portable
+/// mode, so the per-user caches of the computer are left alone.
+fn previous_data_dirs(current: &Path) -> Vec<PathBuf> {
+    if is_portable() {
+        return Vec::new();
+    }
+    [
+        app_dirs::app_local_data_dir(),
+        Some(locations().temp.clone()),
+        Some(default_temp_dir()),
+        exe_dir(),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|dir| dir != current)
+    .collect()
+}
+
+/// Renames `from` to `to`, or copies and removes it across drives.
+fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(from, to)?;
+    std::fs::remove_file(from)
+}
+
+/// A `cache_dir` / `temp_dir` value: `None` when empty, with `%NAME%` environment variables
+/// expanded so `%LOCALAPPDATA%\...` works as written.
+pub fn parse_dir_setting(value: &str) -> Option<PathBuf> {
+    let value = value.trim().trim_matches('"');
+    if value.is_empty() {
+        return None;
+    }
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('%') {
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('%') else {
+            break;
+        };
+        expanded.push_str(&rest[..start]);
+        match std::env::var(&after[..end]) {
+            Ok(var) if end > 0 => expanded.push_str(&var),
+            _ => expanded.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &after[end + 1..];
+    }
+    expanded.push_str(rest);
+    Some(PathBuf::from(expanded))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::parse_dir_setting;
+
+    #[test]
+    fn dir_settings_expand_environment_variables() {
+        std::env::set_var("RIV_STORAGE_TEST_DIR", "D:/Cache");
+        assert_eq!(parse_dir_setting("  "), None);
+        assert_eq!(
+            parse_dir_setting("\"%RIV_STORAGE_TEST_DIR%/viewer\""),
+            Some(PathBuf::from("D:/Cache/viewer"))
+        );
+        assert_eq!(
+            parse_dir_setting("%RIV_STORAGE_TEST_MISSING%/x 100%"),
+            Some(PathBuf::from("%RIV_STORAGE_TEST_MISSING%/x 100%"))
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! Straighten tool: drag along a line that should be level (or plumb) and the view rotates
+//! by the angle needed to make it so. The preview darkens everything outside the largest
+//! upright rectangle that fits inside the rotated image, which is also what gets exported.
+
+use std::path::{Path, PathBuf};
+
+use crate::png_export::blend_bilinear;
+
+/// Lines shorter than this (in screen points) are treated as accidental clicks.
+pub const MIN_LINE_LENGTH: f32 = 12.0;
+/// Spacing of the level grid, in screen points.
+pub const LEVEL_GRID_SPACING: f32 = 48.0;
+/// Pointer positions closer than this to the pivot turn the image too erratically to count.
+const MIN_ROTATE_RADIUS: f32 = 16.0;
+
+/// Rotation in degrees that makes the screen-space line `start -> end` horizontal or
+/// vertical, whichever it is closer to. `None` for lines that are too short.
+pub fn correction_degrees(start: egui::Pos2, end: egui::Pos2) -> Option<f32> {
+    let delta = end - start;
+    if delta.length() < MIN_LINE_LENGTH {
+        return None;
+    }
+    let angle = delta.y.atan2(delta.x).to_degrees();
+    let deviation = angle - (angle / 90.0).round() * 90.0;
+    Some(-deviation)
+}
+
+/// Degrees the pointer sweeps around `pivot` moving from `from` to `to`, clockwise on screen
+/// positive like the view rotation. Zero near the pivot.
+pub fn swept_degrees(pivot: egui::Pos2, from: egui::Pos2, to: egui::Pos2) -> f32 {
+    let (a, b) = (from - pivot, to - pivot);
+    if a.length() < MIN_ROTATE_RADIUS || b.length() < MIN_ROTATE_RADIUS {
+        return 0.0;
+    }
+    (a.x * b.y - a.y * b.x).atan2(a.dot(b)).to_degrees()
+}
+
+/// Horizontal and vertical screen lines across `rect`, centered on it, to line a horizon or an
+/// upright edge up against.
+pub fn level_grid_lines(rect: egui::Rect, spacing: f32) -> Vec<[egui::Pos2; 2]> {
+    let spacing = spacing.max(4.0);
+    let center = rect.center();
+    let columns = ((rect.width() * 0.5) / spacing).floor() as i32;
+    let rows = ((rect.height() * 0.5) / spacing).floor() as i32;
+    let verticals = (-columns..=columns).map(|index| {
+        let x = center.x + index as f32 * spacing;
+        [egui::pos2(x, rect.min.y), egui::pos2(x, rect.max.y)]
+    });
+    let horizontals = (-rows..=rows).map(|index| {
+        let y = center.y + index as f32 * spacing;
+        [egui::pos2(rect.min.x, y), egui::pos2(rect.max.x, y)]
+    });
+    verticals.chain(horizontals).collect()
+}
+
+/// Size of the largest axis-aligned rectangle, centered on the image, that lies entirely
+/// inside a `width` x `height` image rotated by `angle_radians`.
+pub fn inscribed_crop_size(width: f32, height: f32, angle_radians: f32) -> egui::Vec2 {
+    if width <= 0.0 || height <= 0.0 {
+        return egui::Vec2::ZERO;
+    }
+    let (sin, cos) = angle_radians.sin_cos();
+    let (sin, cos) = (sin.abs(), cos.abs());
+    let width_is_longer = width >= height;
+    let (long_side, short_side) = if width_is_longer {
+        (width, height)
+    } else {
+        (height, width)
+    };
+
+    if short_side <= 2.0 * sin * cos * long_side || (sin - cos).abs() < 1e-6 {
+        // Half-constrained: the crop touches the two long sides of the rotated image.
+        let x = 0.5 * short_side;
+        if width_is_longer {
+            egui::vec2(x / sin, x / cos)
+        } else {
+            egui::vec2(x / cos, x / sin)
+        }
+    } else {
+        // Fully constrained: the crop touches all four sides.
+        let cos_2a = cos * cos - sin * sin;
+        egui::vec2(
+            (width * cos - height * sin) / cos_2a,
+            (height * cos - width * sin) / cos_2a,
+        )
+    }
+}
+
+/// Rotates straight RGBA8 pixels by `angle_degrees` (clockwise on screen, like the view),
+/// applying the view flips first, and crops to [`inscribed_crop_size`]. Bilinear sampling.
+pub fn straighten_rgba(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    angle_degrees: f32,
+    flip_horizontal: bool,
+    flip_vertical: bool,
+) -> Option<(Vec<u8>, u32, u32)> {
+    if width == 0 || height == 0 || pixels.len() < width as usize * height as usize * 4 {
+        return None;
+    }
+    let angle = angle_degrees.to_radians();
+    let crop = inscribed_crop_size(width as f32, height as f32, angle);
+    let (out_width, out_height) = (crop.x.floor() as u32, crop.y.floor() as u32);
+    if out_width == 0 || out_height == 0 {
+        return None;
+    }
+
+    let (sin, cos) = angle.sin_cos();
+    let src_center = egui::vec2(width as f32, height as f32) * 0.5;
+    let out_center = egui::vec2(out_width as f32, out_height as f32) * 0.5;
+    let texel = |x: usize, y: usize| -> [f32; 4] {
+        let start = (y * width as usize + x) * 4;
+        std::array::from_fn(|channel| pixels[start + channel] as f32)
+    };
+
+    let mut out = Vec::with_capacity(out_width as usize * out_height as usize * 4);
+    for y in 0..out_height {
+        for x in 0..out_width {
+            let d = egui::vec2(x as f32 + 0.5, y as f32 + 0.5) - out_center;
+            // Undo the rotation, then the flips, to land in source pixel space.
+            let mut local = egui::vec2(d.x * cos + d.y * sin, -d.x * sin + d.y * cos);
+            if flip_horizontal {
+                local.x = -local.x;
+            }
+            if flip_vertical {
+                local.y = -local.y;
+            }
+            let p = local + src_center - egui::vec2(0.5, 0.5);
+            let x0 = p.x.floor().clamp(0.0, (width - 1) as f32);
+            let y0 = p.y.floor().clamp(0.0, (height - 1) as f32);
+            let (fx, fy) = ((p.x - x0).clamp(0.0, 1.0), (p.y - y0).clamp(0.0, 1.0));
+            let (x0, y0) = (x0 as usize, y0 as usize);
+            let x1 = (x0 + 1).min(width as usize - 1);
+            let y1 = (y0 + 1).min(height as usize - 1);
+            out.extend_from_slice(&blend_bilinear(
+                [texel(x0, y0), texel(x1, y0), texel(x0, y1), texel(x1, y1)],
+                fx,
+                fy,
+            ));
+        }
+    }
+    Some((out, out_width, out_height))
+}
+
+/// `photo.jpg` -> `photo.straightened.png`, or `photo.straightened-2.png` and so on if taken.
+pub fn export_path(image_path: &Path) -> PathBuf {
+    crate::image_loader::sibling_export_path(image_path, "straightened", "png")
+}
+
+pub fn export_straightened_png(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    angle_degrees: f32,
+    flips: (bool, bool),
+    background: Option<[u8; 3]>,
+    target: &Path,
+) -> Result<(), String> {
+    let (pixels, width, height) =
+        straighten_rgba(pixels, width, height, angle_degrees, flips.0, flips.1)
+            .ok_or_else(|| "Nothing left after cropping".to_string())?;
+    crate::png_export::write_png(pixels, width, height, background, target)
+}
+
+/// Straighten mode state: the line being dragged plus export progress.
+#[derive(Default)]
+pub struct StraightenTool {
+    pub active: bool,
+    pub line_start: Option<egui::Pos2>,
+    pub line_end: Option<egui::Pos2>,
+    pub status: Option<String>,
+    pub export_rx: Option<crossbeam_channel::Receiver<Result<PathBuf, String>>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        correction_degrees, inscribed_crop_size, level_grid_lines, straighten_rgba, swept_degrees,
+    };
+
+    #[test]
+    fn correction_snaps_to_the_nearest_axis() {
+        let start = egui::pos2(0.0, 0.0);
+        let level = correction_degrees(start, egui::pos2(100.0, 5.0)).unwrap();
+        assert!((level + 5.0f32.atan2(100.0).to_degrees()).abs() < 1e-3);
+        let plumb = correction_degrees(start, egui::pos2(-4.0, 100.0)).unwrap();
+        assert!(plumb < 0.0 && plumb > -5.0);
+        assert!(correction_degrees(start, egui::pos2(3.0, 3.0)).is_none());
+    }
+
+    #[test]
+    fn dragging_around_the_pivot_sweeps_its_angle() {
+        let pivot = egui::pos2(100.0, 100.0);
+        let quarter = swept_degrees(pivot, egui::pos2(200.0, 100.0), egui::pos2(100.0, 200.0));
+        assert!((quarter - 90.0).abs() < 1e-3);
+        let back = swept_degrees(pivot, egui::pos2(100.0, 200.0), egui::pos2(200.0, 100.0));
+        assert!((back + 90.0).abs() < 1e-3);
+        assert_eq!(
+            swept_degrees(pivot, egui::pos2(105.0, 100.0), egui::pos2(200.0, 100.0)),
+            0.0
+        );
+
+        let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(200.0, 100.0));
+        let lines = level_grid_lines(rect, 48.0);
+        // Columns at 100 ± 48 (two steps fit), rows at 50 ± 48.
+        assert_eq!(lines.len(), 5 + 3);
+        assert_eq!(lines[2], [egui::pos2(100.0, 0.0), egui::pos2(100.0, 100.0)]);
+    }
+
+    #[test]
+    fn crop_and_rotation_are_identity_at_zero_degrees() {
+        assert_eq!(
+            inscribed_crop_size(400.0, 300.0, 0.0),
+            egui::vec2(400.0, 300.0)
+        );
+        let rotated = inscribed_crop_size(400.0, 300.0, 10f32.to_radians());
+        assert!(rotated.x < 400.0 && rotated.y < 300.0 && rotated.y > 200.0);
+
+        let pixels: Vec<u8> = (0..2 * 2 * 4).map(|value| value as u8).collect();
+        let (out, width, height) = straighten_rgba(&pixels, 2, 2, 0.0, false, false).unwrap();
+        assert_eq!((width, height), (2, 2));
+        assert_eq!(out, pixels);
+    }
+}
@@ -0,0 +1,209 @@
+//! Taskbar button integration while a video is open.
+//!
+//! The button fills with playback progress (yellow while paused), and its thumbnail
+//! preview gets previous, play/pause and next buttons. Windows only keeps the buttons of a
+//! window, so the viewer attaches once it is active with a video on screen and hides them
+//! for images. Clicks come back as [`TaskbarButton`]s. Only Windows has an implementation;
+//! elsewhere [`TaskbarControls::attach`] returns `None`.
+
+use crossbeam_channel::Receiver;
+
+/// Side of the square button glyphs, in pixels.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub const GLYPH_SIZE: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub enum TaskbarButton {
+    Previous,
+    PlayPause,
+    Next,
+}
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+impl TaskbarButton {
+    pub const ALL: [TaskbarButton; 3] = [Self::Previous, Self::PlayPause, Self::Next];
+
+    /// Command id sent by the thumbnail toolbar. Starts at 1.
+    pub fn id(self) -> u32 {
+        self as u32 + 1
+    }
+
+    pub fn from_id(id: u32) -> Option<Self> {
+        Self::ALL.get(id.checked_sub(1)? as usize).copied()
+    }
+
+    pub fn tooltip(self, playing: bool) -> &'static str {
+        match self {
+            Self::Previous => "Previous",
+            Self::PlayPause if playing => "Pause",
+            Self::PlayPause => "Play",
+            Self::Next => "Next",
+        }
+    }
+}
+
+/// What the taskbar button shows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TaskbarProgress {
+    /// No video: no progress and no thumbnail buttons.
+    Hidden,
+    /// Position as a fraction of the duration.
+    Playing(f64),
+    Paused(f64),
+}
+
+impl TaskbarProgress {
+    /// Progress in thousandths, so the shell is only called when the bar visibly moves.
+    fn quantized(self) -> Option<(u64, bool)> {
+        match self {
+            Self::Hidden => None,
+            Self::Playing(fraction) => Some(((fraction.clamp(0.0, 1.0) * 1000.0) as u64, true)),
+            Self::Paused(fraction) => Some(((fraction.clamp(0.0, 1.0) * 1000.0) as u64, false)),
+        }
+    }
+}
+
+/// Coverage mask (0 or 255, row-major) of a button glyph: bars and triangles in the style of
+/// the shell's own media buttons. The play/pause glyph shows what a click does.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn glyph(button: TaskbarButton, playing: bool) -> Vec<u8> {
+    let triangle_right = |x: f32, y: f32, left: f32, width: f32| {
+        let half_height = 5.5;
+        x >= left && x <= left + width * (1.0 - (y - 8.0).abs() / half_height)
+    };
+    let mut mask = vec![0u8; GLYPH_SIZE * GLYPH_SIZE];
+    for (index, value) in mask.iter_mut().enumerate() {
+        let x = (index % GLYPH_SIZE) as f32 + 0.5;
+        let y = (index / GLYPH_SIZE) as f32 + 0.5;
+        let inside = match button {
+            TaskbarButton::PlayPause if playing => {
+                (3.0..13.0).contains(&y) && ((4.0..7.0).contains(&x) || (9.0..12.0).contains(&x))
+            }
+            TaskbarButton::PlayPause => triangle_right(x, y, 4.0, 9.0),
+            TaskbarButton::Next => {
+                triangle_right(x, y, 3.0, 7.0)
+                    || ((3.0..13.0).contains(&y) && (11.0..13.0).contains(&x))
+            }
+            TaskbarButton::Previous => {
+                let x = GLYPH_SIZE as f32 - x;
+                triangle_right(x, y, 3.0, 7.0)
+                    || ((3.0..13.0).contains(&y) && (11.0..13.0).contains(&x))
+            }
+        };
+        if inside {
+            *value = 255;
+        }
+    }
+    mask
+}
+
+/// Progress and thumbnail buttons of the viewer window's taskbar button; both are cleared
+/// when dropped.
+pub struct TaskbarControls {
+    events: Receiver<TaskbarButton>,
+    /// Last state sent to the shell, quantized.
+    shown: Option<Option<(u64, bool)>>,
+    #[cfg(target_os = "windows")]
+    native: crate::windows_env::TaskbarHandle,
+}
+
+impl TaskbarControls {
+    /// Attaches to the viewer window when it is the active window. `wake` runs after every
+    /// click is queued.
+    pub fn attach(wake: impl Fn() + Send + 'static) -> Option<Self> {
+        let (event_tx, events) = crossbeam_channel::unbounded::<TaskbarButton>();
+
+        #[cfg(target_os = "windows")]
+        {
+            let window = crate::windows_env::active_viewer_window()?;
+            let native = crate::windows_env::TaskbarHandle::attach(
+                window,
+                Box::new(move |id| {
+                    if let Some(button) = TaskbarButton::from_id(id) {
+                        let _ = event_tx.send(button);
+                        wake();
+                    }
+                }),
+            )?;
+            Some(Self {
+                events,
+                shown: None,
+                native,
+            })
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        {
+            let _ = (event_tx, events, wake);
+            None
+        }
+    }
+
+    /// Updates the button when `progress` differs visibly from what it shows.
+    pub fn show(&mut self, progress: TaskbarProgress) {
+        let quantized = progress.quantized();
+        #[cfg(target_os = "windows")]
+        {
+            // Buttons can only be added once Windows has created the taskbar button, so
+            // keep trying until that succeeds.
+            if !self
+                .native
+                .update_buttons(quantized.map(|(_, playing)| playing))
+            {
+                return;
+            }
+        }
+        if self.shown == Some(quantized) {
+            return;
+        }
+        self.shown = Some(quantized);
+        #[cfg(target_os = "windows")]
+        self.native.set_progress(quantized);
+    }
+
+    pub fn events(&self) -> &Receiver<TaskbarButton> {
+        &self.events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{glyph, TaskbarButton, TaskbarProgress, GLYPH_SIZE};
+
+    #[test]
+    fn button_ids_round_trip_and_skip_zero() {
+        for button in TaskbarButton::ALL {
+            assert_eq!(TaskbarButton::from_id(button.id()), Some(button));
+        }
+        assert_eq!(TaskbarButton::from_id(0), None);
+        assert_eq!(TaskbarButton::from_id(4), None);
+        assert_eq!(
+            TaskbarProgress::Playing(0.5).quantized(),
+            TaskbarProgress::Playing(0.5004).quantized()
+        );
+        assert_eq!(
+            TaskbarProgress::Paused(2.0).quantized(),
+            Some((1000, false))
+        );
+    }
+
+    #[test]
+    fn previous_glyph_mirrors_next() {
+        let next = glyph(TaskbarButton::Next, false);
+        let previous = glyph(TaskbarButton::Previous, false);
+        for row in 0..GLYPH_SIZE {
+            let next_row = &next[row * GLYPH_SIZE..(row + 1) * GLYPH_SIZE];
+            let previous_row: Vec<u8> = previous[row * GLYPH_SIZE..(row + 1) * GLYPH_SIZE]
+                .iter()
+                .rev()
+                .copied()
+                .collect();
+            assert_eq!(next_row, previous_row.as_slice());
+        }
+        assert_ne!(
+            glyph(TaskbarButton::PlayPause, true),
+            glyph(TaskbarButton::PlayPause, false)
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Colors and sizes of the overlay UI: the title bar, the video / animation bar and the
+//! on-screen notices.
+//!
+//! `overlay_theme` picks the dark or light base colors, or follows the Windows app theme
+//! with `auto`; the `overlay_*` settings then override single colors, the background
+//! opacity, the corner radius and the bar heights. The viewer resolves the theme when the
+//! config changes and re-reads the Windows theme every few seconds.
+
+use egui::Color32;
+
+use crate::config::{Config, OverlayThemeMode};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OverlayTheme {
+    pub title_bar_fill: Color32,
+    pub video_bar_fill: Color32,
+    /// Notice and popup panels.
+    pub panel_fill: Color32,
+    pub panel_stroke: Color32,
+    pub text: Color32,
+    /// File details next to the title.
+    pub muted_text: Color32,
+    /// Window button and toggle icons.
+    pub icon: Color32,
+    /// Empty part of seek and volume tracks.
+    pub track: Color32,
+    pub handle: Color32,
+    /// Video seek bar fill.
+    pub accent: Color32,
+    /// Seek bar fill of animated images.
+    pub animation_accent: Color32,
+    pub corner_radius: f32,
+    pub title_bar_height: f32,
+    pub video_bar_height: f32,
+}
+
+impl Default for OverlayTheme {
+    fn default() -> Self {
+        Self::resolve(&Config::default(), false)
+    }
+}
+
+impl OverlayTheme {
+    /// The theme `config` asks for. `system_light` is the Windows app theme, used by `auto`.
+    pub fn resolve(config: &Config, system_light: bool) -> Self {
+        let light = match config.overlay_theme {
+            OverlayThemeMode::Dark => false,
+            OverlayThemeMode::Light => true,
+            OverlayThemeMode::Auto => system_light,
+        };
+        let opacity = config.overlay_opacity.clamp(0.0, 1.0);
+        let fill = |[r, g, b]: [u8; 3], alpha: u8| {
+            let alpha = (f32::from(alpha) * opacity).round() as u8;
+            Color32::from_rgba_unmultiplied(r, g, b, alpha)
+        };
+        let rgb = |[r, g, b]: [u8; 3]| Color32::from_rgb(r, g, b);
+
+        let (title_bar, video_bar, panel, text, muted_text, track, handle) = if light {
+            (
+                [236, 236, 236],
+                [244, 244, 244],
+                [250, 251, 253],
+                [24, 24, 24],
+                [96, 96, 96],
+                [196, 196, 196],
+                [72, 72, 72],
+            )
+        } else {
+            (
+                [40, 40, 40],
+                [20, 20, 20],
+                [16, 23, 31],
+                [255, 255, 255],
+                [160, 160, 160],
+                [60, 60, 60],
+                [255, 255, 255],
+            )
+        };
+        let background = config.overlay_background_rgb;
+        let text = config.overlay_text_rgb.unwrap_or(text);
+        Self {
+            title_bar_fill: fill(background.unwrap_or(title_bar), 220),
+            video_bar_fill: fill(background.unwrap_or(video_bar), 230),
+            panel_fill: fill(background.unwrap_or(panel), 236),
+            panel_stroke: if light {
+                Color32::from_rgba_unmultiplied(40, 80, 140, 60)
+            } else {
+                Color32::from_rgba_unmultiplied(166, 207, 255, 62)
+            },
+            text: rgb(text),
+            muted_text: rgb(muted_text),
+            icon: rgb(text),
+            track: rgb(track),
+            handle: rgb(handle),
+            accent: rgb(config.overlay_accent_rgb),
+            animation_accent: rgb(config.overlay_animation_accent_rgb),
+            corner_radius: config.overlay_corner_radius,
+            title_bar_height: config.title_bar_height,
+            video_bar_height: config.video_bar_height,
+        }
+    }
+}
+
+/// Whether Windows apps currently use the light theme; `false` elsewhere.
+pub fn system_prefers_light() -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        crate::windows_env::apps_use_light_theme().unwrap_or(false)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OverlayTheme;
+    use crate::config::{Config, OverlayThemeMode};
+
+    #[test]
+    fn auto_follows_the_system_and_overrides_win() {
+        let mut config = Config::default();
+        let dark = OverlayTheme::resolve(&config, true);
+        assert_eq!(dark.text, egui::Color32::WHITE);
+        assert_eq!(dark.title_bar_fill.a(), 220);
+
+        config.overlay_theme = OverlayThemeMode::Auto;
+        config.overlay_opacity = 0.5;
+        config.overlay_text_rgb = Some([10, 20, 30]);
+        let light = OverlayTheme::resolve(&config, true);
+        assert_ne!(light.video_bar_fill, dark.video_bar_fill);
+        assert_eq!(light.title_bar_fill.a(), 110);
+        assert_eq!(light.text, egui::Color32::from_rgb(10, 20, 30));
+        assert_eq!(
+            OverlayTheme::resolve(&config, false).track,
+            dark.track,
+            "auto on a dark system uses the dark base colors"
+        );
+    }
+}
@@ -0,0 +1,264 @@
+//! Touchscreen gesture recognition on top of egui's raw touch events.
+//!
+//! egui already turns the first finger into pointer events and reports pinch/translation for
+//! two or more fingers (`InputState::multi_touch`). This tracker adds what the viewer needs
+//! on top: the pinch center, touchpad pinches, one-finger horizontal swipes and double taps.
+
+/// Minimum horizontal travel for a one-finger swipe.
+const SWIPE_MIN_PX: f32 = 80.0;
+/// Swipes must be mostly horizontal: `|dx| >= SWIPE_AXIS_RATIO * |dy|`.
+const SWIPE_AXIS_RATIO: f32 = 2.0;
+/// Slower drags are treated as pans, not swipes.
+const SWIPE_MAX_SECONDS: f64 = 0.6;
+/// Maximum finger travel for a touch to still count as a tap.
+const TAP_SLOP_PX: f32 = 14.0;
+const TAP_MAX_SECONDS: f64 = 0.3;
+/// Maximum gap and distance between the two taps of a double tap.
+const DOUBLE_TAP_MAX_GAP_SECONDS: f64 = 0.35;
+const DOUBLE_TAP_MAX_DISTANCE_PX: f32 = 40.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+}
+
+/// Gestures recognized in one frame.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TouchFrame {
+    /// Two or more fingers are on the surface; pointer-drag panning should stand down.
+    pub multi_touch: bool,
+    /// Pinch zoom factor this frame and the center between the fingers.
+    pub pinch: Option<(f32, egui::Pos2)>,
+    /// Movement of the fingers' center this frame (two or more fingers).
+    pub pan: egui::Vec2,
+    /// A one-finger swipe ended this frame.
+    pub swipe: Option<SwipeDirection>,
+    /// The second tap of a double tap ended this frame.
+    pub double_tap: bool,
+}
+
+/// One-finger contact, from touch-down until all fingers lift.
+#[derive(Debug, Clone, Copy)]
+struct Stroke {
+    start_pos: egui::Pos2,
+    start_time: f64,
+    last_pos: egui::Pos2,
+    /// Another finger joined at some point, so this is no longer a swipe or tap.
+    had_multiple_fingers: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct TouchTracker {
+    touches: Vec<(egui::TouchId, egui::Pos2)>,
+    stroke: Option<Stroke>,
+    last_tap: Option<(egui::Pos2, f64)>,
+    /// Pass number of the cached `frame`; the tracker is fed at most once per pass.
+    pass_nr: Option<u64>,
+    frame: TouchFrame,
+}
+
+impl TouchTracker {
+    /// Process this pass's touch events. Repeated calls within a pass return the same result.
+    pub fn update(&mut self, input: &egui::InputState, pass_nr: u64) -> TouchFrame {
+        if self.pass_nr == Some(pass_nr) {
+            return self.frame;
+        }
+        self.pass_nr = Some(pass_nr);
+
+        let mut frame = TouchFrame::default();
+        for event in &input.raw.events {
+            let egui::Event::Touch { id, phase, pos, .. } = event else {
+                continue;
+            };
+
+            match phase {
+                egui::TouchPhase::Start => {
+                    self.touches.retain(|(touch_id, _)| touch_id != id);
+                    self.touches.push((*id, *pos));
+                    match self.stroke.as_mut() {
+                        Some(stroke) => stroke.had_multiple_fingers = true,
+                        None => {
+                            self.stroke = Some(Stroke {
+                                start_pos: *pos,
+                                start_time: input.time,
+                                last_pos: *pos,
+                                had_multiple_fingers: false,
+                            })
+                        }
+                    }
+                }
+                egui::TouchPhase::Move => {
+                    if let Some(touch) =
+                        self.touches.iter_mut().find(|(touch_id, _)| touch_id == id)
+                    {
+                        touch.1 = *pos;
+                    }
+                    if let Some(stroke) = self.stroke.as_mut() {
+                        if self.touches.len() == 1 {
+                            stroke.last_pos = *pos;
+                        }
+                    }
+                }
+                egui::TouchPhase::End | egui::TouchPhase::Cancel => {
+                    self.touches.retain(|(touch_id, _)| touch_id != id);
+                    if !self.touches.is_empty() {
+                        continue;
+                    }
+                    if let Some(stroke) = self.stroke.take() {
+                        if *phase == egui::TouchPhase::End && !stroke.had_multiple_fingers {
+                            self.finish_stroke(stroke, *pos, input.time, &mut frame);
+                        }
+                    }
+                }
+            }
+        }
+
+        if self.touches.len() >= 2 {
+            frame.multi_touch = true;
+            let center = self
+                .touches
+                .iter()
+                .fold(egui::Vec2::ZERO, |sum, (_, pos)| sum + pos.to_vec2())
+                / self.touches.len() as f32;
+            if let Some(info) = input.multi_touch() {
+                if (info.zoom_delta - 1.0).abs() > f32::EPSILON {
+                    frame.pinch = Some((info.zoom_delta, center.to_pos2()));
+                }
+                frame.pan = info.translation_delta;
+            }
+        } else if !input.modifiers.ctrl && !input.modifiers.command {
+            // Precision touchpads report pinches as zoom events without any fingers; Ctrl+wheel
+            // zoom arrives the same way but is left to the existing wheel handling.
+            let zoom_delta = input.zoom_delta();
+            if (zoom_delta - 1.0).abs() > f32::EPSILON {
+                if let Some(pos) = input.pointer.hover_pos() {
+                    frame.pinch = Some((zoom_delta, pos));
+                }
+            }
+        }
+
+        self.frame = frame;
+        frame
+    }
+
+    fn finish_stroke(
+        &mut self,
+        stroke: Stroke,
+        end_pos: egui::Pos2,
+        now: f64,
+        frame: &mut TouchFrame,
+    ) {
+        let delta = end_pos - stroke.start_pos;
+        let duration = now - stroke.start_time;
+
+        if delta.x.abs() >= SWIPE_MIN_PX
+            && delta.x.abs() >= delta.y.abs() * SWIPE_AXIS_RATIO
+            && duration <= SWIPE_MAX_SECONDS
+        {
+            frame.swipe = Some(if delta.x < 0.0 {
+                SwipeDirection::Left
+            } else {
+                SwipeDirection::Right
+            });
+            self.last_tap = None;
+            return;
+        }
+
+        let travel = (stroke.last_pos - stroke.start_pos)
+            .length()
+            .max(delta.length());
+        if travel > TAP_SLOP_PX || duration > TAP_MAX_SECONDS {
+            self.last_tap = None;
+            return;
+        }
+
+        match self.last_tap.take() {
+            Some((tap_pos, tap_time))
+                if now - tap_time <= DOUBLE_TAP_MAX_GAP_SECONDS
+                    && (end_pos - tap_pos).length() <= DOUBLE_TAP_MAX_DISTANCE_PX =>
+            {
+                frame.double_tap = true;
+            }
+            _ => self.last_tap = Some((end_pos, now)),
+        }
+    }
+
+    /// Forget in-progress touches, e.g. while a modal owns the input.
+    pub fn reset(&mut self) {
+        self.touches.clear();
+        self.stroke = None;
+        self.last_tap = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stroke(start: (f32, f32), start_time: f64) -> Stroke {
+        Stroke {
+            start_pos: egui::pos2(start.0, start.1),
+            start_time,
+            last_pos: egui::pos2(start.0, start.1),
+            had_multiple_fingers: false,
+        }
+    }
+
+    #[test]
+    fn fast_horizontal_strokes_are_swipes() {
+        let mut tracker = TouchTracker::default();
+        let mut frame = TouchFrame::default();
+        tracker.finish_stroke(
+            stroke((300.0, 200.0), 0.0),
+            egui::pos2(150.0, 220.0),
+            0.2,
+            &mut frame,
+        );
+        assert_eq!(frame.swipe, Some(SwipeDirection::Left));
+
+        let mut frame = TouchFrame::default();
+        tracker.finish_stroke(
+            stroke((300.0, 200.0), 1.0),
+            egui::pos2(360.0, 300.0),
+            1.2,
+            &mut frame,
+        );
+        assert_eq!(frame.swipe, None);
+    }
+
+    #[test]
+    fn two_nearby_taps_form_a_double_tap() {
+        let mut tracker = TouchTracker::default();
+        let mut frame = TouchFrame::default();
+        tracker.finish_stroke(
+            stroke((100.0, 100.0), 0.0),
+            egui::pos2(102.0, 101.0),
+            0.1,
+            &mut frame,
+        );
+        assert!(!frame.double_tap);
+        tracker.finish_stroke(
+            stroke((105.0, 98.0), 0.3),
+            egui::pos2(105.0, 98.0),
+            0.35,
+            &mut frame,
+        );
+        assert!(frame.double_tap);
+
+        let mut frame = TouchFrame::default();
+        tracker.finish_stroke(
+            stroke((100.0, 100.0), 2.0),
+            egui::pos2(100.0, 100.0),
+            2.1,
+            &mut frame,
+        );
+        tracker.finish_stroke(
+            stroke((100.0, 100.0), 3.0),
+            egui::pos2(100.0, 100.0),
+            3.1,
+            &mut frame,
+        );
+        assert!(!frame.double_tap);
+    }
+}
@@ -0,0 +1,175 @@
+//! Animated transitions between images on next/previous.
+//!
+//! The outgoing image's texture is kept alive until the incoming image is on screen, then
+//! both are drawn for `transition_duration_ms`: [`TransitionEffect::frame`] says where the
+//! outgoing image goes and how opaque it is, and how far the incoming one is shifted.
+
+/// `transition_effect`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionEffect {
+    Off,
+    /// The outgoing image fades out over the incoming one.
+    Crossfade,
+    /// Both images slide sideways, in the direction of travel.
+    Slide,
+    /// The outgoing image grows and fades out, as if passed through.
+    Zoom,
+}
+
+impl TransitionEffect {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "off" | "none" | "false" => Some(Self::Off),
+            "crossfade" | "fade" | "dissolve" => Some(Self::Crossfade),
+            "slide" | "push" => Some(Self::Slide),
+            "zoom" => Some(Self::Zoom),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Crossfade => "crossfade",
+            Self::Slide => "slide",
+            Self::Zoom => "zoom",
+        }
+    }
+
+    /// The transition at eased `progress` (`0..=1`). `outgoing` is where the outgoing image
+    /// was drawn, `forward` is true for next, and `width` is the width of the view.
+    pub fn frame(
+        self,
+        progress: f32,
+        outgoing: egui::Rect,
+        forward: bool,
+        width: f32,
+    ) -> TransitionFrame {
+        let progress = progress.clamp(0.0, 1.0);
+        let direction = if forward { 1.0 } else { -1.0 };
+        match self {
+            Self::Off => TransitionFrame {
+                outgoing_rect: outgoing,
+                outgoing_alpha: 0.0,
+                incoming_offset: egui::Vec2::ZERO,
+            },
+            Self::Crossfade => TransitionFrame {
+                outgoing_rect: outgoing,
+                outgoing_alpha: 1.0 - progress,
+                incoming_offset: egui::Vec2::ZERO,
+            },
+            Self::Slide => TransitionFrame {
+                outgoing_rect: outgoing.translate(egui::vec2(-direction * width * progress, 0.0)),
+                outgoing_alpha: 1.0,
+                incoming_offset: egui::vec2(direction * width * (1.0 - progress), 0.0),
+            },
+            Self::Zoom => TransitionFrame {
+                outgoing_rect: egui::Rect::from_center_size(
+                    outgoing.center(),
+                    outgoing.size() * (1.0 + ZOOM_GROWTH * progress),
+                ),
+                outgoing_alpha: 1.0 - progress,
+                incoming_offset: egui::Vec2::ZERO,
+            },
+        }
+    }
+}
+
+/// How much larger the outgoing image gets over a zoom transition.
+const ZOOM_GROWTH: f32 = 0.3;
+
+/// `transition_easing`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionEasing {
+    Linear,
+    /// Fast start, gentle landing.
+    EaseOut,
+    /// Gentle start and landing.
+    EaseInOut,
+}
+
+impl TransitionEasing {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().replace('-', "_").as_str() {
+            "linear" => Some(Self::Linear),
+            "ease_out" | "easeout" | "out" => Some(Self::EaseOut),
+            "ease_in_out" | "easeinout" | "in_out" | "smooth" => Some(Self::EaseInOut),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Linear => "linear",
+            Self::EaseOut => "ease_out",
+            Self::EaseInOut => "ease_in_out",
+        }
+    }
+
+    /// Eased progress for linear progress `t` (`0..=1`).
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Self::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// One frame of a transition.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransitionFrame {
+    /// Where to draw the outgoing image.
+    pub outgoing_rect: egui::Rect,
+    /// Opacity of the outgoing image, drawn over the incoming one.
+    pub outgoing_alpha: f32,
+    /// Added to the incoming image's position.
+    pub incoming_offset: egui::Vec2,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TransitionEasing, TransitionEffect};
+
+    #[test]
+    fn easings_run_from_zero_to_one() {
+        for easing in [
+            TransitionEasing::Linear,
+            TransitionEasing::EaseOut,
+            TransitionEasing::EaseInOut,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+            assert_eq!(easing.apply(2.0), 1.0);
+        }
+        assert!(TransitionEasing::EaseOut.apply(0.5) > 0.5);
+        assert_eq!(TransitionEasing::EaseInOut.apply(0.5), 0.5);
+        assert_eq!(
+            TransitionEasing::from_str("Ease-Out"),
+            Some(TransitionEasing::EaseOut)
+        );
+    }
+
+    #[test]
+    fn slides_follow_the_direction_of_travel() {
+        let outgoing = egui::Rect::from_min_size(egui::pos2(100.0, 0.0), egui::vec2(800.0, 600.0));
+        let start = TransitionEffect::Slide.frame(0.0, outgoing, true, 1000.0);
+        assert_eq!(start.outgoing_rect, outgoing);
+        assert_eq!(start.incoming_offset, egui::vec2(1000.0, 0.0));
+
+        let halfway = TransitionEffect::Slide.frame(0.5, outgoing, false, 1000.0);
+        assert_eq!(halfway.outgoing_rect.min, egui::pos2(600.0, 0.0));
+        assert_eq!(halfway.incoming_offset, egui::vec2(-500.0, 0.0));
+
+        let zoom = TransitionEffect::Zoom.frame(1.0, outgoing, true, 1000.0);
+        assert_eq!(zoom.outgoing_rect.center(), outgoing.center());
+        assert_eq!(zoom.outgoing_alpha, 0.0);
+        assert_eq!(
+            TransitionEffect::Crossfade
+                .frame(0.25, outgoing, true, 1000.0)
+                .outgoing_alpha,
+            0.75
+        );
+    }
+}
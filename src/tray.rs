@@ -0,0 +1,132 @@
+//! Notification-area (system tray) icon with a context menu.
+//!
+//! The icon lives on its own thread with a hidden window that receives the shell's
+//! callbacks, so it keeps working while the owner blocks or has no window at all. The
+//! menu is rebuilt from [`TrayIcon::set_menu`] each time it opens; picks come back as
+//! [`TrayEvent`]s. Only Windows has an implementation; elsewhere [`TrayIcon::spawn`]
+//! returns `None`.
+
+use std::sync::Arc;
+
+use crossbeam_channel::Receiver;
+use parking_lot::Mutex;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrayMenuItem {
+    /// A command. `id` must not be 0, which Windows reserves for "nothing picked".
+    Item {
+        id: u32,
+        label: String,
+        checked: bool,
+        enabled: bool,
+    },
+    Separator,
+    /// Grayed out when `items` is empty.
+    Submenu {
+        label: String,
+        items: Vec<TrayMenuItem>,
+    },
+}
+
+impl TrayMenuItem {
+    pub fn item(id: u32, label: impl Into<String>) -> Self {
+        Self::Item {
+            id,
+            label: label.into(),
+            checked: false,
+            enabled: true,
+        }
+    }
+
+    pub fn checked(id: u32, label: impl Into<String>, checked: bool) -> Self {
+        Self::Item {
+            id,
+            label: label.into(),
+            checked,
+            enabled: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub enum TrayEvent {
+    /// A menu item was picked.
+    Menu(u32),
+    /// The icon was left-clicked.
+    Activate,
+}
+
+/// The menu, shared with the tray thread that builds it on right-click.
+pub type SharedTrayMenu = Arc<Mutex<Vec<TrayMenuItem>>>;
+
+/// A tray icon; removed from the notification area when dropped.
+pub struct TrayIcon {
+    menu: SharedTrayMenu,
+    events: Receiver<TrayEvent>,
+    /// Hidden window owning the icon, closed on drop.
+    #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+    window: usize,
+}
+
+impl TrayIcon {
+    /// Adds an icon with `tooltip` and `menu`. `wake` runs after every event is queued, so
+    /// an owner that sleeps between frames can request a repaint.
+    pub fn spawn(
+        tooltip: &str,
+        menu: Vec<TrayMenuItem>,
+        wake: impl Fn() + Send + 'static,
+    ) -> Option<Self> {
+        let menu: SharedTrayMenu = Arc::new(Mutex::new(menu));
+        let (event_tx, events) = crossbeam_channel::unbounded::<TrayEvent>();
+
+        #[cfg(target_os = "windows")]
+        {
+            let (ready_tx, ready_rx) = crossbeam_channel::bounded(1);
+            let tooltip = tooltip.to_string();
+            let thread_menu = Arc::clone(&menu);
+            std::thread::Builder::new()
+                .name("tray-icon".to_string())
+                .spawn(move || {
+                    crate::windows_env::run_tray_icon(
+                        &tooltip,
+                        thread_menu,
+                        Box::new(move |event| {
+                            let _ = event_tx.send(event);
+                            wake();
+                        }),
+                        ready_tx,
+                    );
+                })
+                .ok()?;
+            let window = ready_rx.recv().ok().filter(|window| *window != 0)?;
+            Some(Self {
+                menu,
+                events,
+                window,
+            })
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        {
+            let _ = (tooltip, menu, event_tx, events, wake);
+            None
+        }
+    }
+
+    /// Replaces the menu shown the next time the icon is right-clicked.
+    pub fn set_menu(&self, menu: Vec<TrayMenuItem>) {
+        *self.menu.lock() = menu;
+    }
+
+    pub fn events(&self) -> &Receiver<TrayEvent> {
+        &self.events
+    }
+}
+
+impl Drop for TrayIcon {
+    fn drop(&mut self) {
+        #[cfg(target_os = "windows")]
+        crate::windows_env::close_tray_icon(self.window);
+    }
+}
@@ -0,0 +1,43 @@
+//! libjpeg-turbo JPEG decoding, built in with the `turbojpeg` cargo feature.
+//!
+//! libjpeg-turbo's hand-written SIMD kernels decode large JPEGs faster than the pure-Rust
+//! zune-jpeg path; `--bench-decode <folder>` measures the difference on real files. A JPEG
+//! libjpeg-turbo rejects still goes through zune-jpeg.
+
+use std::path::Path;
+
+use crate::image_loader::DEFAULT_MAX_DECODE_ALLOC_BYTES;
+
+/// Decodes the JPEG at `path` to tightly packed RGBA.
+pub fn decode_file(path: &Path) -> Result<(u32, u32, Vec<u8>), String> {
+    let data = std::fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let header = turbojpeg::read_header(&data)
+        .map_err(|e| format!("libjpeg-turbo could not read the header: {}", e))?;
+    let bytes = (header.width as u64)
+        .saturating_mul(header.height as u64)
+        .saturating_mul(4);
+    if bytes > DEFAULT_MAX_DECODE_ALLOC_BYTES {
+        return Err(format!(
+            "{}x{} JPEG exceeds the decode memory limit",
+            header.width, header.height
+        ));
+    }
+
+    let image = turbojpeg::decompress(&data, turbojpeg::PixelFormat::RGBA)
+        .map_err(|e| format!("libjpeg-turbo failed to decode: {}", e))?;
+    let width = u32::try_from(image.width).map_err(|_| "Decoded image width too large")?;
+    let height = u32::try_from(image.height).map_err(|_| "Decoded image height too large")?;
+    let row_bytes = image.width * 4;
+    let pixels = if image.pitch == row_bytes {
+        image.pixels
+    } else {
+        image
+            .pixels
+            .chunks(image.pitch)
+            .flat_map(|row| &row[..row_bytes])
+            .copied()
+            .collect()
+    };
+    Ok((width, height, pixels))
+}
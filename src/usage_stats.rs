@@ -0,0 +1,332 @@
+//! Local usage statistics: files viewed per day, total images/videos browsed and how often
+//! each action ran.
+//!
+//! Nothing leaves the machine. Counters are buffered in memory and written in batches to
+//! `usage_stats.redb` in the local app data directory; the statistics window reads them back
+//! and can clear them. Days are UTC calendar days.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use hashbrown::HashMap;
+use parking_lot::Mutex;
+use redb::{Database, DatabaseError, ReadableTable, TableDefinition};
+
+use crate::storage;
+
+/// Views per UTC day, keyed by days since the Unix epoch.
+const DAILY_VIEWS_TABLE: TableDefinition<u32, u64> = TableDefinition::new("daily_views");
+const TOTALS_TABLE: TableDefinition<&str, u64> = TableDefinition::new("totals");
+const ACTIONS_TABLE: TableDefinition<&str, u64> = TableDefinition::new("actions");
+const STATS_FILE_NAME: &str = "usage_stats.redb";
+const IMAGES_KEY: &str = "images_viewed";
+const VIDEOS_KEY: &str = "videos_viewed";
+/// Buffered counters are written at most this often (and on exit).
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+const TOP_ACTIONS_LIMIT: usize = 10;
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+#[derive(Default)]
+struct PendingUsage {
+    views_by_day: HashMap<u32, u64>,
+    images: u64,
+    videos: u64,
+    actions: HashMap<&'static str, u64>,
+    last_flush: Option<Instant>,
+}
+
+impl PendingUsage {
+    fn is_empty(&self) -> bool {
+        self.views_by_day.is_empty() && self.actions.is_empty()
+    }
+}
+
+/// Counters shown by the statistics window.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UsageSnapshot {
+    pub viewed_today: u64,
+    pub viewed_last_7_days: u64,
+    pub images_viewed: u64,
+    pub videos_viewed: u64,
+    /// First day with recorded views, as `YYYY-MM-DD`.
+    pub tracking_since: Option<String>,
+    /// Most-used actions by config name, most frequent first.
+    pub top_actions: Vec<(String, u64)>,
+}
+
+struct UsageStore {
+    db: Database,
+}
+
+impl UsageStore {
+    fn open_default() -> Option<Self> {
+        let path = default_stats_path()?;
+        let db = open_database(&path)?;
+        Some(Self { db })
+    }
+
+    fn apply(&self, pending: &PendingUsage) -> Option<()> {
+        let write_txn = self.db.begin_write().ok()?;
+        {
+            let mut daily = write_txn.open_table(DAILY_VIEWS_TABLE).ok()?;
+            for (&day, &count) in &pending.views_by_day {
+                let current = daily.get(day).ok()?.map_or(0, |value| value.value());
+                daily.insert(day, current.saturating_add(count)).ok()?;
+            }
+
+            let mut totals = write_txn.open_table(TOTALS_TABLE).ok()?;
+            for (key, count) in [(IMAGES_KEY, pending.images), (VIDEOS_KEY, pending.videos)] {
+                if count == 0 {
+                    continue;
+                }
+                let current = totals.get(key).ok()?.map_or(0, |value| value.value());
+                totals.insert(key, current.saturating_add(count)).ok()?;
+            }
+
+            let mut actions = write_txn.open_table(ACTIONS_TABLE).ok()?;
+            for (&name, &count) in &pending.actions {
+                let current = actions.get(name).ok()?.map_or(0, |value| value.value());
+                actions.insert(name, current.saturating_add(count)).ok()?;
+            }
+        }
+        write_txn.commit().ok()
+    }
+
+    fn snapshot(&self, today: u32) -> Option<UsageSnapshot> {
+        let mut snapshot = UsageSnapshot::default();
+        let read_txn = self.db.begin_read().ok()?;
+
+        // Tables only exist after the first flush.
+        if let Ok(daily) = read_txn.open_table(DAILY_VIEWS_TABLE) {
+            for entry in daily.iter().ok()? {
+                let (day, count) = entry.ok()?;
+                let (day, count) = (day.value(), count.value());
+                if day == today {
+                    snapshot.viewed_today += count;
+                }
+                if today.saturating_sub(day) < 7 {
+                    snapshot.viewed_last_7_days += count;
+                }
+            }
+            if let Some((first_day, _)) = daily.first().ok()? {
+                snapshot.tracking_since = Some(format_day(first_day.value()));
+            }
+        }
+
+        if let Ok(totals) = read_txn.open_table(TOTALS_TABLE) {
+            snapshot.images_viewed = totals
+                .get(IMAGES_KEY)
+                .ok()?
+                .map_or(0, |value| value.value());
+            snapshot.videos_viewed = totals
+                .get(VIDEOS_KEY)
+                .ok()?
+                .map_or(0, |value| value.value());
+        }
+
+        if let Ok(actions) = read_txn.open_table(ACTIONS_TABLE) {
+            for entry in actions.iter().ok()? {
+                let (name, count) = entry.ok()?;
+                snapshot
+                    .top_actions
+                    .push((name.value().to_string(), count.value()));
+            }
+        }
+        snapshot
+            .top_actions
+            .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        snapshot.top_actions.truncate(TOP_ACTIONS_LIMIT);
+        Some(snapshot)
+    }
+
+    fn clear(&self) -> Option<()> {
+        let write_txn = self.db.begin_write().ok()?;
+        write_txn.delete_table(DAILY_VIEWS_TABLE).ok()?;
+        write_txn.delete_table(TOTALS_TABLE).ok()?;
+        write_txn.delete_table(ACTIONS_TABLE).ok()?;
+        write_txn.commit().ok()
+    }
+}
+
+static GLOBAL_USAGE_STORE: OnceLock<Option<Arc<Mutex<UsageStore>>>> = OnceLock::new();
+static PENDING_USAGE: OnceLock<Mutex<PendingUsage>> = OnceLock::new();
+
+fn global_usage_store_handle() -> Option<&'static Arc<Mutex<UsageStore>>> {
+    GLOBAL_USAGE_STORE
+        .get_or_init(|| UsageStore::open_default().map(|store| Arc::new(Mutex::new(store))))
+        .as_ref()
+}
+
+fn pending_usage() -> &'static Mutex<PendingUsage> {
+    PENDING_USAGE.get_or_init(Mutex::default)
+}
+
+/// Apply the `usage_stats` config flag. Disabling drops counters that were not written yet.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        *pending_usage().lock() = PendingUsage::default();
+    }
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Count one opened file.
+pub fn record_file_view(is_video: bool) {
+    if !is_enabled() {
+        return;
+    }
+    let mut pending = pending_usage().lock();
+    *pending.views_by_day.entry(today()).or_insert(0) += 1;
+    if is_video {
+        pending.videos += 1;
+    } else {
+        pending.images += 1;
+    }
+    schedule_flush_if_due(&mut pending);
+}
+
+/// Count one run of a shortcut action, by its config.ini name.
+pub fn record_action(name: &'static str) {
+    if !is_enabled() {
+        return;
+    }
+    let mut pending = pending_usage().lock();
+    *pending.actions.entry(name).or_insert(0) += 1;
+    schedule_flush_if_due(&mut pending);
+}
+
+fn schedule_flush_if_due(pending: &mut PendingUsage) {
+    let now = Instant::now();
+    let last_flush = *pending.last_flush.get_or_insert(now);
+    if now.duration_since(last_flush) >= FLUSH_INTERVAL {
+        pending.last_flush = Some(now);
+        crate::async_runtime::spawn_blocking_or_thread("usage-stats-flush", flush);
+    }
+}
+
+/// Write buffered counters to disk. Called periodically and when the viewer closes.
+pub fn flush() {
+    let pending = std::mem::take(&mut *pending_usage().lock());
+    if pending.is_empty() {
+        return;
+    }
+    let Some(store) = global_usage_store_handle() else {
+        return;
+    };
+    if store.lock().apply(&pending).is_none() {
+        tracing::warn!(target: "usage_stats", "failed to write usage statistics");
+    }
+}
+
+/// Current statistics, including counters that were not written yet.
+pub fn snapshot() -> UsageSnapshot {
+    flush();
+    global_usage_store_handle()
+        .and_then(|store| store.lock().snapshot(today()))
+        .unwrap_or_default()
+}
+
+pub fn clear() -> Result<(), String> {
+    *pending_usage().lock() = PendingUsage::default();
+    let store = global_usage_store_handle()
+        .ok_or_else(|| "Usage statistics store is unavailable".to_string())?;
+    let cleared = store.lock().clear();
+    cleared.ok_or_else(|| "Failed to clear usage statistics".to_string())
+}
+
+fn today() -> u32 {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    (secs / 86_400) as u32
+}
+
+/// Days since the Unix epoch to `YYYY-MM-DD` (proleptic Gregorian calendar).
+fn format_day(day: u32) -> String {
+    let (y, m, d) = civil_date(day);
+    format!("{y:04}-{m:02}-{d:02}")
+}
+
+/// Year, month and day of a day count since the Unix epoch (proleptic Gregorian, UTC).
+pub fn civil_date(day: u32) -> (i64, u32, u32) {
+    let z = day as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let d = day_of_year - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = year_of_era + era * 400 + i64::from(m <= 2);
+    (y, m as u32, d as u32)
+}
+
+fn default_stats_path() -> Option<PathBuf> {
+    storage::data_file(STATS_FILE_NAME)
+}
+
+fn open_database(path: &Path) -> Option<Database> {
+    match Database::create(path) {
+        Ok(db) => Some(db),
+        Err(DatabaseError::Storage(redb::StorageError::Corrupted(_))) => {
+            let _ = std::fs::remove_file(path);
+            Database::create(path).ok()
+        }
+        Err(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_epoch_days_as_dates() {
+        assert_eq!(format_day(0), "1970-01-01");
+        assert_eq!(format_day(19_782), "2024-02-29");
+        assert_eq!(format_day(20_742), "2026-10-16");
+    }
+
+    #[test]
+    fn store_accumulates_and_clears() {
+        let path =
+            std::env::temp_dir().join(format!("riv-usage-stats-test-{}.redb", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let store = UsageStore {
+            db: open_database(&path).unwrap(),
+        };
+
+        let mut pending = PendingUsage::default();
+        pending.views_by_day.insert(100, 3);
+        pending.views_by_day.insert(90, 2);
+        pending.images = 4;
+        pending.videos = 1;
+        pending.actions.insert("next_image", 5);
+        pending.actions.insert("zoom_in", 2);
+        assert!(store.apply(&pending).is_some());
+        assert!(store.apply(&pending).is_some());
+
+        let snapshot = store.snapshot(100).unwrap();
+        assert_eq!(snapshot.viewed_today, 6);
+        assert_eq!(snapshot.viewed_last_7_days, 6);
+        assert_eq!(snapshot.images_viewed, 8);
+        assert_eq!(snapshot.videos_viewed, 2);
+        assert_eq!(snapshot.tracking_since.as_deref(), Some("1970-04-01"));
+        assert_eq!(
+            snapshot.top_actions,
+            vec![("next_image".to_string(), 10), ("zoom_in".to_string(), 4)]
+        );
+
+        assert!(store.clear().is_some());
+        assert_eq!(store.snapshot(100), Some(UsageSnapshot::default()));
+        drop(store);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,118 @@
+//! Review overlays for video frames: broadcast safe areas and letterbox masks for a target
+//! aspect ratio.
+//!
+//! Rectangles are in video frame pixels; the caller maps them to the screen through the
+//! current view. The overlays are only drawn on screen, so trims and conversions still write
+//! the whole frame.
+
+/// Action-safe and title-safe areas as a share of the picture (SMPTE ST 2046-1).
+pub const ACTION_SAFE: f32 = 0.93;
+pub const TITLE_SAFE: f32 = 0.90;
+
+/// Target aspect ratios offered in the video controls, as label and width / height.
+pub const ASPECT_PRESETS: [(&str, f32); 7] = [
+    ("2.39:1", 2.39),
+    ("1.85:1", 1.85),
+    ("16:9", 16.0 / 9.0),
+    ("4:3", 4.0 / 3.0),
+    ("1:1", 1.0),
+    ("4:5", 0.8),
+    ("9:16", 9.0 / 16.0),
+];
+
+/// Parses `2.39`, `2.39:1` or `16:9` into width / height.
+pub fn parse_aspect(value: &str) -> Option<f32> {
+    let value = value.trim();
+    let ratio = match value.split_once(':') {
+        Some((width, height)) => {
+            width.trim().parse::<f32>().ok()? / height.trim().parse::<f32>().ok()?
+        }
+        None => value.parse::<f32>().ok()?,
+    };
+    (ratio.is_finite() && (0.1..=10.0).contains(&ratio)).then_some(ratio)
+}
+
+/// The preset label of `ratio`, or `2.00:1` style for other ratios.
+pub fn aspect_label(ratio: f32) -> String {
+    ASPECT_PRESETS
+        .iter()
+        .find(|(_, preset)| (preset - ratio).abs() < 0.005)
+        .map_or_else(|| format!("{ratio:.2}:1"), |(label, _)| label.to_string())
+}
+
+/// The centered part of `frame` that a `ratio` crop keeps.
+pub fn masked_area(frame: egui::Rect, ratio: f32) -> egui::Rect {
+    let size = frame.size();
+    let kept = if size.x / size.y > ratio {
+        egui::vec2(size.y * ratio, size.y)
+    } else {
+        egui::vec2(size.x, size.x / ratio)
+    };
+    egui::Rect::from_center_size(frame.center(), kept)
+}
+
+/// The bars covering `frame` outside `area`: top and bottom for a letterbox, left and right
+/// for a pillarbox.
+pub fn mask_bars(frame: egui::Rect, area: egui::Rect) -> Vec<egui::Rect> {
+    [
+        egui::Rect::from_min_max(frame.min, egui::pos2(frame.max.x, area.min.y)),
+        egui::Rect::from_min_max(egui::pos2(frame.min.x, area.max.y), frame.max),
+        egui::Rect::from_min_max(
+            egui::pos2(frame.min.x, area.min.y),
+            egui::pos2(area.min.x, area.max.y),
+        ),
+        egui::Rect::from_min_max(
+            egui::pos2(area.max.x, area.min.y),
+            egui::pos2(frame.max.x, area.max.y),
+        ),
+    ]
+    .into_iter()
+    .filter(|bar| bar.width() > 0.5 && bar.height() > 0.5)
+    .collect()
+}
+
+/// The safe area covering `share` of `area`, centered.
+pub fn safe_area(area: egui::Rect, share: f32) -> egui::Rect {
+    egui::Rect::from_center_size(area.center(), area.size() * share)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{aspect_label, mask_bars, masked_area, parse_aspect, safe_area, TITLE_SAFE};
+
+    #[test]
+    fn aspect_ratios_parse_and_round_trip() {
+        assert_eq!(parse_aspect("16:9"), Some(16.0 / 9.0));
+        assert_eq!(parse_aspect(" 2.39:1 "), Some(2.39));
+        assert_eq!(parse_aspect("1.85"), Some(1.85));
+        assert_eq!(parse_aspect("16:0"), None);
+        assert_eq!(parse_aspect("wide"), None);
+        assert_eq!(aspect_label(9.0 / 16.0), "9:16");
+        assert_eq!(aspect_label(2.0), "2.00:1");
+    }
+
+    #[test]
+    fn masks_letterbox_wide_targets_and_pillarbox_tall_ones() {
+        let frame = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(1920.0, 1080.0));
+
+        let scope = masked_area(frame, 2.4);
+        assert_eq!(
+            scope,
+            egui::Rect::from_min_max(egui::pos2(0.0, 140.0), egui::pos2(1920.0, 940.0))
+        );
+        let bars = mask_bars(frame, scope);
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0].height(), 140.0);
+
+        let vertical = masked_area(frame, 9.0 / 16.0);
+        assert!((vertical.width() - 607.5).abs() < 0.01);
+        let bars = mask_bars(frame, vertical);
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0].min, egui::pos2(0.0, 0.0));
+        assert_eq!(bars[0].height(), 1080.0);
+
+        assert!(mask_bars(frame, masked_area(frame, 16.0 / 9.0)).is_empty());
+        let title = safe_area(frame, TITLE_SAFE);
+        assert_eq!(title.min, egui::pos2(96.0, 54.0));
+    }
+}
@@ -3,7 +3,7 @@
 
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::sync::atomic::{AtomicBool, AtomicI8, AtomicU32, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI8, AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::sync::OnceLock;
 use std::time::{Duration, Instant};
@@ -20,6 +20,7 @@ use rayon::prelude::*;
 use std::collections::VecDeque;
 
 use crate::app_dirs;
+use crate::config::DeinterlaceMode;
 
 #[cfg(target_os = "windows")]
 fn configure_gstreamer_env_windows() {
@@ -211,8 +212,8 @@ fn configure_gstreamer_env_windows() {
     // Ensure the registry path is writable (some setups can end up pointing at a non-writable
     // location, which breaks plugin discovery and makes factories "disappear").
     if std::env::var_os("GST_REGISTRY").is_none() {
-        if let Some(local_data_dir) = app_dirs::app_local_data_dir() {
-            let dir = local_data_dir.join("gstreamer");
+        if let Some(data_dir) = crate::storage::data_dir() {
+            let dir = data_dir.join("gstreamer");
             let _ = std::fs::create_dir_all(&dir);
             std::env::set_var("GST_REGISTRY", dir.join("registry.x86_64.bin"));
         }
@@ -421,6 +422,19 @@ pub struct VideoFrame {
     pub width: u32,
     pub height: u32,
     pub pts: Option<Duration>,
+    /// When the frame should be on screen, from its running time on the pipeline clock.
+    /// `None` for preroll frames, which are shown as soon as they arrive.
+    pub due: Option<Instant>,
+}
+
+/// Frame pacing counters of a player, shown in the debug HUD.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FramePacingStats {
+    pub presented: u64,
+    /// Frames replaced by a newer one before they were shown.
+    pub dropped: u64,
+    /// Frames shown more than one display refresh after they were due.
+    pub late: u64,
 }
 
 /// Borrowed Direct3D 11 texture metadata for a GStreamer D3D11-backed sample.
@@ -682,6 +696,8 @@ struct VideoState {
     seek_in_progress: AtomicBool,
     // -1 unknown, 0 full-range (no expand), 1 limited-range (expand)
     needs_range_expand: AtomicI8,
+    /// Frames pushed out of the full queue or skipped for a newer one, never shown.
+    dropped_frames: AtomicU64,
 }
 
 const RANGE_EXPAND_UNKNOWN: i8 = -1;
@@ -699,6 +715,11 @@ const LOCAL_FILE_BUFFER_SIZE_BYTES: i32 = 50 * 1024 * 1024;
 const LOCAL_FILE_RING_BUFFER_MAX_SIZE_BYTES: u64 = 96 * 1024 * 1024;
 const LOCAL_FILE_SOURCE_BLOCK_SIZE_BYTES: i32 = 256 * 1024;
 const APPSINK_MAX_BUFFERS: u32 = 3;
+/// How early the sink hands frames over with frame pacing on, so each can wait in the queue
+/// for the display refresh closest to its presentation time.
+const FRAME_PACING_LEAD: Duration = Duration::from_millis(25);
+/// Deadlines further out than this come from a stale clock and are ignored.
+const FRAME_PACING_MAX_WAIT: Duration = Duration::from_secs(1);
 const KEYFRAME_SEEK_PREROLL_TIMEOUT_MS: u64 = 20;
 const ACCURATE_SEEK_PREROLL_TIMEOUT_MS: u64 = 75;
 const SUBTITLE_FONT_DESC_FALLBACK_CJK: &str =
@@ -769,6 +790,7 @@ impl VideoState {
         while queue.len() >= target {
             if let Some(stale) = queue.pop_front() {
                 self.recycle_buffer(stale.pixels);
+                self.dropped_frames.fetch_add(1, Ordering::Relaxed);
             }
         }
         queue.push_back(frame);
@@ -779,11 +801,35 @@ impl VideoState {
         while queue.len() > 1 {
             if let Some(stale) = queue.pop_front() {
                 self.recycle_buffer(stale.pixels);
+                self.dropped_frames.fetch_add(1, Ordering::Relaxed);
             }
         }
         queue.pop_front()
     }
 
+    /// The newest frame due by `present_at`, dropping the older due ones. Frames that are
+    /// not due yet stay queued.
+    fn pop_due_frame(&self, present_at: Instant) -> Option<VideoFrame> {
+        let mut queue = self.frame_queue.lock();
+        let mut latest: Option<VideoFrame> = None;
+        while queue.front().is_some_and(|frame| {
+            frame.due.map_or(true, |due| {
+                due <= present_at || due > present_at + FRAME_PACING_MAX_WAIT
+            })
+        }) {
+            let frame = queue.pop_front()?;
+            if let Some(stale) = latest.replace(frame) {
+                self.recycle_buffer(stale.pixels);
+                self.dropped_frames.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        latest
+    }
+
+    fn next_frame_due(&self) -> Option<Instant> {
+        self.frame_queue.lock().front().and_then(|frame| frame.due)
+    }
+
     fn clear_frames(&self) {
         let mut queue = self.frame_queue.lock();
         while let Some(stale) = queue.pop_front() {
@@ -1266,7 +1312,24 @@ fn track_infos_from_stream_collection(
     tracks
 }
 
-fn process_video_sample(sample: gst::Sample, state: &VideoState) {
+/// When `sample` is due on screen: its running time plus the sink's render delay on the
+/// pipeline clock, translated to an [`Instant`]. Local files play without pipeline latency.
+fn sample_presentation_due(sink: &gst_app::AppSink, sample: &gst::Sample) -> Option<Instant> {
+    let pts = sample.buffer()?.pts()?;
+    let segment = sample.segment()?.downcast_ref::<gst::ClockTime>()?;
+    let running_time = segment.to_running_time(pts)?;
+    let due = sink.base_time()? + running_time + sink.render_delay();
+    let now_clock = sink.clock()?.time()?;
+    let now = Instant::now();
+    Some(if due >= now_clock {
+        now + Duration::from_nanos((due - now_clock).nseconds())
+    } else {
+        now.checked_sub(Duration::from_nanos((now_clock - due).nseconds()))
+            .unwrap_or(now)
+    })
+}
+
+fn process_video_sample(sample: gst::Sample, state: &VideoState, due: Option<Instant>) {
     let Some(buffer) = sample.buffer() else {
         return;
     };
@@ -1325,6 +1388,7 @@ fn process_video_sample(sample: gst::Sample, state: &VideoState) {
         width,
         height,
         pts: buffer.pts().map(|pts| Duration::from_nanos(pts.nseconds())),
+        due,
     };
 
     state.push_frame(frame);
@@ -1350,6 +1414,51 @@ pub struct VideoPlayer {
     subtitle_selection: VideoSubtitleSelection,
     stream_collection: Option<gst::StreamCollection>,
     selected_stream_ids: Vec<String>,
+    /// End of stream was reached and no seek has happened since.
+    ended: bool,
+    /// Loudness normalization factor on top of the user volume, once one was applied.
+    normalization_gain: Option<f64>,
+    /// Title and artist tags, once the stream has reported them.
+    tag_title: Option<String>,
+    tag_artist: Option<String>,
+    /// Position the pipeline was at when [`suspend`](Self::suspend) released it.
+    suspended_at: Option<Duration>,
+    /// Frames are handed over early and shown on the refresh closest to their deadline.
+    frame_pacing: bool,
+    presented_frames: u64,
+    late_frames: u64,
+    /// `None` when the `deinterlace` element is not installed.
+    deinterlace: Option<gst::Element>,
+    deinterlace_mode: DeinterlaceMode,
+    /// Rotates frames by `user_turns`, after the stream's own orientation is applied.
+    /// `None` when the `videoflip` element is not installed.
+    user_flip: Option<gst::Element>,
+    /// Clockwise quarter turns from the stream's `image-orientation` tag.
+    orientation_turns: u8,
+    /// Clockwise quarter turns added with the rotate actions.
+    user_turns: u8,
+    /// The displayed orientation changed since [`Self::take_orientation_changed`].
+    orientation_changed: bool,
+}
+
+/// Clockwise quarter turns in an `image-orientation` tag value. Mirrored orientations count
+/// only their rotation.
+fn orientation_tag_quarter_turns(value: &str) -> u8 {
+    match value.trim_start_matches("flip-") {
+        "rotate-90" => 1,
+        "rotate-180" => 2,
+        "rotate-270" => 3,
+        _ => 0,
+    }
+}
+
+/// Swaps `(width, height)` when `quarter_turns` is odd.
+fn rotated_dimensions((width, height): (u32, u32), quarter_turns: u8) -> (u32, u32) {
+    if quarter_turns % 2 == 1 {
+        (height, width)
+    } else {
+        (width, height)
+    }
 }
 
 impl VideoPlayer {
@@ -1405,10 +1514,9 @@ impl VideoPlayer {
         Self::ensure_init()?;
 
         // Build a correct file:// URI (including percent-encoding for spaces, etc.).
-        // Using a raw `file:///C:/path with spaces.mp4` string is not a valid URI.
-        let uri = gst::glib::filename_to_uri(path, None)
-            .map_err(|e| format!("Failed to build file URI for {:?}: {}", path, e))?
-            .to_string();
+        // Using a raw `file:///C:/path with spaces.mp4` string is not a valid URI. URLs are
+        // handed over as they are and streamed.
+        let uri = crate::remote::media_uri(path)?;
 
         // Create the pipeline.
         // Prefer `playbin` first because its legacy track-selection properties are more stable
@@ -1447,6 +1555,23 @@ Ensure your GStreamer installation includes the playback elements (usually from
         // Create appsink for video frames.
         // Explicitly request sRGB RGBA output. This nudges GStreamer into producing full-range RGB
         // and avoids washed-out output when input colorimetry/range metadata is incomplete.
+        // The output size is fixed before rotation, so with rotation elements in the bin it goes
+        // on a capsfilter ahead of them and the appsink only pins the format.
+        let (orientation_flip, user_flip) = if gst::ElementFactory::find("videoflip").is_some() {
+            (
+                gst::ElementFactory::make("videoflip")
+                    .property_from_str("video-direction", "auto")
+                    .build()
+                    .ok(),
+                gst::ElementFactory::make("videoflip").build().ok(),
+            )
+        } else {
+            (None, None)
+        };
+        let flips: Vec<gst::Element> = orientation_flip
+            .into_iter()
+            .chain(user_flip.clone())
+            .collect();
         let video_caps_string = match output_dimensions {
             Some((width, height)) if width > 0 && height > 0 => format!(
                 "video/x-raw,format=RGBA,colorimetry=sRGB,width={},height={},pixel-aspect-ratio=1/1",
@@ -1454,6 +1579,17 @@ Ensure your GStreamer installation includes the playback elements (usually from
             ),
             _ => "video/x-raw,format=RGBA,colorimetry=sRGB".to_string(),
         };
+        let (video_caps_string, size_filter) = if flips.is_empty() {
+            (video_caps_string, None)
+        } else {
+            let size_caps = gst::Caps::from_str(&video_caps_string)
+                .map_err(|e| format!("Failed to create video caps: {}", e))?;
+            let filter = gst::ElementFactory::make("capsfilter")
+                .property("caps", &size_caps)
+                .build()
+                .map_err(|e| format!("Failed to create capsfilter: {}", e))?;
+            ("video/x-raw,format=RGBA".to_string(), Some(filter))
+        };
         let video_caps = gst::Caps::from_str(&video_caps_string)
             .map_err(|e| format!("Failed to create video caps: {}", e))?;
         let appsink = gst_app::AppSink::builder()
@@ -1475,29 +1611,53 @@ Ensure your GStreamer installation includes the playback elements (usually from
             .build()
             .map_err(|e| format!("Failed to create videoconvert: {}", e))?;
 
-        let first_video_element = if output_dimensions.is_some() {
-            let videoscale = gst::ElementFactory::make("videoscale")
-                .build()
-                .map_err(|e| format!("Failed to create videoscale: {}", e))?;
-
-            video_bin
-                .add_many([&videoscale, &videoconvert, appsink.upcast_ref()])
-                .map_err(|e| format!("Failed to add elements to bin: {}", e))?;
-
-            gst::Element::link_many([&videoscale, &videoconvert, appsink.upcast_ref()])
-                .map_err(|e| format!("Failed to link video elements: {}", e))?;
-
-            videoscale
+        let videoscale = if output_dimensions.is_some() {
+            Some(
+                gst::ElementFactory::make("videoscale")
+                    .build()
+                    .map_err(|e| format!("Failed to create videoscale: {}", e))?,
+            )
         } else {
-            video_bin
-                .add_many([&videoconvert, appsink.upcast_ref()])
-                .map_err(|e| format!("Failed to add elements to bin: {}", e))?;
-
-            gst::Element::link_many([&videoconvert, appsink.upcast_ref()])
-                .map_err(|e| format!("Failed to link video elements: {}", e))?;
-
-            videoconvert.clone()
+            None
         };
+        // Deinterlacing works on the decoded fields, so it goes ahead of scaling. It is always
+        // in the bin so `deinterlace` can change while playing; otherwise it passes frames through.
+        let deinterlace = gst::ElementFactory::make("deinterlace")
+            .property_from_str("mode", "auto")
+            .build()
+            .ok();
+        if let Some(deinterlace) = &deinterlace {
+            let has_yadif = deinterlace
+                .find_property("method")
+                .and_then(|pspec| gst::glib::EnumClass::with_type(pspec.value_type()))
+                .is_some_and(|methods| methods.value_by_nick("yadif").is_some());
+            if has_yadif {
+                deinterlace.set_property_from_str("method", "yadif");
+            }
+        }
+        let deinterlace_stage: Vec<gst::Element> = match &deinterlace {
+            Some(deinterlace) => vec![
+                gst::ElementFactory::make("videoconvert")
+                    .build()
+                    .map_err(|e| format!("Failed to create videoconvert: {}", e))?,
+                deinterlace.clone(),
+            ],
+            None => Vec::new(),
+        };
+        let video_elements: Vec<gst::Element> = deinterlace_stage
+            .into_iter()
+            .chain(videoscale)
+            .chain([videoconvert])
+            .chain(size_filter)
+            .chain(flips)
+            .chain([appsink.clone().upcast()])
+            .collect();
+        video_bin
+            .add_many(&video_elements)
+            .map_err(|e| format!("Failed to add elements to bin: {}", e))?;
+        gst::Element::link_many(&video_elements)
+            .map_err(|e| format!("Failed to link video elements: {}", e))?;
+        let first_video_element = &video_elements[0];
 
         // Create ghost pad for the bin.
         let pad = first_video_element
@@ -1561,6 +1721,7 @@ Ensure your GStreamer installation includes the playback elements (usually from
             video_height: AtomicU32::new(0),
             seek_in_progress: AtomicBool::new(false),
             needs_range_expand: AtomicI8::new(RANGE_EXPAND_UNKNOWN),
+            dropped_frames: AtomicU64::new(0),
         });
 
         // Set up appsink callbacks.
@@ -1574,13 +1735,14 @@ Ensure your GStreamer installation includes the playback elements (usually from
                 .new_sample(move |sink| {
                     let sample = sink.pull_sample().map_err(|_| gst::FlowError::Eos)?;
                     if !state_clone.seek_in_progress() {
-                        process_video_sample(sample, state_clone.as_ref());
+                        let due = sample_presentation_due(sink, &sample);
+                        process_video_sample(sample, state_clone.as_ref(), due);
                     }
                     Ok(gst::FlowSuccess::Ok)
                 })
                 .new_preroll(move |sink| {
                     if let Ok(sample) = sink.pull_preroll() {
-                        process_video_sample(sample, state_clone_preroll.as_ref());
+                        process_video_sample(sample, state_clone_preroll.as_ref(), None);
                     }
                     Ok(gst::FlowSuccess::Ok)
                 })
@@ -1606,6 +1768,20 @@ Ensure your GStreamer installation includes the playback elements (usually from
             subtitle_selection: VideoSubtitleSelection::Off,
             stream_collection: None,
             selected_stream_ids: Vec::new(),
+            ended: false,
+            normalization_gain: None,
+            tag_title: None,
+            tag_artist: None,
+            suspended_at: None,
+            frame_pacing: false,
+            presented_frames: 0,
+            late_frames: 0,
+            deinterlace,
+            deinterlace_mode: DeinterlaceMode::Auto,
+            user_flip,
+            orientation_turns: 0,
+            user_turns: 0,
+            orientation_changed: false,
         };
 
         let mut player = player;
@@ -1624,6 +1800,7 @@ Ensure your GStreamer installation includes the playback elements (usually from
 
     /// Start playback
     pub fn play(&mut self) -> Result<(), String> {
+        self.resume_suspended()?;
         if let Err(err) = self.pipeline.set_state(gst::State::Playing) {
             // State-change errors are often just a symptom. Try to extract the *real* reason
             // from the bus (missing demuxer/decoder, invalid URI, missing device/sink, etc.).
@@ -1690,6 +1867,7 @@ Ensure your GStreamer installation includes the playback elements (usually from
 
     /// Pause playback
     pub fn pause(&mut self) -> Result<(), String> {
+        self.resume_suspended()?;
         self.pipeline
             .set_state(gst::State::Paused)
             .map_err(|e| format!("Failed to pause playback: {}", e))?;
@@ -1699,6 +1877,47 @@ Ensure your GStreamer installation includes the playback elements (usually from
         Ok(())
     }
 
+    /// Drop a paused pipeline to READY, releasing its decoders, buffers and audio device.
+    /// The frame on screen stays; [`play`](Self::play) and seeks pick up at the same position.
+    pub fn suspend(&mut self) {
+        if self.is_playing || self.suspended_at.is_some() {
+            return;
+        }
+        let position = self.displayed_position().unwrap_or_default();
+        if self.pipeline.set_state(gst::State::Ready).is_ok() {
+            self.suspended_at = Some(position);
+        }
+    }
+
+    pub fn is_suspended(&self) -> bool {
+        self.suspended_at.is_some()
+    }
+
+    /// Bring a suspended pipeline back to PAUSED at the position it was suspended at.
+    fn resume_suspended(&mut self) -> Result<(), String> {
+        let Some(position) = self.suspended_at.take() else {
+            return Ok(());
+        };
+        self.pipeline
+            .set_state(gst::State::Paused)
+            .map_err(|e| format!("Failed to resume playback: {}", e))?;
+        self.seek_to_clock_time(
+            Self::duration_to_clock_time(position),
+            VideoSeekMode::Accurate,
+        )
+    }
+
+    /// Pause at the first frame and wait up to `timeout` for it to be decoded, so a later
+    /// [`play`](Self::play) starts without the pipeline's startup delay.
+    pub fn preroll(&mut self, timeout: Duration) -> Result<(), String> {
+        self.pause()?;
+        self.pipeline
+            .state(Self::duration_to_clock_time(timeout))
+            .0
+            .map(|_| ())
+            .map_err(|e| format!("Failed to preroll: {}", e))
+    }
+
     /// Toggle play/pause
     pub fn toggle_play_pause(&mut self) -> Result<(), String> {
         if self.is_playing {
@@ -1770,13 +1989,13 @@ Ensure your GStreamer installation includes the playback elements (usually from
 
         if let Some(sample) = self.video_sink.try_pull_preroll(timeout_clock_time) {
             self.state.clear_frames();
-            process_video_sample(sample, self.state.as_ref());
+            process_video_sample(sample, self.state.as_ref(), None);
             return;
         }
 
         if let Some(sample) = self.video_sink.try_pull_preroll(gst::ClockTime::ZERO) {
             self.state.clear_frames();
-            process_video_sample(sample, self.state.as_ref());
+            process_video_sample(sample, self.state.as_ref(), None);
         }
     }
 
@@ -1785,6 +2004,8 @@ Ensure your GStreamer installation includes the playback elements (usually from
         target: gst::ClockTime,
         mode: VideoSeekMode,
     ) -> Result<(), String> {
+        self.resume_suspended()?;
+        self.ended = false;
         self.state.begin_seek();
         self.state.clear_frames();
         self.last_frame_pts = None;
@@ -1842,6 +2063,9 @@ Ensure your GStreamer installation includes the playback elements (usually from
 
     /// Get current playback position in seconds
     pub fn position(&self) -> Option<Duration> {
+        if self.suspended_at.is_some() {
+            return self.suspended_at;
+        }
         self.pipeline
             .query_position::<gst::ClockTime>()
             .map(|pos| Duration::from_nanos(pos.nseconds()))
@@ -1886,6 +2110,16 @@ Ensure your GStreamer installation includes the playback elements (usually from
         self.volume
     }
 
+    /// Scales the output by `gain` on top of the user volume, for loudness normalization.
+    pub fn set_normalization_gain(&mut self, gain: f64) {
+        self.normalization_gain = Some(gain);
+        self.apply_volume();
+    }
+
+    pub fn normalization_gain(&self) -> Option<f64> {
+        self.normalization_gain
+    }
+
     fn legacy_audio_tracks(&self) -> Vec<VideoTrackInfo> {
         let Some(track_count) =
             get_optional_i32_or_u32_property(self.pipeline.upcast_ref(), "n-audio")
@@ -2093,6 +2327,7 @@ Ensure your GStreamer installation includes the playback elements (usually from
     }
 
     pub fn set_audio_track(&mut self, index: i32) -> Result<(), String> {
+        self.resume_suspended()?;
         let uses_legacy_selection = !self.legacy_audio_tracks().is_empty();
 
         if index < 0 {
@@ -2204,6 +2439,7 @@ Ensure your GStreamer installation includes the playback elements (usually from
         &mut self,
         selection: VideoSubtitleSelection,
     ) -> Result<(), String> {
+        self.resume_suspended()?;
         let uses_legacy_selection = !self.legacy_embedded_subtitle_tracks().is_empty();
 
         match &selection {
@@ -2306,9 +2542,78 @@ Ensure your GStreamer installation includes the playback elements (usually from
             let effective_volume = if self.is_muted || self.audio_track_disabled {
                 0.0
             } else {
-                self.volume
+                self.volume * self.normalization_gain.unwrap_or(1.0)
             };
-            vol.set_property("volume", effective_volume);
+            vol.set_property("volume", effective_volume.clamp(0.0, 10.0));
+        }
+    }
+
+    /// Turns frame pacing on or off. With pacing the sink delivers frames
+    /// [`FRAME_PACING_LEAD`] ahead of time and [`get_paced_frame`](Self::get_paced_frame)
+    /// holds each back until the display refresh it belongs to, so a 24 fps video on a 60 Hz
+    /// display keeps an even 3:2 cadence instead of jittering with frame arrival.
+    /// Switches deinterlacing; takes effect on the next frame.
+    pub fn set_deinterlace(&mut self, mode: DeinterlaceMode) {
+        if self.deinterlace_mode == mode {
+            return;
+        }
+        self.deinterlace_mode = mode;
+        if let Some(deinterlace) = &self.deinterlace {
+            let nick = match mode {
+                DeinterlaceMode::Auto => "auto",
+                DeinterlaceMode::On => "interlaced",
+                DeinterlaceMode::Off => "disabled",
+            };
+            deinterlace.set_property_from_str("mode", nick);
+        }
+    }
+
+    pub fn set_frame_pacing(&mut self, enabled: bool) {
+        if self.frame_pacing == enabled {
+            return;
+        }
+        self.frame_pacing = enabled;
+        let lead = if enabled {
+            -(FRAME_PACING_LEAD.as_nanos() as i64)
+        } else {
+            0
+        };
+        self.video_sink.set_ts_offset(lead);
+    }
+
+    /// The frame to show on the display refresh at `present_at`, `refresh` apart from the
+    /// next one. Without pacing, or while paused, this is the newest frame.
+    pub fn get_paced_frame(
+        &mut self,
+        present_at: Instant,
+        refresh: Duration,
+    ) -> Option<VideoFrame> {
+        if !self.frame_pacing || !self.is_playing {
+            return self.get_frame();
+        }
+        let frame = self.state.pop_due_frame(present_at)?;
+        if frame.pts.is_some() {
+            self.last_frame_pts = frame.pts;
+        }
+        self.presented_frames += 1;
+        if frame.due.is_some_and(|due| present_at > due + refresh) {
+            self.late_frames += 1;
+        }
+        Some(frame)
+    }
+
+    /// When the next queued frame is due, with frame pacing on.
+    pub fn next_frame_due(&self) -> Option<Instant> {
+        self.frame_pacing
+            .then(|| self.state.next_frame_due())
+            .flatten()
+    }
+
+    pub fn frame_pacing_stats(&self) -> FramePacingStats {
+        FramePacingStats {
+            presented: self.presented_frames,
+            dropped: self.state.dropped_frames.load(Ordering::Relaxed),
+            late: self.late_frames,
         }
     }
 
@@ -2321,13 +2626,14 @@ Ensure your GStreamer installation includes the playback elements (usually from
             if frame.pts.is_some() {
                 self.last_frame_pts = frame.pts;
             }
+            self.presented_frames += 1;
             if self.original_width == 0
                 && self.original_height == 0
                 && frame.width > 0
                 && frame.height > 0
             {
-                self.original_width = frame.width;
-                self.original_height = frame.height;
+                (self.original_width, self.original_height) =
+                    rotated_dimensions((frame.width, frame.height), self.quarter_turns());
             }
             return Some(frame);
         }
@@ -2335,18 +2641,65 @@ Ensure your GStreamer installation includes the playback elements (usually from
         None
     }
 
-    /// Get video dimensions
+    /// Get video dimensions, as stored in the file (before any rotation)
     pub fn dimensions(&self) -> (u32, u32) {
         if self.original_width > 0 && self.original_height > 0 {
             (self.original_width, self.original_height)
         } else {
-            (
-                self.state.video_width.load(Ordering::Acquire),
-                self.state.video_height.load(Ordering::Acquire),
+            rotated_dimensions(
+                (
+                    self.state.video_width.load(Ordering::Acquire),
+                    self.state.video_height.load(Ordering::Acquire),
+                ),
+                self.quarter_turns(),
             )
         }
     }
 
+    /// Video dimensions as shown, with the stream orientation and manual rotation applied.
+    pub fn display_dimensions(&self) -> (u32, u32) {
+        rotated_dimensions(self.dimensions(), self.quarter_turns())
+    }
+
+    /// Clockwise quarter turns frames are shown with.
+    pub fn quarter_turns(&self) -> u8 {
+        (self.orientation_turns + self.user_turns) % 4
+    }
+
+    /// Rotates the video by a quarter turn. Does nothing when `videoflip` is unavailable.
+    pub fn rotate(&mut self, clockwise: bool) {
+        let Some(flip) = &self.user_flip else {
+            return;
+        };
+        self.user_turns = (self.user_turns + if clockwise { 1 } else { 3 }) % 4;
+        let direction = match self.user_turns {
+            1 => "90r",
+            2 => "180",
+            3 => "90l",
+            _ => "identity",
+        };
+        flip.set_property_from_str("video-direction", direction);
+        self.orientation_changed = true;
+        self.redraw_paused_frame();
+    }
+
+    /// Delivers the current frame again while paused, e.g. after its texture was lost.
+    pub fn redraw_paused_frame(&mut self) {
+        if !self.is_playing {
+            // Paused frames are only redrawn by a new preroll.
+            let _ = self.seek_to_time_with_mode(
+                self.position().unwrap_or_default().as_secs_f64(),
+                VideoSeekMode::Accurate,
+            );
+        }
+    }
+
+    /// Whether the displayed orientation changed since the last call, from a rotation or
+    /// from the stream reporting its orientation.
+    pub fn take_orientation_changed(&mut self) -> bool {
+        std::mem::take(&mut self.orientation_changed)
+    }
+
     /// Check if video has ended
     pub fn is_eos(&mut self) -> bool {
         const EOS_BUS_MESSAGES_PER_TICK: usize = 64;
@@ -2359,7 +2712,28 @@ Ensure your GStreamer installation includes the playback elements (usually from
                 };
 
                 match msg.view() {
-                    gst::MessageView::Eos(_) => return true,
+                    gst::MessageView::Eos(_) => {
+                        self.ended = true;
+                        return true;
+                    }
+                    gst::MessageView::Tag(tag) => {
+                        let tags = tag.tags();
+                        if let Some(title) = tag_string_from_list::<gst::tags::Title>(&tags) {
+                            self.tag_title = Some(title);
+                        }
+                        if let Some(artist) = tag_string_from_list::<gst::tags::Artist>(&tags) {
+                            self.tag_artist = Some(artist);
+                        }
+                        if let Some(orientation) =
+                            tag_string_from_list::<gst::tags::ImageOrientation>(&tags)
+                        {
+                            let turns = orientation_tag_quarter_turns(&orientation);
+                            if self.user_flip.is_some() && turns != self.orientation_turns {
+                                self.orientation_turns = turns;
+                                self.orientation_changed = true;
+                            }
+                        }
+                    }
                     gst::MessageView::StreamCollection(collection) => {
                         self.stream_collection = Some(collection.stream_collection());
                     }
@@ -2396,6 +2770,22 @@ Ensure your GStreamer installation includes the playback elements (usually from
         false
     }
 
+    /// The stream's title tag, if it has one.
+    pub fn tag_title(&self) -> Option<&str> {
+        self.tag_title.as_deref()
+    }
+
+    /// The stream's artist tag, if it has one.
+    pub fn tag_artist(&self) -> Option<&str> {
+        self.tag_artist.as_deref()
+    }
+
+    /// Whether playback sits at the end. Unlike [`Self::is_eos`], which reports the end
+    /// once, this stays set until the next seek.
+    pub fn has_ended(&self) -> bool {
+        self.ended
+    }
+
     /// Restart playback from the beginning
     pub fn restart(&mut self) -> Result<(), String> {
         self.seek_to_time(0.0)?;
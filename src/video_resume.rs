@@ -0,0 +1,212 @@
+//! Remembered playback positions, so a video reopened later can continue where it was left.
+//!
+//! Positions are keyed by path and file size: a file replaced by another of a different size
+//! starts over. They are buffered in memory while a video plays and written to
+//! `video_resume.redb` in the local app data directory when playback stops and when the
+//! viewer closes. Videos watched to the end are forgotten, and the first write of a session
+//! drops entries not updated for [`RETENTION`] and the oldest beyond [`MAX_ENTRIES`].
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hashbrown::HashMap;
+use parking_lot::Mutex;
+use redb::{Database, DatabaseError, ReadableTable, TableDefinition};
+
+use crate::storage;
+
+/// Position in seconds and when it was saved, in seconds since the Unix epoch, by key.
+const POSITIONS_TABLE: TableDefinition<&str, (f64, u64)> = TableDefinition::new("video_positions");
+const STORE_FILE_NAME: &str = "video_resume.redb";
+/// Videos shorter than this always start from the beginning.
+const MIN_DURATION: Duration = Duration::from_secs(60);
+/// Positions this close to the start are not worth resuming.
+const MIN_POSITION: Duration = Duration::from_secs(10);
+/// A video stopped this close to its end counts as watched.
+const END_MARGIN: Duration = Duration::from_secs(15);
+/// Entries not updated for this long are dropped.
+const RETENTION: Duration = Duration::from_secs(180 * 86_400);
+const MAX_ENTRIES: usize = 2_000;
+
+/// Position in seconds and when it was saved, as stored in [`POSITIONS_TABLE`].
+type SavedPosition = (f64, u64);
+
+/// Positions not written yet; `None` forgets the entry.
+static PENDING: OnceLock<Mutex<HashMap<String, Option<SavedPosition>>>> = OnceLock::new();
+static STORE: OnceLock<Option<Arc<Mutex<Database>>>> = OnceLock::new();
+static CLEANED_UP: AtomicBool = AtomicBool::new(false);
+
+fn pending() -> &'static Mutex<HashMap<String, Option<SavedPosition>>> {
+    PENDING.get_or_init(Mutex::default)
+}
+
+fn store() -> Option<&'static Arc<Mutex<Database>>> {
+    STORE
+        .get_or_init(|| {
+            let path = default_store_path()?;
+            open_database(&path).map(|db| Arc::new(Mutex::new(db)))
+        })
+        .as_ref()
+}
+
+fn entry_key(path: &Path) -> Option<String> {
+    let size = std::fs::metadata(path).ok()?.len();
+    Some(format!("{}#{size}", path.to_string_lossy()))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// The position worth saving for a video of `duration` stopped at `position`; `None` when
+/// it should start over next time.
+fn resumable_position(position: Duration, duration: Duration) -> Option<Duration> {
+    (duration >= MIN_DURATION
+        && position >= MIN_POSITION
+        && position.saturating_add(END_MARGIN) < duration)
+        .then_some(position)
+}
+
+/// Remembers that `path` is at `position` of `duration`. Cheap enough to call while playing;
+/// nothing is written until [`flush`].
+pub fn record(path: &Path, position: Duration, duration: Duration) {
+    let Some(key) = entry_key(path) else {
+        return;
+    };
+    let entry =
+        resumable_position(position, duration).map(|position| (position.as_secs_f64(), now_secs()));
+    pending().lock().insert(key, entry);
+}
+
+/// Where `path` was left last time, if it is worth resuming.
+pub fn lookup(path: &Path) -> Option<Duration> {
+    let key = entry_key(path)?;
+    let entry = match pending().lock().get(&key) {
+        Some(entry) => *entry,
+        None => {
+            let db = store()?.lock();
+            let read_txn = db.begin_read().ok()?;
+            let table = read_txn.open_table(POSITIONS_TABLE).ok()?;
+            let value = table.get(key.as_str()).ok()??.value();
+            Some(value)
+        }
+    };
+    let (secs, _) = entry?;
+    (secs.is_finite() && secs > 0.0).then(|| Duration::from_secs_f64(secs))
+}
+
+/// Writes buffered positions to disk, on a worker.
+pub fn flush_in_background() {
+    if pending().lock().is_empty() {
+        return;
+    }
+    crate::async_runtime::spawn_blocking_or_thread("video-resume-flush", flush);
+}
+
+/// Writes buffered positions to disk. Called when playback stops and when the viewer closes.
+pub fn flush() {
+    let entries = std::mem::take(&mut *pending().lock());
+    if entries.is_empty() {
+        return;
+    }
+    let Some(store) = store() else {
+        return;
+    };
+    let cleanup = !CLEANED_UP.swap(true, Ordering::Relaxed);
+    if write(&store.lock(), &entries, cleanup).is_none() {
+        tracing::warn!("failed to save video resume positions");
+    }
+}
+
+fn write(
+    db: &Database,
+    entries: &HashMap<String, Option<SavedPosition>>,
+    cleanup: bool,
+) -> Option<()> {
+    let write_txn = db.begin_write().ok()?;
+    {
+        let mut table = write_txn.open_table(POSITIONS_TABLE).ok()?;
+        for (key, entry) in entries {
+            match entry {
+                Some(value) => table.insert(key.as_str(), *value).ok()?,
+                None => table.remove(key.as_str()).ok()?,
+            };
+        }
+        if cleanup {
+            let mut saved: Vec<(String, u64)> = Vec::new();
+            for entry in table.iter().ok()? {
+                let (key, value) = entry.ok()?;
+                saved.push((key.value().to_string(), value.value().1));
+            }
+            for key in stale_keys(saved, now_secs()) {
+                table.remove(key.as_str()).ok()?;
+            }
+        }
+    }
+    write_txn.commit().ok()
+}
+
+/// Keys of entries saved before the retention period, plus the oldest beyond
+/// [`MAX_ENTRIES`].
+fn stale_keys(mut saved: Vec<(String, u64)>, now: u64) -> Vec<String> {
+    let cutoff = now.saturating_sub(RETENTION.as_secs());
+    saved.sort_by_key(|(_, saved_at)| std::cmp::Reverse(*saved_at));
+    saved
+        .into_iter()
+        .enumerate()
+        .filter(|(rank, (_, saved_at))| *rank >= MAX_ENTRIES || *saved_at < cutoff)
+        .map(|(_, (key, _))| key)
+        .collect()
+}
+
+fn default_store_path() -> Option<PathBuf> {
+    storage::data_file(STORE_FILE_NAME)
+}
+
+fn open_database(path: &Path) -> Option<Database> {
+    match Database::create(path) {
+        Ok(db) => Some(db),
+        Err(DatabaseError::Storage(redb::StorageError::Corrupted(_))) => {
+            let _ = std::fs::remove_file(path);
+            Database::create(path).ok()
+        }
+        Err(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{resumable_position, stale_keys, MAX_ENTRIES, RETENTION};
+
+    #[test]
+    fn only_positions_inside_longer_videos_are_kept() {
+        let hour = Duration::from_secs(3600);
+        let middle = Duration::from_secs(1200);
+        assert_eq!(resumable_position(middle, hour), Some(middle));
+        assert_eq!(resumable_position(Duration::from_secs(3), hour), None);
+        assert_eq!(resumable_position(Duration::from_secs(3590), hour), None);
+        assert_eq!(
+            resumable_position(Duration::from_secs(20), Duration::from_secs(45)),
+            None
+        );
+    }
+
+    #[test]
+    fn stale_entries_are_dropped_oldest_first() {
+        let now = RETENTION.as_secs() * 2;
+        let mut saved = vec![("old".to_string(), 1), ("fresh".to_string(), now)];
+        saved.extend((0..MAX_ENTRIES).map(|i| (format!("recent{i}"), now - 1 - i as u64)));
+        let mut stale = stale_keys(saved, now);
+        stale.sort();
+        assert_eq!(
+            stale,
+            vec![format!("recent{}", MAX_ENTRIES - 1), "old".to_string()]
+        );
+    }
+}
@@ -0,0 +1,386 @@
+//! Trimming videos: the range between the in and out marks is written to a new file by
+//! ffmpeg.
+//!
+//! [`TrimMode::Copy`] copies the streams without re-encoding, which is fast and lossless but
+//! can only cut at keyframes, so the clip may start a little before the in mark.
+//! [`TrimMode::Mp4`] re-encodes to H.264/AAC and cuts exactly. ffmpeg runs as a child process
+//! in a background job and reports how far it got through `-progress`; cancelling kills it.
+
+use std::ffi::OsString;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use crate::config::TrimMode;
+
+/// Shortest range that can be exported.
+pub const MIN_TRIM_LENGTH: Duration = Duration::from_millis(100);
+/// A mark set again this close to where it already is is cleared instead.
+const MARK_CLEAR_DISTANCE: Duration = Duration::from_millis(250);
+/// Lines of ffmpeg's error output kept for the failure message.
+const ERROR_TAIL_LINES: usize = 4;
+
+/// In and out marks set on a video.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrimMarks {
+    pub path: PathBuf,
+    pub start: Option<Duration>,
+    pub end: Option<Duration>,
+}
+
+impl TrimMarks {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            start: None,
+            end: None,
+        }
+    }
+
+    /// Sets the in mark at `position`, or clears it when it is already there. An out mark
+    /// before it is cleared.
+    pub fn toggle_start(&mut self, position: Duration) {
+        toggle_mark(&mut self.start, position);
+        if self
+            .start
+            .zip(self.end)
+            .is_some_and(|(start, end)| end <= start)
+        {
+            self.end = None;
+        }
+    }
+
+    /// Sets the out mark at `position`, or clears it when it is already there. An in mark
+    /// after it is cleared.
+    pub fn toggle_end(&mut self, position: Duration) {
+        toggle_mark(&mut self.end, position);
+        if self
+            .start
+            .zip(self.end)
+            .is_some_and(|(start, end)| end <= start)
+        {
+            self.start = None;
+        }
+    }
+
+    /// The marked range of a video of `duration`; missing marks stand for its start and end.
+    pub fn range(&self, duration: Duration) -> (Duration, Duration) {
+        let start = self.start.unwrap_or(Duration::ZERO).min(duration);
+        let end = self.end.unwrap_or(duration).min(duration);
+        (start, end)
+    }
+}
+
+fn toggle_mark(mark: &mut Option<Duration>, position: Duration) {
+    *mark = match *mark {
+        Some(current) if current.max(position) - current.min(position) < MARK_CLEAR_DISTANCE => {
+            None
+        }
+        _ => Some(position),
+    };
+}
+
+/// `<stem>.trim.<ext>` next to `source`: the source extension for stream copies, `mp4` for
+/// re-encodes. Never overwrites.
+pub fn export_path(source: &Path, mode: TrimMode) -> PathBuf {
+    let extension = match mode {
+        TrimMode::Copy => source
+            .extension()
+            .map(|ext| ext.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "mp4".to_string()),
+        TrimMode::Mp4 => "mp4".to_string(),
+    };
+    crate::image_loader::sibling_export_path(source, "trim", &extension)
+}
+
+fn seconds_arg(time: Duration) -> OsString {
+    format!("{:.3}", time.as_secs_f64()).into()
+}
+
+/// ffmpeg arguments that write `start..end` of `source` to `target`.
+fn ffmpeg_args(
+    source: &Path,
+    start: Duration,
+    end: Duration,
+    mode: TrimMode,
+    target: &Path,
+) -> Vec<OsString> {
+    let mut args: Vec<OsString> = ["-hide_banner", "-nostdin", "-y", "-ss"]
+        .into_iter()
+        .map(OsString::from)
+        .collect();
+    args.push(seconds_arg(start));
+    args.push("-i".into());
+    args.push(source.into());
+    args.push("-t".into());
+    args.push(seconds_arg(end.saturating_sub(start)));
+    let codec_args: &[&str] = match mode {
+        TrimMode::Copy => &[
+            "-map",
+            "0:v?",
+            "-map",
+            "0:a?",
+            "-map",
+            "0:s?",
+            "-c",
+            "copy",
+            "-avoid_negative_ts",
+            "make_zero",
+        ],
+        TrimMode::Mp4 => &[
+            "-map",
+            "0:v?",
+            "-map",
+            "0:a?",
+            "-c:v",
+            "libx264",
+            "-preset",
+            "medium",
+            "-crf",
+            "20",
+            "-pix_fmt",
+            "yuv420p",
+            "-c:a",
+            "aac",
+            "-b:a",
+            "192k",
+            "-movflags",
+            "+faststart",
+        ],
+    };
+    args.extend(codec_args.iter().map(OsString::from));
+    args.extend(["-progress", "pipe:1", "-nostats"].map(OsString::from));
+    args.push(target.into());
+    args
+}
+
+/// Time written so far, from one line of ffmpeg's `-progress` output.
+fn parse_progress_line(line: &str) -> Option<Duration> {
+    let (key, value) = line.trim().split_once('=')?;
+    // `out_time_ms` is in microseconds too; older builds only print that one.
+    match key {
+        "out_time_us" | "out_time_ms" => value.parse::<u64>().ok().map(Duration::from_micros),
+        _ => None,
+    }
+}
+
+/// One run of ffmpeg: `start..end` of `source` written to `target`.
+#[derive(Debug, Clone)]
+pub struct TrimJob {
+    /// ffmpeg executable.
+    pub ffmpeg: PathBuf,
+    pub source: PathBuf,
+    pub start: Duration,
+    pub end: Duration,
+    pub mode: TrimMode,
+    pub target: PathBuf,
+}
+
+/// Runs `job`, calling `progress` with the fraction done. A cancelled or failed run leaves
+/// no file behind.
+pub fn trim_video(
+    job: &TrimJob,
+    cancelled: &AtomicBool,
+    progress: impl Fn(f32),
+) -> Result<(), String> {
+    let TrimJob {
+        ffmpeg,
+        source,
+        start,
+        end,
+        mode,
+        target,
+    } = job;
+    let (start, end) = (*start, *end);
+    if end < start + MIN_TRIM_LENGTH {
+        return Err("The selected range is empty".to_string());
+    }
+    let mut command = Command::new(ffmpeg);
+    command
+        .args(ffmpeg_args(source, start, end, *mode, target))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    let mut child = command.spawn().map_err(|err| {
+        if err.kind() == std::io::ErrorKind::NotFound {
+            format!(
+                "ffmpeg was not found ({}); install it or set ffmpeg_path",
+                ffmpeg.display()
+            )
+        } else {
+            format!("Failed to start ffmpeg: {err}")
+        }
+    })?;
+
+    // Drained on its own thread so a chatty ffmpeg never blocks on a full pipe.
+    let (stderr_tx, stderr_rx) = crossbeam_channel::bounded(1);
+    if let Some(mut stderr) = child.stderr.take() {
+        crate::async_runtime::spawn_blocking_or_thread("ffmpeg-stderr", move || {
+            let mut text = String::new();
+            let _ = stderr.read_to_string(&mut text);
+            let _ = stderr_tx.send(text);
+        });
+    }
+
+    let length = end - start;
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if cancelled.load(Ordering::Relaxed) {
+                let _ = child.kill();
+                break;
+            }
+            if let Some(done) = parse_progress_line(&line) {
+                progress((done.as_secs_f32() / length.as_secs_f32()).clamp(0.0, 1.0));
+            }
+        }
+    }
+    let status = child.wait().map_err(|err| err.to_string());
+    let result = if cancelled.load(Ordering::Relaxed) {
+        Err("Cancelled".to_string())
+    } else {
+        match status {
+            Ok(status) if status.success() => Ok(()),
+            Ok(status) => {
+                let output = stderr_rx.recv().unwrap_or_default();
+                let tail: Vec<&str> = output
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .rev()
+                    .take(ERROR_TAIL_LINES)
+                    .collect();
+                let tail: Vec<&str> = tail.into_iter().rev().collect();
+                Err(format!("ffmpeg failed ({status}): {}", tail.join(" / ")))
+            }
+            Err(err) => Err(err),
+        }
+    };
+    if result.is_err() {
+        let _ = std::fs::remove_file(target);
+    }
+    result
+}
+
+/// State of the trim dialog while it is open.
+pub struct TrimDialog {
+    pub path: PathBuf,
+    pub duration: Duration,
+    /// Range to export, in seconds.
+    pub start_secs: f32,
+    pub end_secs: f32,
+    pub mode: TrimMode,
+    /// The last export started from this dialog.
+    pub job: Option<crate::jobs::JobId>,
+}
+
+impl TrimDialog {
+    pub fn new(marks: &TrimMarks, duration: Duration, mode: TrimMode) -> Self {
+        let (start, end) = marks.range(duration);
+        Self {
+            path: marks.path.clone(),
+            duration,
+            start_secs: start.as_secs_f32(),
+            end_secs: end.as_secs_f32(),
+            mode,
+            job: None,
+        }
+    }
+
+    /// The range to export.
+    pub fn range(&self) -> (Duration, Duration) {
+        let seconds = |secs: f32| Duration::from_secs_f32(secs.max(0.0)).min(self.duration);
+        (seconds(self.start_secs), seconds(self.end_secs))
+    }
+
+    pub fn has_valid_range(&self) -> bool {
+        let (start, end) = self.range();
+        end >= start + MIN_TRIM_LENGTH
+    }
+
+    /// The export of the chosen range, written next to the source.
+    pub fn job(&self, ffmpeg: PathBuf) -> TrimJob {
+        let (start, end) = self.range();
+        TrimJob {
+            ffmpeg,
+            source: self.path.clone(),
+            start,
+            end,
+            mode: self.mode,
+            target: export_path(&self.path, self.mode),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    use super::{ffmpeg_args, parse_progress_line, TrimMarks};
+    use crate::config::TrimMode;
+
+    #[test]
+    fn ffmpeg_cuts_the_marked_range() {
+        let args = ffmpeg_args(
+            Path::new("in.mkv"),
+            Duration::from_millis(1500),
+            Duration::from_secs(4),
+            TrimMode::Copy,
+            Path::new("in.trim.mkv"),
+        );
+        let args: Vec<String> = args
+            .iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        let position = |flag: &str| args.iter().position(|arg| arg == flag).unwrap();
+        assert_eq!(args[position("-ss") + 1], "1.500");
+        assert_eq!(args[position("-t") + 1], "2.500");
+        assert_eq!(args[position("-c") + 1], "copy");
+        assert!(position("-ss") < position("-i"));
+        assert_eq!(args.last().map(String::as_str), Some("in.trim.mkv"));
+
+        let reencode = ffmpeg_args(
+            Path::new("in.mkv"),
+            Duration::ZERO,
+            Duration::from_secs(1),
+            TrimMode::Mp4,
+            Path::new("in.trim.mp4"),
+        );
+        assert!(reencode.contains(&OsString::from("libx264")));
+    }
+
+    #[test]
+    fn progress_and_marks() {
+        assert_eq!(
+            parse_progress_line("out_time_us=2500000\n"),
+            Some(Duration::from_millis(2500))
+        );
+        assert_eq!(parse_progress_line("out_time=00:00:02.500000"), None);
+        assert_eq!(parse_progress_line("progress=continue"), None);
+
+        let mut marks = TrimMarks::new(PathBuf::from("a.mp4"));
+        marks.toggle_end(Duration::from_secs(30));
+        assert_eq!(
+            marks.range(Duration::from_secs(20)),
+            (Duration::ZERO, Duration::from_secs(20))
+        );
+        marks.toggle_start(Duration::from_secs(40));
+        assert_eq!(
+            (marks.start, marks.end),
+            (Some(Duration::from_secs(40)), None)
+        );
+        marks.toggle_start(Duration::from_millis(40_100));
+        assert_eq!(marks.start, None);
+    }
+}
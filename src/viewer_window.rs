@@ -0,0 +1,395 @@
+//! Additional viewer windows (`new_window`, "Open in new window").
+//!
+//! Each window is an egui immediate viewport with its own image, zoom and pan, and its own
+//! copy of the folder list for left/right navigation. Config is snapshotted when the window
+//! opens; decoding goes through the same loader as the main window, so the static thumbnail
+//! cache is shared. Videos stay in the main window.
+//!
+//! With zoom sync on, zooming or panning one window moves the others to the same view, as a
+//! [`SyncedView`] that does not depend on window or image size.
+
+use std::path::{Path, PathBuf};
+
+use image::imageops::FilterType;
+
+use crate::image_loader::ImageFrame;
+
+/// Loader used by the worker thread; the main window passes its cache-aware loader.
+pub type FrameLoader = fn(&Path, &ViewerWindowSettings) -> Result<ImageFrame, String>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ViewerWindowSettings {
+    pub max_texture_side: u32,
+    pub downscale_filter: FilterType,
+    pub gif_filter: FilterType,
+    pub background: egui::Color32,
+}
+
+/// A view shared by synced windows: the displayed image width in points and the image point
+/// at the center of the view, in 0..1 image coordinates. Images of different resolutions but
+/// the same framing, such as a RAW and its JPEG, therefore line up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SyncedView {
+    pub width: f32,
+    pub center: egui::Vec2,
+}
+
+impl SyncedView {
+    /// The view of an image shown at `displayed` size with its center `offset` from the
+    /// center of the view.
+    pub fn from_offset(displayed: egui::Vec2, offset: egui::Vec2) -> Option<Self> {
+        if displayed.x <= 0.0 || displayed.y <= 0.0 {
+            return None;
+        }
+        Some(Self {
+            width: displayed.x,
+            center: egui::vec2(0.5 - offset.x / displayed.x, 0.5 - offset.y / displayed.y),
+        })
+    }
+
+    /// Offset of the image center from the view center that shows this view at `displayed`
+    /// size.
+    pub fn offset_for(&self, displayed: egui::Vec2) -> egui::Vec2 {
+        egui::vec2(
+            (0.5 - self.center.x) * displayed.x,
+            (0.5 - self.center.y) * displayed.y,
+        )
+    }
+
+    /// Equal within a fraction of a point, so rounding does not bounce views back and forth.
+    pub fn approx_eq(&self, other: &Self) -> bool {
+        (self.width - other.width).abs() < 0.01
+            && ((self.center - other.center) * self.width).length() < 0.5
+    }
+}
+
+pub struct ViewerWindow {
+    id: egui::ViewportId,
+    paths: Vec<PathBuf>,
+    index: usize,
+    settings: ViewerWindowSettings,
+    load: FrameLoader,
+    texture: Option<egui::TextureHandle>,
+    load_rx: Option<crossbeam_channel::Receiver<Result<ImageFrame, String>>>,
+    error: Option<String>,
+    /// Zoom on top of fit-to-window.
+    zoom: f32,
+    offset: egui::Vec2,
+    title_dirty: bool,
+    /// Image size at zoom 1 in the last frame, once the image is shown.
+    fit_size: Option<egui::Vec2>,
+    /// Zoom sync is on: keeps the view when browsing and shows a badge.
+    synced: bool,
+    /// The user zoomed or panned this frame.
+    view_changed: bool,
+    /// View from another window, applied once the image is shown.
+    pending_view: Option<SyncedView>,
+    sync_toggle_requested: bool,
+}
+
+impl ViewerWindow {
+    /// Opens `paths[index]`; `serial` only has to be unique among open windows.
+    pub fn new(
+        serial: u64,
+        paths: Vec<PathBuf>,
+        index: usize,
+        settings: ViewerWindowSettings,
+        load: FrameLoader,
+    ) -> Self {
+        let mut window = Self {
+            id: egui::ViewportId::from_hash_of(("viewer_window", serial)),
+            index: index.min(paths.len().saturating_sub(1)),
+            paths,
+            settings,
+            load,
+            texture: None,
+            load_rx: None,
+            error: None,
+            zoom: 1.0,
+            offset: egui::Vec2::ZERO,
+            title_dirty: true,
+            fit_size: None,
+            synced: false,
+            view_changed: false,
+            pending_view: None,
+            sync_toggle_requested: false,
+        };
+        window.start_load();
+        window
+    }
+
+    pub fn set_synced(&mut self, synced: bool) {
+        self.synced = synced;
+    }
+
+    /// The window's view after the user zoomed or panned it since the last call.
+    pub fn take_view_change(&mut self) -> Option<SyncedView> {
+        if !std::mem::take(&mut self.view_changed) {
+            return None;
+        }
+        let fit_size = self.fit_size?;
+        SyncedView::from_offset(fit_size * self.zoom, self.offset)
+    }
+
+    /// Moves to `view`, set by another synced window.
+    pub fn apply_synced_view(&mut self, view: SyncedView) {
+        self.pending_view = Some(view);
+    }
+
+    /// Whether `l` was pressed in this window to toggle zoom sync.
+    pub fn take_sync_toggle_request(&mut self) -> bool {
+        std::mem::take(&mut self.sync_toggle_requested)
+    }
+
+    fn current_path(&self) -> Option<&Path> {
+        self.paths.get(self.index).map(PathBuf::as_path)
+    }
+
+    fn title(&self) -> String {
+        match self.current_path().and_then(Path::file_name) {
+            Some(name) => format!("{} - Image & Video Viewer", name.to_string_lossy()),
+            None => "Image & Video Viewer".to_string(),
+        }
+    }
+
+    /// Loads the current image again, e.g. after the GPU context was lost.
+    pub fn reload(&mut self) {
+        self.texture = None;
+        self.start_load();
+    }
+
+    fn start_load(&mut self) {
+        let Some(path) = self.current_path().map(Path::to_path_buf) else {
+            self.error = Some("No image".to_string());
+            return;
+        };
+        let (settings, load) = (self.settings, self.load);
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        crate::async_runtime::spawn_blocking_or_thread("viewer-window-load", move || {
+            let _ = tx.send(load(&path, &settings));
+        });
+        self.load_rx = Some(rx);
+        self.error = None;
+        self.title_dirty = true;
+    }
+
+    fn step(&mut self, forward: bool) {
+        let len = self.paths.len();
+        if len < 2 {
+            return;
+        }
+        self.index = if forward {
+            (self.index + 1) % len
+        } else {
+            (self.index + len - 1) % len
+        };
+        // Synced windows keep the view so the same region of the next image is compared.
+        if !self.synced {
+            self.zoom = 1.0;
+            self.offset = egui::Vec2::ZERO;
+        }
+        self.start_load();
+    }
+
+    fn poll_load(&mut self, ctx: &egui::Context) {
+        let Some(rx) = self.load_rx.as_ref() else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(Ok(frame)) => {
+                let image = egui::ColorImage::from_rgba_unmultiplied(
+                    [frame.width as usize, frame.height as usize],
+                    &frame.pixels,
+                );
+                self.texture = Some(ctx.load_texture(
+                    format!("viewer_window_{:?}", self.id),
+                    image,
+                    egui::TextureOptions::LINEAR,
+                ));
+                self.load_rx = None;
+            }
+            Ok(Err(err)) => {
+                self.texture = None;
+                self.error = Some(err);
+                self.load_rx = None;
+            }
+            Err(crossbeam_channel::TryRecvError::Empty) => {
+                ctx.request_repaint_after(std::time::Duration::from_millis(30));
+            }
+            Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                self.error = Some("Failed to load the image".to_string());
+                self.load_rx = None;
+            }
+        }
+    }
+
+    /// Shows the window for this frame. Returns `false` once the user closed it.
+    pub fn show(&mut self, ctx: &egui::Context, icon: &std::sync::Arc<egui::IconData>) -> bool {
+        let builder = egui::ViewportBuilder::default()
+            .with_title(self.title())
+            .with_icon(icon.clone())
+            .with_inner_size([960.0, 720.0])
+            .with_min_inner_size([200.0, 150.0]);
+        ctx.show_viewport_immediate(self.id, builder, |ctx, _class| self.ui(ctx))
+    }
+
+    fn ui(&mut self, ctx: &egui::Context) -> bool {
+        if ctx.input(|input| input.viewport().close_requested()) {
+            return false;
+        }
+        if self.title_dirty {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(self.title()));
+            self.title_dirty = false;
+        }
+        self.poll_load(ctx);
+
+        let (next, previous, close, toggle_sync) = ctx.input(|input| {
+            (
+                input.key_pressed(egui::Key::ArrowRight) || input.key_pressed(egui::Key::PageDown),
+                input.key_pressed(egui::Key::ArrowLeft) || input.key_pressed(egui::Key::PageUp),
+                input.key_pressed(egui::Key::Escape),
+                input.key_pressed(egui::Key::L),
+            )
+        });
+        if close {
+            return false;
+        }
+        if toggle_sync {
+            self.sync_toggle_requested = true;
+        }
+        if next || previous {
+            self.step(next);
+        }
+
+        egui::CentralPanel::default()
+            .frame(egui::Frame::none().fill(self.settings.background))
+            .show(ctx, |ui| {
+                let rect = ui.max_rect();
+                let response = ui.interact(
+                    rect,
+                    ui.id().with("viewer_window_canvas"),
+                    egui::Sense::click_and_drag(),
+                );
+
+                if let Some(err) = self.error.as_ref() {
+                    ui.put(
+                        rect,
+                        egui::Label::new(egui::RichText::new(err).color(egui::Color32::RED)),
+                    );
+                    return;
+                }
+                let Some(texture) = self.texture.as_ref() else {
+                    ui.put(rect, egui::Spinner::new().size(28.0));
+                    return;
+                };
+
+                let size = texture.size_vec2();
+                let fit = (rect.width() / size.x).min(rect.height() / size.y).min(1.0);
+                let fit_size = size * fit;
+                self.fit_size = Some(fit_size);
+                if let Some(view) = self.pending_view.take() {
+                    self.zoom = (view.width / fit_size.x).clamp(0.1, 64.0);
+                    self.offset = view.offset_for(fit_size * self.zoom);
+                }
+                if let Some(pointer) = response.hover_pos() {
+                    let (scroll, zoom_delta) =
+                        ui.input(|input| (input.smooth_scroll_delta.y, input.zoom_delta()));
+                    let factor = zoom_delta * (scroll * 0.0015).exp();
+                    if (factor - 1.0).abs() > f32::EPSILON {
+                        let new_zoom = (self.zoom * factor).clamp(0.1, 64.0);
+                        let applied = new_zoom / self.zoom;
+                        let image_center = rect.center() + self.offset;
+                        self.offset = pointer + (image_center - pointer) * applied - rect.center();
+                        self.zoom = new_zoom;
+                        self.view_changed = true;
+                    }
+                }
+                if response.dragged() {
+                    self.offset += response.drag_delta();
+                    self.view_changed = true;
+                }
+                if response.double_clicked() {
+                    self.zoom = 1.0;
+                    self.offset = egui::Vec2::ZERO;
+                    self.view_changed = true;
+                }
+
+                let image_rect = egui::Rect::from_center_size(
+                    rect.center() + self.offset,
+                    size * fit * self.zoom,
+                );
+                ui.painter().image(
+                    texture.id(),
+                    image_rect,
+                    egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                    egui::Color32::WHITE,
+                );
+                if self.synced {
+                    ui.painter().text(
+                        rect.right_top() + egui::vec2(-10.0, 8.0),
+                        egui::Align2::RIGHT_TOP,
+                        "Zoom synced",
+                        egui::FontId::proportional(12.0),
+                        egui::Color32::from_white_alpha(180),
+                    );
+                }
+            });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SyncedView, ViewerWindow, ViewerWindowSettings};
+    use crate::image_loader::ImageFrame;
+    use std::path::{Path, PathBuf};
+
+    fn stub_loader(_path: &Path, _settings: &ViewerWindowSettings) -> Result<ImageFrame, String> {
+        Err("stub".to_string())
+    }
+
+    fn window(paths: Vec<PathBuf>, index: usize) -> ViewerWindow {
+        let settings = ViewerWindowSettings {
+            max_texture_side: 1024,
+            downscale_filter: image::imageops::FilterType::Triangle,
+            gif_filter: image::imageops::FilterType::Triangle,
+            background: egui::Color32::BLACK,
+        };
+        ViewerWindow::new(1, paths, index, settings, stub_loader)
+    }
+
+    #[test]
+    fn navigation_wraps_around_the_folder() {
+        let paths: Vec<PathBuf> = ["a.png", "b.png", "c.png"].map(PathBuf::from).into();
+        let mut viewer = window(paths, 2);
+        viewer.step(true);
+        assert_eq!(viewer.current_path(), Some(Path::new("a.png")));
+        viewer.step(false);
+        viewer.step(false);
+        assert_eq!(viewer.current_path(), Some(Path::new("b.png")));
+    }
+
+    #[test]
+    fn synced_view_keeps_the_region_across_image_sizes() {
+        let view =
+            SyncedView::from_offset(egui::vec2(800.0, 600.0), egui::vec2(-200.0, 150.0)).unwrap();
+        assert_eq!(view.center, egui::vec2(0.75, 0.25));
+        // A half-resolution copy shown at the same width is offset by the same amount.
+        assert_eq!(
+            view.offset_for(egui::vec2(800.0, 600.0)),
+            egui::vec2(-200.0, 150.0)
+        );
+        assert_eq!(
+            view.offset_for(egui::vec2(1600.0, 1200.0)),
+            egui::vec2(-400.0, 300.0)
+        );
+        assert!(SyncedView::from_offset(egui::Vec2::ZERO, egui::Vec2::ZERO).is_none());
+    }
+
+    #[test]
+    fn title_uses_the_file_name() {
+        let viewer = window(vec![PathBuf::from("dir/photo.jpg")], 5);
+        assert_eq!(viewer.title(), "photo.jpg - Image & Video Viewer");
+        assert!(window(Vec::new(), 0).error.is_some());
+    }
+}
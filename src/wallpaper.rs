@@ -0,0 +1,208 @@
+//! `--wallpaper <folder>`: rotate the desktop wallpaper through a folder.
+//!
+//! Runs without a window, with only a tray icon for "next", "pause" and "exit". Files come
+//! from the same directory listing and natural sort as the viewer, and every format the
+//! viewer decodes works: each image is decoded with the viewer's loader and written as a PNG
+//! under the app's local data folder, which is what Windows is pointed at. The folder is
+//! listed again after each full round, so added and removed files are picked up.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use image::imageops::FilterType;
+
+use crate::config::Config;
+use crate::image_loader::{get_media_in_directory, is_supported_image, LoadedImage};
+use crate::tray::{TrayEvent, TrayIcon, TrayMenuItem};
+
+const MENU_NEXT: u32 = 1;
+const MENU_PAUSE: u32 = 2;
+const MENU_EXIT: u32 = 3;
+
+/// Wallpapers larger than this are downscaled before they are handed to Windows.
+const MAX_WALLPAPER_SIDE: u32 = 7680;
+
+/// The images of one folder in viewer order, and the one to show next.
+struct Playlist {
+    folder: PathBuf,
+    images: Vec<PathBuf>,
+    next: usize,
+}
+
+impl Playlist {
+    /// Starts at `start` when it is an image, else at the first image of the folder.
+    fn new(start: &Path) -> Self {
+        let folder = if start.is_dir() {
+            start.to_path_buf()
+        } else {
+            start.parent().map(Path::to_path_buf).unwrap_or_default()
+        };
+        let images = images_in(get_media_in_directory(&folder));
+        let next = images.iter().position(|image| image == start).unwrap_or(0);
+        Self {
+            folder,
+            images,
+            next,
+        }
+    }
+
+    /// The next image, listing the folder again once every image has been shown.
+    fn advance(&mut self) -> Option<PathBuf> {
+        if self.next >= self.images.len() {
+            self.images = images_in(get_media_in_directory(&self.folder));
+            self.next = 0;
+        }
+        let image = self.images.get(self.next).cloned();
+        self.next += 1;
+        image
+    }
+}
+
+/// Still images from a directory listing, in listing order.
+fn images_in(entries: Vec<PathBuf>) -> Vec<PathBuf> {
+    entries
+        .into_iter()
+        .filter(|path| is_supported_image(path) && !path.is_dir())
+        .collect()
+}
+
+/// Decodes `image` and sets it as the wallpaper. `slot` alternates between two output
+/// files so Windows never reads a file that is being rewritten.
+fn apply(image: &Path, slot: usize) -> Result<(), String> {
+    let loaded = LoadedImage::load_first_frame_only(
+        image,
+        Some(MAX_WALLPAPER_SIDE),
+        FilterType::Lanczos3,
+        FilterType::Triangle,
+    )?;
+    let frame = loaded.current_frame_data();
+    let dir = crate::storage::data_dir()
+        .ok_or_else(|| "no local data folder".to_string())?
+        .join("wallpaper");
+    std::fs::create_dir_all(&dir).map_err(|err| err.to_string())?;
+    let target = dir.join(format!("wallpaper-{}.png", slot % 2));
+    image::RgbaImage::from_raw(frame.width, frame.height, frame.pixels.clone())
+        .ok_or_else(|| "decoded image has the wrong size".to_string())?
+        .save_with_format(&target, image::ImageFormat::Png)
+        .map_err(|err| err.to_string())?;
+    set_desktop_wallpaper(&target)
+}
+
+#[cfg(target_os = "windows")]
+fn set_desktop_wallpaper(path: &Path) -> Result<(), String> {
+    if crate::windows_env::set_desktop_wallpaper(path) {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error().to_string())
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn set_desktop_wallpaper(_path: &Path) -> Result<(), String> {
+    Err("setting the wallpaper is only supported on Windows".to_string())
+}
+
+/// Sets the next image that decodes. Files that fail are skipped, for at most one round.
+fn show_next(playlist: &mut Playlist, shown: &mut usize) {
+    for _ in 0..playlist.images.len().max(1) {
+        let Some(image) = playlist.advance() else {
+            return;
+        };
+        match apply(&image, *shown) {
+            Ok(()) => {
+                *shown += 1;
+                return;
+            }
+            Err(err) => tracing::warn!("Skipping wallpaper {}: {err}", image.display()),
+        }
+    }
+}
+
+fn tray_menu(paused: bool) -> Vec<TrayMenuItem> {
+    vec![
+        TrayMenuItem::item(MENU_NEXT, "Next wallpaper"),
+        TrayMenuItem::checked(MENU_PAUSE, "Pause", paused),
+        TrayMenuItem::Separator,
+        TrayMenuItem::item(MENU_EXIT, "Exit"),
+    ]
+}
+
+/// Runs the slideshow until "Exit" is picked. `start` is a folder or an image in it.
+pub fn run(config: &Config, start: &Path) -> Result<(), String> {
+    let mut playlist = Playlist::new(start);
+    if playlist.images.is_empty() {
+        return Err(format!("No images found in {}", playlist.folder.display()));
+    }
+    let tray = TrayIcon::spawn("Rust Image Viewer wallpaper", tray_menu(false), || {})
+        .ok_or_else(|| "The system tray is not available".to_string())?;
+
+    let interval = Duration::from_secs(u64::from(config.wallpaper_interval_minutes) * 60);
+    let mut paused = false;
+    let mut next_change = Instant::now();
+    let mut shown = 0usize;
+    tracing::info!(
+        "Wallpaper slideshow over {} images in {}",
+        playlist.images.len(),
+        playlist.folder.display()
+    );
+
+    loop {
+        if !paused && Instant::now() >= next_change {
+            show_next(&mut playlist, &mut shown);
+            next_change = Instant::now() + interval;
+        }
+
+        let timeout = if paused {
+            Duration::from_secs(3600)
+        } else {
+            next_change.saturating_duration_since(Instant::now())
+        };
+        match tray.events().recv_timeout(timeout) {
+            Ok(TrayEvent::Menu(MENU_NEXT)) => {
+                // Also works while paused, without resuming.
+                show_next(&mut playlist, &mut shown);
+                next_change = Instant::now() + interval;
+            }
+            Ok(TrayEvent::Menu(MENU_PAUSE)) => {
+                paused = !paused;
+                tray.set_menu(tray_menu(paused));
+                next_change = Instant::now() + interval;
+            }
+            Ok(TrayEvent::Menu(MENU_EXIT))
+            | Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {
+                return Ok(());
+            }
+            Ok(_) | Err(crossbeam_channel::RecvTimeoutError::Timeout) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{images_in, Playlist};
+    use std::path::PathBuf;
+
+    #[test]
+    fn playlist_keeps_listing_order_and_wraps() {
+        let listing = ["..", "Sub", "b.png", "clip.mp4", "c.webp", "notes.txt"]
+            .map(|name| PathBuf::from("wallpapers").join(name))
+            .to_vec();
+        let images = images_in(listing);
+        assert_eq!(
+            images,
+            [
+                PathBuf::from("wallpapers/b.png"),
+                PathBuf::from("wallpapers/c.webp")
+            ]
+        );
+
+        // A folder that does not exist lists as empty, so the wrap leaves nothing to show.
+        let mut playlist = Playlist {
+            folder: PathBuf::from("missing-wallpaper-folder"),
+            images,
+            next: 1,
+        };
+        assert_eq!(playlist.advance(), Some(PathBuf::from("wallpapers/c.webp")));
+        assert_eq!(playlist.advance(), None);
+    }
+}
@@ -0,0 +1,146 @@
+//! Snapping the floating window to halves and quarters of its monitor, and resizing it to
+//! size presets.
+//!
+//! Snap keys combine like the Windows ones: `left` takes the left half, then `up` narrows
+//! it to the top-left quarter, and the opposite key of an axis switches sides. A snapped or
+//! pixel-sized window keeps its size while the media is fitted inside; percentage presets
+//! set the zoom and leave the window size to the usual autosize.
+
+/// Side of one axis the window is snapped to; `None` in [`SnapRegion`] spans the axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapSide {
+    Start,
+    End,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// Part of the work area the window covers: a half when one axis is set, a quarter when
+/// both are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SnapRegion {
+    pub horizontal: Option<SnapSide>,
+    pub vertical: Option<SnapSide>,
+}
+
+impl SnapRegion {
+    /// The region after pressing the snap key for `direction`.
+    pub fn step(self, direction: SnapDirection) -> Self {
+        match direction {
+            SnapDirection::Left => Self {
+                horizontal: Some(SnapSide::Start),
+                ..self
+            },
+            SnapDirection::Right => Self {
+                horizontal: Some(SnapSide::End),
+                ..self
+            },
+            SnapDirection::Up => Self {
+                vertical: Some(SnapSide::Start),
+                ..self
+            },
+            SnapDirection::Down => Self {
+                vertical: Some(SnapSide::End),
+                ..self
+            },
+        }
+    }
+
+    pub fn rect(self, area: egui::Rect) -> egui::Rect {
+        let span = |min: f32, max: f32, side: Option<SnapSide>| {
+            let middle = (min + max) * 0.5;
+            match side {
+                None => (min, max),
+                Some(SnapSide::Start) => (min, middle),
+                Some(SnapSide::End) => (middle, max),
+            }
+        };
+        let (left, right) = span(area.min.x, area.max.x, self.horizontal);
+        let (top, bottom) = span(area.min.y, area.max.y, self.vertical);
+        egui::Rect::from_min_max(
+            egui::pos2(left, top).round(),
+            egui::pos2(right, bottom).round(),
+        )
+    }
+}
+
+/// A `window_size_presets` entry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SizePreset {
+    /// Inner window size in physical pixels, e.g. `1280x720`.
+    Pixels(u32, u32),
+    /// Zoom relative to the media, e.g. `50%`.
+    MediaPercent(f32),
+}
+
+impl SizePreset {
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Some(percent) = value.strip_suffix('%') {
+            let percent = percent.trim().parse::<f32>().ok()?;
+            return (percent.is_finite() && percent > 0.0).then_some(Self::MediaPercent(percent));
+        }
+        let (width, height) = value.split_once(['x', 'X', '×'])?;
+        let width = width.trim().parse::<u32>().ok()?;
+        let height = height.trim().parse::<u32>().ok()?;
+        (width >= 100 && height >= 100).then_some(Self::Pixels(width, height))
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Pixels(width, height) => format!("{width}x{height}"),
+            Self::MediaPercent(percent) => format!("{percent}%"),
+        }
+    }
+}
+
+/// Comma-separated presets; entries that do not parse are skipped.
+pub fn parse_size_presets(value: &str) -> Vec<SizePreset> {
+    value.split(',').filter_map(SizePreset::parse).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_size_presets, SizePreset, SnapDirection, SnapRegion, SnapSide};
+
+    #[test]
+    fn snap_keys_combine_into_halves_and_quarters() {
+        let area = egui::Rect::from_min_size(egui::pos2(0.0, 0.0), egui::vec2(1920.0, 1040.0));
+        let left = SnapRegion::default().step(SnapDirection::Left);
+        assert_eq!(
+            left.rect(area),
+            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(960.0, 1040.0))
+        );
+        let top_left = left.step(SnapDirection::Up);
+        assert_eq!(
+            top_left.rect(area),
+            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(960.0, 520.0))
+        );
+        let top_right = top_left.step(SnapDirection::Right);
+        assert_eq!(top_right.horizontal, Some(SnapSide::End));
+        assert_eq!(top_right.rect(area).min, egui::pos2(960.0, 0.0));
+        let bottom = SnapRegion::default().step(SnapDirection::Down);
+        assert_eq!(bottom.rect(area).min, egui::pos2(0.0, 520.0));
+        assert_eq!(bottom.rect(area).width(), 1920.0);
+    }
+
+    #[test]
+    fn size_presets_parse_pixels_and_percentages() {
+        assert_eq!(
+            parse_size_presets("1280x720, 100%, 50 %, bogus, 10x10"),
+            [
+                SizePreset::Pixels(1280, 720),
+                SizePreset::MediaPercent(100.0),
+                SizePreset::MediaPercent(50.0),
+            ]
+        );
+        assert_eq!(SizePreset::Pixels(1920, 1080).label(), "1920x1080");
+        assert_eq!(SizePreset::MediaPercent(50.0).label(), "50%");
+    }
+}
@@ -3,6 +3,8 @@
 use std::ffi::OsStr;
 use std::os::windows::ffi::OsStrExt;
 
+use crate::monitors::Monitor;
+
 fn wide(s: &OsStr) -> Vec<u16> {
     s.encode_wide().chain(std::iter::once(0)).collect()
 }
@@ -147,6 +149,44 @@ fn registry_effective_path() -> Option<String> {
     }
 }
 
+/// Whether apps should use the light theme (Settings > Personalization > Colors), or
+/// `None` when Windows does not say.
+pub fn apps_use_light_theme() -> Option<bool> {
+    use winapi::shared::minwindef::DWORD;
+    use winapi::um::winreg::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD};
+
+    let subkey = wide(OsStr::new(
+        r"Software\Microsoft\Windows\CurrentVersion\Themes\Personalize",
+    ));
+    let value = wide(OsStr::new("AppsUseLightTheme"));
+    let mut data: DWORD = 0;
+    let mut bytes = std::mem::size_of::<DWORD>() as DWORD;
+    let status = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            subkey.as_ptr(),
+            value.as_ptr(),
+            RRF_RT_REG_DWORD,
+            std::ptr::null_mut(),
+            &mut data as *mut DWORD as *mut _,
+            &mut bytes,
+        )
+    };
+    (status == 0).then_some(data != 0)
+}
+
+/// The user's display locale, such as `ja-JP`.
+pub fn user_locale_name() -> Option<String> {
+    use winapi::um::winreg::HKEY_CURRENT_USER;
+
+    read_reg_string(
+        HKEY_CURRENT_USER,
+        r"Control Panel\International",
+        "LocaleName",
+    )
+    .filter(|locale| !locale.is_empty())
+}
+
 fn active_or_foreground_window() -> winapi::shared::windef::HWND {
     use winapi::um::winuser::{GetActiveWindow, GetForegroundWindow};
 
@@ -214,3 +254,1072 @@ pub fn refresh_process_path_from_registry() {
         std::env::set_var("PATH", merged);
     }
 }
+
+/// Write a minidump of the current process to `path`. `exception_pointers` may be null
+/// (e.g. for Rust panics), in which case the dump carries no exception stream.
+pub fn write_minidump(
+    path: &std::path::Path,
+    exception_pointers: *mut winapi::um::winnt::EXCEPTION_POINTERS,
+) -> bool {
+    use std::os::windows::io::AsRawHandle;
+    use winapi::shared::minwindef::FALSE;
+    use winapi::um::dbghelp::{MiniDumpNormal, MiniDumpWriteDump, MINIDUMP_EXCEPTION_INFORMATION};
+    use winapi::um::processthreadsapi::{
+        GetCurrentProcess, GetCurrentProcessId, GetCurrentThreadId,
+    };
+
+    let Ok(file) = std::fs::File::create(path) else {
+        return false;
+    };
+
+    unsafe {
+        let mut exception_info = MINIDUMP_EXCEPTION_INFORMATION {
+            ThreadId: GetCurrentThreadId(),
+            ExceptionPointers: exception_pointers,
+            ClientPointers: FALSE,
+        };
+        let exception_param = if exception_pointers.is_null() {
+            std::ptr::null_mut()
+        } else {
+            &mut exception_info as *mut MINIDUMP_EXCEPTION_INFORMATION
+        };
+
+        MiniDumpWriteDump(
+            GetCurrentProcess(),
+            GetCurrentProcessId(),
+            file.as_raw_handle() as winapi::um::winnt::HANDLE,
+            MiniDumpNormal,
+            exception_param,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        ) != 0
+    }
+}
+
+/// Route crashes that bypass the Rust panic machinery (access violations in native code,
+/// stack overflows in plugins, ...) to `handler`.
+pub fn install_unhandled_exception_filter(
+    handler: unsafe extern "system" fn(*mut winapi::um::winnt::EXCEPTION_POINTERS) -> i32,
+) {
+    use winapi::um::errhandlingapi::SetUnhandledExceptionFilter;
+
+    unsafe {
+        SetUnhandledExceptionFilter(Some(handler));
+    }
+}
+
+fn wide_to_string(buf: &[u16]) -> String {
+    let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+    String::from_utf16_lossy(&buf[..len])
+}
+
+/// One line per display adapter output: device name, adapter name, state and the driver
+/// version recorded in the adapter's registry key.
+pub fn describe_display_adapters() -> Vec<String> {
+    use winapi::um::wingdi::{
+        DISPLAY_DEVICEW, DISPLAY_DEVICE_ATTACHED_TO_DESKTOP, DISPLAY_DEVICE_PRIMARY_DEVICE,
+    };
+    use winapi::um::winreg::HKEY_LOCAL_MACHINE;
+    use winapi::um::winuser::EnumDisplayDevicesW;
+
+    let mut lines = Vec::new();
+    for index in 0.. {
+        let mut device: DISPLAY_DEVICEW = unsafe { std::mem::zeroed() };
+        device.cb = std::mem::size_of::<DISPLAY_DEVICEW>() as u32;
+        if unsafe { EnumDisplayDevicesW(std::ptr::null(), index, &mut device, 0) } == 0 {
+            break;
+        }
+
+        // DeviceKey looks like `\Registry\Machine\System\CurrentControlSet\Control\Video\{…}\0000`.
+        let device_key = wide_to_string(&device.DeviceKey);
+        const MACHINE_PREFIX: &str = r"\Registry\Machine\";
+        let driver_version = device_key
+            .get(..MACHINE_PREFIX.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(MACHINE_PREFIX))
+            .and_then(|_| {
+                read_reg_string(
+                    HKEY_LOCAL_MACHINE,
+                    &device_key[MACHINE_PREFIX.len()..],
+                    "DriverVersion",
+                )
+            })
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let mut state = Vec::new();
+        if device.StateFlags & DISPLAY_DEVICE_ATTACHED_TO_DESKTOP != 0 {
+            state.push("attached");
+        }
+        if device.StateFlags & DISPLAY_DEVICE_PRIMARY_DEVICE != 0 {
+            state.push("primary");
+        }
+
+        lines.push(format!(
+            "{}: {} [{}] driver {}",
+            wide_to_string(&device.DeviceName),
+            wide_to_string(&device.DeviceString),
+            state.join(", "),
+            driver_version,
+        ));
+    }
+    lines
+}
+
+/// One line per monitor: device name, desktop rect, work area, mode and primary flag.
+pub fn describe_monitors() -> Vec<String> {
+    use winapi::shared::minwindef::{BOOL, LPARAM, TRUE};
+    use winapi::shared::windef::{HDC, HMONITOR, LPRECT};
+    use winapi::um::wingdi::DEVMODEW;
+    use winapi::um::winuser::{
+        EnumDisplayMonitors, EnumDisplaySettingsW, GetMonitorInfoW, ENUM_CURRENT_SETTINGS,
+        MONITORINFO, MONITORINFOEXW, MONITORINFOF_PRIMARY,
+    };
+
+    unsafe extern "system" fn collect(
+        monitor: HMONITOR,
+        _hdc: HDC,
+        _clip: LPRECT,
+        data: LPARAM,
+    ) -> BOOL {
+        let lines = &mut *(data as *mut Vec<String>);
+
+        let mut info: MONITORINFOEXW = std::mem::zeroed();
+        info.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+        if GetMonitorInfoW(
+            monitor,
+            &mut info as *mut MONITORINFOEXW as *mut MONITORINFO,
+        ) == 0
+        {
+            return TRUE;
+        }
+
+        let mut mode: DEVMODEW = std::mem::zeroed();
+        mode.dmSize = std::mem::size_of::<DEVMODEW>() as u16;
+        let mode_text = if EnumDisplaySettingsW(
+            info.szDevice.as_ptr(),
+            ENUM_CURRENT_SETTINGS,
+            &mut mode,
+        ) != 0
+        {
+            format!(
+                "{}x{} @ {} Hz, {} bpp",
+                mode.dmPelsWidth, mode.dmPelsHeight, mode.dmDisplayFrequency, mode.dmBitsPerPel
+            )
+        } else {
+            "mode unknown".to_string()
+        };
+
+        let (rect, work) = (info.rcMonitor, info.rcWork);
+        lines.push(format!(
+            "{}: desktop ({}, {})-({}, {}), work area ({}, {})-({}, {}), {}{}",
+            wide_to_string(&info.szDevice),
+            rect.left,
+            rect.top,
+            rect.right,
+            rect.bottom,
+            work.left,
+            work.top,
+            work.right,
+            work.bottom,
+            mode_text,
+            if info.dwFlags & MONITORINFOF_PRIMARY != 0 {
+                ", primary"
+            } else {
+                ""
+            },
+        ));
+        TRUE
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    unsafe {
+        EnumDisplayMonitors(
+            std::ptr::null_mut(),
+            std::ptr::null(),
+            Some(collect),
+            &mut lines as *mut Vec<String> as LPARAM,
+        );
+    }
+    lines
+}
+
+fn monitor_from_handle(monitor: winapi::shared::windef::HMONITOR) -> Option<Monitor> {
+    use winapi::shared::winerror::S_OK;
+    use winapi::um::shellscalingapi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI};
+    use winapi::um::winuser::{GetMonitorInfoW, MONITORINFO, MONITORINFOF_PRIMARY};
+
+    let mut info: MONITORINFO = unsafe { std::mem::zeroed() };
+    info.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
+    if unsafe { GetMonitorInfoW(monitor, &mut info) } == 0 {
+        return None;
+    }
+
+    let (mut dpi_x, mut dpi_y) = (0u32, 0u32);
+    let scale = if unsafe { GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) }
+        == S_OK
+        && dpi_x > 0
+    {
+        dpi_x as f32 / 96.0
+    } else {
+        1.0
+    };
+
+    let to_rect = |rect: winapi::shared::windef::RECT| {
+        egui::Rect::from_min_max(
+            egui::pos2(rect.left as f32, rect.top as f32),
+            egui::pos2(rect.right as f32, rect.bottom as f32),
+        )
+    };
+    Some(Monitor {
+        rect: to_rect(info.rcMonitor),
+        work: to_rect(info.rcWork),
+        scale,
+        primary: info.dwFlags & MONITORINFOF_PRIMARY != 0,
+    })
+}
+
+/// Every monitor with its desktop bounds in physical pixels and its effective DPI scale.
+pub fn monitors() -> Vec<Monitor> {
+    use winapi::shared::minwindef::{BOOL, LPARAM, TRUE};
+    use winapi::shared::windef::{HDC, HMONITOR, LPRECT};
+    use winapi::um::winuser::EnumDisplayMonitors;
+
+    unsafe extern "system" fn collect(
+        monitor: HMONITOR,
+        _hdc: HDC,
+        _clip: LPRECT,
+        data: LPARAM,
+    ) -> BOOL {
+        let monitors = &mut *(data as *mut Vec<Monitor>);
+        monitors.extend(monitor_from_handle(monitor));
+        TRUE
+    }
+
+    let mut monitors: Vec<Monitor> = Vec::new();
+    unsafe {
+        EnumDisplayMonitors(
+            std::ptr::null_mut(),
+            std::ptr::null(),
+            Some(collect),
+            &mut monitors as *mut Vec<Monitor> as LPARAM,
+        );
+    }
+    monitors
+}
+
+/// The monitor holding most of the active window, or the nearest one.
+pub fn active_window_monitor() -> Option<Monitor> {
+    use winapi::um::winuser::{IsWindow, MonitorFromWindow, MONITOR_DEFAULTTONEAREST};
+
+    let hwnd = active_or_foreground_window();
+    if hwnd.is_null() || unsafe { IsWindow(hwnd) } == 0 {
+        return None;
+    }
+    let monitor = unsafe { MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST) };
+    if monitor.is_null() {
+        return None;
+    }
+    monitor_from_handle(monitor)
+}
+
+/// Blocking information dialog, used for command-line modes that have no window.
+pub fn show_info_message_box(title: &str, text: &str) {
+    use winapi::um::winuser::{
+        MessageBoxW, MB_ICONINFORMATION, MB_OK, MB_SETFOREGROUND, MB_TOPMOST,
+    };
+
+    let title = wide(OsStr::new(title));
+    let text = wide(OsStr::new(text));
+    unsafe {
+        MessageBoxW(
+            std::ptr::null_mut(),
+            text.as_ptr(),
+            title.as_ptr(),
+            MB_OK | MB_ICONINFORMATION | MB_SETFOREGROUND | MB_TOPMOST,
+        );
+    }
+}
+
+/// Sends stdout and stderr to the console the viewer was started from, if any. The viewer is
+/// a GUI program, so command-line modes that print need this to show up in a terminal;
+/// redirected output works without it.
+pub fn attach_parent_console() {
+    use winapi::um::wincon::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+/// Blocking error dialog that stays on top even when the app window is gone.
+pub fn show_error_message_box(title: &str, text: &str) {
+    use winapi::um::winuser::{MessageBoxW, MB_ICONERROR, MB_OK, MB_SETFOREGROUND, MB_TOPMOST};
+
+    let title = wide(OsStr::new(title));
+    let text = wide(OsStr::new(text));
+    unsafe {
+        MessageBoxW(
+            std::ptr::null_mut(),
+            text.as_ptr(),
+            title.as_ptr(),
+            MB_OK | MB_ICONERROR | MB_SETFOREGROUND | MB_TOPMOST,
+        );
+    }
+}
+
+/// Sets the desktop wallpaper on every monitor to the image at `path`.
+pub fn set_desktop_wallpaper(path: &std::path::Path) -> bool {
+    use winapi::um::winuser::{
+        SystemParametersInfoW, SPIF_SENDCHANGE, SPIF_UPDATEINIFILE, SPI_SETDESKWALLPAPER,
+    };
+
+    let mut path = wide(crate::path_access::extended_length(path).as_os_str());
+    unsafe {
+        SystemParametersInfoW(
+            SPI_SETDESKWALLPAPER,
+            0,
+            path.as_mut_ptr().cast(),
+            SPIF_UPDATEINIFILE | SPIF_SENDCHANGE,
+        ) != 0
+    }
+}
+
+const WM_TRAY_CALLBACK: u32 = winapi::um::winuser::WM_APP + 1;
+
+/// Window data of the hidden tray window.
+struct TrayWindow {
+    icon: winapi::um::shellapi::NOTIFYICONDATAW,
+    menu: crate::tray::SharedTrayMenu,
+    notify: Box<dyn Fn(crate::tray::TrayEvent)>,
+    /// Broadcast when Explorer restarts; the icon has to be added again.
+    taskbar_created: u32,
+}
+
+/// Runs a tray icon until [`close_tray_icon`] is called with the window sent on `ready`
+/// (0 when the icon could not be added).
+pub fn run_tray_icon(
+    tooltip: &str,
+    menu: crate::tray::SharedTrayMenu,
+    notify: Box<dyn Fn(crate::tray::TrayEvent)>,
+    ready: crossbeam_channel::Sender<usize>,
+) {
+    use winapi::um::libloaderapi::GetModuleHandleW;
+    use winapi::um::shellapi::{
+        Shell_NotifyIconW, NIF_ICON, NIF_MESSAGE, NIF_TIP, NIM_ADD, NIM_DELETE, NOTIFYICONDATAW,
+    };
+    use winapi::um::winuser::{
+        CreateWindowExW, DestroyWindow, DispatchMessageW, GetMessageW, LoadIconW, RegisterClassW,
+        RegisterWindowMessageW, SetWindowLongPtrW, TranslateMessage, GWLP_USERDATA,
+        IDI_APPLICATION, MAKEINTRESOURCEW, MSG, WNDCLASSW, WS_OVERLAPPED,
+    };
+
+    let class_name = wide(OsStr::new("RustImageViewerTray"));
+    let taskbar_created = wide(OsStr::new("TaskbarCreated"));
+    unsafe {
+        let instance = GetModuleHandleW(std::ptr::null());
+        let mut class: WNDCLASSW = std::mem::zeroed();
+        class.lpfnWndProc = Some(tray_window_proc);
+        class.hInstance = instance;
+        class.lpszClassName = class_name.as_ptr();
+        // Fails harmlessly when a previous icon already registered the class.
+        RegisterClassW(&class);
+
+        let hwnd = CreateWindowExW(
+            0,
+            class_name.as_ptr(),
+            class_name.as_ptr(),
+            WS_OVERLAPPED,
+            0,
+            0,
+            0,
+            0,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            instance,
+            std::ptr::null_mut(),
+        );
+        if hwnd.is_null() {
+            let _ = ready.send(0);
+            return;
+        }
+
+        // winres embeds the application icon with resource id 1.
+        let mut icon_handle = LoadIconW(instance, MAKEINTRESOURCEW(1));
+        if icon_handle.is_null() {
+            icon_handle = LoadIconW(std::ptr::null_mut(), IDI_APPLICATION);
+        }
+
+        let mut icon: NOTIFYICONDATAW = std::mem::zeroed();
+        icon.cbSize = std::mem::size_of::<NOTIFYICONDATAW>() as u32;
+        icon.hWnd = hwnd;
+        icon.uID = 1;
+        icon.uFlags = NIF_ICON | NIF_MESSAGE | NIF_TIP;
+        icon.uCallbackMessage = WM_TRAY_CALLBACK;
+        icon.hIcon = icon_handle;
+        let tip: Vec<u16> = OsStr::new(tooltip).encode_wide().collect();
+        let tip_len = tip.len().min(icon.szTip.len() - 1);
+        icon.szTip[..tip_len].copy_from_slice(&tip[..tip_len]);
+
+        if Shell_NotifyIconW(NIM_ADD, &mut icon) == 0 {
+            DestroyWindow(hwnd);
+            let _ = ready.send(0);
+            return;
+        }
+
+        let window = Box::into_raw(Box::new(TrayWindow {
+            icon,
+            menu,
+            notify,
+            taskbar_created: RegisterWindowMessageW(taskbar_created.as_ptr()),
+        }));
+        SetWindowLongPtrW(hwnd, GWLP_USERDATA, window as isize);
+        let _ = ready.send(hwnd as usize);
+
+        let mut msg: MSG = std::mem::zeroed();
+        while GetMessageW(&mut msg, std::ptr::null_mut(), 0, 0) > 0 {
+            TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+
+        let mut window = Box::from_raw(window);
+        Shell_NotifyIconW(NIM_DELETE, &mut window.icon);
+    }
+}
+
+/// Removes a tray icon started by [`run_tray_icon`] and ends its thread.
+pub fn close_tray_icon(window: usize) {
+    use winapi::um::winuser::{PostMessageW, WM_CLOSE};
+
+    unsafe {
+        PostMessageW(window as winapi::shared::windef::HWND, WM_CLOSE, 0, 0);
+    }
+}
+
+unsafe extern "system" fn tray_window_proc(
+    hwnd: winapi::shared::windef::HWND,
+    msg: u32,
+    wparam: winapi::shared::minwindef::WPARAM,
+    lparam: winapi::shared::minwindef::LPARAM,
+) -> winapi::shared::minwindef::LRESULT {
+    use winapi::um::shellapi::{Shell_NotifyIconW, NIM_ADD};
+    use winapi::um::winuser::{
+        DefWindowProcW, DestroyWindow, GetWindowLongPtrW, PostQuitMessage, SetWindowLongPtrW,
+        GWLP_USERDATA, WM_CLOSE, WM_CONTEXTMENU, WM_DESTROY, WM_LBUTTONUP, WM_RBUTTONUP,
+    };
+
+    let window = GetWindowLongPtrW(hwnd, GWLP_USERDATA) as *mut TrayWindow;
+    match msg {
+        WM_TRAY_CALLBACK if !window.is_null() => {
+            match lparam as u32 {
+                WM_LBUTTONUP => ((*window).notify)(crate::tray::TrayEvent::Activate),
+                WM_RBUTTONUP | WM_CONTEXTMENU => show_tray_menu(hwnd, &*window),
+                _ => {}
+            }
+            0
+        }
+        WM_CLOSE => {
+            DestroyWindow(hwnd);
+            0
+        }
+        WM_DESTROY => {
+            // `run_tray_icon` frees the data once the message loop ends.
+            SetWindowLongPtrW(hwnd, GWLP_USERDATA, 0);
+            PostQuitMessage(0);
+            0
+        }
+        _ if !window.is_null() && msg == (*window).taskbar_created => {
+            Shell_NotifyIconW(NIM_ADD, &mut (*window).icon);
+            0
+        }
+        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+}
+
+fn build_tray_menu(items: &[crate::tray::TrayMenuItem]) -> winapi::shared::windef::HMENU {
+    use crate::tray::TrayMenuItem;
+    use winapi::um::winuser::{
+        AppendMenuW, CreatePopupMenu, MF_CHECKED, MF_GRAYED, MF_POPUP, MF_SEPARATOR, MF_STRING,
+    };
+
+    unsafe {
+        let menu = CreatePopupMenu();
+        for item in items {
+            match item {
+                TrayMenuItem::Item {
+                    id,
+                    label,
+                    checked,
+                    enabled,
+                } => {
+                    let mut flags = MF_STRING;
+                    if *checked {
+                        flags |= MF_CHECKED;
+                    }
+                    if !*enabled {
+                        flags |= MF_GRAYED;
+                    }
+                    let label = wide(OsStr::new(label));
+                    AppendMenuW(menu, flags, *id as usize, label.as_ptr());
+                }
+                TrayMenuItem::Separator => {
+                    AppendMenuW(menu, MF_SEPARATOR, 0, std::ptr::null());
+                }
+                TrayMenuItem::Submenu { label, items } => {
+                    let submenu = build_tray_menu(items);
+                    let mut flags = MF_POPUP;
+                    if items.is_empty() {
+                        flags |= MF_GRAYED;
+                    }
+                    let label = wide(OsStr::new(label));
+                    AppendMenuW(menu, flags, submenu as usize, label.as_ptr());
+                }
+            }
+        }
+        menu
+    }
+}
+
+fn show_tray_menu(hwnd: winapi::shared::windef::HWND, window: &TrayWindow) {
+    use winapi::shared::windef::POINT;
+    use winapi::um::winuser::{
+        DestroyMenu, GetCursorPos, PostMessageW, SetForegroundWindow, TrackPopupMenu, TPM_NONOTIFY,
+        TPM_RETURNCMD, TPM_RIGHTBUTTON, WM_NULL,
+    };
+
+    let menu = build_tray_menu(&window.menu.lock());
+    unsafe {
+        let mut point = POINT { x: 0, y: 0 };
+        GetCursorPos(&mut point);
+        // Without this the menu does not close when the user clicks elsewhere.
+        SetForegroundWindow(hwnd);
+        let picked = TrackPopupMenu(
+            menu,
+            TPM_RETURNCMD | TPM_NONOTIFY | TPM_RIGHTBUTTON,
+            point.x,
+            point.y,
+            0,
+            hwnd,
+            std::ptr::null(),
+        );
+        PostMessageW(hwnd, WM_NULL, 0, 0);
+        DestroyMenu(menu);
+        if picked > 0 {
+            (window.notify)(crate::tray::TrayEvent::Menu(picked as u32));
+        }
+    }
+}
+
+/// Initializes COM for the calling thread, runs `f`, and uninitializes again when this
+/// call did the initializing. A thread already in another apartment mode is used as is.
+fn with_com<T>(f: impl FnOnce() -> Option<T>) -> Option<T> {
+    use windows::Win32::Foundation::RPC_E_CHANGED_MODE;
+    use windows::Win32::System::Com::{CoInitializeEx, CoUninitialize, COINIT_APARTMENTTHREADED};
+
+    let initialized = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) };
+    if initialized.is_err() && initialized != RPC_E_CHANGED_MODE {
+        return None;
+    }
+    let result = f();
+    if initialized.is_ok() {
+        unsafe { CoUninitialize() };
+    }
+    result
+}
+
+/// Shows the system Open dialog in `folder`, filtered to `extensions`; blocks until it closes.
+pub fn pick_file_to_open(
+    folder: Option<&std::path::Path>,
+    extensions: &[&str],
+) -> Option<std::path::PathBuf> {
+    with_com(|| unsafe { show_open_dialog(folder, Some(extensions)) })
+}
+
+/// Shows the system folder picker; blocks until it closes.
+pub fn pick_folder_to_open() -> Option<std::path::PathBuf> {
+    with_com(|| unsafe { show_open_dialog(None, None) })
+}
+
+/// Open dialog for one file matching `extensions`, or for a folder when that is `None`.
+unsafe fn show_open_dialog(
+    folder: Option<&std::path::Path>,
+    extensions: Option<&[&str]>,
+) -> Option<std::path::PathBuf> {
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoTaskMemFree, IBindCtx, CLSCTX_INPROC_SERVER,
+    };
+    use windows::Win32::UI::Shell::Common::COMDLG_FILTERSPEC;
+    use windows::Win32::UI::Shell::{
+        FileOpenDialog, IFileOpenDialog, IShellItem, SHCreateItemFromParsingName, FOS_PICKFOLDERS,
+        SIGDN_FILESYSPATH,
+    };
+
+    let dialog: IFileOpenDialog =
+        CoCreateInstance(&FileOpenDialog, None, CLSCTX_INPROC_SERVER).ok()?;
+    let name = wide(OsStr::new("Media files"));
+    let pattern = extensions.map(|extensions| {
+        let pattern: Vec<String> = extensions.iter().map(|ext| format!("*.{ext}")).collect();
+        wide(OsStr::new(&pattern.join(";")))
+    });
+    match pattern.as_ref() {
+        Some(pattern) => {
+            let filters = [COMDLG_FILTERSPEC {
+                pszName: PCWSTR(name.as_ptr()),
+                pszSpec: PCWSTR(pattern.as_ptr()),
+            }];
+            let _ = dialog.SetFileTypes(&filters);
+        }
+        None => {
+            let options = dialog.GetOptions().ok()?;
+            dialog.SetOptions(options | FOS_PICKFOLDERS).ok()?;
+        }
+    }
+    if let Some(folder) = folder {
+        let folder = wide(folder.as_os_str());
+        if let Ok(item) = SHCreateItemFromParsingName::<_, _, IShellItem>(
+            PCWSTR(folder.as_ptr()),
+            None::<&IBindCtx>,
+        ) {
+            let _ = dialog.SetFolder(&item);
+        }
+    }
+    dialog.Show(HWND::default()).ok()?;
+    let path = dialog
+        .GetResult()
+        .ok()?
+        .GetDisplayName(SIGDN_FILESYSPATH)
+        .ok()?;
+    let picked = path.to_string().ok().map(std::path::PathBuf::from);
+    CoTaskMemFree(Some(path.0 as *const _));
+    picked
+}
+
+/// Replaces the taskbar jump list with a "Recent" category for `recent` and the "Open
+/// folder..." and "Start slideshow" tasks. Entries the user removed from the list stay out.
+pub fn update_jump_list(recent: &[std::path::PathBuf]) -> Result<(), String> {
+    with_com(|| Some(unsafe { build_jump_list(recent) }))
+        .unwrap_or_else(|| Err("COM is not available".to_string()))
+}
+
+unsafe fn build_jump_list(recent: &[std::path::PathBuf]) -> Result<(), String> {
+    use windows::core::{w, Interface};
+    use windows::Win32::System::Com::{CoCreateInstance, CLSCTX_INPROC_SERVER};
+    use windows::Win32::UI::Shell::Common::{IObjectArray, IObjectCollection};
+    use windows::Win32::UI::Shell::{
+        DestinationList, EnumerableObjectCollection, ICustomDestinationList, IShellLinkW,
+    };
+
+    let err = |err: windows::core::Error| err.to_string();
+    let exe = std::env::current_exe().map_err(|err| err.to_string())?;
+    let list: ICustomDestinationList =
+        CoCreateInstance(&DestinationList, None, CLSCTX_INPROC_SERVER).map_err(err)?;
+    let mut max_slots = 0u32;
+    let removed: IObjectArray = list.BeginList(&mut max_slots).map_err(err)?;
+    let mut removed_arguments = Vec::new();
+    for index in 0..removed.GetCount().map_err(err)? {
+        if let Ok(link) = removed.GetAt::<IShellLinkW>(index) {
+            let mut buf = [0u16; 1024];
+            if link.GetArguments(&mut buf).is_ok() {
+                removed_arguments.push(wide_to_string(&buf));
+            }
+        }
+    }
+
+    let items: IObjectCollection =
+        CoCreateInstance(&EnumerableObjectCollection, None, CLSCTX_INPROC_SERVER).map_err(err)?;
+    let mut added = 0;
+    for path in recent.iter().filter(|path| path.exists()) {
+        let arguments = format!("\"{}\"", path.display());
+        if added >= max_slots || removed_arguments.contains(&arguments) {
+            continue;
+        }
+        let title = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        let link =
+            jump_list_link(&exe, &arguments, &title, &path.display().to_string()).map_err(err)?;
+        items.AddObject(&link).map_err(err)?;
+        added += 1;
+    }
+    if added > 0 {
+        list.AppendCategory(w!("Recent"), &items.cast::<IObjectArray>().map_err(err)?)
+            .map_err(err)?;
+    }
+
+    let tasks: IObjectCollection =
+        CoCreateInstance(&EnumerableObjectCollection, None, CLSCTX_INPROC_SERVER).map_err(err)?;
+    let open_folder = jump_list_link(
+        &exe,
+        "--open-folder",
+        "Open folder...",
+        "Pick a folder and open its first file",
+    )
+    .map_err(err)?;
+    tasks.AddObject(&open_folder).map_err(err)?;
+    if let Some(latest) = recent.iter().find(|path| path.exists()) {
+        let slideshow = jump_list_link(
+            &exe,
+            &format!("--slideshow \"{}\"", latest.display()),
+            "Start slideshow",
+            "Play a slideshow of the most recent folder",
+        )
+        .map_err(err)?;
+        tasks.AddObject(&slideshow).map_err(err)?;
+    }
+    list.AddUserTasks(&tasks.cast::<IObjectArray>().map_err(err)?)
+        .map_err(err)?;
+    list.CommitList().map_err(err)
+}
+
+/// A shell link that starts this executable with `arguments`, shown as `title`.
+unsafe fn jump_list_link(
+    exe: &std::path::Path,
+    arguments: &str,
+    title: &str,
+    description: &str,
+) -> windows::core::Result<windows::Win32::UI::Shell::IShellLinkW> {
+    use windows::core::{Interface, HSTRING, PROPVARIANT};
+    use windows::Win32::Storage::EnhancedStorage::PKEY_Title;
+    use windows::Win32::System::Com::{CoCreateInstance, CLSCTX_INPROC_SERVER};
+    use windows::Win32::UI::Shell::PropertiesSystem::IPropertyStore;
+    use windows::Win32::UI::Shell::{IShellLinkW, ShellLink};
+
+    let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+    link.SetPath(&HSTRING::from(exe.as_os_str()))?;
+    let arguments = if crate::storage::is_portable() {
+        format!("--portable {arguments}")
+    } else {
+        arguments.to_string()
+    };
+    link.SetArguments(&HSTRING::from(arguments))?;
+    link.SetDescription(&HSTRING::from(description))?;
+    link.SetIconLocation(&HSTRING::from(exe.as_os_str()), 0)?;
+    let store: IPropertyStore = link.cast()?;
+    store.SetValue(&PKEY_Title, &PROPVARIANT::from(title))?;
+    store.Commit()?;
+    Ok(link)
+}
+
+/// The viewer's own top-level window while it is active. `GetActiveWindow` only reports
+/// windows of the calling thread, so this never returns another application's window.
+pub fn active_viewer_window() -> Option<usize> {
+    let hwnd = unsafe { winapi::um::winuser::GetActiveWindow() };
+    (!hwnd.is_null()).then_some(hwnd as usize)
+}
+
+/// `THBN_CLICKED`, the high word of `WM_COMMAND` from a thumbnail toolbar button.
+const THBN_CLICKED: u32 = 0x1800;
+const TASKBAR_SUBCLASS_ID: usize = 0x7462;
+
+/// Progress and thumbnail toolbar of one window's taskbar button, with a window subclass
+/// that forwards the toolbar's clicks.
+pub struct TaskbarHandle {
+    taskbar: windows::Win32::UI::Shell::ITaskbarList3,
+    hwnd: windows::Win32::Foundation::HWND,
+    /// Previous, play, pause and next glyphs.
+    icons: [windows::Win32::UI::WindowsAndMessaging::HICON; 4],
+    /// Button state last applied: not added yet, or whether play/pause showed "pause".
+    buttons: Option<Option<bool>>,
+    notify: *mut Box<dyn Fn(u32)>,
+}
+
+impl TaskbarHandle {
+    /// `notify` runs on the UI thread with the id of each clicked button.
+    pub fn attach(window: usize, notify: Box<dyn Fn(u32)>) -> Option<Self> {
+        use crate::taskbar::TaskbarButton;
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::System::Com::{
+            CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED,
+        };
+        use windows::Win32::UI::Shell::{ITaskbarList3, SetWindowSubclass, TaskbarList};
+
+        unsafe {
+            // The UI thread keeps its apartment for the life of the process.
+            let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+            let taskbar: ITaskbarList3 =
+                CoCreateInstance(&TaskbarList, None, CLSCTX_INPROC_SERVER).ok()?;
+            taskbar.HrInit().ok()?;
+            let hwnd = HWND(window as *mut _);
+            let icons = [
+                glyph_icon(&crate::taskbar::glyph(TaskbarButton::Previous, false))?,
+                glyph_icon(&crate::taskbar::glyph(TaskbarButton::PlayPause, false))?,
+                glyph_icon(&crate::taskbar::glyph(TaskbarButton::PlayPause, true))?,
+                glyph_icon(&crate::taskbar::glyph(TaskbarButton::Next, false))?,
+            ];
+            let notify = Box::into_raw(Box::new(notify));
+            if !SetWindowSubclass(
+                hwnd,
+                Some(taskbar_subclass_proc),
+                TASKBAR_SUBCLASS_ID,
+                notify as usize,
+            )
+            .as_bool()
+            {
+                drop(Box::from_raw(notify));
+                return None;
+            }
+            Some(Self {
+                taskbar,
+                hwnd,
+                icons,
+                buttons: None,
+                notify,
+            })
+        }
+    }
+
+    /// Shows the buttons with play/pause matching `playing`, or hides them for `None`.
+    /// Returns `false` while Windows has not created the taskbar button yet.
+    pub fn update_buttons(&mut self, playing: Option<bool>) -> bool {
+        use crate::taskbar::TaskbarButton;
+        use windows::Win32::UI::Shell::{
+            THBF_ENABLED, THBF_HIDDEN, THB_FLAGS, THB_ICON, THB_TOOLTIP, THUMBBUTTON,
+        };
+
+        if self.buttons == Some(playing) || (self.buttons.is_none() && playing.is_none()) {
+            return true;
+        }
+        let buttons = TaskbarButton::ALL.map(|button| {
+            let icon = match button {
+                TaskbarButton::Previous => self.icons[0],
+                TaskbarButton::PlayPause if playing == Some(true) => self.icons[2],
+                TaskbarButton::PlayPause => self.icons[1],
+                TaskbarButton::Next => self.icons[3],
+            };
+            let mut tip = [0u16; 260];
+            for (slot, unit) in tip
+                .iter_mut()
+                .zip(button.tooltip(playing == Some(true)).encode_utf16())
+            {
+                *slot = unit;
+            }
+            THUMBBUTTON {
+                dwMask: THB_ICON | THB_TOOLTIP | THB_FLAGS,
+                iId: button.id(),
+                iBitmap: 0,
+                hIcon: icon,
+                szTip: tip,
+                dwFlags: if playing.is_some() {
+                    THBF_ENABLED
+                } else {
+                    THBF_HIDDEN
+                },
+            }
+        });
+        let applied = unsafe {
+            if self.buttons.is_none() {
+                self.taskbar.ThumbBarAddButtons(self.hwnd, &buttons)
+            } else {
+                self.taskbar.ThumbBarUpdateButtons(self.hwnd, &buttons)
+            }
+        };
+        if applied.is_err() {
+            return false;
+        }
+        self.buttons = Some(playing);
+        true
+    }
+
+    /// Progress in thousandths and whether it plays, or `None` to clear the bar.
+    pub fn set_progress(&self, progress: Option<(u64, bool)>) {
+        use windows::Win32::UI::Shell::{TBPF_NOPROGRESS, TBPF_NORMAL, TBPF_PAUSED};
+
+        unsafe {
+            match progress {
+                Some((completed, playing)) => {
+                    let state = if playing { TBPF_NORMAL } else { TBPF_PAUSED };
+                    let _ = self.taskbar.SetProgressState(self.hwnd, state);
+                    let _ = self.taskbar.SetProgressValue(self.hwnd, completed, 1000);
+                }
+                None => {
+                    let _ = self.taskbar.SetProgressState(self.hwnd, TBPF_NOPROGRESS);
+                }
+            }
+        }
+    }
+}
+
+impl Drop for TaskbarHandle {
+    fn drop(&mut self) {
+        use windows::Win32::UI::Shell::RemoveWindowSubclass;
+        use windows::Win32::UI::WindowsAndMessaging::DestroyIcon;
+
+        self.set_progress(None);
+        self.update_buttons(None);
+        unsafe {
+            let _ =
+                RemoveWindowSubclass(self.hwnd, Some(taskbar_subclass_proc), TASKBAR_SUBCLASS_ID);
+            drop(Box::from_raw(self.notify));
+            for icon in self.icons {
+                let _ = DestroyIcon(icon);
+            }
+        }
+    }
+}
+
+unsafe extern "system" fn taskbar_subclass_proc(
+    hwnd: windows::Win32::Foundation::HWND,
+    msg: u32,
+    wparam: windows::Win32::Foundation::WPARAM,
+    lparam: windows::Win32::Foundation::LPARAM,
+    _id: usize,
+    data: usize,
+) -> windows::Win32::Foundation::LRESULT {
+    use windows::Win32::UI::Shell::DefSubclassProc;
+    use windows::Win32::UI::WindowsAndMessaging::WM_COMMAND;
+
+    if msg == WM_COMMAND && (wparam.0 >> 16) as u32 & 0xFFFF == THBN_CLICKED {
+        let notify = &*(data as *const Box<dyn Fn(u32)>);
+        notify((wparam.0 & 0xFFFF) as u32);
+        return windows::Win32::Foundation::LRESULT(0);
+    }
+    DefSubclassProc(hwnd, msg, wparam, lparam)
+}
+
+/// White icon from a [`crate::taskbar::GLYPH_SIZE`]-square coverage mask.
+unsafe fn glyph_icon(mask: &[u8]) -> Option<windows::Win32::UI::WindowsAndMessaging::HICON> {
+    use crate::taskbar::GLYPH_SIZE;
+    use windows::Win32::Foundation::BOOL;
+    use windows::Win32::Graphics::Gdi::{CreateBitmap, DeleteObject};
+    use windows::Win32::UI::WindowsAndMessaging::{CreateIconIndirect, ICONINFO};
+
+    // Premultiplied BGRA; the monochrome mask is unused once the color bitmap has alpha.
+    let pixels: Vec<u32> = mask
+        .iter()
+        .map(|&alpha| u32::from(alpha) * 0x0101_0101)
+        .collect();
+    let side = GLYPH_SIZE as i32;
+    let color = CreateBitmap(side, side, 1, 32, Some(pixels.as_ptr().cast()));
+    let empty_mask = vec![0u8; GLYPH_SIZE * GLYPH_SIZE / 8];
+    let monochrome = CreateBitmap(side, side, 1, 1, Some(empty_mask.as_ptr().cast()));
+    let icon = CreateIconIndirect(&ICONINFO {
+        fIcon: BOOL::from(true),
+        xHotspot: 0,
+        yHotspot: 0,
+        hbmMask: monochrome,
+        hbmColor: color,
+    });
+    let _ = DeleteObject(color);
+    let _ = DeleteObject(monochrome);
+    icon.ok()
+}
+
+/// The shell's media session for one window (`SystemMediaTransportControls`).
+pub struct MediaControlsHandle {
+    controls: windows::Media::SystemMediaTransportControls,
+    token: windows::Foundation::EventRegistrationToken,
+}
+
+impl MediaControlsHandle {
+    /// `notify` runs on a shell thread for each media key or flyout button.
+    pub fn attach(
+        window: usize,
+        notify: Box<dyn Fn(crate::media_controls::MediaCommand) + Send + Sync>,
+    ) -> Option<Self> {
+        use crate::media_controls::MediaCommand;
+        use windows::Foundation::TypedEventHandler;
+        use windows::Media::{
+            SystemMediaTransportControls, SystemMediaTransportControlsButton,
+            SystemMediaTransportControlsButtonPressedEventArgs,
+        };
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::System::WinRT::ISystemMediaTransportControlsInterop;
+
+        unsafe {
+            let interop = windows::core::factory::<
+                SystemMediaTransportControls,
+                ISystemMediaTransportControlsInterop,
+            >()
+            .ok()?;
+            let controls: SystemMediaTransportControls =
+                interop.GetForWindow(HWND(window as *mut _)).ok()?;
+            controls.SetIsPlayEnabled(true).ok()?;
+            controls.SetIsPauseEnabled(true).ok()?;
+            controls.SetIsNextEnabled(true).ok()?;
+            controls.SetIsPreviousEnabled(true).ok()?;
+            let token = controls
+                .ButtonPressed(&TypedEventHandler::new(
+                    move |_, args: &Option<SystemMediaTransportControlsButtonPressedEventArgs>| {
+                        let command = match args.as_ref().map(|args| args.Button()) {
+                            Some(Ok(SystemMediaTransportControlsButton::Play)) => {
+                                MediaCommand::Play
+                            }
+                            Some(Ok(SystemMediaTransportControlsButton::Pause)) => {
+                                MediaCommand::Pause
+                            }
+                            Some(Ok(SystemMediaTransportControlsButton::Next)) => {
+                                MediaCommand::Next
+                            }
+                            Some(Ok(SystemMediaTransportControlsButton::Previous)) => {
+                                MediaCommand::Previous
+                            }
+                            _ => return Ok(()),
+                        };
+                        notify(command);
+                        Ok(())
+                    },
+                ))
+                .ok()?;
+            Some(Self { controls, token })
+        }
+    }
+
+    /// Shows `now_playing`, or turns the session off for `None`. `media_changed` also
+    /// reloads title and thumbnail, which reads the file's shell properties.
+    pub fn update(
+        &self,
+        now_playing: Option<&crate::media_controls::NowPlaying>,
+        media_changed: bool,
+    ) {
+        use windows::core::HSTRING;
+        use windows::Media::{MediaPlaybackStatus, MediaPlaybackType};
+        use windows::Storage::StorageFile;
+
+        let Some(now_playing) = now_playing else {
+            let _ = self.controls.SetPlaybackStatus(MediaPlaybackStatus::Closed);
+            let _ = self.controls.SetIsEnabled(false);
+            return;
+        };
+        let _ = self.controls.SetIsEnabled(true);
+        let status = if now_playing.playing {
+            MediaPlaybackStatus::Playing
+        } else {
+            MediaPlaybackStatus::Paused
+        };
+        let _ = self.controls.SetPlaybackStatus(status);
+        if !media_changed {
+            return;
+        }
+
+        let apply = || -> windows::core::Result<()> {
+            let updater = self.controls.DisplayUpdater()?;
+            updater.ClearAll()?;
+            // Picks up the shell's thumbnail; the title is replaced below.
+            let copied =
+                StorageFile::GetFileFromPathAsync(&HSTRING::from(now_playing.path.as_os_str()))
+                    .and_then(|file| file.get())
+                    .and_then(|file| updater.CopyFromFileAsync(MediaPlaybackType::Video, &file))
+                    .and_then(|copy| copy.get());
+            if !matches!(copied, Ok(true)) {
+                updater.SetType(MediaPlaybackType::Video)?;
+            }
+            let video = updater.VideoProperties()?;
+            video.SetTitle(&HSTRING::from(now_playing.title.as_str()))?;
+            video.SetSubtitle(&HSTRING::from(
+                now_playing.artist.as_deref().unwrap_or_default(),
+            ))?;
+            updater.Update()
+        };
+        if let Err(err) = apply() {
+            tracing::debug!("Media controls update failed: {err}");
+        }
+    }
+}
+
+impl Drop for MediaControlsHandle {
+    fn drop(&mut self) {
+        let _ = self.controls.RemoveButtonPressed(self.token);
+        self.update(None, false);
+    }
+}